@@ -0,0 +1,230 @@
+//! Finding anchors.
+//!
+//! Detectors report findings at line and column offsets, which shift as soon
+//! as code above them changes. [`anchor_bugs`] attaches a [`SourceAnchor`] to
+//! each finding instead: the enclosing contract and function, and a hash of
+//! the SIR statement the finding points at. Findings on other contract
+//! members, such as storage variables or events, are anchored by a hash of
+//! the member's declaration. Statements and declarations are hashed in their
+//! printed form, which carries no spans, so the anchor (see [`Bug::anchor`])
+//! only changes when that code itself does.
+
+use bugs::bug::{Bug, SourceAnchor};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmMemberDecl;
+use scirs::sir::dialect::{DialectMemberDecl, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{Decl, FunctionDecl, MemberDecl, Module, Stmt};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Attach a [`SourceAnchor`] to every finding located inside a contract of
+/// `modules`. Findings that already have one are left unchanged.
+pub fn anchor_bugs(bugs: &mut [Bug], modules: &[Module]) {
+    for bug in bugs {
+        if bug.source_anchor.is_none() && bug.loc.is_valid() {
+            bug.source_anchor = modules.iter().find_map(|m| anchor_of(&bug.loc, m));
+        }
+    }
+}
+
+/// Returns `true` if `inner` lies within `outer`. Spans of different files
+/// never contain each other.
+fn contains(outer: &Loc, inner: &Loc) -> bool {
+    if let (Some(a), Some(b)) = (&outer.file, &inner.file)
+        && a != b
+    {
+        return false;
+    }
+    (outer.start_line, outer.start_col) <= (inner.start_line, inner.start_col)
+        && (inner.end_line, inner.end_col) <= (outer.end_line, outer.end_col)
+}
+
+/// Span of a contract member, if it has one.
+fn member_span(member: &MemberDecl) -> Option<&Loc> {
+    match member {
+        MemberDecl::Storage(s) => s.span.as_ref(),
+        MemberDecl::Function(f) => f.span.as_ref(),
+        MemberDecl::Dialect(DialectMemberDecl::Evm(d)) => Some(match d {
+            EvmMemberDecl::EventDef(e) => &e.loc,
+            EvmMemberDecl::ModifierDef(m) => &m.loc,
+            EvmMemberDecl::ErrorDef(e) => &e.loc,
+            EvmMemberDecl::EnumDef(e) => &e.loc,
+            EvmMemberDecl::StructDef(s) => &s.loc,
+        }),
+        _ => None,
+    }
+}
+
+fn anchor_of(loc: &Loc, module: &Module) -> Option<SourceAnchor> {
+    for decl in &module.decls {
+        let Decl::Contract(contract) = decl else {
+            continue;
+        };
+        let member = contract
+            .members
+            .iter()
+            .find(|m| member_span(m).is_some_and(|s| contains(s, loc)));
+        if member.is_none() && !contract.span.as_ref().is_some_and(|s| contains(s, loc)) {
+            continue;
+        }
+        let (function, statement) = match member {
+            Some(MemberDecl::Function(f)) => (Some(f.abi_signature()), statement_hash(f, loc)),
+            Some(m) => (None, Some(hash(&normalize(&m.to_string()), 0))),
+            None => (None, None),
+        };
+        return Some(SourceAnchor { contract: contract.name.clone(), function, statement });
+    }
+    None
+}
+
+/// `text` with every run of whitespace replaced by a single space.
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Short hash of `text` and its `occurrence` among identical texts.
+fn hash(text: &str, occurrence: usize) -> String {
+    let mut keccak = Keccak::v256();
+    keccak.update(text.as_bytes());
+    keccak.update(&occurrence.to_be_bytes());
+    let mut hash = [0u8; 32];
+    keccak.finalize(&mut hash);
+    hash[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hash of the innermost statement of `func` containing `loc`. Identical
+/// statements are told apart by their occurrence within the function.
+fn statement_hash(func: &FunctionDecl, loc: &Loc) -> Option<String> {
+    /// Every statement of a body in pre-order, with its normalized text.
+    struct Collector<'a>(Vec<(&'a Stmt, String)>);
+    impl<'a> Visit<'a> for Collector<'a> {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            self.0.push((stmt, normalize(&stmt.to_string())));
+            visit::default::visit_stmt(self, stmt);
+        }
+    }
+
+    let mut collector = Collector(vec![]);
+    collector.visit_stmts(func.body.as_ref()?);
+    let stmts = collector.0;
+
+    // In pre-order, the last statement containing `loc` is the innermost.
+    let index = stmts
+        .iter()
+        .rposition(|(stmt, _)| stmt.span().is_some_and(|s| contains(s, loc)))?;
+    let text = &stmts[index].1;
+    let occurrence = stmts[..index].iter().filter(|(_, t)| t == text).count();
+    Some(hash(text, occurrence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bugs::bug::{BugCategory, BugKind, RiskLevel};
+    use scirs::sir::*;
+
+    fn span(line: usize, len: usize) -> Option<Loc> {
+        Some(Loc::new(line, 1, line + len, 1))
+    }
+
+    /// `balance = amount;` on `line`.
+    fn reset(line: usize) -> Stmt {
+        Stmt::Assign(AssignStmt {
+            lhs: Expr::Var(VarExpr::new("balance".to_string(), Type::I256, None)),
+            rhs: Expr::Var(VarExpr::new("amount".to_string(), Type::I256, None)),
+            span: Some(Loc::new(line, 9, line, 22)),
+        })
+    }
+
+    /// A `Bank` module whose `withdraw(uint256)` starts at `line`, preceded
+    /// by `helpers` unrelated functions of ten lines each.
+    fn bank(line: usize, helpers: usize) -> Module {
+        let mut members = vec![];
+        for i in 0..helpers {
+            let helper = FunctionDecl::new(
+                format!("helper{i}"),
+                vec![],
+                vec![],
+                Some(vec![]),
+                span(2 + i * 10, 9),
+            );
+            members.push(MemberDecl::Function(helper));
+        }
+        let param = Param::new("amount".to_string(), Type::I256);
+        let withdraw = FunctionDecl::new(
+            "withdraw".to_string(),
+            vec![param],
+            vec![],
+            Some(vec![reset(line + 1), reset(line + 2)]),
+            span(line, 3),
+        );
+        members.push(MemberDecl::Function(withdraw));
+        let contract = ContractDecl::new("Bank".to_string(), members, span(1, line + 3));
+        Module { id: "Bank.sol".to_string(), attrs: vec![], decls: vec![Decl::Contract(contract)] }
+    }
+
+    fn bug_at(loc: Loc) -> Bug {
+        Bug::new(
+            "Test Bug",
+            None,
+            loc,
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            RiskLevel::Low,
+            vec![],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_anchor_unchanged_after_inserting_functions_above() {
+        let mut before = [bug_at(Loc::new(11, 9, 11, 22))];
+        anchor_bugs(&mut before, &[bank(10, 0)]);
+
+        // Two helpers pushed `withdraw` down by 20 lines.
+        let mut after = [bug_at(Loc::new(31, 9, 31, 22))];
+        anchor_bugs(&mut after, &[bank(30, 2)]);
+
+        assert_ne!(before[0].loc, after[0].loc);
+        assert_eq!(before[0].anchor(), after[0].anchor());
+        assert!(before[0].anchor().starts_with("Bank::withdraw(uint256)::"));
+    }
+
+    #[test]
+    fn test_identical_statements_have_distinct_anchors() {
+        let mut bugs = [
+            bug_at(Loc::new(11, 9, 11, 22)),
+            bug_at(Loc::new(12, 9, 12, 22)),
+        ];
+        anchor_bugs(&mut bugs, &[bank(10, 0)]);
+        assert_ne!(bugs[0].anchor(), bugs[1].anchor());
+    }
+
+    #[test]
+    fn test_storage_findings_are_anchored_to_their_declaration() {
+        // `balance` and `owner` declared on `line` and the line after.
+        let bank = |line: usize| {
+            let storage = |name: &str, line: usize| {
+                let span = Some(Loc::new(line, 5, line, 25));
+                MemberDecl::Storage(StorageDecl::new(name.to_string(), Type::I256, None, span))
+            };
+            let members = vec![storage("balance", line), storage("owner", line + 1)];
+            let contract = ContractDecl::new("Bank".to_string(), members, span(1, line + 2));
+            Module {
+                id: "Bank.sol".to_string(),
+                attrs: vec![],
+                decls: vec![Decl::Contract(contract)],
+            }
+        };
+
+        let mut before = [bug_at(Loc::new(2, 5, 2, 25)), bug_at(Loc::new(3, 5, 3, 25))];
+        anchor_bugs(&mut before, &[bank(2)]);
+        assert_ne!(before[0].anchor(), before[1].anchor());
+        assert!(before[0].anchor().starts_with("Bank::"));
+
+        let mut after = [bug_at(Loc::new(12, 5, 12, 25))];
+        anchor_bugs(&mut after, &[bank(12)]);
+        assert_eq!(before[0].anchor(), after[0].anchor());
+    }
+}
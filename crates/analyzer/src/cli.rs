@@ -2,17 +2,24 @@
 //!
 //! This is the main entry point for the Analyzer tool.
 
+use crate::context::SmtFindingsArtifact;
+use crate::incremental::{CachedFindings, FindingsCache, ImportResolver};
 use crate::{
-    AnalysisConfig, AnalysisContext, AnalysisReport, Config, DetectorRegistry, InputLanguage,
-    JsonFormatter, MarkdownFormatter, OutputFormat, OutputFormatter, PipelineConfig,
-    PipelineEngine, SarifFormatter, SeverityFilter, register_all_detectors,
+    AnalysisConfig, AnalysisContext, AnalysisReport, Baseline, ConfidenceLevel, Config,
+    DetectorRegistry, GitLabCodeQualityFormatter, HtmlFormatter, InputLanguage, JsonFormatter,
+    LspFormatter, MarkdownFormatter, NdjsonFormatter, OutputFormat, OutputFormatter,
+    OutputOptions, PipelineConfig, PipelineEngine, PipelineResult, SarifFormatter, SeverityFilter,
+    SummaryFormatter, analyze_path, register_all_detectors,
 };
+use bugs::bug::RiskLevel;
 use clap::{Parser, Subcommand, crate_version};
 use common::error;
 use frontend::solidity::{
-    ast::SourceUnit, ast::utils::export::export_debugging_source_unit, parsing::parse_input_file,
+    ast::SourceUnit, ast::utils::export::export_debugging_source_unit,
+    parsing::compile_input_file, parsing::compile_input_file_with_smt,
 };
 use std::fs;
+use std::io::{self, Write};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -26,7 +33,7 @@ pub struct Arguments {
     #[command(subcommand)]
     pub command: Option<Command>,
 
-    /// Input Solidity files to be compiled.
+    /// Input Solidity files, or a single project directory, to be compiled.
     pub input_files: Vec<String>,
 
     /// The root directory of the source tree, if specified.
@@ -37,6 +44,11 @@ pub struct Arguments {
     #[arg(long, default_value = None)]
     pub include_path: Vec<String>,
 
+    /// Import remapping as `prefix=target` (e.g.
+    /// `@openzeppelin/=node_modules/@openzeppelin/`), passed to Solc >=0.5.0.
+    #[arg(long, value_name = "PREFIX=TARGET")]
+    pub remapping: Vec<String>,
+
     /// Print debugging information.
     #[arg(short, long, default_value_t = false)]
     pub debug: bool,
@@ -45,6 +57,21 @@ pub struct Arguments {
     #[arg(long, default_value = None)]
     pub solc_version: Option<String>,
 
+    /// Directory for caching compiler outputs (project analysis) and the
+    /// findings of each input file between runs.
+    #[arg(long, default_value = None)]
+    pub cache_dir: Option<String>,
+
+    /// Run Solc's SMTChecker (CHC engine, all targets) while compiling and
+    /// let detectors use its results. Slow.
+    #[arg(long, default_value_t = false)]
+    pub model_checker: bool,
+
+    /// Print the time spent in each analysis pass and detector, slowest
+    /// first.
+    #[arg(long, default_value_t = false)]
+    pub profile: bool,
+
     /// Input language: solidity, vyper.
     /// Auto-detected from file extension if not specified.
     #[arg(long, default_value = None)]
@@ -58,10 +85,19 @@ pub struct Arguments {
     #[arg(long, visible_alias = "pip", default_value_t = false)]
     pub print_input_program: bool,
 
-    /// Output format: json, markdown, sarif, text
+    /// Output format: json, ndjson, markdown, html, sarif, gitlab, lsp,
+    /// summary, text
     #[arg(long, short, default_value = "text")]
     pub format: String,
 
+    /// Lines of source context shown around each finding (text, markdown, html)
+    #[arg(long)]
+    pub context_lines: Option<usize>,
+
+    /// Omit the long description of each finding (text, markdown, html)
+    #[arg(long, default_value_t = false)]
+    pub no_description: bool,
+
     /// Output file (default: stdout)
     #[arg(long, short)]
     pub output: Option<String>,
@@ -78,10 +114,35 @@ pub struct Arguments {
     #[arg(long)]
     pub disable: Option<String>,
 
+    /// Enable the detectors carrying these tags (comma-separated, e.g.
+    /// `proxy,erc20`)
+    #[arg(long)]
+    pub tags: Option<String>,
+
+    /// Per-detector parameter as `ID.KEY=VALUE` (e.g.
+    /// `deep-nesting.max_depth=3`)
+    #[arg(long = "detector-param", value_name = "ID.KEY=VALUE")]
+    pub detector_params: Vec<String>,
+
     /// Minimum severity to report: info, low, medium, high, critical
     #[arg(long, default_value = "info")]
     pub min_severity: String,
 
+    /// Exit with status 1 when a reported finding has this severity or
+    /// higher: critical, high, medium, low. Defaults to high. Tool errors,
+    /// such as no input file compiling, also exit with status 1 and take
+    /// precedence: they stop the run before any report is written.
+    #[arg(
+        long,
+        value_name = "SEVERITY",
+        value_parser = ["critical", "high", "medium", "low"]
+    )]
+    pub fail_on: Option<String>,
+
+    /// Minimum detector confidence to report: low, medium, high
+    #[arg(long, default_value = "low")]
+    pub min_confidence: String,
+
     /// Automatically install the required compiler version if none is
     /// available. Skips the interactive prompt.
     #[arg(long, default_value_t = false)]
@@ -91,6 +152,19 @@ pub struct Arguments {
     #[arg(long, default_value_t = false)]
     pub parallel: bool,
 
+    /// Analysis time budget per file, in seconds. Files exceeding it are
+    /// reported as timed out with partial findings.
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Report only findings that are not recorded in this baseline file
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<String>,
+
+    /// Record every finding of this run in a baseline file
+    #[arg(long, value_name = "FILE")]
+    pub write_baseline: Option<String>,
+
     /// Verbosity
     #[command(flatten)]
     pub verbose: clap_verbosity_flag::Verbosity<clap_verbosity_flag::ErrorLevel>,
@@ -172,16 +246,17 @@ fn list_detectors() {
     let mut sorted_detectors = detectors.clone();
     sorted_detectors.sort_by(|a, b| a.name().cmp(&b.name()));
 
-    println!("{:<25} {:<35} {:<10} {:<10}", "ID", "Name", "Severity", "Confidence");
-    println!("{}", "-".repeat(85));
+    println!("{:<25} {:<35} {:<10} {:<10} Tags", "ID", "Name", "Severity", "Confidence");
+    println!("{}", "-".repeat(100));
 
     for detector in sorted_detectors {
         println!(
-            "{:<25} {:<35} {:<10} {:<10}",
+            "{:<25} {:<35} {:<10} {:<10} {}",
             detector.detector_id().as_str(),
             detector.name(),
             detector.risk_level().as_str(),
             format!("{:?}", detector.confidence()).to_lowercase(),
+            detector.tags().join(", "),
         );
     }
 
@@ -198,6 +273,10 @@ fn show_detector(id: &str) {
             println!("ID: {}", detector.detector_id().as_str());
             println!("Severity: {}", detector.risk_level());
             println!("Confidence: {:?}", detector.confidence());
+            let tags = detector.tags();
+            if !tags.is_empty() {
+                println!("Tags: {}", tags.join(", "));
+            }
             println!();
             println!("Description:");
             println!("  {}", detector.description());
@@ -270,11 +349,25 @@ optimization = true
 # Explicitly disable specific detectors
 # disabled = []
 
+# Enable the detectors carrying any of these tags (erc20, proxy, defi, gas)
+# tags = ["proxy"]
+
+# Per-detector options; `enabled = false` disables a detector
+# [detectors.complex-signature]
+# max_params = 7
+# [detectors.deep-nesting]
+# max_depth = 4
+# [detectors.floating-pragma]
+# allow_caret = true
+
 [output]
-# Output format: "text", "json", "markdown", "sarif"
+# Output format: "text", "json", "ndjson", "markdown", "html", "sarif", "gitlab", "lsp",
+# "summary"
 format = "text"
 # Minimum severity to report: "info", "low", "medium", "high", "critical"
 min_severity = "info"
+# Minimum detector confidence to report: "low", "medium", "high"
+min_confidence = "low"
 
 [ignore]
 # Patterns to ignore in files
@@ -333,13 +426,45 @@ fn run_analysis(args: Arguments) {
         config.detectors.disabled = disable.split(',').map(|s| s.trim().to_string()).collect();
     }
 
+    if let Some(tags) = &args.tags {
+        config.detectors.tags = tags.split(',').map(|s| s.trim().to_string()).collect();
+    }
+
+    for param in &args.detector_params {
+        let parsed = param
+            .split_once('=')
+            .and_then(|(path, value)| path.split_once('.').map(|(id, key)| (id, key, value)));
+        match parsed {
+            Some((id, key, value)) => {
+                config
+                    .detectors
+                    .params
+                    .entry(id.trim().to_string())
+                    .or_default()
+                    .insert(key.trim().to_string(), value.trim().to_string());
+            }
+            None => {
+                eprintln!("Invalid detector parameter '{param}', expected ID.KEY=VALUE");
+                std::process::exit(1);
+            }
+        }
+    }
+
     config.output_format = match args.format.as_str() {
         "json" => OutputFormat::Json,
+        "ndjson" | "jsonl" => OutputFormat::Ndjson,
         "markdown" | "md" => OutputFormat::Markdown,
         "sarif" => OutputFormat::Sarif,
+        "gitlab" | "codequality" => OutputFormat::GitLab,
+        "lsp" => OutputFormat::Lsp,
+        "html" => OutputFormat::Html,
+        "summary" => OutputFormat::Summary,
         _ => OutputFormat::Text,
     };
 
+    config.output_options.context_lines = args.context_lines;
+    config.output_options.include_description = !args.no_description;
+
     config.min_severity = match args.min_severity.as_str() {
         "critical" => SeverityFilter::Critical,
         "high" => SeverityFilter::High,
@@ -348,19 +473,96 @@ fn run_analysis(args: Arguments) {
         _ => SeverityFilter::Informational,
     };
 
+    config.min_confidence = match args.min_confidence.as_str() {
+        "high" => ConfidenceLevel::High,
+        "medium" => ConfidenceLevel::Medium,
+        _ => ConfidenceLevel::Low,
+    };
+
+    config.file_timeout = args.timeout.map(std::time::Duration::from_secs);
+
+    config.project.base_path = args.base_path.clone();
+    config.project.include_paths = args.include_path.clone();
+    config.project.remappings = args.remapping.clone();
+    config.project.solc_version = args.solc_version.clone();
+    config.project.cache_dir = args.cache_dir.clone();
+    config.project.model_checker = args.model_checker;
+
+    // A single directory input is analyzed as one project.
+    if let [input] = args.input_files.as_slice()
+        && std::path::Path::new(input).is_dir()
+    {
+        let report = analyze_path(std::path::Path::new(input), &config);
+        for (file, err) in &report.compile_errors {
+            eprintln!("Error compiling {}: {}", file, err);
+        }
+        if report.files_analyzed.is_empty() {
+            eprintln!("No source files were successfully compiled.");
+            std::process::exit(1);
+        }
+        write_report(&args, &config, &report);
+        return;
+    }
+
     // Parse input files
     let solc_ver = args.solc_version.as_deref();
     let vyper_ver = args.vyper_version.as_deref();
     let base_path = args.base_path.as_deref();
     let include_paths: &[String] = &args.include_path;
+    let remappings: &[String] = &args.remapping;
 
     // Detect input language
     let input_language = detect_language(&args.input_files, args.language.as_deref());
 
-    let mut ir_units: Vec<scirs::sir::Module> = Vec::new();
+    // Create the pipeline
+    let engine = PipelineEngine::new(PipelineConfig {
+        parallel: config.num_threads > 1,
+        num_threads: config.num_threads,
+        enabled: config.detectors.enabled.clone(),
+        disabled: config.detectors.disabled.clone(),
+        tags: config.detectors.tags.clone(),
+        params: config.detectors.params.clone(),
+        timeout: config.file_timeout,
+    });
+
+    // With a cache directory, files whose import closure is unchanged since
+    // the last run with the same detectors are neither compiled nor analyzed.
+    let mut findings_cache = args.cache_dir.as_ref().map(|dir| {
+        let settings = format!(
+            "{} {:?}",
+            engine.detector_fingerprint(input_language),
+            (base_path, include_paths, remappings, solc_ver, vyper_ver, args.model_checker)
+        );
+        let resolver = ImportResolver::new(base_path, include_paths, remappings);
+        FindingsCache::open(std::path::Path::new(dir), resolver, &settings)
+    });
+
+    let mut file_units: Vec<(String, Vec<scirs::sir::Module>)> = Vec::new();
+    let mut cached_results: Vec<(String, PipelineResult)> = Vec::new();
     let mut files_analyzed: Vec<String> = Vec::new();
+    let mut compiler_versions = std::collections::BTreeMap::new();
+    let mut smt_findings = std::collections::HashMap::new();
 
     for file in &args.input_files {
+        if let Some(cache) = &mut findings_cache
+            && let Some(cached) = cache.get(std::path::Path::new(file))
+        {
+            if args.debug {
+                eprintln!("Reusing cached findings of {}", file);
+            }
+            if let Some(version) = cached.compiler_version {
+                compiler_versions.insert(file.clone(), version);
+            }
+            let result = PipelineResult {
+                bugs: cached.bugs,
+                contract_metrics: cached.contract_metrics,
+                ..PipelineResult::default()
+            };
+            cached_results.push((file.clone(), result));
+            files_analyzed.push(file.clone());
+            continue;
+        }
+
         if args.debug {
             let rel_file = common::utils::format_relative_path(std::path::Path::new(file));
             eprintln!("\nCompiling: {}", rel_file);
@@ -368,19 +570,35 @@ fn run_analysis(args: Arguments) {
 
         match input_language {
             InputLanguage::Solidity => {
-                let source_units = match parse_input_file(file, base_path, include_paths, solc_ver)
-                {
-                    Ok(source_units) => source_units,
+                let compiled = if args.model_checker {
+                    compile_input_file_with_smt(
+                        file,
+                        base_path,
+                        include_paths,
+                        remappings,
+                        solc_ver,
+                    )
+                    .map(|(source_units, ver, findings)| {
+                        smt_findings.insert(file.clone(), findings);
+                        (source_units, ver.to_string())
+                    })
+                } else {
+                    compile_input_file(file, base_path, include_paths, remappings, solc_ver)
+                        .map(|(source_units, ver)| (source_units, ver.to_string()))
+                };
+                let (source_units, solc_ver_used) = match compiled {
+                    Ok(compiled) => compiled,
                     Err(err) => {
                         // Try auto-install recovery
                         match try_install_and_compile_solidity(
                             file,
                             base_path,
                             include_paths,
+                            remappings,
                             solc_ver,
                             args.install_compiler,
                         ) {
-                            Some(units) => units,
+                            Some(compiled) => compiled,
                             None => {
                                 eprintln!("Error compiling {}: {}", file, err);
                                 continue;
@@ -408,7 +626,8 @@ fn run_analysis(args: Arguments) {
                 // Lower AST to SIR
                 match frontend::solidity::lowering::lower_source_units(&source_units) {
                     Ok(modules) => {
-                        ir_units.extend(modules);
+                        file_units.push((file.clone(), modules));
+                        compiler_versions.insert(file.clone(), solc_ver_used);
                     }
                     Err(err) => {
                         eprintln!("Error lowering {}: {}", file, err);
@@ -418,13 +637,13 @@ fn run_analysis(args: Arguments) {
             }
             InputLanguage::Vyper => match frontend::vyper::compile_file(file, vyper_ver) {
                 Ok(module) => {
-                    ir_units.push(module);
+                    file_units.push((file.clone(), vec![module]));
                 }
                 Err(err) => {
                     // Try auto-install recovery
                     match try_install_and_compile_vyper(file, vyper_ver, args.install_compiler) {
                         Some(module) => {
-                            ir_units.push(module);
+                            file_units.push((file.clone(), vec![module]));
                         }
                         None => {
                             eprintln!("Error compiling {}: {}", file, err);
@@ -450,17 +669,7 @@ fn run_analysis(args: Arguments) {
         std::process::exit(1);
     }
 
-    // Create analysis context
     let analysis_config = AnalysisConfig { input_language, ..AnalysisConfig::default() };
-    let mut context = AnalysisContext::new(ir_units, analysis_config);
-
-    // Create and run the pipeline
-    let engine = PipelineEngine::new(PipelineConfig {
-        parallel: config.num_threads > 1,
-        num_threads: config.num_threads,
-        enabled: config.detectors.enabled.clone(),
-        disabled: config.detectors.disabled.clone(),
-    });
 
     if args.debug {
         eprintln!(
@@ -473,9 +682,56 @@ fn run_analysis(args: Arguments) {
         );
     }
 
-    let result = engine.run(&mut context);
+    // With a timeout, each file is analyzed on its own so that a slow file
+    // cannot use up the budget of the others. With several threads, files
+    // are analyzed on their own as well, in parallel, and so are they with a
+    // cache, which stores the findings of each file.
+    let per_file =
+        config.file_timeout.is_some() || config.num_threads > 1 || findings_cache.is_some();
+    let (result, timed_out) = if per_file {
+        let start = std::time::Instant::now();
+        let contexts = file_units
+            .into_iter()
+            .map(|(file, units)| {
+                let mut context = AnalysisContext::new(units, analysis_config.clone());
+                if let Some(findings) = smt_findings.remove(&file) {
+                    context.store::<SmtFindingsArtifact>(findings);
+                }
+                (file, context)
+            })
+            .collect();
+        let mut results = engine.run_each(contexts);
+        if let Some(cache) = &mut findings_cache {
+            for (file, result) in results.iter().filter(|(_, result)| !result.timed_out) {
+                let findings = CachedFindings {
+                    bugs: result.bugs.clone(),
+                    compiler_version: compiler_versions.get(file).cloned(),
+                    contract_metrics: result.contract_metrics.clone(),
+                };
+                cache.insert(std::path::Path::new(file), &findings);
+            }
+            if let Err(err) = cache.save() {
+                eprintln!("Warning: failed to write findings cache: {}", err);
+            }
+        }
+        results.extend(cached_results);
+        let (mut result, timed_out) = engine.merge_results(results);
+        result.total_duration = start.elapsed();
+        (result, timed_out)
+    } else {
+        let ir_units = file_units
+            .into_iter()
+            .flat_map(|(_, units)| units)
+            .collect();
+        let mut context = AnalysisContext::new(ir_units, analysis_config);
+        if args.model_checker {
+            context.store::<SmtFindingsArtifact>(smt_findings.into_values().flatten().collect());
+        }
+        (engine.run(&mut context), vec![])
+    };
 
     // Create report
+    let timing = result.timing();
     let lang_str = match input_language {
         InputLanguage::Vyper => "vyper",
         InputLanguage::Solidity => "solidity",
@@ -483,48 +739,151 @@ fn run_analysis(args: Arguments) {
         InputLanguage::MoveAptos => "move_aptos",
         InputLanguage::Solana => "solana",
     };
-    let report = AnalysisReport::with_language(
+    let mut report = AnalysisReport::with_language(
         result.bugs,
         files_analyzed,
         result.total_duration,
         lang_str,
     );
+    report.compiler_versions = compiler_versions;
+    report.timed_out = timed_out.into_iter().collect();
+    report.contract_metrics = result.contract_metrics;
+    report.timing = timing;
+
+    if args.profile {
+        eprint!("{}", crate::output::format_profile(&report));
+    }
+    write_report(&args, &config, &report);
+}
+
+/// Format the report, write it to the requested output and exit with an
+/// error code if high severity issues were found.
+fn write_report(args: &Arguments, config: &Config, report: &AnalysisReport) {
+    if let Some(path) = &args.write_baseline {
+        if let Err(e) = Baseline::from_report(report).save(std::path::Path::new(path)) {
+            eprintln!("Failed to write baseline: {}", e);
+            std::process::exit(1);
+        }
+        eprintln!("Baseline written to: {}", path);
+    }
+
+    // Keep only the findings that are not in the baseline
+    let new_findings;
+    let report = match &args.baseline {
+        Some(path) => match Baseline::load(std::path::Path::new(path)) {
+            Ok(baseline) => {
+                new_findings = report.diff_against(&baseline);
+                &new_findings
+            }
+            Err(e) => {
+                eprintln!("Failed to read baseline {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => report,
+    };
+
+    // Drop the findings of detectors below the confidence threshold
+    let confident_findings;
+    let report = match config.min_confidence {
+        ConfidenceLevel::Low => report,
+        min => {
+            confident_findings = report.with_min_confidence(min);
+            &confident_findings
+        }
+    };
 
     // Format output
     let output = match config.output_format {
-        OutputFormat::Json => {
-            let formatter = JsonFormatter::new(true);
-            formatter.format(&report)
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            // Streamed straight to the sink so that huge reports are never
+            // held in memory as a single string.
+            stream_json_report(args, report, config.output_format);
+            None
         }
         OutputFormat::Markdown => {
-            let formatter = MarkdownFormatter::new();
-            formatter.format(&report)
+            let formatter = MarkdownFormatter::with_options(config.output_options);
+            Some(formatter.format(report))
+        }
+        OutputFormat::Html => {
+            let formatter = HtmlFormatter::with_options(config.output_options);
+            Some(formatter.format(report))
         }
         OutputFormat::Sarif => {
             let formatter = SarifFormatter::new(true);
-            formatter.format(&report)
+            Some(formatter.format(report))
         }
-        OutputFormat::Text => format_text_output(&report),
+        OutputFormat::GitLab => {
+            let formatter = GitLabCodeQualityFormatter::new(true);
+            Some(formatter.format(report))
+        }
+        OutputFormat::Lsp => Some(LspFormatter::new(true).format(report)),
+        OutputFormat::Summary => Some(SummaryFormatter::new().format(report)),
+        OutputFormat::Text => Some(format_text_output(report, &config.output_options)),
     };
 
     // Write output
-    match &args.output {
-        Some(path) => {
-            if let Err(e) = fs::write(path, &output) {
-                eprintln!("Failed to write output: {}", e);
-                std::process::exit(1);
+    if let Some(output) = output {
+        match &args.output {
+            Some(path) => {
+                if let Err(e) = fs::write(path, &output) {
+                    eprintln!("Failed to write output: {}", e);
+                    std::process::exit(1);
+                }
+                eprintln!("Report written to: {}", path);
+            }
+            None => {
+                println!("{}", output);
             }
-            eprintln!("Report written to: {}", path);
         }
+    }
+
+    // Exit with error code if findings meet the `--fail-on` threshold
+    let fail_on = match args.fail_on.as_deref() {
+        Some("critical") => RiskLevel::Critical,
+        Some("medium") => RiskLevel::Medium,
+        Some("low") => RiskLevel::Low,
+        _ => RiskLevel::High,
+    };
+    let code = report.exit_code(fail_on);
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// Stream the JSON, or JSON Lines, report to the output file, or to stdout.
+fn stream_json_report(args: &Arguments, report: &AnalysisReport, format: OutputFormat) {
+    let write = |writer: &mut dyn Write| match format {
+        OutputFormat::Ndjson => NdjsonFormatter::new().write_to(report, writer),
+        _ => JsonFormatter::new(true).write_to(report, writer),
+    };
+    let result = match &args.output {
+        Some(path) => fs::File::create(path)
+            .map_err(serde_json::Error::io)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                write(&mut writer)?;
+                writer.flush().map_err(serde_json::Error::io)
+            }),
         None => {
-            println!("{}", output);
+            let mut writer = io::BufWriter::new(io::stdout().lock());
+            write(&mut writer).and_then(|_| {
+                // JSON Lines output already ends with a newline.
+                if format != OutputFormat::Ndjson {
+                    writeln!(writer).map_err(serde_json::Error::io)?;
+                }
+                writer.flush().map_err(serde_json::Error::io)
+            })
         }
-    }
+    };
 
-    // Exit with error code if high severity issues found
-    if report.has_high_severity() {
+    if let Err(e) = result {
+        eprintln!("Failed to write output: {}", e);
         std::process::exit(1);
     }
+    if let Some(path) = &args.output {
+        eprintln!("Report written to: {}", path);
+    }
 }
 
 fn format_header(title: &str) -> String {
@@ -532,7 +891,7 @@ fn format_header(title: &str) -> String {
     format!("\n{}\n*** {} ***\n{}\n\n", ruler, title, ruler)
 }
 
-fn format_text_output(report: &AnalysisReport) -> String {
+fn format_text_output(report: &AnalysisReport, options: &OutputOptions) -> String {
     let mut output = String::new();
 
     if report.bugs.is_empty() {
@@ -541,7 +900,7 @@ fn format_text_output(report: &AnalysisReport) -> String {
     } else {
         output.push_str(&format_header("Detected Bugs"));
 
-        for (i, bug) in report.bugs.iter().enumerate() {
+        for (i, bug) in report.sorted_by_priority().into_iter().enumerate() {
             output.push_str(&format!("🐛 Issue {}: {} ({})\n\n", i + 1, bug.name, bug.category));
 
             let snippet_file = bug.loc.file.as_deref().unwrap_or("");
@@ -574,7 +933,7 @@ fn format_text_output(report: &AnalysisReport) -> String {
                 bug.loc.end_line,
                 bug.loc.start_col,
                 bug.loc.end_col,
-                1,
+                options.context_lines.unwrap_or(1),
             ) {
                 output.push_str(&snippet);
             } else {
@@ -582,8 +941,10 @@ fn format_text_output(report: &AnalysisReport) -> String {
             }
 
             output.push('\n');
-            let desc = bug.description.as_deref().unwrap_or("None");
-            output.push_str(&format!("Description: {}\n\n", desc));
+            if options.include_description {
+                let desc = bug.description.as_deref().unwrap_or("None");
+                output.push_str(&format!("Description: {}\n\n", desc));
+            }
             output.push_str(&format!("Severity: {}\n\n", bug.risk_level));
             if let Some(ref remedy) = bug.remediation {
                 output.push_str(&format!("Remediation: {}\n\n", remedy));
@@ -612,6 +973,20 @@ fn format_text_output(report: &AnalysisReport) -> String {
         report.total_bugs(),
     ));
 
+    if !report.timed_out.is_empty() {
+        output.push_str("⚠️  Timed out (partial findings):\n");
+        for file in &report.timed_out {
+            output.push_str(&format!("- {}\n", file));
+        }
+        output.push('\n');
+    }
+
+    let metrics = crate::output::format_contract_metrics(report);
+    if !metrics.is_empty() {
+        output.push_str(&metrics);
+        output.push('\n');
+    }
+
     output
 }
 
@@ -742,13 +1117,16 @@ fn try_install_and_compile_vyper(
 }
 
 /// Try to install a compatible solc compiler and re-compile the file.
+///
+/// Returns the source units and the solc version that compiled them.
 fn try_install_and_compile_solidity(
     file: &str,
     base_path: Option<&str>,
     include_paths: &[String],
+    remappings: &[String],
     solc_ver: Option<&str>,
     auto: bool,
-) -> Option<Vec<SourceUnit>> {
+) -> Option<(Vec<SourceUnit>, String)> {
     // Step 0: Ensure solc-select itself is present.
     if !ensure_select_installed("solc-select", "solc-select", auto) {
         return None;
@@ -778,5 +1156,7 @@ fn try_install_and_compile_solidity(
     }
     eprintln!("solc {best} installed successfully.");
 
-    parse_input_file(file, base_path, include_paths, solc_ver).ok()
+    compile_input_file(file, base_path, include_paths, remappings, solc_ver)
+        .ok()
+        .map(|(source_units, ver)| (source_units, ver.to_string()))
 }
@@ -2,7 +2,12 @@
 //!
 //! Provides basic configuration for the CLI tool.
 
+use crate::detectors::ConfidenceLevel;
+use crate::output::OutputOptions;
+use scanner::DetectorParams;
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::Duration;
 
 // Re-export InputLanguage from the analysis crate so existing code using
 // `crate::config::InputLanguage` continues to work without changes.
@@ -12,8 +17,13 @@ pub use crate::context::InputLanguage;
 pub enum OutputFormat {
     Text,
     Json,
+    Ndjson,
     Markdown,
     Sarif,
+    Summary,
+    GitLab,
+    Lsp,
+    Html,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,20 +39,96 @@ pub enum SeverityFilter {
 pub struct DetectorConfig {
     pub enabled: Vec<String>,
     pub disabled: Vec<String>,
+    /// Tags of detector groups to enable in addition to `enabled`.
+    pub tags: Vec<String>,
+    /// Per-detector parameters, keyed by detector ID.
+    pub params: HashMap<String, DetectorParams>,
 }
 
 impl Default for DetectorConfig {
     fn default() -> Self {
-        Self { enabled: vec![], disabled: vec![] }
+        Self { enabled: vec![], disabled: vec![], tags: vec![], params: HashMap::new() }
     }
 }
 
+impl DetectorConfig {
+    /// Record the options of detector `id` from its TOML table. Values are
+    /// passed to the detector in their TOML spelling, strings unquoted.
+    fn add_options(&mut self, id: &str, options: &toml::Value) -> Result<(), String> {
+        let options = options
+            .as_table()
+            .ok_or_else(|| format!("options of detector '{id}' must be a table"))?;
+        for (key, value) in options {
+            match (key.as_str(), value) {
+                ("enabled", toml::Value::Boolean(false)) => self.disabled.push(id.to_string()),
+                ("enabled", toml::Value::Boolean(true)) => {}
+                (key, value) => {
+                    let value = match value {
+                        toml::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    self.params
+                        .entry(id.to_string())
+                        .or_default()
+                        .insert(key.to_string(), value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn string_list(key: &str, value: &toml::Value) -> Result<Vec<String>, String> {
+    value
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .ok_or_else(|| format!("'detectors.{key}' must be a list of strings"))
+}
+
+/// Project-wide settings used when analyzing a directory of sources.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectConfig {
+    /// Root directory of the source tree (defaults to the analyzed directory).
+    pub base_path: Option<String>,
+    /// Additional directories to look for imported files.
+    pub include_paths: Vec<String>,
+    /// Import remappings in Solc's `prefix=target` form.
+    pub remappings: Vec<String>,
+    /// Glob patterns (relative to the project root) of files to analyze.
+    /// Empty means all `.sol` files.
+    pub include: Vec<String>,
+    /// Patterns of files to skip, in `.gitignore` syntax (relative to the
+    /// project root).
+    pub exclude: Vec<String>,
+    /// Solidity compiler version constraint.
+    pub solc_version: Option<String>,
+    /// Directory caching Solc outputs between runs. When set, only files
+    /// whose import closure changed since the last run are recompiled.
+    pub cache_dir: Option<String>,
+    /// Run Solc's SMTChecker while compiling and give the targets it could
+    /// not prove to the detectors. Slow, and bypasses `cache_dir`.
+    pub model_checker: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub num_threads: usize,
     pub output_format: OutputFormat,
+    pub output_options: OutputOptions,
     pub min_severity: SeverityFilter,
+    /// Findings of detectors less confident than this are left out of the
+    /// report.
+    pub min_confidence: ConfidenceLevel,
     pub detectors: DetectorConfig,
+    pub project: ProjectConfig,
+    /// Analysis time budget per file. Files exceeding it are reported as
+    /// timed out with the findings produced so far.
+    pub file_timeout: Option<Duration>,
 }
 
 impl Default for Config {
@@ -50,17 +136,69 @@ impl Default for Config {
         Self {
             num_threads: 1,
             output_format: OutputFormat::Text,
+            output_options: OutputOptions::default(),
             min_severity: SeverityFilter::Informational,
+            min_confidence: ConfidenceLevel::Low,
             detectors: DetectorConfig::default(),
+            project: ProjectConfig::default(),
+            file_timeout: None,
         }
     }
 }
 
 impl Config {
-    pub fn from_file(_path: &Path) -> Result<Self, String> {
-        // TODO: Implement config file loading
-        // For now, just return default
-        Ok(Self::default())
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        Self::from_toml(&content).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parse a configuration file in the format written by `analyze init`.
+    ///
+    /// Only the `[detectors]` section is read; output settings come from the
+    /// command line. Each `[detectors.<id>]` table holds the options of one
+    /// detector, handed to it as parameters, and `enabled = false` in it
+    /// disables the detector:
+    ///
+    /// ```toml
+    /// [detectors]
+    /// disabled = ["dead-code"]
+    ///
+    /// [detectors.floating-pragma]
+    /// allow_caret = true
+    /// ```
+    ///
+    /// The older `[detectors.params.<id>]` tables are still accepted.
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| e.to_string())?;
+        let mut config = Self::default();
+        let Some(detectors) = table.get("detectors") else {
+            return Ok(config);
+        };
+        let detectors = detectors
+            .as_table()
+            .ok_or_else(|| "'detectors' must be a table".to_string())?;
+
+        // Keys come sorted: `[detectors.<id>]` tables may precede `disabled`
+        // and already have added to it.
+        for (key, value) in detectors {
+            match (key.as_str(), value) {
+                ("enabled", _) => config.detectors.enabled = string_list(key, value)?,
+                ("disabled", _) => config.detectors.disabled.extend(string_list(key, value)?),
+                ("tags", _) => config.detectors.tags = string_list(key, value)?,
+                ("params", toml::Value::Table(tables)) => {
+                    for (id, options) in tables {
+                        config.detectors.add_options(id, options)?;
+                    }
+                }
+                (id, toml::Value::Table(_)) => config.detectors.add_options(id, value)?,
+                // Switches of whole detector kinds, e.g. `refactoring = true`
+                _ => {}
+            }
+        }
+        Ok(config)
     }
 
     pub fn is_detector_enabled(&self, id: &str) -> bool {
@@ -15,6 +15,8 @@ pub enum InputLanguage {
     Solana,
 }
 
+use common::loc::Loc;
+use frontend::solidity::parsing::smt_checker::SmtFinding;
 use std::any::{Any, TypeId};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -54,6 +56,15 @@ pub trait ContextKey: 'static {
     const NAME: &'static str;
 }
 
+/// Targets that Solc's SMTChecker could not prove, stored when the project
+/// is compiled with model checking. See [`AnalysisContext::smt_findings_at`].
+pub struct SmtFindingsArtifact;
+
+impl ContextKey for SmtFindingsArtifact {
+    type Value = Vec<SmtFinding>;
+    const NAME: &'static str = "smt_findings";
+}
+
 /// Configuration for analysis.
 #[derive(Debug, Clone, Default)]
 pub struct AnalysisConfig {
@@ -108,6 +119,9 @@ pub struct AnalysisStats {
 
     /// Passes that were skipped (already completed).
     pub passes_skipped: usize,
+
+    /// Passes that were skipped because their deadline had passed.
+    pub passes_timed_out: usize,
 }
 
 /// The central analysis context holding all data.
@@ -368,6 +382,15 @@ impl AnalysisContext {
     pub fn record_ir_traversal(&mut self) {
         self.stats.ir_traversals += 1;
     }
+
+    /// SMTChecker findings on the lines of `loc`, so that detectors can
+    /// raise their confidence when the formal checker agrees. Empty unless
+    /// the sources were compiled with model checking.
+    pub fn smt_findings_at(&self, loc: &Loc) -> Vec<&SmtFinding> {
+        self.get::<SmtFindingsArtifact>()
+            .map(|findings| findings.iter().filter(|f| f.overlaps(loc)).collect())
+            .unwrap_or_default()
+    }
 }
 
 impl Clone for AnalysisContext {
@@ -444,4 +467,20 @@ mod tests {
         assert!(context.is_pass_completed(TypeId::of::<u8>()));
         assert_eq!(context.completed_pass_count(), 1);
     }
+
+    #[test]
+    fn test_smt_findings_at() {
+        let mut context = AnalysisContext::new(vec![], AnalysisConfig::default());
+        let loc = Loc::new(7, 9, 7, 20).with_file("contracts/Vault.sol".to_string());
+        assert!(context.smt_findings_at(&loc).is_empty());
+
+        context.store::<SmtFindingsArtifact>(vec![SmtFinding {
+            engine: "CHC".to_string(),
+            message: "Assertion violation happens here.".to_string(),
+            counterexample: vec![],
+            loc: Loc::new(7, 9, 7, 25).with_file("Vault.sol".to_string()),
+        }]);
+        assert_eq!(context.smt_findings_at(&loc).len(), 1);
+        assert!(context.smt_findings_at(&Loc::new(9, 1, 9, 5)).is_empty());
+    }
 }
@@ -2,90 +2,263 @@
 //!
 //! `DetectorId` enumerates the scanner's built-in bug detectors.
 //! Unlike the analysis crate's `TypeId`-based pass identity, detector IDs
-//! are a closed enum because the scanner needs them for CLI filtering,
-//! human-readable output, and stable serialization.
+//! are an enum because the scanner needs them for CLI filtering,
+//! human-readable output, and stable serialization. Detectors registered
+//! by downstream crates are identified by [`DetectorId::Custom`].
 
 /// Unique identifier for each built-in bug detector.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DetectorId {
     // ── SIR structural detectors ────────────────────────────────
+    AmbiguousOverride,
     ArithmeticOverflow,
+    AssemblyMemorySafety,
+    AssemblyUncheckedMath,
     BadRandomness,
+    BalanceConfusion,
+    BlockNumberDependence,
     CeiViolation,
     CentralizationRisk,
+    ComplexSignature,
     ConstantStateVar,
+    ConstructorExternalCall,
+    CouldBeView,
+    DanglingStorageRef,
+    CrossFunctionReentrancy,
     DeadCode,
+    DecimalsMismatch,
+    DeepNesting,
     Delegatecall,
+    DelegatecallMutableTarget,
     DenialOfService,
     Deprecated,
+    DeprecatedDifficulty,
+    DeprecatedSelfdestruct,
+    EncodeTypeMismatch,
+    EoaOnlyCheck,
+    Erc165Missing,
     FloatingPragma,
     FrontRunning,
+    HardcodedGas,
+    InfiniteApproval,
+    LoopClearCollection,
     LowLevelCall,
     MissingAccessControl,
+    MissingDeadline,
+    MissingEventEmission,
+    MissingReentrancyGuard,
+    MissingReturn,
+    MissingStorageGap,
+    MissingZeroCheck,
+    ModifyWhileIterate,
+    MulOverflowBeforeDiv,
+    NonstandardErcOverride,
+    PossibleUnderflowRevert,
+    PrecisionLossDivision,
+    RedundantRequire,
+    RedundantSafemath,
     Reentrancy,
+    ReentrancyCallback,
+    SendToZero,
     Shadowing,
     ShortAddress,
+    SignatureMalleability,
+    StaleStateRead,
     TimestampDependence,
+    StorageLayoutCollision,
+    TransparentProxyClash,
     TxOrigin,
+    UncheckedAbiDecode,
     UncheckedCall,
+    UncheckedEcrecover,
+    UncheckedErc20Return,
+    UncheckedSend,
     UninitializedStorage,
+    UnprotectedMint,
+    UnusedEvent,
+    UnusedPayable,
     Visibility,
+    WeakRandomness,
+    WithdrawOrdering,
+
+    // ── BIR dataflow detectors ──────────────────────────────────
+    DelegatecallInLoop,
+    UnboundedLoop,
+    UnprotectedSelfdestruct,
+
+    // ── Detectors registered by downstream crates ───────────────
+    /// A custom detector, with its own kebab-case ID. The ID must not be
+    /// the ID of a built-in detector.
+    Custom(&'static str),
 }
 
 impl DetectorId {
     /// Return a stable kebab-case string for CLI and serialization.
     pub fn as_str(&self) -> &'static str {
         match self {
+            Self::AmbiguousOverride => "ambiguous-override",
             Self::ArithmeticOverflow => "arithmetic-overflow",
+            Self::AssemblyMemorySafety => "assembly-memory-safety",
+            Self::AssemblyUncheckedMath => "assembly-unchecked-math",
             Self::BadRandomness => "bad-randomness",
+            Self::BalanceConfusion => "balance-confusion",
+            Self::BlockNumberDependence => "block-number-dependence",
             Self::CeiViolation => "cei-violation",
             Self::CentralizationRisk => "centralization-risk",
+            Self::ComplexSignature => "complex-signature",
             Self::ConstantStateVar => "constant-state-var",
+            Self::ConstructorExternalCall => "constructor-external-call",
+            Self::CouldBeView => "could-be-view",
+            Self::DanglingStorageRef => "dangling-storage-ref",
+            Self::CrossFunctionReentrancy => "cross-function-reentrancy",
             Self::DeadCode => "dead-code",
+            Self::DecimalsMismatch => "decimals-mismatch",
+            Self::DeepNesting => "deep-nesting",
             Self::Delegatecall => "delegatecall",
+            Self::DelegatecallMutableTarget => "delegatecall-mutable-target",
             Self::DenialOfService => "denial-of-service",
             Self::Deprecated => "deprecated",
+            Self::DeprecatedDifficulty => "deprecated-difficulty",
+            Self::DeprecatedSelfdestruct => "deprecated-selfdestruct",
+            Self::EncodeTypeMismatch => "encode-type-mismatch",
+            Self::EoaOnlyCheck => "eoa-only-check",
+            Self::Erc165Missing => "erc165-missing",
             Self::FloatingPragma => "floating-pragma",
             Self::FrontRunning => "front-running",
+            Self::HardcodedGas => "hardcoded-gas",
+            Self::InfiniteApproval => "infinite-approval",
+            Self::LoopClearCollection => "loop-clear-collection",
             Self::LowLevelCall => "low-level-call",
             Self::MissingAccessControl => "missing-access-control",
+            Self::MissingDeadline => "missing-deadline",
+            Self::MissingEventEmission => "missing-event-emission",
+            Self::MissingReentrancyGuard => "missing-reentrancy-guard",
+            Self::MissingReturn => "missing-return",
+            Self::MissingStorageGap => "missing-storage-gap",
+            Self::MissingZeroCheck => "missing-zero-check",
+            Self::ModifyWhileIterate => "modify-while-iterate",
+            Self::MulOverflowBeforeDiv => "mul-overflow-before-div",
+            Self::NonstandardErcOverride => "nonstandard-erc-override",
+            Self::PossibleUnderflowRevert => "possible-underflow-revert",
+            Self::PrecisionLossDivision => "precision-loss-division",
+            Self::RedundantRequire => "redundant-require",
+            Self::RedundantSafemath => "redundant-safemath",
             Self::Reentrancy => "reentrancy",
+            Self::ReentrancyCallback => "reentrancy-callback",
+            Self::SendToZero => "send-to-zero",
             Self::Shadowing => "shadowing",
             Self::ShortAddress => "short-address",
+            Self::SignatureMalleability => "signature-malleability",
+            Self::StaleStateRead => "stale-state-read",
             Self::TimestampDependence => "timestamp-dependence",
+            Self::StorageLayoutCollision => "storage-layout-collision",
+            Self::TransparentProxyClash => "transparent-proxy-clash",
             Self::TxOrigin => "tx-origin",
+            Self::UncheckedAbiDecode => "unchecked-abi-decode",
             Self::UncheckedCall => "unchecked-call",
+            Self::UncheckedEcrecover => "unchecked-ecrecover",
+            Self::UncheckedErc20Return => "unchecked-erc20-return",
+            Self::UncheckedSend => "unchecked-send",
             Self::UninitializedStorage => "uninitialized-storage",
+            Self::UnprotectedMint => "unprotected-mint",
+            Self::UnusedEvent => "unused-event",
+            Self::UnusedPayable => "unused-payable",
             Self::Visibility => "visibility",
+            Self::WeakRandomness => "weak-randomness",
+            Self::WithdrawOrdering => "withdraw-ordering",
+            Self::DelegatecallInLoop => "delegatecall-in-loop",
+            Self::UnboundedLoop => "unbounded-loop",
+            Self::UnprotectedSelfdestruct => "unprotected-selfdestruct",
+            Self::Custom(id) => id,
         }
     }
 
     /// Parse a kebab-case string into a `DetectorId`.
+    ///
+    /// Panics if `s` is not the ID of a built-in detector.
     pub fn from_str(s: &str) -> Self {
-        match s {
+        Self::parse(s).unwrap_or_else(|| panic!("Unknown detector ID: {s}"))
+    }
+
+    /// Parse the kebab-case ID of a built-in detector, or return `None`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let id = match s {
+            "ambiguous-override" => Self::AmbiguousOverride,
             "arithmetic-overflow" => Self::ArithmeticOverflow,
+            "assembly-memory-safety" => Self::AssemblyMemorySafety,
+            "assembly-unchecked-math" => Self::AssemblyUncheckedMath,
             "bad-randomness" => Self::BadRandomness,
+            "balance-confusion" => Self::BalanceConfusion,
+            "block-number-dependence" => Self::BlockNumberDependence,
             "cei-violation" => Self::CeiViolation,
             "centralization-risk" => Self::CentralizationRisk,
+            "complex-signature" => Self::ComplexSignature,
             "constant-state-var" => Self::ConstantStateVar,
+            "constructor-external-call" => Self::ConstructorExternalCall,
+            "could-be-view" => Self::CouldBeView,
+            "dangling-storage-ref" => Self::DanglingStorageRef,
+            "cross-function-reentrancy" => Self::CrossFunctionReentrancy,
             "dead-code" => Self::DeadCode,
+            "decimals-mismatch" => Self::DecimalsMismatch,
+            "deep-nesting" => Self::DeepNesting,
             "delegatecall" => Self::Delegatecall,
+            "delegatecall-mutable-target" => Self::DelegatecallMutableTarget,
             "denial-of-service" => Self::DenialOfService,
             "deprecated" | "deprecated-features" => Self::Deprecated,
+            "deprecated-difficulty" => Self::DeprecatedDifficulty,
+            "deprecated-selfdestruct" => Self::DeprecatedSelfdestruct,
+            "encode-type-mismatch" => Self::EncodeTypeMismatch,
+            "eoa-only-check" => Self::EoaOnlyCheck,
+            "erc165-missing" => Self::Erc165Missing,
             "floating-pragma" => Self::FloatingPragma,
             "front-running" => Self::FrontRunning,
+            "hardcoded-gas" => Self::HardcodedGas,
+            "infinite-approval" => Self::InfiniteApproval,
+            "loop-clear-collection" => Self::LoopClearCollection,
             "low-level-call" => Self::LowLevelCall,
             "missing-access-control" => Self::MissingAccessControl,
+            "missing-deadline" => Self::MissingDeadline,
+            "missing-event-emission" => Self::MissingEventEmission,
+            "missing-reentrancy-guard" => Self::MissingReentrancyGuard,
+            "missing-return" => Self::MissingReturn,
+            "missing-storage-gap" => Self::MissingStorageGap,
+            "missing-zero-check" => Self::MissingZeroCheck,
+            "modify-while-iterate" => Self::ModifyWhileIterate,
+            "mul-overflow-before-div" => Self::MulOverflowBeforeDiv,
+            "nonstandard-erc-override" => Self::NonstandardErcOverride,
+            "possible-underflow-revert" => Self::PossibleUnderflowRevert,
+            "precision-loss-division" => Self::PrecisionLossDivision,
+            "redundant-require" => Self::RedundantRequire,
+            "redundant-safemath" => Self::RedundantSafemath,
             "reentrancy" => Self::Reentrancy,
+            "reentrancy-callback" => Self::ReentrancyCallback,
+            "send-to-zero" => Self::SendToZero,
             "shadowing" => Self::Shadowing,
             "short-address" => Self::ShortAddress,
+            "signature-malleability" => Self::SignatureMalleability,
+            "stale-state-read" => Self::StaleStateRead,
             "timestamp-dependence" => Self::TimestampDependence,
+            "storage-layout-collision" => Self::StorageLayoutCollision,
+            "transparent-proxy-clash" => Self::TransparentProxyClash,
             "tx-origin" => Self::TxOrigin,
+            "unchecked-abi-decode" => Self::UncheckedAbiDecode,
             "unchecked-call" => Self::UncheckedCall,
+            "unchecked-ecrecover" => Self::UncheckedEcrecover,
+            "unchecked-erc20-return" => Self::UncheckedErc20Return,
+            "unchecked-send" => Self::UncheckedSend,
             "uninitialized-storage" => Self::UninitializedStorage,
+            "unprotected-mint" => Self::UnprotectedMint,
+            "unused-event" => Self::UnusedEvent,
+            "unused-payable" => Self::UnusedPayable,
             "visibility" => Self::Visibility,
-            _ => panic!("Unknown detector ID: {s}"),
-        }
+            "weak-randomness" => Self::WeakRandomness,
+            "withdraw-ordering" => Self::WithdrawOrdering,
+            "delegatecall-in-loop" => Self::DelegatecallInLoop,
+            "unbounded-loop" => Self::UnboundedLoop,
+            "unprotected-selfdestruct" => Self::UnprotectedSelfdestruct,
+            _ => return None,
+        };
+        Some(id)
     }
 }
 
@@ -3,6 +3,7 @@
 //! Manages registration and discovery of bug detectors.
 
 use crate::detectors::BugDetectionPass;
+use scanner::DetectorParams;
 use std::collections::HashMap;
 
 /// Registry for managing bug detectors.
@@ -27,6 +28,17 @@ impl DetectorRegistry {
     }
 
     /// Register a detector.
+    ///
+    /// This is the stable extension point for downstream crates: detectors
+    /// implementing [`BugDetectionPass`], built-in or not, are registered
+    /// here and then run by a [`PipelineEngine`] created with
+    /// [`PipelineEngine::with_registry`]. A custom detector is looked up by
+    /// its name and by its [`DetectorId::Custom`] ID, which should not
+    /// collide with the ID of another registered detector.
+    ///
+    /// [`PipelineEngine`]: crate::pipeline::PipelineEngine
+    /// [`PipelineEngine::with_registry`]: crate::pipeline::PipelineEngine::with_registry
+    /// [`DetectorId::Custom`]: crate::detectors::DetectorId::Custom
     pub fn register(&mut self, detector: Box<dyn BugDetectionPass>) {
         let name = detector.name().to_string();
         let detector_id_str = detector.detector_id().as_str().to_string();
@@ -43,6 +55,33 @@ impl DetectorRegistry {
             .map(|&idx| self.detectors[idx].as_ref())
     }
 
+    /// Get a mutable detector by name or ID.
+    pub fn get_mut(&mut self, name_or_id: &str) -> Option<&mut dyn BugDetectionPass> {
+        match self.by_id.get(name_or_id) {
+            Some(&idx) => Some(self.detectors[idx].as_mut()),
+            None => None,
+        }
+    }
+
+    /// Apply per-detector parameters, keyed by detector ID or name.
+    /// Parameters for unknown detectors, and parameters a detector does not
+    /// accept, are ignored with a warning.
+    pub fn configure(&mut self, params: &HashMap<String, DetectorParams>) {
+        for (id, detector_params) in params {
+            let Some(detector) = self.get_mut(id) else {
+                log::warn!("Ignoring options of unknown detector '{id}'");
+                continue;
+            };
+            let accepted = detector.param_names();
+            for key in detector_params.keys() {
+                if !accepted.contains(&key.as_str()) {
+                    log::warn!("Ignoring unknown option '{key}' of detector '{id}'");
+                }
+            }
+            detector.configure(detector_params);
+        }
+    }
+
     /// Get all registered detectors.
     pub fn all(&self) -> impl Iterator<Item = &dyn BugDetectionPass> {
         self.detectors.iter().map(|d| d.as_ref())
@@ -83,6 +122,11 @@ impl DetectorRegistry {
         self.detectors.iter().map(|d| d.name()).collect()
     }
 
+    /// Get the detectors carrying `tag`.
+    pub fn by_tag(&self, tag: &str) -> Vec<&dyn BugDetectionPass> {
+        self.filter(|d| d.tags().contains(&tag))
+    }
+
     /// Filter detectors by a predicate.
     pub fn filter<F>(&self, predicate: F) -> Vec<&dyn BugDetectionPass>
     where
@@ -106,6 +150,11 @@ pub fn register_all_detectors(registry: &mut DetectorRegistry) {
     for detector in scan_registry.into_detectors() {
         registry.register(Box::new(ScanDetectorAdapter::new(detector)));
     }
+
+    // BIR dataflow detectors
+    registry.register(Box::new(crate::detectors::bir::DelegatecallInLoopDfaDetector::new()));
+    registry.register(Box::new(crate::detectors::bir::UnboundedLoopDfaDetector::new()));
+    registry.register(Box::new(crate::detectors::bir::UnprotectedSelfdestructDfaDetector::new()));
 }
 
 #[cfg(test)]
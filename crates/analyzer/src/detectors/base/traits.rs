@@ -6,29 +6,14 @@
 use super::id::DetectorId;
 use crate::context::AnalysisContext;
 use crate::passes::base::Pass;
-use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use bugs::bug::{Bug, BugCategory, BugKind, FixSuggestion, RiskLevel};
 use frontend::solidity::ast::Loc;
+use scanner::DetectorParams;
 
-/// Confidence level for a detection.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum ConfidenceLevel {
-    /// Low confidence - possible issue, needs careful review.
-    Low,
-    /// Medium confidence - likely issue but may need manual review.
-    Medium,
-    /// High confidence - very likely to be a real issue.
-    High,
-}
-
-impl std::fmt::Display for ConfidenceLevel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ConfidenceLevel::High => write!(f, "High"),
-            ConfidenceLevel::Medium => write!(f, "Medium"),
-            ConfidenceLevel::Low => write!(f, "Low"),
-        }
-    }
-}
+/// Confidence level for a detection. This is the same type as
+/// `scanner::Confidence`, so scan detectors and passes rate findings on
+/// one scale.
+pub use bugs::bug::Confidence as ConfidenceLevel;
 
 /// Result type for detector operations.
 pub type DetectorResult<T> = Result<T, DetectorError>;
@@ -84,6 +69,17 @@ pub enum DetectorError {
 ///     fn swc_ids(&self) -> Vec<usize> { vec![115] }
 /// }
 /// ```
+///
+/// # Custom detectors
+///
+/// Downstream crates add their own detectors without forking by
+/// implementing this trait with a [`DetectorId::Custom`] ID and passing
+/// them to [`DetectorRegistry::register`]. As required by [`Pass`],
+/// detectors must be `Send + Sync + 'static`: the pipeline runs them in
+/// parallel and shares them between threads. Keep per-run state in the
+/// [`AnalysisContext`] rather than in the detector.
+///
+/// [`DetectorRegistry::register`]: crate::detectors::DetectorRegistry::register
 pub trait BugDetectionPass: Pass {
     /// The detector's unique identity for CLI filtering and output.
     fn detector_id(&self) -> DetectorId;
@@ -122,18 +118,37 @@ pub trait BugDetectionPass: Pass {
         vec![]
     }
 
+    /// Topic tags used to select related detectors (e.g. `proxy`).
+    fn tags(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
     /// Check if this detector is enabled for the given configuration.
     fn is_enabled(&self, _context: &AnalysisContext) -> bool {
         true
     }
+
+    /// Apply per-detector parameters such as thresholds.
+    fn configure(&mut self, _params: &DetectorParams) {}
+
+    /// Names of the parameters accepted by [`configure`](Self::configure).
+    fn param_names(&self) -> Vec<&'static str> {
+        vec![]
+    }
 }
 
 /// Helper function to create a Bug from detector metadata.
 ///
 /// This is a convenience function for detectors to create Bug instances
-/// with consistent metadata.
-pub fn create_bug(detector: &dyn BugDetectionPass, description: Option<&str>, loc: Loc) -> Bug {
-    Bug::new(
+/// with consistent metadata. Detectors that cannot suggest a fix pass
+/// `None` as `suggestion`.
+pub fn create_bug(
+    detector: &dyn BugDetectionPass,
+    description: Option<&str>,
+    loc: Loc,
+    suggestion: Option<FixSuggestion>,
+) -> Bug {
+    let bug = Bug::new(
         detector.name(),
         description,
         loc,
@@ -143,7 +158,11 @@ pub fn create_bug(detector: &dyn BugDetectionPass, description: Option<&str>, lo
         detector.cwe_ids(),
         detector.swc_ids(),
         Some(detector.recommendation()),
-    )
+    );
+    match suggestion {
+        Some(suggestion) => bug.with_suggestion(suggestion),
+        None => bug,
+    }
 }
 
 /// Helper function to create a Bug with additional details.
@@ -0,0 +1,407 @@
+//! Delegatecall In Loop Detector
+//!
+//! Detects `delegatecall` and low-level `call` made inside a loop:
+//!
+//! ```solidity
+//! function multicall(bytes[] calldata data) external {
+//!     for (uint i = 0; i < data.length; i++) {
+//!         (bool ok, ) = implementation.delegatecall(data[i]);
+//!         require(ok);
+//!     }
+//! }
+//! ```
+//!
+//! Each iteration hands control to other code, which multiplies the gas
+//! spent and the number of reentrancy windows; a delegatecall also runs
+//! that code against this contract's storage, so `msg.value` and any state
+//! it checks are reused across iterations.
+//!
+//! Loops are the natural loops of the BIR CFG, as for the `unbounded-loop`
+//! detector. A call whose target is a literal address or a `constant` or
+//! `immutable` storage variable is reported with medium severity; any other
+//! target keeps the detector's high severity.
+
+use crate::context::AnalysisContext;
+use crate::detectors::base::{
+    BugDetectionPass, ConfidenceLevel, DetectorId, DetectorResult, create_bug,
+};
+use crate::frameworks::cfa::domtree::DomTree;
+use crate::frameworks::cfa::loops::LoopInfo;
+use crate::passes::base::Pass;
+use crate::passes::base::meta::{PassLevel, PassRepresentation};
+use crate::passes::bir::def_use::collect_operands;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::bir::cfg::{Function, Terminator};
+use scirs::bir::ops::{Op, OpKind, OpRef};
+use scirs::sir::dialect::evm::EvmStorageExt;
+use scirs::sir::{Decl, MemberDecl};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+/// Opaque ops handing control to another contract, with the name used in
+/// reports.
+const LOOPED_CALLS: &[(&str, &str)] = &[
+    ("evm.delegatecall(", "delegatecall"),
+    ("evm.low_level_call(", "call"),
+];
+
+/// The kind and target of the call made by `op`, if any.
+fn looped_call(op: &Op) -> Option<(&'static str, &str)> {
+    let OpKind::Opaque { description } = &op.kind else {
+        return None;
+    };
+    LOOPED_CALLS.iter().find_map(|(prefix, kind)| {
+        let args = description.strip_prefix(prefix)?;
+        Some((*kind, first_arg(args)))
+    })
+}
+
+/// The first argument of the comma-separated argument list `args`.
+fn first_arg(args: &str) -> &str {
+    let mut depth = 0usize;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' if depth == 0 => return args[..i].trim(),
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => return args[..i].trim(),
+            _ => {}
+        }
+    }
+    args.trim()
+}
+
+/// Whether `target` is a literal address or a constant or immutable storage
+/// variable, looking through casts such as `address(...)`.
+fn is_constant_target(target: &str, constants: &HashSet<String>) -> bool {
+    let mut target = target;
+    while let Some(inner) = target
+        .strip_suffix(')')
+        .and_then(|rest| rest.split_once('('))
+        .map(|(_, inner)| inner)
+    {
+        target = inner;
+    }
+    target.starts_with(|c: char| c.is_ascii_digit()) || constants.contains(target)
+}
+
+/// Detector for `delegatecall` and low-level `call` inside loops.
+#[derive(Debug, Default)]
+pub struct DelegatecallInLoopDfaDetector;
+
+impl DelegatecallInLoopDfaDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Constant and immutable storage variables of every contract, by
+    /// contract name.
+    fn constant_vars(context: &AnalysisContext) -> HashMap<String, HashSet<String>> {
+        let mut contracts: HashMap<String, HashSet<String>> = HashMap::new();
+        if !context.has_ir() {
+            return contracts;
+        }
+        for module in context.ir_units() {
+            for decl in &module.decls {
+                let Decl::Contract(contract) = decl else {
+                    continue;
+                };
+                let vars = contracts.entry(contract.name.clone()).or_default();
+                for member in &contract.members {
+                    if let MemberDecl::Storage(storage) = member
+                        && storage.is_constant_storage()
+                    {
+                        vars.insert(storage.name.clone());
+                    }
+                }
+            }
+        }
+        contracts
+    }
+
+    fn check_function(&self, func: &Function, constants: &HashSet<String>) -> Vec<Bug> {
+        let mut bugs = vec![];
+        let Some(dom) = DomTree::build(func) else {
+            return bugs;
+        };
+        let loops = LoopInfo::build(func, &dom);
+        let terms: HashMap<_, _> = func
+            .blocks
+            .iter()
+            .map(|block| (block.id, &block.term))
+            .collect();
+        let ops: HashMap<_, _> = func
+            .blocks
+            .iter()
+            .flat_map(|block| &block.ops)
+            .map(|op| (op.id, op))
+            .collect();
+
+        for block in &func.blocks {
+            let Some(header) = loops.loop_header_of(block.id) else {
+                continue;
+            };
+            for op in &block.ops {
+                let Some((kind, target)) = looped_call(op) else {
+                    continue;
+                };
+
+                // Dialect calls carry no span; the statement using the call
+                // does.
+                let call_loc = op.span.clone().or_else(|| {
+                    block
+                        .ops
+                        .iter()
+                        .filter(|user| collect_operands(&user.kind).contains(&OpRef(op.id)))
+                        .find_map(|user| user.span.clone())
+                });
+                let header_loc = match terms.get(&header) {
+                    Some(Terminator::Branch { cond: OpRef(cond), .. }) => {
+                        ops.get(cond).and_then(|cond| cond.span.clone())
+                    }
+                    _ => None,
+                };
+                let header_line = header_loc
+                    .as_ref()
+                    .map(|loc| format!(" at line {}", loc.start_line))
+                    .unwrap_or_default();
+
+                let constant = is_constant_target(target, constants);
+                let mut bug = create_bug(
+                    self,
+                    Some(&format!(
+                        "'{}' makes a {} to '{}' inside the loop{}; every iteration \
+                         runs external code, multiplying the gas spent and the \
+                         reentrancy windows.",
+                        func.id.0, kind, target, header_line
+                    )),
+                    call_loc
+                        .or(header_loc)
+                        .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    None,
+                );
+                if constant {
+                    bug.risk_level = RiskLevel::Medium;
+                }
+                bugs.push(bug);
+            }
+        }
+
+        bugs
+    }
+}
+
+impl Pass for DelegatecallInLoopDfaDetector {
+    fn name(&self) -> &'static str {
+        "Delegatecall In Loop"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects delegatecall and low-level call made inside loops."
+    }
+
+    fn level(&self) -> PassLevel {
+        PassLevel::Function
+    }
+
+    fn representation(&self) -> PassRepresentation {
+        PassRepresentation::Air
+    }
+
+    fn dependencies(&self) -> Vec<TypeId> {
+        vec![]
+    }
+}
+
+impl BugDetectionPass for DelegatecallInLoopDfaDetector {
+    fn detector_id(&self) -> DetectorId {
+        DetectorId::DelegatecallInLoop
+    }
+
+    fn detect(&self, context: &AnalysisContext) -> DetectorResult<Vec<Bug>> {
+        if !context.has_air() {
+            return Ok(vec![]);
+        }
+        let contracts = Self::constant_vars(context);
+        let no_constants = HashSet::new();
+        let mut bugs = vec![];
+
+        for module in context.air_units() {
+            for func in &module.functions {
+                let constants = func
+                    .id
+                    .0
+                    .split_once('.')
+                    .and_then(|(contract, _)| contracts.get(contract))
+                    .unwrap_or(&no_constants);
+                bugs.extend(self.check_function(func, constants));
+            }
+        }
+
+        Ok(bugs)
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Reentrancy
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> ConfidenceLevel {
+        ConfidenceLevel::Medium
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![400, 841]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![113]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Avoid calling other contracts in a loop. Batch the work into a single \
+         call, or let each party pull its own result; never reuse `msg.value` \
+         across delegatecalls."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-113",
+            "https://samczsun.com/two-rights-might-make-a-wrong/",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::AnalysisConfig;
+    use scirs::sir::dialect::DialectExpr;
+    use scirs::sir::dialect::evm::{EvmDelegatecall, EvmExpr};
+    use scirs::sir::*;
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), Type::I256, None))
+    }
+
+    fn num() -> Expr {
+        Expr::Lit(Lit::Num(NumLit::new(Num::Int(IntNum::one()), None)))
+    }
+
+    /// `target.delegatecall(data);`
+    fn delegatecall(target: &str) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::Dialect(DialectExpr::Evm(EvmExpr::Delegatecall(EvmDelegatecall {
+                target: Box::new(var(target)),
+                data: Box::new(var("data")),
+                loc: Loc::new(0, 0, 0, 0),
+            }))),
+            span: Some(Loc::new(5, 9, 5, 40)),
+        })
+    }
+
+    /// `for (i = 0; i < n; i += 1) { body }`
+    fn loop_with(body: Vec<Stmt>) -> Stmt {
+        Stmt::For(ForStmt {
+            init: Some(Box::new(Stmt::Assign(AssignStmt {
+                lhs: var("i"),
+                rhs: num(),
+                span: None,
+            }))),
+            cond: Some(Expr::BinOp(BinOpExpr {
+                op: BinOp::Lt,
+                lhs: Box::new(var("i")),
+                rhs: Box::new(var("n")),
+                overflow: OverflowSemantics::Checked,
+                span: Some(Loc::new(4, 25, 4, 30)),
+            })),
+            update: Some(Box::new(Stmt::AugAssign(AugAssignStmt {
+                op: BinOp::Add,
+                lhs: var("i"),
+                rhs: num(),
+                span: None,
+            }))),
+            body,
+            invariant: None,
+            span: None,
+        })
+    }
+
+    fn detect(body: Vec<Stmt>) -> Vec<Bug> {
+        let mut implementation =
+            StorageDecl::new("implementation".to_string(), Type::I256, None, None);
+        implementation
+            .attrs
+            .push(Attr::evm("is_immutable", AttrValue::Bool(true)));
+        let members = vec![
+            MemberDecl::Storage(implementation),
+            MemberDecl::Function(FunctionDecl::new(
+                "multicall".to_string(),
+                vec![],
+                vec![],
+                Some(body),
+                None,
+            )),
+        ];
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(ContractDecl::new(
+                "Proxy".to_string(),
+                members,
+                None,
+            ))],
+        };
+        let context = AnalysisContext::new(vec![module], AnalysisConfig::default());
+        DelegatecallInLoopDfaDetector::new()
+            .detect(&context)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_delegatecall_in_loop() {
+        let bugs = detect(vec![loop_with(vec![delegatecall("target")])]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].risk_level, RiskLevel::High);
+        assert_eq!(bugs[0].loc.start_line, 5);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "'Proxy.multicall' makes a delegatecall to 'target' inside the loop at \
+                 line 4; every iteration runs external code, multiplying the gas spent \
+                 and the reentrancy windows."
+            )
+        );
+    }
+
+    #[test]
+    fn test_constant_target_has_medium_severity() {
+        let bugs = detect(vec![loop_with(vec![delegatecall("implementation")])]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].risk_level, RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_delegatecall_outside_loop_is_skipped() {
+        assert!(detect(vec![delegatecall("target"), loop_with(vec![])]).is_empty());
+    }
+
+    #[test]
+    fn test_first_arg() {
+        assert_eq!(first_arg("address(a, b), data)"), "address(a, b)");
+        assert_eq!(first_arg("impl)"), "impl");
+        assert!(is_constant_target("address(0x1234)", &HashSet::new()));
+    }
+}
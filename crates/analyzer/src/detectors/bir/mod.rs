@@ -2,3 +2,11 @@
 //!
 //! All detectors that operate on the BIR (Basic IR) representation,
 //! using ICFG / taint / alias-set patterns.
+
+pub mod delegatecall_in_loop;
+pub mod unbounded_loop;
+pub mod unprotected_selfdestruct;
+
+pub use delegatecall_in_loop::DelegatecallInLoopDfaDetector;
+pub use unbounded_loop::UnboundedLoopDfaDetector;
+pub use unprotected_selfdestruct::UnprotectedSelfdestructDfaDetector;
@@ -0,0 +1,636 @@
+//! Unbounded Loop Detector
+//!
+//! Detects loops whose iteration count depends on the length of a storage
+//! array and whose body makes an external call or writes state:
+//!
+//! ```solidity
+//! address[] payees;
+//!
+//! function payAll() public {
+//!     for (uint i = 0; i < payees.length; i++) {
+//!         payable(payees[i]).transfer(1 ether);
+//!     }
+//! }
+//! ```
+//!
+//! Anyone able to grow `payees` can push `payAll` over the block gas limit
+//! and lock the contract for good.
+//!
+//! Loops are the natural loops of the BIR CFG. The bound is traced from the
+//! header's branch condition along the def-use chains of the function; since
+//! BIR reads variables by name, a variable is followed to every op defining
+//! it. Loops also bounded by a constant are skipped, as are arrays whose
+//! length is compared against a constant by an `assert` or `require`, either
+//! dominating the loop or in another function of the contract.
+
+use crate::context::AnalysisContext;
+use crate::detectors::base::{
+    BugDetectionPass, ConfidenceLevel, DetectorId, DetectorResult, create_bug,
+};
+use crate::frameworks::cfa::domtree::DomTree;
+use crate::frameworks::cfa::loops::LoopInfo;
+use crate::passes::base::Pass;
+use crate::passes::base::meta::{PassLevel, PassRepresentation};
+use crate::passes::bir::def_use::collect_operands;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::bir::cfg::{BlockId, Function, Terminator};
+use scirs::bir::interfaces::CallTarget;
+use scirs::bir::ops::{Op, OpId, OpKind, OpRef};
+use scirs::sir::{BinOp, Decl, MemberDecl, Type, UnOp};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// External calls that EVM dialect lowering leaves as opaque ops.
+const OPAQUE_EXTERNAL_CALLS: &[&str] =
+    &["evm.transfer(", "evm.low_level_call(", "evm.delegatecall("];
+
+/// Storage variables of a contract.
+#[derive(Debug, Default)]
+struct StorageVars {
+    /// Names of all storage variables.
+    all: HashSet<String>,
+
+    /// Names of the dynamic storage arrays.
+    arrays: HashSet<String>,
+}
+
+impl StorageVars {
+    /// The storage variable `name` refers to, for names that lowering
+    /// derives from accesses such as `balances_idx` or `config_owner`.
+    fn root_of(&self, name: &str) -> Option<&str> {
+        self.all
+            .iter()
+            .find(|var| {
+                name == var.as_str()
+                    || name
+                        .strip_prefix(var.as_str())
+                        .is_some_and(|rest| rest.starts_with('_'))
+            })
+            .map(String::as_str)
+    }
+}
+
+/// Def-use view of a single BIR function.
+struct FunctionFlow<'a> {
+    ops: HashMap<OpId, &'a Op>,
+    /// Ops carrying each variable name as their result.
+    defs: HashMap<&'a str, Vec<OpId>>,
+    uses: HashMap<OpId, Vec<OpId>>,
+    block_of: HashMap<OpId, BlockId>,
+    terms: HashMap<BlockId, &'a Terminator>,
+}
+
+impl<'a> FunctionFlow<'a> {
+    fn new(func: &'a Function) -> Self {
+        let mut flow = Self {
+            ops: HashMap::new(),
+            defs: HashMap::new(),
+            uses: HashMap::new(),
+            block_of: HashMap::new(),
+            terms: HashMap::new(),
+        };
+        for block in &func.blocks {
+            flow.terms.insert(block.id, &block.term);
+            for op in &block.ops {
+                flow.ops.insert(op.id, op);
+                flow.block_of.insert(op.id, block.id);
+                if let Some((name, _)) = &op.result {
+                    flow.defs.entry(name.base.as_str()).or_default().push(op.id);
+                }
+                for OpRef(operand) in collect_operands(&op.kind) {
+                    flow.uses.entry(operand).or_default().push(op.id);
+                }
+            }
+        }
+        flow
+    }
+
+    /// Ops whose values `op` reads: its operands and, for a variable read,
+    /// the ops defining the variable.
+    fn sources(&self, op: &Op) -> Vec<OpId> {
+        let mut sources: Vec<OpId> = collect_operands(&op.kind)
+            .into_iter()
+            .map(|OpRef(id)| id)
+            .collect();
+        if let OpKind::PseudoValue { label, .. } = &op.kind
+            && let Some(defs) = self.defs.get(label.as_str())
+        {
+            sources.extend(defs.iter().filter(|&&id| id != op.id));
+        }
+        sources
+    }
+
+    /// Ops reading the value of `op`: its users and, for a variable
+    /// definition, the reads of the variable.
+    fn sinks(&self, op: &Op) -> Vec<OpId> {
+        let mut sinks = self.uses.get(&op.id).cloned().unwrap_or_default();
+        if let Some((name, _)) = &op.result
+            && let Some(defs) = self.defs.get(name.base.as_str())
+        {
+            sinks.extend(defs.iter().filter(|&&id| {
+                id != op.id
+                    && matches!(
+                        &self.ops[&id].kind,
+                        OpKind::PseudoValue { label, .. } if *label == name.base
+                    )
+            }));
+        }
+        sinks
+    }
+
+    /// All ops reachable from `start` by following `next`.
+    fn closure(&self, start: OpId, next: impl Fn(&Op) -> Vec<OpId>) -> Vec<&'a Op> {
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut reached = vec![];
+        while let Some(id) = queue.pop_front() {
+            let Some(&op) = self.ops.get(&id) else {
+                continue;
+            };
+            reached.push(op);
+            for next_id in next(op) {
+                if seen.insert(next_id) {
+                    queue.push_back(next_id);
+                }
+            }
+        }
+        reached
+    }
+
+    /// The storage array whose `.length` the value of `op` depends on.
+    fn length_source(&self, op: OpId, storage: &StorageVars) -> Option<String> {
+        self.closure(op, |op| self.sources(op))
+            .into_iter()
+            .find_map(|op| match &op.kind {
+                OpKind::Opaque { description } => description
+                    .strip_suffix(".length")
+                    .filter(|array| storage.arrays.contains(*array))
+                    .map(str::to_string),
+                _ => None,
+            })
+    }
+
+    /// Whether `op` is a constant, or a variable only ever set to constants.
+    fn is_constant(&self, op: OpId) -> bool {
+        match &self.ops.get(&op).map(|op| &op.kind) {
+            Some(OpKind::Const(_)) => true,
+            Some(OpKind::PseudoValue { label, .. }) => {
+                let defs: Vec<&OpKind> = self
+                    .defs
+                    .get(label.as_str())
+                    .into_iter()
+                    .flatten()
+                    .map(|id| &self.ops[id].kind)
+                    .filter(|kind| !matches!(kind, OpKind::PseudoValue { .. }))
+                    .collect();
+                !defs.is_empty() && defs.iter().all(|kind| matches!(kind, OpKind::Const(_)))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the condition `cond` contains a comparison against a constant,
+    /// looking through `&&`, `||` and `!`.
+    fn bounded_by_constant(&self, cond: OpId) -> bool {
+        let Some(op) = self.ops.get(&cond) else {
+            return false;
+        };
+        match &op.kind {
+            OpKind::BinOp { op: BinOp::And | BinOp::Or, lhs, rhs, .. } => {
+                self.bounded_by_constant(lhs.0) || self.bounded_by_constant(rhs.0)
+            }
+            OpKind::UnOp { op: UnOp::Not, operand } => self.bounded_by_constant(operand.0),
+            OpKind::BinOp {
+                op: BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge, lhs, rhs, ..
+            } => self.is_constant(lhs.0) || self.is_constant(rhs.0),
+            _ => false,
+        }
+    }
+
+    /// Blocks holding an `assert` or `require` on a comparison of the length
+    /// of `array` against a constant.
+    fn length_guards(&self, array: &str, storage: &StorageVars) -> Vec<BlockId> {
+        let mut guards = vec![];
+        for op in self.ops.values() {
+            let (length, bound) = match &op.kind {
+                OpKind::BinOp { op: BinOp::Lt | BinOp::Le | BinOp::Eq, lhs, rhs, .. } => {
+                    (lhs, rhs)
+                }
+                OpKind::BinOp { op: BinOp::Gt | BinOp::Ge, lhs, rhs, .. } => (rhs, lhs),
+                _ => continue,
+            };
+            if self.length_source(length.0, storage).as_deref() != Some(array)
+                || !self.is_constant(bound.0)
+            {
+                continue;
+            }
+            for check in self.closure(op.id, |op| self.sinks(op)) {
+                let block = self.block_of[&check.id];
+                if matches!(check.kind, OpKind::Assert { .. }) || self.reverts_on(block, check.id)
+                {
+                    guards.push(block);
+                }
+            }
+        }
+        guards
+    }
+
+    /// Whether `block` branches on `cond` to a reverting block.
+    fn reverts_on(&self, block: BlockId, cond: OpId) -> bool {
+        let Some(Terminator::Branch { cond: OpRef(branch_cond), then_bb, else_bb }) =
+            self.terms.get(&block)
+        else {
+            return false;
+        };
+        *branch_cond == cond
+            && [then_bb, else_bb].into_iter().any(|bb| {
+                matches!(self.terms.get(bb), Some(Terminator::TxnExit { reverted: true }))
+            })
+    }
+}
+
+/// What a loop body does on each iteration.
+fn body_effect(op: &Op, storage: &StorageVars) -> Option<&'static str> {
+    match &op.kind {
+        OpKind::Call(call) if call.callee == CallTarget::Dynamic => {
+            return Some("makes an external call");
+        }
+        OpKind::Opaque { description } => {
+            if OPAQUE_EXTERNAL_CALLS
+                .iter()
+                .any(|call| description.starts_with(call))
+            {
+                return Some("makes an external call");
+            }
+            if storage.arrays.iter().any(|array| {
+                description.starts_with(&format!("{array}.push("))
+                    || description.starts_with(&format!("{array}.pop("))
+            }) {
+                return Some("writes state");
+            }
+        }
+        _ => {}
+    }
+
+    // Writes carry the name of the written variable; so do reads, whose
+    // kind is the read expression itself.
+    let (name, _) = op.result.as_ref()?;
+    let var = storage.root_of(&name.base)?;
+    let is_read = match &op.kind {
+        OpKind::PseudoValue { label, .. } => *label == name.base,
+        OpKind::Opaque { description } => description
+            .strip_prefix(var)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('[') || rest.starts_with('.')),
+        _ => false,
+    };
+    (!is_read).then_some("writes state")
+}
+
+/// Detector for loops bounded by the length of a storage array.
+#[derive(Debug, Default)]
+pub struct UnboundedLoopDfaDetector;
+
+impl UnboundedLoopDfaDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Storage variables of every contract, by contract name.
+    fn storage_vars(context: &AnalysisContext) -> HashMap<String, StorageVars> {
+        let mut contracts: HashMap<String, StorageVars> = HashMap::new();
+        if !context.has_ir() {
+            return contracts;
+        }
+        for module in context.ir_units() {
+            for decl in &module.decls {
+                let Decl::Contract(contract) = decl else {
+                    continue;
+                };
+                let vars = contracts.entry(contract.name.clone()).or_default();
+                for member in &contract.members {
+                    if let MemberDecl::Storage(storage) = member {
+                        vars.all.insert(storage.name.clone());
+                        if matches!(storage.ty, Type::Array(_)) {
+                            vars.arrays.insert(storage.name.clone());
+                        }
+                    }
+                }
+            }
+        }
+        contracts
+    }
+
+    fn check_function(
+        &self,
+        func: &Function,
+        siblings: &[&Function],
+        storage: &StorageVars,
+    ) -> Vec<Bug> {
+        let mut bugs = vec![];
+        let Some(dom) = DomTree::build(func) else {
+            return bugs;
+        };
+        let loops = LoopInfo::build(func, &dom);
+        let flow = FunctionFlow::new(func);
+
+        for natural_loop in loops.iter() {
+            let Some(Terminator::Branch { cond: OpRef(cond), .. }) =
+                flow.terms.get(&natural_loop.header)
+            else {
+                continue;
+            };
+            let Some(array) = flow.length_source(*cond, storage) else {
+                continue;
+            };
+            if flow.bounded_by_constant(*cond) {
+                continue;
+            }
+
+            // A guard in another function bounds how far the array grows.
+            let guarded = flow
+                .length_guards(&array, storage)
+                .into_iter()
+                .any(|block| dom.dominates(block, natural_loop.header))
+                || siblings.iter().any(|sibling| {
+                    !FunctionFlow::new(sibling)
+                        .length_guards(&array, storage)
+                        .is_empty()
+                });
+            if guarded {
+                continue;
+            }
+
+            let Some(effect) = func
+                .blocks
+                .iter()
+                .filter(|block| natural_loop.body.contains(&block.id))
+                .flat_map(|block| &block.ops)
+                .filter_map(|op| body_effect(op, storage))
+                .min()
+            else {
+                continue;
+            };
+
+            let loc = flow.ops[cond]
+                .span
+                .clone()
+                .unwrap_or_else(|| Loc::new(0, 0, 0, 0));
+            bugs.push(create_bug(
+                self,
+                Some(&format!(
+                    "Loop in '{}' iterates over storage array '{}' and {} in each \
+                     iteration; a large '{}' can exceed the block gas limit.",
+                    func.id.0, array, effect, array
+                )),
+                loc,
+                None,
+            ));
+        }
+
+        bugs
+    }
+}
+
+impl Pass for UnboundedLoopDfaDetector {
+    fn name(&self) -> &'static str {
+        "Unbounded Loop"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects loops over storage arrays that make external calls or write state."
+    }
+
+    fn level(&self) -> PassLevel {
+        PassLevel::Function
+    }
+
+    fn representation(&self) -> PassRepresentation {
+        PassRepresentation::Air
+    }
+
+    fn dependencies(&self) -> Vec<TypeId> {
+        vec![]
+    }
+}
+
+impl BugDetectionPass for UnboundedLoopDfaDetector {
+    fn detector_id(&self) -> DetectorId {
+        DetectorId::UnboundedLoop
+    }
+
+    fn detect(&self, context: &AnalysisContext) -> DetectorResult<Vec<Bug>> {
+        if !context.has_air() {
+            return Ok(vec![]);
+        }
+        let contracts = Self::storage_vars(context);
+        let mut bugs = vec![];
+
+        for module in context.air_units() {
+            for func in &module.functions {
+                let Some((contract, _)) = func.id.0.split_once('.') else {
+                    continue;
+                };
+                let Some(storage) = contracts.get(contract) else {
+                    continue;
+                };
+                let siblings: Vec<&Function> = module
+                    .functions
+                    .iter()
+                    .filter(|other| {
+                        other.id != func.id
+                            && other.id.0.split_once('.').map(|(c, _)| c) == Some(contract)
+                    })
+                    .collect();
+                bugs.extend(self.check_function(func, &siblings, storage));
+            }
+        }
+
+        Ok(bugs)
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::DenialOfService
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> ConfidenceLevel {
+        ConfidenceLevel::Medium
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![400]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![128]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Bound the array with a `require` on its length where it grows, or \
+         process it in batches. Prefer pull payments to pushing funds in a loop."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-128",
+            "https://consensys.github.io/smart-contract-best-practices/attacks/denial-of-service/",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::AnalysisConfig;
+    use scirs::sir::*;
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), Type::I256, None))
+    }
+
+    fn num() -> Expr {
+        Expr::Lit(Lit::Num(NumLit::new(Num::Int(IntNum::one()), None)))
+    }
+
+    fn binop(op: BinOp, lhs: Expr, rhs: Expr) -> Expr {
+        Expr::BinOp(BinOpExpr {
+            op,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        })
+    }
+
+    fn length(array: &str) -> Expr {
+        Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(var(array)),
+            field: "length".to_string(),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    /// `for (i = 0; cond; i += 1) { total = i; }`
+    fn loop_over(cond: Expr) -> Stmt {
+        Stmt::For(ForStmt {
+            init: Some(Box::new(Stmt::Assign(AssignStmt {
+                lhs: var("i"),
+                rhs: num(),
+                span: None,
+            }))),
+            cond: Some(cond),
+            update: Some(Box::new(Stmt::AugAssign(AugAssignStmt {
+                op: BinOp::Add,
+                lhs: var("i"),
+                rhs: num(),
+                span: None,
+            }))),
+            body: vec![Stmt::Assign(AssignStmt {
+                lhs: var("total"),
+                rhs: var("i"),
+                span: None,
+            })],
+            invariant: None,
+            span: None,
+        })
+    }
+
+    /// `require(payees.length < 1)`
+    fn require_bounded() -> Stmt {
+        Stmt::If(IfStmt {
+            cond: Expr::UnOp(UnOpExpr {
+                op: UnOp::Not,
+                operand: Box::new(binop(BinOp::Lt, length("payees"), num())),
+                span: None,
+            }),
+            then_body: vec![Stmt::Revert(RevertStmt {
+                error: None,
+                args: vec![],
+                span: None,
+            })],
+            else_body: None,
+            span: None,
+        })
+    }
+
+    fn detect(functions: Vec<(&str, Vec<Stmt>)>) -> Vec<Bug> {
+        let mut members = vec![
+            MemberDecl::Storage(StorageDecl::new(
+                "payees".to_string(),
+                Type::Array(Box::new(Type::I256)),
+                None,
+                None,
+            )),
+            MemberDecl::Storage(StorageDecl::new("total".to_string(), Type::I256, None, None)),
+        ];
+        for (name, body) in functions {
+            members.push(MemberDecl::Function(FunctionDecl::new(
+                name.to_string(),
+                vec![],
+                vec![],
+                Some(body),
+                None,
+            )));
+        }
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(ContractDecl::new(
+                "Pay".to_string(),
+                members,
+                None,
+            ))],
+        };
+        let context = AnalysisContext::new(vec![module], AnalysisConfig::default());
+        UnboundedLoopDfaDetector::new().detect(&context).unwrap()
+    }
+
+    #[test]
+    fn test_loop_over_storage_array_writing_state() {
+        let bugs = detect(vec![(
+            "payAll",
+            vec![loop_over(binop(BinOp::Lt, var("i"), length("payees")))],
+        )]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "Loop in 'Pay.payAll' iterates over storage array 'payees' and writes state \
+                 in each iteration; a large 'payees' can exceed the block gas limit."
+            )
+        );
+    }
+
+    #[test]
+    fn test_bounded_loops_are_skipped() {
+        let unbounded = binop(BinOp::Lt, var("i"), length("payees"));
+
+        // Also bounded by a constant.
+        let cond = binop(BinOp::And, unbounded.clone(), binop(BinOp::Lt, var("i"), num()));
+        assert!(detect(vec![("payAll", vec![loop_over(cond)])]).is_empty());
+
+        // Length checked before the loop.
+        let body = vec![require_bounded(), loop_over(unbounded.clone())];
+        assert!(detect(vec![("payAll", body)]).is_empty());
+
+        // Length checked where the array grows.
+        let functions = vec![
+            ("payAll", vec![loop_over(unbounded)]),
+            ("addPayee", vec![require_bounded()]),
+        ];
+        assert!(detect(functions).is_empty());
+    }
+}
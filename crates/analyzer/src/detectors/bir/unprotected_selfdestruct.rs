@@ -0,0 +1,435 @@
+//! Unprotected Selfdestruct Detector
+//!
+//! Detects `selfdestruct` (or the deprecated `suicide`) that a public or
+//! external function can reach without passing an access control check:
+//!
+//! ```solidity
+//! function kill() public {  // no `onlyOwner`
+//!     selfdestruct(payable(msg.sender));
+//! }
+//! ```
+//!
+//! Anyone can then destroy the contract, as happened to the Parity
+//! multi-sig wallet library.
+//!
+//! The search walks the BIR CFG from the entry of each public function,
+//! following calls to the internal functions of the contract, and stops at
+//! the checks on `msg.sender` found by the `AccessControlPass`. Since
+//! modifiers are inlined before BIR is built, `onlyOwner`-style modifiers
+//! are such checks. At a branch on `msg.sender`, only the code both arms
+//! lead to is explored further: the arms themselves depend on the caller,
+//! and nothing follows the arm of a `require` that reverts.
+
+use crate::context::AnalysisContext;
+use crate::detectors::base::{
+    BugDetectionPass, ConfidenceLevel, DetectorId, DetectorResult, create_bug,
+};
+use crate::passes::base::Pass;
+use crate::passes::base::meta::{PassLevel, PassRepresentation};
+use crate::passes::bir::access_control::{
+    AccessControlArtifact, AccessControlPass, AccessGuards, compute_access_guards,
+};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::bir::cfg::{BlockId, Function, FunctionId, Terminator};
+use scirs::bir::ops::{Op, OpId, OpKind};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+/// Prefixes of the opaque ops destroying the contract.
+const SELFDESTRUCT_CALLS: &[&str] = &["selfdestruct(", "suicide("];
+
+/// Returns `true` if `op` destroys the contract.
+fn is_selfdestruct(op: &Op) -> bool {
+    matches!(&op.kind, OpKind::Opaque { description }
+        if SELFDESTRUCT_CALLS.iter().any(|call| description.starts_with(call)))
+}
+
+/// Successor blocks of `term`.
+fn successors(term: &Terminator) -> Vec<BlockId> {
+    match term {
+        Terminator::Jump(target) => vec![*target],
+        Terminator::Branch { then_bb, else_bb, .. } => vec![*then_bb, *else_bb],
+        _ => vec![],
+    }
+}
+
+/// Blocks of `func` reachable from `start`, including `start` itself.
+fn reachable(func: &Function, start: BlockId) -> HashSet<BlockId> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(block_id) = stack.pop() {
+        if !seen.insert(block_id) {
+            continue;
+        }
+        if let Some(block) = func.blocks.iter().find(|b| b.id == block_id) {
+            stack.extend(successors(&block.term));
+        }
+    }
+    seen
+}
+
+/// Detector for `selfdestruct` reachable without access control.
+#[derive(Debug, Default)]
+pub struct UnprotectedSelfdestructDfaDetector;
+
+impl UnprotectedSelfdestructDfaDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The `selfdestruct` ops that `entry` reaches without passing a check
+    /// on `msg.sender`, with the functions holding them. `functions` are
+    /// the functions of the contract of `entry`, by short name.
+    fn unguarded_selfdestructs<'a>(
+        entry: &'a Function,
+        functions: &HashMap<&str, &'a Function>,
+        guards: &HashMap<String, AccessGuards>,
+    ) -> Vec<(&'a Function, &'a Op)> {
+        let no_guards = AccessGuards::default();
+        let mut found = vec![];
+        let mut seen_sites: HashSet<(&FunctionId, OpId)> = HashSet::new();
+        let mut visited: HashSet<(&str, BlockId)> = HashSet::new();
+        let mut stack: Vec<(&Function, BlockId)> = entry
+            .blocks
+            .first()
+            .map(|b| (entry, b.id))
+            .into_iter()
+            .collect();
+
+        while let Some((func, block_id)) = stack.pop() {
+            if !visited.insert((func.id.0.as_str(), block_id)) {
+                continue;
+            }
+            let Some(block) = func.blocks.iter().find(|b| b.id == block_id) else {
+                continue;
+            };
+            let func_guards = guards.get(&func.id.0).unwrap_or(&no_guards);
+
+            let mut guarded = false;
+            for op in &block.ops {
+                if func_guards.asserts.contains(&op.id) {
+                    guarded = true;
+                    break;
+                }
+                if is_selfdestruct(op) {
+                    if seen_sites.insert((&func.id, op.id)) {
+                        found.push((func, op));
+                    }
+                    continue;
+                }
+                if let OpKind::Opaque { description } = &op.kind
+                    && let Some((callee, _)) = description.split_once('(')
+                    && let Some(&callee) = functions.get(callee)
+                    && let Some(callee_entry) = callee.blocks.first()
+                {
+                    stack.push((callee, callee_entry.id));
+                }
+            }
+            if guarded {
+                continue;
+            }
+            match block.term {
+                // Only the code after the branch is reached whatever the
+                // caller; a reverting arm reaches nothing.
+                Terminator::Branch { then_bb, else_bb, .. }
+                    if func_guards.branches.contains(&block_id) =>
+                {
+                    let after_then = reachable(func, then_bb);
+                    let after_else = reachable(func, else_bb);
+                    stack.extend(after_then.intersection(&after_else).map(|&bb| (func, bb)));
+                }
+                _ => stack.extend(successors(&block.term).into_iter().map(|bb| (func, bb))),
+            }
+        }
+        found
+    }
+}
+
+impl Pass for UnprotectedSelfdestructDfaDetector {
+    fn name(&self) -> &'static str {
+        "Unprotected Selfdestruct"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects selfdestruct reachable from public functions without access control."
+    }
+
+    fn level(&self) -> PassLevel {
+        PassLevel::Function
+    }
+
+    fn representation(&self) -> PassRepresentation {
+        PassRepresentation::Air
+    }
+
+    fn dependencies(&self) -> Vec<TypeId> {
+        vec![TypeId::of::<AccessControlPass>()]
+    }
+}
+
+impl BugDetectionPass for UnprotectedSelfdestructDfaDetector {
+    fn detector_id(&self) -> DetectorId {
+        DetectorId::UnprotectedSelfdestruct
+    }
+
+    fn detect(&self, context: &AnalysisContext) -> DetectorResult<Vec<Bug>> {
+        if !context.has_air() {
+            return Ok(vec![]);
+        }
+        let computed;
+        let guards = match context.get::<AccessControlArtifact>() {
+            Some(guards) => guards,
+            None => {
+                computed = compute_access_guards(context.air_units());
+                &computed
+            }
+        };
+        let mut bugs = vec![];
+
+        for module in context.air_units() {
+            let mut contracts: HashMap<&str, HashMap<&str, &Function>> = HashMap::new();
+            for func in &module.functions {
+                if let Some((contract, name)) = func.id.0.split_once('.') {
+                    contracts.entry(contract).or_default().insert(name, func);
+                }
+            }
+
+            for entry in module.functions.iter().filter(|func| func.is_public) {
+                let Some((contract, _)) = entry.id.0.split_once('.') else {
+                    continue;
+                };
+                let functions = &contracts[contract];
+                for (func, op) in Self::unguarded_selfdestructs(entry, functions, guards) {
+                    let through = if func.id == entry.id {
+                        String::new()
+                    } else {
+                        format!(" through '{}'", func.id.0)
+                    };
+                    bugs.push(create_bug(
+                        self,
+                        Some(&format!(
+                            "'{}' can reach `selfdestruct`{} without an access control \
+                             check; anyone can destroy the contract.",
+                            entry.id.0, through
+                        )),
+                        op.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                        None,
+                    ));
+                }
+            }
+        }
+
+        Ok(bugs)
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Critical
+    }
+
+    fn confidence(&self) -> ConfidenceLevel {
+        ConfidenceLevel::Medium
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![284]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![106]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Restrict functions reaching `selfdestruct` to authorized callers, e.g. \
+         with an `onlyOwner` modifier, or remove `selfdestruct` altogether."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-106",
+            "https://www.parity.io/blog/a-postmortem-on-the-parity-multi-sig-library-self-destruct/",
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::AnalysisConfig;
+    use crate::passes::base::AnalysisPass;
+    use scirs::sir::dialect::evm::{EvmExpr, EvmMsgSender, EvmSelfdestruct, EvmStmt, EvmType};
+    use scirs::sir::*;
+
+    fn sender() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgSender(EvmMsgSender {
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    /// `selfdestruct(msg.sender)` on line 7.
+    fn selfdestruct() -> Stmt {
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::Selfdestruct(EvmSelfdestruct {
+            recipient: sender(),
+            loc: Loc::new(7, 9, 7, 34),
+        })))
+    }
+
+    /// `msg.sender == owner`
+    fn is_owner() -> Expr {
+        let owner = Type::Dialect(DialectType::Evm(EvmType::Address));
+        Expr::BinOp(BinOpExpr {
+            op: BinOp::Eq,
+            lhs: Box::new(sender()),
+            rhs: Box::new(Expr::Var(VarExpr::new("owner".to_string(), owner, None))),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        })
+    }
+
+    /// `require(msg.sender == owner)`
+    fn only_owner() -> Stmt {
+        Stmt::If(IfStmt {
+            cond: Expr::UnOp(UnOpExpr {
+                op: UnOp::Not,
+                operand: Box::new(is_owner()),
+                span: None,
+            }),
+            then_body: vec![Stmt::Revert(RevertStmt {
+                error: None,
+                args: vec![],
+                span: None,
+            })],
+            else_body: None,
+            span: None,
+        })
+    }
+
+    /// `_destroy()`
+    fn call_destroy() -> Stmt {
+        call("_destroy")
+    }
+
+    /// `name()`
+    fn call(name: &str) -> Stmt {
+        let callee = Expr::Var(VarExpr::new(name.to_string(), Type::None, None));
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(callee),
+                args: CallArgs::Positional(vec![]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    fn function(name: &str, public: bool, body: Vec<Stmt>) -> MemberDecl {
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), None);
+        let visibility = if public { "public" } else { "internal" };
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String(visibility.into())));
+        MemberDecl::Function(func)
+    }
+
+    fn detect(members: Vec<MemberDecl>) -> Vec<Bug> {
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(ContractDecl::new(
+                "Wallet".to_string(),
+                members,
+                None,
+            ))],
+        };
+        let mut context = AnalysisContext::new(vec![module], AnalysisConfig::default());
+        AccessControlPass.run(&mut context).unwrap();
+        UnprotectedSelfdestructDfaDetector::new()
+            .detect(&context)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unprotected_selfdestruct() {
+        let bugs = detect(vec![function("kill", true, vec![selfdestruct()])]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].loc, Loc::new(7, 9, 7, 34));
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "'Wallet.kill' can reach `selfdestruct` without an access control check; \
+                 anyone can destroy the contract."
+            )
+        );
+
+        // Through an internal helper.
+        let bugs = detect(vec![
+            function("kill", true, vec![call_destroy()]),
+            function("_destroy", false, vec![selfdestruct()]),
+        ]);
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .starts_with("'Wallet.kill' can reach `selfdestruct` through 'Wallet._destroy'")
+        );
+    }
+
+    #[test]
+    fn test_protected_selfdestruct_is_not_reported() {
+        let bugs = detect(vec![function("kill", true, vec![only_owner(), selfdestruct()])]);
+        assert!(bugs.is_empty());
+
+        // The helper is only reachable after the check.
+        let bugs = detect(vec![
+            function("kill", true, vec![only_owner(), call_destroy()]),
+            function("_destroy", false, vec![selfdestruct()]),
+        ]);
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_code_after_a_sender_branch_is_not_protected() {
+        // if (msg.sender == owner) { _log(); } selfdestruct(msg.sender);
+        let branch = Stmt::If(IfStmt {
+            cond: is_owner(),
+            then_body: vec![call("_log")],
+            else_body: None,
+            span: None,
+        });
+        let bugs = detect(vec![
+            function("kill", true, vec![branch, selfdestruct()]),
+            function("_log", false, vec![]),
+        ]);
+        assert_eq!(bugs.len(), 1);
+
+        // The arm itself is only reached by the owner.
+        let branch = Stmt::If(IfStmt {
+            cond: is_owner(),
+            then_body: vec![selfdestruct()],
+            else_body: None,
+            span: None,
+        });
+        assert!(detect(vec![function("kill", true, vec![branch])]).is_empty());
+    }
+
+    #[test]
+    fn test_selfdestructs_of_different_helpers_are_all_reported() {
+        // Both helpers hold their `selfdestruct` at the same op id.
+        let bugs = detect(vec![
+            function("kill", true, vec![call("_destroy"), call("_destroy2")]),
+            function("_destroy", false, vec![selfdestruct()]),
+            function("_destroy2", false, vec![selfdestruct()]),
+        ]);
+        assert_eq!(bugs.len(), 2);
+    }
+}
@@ -3,7 +3,7 @@
 //! Detectors are organised into tiers:
 //! - `base/`: Core traits, ID types, and registry
 //! - `scan_adapter`: Wraps `scanner::ScanDetector` → `BugDetectionPass`
-//! - `bir/`: BIR dataflow detectors (ICFG / taint) — placeholder
+//! - `bir/`: BIR dataflow detectors (ICFG / taint)
 
 pub mod base;
 pub mod bir;
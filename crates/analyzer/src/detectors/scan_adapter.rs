@@ -10,8 +10,8 @@ use crate::detectors::BugDetectionPass;
 use crate::passes::base::Pass;
 use crate::passes::base::meta::{PassLevel, PassRepresentation};
 use bugs::bug::Bug;
-use scanner::detector::{Confidence, DetectionLevel};
-use scanner::ScanDetector;
+use scanner::detector::DetectionLevel;
+use scanner::{DetectorParams, ScanDetector};
 use scirs::sir::{Decl, MemberDecl};
 
 /// Wraps a `ScanDetector` so it can participate in the analyzer pipeline.
@@ -36,7 +36,7 @@ impl Pass for ScanDetectorAdapter {
 
     fn level(&self) -> PassLevel {
         match self.detector.level() {
-            DetectionLevel::Module => PassLevel::Program,
+            DetectionLevel::Project | DetectionLevel::Module => PassLevel::Program,
             DetectionLevel::Contract => PassLevel::Contract,
             DetectionLevel::Function => PassLevel::Function,
         }
@@ -53,7 +53,8 @@ impl Pass for ScanDetectorAdapter {
 
 impl BugDetectionPass for ScanDetectorAdapter {
     fn detector_id(&self) -> DetectorId {
-        DetectorId::from_str(self.detector.id())
+        let id = self.detector.id();
+        DetectorId::parse(id).unwrap_or(DetectorId::Custom(id))
     }
 
     fn detect(&self, context: &AnalysisContext) -> DetectorResult<Vec<Bug>> {
@@ -64,6 +65,9 @@ impl BugDetectionPass for ScanDetectorAdapter {
         let mut bugs = Vec::new();
 
         match self.detector.level() {
+            DetectionLevel::Project => {
+                bugs.extend(self.detector.check_project(modules));
+            }
             DetectionLevel::Module => {
                 for module in modules {
                     bugs.extend(self.detector.check_module(module));
@@ -112,11 +116,7 @@ impl BugDetectionPass for ScanDetectorAdapter {
     }
 
     fn confidence(&self) -> ConfidenceLevel {
-        match self.detector.confidence() {
-            Confidence::Low => ConfidenceLevel::Low,
-            Confidence::Medium => ConfidenceLevel::Medium,
-            Confidence::High => ConfidenceLevel::High,
-        }
+        self.detector.confidence()
     }
 
     fn cwe_ids(&self) -> Vec<usize> {
@@ -134,4 +134,16 @@ impl BugDetectionPass for ScanDetectorAdapter {
     fn references(&self) -> Vec<&'static str> {
         self.detector.references()
     }
+
+    fn tags(&self) -> Vec<&'static str> {
+        self.detector.tags()
+    }
+
+    fn configure(&mut self, params: &DetectorParams) {
+        self.detector.configure(params);
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        self.detector.param_names()
+    }
 }
@@ -0,0 +1,173 @@
+//! Detector explanation (dry-run) mode.
+//!
+//! Runs a single detector on a Solidity snippet and records, next to its
+//! findings, the intermediate facts the run relied on: what was compiled and
+//! lowered, which contracts and functions were in scope, and which function
+//! each match was attributed to. Intended for rule debugging and teaching.
+
+use crate::{AnalysisConfig, AnalysisContext, InputLanguage, PipelineConfig, PipelineEngine};
+use bugs::bug::Bug;
+use common::loc::Loc;
+use scirs::sir::{Decl, MemberDecl, Module};
+use std::fmt;
+
+/// Kind of a fact recorded in a detector trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceKind {
+    /// The snippet was compiled to source units.
+    Compiled,
+    /// Source units were lowered to SIR modules.
+    Lowered,
+    /// A contract was in the detector's scope.
+    Scope,
+    /// The detector matched at a location.
+    Match,
+}
+
+impl fmt::Display for TraceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TraceKind::Compiled => "compiled",
+            TraceKind::Lowered => "lowered",
+            TraceKind::Scope => "scope",
+            TraceKind::Match => "match",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// One step of a detector trace.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    pub kind: TraceKind,
+    pub message: String,
+    pub loc: Option<Loc>,
+}
+
+/// Findings of a single detector together with the trace that produced them.
+#[derive(Debug, Clone)]
+pub struct DetectorExplanation {
+    /// Kebab-case ID of the explained detector.
+    pub detector_id: String,
+    /// Human-readable detector name.
+    pub detector_name: String,
+    /// Findings reported by the detector.
+    pub bugs: Vec<Bug>,
+    /// Intermediate facts, in the order they were established.
+    pub trace: Vec<TraceStep>,
+}
+
+impl DetectorExplanation {
+    fn push(&mut self, kind: TraceKind, message: String, loc: Option<Loc>) {
+        self.trace.push(TraceStep { kind, message, loc });
+    }
+}
+
+/// Compile `source_code`, run only the detector `id` on it, and explain the
+/// result.
+///
+/// The snippet is passed as a string; callers don't need to write it to a
+/// project file. Returns an error if the detector is unknown or the snippet
+/// fails to compile or lower.
+pub fn explain_detector(
+    id: &str,
+    source_code: &str,
+    solc_ver: &str,
+) -> Result<DetectorExplanation, String> {
+    let engine = PipelineEngine::new(PipelineConfig {
+        parallel: false,
+        enabled: vec![id.to_string()],
+        ..PipelineConfig::default()
+    });
+    let (detector_id, detector_name) = match engine.registry().get(id) {
+        Some(d) => (d.detector_id().as_str().to_string(), d.name().to_string()),
+        None => return Err(format!("Unknown detector: {id}")),
+    };
+
+    let mut explanation =
+        DetectorExplanation { detector_id, detector_name, bugs: vec![], trace: vec![] };
+
+    let source_units =
+        frontend::solidity::parsing::parse_solidity_source_code(source_code, solc_ver)
+            .map_err(|err| format!("Failed to compile snippet: {err}"))?;
+    explanation.push(
+        TraceKind::Compiled,
+        format!("Compiled snippet to {} source unit(s)", source_units.len()),
+        None,
+    );
+
+    let modules = frontend::solidity::lowering::lower_source_units(&source_units)
+        .map_err(|err| format!("Failed to lower snippet: {err}"))?;
+    explanation.push(
+        TraceKind::Lowered,
+        format!("Lowered to {} SIR module(s)", modules.len()),
+        None,
+    );
+
+    for module in &modules {
+        for decl in &module.decls {
+            if let Decl::Contract(contract) = decl {
+                let functions: Vec<&str> = contract
+                    .members
+                    .iter()
+                    .filter_map(|m| match m {
+                        MemberDecl::Function(f) => Some(f.name.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                explanation.push(
+                    TraceKind::Scope,
+                    format!(
+                        "Scanned contract '{}' ({} function(s): {})",
+                        contract.name,
+                        functions.len(),
+                        functions.join(", ")
+                    ),
+                    contract.span.clone(),
+                );
+            }
+        }
+    }
+
+    let analysis_config =
+        AnalysisConfig { input_language: InputLanguage::Solidity, ..AnalysisConfig::default() };
+    let mut context = AnalysisContext::new(modules, analysis_config);
+    let result = engine.run(&mut context);
+
+    for bug in &result.bugs {
+        let location = match enclosing_function(context.ir_units(), &bug.loc) {
+            Some((contract, func)) => format!("'{contract}.{func}'"),
+            None => "module scope".to_string(),
+        };
+        let description = bug.description.as_deref().unwrap_or(&bug.name);
+        explanation.push(
+            TraceKind::Match,
+            format!("Matched in {} at line {}: {}", location, bug.loc.start_line, description),
+            Some(bug.loc.clone()),
+        );
+    }
+    explanation.bugs = result.bugs;
+
+    Ok(explanation)
+}
+
+/// Find the contract and function whose span contains `loc`.
+fn enclosing_function(modules: &[Module], loc: &Loc) -> Option<(String, String)> {
+    for module in modules {
+        for decl in &module.decls {
+            let Decl::Contract(contract) = decl else {
+                continue;
+            };
+            for member in &contract.members {
+                if let MemberDecl::Function(func) = member
+                    && let Some(span) = &func.span
+                    && span.start_line <= loc.start_line
+                    && loc.end_line <= span.end_line
+                {
+                    return Some((contract.name.clone(), func.name.clone()));
+                }
+            }
+        }
+    }
+    None
+}
@@ -13,7 +13,7 @@ use scirs::sir::defs::{FunctionDecl, MemberDecl};
 use scirs::sir::exprs::Expr;
 use scirs::sir::module::{Decl, Module};
 use scirs::sir::stmts::Stmt;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // ═══════════════════════════════════════════════════════════════════
 // SirCallGraph
@@ -119,6 +119,30 @@ impl SirCallGraph {
         self.name_to_node.keys().map(|s| s.as_str())
     }
 
+    /// Render the call graph in Graphviz DOT format, one node per
+    /// `Contract.function` and one edge per distinct caller/callee pair.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph CallGraph {\n    node [shape=box];\n");
+        for node in self.graph.node_indices() {
+            dot.push_str(&format!("    {};\n", dot_id(&self.graph[node])));
+        }
+        let mut seen = HashSet::new();
+        for edge in self.graph.edge_indices() {
+            let Some((caller, callee)) = self.graph.edge_endpoints(edge) else {
+                continue;
+            };
+            if seen.insert((caller, callee)) {
+                dot.push_str(&format!(
+                    "    {} -> {};\n",
+                    dot_id(&self.graph[caller]),
+                    dot_id(&self.graph[callee])
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
     fn get_or_insert_node(&mut self, name: &str) -> NodeIndex {
         if let Some(&idx) = self.name_to_node.get(name) {
             idx
@@ -138,6 +162,11 @@ fn qualified_name(contract: &str, function: &str) -> String {
     format!("{contract}.{function}")
 }
 
+/// Quote `name` as a DOT identifier.
+pub(crate) fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 /// Collect all callee names found in a function's body.
 fn collect_callees(func: &FunctionDecl, contract_name: &str) -> Vec<String> {
     let mut callees = Vec::new();
@@ -323,6 +352,7 @@ mod tests {
             type_params: vec![],
             params: vec![],
             returns: vec![],
+            return_names: vec![],
             attrs: vec![],
             spec: None,
             body: Some(body),
@@ -364,6 +394,32 @@ mod tests {
         assert_eq!(cg.callers_of("Foo.b"), vec!["Foo.a"]);
     }
 
+    #[test]
+    fn test_call_graph_to_dot() {
+        // Contract Foo { fn a() { b(); b(); } fn b() {} }
+        let call_b = || Stmt::Expr(ExprStmt { expr: make_call_expr("b"), span: None });
+        let func_a = make_function("a", vec![call_b(), call_b()]);
+        let func_b = make_function("b", vec![]);
+        let module = Module {
+            id: "test".into(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(ContractDecl {
+                name: "Foo".into(),
+                parents: vec![],
+                attrs: vec![],
+                members: vec![MemberDecl::Function(func_a), MemberDecl::Function(func_b)],
+                span: None,
+            })],
+        };
+
+        let dot = SirCallGraph::build(&module).to_dot();
+        assert_eq!(
+            dot,
+            "digraph CallGraph {\n    node [shape=box];\n    \"Foo.a\";\n    \"Foo.b\";\n    \
+             \"Foo.a\" -> \"Foo.b\";\n}\n"
+        );
+    }
+
     #[test]
     fn test_no_calls() {
         let func = make_function("f", vec![]);
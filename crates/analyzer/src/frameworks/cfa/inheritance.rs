@@ -0,0 +1,347 @@
+//! Contract Inheritance Graph from SIR
+//!
+//! Builds a graph of the contracts of an SIR `Module` with an edge from
+//! each contract to each of its direct bases, in declaration order. Next
+//! to these, override edges link a contract to every ancestor declaring a
+//! function it redefines (same name and arity), labelled with the
+//! functions, so that diamond overrides show up as several such edges.
+//!
+//! Bases that are not declared in the module (e.g. imported interfaces)
+//! are still nodes, without bases of their own.
+
+use crate::frameworks::cfa::callgraph::dot_id;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use scirs::sir::defs::{ContractDecl, MemberDecl};
+use scirs::sir::lower::linearize;
+use scirs::sir::module::{Decl, Module};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// ═══════════════════════════════════════════════════════════════════
+// InheritanceGraph
+// ═══════════════════════════════════════════════════════════════════
+
+/// Edge of an [`InheritanceGraph`], from a contract to one of its
+/// ancestors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InheritanceEdge {
+    /// The ancestor is a direct base of the contract.
+    Inherits,
+    /// The contract redefines these functions of the ancestor.
+    Overrides(Vec<String>),
+}
+
+/// Inheritance and override relationships between the contracts of a
+/// module.
+#[derive(Debug, Clone)]
+pub struct InheritanceGraph {
+    /// The underlying directed graph, with contract names as nodes.
+    pub graph: DiGraph<String, InheritanceEdge>,
+    /// Name → node index lookup.
+    name_to_node: HashMap<String, NodeIndex>,
+    /// C3 linearization of every contract that has one.
+    linearizations: HashMap<String, Vec<String>>,
+}
+
+impl InheritanceGraph {
+    /// Build the inheritance graph of an SIR module.
+    pub fn build(module: &Module) -> Self {
+        let mut ig = InheritanceGraph {
+            graph: DiGraph::new(),
+            name_to_node: HashMap::new(),
+            linearizations: HashMap::new(),
+        };
+        let contracts: HashMap<&str, &ContractDecl> = module
+            .decls
+            .iter()
+            .filter_map(|decl| match decl {
+                Decl::Contract(c) => Some((c.name.as_str(), c)),
+                Decl::Dialect(_) => None,
+            })
+            .collect();
+
+        // Phase 1: Contracts and their direct bases.
+        for decl in &module.decls {
+            if let Decl::Contract(contract) = decl {
+                let node = ig.get_or_insert_node(&contract.name);
+                for parent in &contract.parents {
+                    let parent_node = ig.get_or_insert_node(parent);
+                    ig.graph
+                        .add_edge(node, parent_node, InheritanceEdge::Inherits);
+                }
+            }
+        }
+
+        // Phase 2: Functions redefined from ancestors.
+        for decl in &module.decls {
+            if let Decl::Contract(contract) = decl {
+                let node = ig.get_or_insert_node(&contract.name);
+                for ancestor in ancestors(contract, &contracts) {
+                    let overridden: Vec<String> = functions(contract)
+                        .filter(|(name, arity)| {
+                            functions(ancestor).any(|(n, a)| n == *name && a == *arity)
+                        })
+                        .map(|(name, _)| name.to_string())
+                        .collect();
+                    if !overridden.is_empty() {
+                        let ancestor_node = ig.get_or_insert_node(&ancestor.name);
+                        ig.graph.add_edge(
+                            node,
+                            ancestor_node,
+                            InheritanceEdge::Overrides(overridden),
+                        );
+                    }
+                }
+            }
+        }
+
+        // Phase 3: Linearizations.
+        let by_name: HashMap<String, &ContractDecl> = contracts
+            .iter()
+            .map(|(&name, &contract)| (name.to_string(), contract))
+            .collect();
+        for name in ig.name_to_node.keys() {
+            if let Ok(order) = linearize(name, &by_name) {
+                ig.linearizations.insert(name.clone(), order);
+            }
+        }
+
+        ig
+    }
+
+    /// Look up the node index for a contract name.
+    pub fn node_index(&self, name: &str) -> Option<NodeIndex> {
+        self.name_to_node.get(name).copied()
+    }
+
+    /// Direct bases of a contract, in declaration order.
+    pub fn parents_of(&self, name: &str) -> Vec<&str> {
+        self.edges_of(name, |edge| matches!(edge, InheritanceEdge::Inherits))
+            .map(|(parent, _)| parent)
+            .collect()
+    }
+
+    /// Ancestors whose functions the contract redefines, with those
+    /// functions.
+    pub fn overrides_of(&self, name: &str) -> Vec<(&str, &[String])> {
+        self.edges_of(name, |edge| matches!(edge, InheritanceEdge::Overrides(_)))
+            .filter_map(|(ancestor, edge)| match edge {
+                InheritanceEdge::Overrides(functions) => Some((ancestor, functions.as_slice())),
+                InheritanceEdge::Inherits => None,
+            })
+            .collect()
+    }
+
+    /// Solidity's C3 linearization of a contract, most derived first, as
+    /// used to resolve overrides and `super` calls. Direct bases are listed
+    /// most base first, so `D is B, C` linearizes as `[D, C, B, ...]`.
+    ///
+    /// Returns `None` for an unknown contract or a hierarchy that cannot be
+    /// linearized.
+    pub fn linearization(&self, contract: &str) -> Option<Vec<String>> {
+        self.linearizations.get(contract).cloned()
+    }
+
+    /// Number of contracts (nodes) in the graph.
+    pub fn contract_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Render the graph in Graphviz DOT format. Inheritance edges are solid;
+    /// override edges are dashed and labelled with the redefined
+    /// functions as `Contract.function`.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph InheritanceGraph {\n    node [shape=box];\n");
+        for node in self.graph.node_indices() {
+            dot.push_str(&format!("    {};\n", dot_id(&self.graph[node])));
+        }
+        for edge in self.graph.edge_indices() {
+            let Some((from, to)) = self.graph.edge_endpoints(edge) else {
+                continue;
+            };
+            let (contract, ancestor) = (&self.graph[from], &self.graph[to]);
+            match &self.graph[edge] {
+                InheritanceEdge::Inherits => {
+                    dot.push_str(&format!("    {} -> {};\n", dot_id(contract), dot_id(ancestor)));
+                }
+                InheritanceEdge::Overrides(functions) => {
+                    let label: Vec<String> = functions
+                        .iter()
+                        .map(|f| format!("{contract}.{f}"))
+                        .collect();
+                    dot.push_str(&format!(
+                        "    {} -> {} [style=dashed, label={}];\n",
+                        dot_id(contract),
+                        dot_id(ancestor),
+                        dot_id(&label.join(", "))
+                    ));
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Targets of the outgoing edges of `name` satisfying `filter`, in the
+    /// order the edges were added.
+    fn edges_of(
+        &self,
+        name: &str,
+        filter: impl Fn(&InheritanceEdge) -> bool,
+    ) -> impl Iterator<Item = (&str, &InheritanceEdge)> {
+        let mut edges: Vec<_> = match self.name_to_node.get(name) {
+            Some(&node) => self
+                .graph
+                .edges(node)
+                .filter(|e| filter(e.weight()))
+                .map(|e| (e.id(), self.graph[e.target()].as_str(), e.weight()))
+                .collect(),
+            None => Vec::new(),
+        };
+        // petgraph lists outgoing edges newest first.
+        edges.sort_by_key(|(id, _, _)| *id);
+        edges.into_iter().map(|(_, target, edge)| (target, edge))
+    }
+
+    fn get_or_insert_node(&mut self, name: &str) -> NodeIndex {
+        if let Some(&idx) = self.name_to_node.get(name) {
+            idx
+        } else {
+            let idx = self.graph.add_node(name.to_string());
+            self.name_to_node.insert(name.to_string(), idx);
+            idx
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// SIR helpers
+// ═══════════════════════════════════════════════════════════════════
+
+/// Names and arities of the functions declared in a contract.
+fn functions(contract: &ContractDecl) -> impl Iterator<Item = (&str, usize)> {
+    contract.members.iter().filter_map(|m| match m {
+        MemberDecl::Function(f) => Some((f.name.as_str(), f.params.len())),
+        _ => None,
+    })
+}
+
+/// Ancestors of `contract` declared in the module, breadth first.
+fn ancestors<'a>(
+    contract: &ContractDecl,
+    contracts: &HashMap<&str, &'a ContractDecl>,
+) -> Vec<&'a ContractDecl> {
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+    let mut queue: VecDeque<&str> = contract.parents.iter().map(String::as_str).collect();
+    while let Some(name) = queue.pop_front() {
+        if !seen.insert(name) {
+            continue;
+        }
+        if let Some(&ancestor) = contracts.get(name) {
+            result.push(ancestor);
+            queue.extend(ancestor.parents.iter().map(String::as_str));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::defs::*;
+
+    fn make_function(name: &str) -> MemberDecl {
+        MemberDecl::Function(FunctionDecl::new(name.to_string(), vec![], vec![], None, None))
+    }
+
+    fn make_contract(name: &str, parents: &[&str], functions: &[&str]) -> Decl {
+        Decl::Contract(ContractDecl {
+            name: name.into(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            attrs: vec![],
+            members: functions.iter().map(|f| make_function(f)).collect(),
+            span: None,
+        })
+    }
+
+    /// Diamond: `D is B, C`, `B is A`, `C is A`, all defining `f`.
+    fn diamond() -> Module {
+        Module {
+            id: "test".into(),
+            attrs: vec![],
+            decls: vec![
+                make_contract("A", &[], &["f"]),
+                make_contract("B", &["A"], &["f"]),
+                make_contract("C", &["A"], &["f", "g"]),
+                make_contract("D", &["B", "C"], &["f", "g"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_diamond_inheritance_graph() {
+        let ig = InheritanceGraph::build(&diamond());
+
+        assert_eq!(ig.contract_count(), 4);
+        assert_eq!(ig.parents_of("D"), vec!["B", "C"]);
+        assert!(ig.parents_of("A").is_empty());
+        let f = vec!["f".to_string()];
+        let fg = vec!["f".to_string(), "g".to_string()];
+        assert_eq!(
+            ig.overrides_of("D"),
+            vec![
+                ("B", f.as_slice()),
+                ("C", fg.as_slice()),
+                ("A", f.as_slice())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diamond_linearization() {
+        let ig = InheritanceGraph::build(&diamond());
+
+        assert_eq!(
+            ig.linearization("D"),
+            Some(
+                vec!["D", "C", "B", "A"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            )
+        );
+        assert_eq!(ig.linearization("A"), Some(vec!["A".to_string()]));
+        assert_eq!(ig.linearization("E"), None);
+
+        // `Y is X, Z` with `Z is X`: `X` must come after `Z`, contradicting
+        // the order of the bases of `Y`.
+        let module = Module {
+            id: "test".into(),
+            attrs: vec![],
+            decls: vec![
+                make_contract("X", &[], &[]),
+                make_contract("Z", &["X"], &[]),
+                make_contract("Y", &["Z", "X"], &[]),
+            ],
+        };
+        assert_eq!(InheritanceGraph::build(&module).linearization("Y"), None);
+    }
+
+    #[test]
+    fn test_inheritance_graph_to_dot() {
+        let module = Module {
+            id: "test".into(),
+            attrs: vec![],
+            decls: vec![
+                make_contract("A", &[], &["f"]),
+                make_contract("B", &["A"], &["f"]),
+            ],
+        };
+
+        let dot = InheritanceGraph::build(&module).to_dot();
+        assert!(dot.starts_with("digraph InheritanceGraph {"));
+        assert!(dot.contains("    \"B\" -> \"A\";\n"));
+        assert!(dot.contains("    \"B\" -> \"A\" [style=dashed, label=\"B.f\"];\n"));
+    }
+}
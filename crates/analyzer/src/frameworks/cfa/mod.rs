@@ -14,8 +14,10 @@
 //! - [`loops`] — natural-loop detection from the dominator tree
 //! - [`reachability`] — BFS/DFS reachability queries over function CFGs
 //! - [`callgraph`] — inter-procedural call graph built from SIR call sites
+//! - [`inheritance`] — contract inheritance and override graph built from SIR
 
 pub mod callgraph;
 pub mod domtree;
+pub mod inheritance;
 pub mod loops;
 pub mod reachability;
@@ -21,6 +21,15 @@ pub trait Lattice: Clone + Eq + Debug + Send + Sync {
     /// Partial order check: self ⊑ other
     fn less_or_equal(&self, other: &Self) -> bool;
 
+    /// Widening operation (∇) - an upper bound of both facts that makes
+    /// ascending chains finite. The solver uses it instead of `join` at the
+    /// targets of back-edges once a block has been visited `iteration`
+    /// times. The default joins, which suffices for lattices of finite
+    /// height.
+    fn widen(&self, other: &Self, _iteration: usize) -> Self {
+        self.join(other)
+    }
+
     /// Check if this is the bottom element
     fn is_bottom(&self) -> bool {
         self == &Self::bottom()
@@ -166,6 +175,16 @@ impl<K: Clone + Eq + Hash + Send + Sync + Debug, V: Lattice> Lattice for MapLatt
         Self { map: result }
     }
 
+    fn widen(&self, other: &Self, iteration: usize) -> Self {
+        let mut result = self.join(other);
+        for (k, v) in result.map.iter_mut() {
+            if let (Some(old), Some(new)) = (self.map.get(k), other.map.get(k)) {
+                *v = old.widen(new, iteration);
+            }
+        }
+        result
+    }
+
     fn meet(&self, other: &Self) -> Self {
         let mut result = HashMap::new();
 
@@ -275,6 +294,13 @@ impl<A: Lattice, B: Lattice> Lattice for ProductLattice<A, B> {
         Self { first: self.first.join(&other.first), second: self.second.join(&other.second) }
     }
 
+    fn widen(&self, other: &Self, iteration: usize) -> Self {
+        Self {
+            first: self.first.widen(&other.first, iteration),
+            second: self.second.widen(&other.second, iteration),
+        }
+    }
+
     fn meet(&self, other: &Self) -> Self {
         Self { first: self.first.meet(&other.first), second: self.second.meet(&other.second) }
     }
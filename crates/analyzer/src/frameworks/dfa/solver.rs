@@ -65,17 +65,29 @@ impl<L: Lattice> DataFlowResult<L> {
 }
 
 /// Generic worklist-based data flow solver
+///
+/// Facts flowing along back-edges (loops) are combined with
+/// [`Lattice::widen`] instead of [`Lattice::join`] once the block has been
+/// visited more than the widening threshold, so that lattices of unbounded
+/// height still reach a fixpoint.
 pub struct DataFlowSolver<L: Lattice, T: Transfer<L>> {
     direction: Direction,
     transfer: T,
     max_iterations: usize,
+    widening_threshold: usize,
     _phantom: PhantomData<L>,
 }
 
 impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
     /// Create a new solver
     pub fn new(direction: Direction, transfer: T) -> Self {
-        Self { direction, transfer, max_iterations: 1000, _phantom: PhantomData }
+        Self {
+            direction,
+            transfer,
+            max_iterations: 1000,
+            widening_threshold: 3,
+            _phantom: PhantomData,
+        }
     }
 
     /// Set maximum iterations before giving up
@@ -84,6 +96,13 @@ impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
         self
     }
 
+    /// Set the number of visits of a block after which facts flowing
+    /// along its back-edges are widened
+    pub fn with_widening_threshold(mut self, threshold: usize) -> Self {
+        self.widening_threshold = threshold;
+        self
+    }
+
     /// Solve data flow equations on CFG
     pub fn solve(&self, cfg: &ControlFlowGraph, initial: L) -> DataFlowResult<L> {
         match self.direction {
@@ -109,9 +128,14 @@ impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
         // Worklist algorithm using reverse postorder for efficiency
         let mut worklist: VecDeque<BasicBlockId> = cfg.reverse_postorder.iter().copied().collect();
         let mut iterations = 0;
+        let is_back_edge = back_edges(cfg);
+        let mut visits: HashMap<BasicBlockId, usize> = HashMap::new();
 
         while let Some(block_id) = worklist.pop_front() {
             iterations += 1;
+            let visit = visits.entry(block_id).or_default();
+            *visit += 1;
+            let visit = *visit;
 
             if iterations > self.max_iterations {
                 return DataFlowResult { block_entry, block_exit, iterations, converged: false };
@@ -132,6 +156,14 @@ impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
                         result = result.join(pred_exit);
                     }
                 }
+                let widen = visit > self.widening_threshold
+                    && block
+                        .predecessors
+                        .iter()
+                        .any(|&pred| is_back_edge(pred, block_id));
+                if widen {
+                    result = block_entry[&block_id].widen(&result, visit);
+                }
                 result
             };
 
@@ -178,9 +210,14 @@ impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
         let mut worklist: VecDeque<BasicBlockId> =
             cfg.reverse_postorder.iter().rev().copied().collect();
         let mut iterations = 0;
+        let is_back_edge = back_edges(cfg);
+        let mut visits: HashMap<BasicBlockId, usize> = HashMap::new();
 
         while let Some(block_id) = worklist.pop_front() {
             iterations += 1;
+            let visit = visits.entry(block_id).or_default();
+            *visit += 1;
+            let visit = *visit;
 
             if iterations > self.max_iterations {
                 return DataFlowResult { block_entry, block_exit, iterations, converged: false };
@@ -201,6 +238,14 @@ impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
                         result = result.join(succ_entry);
                     }
                 }
+                let widen = visit > self.widening_threshold
+                    && block
+                        .successors
+                        .iter()
+                        .any(|&succ| is_back_edge(block_id, succ));
+                if widen {
+                    result = block_exit[&block_id].widen(&result, visit);
+                }
                 result
             };
 
@@ -228,11 +273,27 @@ impl<L: Lattice, T: Transfer<L>> DataFlowSolver<L, T> {
     }
 }
 
+/// Back-edge test of a CFG: an edge is a back-edge if it does not go
+/// forward in reverse postorder. Every cycle contains one.
+fn back_edges(cfg: &ControlFlowGraph) -> impl Fn(BasicBlockId, BasicBlockId) -> bool {
+    let order: HashMap<BasicBlockId, usize> = cfg
+        .reverse_postorder
+        .iter()
+        .enumerate()
+        .map(|(i, &id)| (id, i))
+        .collect();
+    move |from, to| match (order.get(&from), order.get(&to)) {
+        (Some(from), Some(to)) => from >= to,
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::frameworks::dfa::annotated_cfg::Terminator;
     use crate::frameworks::dfa::lattice::PowerSetLattice;
+    use scirs::sir::{Expr, Type, VarExpr};
 
     struct TestTransfer;
 
@@ -268,4 +329,90 @@ mod tests {
         assert!(result.converged);
         assert!(result.iterations > 0);
     }
+
+    /// Natural numbers ordered by `<=`, with `u64::MAX` as top: a lattice
+    /// of (practically) unbounded height.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Height(u64);
+
+    impl Lattice for Height {
+        fn bottom() -> Self {
+            Height(0)
+        }
+
+        fn top() -> Self {
+            Height(u64::MAX)
+        }
+
+        fn join(&self, other: &Self) -> Self {
+            Height(self.0.max(other.0))
+        }
+
+        fn meet(&self, other: &Self) -> Self {
+            Height(self.0.min(other.0))
+        }
+
+        fn less_or_equal(&self, other: &Self) -> bool {
+            self.0 <= other.0
+        }
+
+        fn widen(&self, other: &Self, _iteration: usize) -> Self {
+            if other.0 > self.0 { Self::top() } else { *self }
+        }
+    }
+
+    /// Increments the fact once per block.
+    struct Increment;
+
+    impl Transfer<Height> for Increment {
+        fn transfer_stmt(&self, _stmt: &Stmt, fact: &Height) -> Height {
+            *fact
+        }
+
+        fn transfer_block(&self, _block: &BasicBlock, fact: Height, _: Direction) -> Height {
+            Height(fact.0.saturating_add(1))
+        }
+    }
+
+    /// `0 -> 1 (loop head) -> 2 -> 1`, `1 -> 3 (return)`
+    fn loop_cfg() -> ControlFlowGraph {
+        let mut cfg = ControlFlowGraph::new("test".to_string(), BasicBlockId(0));
+        let branch = Terminator::Branch {
+            condition: Expr::Var(VarExpr::new("c".to_string(), Type::Bool, None)),
+            true_block: BasicBlockId(2),
+            false_block: BasicBlockId(3),
+        };
+        let blocks = [
+            BasicBlock::new(BasicBlockId(0), Terminator::Jump(BasicBlockId(1))),
+            BasicBlock::new(BasicBlockId(1), branch),
+            BasicBlock::new(BasicBlockId(2), Terminator::Jump(BasicBlockId(1))),
+            BasicBlock::new(BasicBlockId(3), Terminator::Return),
+        ];
+        for mut block in blocks {
+            block.compute_successors();
+            cfg.add_block(block);
+        }
+        cfg.compute_metadata();
+        cfg
+    }
+
+    #[test]
+    fn test_widening_terminates_on_tall_lattice() {
+        let cfg = loop_cfg();
+
+        // Without widening the counter climbs until the iteration limit.
+        let solver =
+            DataFlowSolver::new(Direction::Forward, Increment).with_widening_threshold(usize::MAX);
+        assert!(!solver.solve(&cfg, Height(0)).converged);
+
+        let solver = DataFlowSolver::new(Direction::Forward, Increment);
+        let result = solver.solve(&cfg, Height(0));
+        assert!(result.converged);
+        assert!(result.iterations < 20);
+        assert_eq!(result.entry(BasicBlockId(1)), Some(&Height::top()));
+
+        let result = DataFlowSolver::new(Direction::Backward, Increment).solve(&cfg, Height(0));
+        assert!(result.converged);
+        assert_eq!(result.exit(BasicBlockId(1)), Some(&Height::top()));
+    }
 }
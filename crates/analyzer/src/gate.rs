@@ -0,0 +1,210 @@
+//! Pass/fail gating of analysis reports.
+//!
+//! A [`GatePolicy`] limits how many findings a report may contain, per
+//! severity and per detector. [`AnalysisReport::ci_gate`] checks a report
+//! against a policy and returns a [`GateResult`] listing the exceeded limits
+//! and the findings counted by them, so that every front end implements
+//! `--fail-on` style options the same way.
+//!
+//! [`AnalysisReport::ci_gate`]: crate::output::AnalysisReport::ci_gate
+
+use crate::detectors::base::registry::{DetectorRegistry, register_all_detectors};
+use bugs::bug::{Bug, RiskLevel};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Severities from the most to the least severe.
+const SEVERITIES: [RiskLevel; 5] = [
+    RiskLevel::Critical,
+    RiskLevel::High,
+    RiskLevel::Medium,
+    RiskLevel::Low,
+    RiskLevel::No,
+];
+
+/// Limits on the number of findings of a report. Severities and detectors
+/// without a limit are not gated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GatePolicy {
+    /// Most findings allowed of each severity.
+    pub max_by_severity: HashMap<RiskLevel, usize>,
+
+    /// Most findings allowed of each detector, keyed by detector name or ID.
+    pub max_by_detector: BTreeMap<String, usize>,
+}
+
+impl GatePolicy {
+    /// A policy without limits, which every report passes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy failing on any finding of `level` or a higher severity.
+    pub fn fail_on(level: RiskLevel) -> Self {
+        let mut policy = Self::new();
+        for severity in SEVERITIES {
+            policy.max_by_severity.insert(severity, 0);
+            if severity == level {
+                break;
+            }
+        }
+        policy
+    }
+
+    /// Allow at most `max` findings of severity `level`.
+    pub fn max_severity(mut self, level: RiskLevel, max: usize) -> Self {
+        self.max_by_severity.insert(level, max);
+        self
+    }
+
+    /// Allow at most `max` findings of `detector`, given by name or ID.
+    pub fn max_detector(mut self, detector: impl Into<String>, max: usize) -> Self {
+        self.max_by_detector.insert(detector.into(), max);
+        self
+    }
+
+    /// Check `bugs` against the limits of this policy.
+    pub fn check(&self, bugs: &[Bug]) -> GateResult {
+        let mut registry = DetectorRegistry::new();
+        register_all_detectors(&mut registry);
+
+        let mut violations = vec![];
+        let mut violating = BTreeSet::new();
+        let mut check_limit = |rule: String, limit: usize, matches: &dyn Fn(&Bug) -> bool| {
+            let counted: Vec<usize> = (0..bugs.len()).filter(|&i| matches(&bugs[i])).collect();
+            if counted.len() > limit {
+                violations.push(GateViolation { rule, limit, found: counted.len() });
+                violating.extend(counted);
+            }
+        };
+
+        for severity in SEVERITIES {
+            if let Some(&limit) = self.max_by_severity.get(&severity) {
+                check_limit(severity.as_str().to_string(), limit, &|bug| {
+                    bug.risk_level == severity
+                });
+            }
+        }
+        for (detector, &limit) in &self.max_by_detector {
+            let name = registry.get(detector).map(|d| d.name().to_string());
+            check_limit(detector.clone(), limit, &|bug| {
+                &bug.name == detector || name.as_ref() == Some(&bug.name)
+            });
+        }
+
+        GateResult {
+            passed: violations.is_empty(),
+            violations,
+            findings: violating.into_iter().map(|i| bugs[i].clone()).collect(),
+        }
+    }
+}
+
+/// A limit of a [`GatePolicy`] that a report exceeds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateViolation {
+    /// The limited severity (e.g. `High`) or detector.
+    pub rule: String,
+
+    /// Most findings allowed.
+    pub limit: usize,
+
+    /// Findings in the report.
+    pub found: usize,
+}
+
+/// Outcome of checking a report against a [`GatePolicy`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GateResult {
+    /// Whether the report is within every limit.
+    pub passed: bool,
+
+    /// The exceeded limits.
+    pub violations: Vec<GateViolation>,
+
+    /// Findings counted by an exceeded limit, in report order.
+    pub findings: Vec<Bug>,
+}
+
+impl GateResult {
+    /// Process exit status for the result: 0 when passed, 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        if self.passed { 0 } else { 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::AnalysisReport;
+    use bugs::bug::{BugCategory, BugKind};
+    use common::loc::Loc;
+    use std::time::Duration;
+
+    fn bug(name: &str, risk: RiskLevel) -> Bug {
+        Bug::new(
+            name,
+            None,
+            Loc::new(1, 1, 1, 1),
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            risk,
+            vec![],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_severity_limits() {
+        let bugs = vec![
+            bug("Reentrancy", RiskLevel::High),
+            bug("Dangerous use of tx.origin", RiskLevel::High),
+            bug("Floating Pragma", RiskLevel::No),
+        ];
+        let report = AnalysisReport::new(bugs, vec![], Duration::ZERO);
+
+        let result = report.ci_gate(&GatePolicy::fail_on(RiskLevel::High));
+        assert!(!result.passed);
+        assert_eq!(result.exit_code(), 1);
+        assert_eq!(
+            result.violations,
+            vec![GateViolation { rule: "High".to_string(), limit: 0, found: 2 }]
+        );
+        assert_eq!(result.findings.len(), 2);
+
+        let result = report.ci_gate(&GatePolicy::new().max_severity(RiskLevel::High, 2));
+        assert!(result.passed);
+        assert!(result.findings.is_empty());
+    }
+
+    #[test]
+    fn test_report_exit_code() {
+        let report = AnalysisReport::new(
+            vec![
+                bug("Reentrancy", RiskLevel::Medium),
+                bug("Floating Pragma", RiskLevel::No),
+            ],
+            vec![],
+            Duration::ZERO,
+        );
+        assert_eq!(report.exit_code(RiskLevel::High), 0);
+        assert_eq!(report.exit_code(RiskLevel::Medium), 1);
+        assert_eq!(report.exit_code(RiskLevel::Low), 1);
+    }
+
+    #[test]
+    fn test_detector_limits() {
+        let report = AnalysisReport::new(
+            vec![
+                bug("Reentrancy", RiskLevel::High),
+                bug("Dangerous use of tx.origin", RiskLevel::Medium),
+            ],
+            vec![],
+            Duration::ZERO,
+        );
+        let result = report.ci_gate(&GatePolicy::new().max_detector("tx-origin", 0));
+        assert!(!result.passed);
+        assert_eq!(result.findings[0].name, "Dangerous use of tx.origin");
+    }
+}
@@ -0,0 +1,672 @@
+//! Incremental project compilation and analysis.
+//!
+//! Compiling a project runs Solc once per source file, and each run also
+//! compiles everything the file imports. [`BuildCache`] keeps the Solc output
+//! of every file on disk, keyed by a fingerprint of the file and of all files
+//! it transitively imports. On the next run only files whose fingerprint
+//! changed are recompiled: editing a file recompiles it and its importers,
+//! while the cached output of every other file is reused.
+//!
+//! [`FindingsCache`] does the same for the findings of each file, together
+//! with the other results of analyzing it (see [`CachedFindings`]), so that
+//! unchanged files are neither compiled nor analyzed again. Since a contract
+//! can only inherit from contracts it imports, the import closure also
+//! covers changes to base contracts.
+//!
+//! Long-running services can observe the caches through [`CacheStats`] and
+//! bound them with [`BuildCache::invalidate`], [`BuildCache::clear`],
+//! [`FindingsCache::invalidate`] and [`FindingsCache::clear`].
+
+use crate::passes::sir::ContractMetrics;
+use bugs::bug::Bug;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Name of the index file inside the cache directory.
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Name of the index file of cached findings inside the cache directory.
+const FINDINGS_MANIFEST_FILE: &str = "findings.json";
+
+/// Results of analyzing one input file, as stored in a [`FindingsCache`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CachedFindings {
+    /// Findings of the file.
+    pub bugs: Vec<Bug>,
+    /// Version of the compiler the file was compiled with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_version: Option<String>,
+    /// Size and complexity metrics of each contract, keyed by name.
+    #[serde(default)]
+    pub contract_metrics: BTreeMap<String, ContractMetrics>,
+}
+
+/// Solc output of one input file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledFile {
+    /// Combined JSON output of Solc.
+    pub json: String,
+    /// Version of Solc that produced the output.
+    pub solc_version: String,
+}
+
+/// Paths of the `import` directives of a Solidity source, in order. All
+/// forms are recognized: `import "a.sol";`, `import "a.sol" as A;`,
+/// `import {X} from "a.sol";` and `import * as A from "a.sol";`.
+pub fn parse_imports(source: &str) -> Vec<String> {
+    let import_re =
+        Regex::new(r#"(?m)^\s*import\s+(?:[^;"']*?\s+from\s+)?["']([^"']+)["']"#).unwrap();
+    import_re
+        .captures_iter(source)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Resolves import paths to files the way Solc does for a project: relative
+/// imports against the importing file, other imports through the remappings
+/// and then against the base path and include paths.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResolver {
+    base_path: Option<PathBuf>,
+    include_paths: Vec<PathBuf>,
+    /// Import remappings as `(prefix, target)` pairs.
+    remappings: Vec<(String, String)>,
+}
+
+impl ImportResolver {
+    pub fn new(base_path: Option<&str>, include_paths: &[String], remappings: &[String]) -> Self {
+        Self {
+            base_path: base_path.map(PathBuf::from),
+            include_paths: include_paths.iter().map(PathBuf::from).collect(),
+            remappings: remappings
+                .iter()
+                .filter_map(|r| r.split_once('='))
+                .map(|(prefix, target)| (prefix.to_string(), target.to_string()))
+                .collect(),
+        }
+    }
+
+    /// The file `import` refers to from `importer`, if it exists.
+    pub fn resolve(&self, importer: &Path, import: &str) -> Option<PathBuf> {
+        if import.starts_with("./") || import.starts_with("../") {
+            let dir = importer.parent().unwrap_or(Path::new("."));
+            return fs::canonicalize(dir.join(import)).ok();
+        }
+
+        let import = self
+            .remappings
+            .iter()
+            .find(|(prefix, _)| import.starts_with(prefix.as_str()))
+            .map(|(prefix, target)| format!("{target}{}", &import[prefix.len()..]))
+            .unwrap_or_else(|| import.to_string());
+
+        let roots = self.base_path.iter().chain(&self.include_paths);
+        std::iter::once(PathBuf::from(&import))
+            .chain(roots.map(|root| root.join(&import)))
+            .find_map(|path| fs::canonicalize(path).ok())
+    }
+}
+
+/// Content hashes and resolved imports of the source files read during a
+/// run.
+struct SourceGraph {
+    resolver: ImportResolver,
+    sources: HashMap<PathBuf, (String, Vec<PathBuf>)>,
+}
+
+impl SourceGraph {
+    fn new(resolver: ImportResolver) -> Self {
+        Self { resolver, sources: HashMap::new() }
+    }
+
+    /// Hash of the contents of `file` and of every file it transitively
+    /// imports. Unresolvable imports are ignored.
+    fn fingerprint(&mut self, file: &Path) -> String {
+        let root = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        let mut closure = BTreeMap::new();
+        let mut worklist = vec![root];
+        while let Some(path) = worklist.pop() {
+            if closure.contains_key(&path) {
+                continue;
+            }
+            let (hash, imports) = self.source(&path);
+            worklist.extend(imports);
+            closure.insert(path, hash);
+        }
+
+        let mut keccak = Keccak::v256();
+        for (path, hash) in &closure {
+            keccak.update(path.to_string_lossy().as_bytes());
+            keccak.update(hash.as_bytes());
+        }
+        finalize(keccak)
+    }
+
+    /// Content hash and resolved imports of `path`, read once per run.
+    fn source(&mut self, path: &Path) -> (String, Vec<PathBuf>) {
+        if let Some(source) = self.sources.get(path) {
+            return source.clone();
+        }
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let mut keccak = Keccak::v256();
+        keccak.update(content.as_bytes());
+        let imports = parse_imports(&content)
+            .iter()
+            .filter_map(|import| self.resolver.resolve(path, import))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let source = (finalize(keccak), imports);
+        self.sources.insert(path.to_path_buf(), source.clone());
+        source
+    }
+
+    /// Forget the content of `file` read during this run.
+    fn forget(&mut self, file: &Path) {
+        let root = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        self.sources.remove(&root);
+    }
+}
+
+/// Usage and size of a [`BuildCache`] or [`FindingsCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Files whose cached output was reused since the cache was opened.
+    pub hits: usize,
+    /// Files that had to be compiled since the cache was opened.
+    pub misses: usize,
+    /// Files with a cached output.
+    pub entries: usize,
+    /// Size on disk of the cached outputs, in bytes.
+    pub size_bytes: u64,
+}
+
+/// Cache entry of one input file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// Fingerprint of the file's import closure when it was compiled. Also
+    /// the name (without extension) of the file holding the Solc output.
+    fingerprint: String,
+    solc_version: String,
+}
+
+/// Index of the cache directory, keyed by input file path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// Compiler settings the entries were produced with.
+    settings: String,
+    entries: BTreeMap<String, ManifestEntry>,
+}
+
+/// On-disk cache of Solc outputs, invalidated along the import graph.
+pub struct BuildCache {
+    dir: PathBuf,
+    graph: SourceGraph,
+    manifest: Manifest,
+    hits: usize,
+    misses: usize,
+}
+
+impl BuildCache {
+    /// Open the cache stored in `dir`. `settings` describes the compiler
+    /// configuration (version constraint, paths, remappings); outputs cached
+    /// under different settings are discarded, as is a missing or unreadable
+    /// cache.
+    pub fn open(dir: &Path, resolver: ImportResolver, settings: &str) -> Self {
+        let manifest = fs::read_to_string(dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_str::<Manifest>(&data).ok())
+            .filter(|manifest| manifest.settings == settings)
+            .unwrap_or_else(|| Manifest { settings: settings.to_string(), ..Manifest::default() });
+        Self {
+            dir: dir.to_path_buf(),
+            graph: SourceGraph::new(resolver),
+            manifest,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Hit and miss counts since the cache was opened, and the current
+    /// number and size of cached outputs.
+    pub fn stats(&self) -> CacheStats {
+        let fingerprints: BTreeSet<&str> = self
+            .manifest
+            .entries
+            .values()
+            .map(|e| e.fingerprint.as_str())
+            .collect();
+        let size_bytes = fingerprints
+            .iter()
+            .filter_map(|fp| fs::metadata(self.artifact_path(fp)).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.manifest.entries.len(),
+            size_bytes,
+        }
+    }
+
+    /// Drop the cached output of `file`, so that the next compilation of it
+    /// runs Solc again, and forget its content read during this run.
+    /// Returns whether an output was cached. Files importing `file` keep
+    /// their outputs unless its content changed.
+    pub fn invalidate(&mut self, file: &Path) -> std::io::Result<bool> {
+        self.graph.forget(file);
+        let Some(old) = self
+            .manifest
+            .entries
+            .remove(file.to_string_lossy().as_ref())
+        else {
+            return Ok(false);
+        };
+        self.remove_unused_artifact(&old.fingerprint);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Drop every cached output and every file content read during this
+    /// run. Hit and miss counts are kept.
+    pub fn clear(&mut self) -> std::io::Result<()> {
+        let entries = std::mem::take(&mut self.manifest.entries);
+        for entry in entries.values() {
+            self.remove_unused_artifact(&entry.fingerprint);
+        }
+        self.graph.sources.clear();
+        self.save()
+    }
+
+    /// Return the Solc output of each of `files`, calling `compile` only for
+    /// files without a cached output for their current import closure.
+    /// Failed compilations are not cached.
+    pub fn compile<F, E>(
+        &mut self,
+        files: &[PathBuf],
+        mut compile: F,
+    ) -> Vec<(PathBuf, Result<CompiledFile, E>)>
+    where
+        F: FnMut(&Path) -> Result<CompiledFile, E>,
+    {
+        let mut results = vec![];
+        for file in files {
+            let key = file.to_string_lossy().to_string();
+            let fingerprint = self.graph.fingerprint(file);
+
+            if let Some(cached) = self.load(&key, &fingerprint) {
+                log::debug!("Reusing cached compilation of {}", key);
+                self.hits += 1;
+                results.push((file.clone(), Ok(cached)));
+                continue;
+            }
+
+            self.misses += 1;
+            let result = compile(file);
+            match &result {
+                Ok(compiled) => self.store(key, fingerprint, compiled),
+                Err(_) => {
+                    self.manifest.entries.remove(&key);
+                }
+            }
+            results.push((file.clone(), result));
+        }
+
+        if let Err(err) = self.save() {
+            log::warn!("Failed to write build cache {}: {}", self.dir.display(), err);
+        }
+        results
+    }
+
+    fn artifact_path(&self, fingerprint: &str) -> PathBuf {
+        self.dir.join(format!("{fingerprint}.json"))
+    }
+
+    fn load(&self, key: &str, fingerprint: &str) -> Option<CompiledFile> {
+        let entry = self.manifest.entries.get(key)?;
+        if entry.fingerprint != fingerprint {
+            return None;
+        }
+        let json = fs::read_to_string(self.artifact_path(fingerprint)).ok()?;
+        Some(CompiledFile { json, solc_version: entry.solc_version.clone() })
+    }
+
+    fn store(&mut self, key: String, fingerprint: String, compiled: &CompiledFile) {
+        if let Err(err) = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(self.artifact_path(&fingerprint), &compiled.json))
+        {
+            log::warn!("Failed to cache compilation of {}: {}", key, err);
+            return;
+        }
+        let entry = ManifestEntry { fingerprint, solc_version: compiled.solc_version.clone() };
+        if let Some(old) = self.manifest.entries.insert(key, entry) {
+            self.remove_unused_artifact(&old.fingerprint);
+        }
+    }
+
+    /// Delete the artifact of `fingerprint` unless an entry still uses it.
+    fn remove_unused_artifact(&self, fingerprint: &str) {
+        if !self
+            .manifest
+            .entries
+            .values()
+            .any(|e| e.fingerprint == fingerprint)
+        {
+            let _ = fs::remove_file(self.artifact_path(fingerprint));
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let data = serde_json::to_string_pretty(&self.manifest)?;
+        fs::write(self.dir.join(MANIFEST_FILE), data)
+    }
+}
+
+/// Index of the cached findings, mapping each input file path to the
+/// fingerprint its findings were stored under.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FindingsManifest {
+    /// Detector set and compiler settings the findings were produced with.
+    settings: String,
+    entries: BTreeMap<String, String>,
+}
+
+/// On-disk cache of the findings of each analyzed file, invalidated along
+/// the import graph.
+pub struct FindingsCache {
+    dir: PathBuf,
+    graph: SourceGraph,
+    manifest: FindingsManifest,
+    hits: usize,
+    misses: usize,
+}
+
+impl FindingsCache {
+    /// Open the cache stored in `dir`. `settings` describes everything
+    /// besides the sources that the findings depend on, such as the enabled
+    /// detectors, their parameters and the compiler configuration; findings
+    /// cached under different settings are discarded.
+    pub fn open(dir: &Path, resolver: ImportResolver, settings: &str) -> Self {
+        let manifest = fs::read_to_string(dir.join(FINDINGS_MANIFEST_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_str::<FindingsManifest>(&data).ok())
+            .filter(|manifest| manifest.settings == settings)
+            .unwrap_or_else(|| FindingsManifest {
+                settings: settings.to_string(),
+                ..FindingsManifest::default()
+            });
+        Self {
+            dir: dir.to_path_buf(),
+            graph: SourceGraph::new(resolver),
+            manifest,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Hit and miss counts since the cache was opened, and the current
+    /// number and size of cached findings.
+    pub fn stats(&self) -> CacheStats {
+        let size_bytes = self
+            .manifest
+            .entries
+            .values()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|fp| fs::metadata(self.artifact_path(fp)).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.manifest.entries.len(),
+            size_bytes,
+        }
+    }
+
+    /// Cached findings of `file`, if neither it nor any file it transitively
+    /// imports changed since they were stored.
+    pub fn get(&mut self, file: &Path) -> Option<CachedFindings> {
+        let fingerprint = self.graph.fingerprint(file);
+        let cached = self
+            .manifest
+            .entries
+            .get(file.to_string_lossy().as_ref())
+            .filter(|fp| **fp == fingerprint)
+            .and_then(|fp| fs::read_to_string(self.artifact_path(fp)).ok())
+            .and_then(|data| serde_json::from_str(&data).ok());
+        match cached {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        cached
+    }
+
+    /// Store the findings of `file`, replacing those of an older version.
+    pub fn insert(&mut self, file: &Path, findings: &CachedFindings) {
+        let key = file.to_string_lossy().to_string();
+        let fingerprint = self.graph.fingerprint(file);
+        let stored = serde_json::to_string(findings)
+            .map_err(std::io::Error::from)
+            .and_then(|data| {
+                fs::create_dir_all(&self.dir)?;
+                fs::write(self.artifact_path(&fingerprint), data)
+            });
+        if let Err(err) = stored {
+            log::warn!("Failed to cache findings of {}: {}", key, err);
+            return;
+        }
+        if let Some(old) = self.manifest.entries.insert(key, fingerprint) {
+            self.remove_unused_artifact(&old);
+        }
+    }
+
+    /// Drop the cached findings of `file`, so that the next run analyzes it
+    /// again, and forget its content read during this run. Returns whether
+    /// findings were cached. Files importing `file` keep their findings
+    /// unless its content changed.
+    pub fn invalidate(&mut self, file: &Path) -> std::io::Result<bool> {
+        self.graph.forget(file);
+        let Some(old) = self
+            .manifest
+            .entries
+            .remove(file.to_string_lossy().as_ref())
+        else {
+            return Ok(false);
+        };
+        self.remove_unused_artifact(&old);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Drop all cached findings and every file content read during this
+    /// run. Hit and miss counts are kept.
+    pub fn clear(&mut self) -> std::io::Result<()> {
+        let entries = std::mem::take(&mut self.manifest.entries);
+        for fingerprint in entries.values() {
+            self.remove_unused_artifact(fingerprint);
+        }
+        self.graph.sources.clear();
+        self.save()
+    }
+
+    /// Write the index of the cache to disk.
+    pub fn save(&self) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let data = serde_json::to_string_pretty(&self.manifest)?;
+        fs::write(self.dir.join(FINDINGS_MANIFEST_FILE), data)
+    }
+
+    fn artifact_path(&self, fingerprint: &str) -> PathBuf {
+        self.dir.join(format!("{fingerprint}.findings.json"))
+    }
+
+    /// Delete the findings of `fingerprint` unless an entry still uses them.
+    fn remove_unused_artifact(&self, fingerprint: &str) {
+        if !self.manifest.entries.values().any(|fp| fp == fingerprint) {
+            let _ = fs::remove_file(self.artifact_path(fingerprint));
+        }
+    }
+}
+
+fn finalize(keccak: Keccak) -> String {
+    let mut hash = [0u8; 32];
+    keccak.finalize(&mut hash);
+    hash.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_imports() {
+        let source = r#"
+            pragma solidity ^0.8.0;
+            import "./A.sol";
+            import "./B.sol" as B;
+            import {C, D} from "../lib/C.sol";
+            import * as E from '@oz/E.sol';
+        "#;
+        assert_eq!(parse_imports(source), vec!["./A.sol", "./B.sol", "../lib/C.sol", "@oz/E.sol"]);
+    }
+
+    #[test]
+    fn test_edit_recompiles_file_and_importers() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("Math.sol"), "library Math {}").unwrap();
+        fs::write(src.join("Token.sol"), "import \"./Math.sol\";\ncontract Token {}").unwrap();
+        fs::write(src.join("Vault.sol"), "import {Token} from \"./Token.sol\";").unwrap();
+        fs::write(src.join("Ownable.sol"), "contract Ownable {}").unwrap();
+        let files: Vec<PathBuf> = ["Math.sol", "Ownable.sol", "Token.sol", "Vault.sol"]
+            .iter()
+            .map(|f| src.join(f))
+            .collect();
+
+        // Each run opens the cache afresh, as a new process would.
+        let cache_dir = dir.path().join("cache");
+        let run = || {
+            let mut cache = BuildCache::open(&cache_dir, ImportResolver::default(), "");
+            let mut compiled = vec![];
+            let results = cache.compile(&files, |file| {
+                let name = file.file_name().unwrap().to_string_lossy().to_string();
+                compiled.push(name.clone());
+                Ok::<_, String>(CompiledFile { json: name, solc_version: "0.8.20".into() })
+            });
+            for (file, result) in results {
+                assert_eq!(result.unwrap().json, file.file_name().unwrap().to_string_lossy());
+            }
+            compiled
+        };
+
+        assert_eq!(run(), vec!["Math.sol", "Ownable.sol", "Token.sol", "Vault.sol"]);
+        assert!(run().is_empty());
+
+        fs::write(src.join("Math.sol"), "library Math { uint constant ONE = 1; }").unwrap();
+        assert_eq!(run(), vec!["Math.sol", "Token.sol", "Vault.sol"]);
+        assert!(run().is_empty());
+    }
+
+    #[test]
+    fn test_cache_stats_and_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("Token.sol");
+        fs::write(&file, "contract Token {}").unwrap();
+        let files = vec![file.clone()];
+        let compile = |_: &Path| {
+            Ok::<_, String>(CompiledFile { json: "{}".into(), solc_version: "0.8.20".into() })
+        };
+
+        let mut cache = BuildCache::open(&dir.path().join("cache"), ImportResolver::default(), "");
+        cache.compile(&files, compile);
+        cache.compile(&files, compile);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries, stats.size_bytes), (1, 1, 1, 2));
+
+        assert!(cache.invalidate(&file).unwrap());
+        assert!(!cache.invalidate(&file).unwrap());
+        assert_eq!(cache.stats().entries, 0);
+        cache.compile(&files, compile);
+        assert_eq!(cache.stats().misses, 2);
+
+        cache.clear().unwrap();
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries, stats.size_bytes), (1, 2, 0, 0));
+    }
+
+    #[test]
+    fn test_findings_reused_until_import_changes() {
+        use bugs::bug::{BugCategory, BugKind, RiskLevel};
+        use common::loc::Loc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("Base.sol");
+        let token = dir.path().join("Token.sol");
+        let other = dir.path().join("Other.sol");
+        fs::write(&base, "contract Base {}").unwrap();
+        fs::write(&token, "import \"./Base.sol\";\ncontract Token is Base {}").unwrap();
+        fs::write(&other, "contract Other {}").unwrap();
+        let bug = Bug::new(
+            "Reentrancy",
+            None,
+            Loc::new(2, 1, 2, 20),
+            BugKind::Vulnerability,
+            BugCategory::Reentrancy,
+            RiskLevel::High,
+            vec![],
+            vec![],
+            None,
+        );
+
+        let cache_dir = dir.path().join("cache");
+        let open =
+            |settings: &str| FindingsCache::open(&cache_dir, ImportResolver::default(), settings);
+        let findings = CachedFindings {
+            bugs: vec![bug],
+            compiler_version: Some("0.8.20".to_string()),
+            contract_metrics: BTreeMap::from([("Token".to_string(), ContractMetrics::default())]),
+        };
+        let mut cache = open("v1");
+        assert!(cache.get(&token).is_none());
+        cache.insert(&token, &findings);
+        cache.insert(&other, &CachedFindings::default());
+        cache.save().unwrap();
+
+        // The compiler version and metrics come back with the findings.
+        let mut cache = open("v1");
+        let cached = cache.get(&token).unwrap();
+        assert_eq!(cached.bugs.len(), 1);
+        assert_eq!(cached.compiler_version, findings.compiler_version);
+        assert_eq!(cached.contract_metrics, findings.contract_metrics);
+        assert_eq!(cache.get(&other).map(|f| f.bugs.len()), Some(0));
+        assert_eq!(cache.stats().hits, 2);
+
+        // Editing the base contract invalidates the file inheriting from it.
+        fs::write(&base, "contract Base { uint x; }").unwrap();
+        let mut cache = open("v1");
+        assert!(cache.get(&token).is_none());
+        assert!(cache.get(&other).is_some());
+
+        // Findings of another detector set are discarded.
+        assert!(open("v2").get(&other).is_none());
+
+        // Dropped findings are not reused.
+        assert!(cache.invalidate(&other).unwrap());
+        assert!(!cache.invalidate(&other).unwrap());
+        assert!(cache.get(&other).is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+
+        cache.clear().unwrap();
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries, stats.size_bytes), (1, 2, 0, 0));
+        assert!(open("v1").get(&token).is_none());
+    }
+}
@@ -22,7 +22,22 @@
 //!     `DetectorRegistry`)
 //!   - `scan_adapter`: Wraps `scanner::ScanDetector` → `BugDetectionPass`
 //!   - `bir/`: BIR dataflow detectors
-//! - `output`: Report formatting (JSON, SARIF, Markdown)
+//! - `output`: Report formatting (JSON, SARIF, Markdown) and baselines of
+//!   known findings (`Baseline`)
+//! - `anchor`: Offset-independent finding positions (`anchor_bugs`)
+//! - `reachability`: Function surface reachable from entry contracts
+//!   (`ReachableSurface`)
+//! - `ranking`: Heuristic ordering of findings by severity, confidence and
+//!   reachability (`RankingWeights`)
+//! - `gate`: Per-severity and per-detector limits on findings for CI
+//!   (`GatePolicy`, `GateResult`)
+//! - `project`: Directory analysis entry points (`analyze_directory`,
+//!   `analyze_path`, `analyze_sources`)
+//! - `incremental`: On-disk Solc output and findings caches invalidated
+//!   along the import graph (`BuildCache`, `FindingsCache`,
+//!   `CachedFindings`, `CacheStats`)
+//! - `explain`: Single-detector dry run with a fact trace
+//!   (`explain_detector`)
 
 // CLI entry module
 pub mod cli;
@@ -43,9 +58,30 @@ pub mod pipeline;
 // Output formatting
 pub mod output;
 
+// Code-based finding anchors
+pub mod anchor;
+
+// Findings reachable from the deployed contracts
+pub mod reachability;
+
+// Heuristic ranking of findings
+pub mod ranking;
+
+// Pass/fail gating of reports
+pub mod gate;
+
 // CLI configuration
 pub mod config;
 
+// Project (directory) analysis
+pub mod project;
+
+// Incremental project compilation
+pub mod incremental;
+
+// Detector explanation (dry-run) mode
+pub mod explain;
+
 // Re-export core analysis types for convenience
 pub use crate::context::{AnalysisConfig, AnalysisContext};
 pub use crate::pass_manager::{PassManager, PassManagerConfig};
@@ -55,10 +91,17 @@ pub use crate::passes::base::{AnalysisPass, Pass};
 // Re-export from detectors framework
 pub use detectors::base::registry::{DetectorRegistry, register_all_detectors};
 pub use detectors::{BugDetectionPass, ConfidenceLevel, DetectorId, DetectorResult, create_bug};
-pub use pipeline::{PipelineConfig, PipelineEngine, PipelineResult};
+pub use pipeline::{DetectionObserver, PipelineConfig, PipelineEngine, PipelineResult};
+
+// Re-export the finding taxonomy shared with the scanner
+pub use bugs::bug::{Category, Severity};
 
 // Re-export output types
-pub use config::{Config, InputLanguage, OutputFormat, SeverityFilter};
+pub use config::{Config, InputLanguage, OutputFormat, ProjectConfig, SeverityFilter};
+pub use explain::{DetectorExplanation, TraceKind, TraceStep, explain_detector};
+pub use project::{analyze_directory, analyze_path, analyze_sources};
 pub use output::{
-    AnalysisReport, JsonFormatter, MarkdownFormatter, OutputFormatter, SarifFormatter,
+    AnalysisReport, Baseline, GitLabCodeQualityFormatter, HtmlFormatter, JsonFormatter,
+    LspFormatter, MarkdownFormatter, NdjsonFormatter, OutputFormatter, OutputOptions,
+    SarifFormatter, SummaryFormatter,
 };
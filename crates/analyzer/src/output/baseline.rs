@@ -0,0 +1,177 @@
+//! Baselines of known findings.
+//!
+//! A [`Baseline`] records the findings of a report so that later runs can
+//! report only the new ones with [`AnalysisReport::diff_against`]. Findings
+//! are matched by a fingerprint of the detector, the file and the finding's
+//! [anchor](Bug::anchor), which names the contract, the function and a hash
+//! of the statement rather than line numbers, so that edits elsewhere in the
+//! file do not bring known findings back.
+
+use crate::output::formatter::AnalysisReport;
+use bugs::bug::Bug;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Version of the baseline file format.
+const BASELINE_VERSION: u32 = 1;
+
+/// A known finding of a [`Baseline`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Fingerprint the finding is matched by.
+    pub fingerprint: String,
+
+    /// Name of the detector that reported the finding.
+    pub detector: String,
+
+    /// File of the finding, if known.
+    pub file: Option<String>,
+
+    /// [Anchor](Bug::anchor) of the finding, kept for readability.
+    pub anchor: String,
+}
+
+/// Known findings, stored as a JSON file next to the code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Version of the file format.
+    pub version: u32,
+
+    /// Known findings, sorted by fingerprint.
+    pub findings: Vec<BaselineEntry>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self { version: BASELINE_VERSION, findings: vec![] }
+    }
+}
+
+impl Baseline {
+    /// Create an empty baseline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A baseline of every finding of `report`, including unreachable ones.
+    pub fn from_report(report: &AnalysisReport) -> Self {
+        let findings: BTreeSet<BaselineEntry> = report
+            .bugs
+            .iter()
+            .chain(&report.unreachable)
+            .map(|bug| BaselineEntry {
+                fingerprint: fingerprint(bug),
+                detector: bug.name.clone(),
+                file: bug.loc.file.clone(),
+                anchor: bug.anchor(),
+            })
+            .collect();
+        Self { version: BASELINE_VERSION, findings: findings.into_iter().collect() }
+    }
+
+    /// Whether `bug` is a known finding.
+    pub fn contains(&self, bug: &Bug) -> bool {
+        let fingerprint = fingerprint(bug);
+        self.findings
+            .binary_search_by(|entry| entry.fingerprint.cmp(&fingerprint))
+            .is_ok()
+    }
+
+    /// Parse a baseline from JSON.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let mut baseline: Self = serde_json::from_str(json)?;
+        baseline.findings.sort();
+        Ok(baseline)
+    }
+
+    /// Render the baseline as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Read a baseline file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Ok(Self::from_json(&fs::read_to_string(path)?)?)
+    }
+
+    /// Write the baseline to `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+/// Fingerprint of `bug`: a hash of its detector, file and anchor.
+pub fn fingerprint(bug: &Bug) -> String {
+    let mut keccak = Keccak::v256();
+    for part in [
+        bug.name.as_str(),
+        bug.loc.file.as_deref().unwrap_or_default(),
+    ] {
+        keccak.update(part.as_bytes());
+        keccak.update(b"\n");
+    }
+    keccak.update(bug.anchor().as_bytes());
+    let mut hash = [0u8; 32];
+    keccak.finalize(&mut hash);
+    hash[..16].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bugs::bug::{BugCategory, BugKind, RiskLevel, SourceAnchor};
+    use common::loc::Loc;
+    use std::time::Duration;
+
+    /// A finding of `name` in `Vault::withdraw()` at `line`, on the
+    /// statement hashed as `statement`.
+    fn bug(name: &str, line: usize, statement: &str) -> Bug {
+        let mut bug = Bug::new(
+            name,
+            None,
+            Loc::new(line, 1, line, 20).with_file("Vault.sol".to_string()),
+            BugKind::Vulnerability,
+            BugCategory::Reentrancy,
+            RiskLevel::High,
+            vec![],
+            vec![],
+            None,
+        );
+        bug.source_anchor = Some(SourceAnchor {
+            contract: "Vault".to_string(),
+            function: Some("withdraw()".to_string()),
+            statement: Some(statement.to_string()),
+        });
+        bug
+    }
+
+    #[test]
+    fn test_diff_against_baseline() {
+        let old = AnalysisReport::new(vec![bug("Reentrancy", 10, "a1")], vec![], Duration::ZERO);
+        let baseline = Baseline::from_json(&Baseline::from_report(&old).to_json()).unwrap();
+
+        // The known finding moved down by five lines; the other one is new.
+        let new = AnalysisReport::new(
+            vec![bug("Reentrancy", 15, "a1"), bug("Reentrancy", 20, "b2")],
+            vec![],
+            Duration::ZERO,
+        );
+        let diff = new.diff_against(&baseline);
+        assert_eq!(diff.total_bugs(), 1);
+        assert_eq!(diff.bugs[0].loc.start_line, 20);
+        assert_eq!(diff.stats.bugs_by_severity.high, 1);
+    }
+
+    #[test]
+    fn test_baseline_distinguishes_detectors() {
+        let report =
+            AnalysisReport::new(vec![bug("Reentrancy", 10, "a1")], vec![], Duration::ZERO);
+        let baseline = Baseline::from_report(&report);
+        assert!(baseline.contains(&bug("Reentrancy", 12, "a1")));
+        assert!(!baseline.contains(&bug("Unchecked Call", 10, "a1")));
+    }
+}
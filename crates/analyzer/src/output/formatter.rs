@@ -1,6 +1,14 @@
 //! Output formatter trait.
 
-use bugs::bug::Bug;
+use crate::detectors::ConfidenceLevel;
+use crate::gate::{GatePolicy, GateResult};
+use crate::output::baseline::Baseline;
+use crate::passes::sir::ContractMetrics;
+use crate::ranking::{self, ConfidenceTable, RankingWeights};
+use crate::reachability::ReachableSurface;
+use bugs::bug::{Bug, Severity};
+use scirs::sir::Module;
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
 /// Analysis report containing all findings.
@@ -23,6 +31,30 @@ pub struct AnalysisReport {
     /// Source language ("solidity" or "vyper")
     pub source_language: String,
 
+    /// Compiler version that compiled each analyzed file, keyed by path.
+    /// Files whose compiler version is unknown have no entry.
+    pub compiler_versions: BTreeMap<String, String>,
+
+    /// Files whose analysis exceeded the per-file timeout. Their findings
+    /// are partial.
+    pub timed_out: BTreeSet<String>,
+
+    /// Files that failed to compile, with the compiler's error, keyed by
+    /// path. They are left out of the analysis.
+    pub compile_errors: BTreeMap<String, String>,
+
+    /// Size and complexity metrics of each analyzed contract, keyed by
+    /// contract name.
+    pub contract_metrics: BTreeMap<String, ContractMetrics>,
+
+    /// Wall-clock time of each analysis pass, keyed `pass:NAME`, and of each
+    /// detector, keyed `detector:NAME`.
+    pub timing: BTreeMap<String, Duration>,
+
+    /// Findings set apart by [`reachable_findings`](Self::reachable_findings)
+    /// because no entry contract can reach them. Empty otherwise.
+    pub unreachable: Vec<Bug>,
+
     /// Statistics
     pub stats: AnalysisStats,
 }
@@ -41,6 +73,9 @@ pub struct AnalysisStats {
 
     /// Bugs by severity
     pub bugs_by_severity: BugsBySeverity,
+
+    /// Bugs by detector name
+    pub bugs_by_detector: BTreeMap<String, usize>,
 }
 
 /// Bug counts by severity.
@@ -68,7 +103,7 @@ impl AnalysisReport {
     ) -> Self {
         let mut stats = AnalysisStats::default();
 
-        // Count bugs by severity
+        // Count bugs by severity and detector
         for bug in &bugs {
             match bug.risk_level {
                 bugs::bug::RiskLevel::Critical => stats.bugs_by_severity.critical += 1,
@@ -77,6 +112,7 @@ impl AnalysisReport {
                 bugs::bug::RiskLevel::Low => stats.bugs_by_severity.low += 1,
                 bugs::bug::RiskLevel::No => stats.bugs_by_severity.info += 1,
             }
+            *stats.bugs_by_detector.entry(bug.name.clone()).or_default() += 1;
         }
 
         Self {
@@ -86,6 +122,12 @@ impl AnalysisReport {
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: chrono::Utc::now(),
             source_language: source_language.to_string(),
+            compiler_versions: BTreeMap::new(),
+            timed_out: BTreeSet::new(),
+            compile_errors: BTreeMap::new(),
+            contract_metrics: BTreeMap::new(),
+            timing: BTreeMap::new(),
+            unreachable: vec![],
             stats,
         }
     }
@@ -104,6 +146,101 @@ impl AnalysisReport {
     pub fn has_high_severity(&self) -> bool {
         self.stats.bugs_by_severity.critical > 0 || self.stats.bugs_by_severity.high > 0
     }
+
+    /// A copy of this report keeping only the findings reachable from
+    /// `entry_contracts` (e.g. the deployed ones) through the call graph of
+    /// `modules`. The other findings are moved to
+    /// [`unreachable`](Self::unreachable) and left out of the statistics.
+    pub fn reachable_findings(&self, modules: &[Module], entry_contracts: &[String]) -> Self {
+        let surface = ReachableSurface::compute(modules, entry_contracts);
+        let (bugs, unreachable) = surface.partition(&self.bugs, modules);
+        self.with_findings(bugs, unreachable)
+    }
+
+    /// A copy of this report keeping only the findings, reachable or not,
+    /// that are not in `baseline`.
+    pub fn diff_against(&self, baseline: &Baseline) -> Self {
+        self.filtered(|bug| !baseline.contains(bug))
+    }
+
+    /// A copy of this report keeping only the findings, reachable or not,
+    /// whose detector's confidence is at least `min`.
+    pub fn with_min_confidence(&self, min: ConfidenceLevel) -> Self {
+        let table = ConfidenceTable::builtin();
+        self.filtered(|bug| table.confidence_of(bug) >= min)
+    }
+
+    /// A copy of this report keeping only the findings, reachable or not,
+    /// satisfying `keep`.
+    fn filtered(&self, keep: impl Fn(&Bug) -> bool) -> Self {
+        let bugs = self.bugs.iter().filter(|bug| keep(bug)).cloned().collect();
+        let unreachable = self
+            .unreachable
+            .iter()
+            .filter(|bug| keep(bug))
+            .cloned()
+            .collect();
+        self.with_findings(bugs, unreachable)
+    }
+
+    /// A copy of this report with other findings, and statistics recounted
+    /// for them.
+    fn with_findings(&self, bugs: Vec<Bug>, unreachable: Vec<Bug>) -> Self {
+        let mut report = Self::with_language(
+            bugs,
+            self.files_analyzed.clone(),
+            self.duration,
+            &self.source_language,
+        );
+        report.version = self.version.clone();
+        report.timestamp = self.timestamp;
+        report.compiler_versions = self.compiler_versions.clone();
+        report.timed_out = self.timed_out.clone();
+        report.compile_errors = self.compile_errors.clone();
+        report.contract_metrics = self.contract_metrics.clone();
+        report.timing = self.timing.clone();
+        report.unreachable = unreachable;
+        report.stats.contracts = self.stats.contracts;
+        report.stats.functions = self.stats.functions;
+        report.stats.detectors_run = self.stats.detectors_run;
+        report
+    }
+
+    /// The findings, without [`unreachable`](Self::unreachable) ones, most
+    /// important first by severity blended with the confidence of their
+    /// detector: high severity and high confidence first, low severity and
+    /// low confidence last. Formatters list findings in this order.
+    pub fn sorted_by_priority(&self) -> Vec<&Bug> {
+        ranking::rank(&self.bugs, &[], &RankingWeights::default())
+    }
+
+    /// All findings, including [`unreachable`](Self::unreachable) ones,
+    /// most important first under the default [`RankingWeights`].
+    pub fn ranked(&self) -> Vec<&Bug> {
+        self.ranked_with(&RankingWeights::default())
+    }
+
+    /// All findings, including [`unreachable`](Self::unreachable) ones,
+    /// most important first under `weights`.
+    pub fn ranked_with(&self, weights: &RankingWeights) -> Vec<&Bug> {
+        ranking::rank(&self.bugs, &self.unreachable, weights)
+    }
+
+    /// Check the findings against the limits of `policy`.
+    /// [`Unreachable`](Self::unreachable) findings are not counted.
+    pub fn ci_gate(&self, policy: &GatePolicy) -> GateResult {
+        policy.check(&self.bugs)
+    }
+
+    /// Process exit status under a `--fail-on` threshold: 1 when a finding
+    /// has severity `fail_on` or higher, 0 otherwise.
+    ///
+    /// This only covers findings. Tool errors, such as no input file
+    /// compiling, exit with status 1 before a report exists, so they take
+    /// precedence over this status.
+    pub fn exit_code(&self, fail_on: Severity) -> i32 {
+        self.ci_gate(&GatePolicy::fail_on(fail_on)).exit_code()
+    }
 }
 
 /// Trait for output formatters.
@@ -118,6 +255,88 @@ pub trait OutputFormatter {
     fn content_type(&self) -> &'static str;
 }
 
+/// Rendering options for the human-readable formatters (text, Markdown).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputOptions {
+    /// Lines of source context shown around each finding. `None` keeps the
+    /// format's default: one line for text output, no snippet for Markdown.
+    pub context_lines: Option<usize>,
+
+    /// Include each finding's long description.
+    pub include_description: bool,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self { context_lines: None, include_description: true }
+    }
+}
+
+/// Extract the source snippet of a finding with `context` surrounding lines.
+pub fn format_snippet(bug: &Bug, context: usize) -> Option<String> {
+    let file = bug.loc.file.as_deref()?;
+    common::snippet::extract_snippet(
+        file,
+        bug.loc.start_line,
+        bug.loc.end_line,
+        bug.loc.start_col,
+        bug.loc.end_col,
+        context,
+    )
+}
+
+/// Format the contract metrics of a report, one line per contract. Empty if
+/// no metrics were computed.
+pub fn format_contract_metrics(report: &AnalysisReport) -> String {
+    if report.contract_metrics.is_empty() {
+        return String::new();
+    }
+    let mut output = String::from("Contract metrics:\n");
+    for (contract, m) in &report.contract_metrics {
+        output.push_str(&format!(
+            "  {}: {} functions, {} external calls, {} loops, {} storage variables, \
+             {} modifiers, {} lines, cyclomatic complexity {}\n",
+            contract,
+            m.functions,
+            m.external_calls,
+            m.loops,
+            m.storage_variables,
+            m.modifiers,
+            m.lines_of_code,
+            m.cyclomatic_complexity
+        ));
+    }
+    output
+}
+
+/// Format the timing of a report as a table of the analysis passes and
+/// detectors, slowest first. Empty if nothing was timed.
+pub fn format_profile(report: &AnalysisReport) -> String {
+    if report.timing.is_empty() {
+        return String::new();
+    }
+    let mut entries: Vec<(&String, &Duration)> = report.timing.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let total: Duration = report.timing.values().sum();
+
+    let mut output = String::from("Profile (slowest first):\n");
+    output.push_str(&format!("  {:>12}  {:>6}  {}\n", "Time (ms)", "Share", "Pass/Detector"));
+    for (name, duration) in entries {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            100.0 * duration.as_secs_f64() / total.as_secs_f64()
+        };
+        output.push_str(&format!(
+            "  {:>12.3}  {:>5.1}%  {}\n",
+            duration.as_secs_f64() * 1000.0,
+            share,
+            name
+        ));
+    }
+    output
+}
+
 /// Format a location for display.
 pub fn format_location(bug: &Bug) -> String {
     match &bug.loc.file {
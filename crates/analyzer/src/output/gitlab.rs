@@ -0,0 +1,148 @@
+//! GitLab Code Quality output formatter.
+//!
+//! The Code Quality report is a JSON array of issues, which GitLab shows in
+//! the merge request widget. Issues are tracked across commits by their
+//! fingerprint, which is the [baseline fingerprint](fingerprint) of the
+//! finding and so does not change when unrelated lines move.
+
+use crate::output::baseline::fingerprint;
+use crate::output::formatter::{AnalysisReport, OutputFormatter};
+use bugs::bug::{Bug, Severity};
+use serde::{Deserialize, Serialize};
+
+/// GitLab Code Quality output formatter.
+#[derive(Debug, Default)]
+pub struct GitLabCodeQualityFormatter {
+    /// Whether to pretty print the output.
+    pub pretty: bool,
+}
+
+impl GitLabCodeQualityFormatter {
+    pub fn new(pretty: bool) -> Self {
+        Self { pretty }
+    }
+}
+
+impl OutputFormatter for GitLabCodeQualityFormatter {
+    fn format(&self, report: &AnalysisReport) -> String {
+        let issues: Vec<CodeQualityIssue> = report
+            .sorted_by_priority()
+            .into_iter()
+            .map(CodeQualityIssue::from)
+            .collect();
+        let json = if self.pretty {
+            serde_json::to_string_pretty(&issues)
+        } else {
+            serde_json::to_string(&issues)
+        };
+        json.unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+}
+
+/// An issue of a Code Quality report.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeQualityIssue {
+    pub description: String,
+    pub check_name: String,
+    pub fingerprint: String,
+    pub severity: String,
+    pub location: CodeQualityLocation,
+}
+
+/// Location of an issue.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeQualityLocation {
+    pub path: String,
+    pub lines: CodeQualityLines,
+}
+
+/// Lines of an issue.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeQualityLines {
+    pub begin: usize,
+}
+
+impl From<&Bug> for CodeQualityIssue {
+    fn from(bug: &Bug) -> Self {
+        CodeQualityIssue {
+            description: bug.description.clone().unwrap_or_else(|| bug.name.clone()),
+            check_name: bug.name.clone(),
+            fingerprint: fingerprint(bug),
+            severity: severity_to_gitlab(bug.risk_level).to_string(),
+            location: CodeQualityLocation {
+                path: bug
+                    .loc
+                    .file
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+                lines: CodeQualityLines { begin: bug.loc.start_line },
+            },
+        }
+    }
+}
+
+/// GitLab severity of a finding.
+fn severity_to_gitlab(severity: Severity) -> &'static str {
+    match severity {
+        Severity::No => "info",
+        Severity::Low => "minor",
+        Severity::Medium => "major",
+        Severity::High => "critical",
+        Severity::Critical => "blocker",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bugs::bug::{BugCategory, BugKind, RiskLevel, SourceAnchor};
+    use common::loc::Loc;
+    use std::time::Duration;
+
+    /// A finding in `Vault::withdraw()` at `line`.
+    fn bug(line: usize) -> Bug {
+        let mut bug = Bug::new(
+            "Reentrancy",
+            Some("State written after external call."),
+            Loc::new(line, 5, line, 30).with_file("Vault.sol".to_string()),
+            BugKind::Vulnerability,
+            BugCategory::Reentrancy,
+            RiskLevel::High,
+            vec![],
+            vec![],
+            None,
+        );
+        bug.source_anchor = Some(SourceAnchor {
+            contract: "Vault".to_string(),
+            function: Some("withdraw()".to_string()),
+            statement: Some("a1".to_string()),
+        });
+        bug
+    }
+
+    #[test]
+    fn test_gitlab_code_quality_formatter() {
+        let report = AnalysisReport::new(vec![bug(12)], vec![], Duration::from_secs(1));
+        let output = GitLabCodeQualityFormatter::new(false).format(&report);
+        let issues: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let issue = &issues[0];
+        assert_eq!(issue["description"], "State written after external call.");
+        assert_eq!(issue["severity"], "critical");
+        assert_eq!(issue["location"]["path"], "Vault.sol");
+        assert_eq!(issue["location"]["lines"]["begin"], 12);
+    }
+
+    #[test]
+    fn test_gitlab_fingerprint_is_stable_across_lines() {
+        let moved = CodeQualityIssue::from(&bug(20));
+        assert_eq!(CodeQualityIssue::from(&bug(12)).fingerprint, moved.fingerprint);
+    }
+}
@@ -0,0 +1,276 @@
+//! HTML output formatter.
+//!
+//! Renders a report as a single self-contained page for readers outside the
+//! command line: a summary table of the findings by severity, then one
+//! collapsible `<details>` section per finding showing its source lines,
+//! with the lines of the finding highlighted. The stylesheet is inlined and
+//! the page has no script, so it works offline.
+
+use crate::output::formatter::{AnalysisReport, OutputFormatter, OutputOptions, format_location};
+use bugs::bug::{Bug, RiskLevel};
+use common::source::{FileSystem, SourceProvider};
+
+/// Lines of source context shown around each finding by default.
+const DEFAULT_CONTEXT_LINES: usize = 2;
+
+/// Inline stylesheet of the report.
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 60em; color: #222; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+details { border: 1px solid #ccc; border-left-width: 6px; border-radius: 4px; margin: 0.6em 0; }
+summary { cursor: pointer; padding: 0.5em; }
+details > div { padding: 0 1em 0.5em; }
+.badge { border-radius: 3px; color: #fff; font-size: 0.85em; padding: 0.1em 0.5em; }
+.location { color: #666; font-family: monospace; }
+pre { background: #f6f8fa; overflow-x: auto; padding: 0.5em 0; }
+pre .line { display: block; padding: 0 0.5em; }
+pre .line.hl { background: #fff3b0; }
+pre .ln { color: #999; display: inline-block; margin-right: 1em; text-align: right; width: 3em; }
+.critical { border-color: #8b0000; } .badge.critical { background: #8b0000; }
+.high { border-color: #d9480f; } .badge.high { background: #d9480f; }
+.medium { border-color: #e6a700; } .badge.medium { background: #e6a700; }
+.low { border-color: #1c7ed6; } .badge.low { background: #1c7ed6; }
+.info { border-color: #868e96; } .badge.info { background: #868e96; }
+";
+
+/// HTML output formatter.
+#[derive(Debug, Default)]
+pub struct HtmlFormatter {
+    options: OutputOptions,
+}
+
+impl HtmlFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an HTML formatter with custom rendering options.
+    pub fn with_options(options: OutputOptions) -> Self {
+        Self { options }
+    }
+
+    /// Render `report`, reading the source of the findings through
+    /// `sources`.
+    pub fn render(&self, report: &AnalysisReport, sources: &dyn SourceProvider) -> String {
+        let mut output = String::new();
+        output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        output.push_str("<meta charset=\"utf-8\">\n");
+        output.push_str("<title>Verazt Analyzer Report</title>\n");
+        output.push_str(&format!("<style>{}</style>\n", STYLE));
+        output.push_str("</head>\n<body>\n");
+        output.push_str("<h1>Verazt Analyzer Report</h1>\n");
+
+        // Summary
+        output.push_str("<h2>Summary</h2>\n<ul>\n");
+        output.push_str(&format!("<li>Version: {}</li>\n", escape_html(&report.version)));
+        output.push_str(&format!("<li>Timestamp: {}</li>\n", report.timestamp.to_rfc3339()));
+        output.push_str(&format!("<li>Duration: {:.2}s</li>\n", report.duration.as_secs_f64()));
+        output.push_str(&format!("<li>Files analyzed: {}</li>\n", report.files_analyzed.len()));
+        output.push_str("</ul>\n");
+
+        let counts = &report.stats.bugs_by_severity;
+        output.push_str("<table>\n<tr><th>Severity</th><th>Count</th></tr>\n");
+        for (risk, count) in [
+            (RiskLevel::Critical, counts.critical),
+            (RiskLevel::High, counts.high),
+            (RiskLevel::Medium, counts.medium),
+            (RiskLevel::Low, counts.low),
+            (RiskLevel::No, counts.info),
+        ] {
+            output.push_str(&format!(
+                "<tr><td><span class=\"badge {}\">{}</span></td><td>{}</td></tr>\n",
+                severity_class(risk),
+                severity_label(risk),
+                count
+            ));
+        }
+        output.push_str(&format!(
+            "<tr><th>Total</th><th>{}</th></tr>\n</table>\n",
+            report.total_bugs()
+        ));
+
+        // Findings
+        output.push_str("<h2>Findings</h2>\n");
+        if report.bugs.is_empty() {
+            output.push_str("<p>No issues found.</p>\n");
+        }
+        let context = self.options.context_lines.unwrap_or(DEFAULT_CONTEXT_LINES);
+        for bug in report.sorted_by_priority() {
+            output.push_str(&self.format_finding(bug, sources, context));
+        }
+
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+
+    /// Render a finding as a collapsible section.
+    fn format_finding(&self, bug: &Bug, sources: &dyn SourceProvider, context: usize) -> String {
+        let class = severity_class(bug.risk_level);
+        let mut output = format!("<details class=\"{}\">\n", class);
+        output.push_str(&format!(
+            "<summary><span class=\"badge {}\">{}</span> <strong>{}</strong> \
+             <span class=\"location\">{}</span></summary>\n<div>\n",
+            class,
+            severity_label(bug.risk_level),
+            escape_html(&bug.name),
+            escape_html(&format_location(bug))
+        ));
+
+        let mut ids: Vec<String> = bug.swc_ids.iter().map(|id| format!("SWC-{id}")).collect();
+        ids.extend(bug.cwe_ids.iter().map(|id| format!("CWE-{id}")));
+        if !ids.is_empty() {
+            output.push_str(&format!("<p>{}</p>\n", ids.join(", ")));
+        }
+        if let Some(desc) = &bug.description
+            && self.options.include_description
+        {
+            output.push_str(&format!("<p>{}</p>\n", escape_html(desc)));
+        }
+        if let Some(excerpt) = source_excerpt(bug, sources, context) {
+            output.push_str(&excerpt);
+        }
+        if let Some(remedy) = &bug.remediation {
+            output.push_str(&format!(
+                "<p><strong>Remediation:</strong> {}</p>\n",
+                escape_html(remedy)
+            ));
+        }
+        output.push_str("</div>\n</details>\n");
+        output
+    }
+}
+
+impl OutputFormatter for HtmlFormatter {
+    fn format(&self, report: &AnalysisReport) -> String {
+        self.render(report, &FileSystem)
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "text/html"
+    }
+}
+
+/// The source lines of `bug` with `context` lines around them, the lines of
+/// the finding highlighted. `None` if the source cannot be read or the
+/// location is out of the file.
+fn source_excerpt(bug: &Bug, sources: &dyn SourceProvider, context: usize) -> Option<String> {
+    let text = sources.read(bug.loc.file.as_deref()?).ok()?;
+    let lines: Vec<&str> = text.lines().collect();
+    if bug.loc.start_line == 0 || bug.loc.start_line > lines.len() {
+        return None;
+    }
+    let end_line = bug.loc.end_line.clamp(bug.loc.start_line, lines.len());
+    let first = bug.loc.start_line.saturating_sub(context).max(1);
+    let last = (end_line + context).min(lines.len());
+
+    let mut output = String::from("<pre><code>");
+    for (number, line) in (first..=last).zip(&lines[first - 1..last]) {
+        let hl = if (bug.loc.start_line..=end_line).contains(&number) {
+            " hl"
+        } else {
+            ""
+        };
+        output.push_str(&format!(
+            "<span class=\"line{}\"><span class=\"ln\">{}</span>{}</span>",
+            hl,
+            number,
+            escape_html(line)
+        ));
+    }
+    output.push_str("</code></pre>\n");
+    Some(output)
+}
+
+/// CSS class of a severity.
+fn severity_class(risk: RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "critical",
+        RiskLevel::High => "high",
+        RiskLevel::Medium => "medium",
+        RiskLevel::Low => "low",
+        RiskLevel::No => "info",
+    }
+}
+
+/// Display name of a severity.
+fn severity_label(risk: RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "Critical",
+        RiskLevel::High => "High",
+        RiskLevel::Medium => "Medium",
+        RiskLevel::Low => "Low",
+        RiskLevel::No => "Info",
+    }
+}
+
+/// Escape the HTML special characters of `text`.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bugs::bug::{BugCategory, BugKind};
+    use common::loc::Loc;
+    use common::source::InMemorySources;
+    use std::time::Duration;
+
+    #[test]
+    fn test_html_formatter() {
+        let report = AnalysisReport::new(vec![], vec![], Duration::from_secs(1));
+        let output = HtmlFormatter::new().format(&report);
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<style>"));
+        assert!(output.contains("No issues found."));
+        assert!(!output.contains("<script"));
+    }
+
+    #[test]
+    fn test_html_finding_with_source() {
+        let source = "contract Vault {\n    uint x;\n    function f() public {\n        \
+                      require(tx.origin == owner);\n    }\n}\n";
+        let sources = InMemorySources::new().with_file("Vault.sol", source);
+        let bug = Bug::new(
+            "Tx Origin",
+            Some("Uses <tx.origin> for authorization."),
+            Loc::new(4, 9, 4, 35).with_file("Vault.sol".to_string()),
+            BugKind::Vulnerability,
+            BugCategory::AccessControl,
+            RiskLevel::High,
+            vec![477],
+            vec![115],
+            None,
+        );
+        let report = AnalysisReport::new(vec![bug], vec![], Duration::from_secs(1));
+        let output = HtmlFormatter::new().render(&report, &sources);
+
+        assert!(output.contains("<details class=\"high\">"));
+        assert!(output.contains("Vault.sol:4:9"));
+        assert!(output.contains("SWC-115, CWE-477"));
+        assert!(output.contains("Uses &lt;tx.origin&gt; for authorization."));
+        // Lines 2 to 6, the fourth highlighted.
+        assert!(output.contains("<span class=\"ln\">2</span>    uint x;"));
+        assert!(output.contains(
+            "<span class=\"line hl\"><span class=\"ln\">4</span>        \
+             require(tx.origin == owner);</span>"
+        ));
+        assert!(!output.contains("<span class=\"ln\">1</span>"));
+    }
+}
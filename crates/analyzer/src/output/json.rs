@@ -1,8 +1,14 @@
 //! JSON output formatter.
 
 use crate::output::formatter::{AnalysisReport, OutputFormatter};
-use bugs::bug::Bug;
-use serde::{Deserialize, Serialize};
+use crate::passes::sir::ContractMetrics;
+use crate::ranking::ConfidenceTable;
+use bugs::bug::{Bug, FixSuggestion};
+use common::loc::Loc;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::io::Write;
 
 /// JSON output formatter.
 #[derive(Debug, Default)]
@@ -15,6 +21,25 @@ impl JsonFormatter {
     pub fn new(pretty: bool) -> Self {
         Self { pretty }
     }
+
+    /// Write the report to `writer`, streaming the `findings` array one
+    /// finding at a time instead of building the whole document in memory.
+    ///
+    /// The bytes written are identical to the output of [`format`].
+    ///
+    /// [`format`]: OutputFormatter::format
+    pub fn write_to<W: Write>(
+        &self,
+        report: &AnalysisReport,
+        writer: W,
+    ) -> serde_json::Result<()> {
+        let streamed = StreamedReport(report);
+        if self.pretty {
+            serde_json::to_writer_pretty(writer, &streamed)
+        } else {
+            serde_json::to_writer(writer, &streamed)
+        }
+    }
 }
 
 impl OutputFormatter for JsonFormatter {
@@ -56,6 +81,22 @@ pub struct JsonReport {
     /// Files analyzed
     pub files_analyzed: Vec<String>,
 
+    /// Compiler version used for each analyzed file
+    #[serde(default)]
+    pub compiler_versions: BTreeMap<String, String>,
+
+    /// Files whose analysis timed out (findings for them are partial)
+    #[serde(default)]
+    pub timed_out: Vec<String>,
+
+    /// Files that failed to compile, with the compiler's error
+    #[serde(default)]
+    pub compile_errors: BTreeMap<String, String>,
+
+    /// Size and complexity metrics of each contract
+    #[serde(default)]
+    pub contract_metrics: BTreeMap<String, ContractMetrics>,
+
     /// Summary statistics
     pub summary: JsonSummary,
 
@@ -86,6 +127,28 @@ pub struct JsonFinding {
     pub swc_id: Option<String>,
     pub cwe_id: Option<String>,
     pub confidence: String,
+    /// Offset-independent position of the finding, see `Bug::anchor`.
+    #[serde(default)]
+    pub anchor: String,
+    /// Suggested fix, if the detector proposes one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<JsonFix>,
+}
+
+/// Suggested fix of a finding.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonFix {
+    pub description: String,
+    /// Source edit applying the fix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit: Option<JsonEdit>,
+}
+
+/// Replacement of the source text at `location`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonEdit {
+    pub location: JsonLocation,
+    pub replacement: String,
 }
 
 /// Location information.
@@ -98,6 +161,44 @@ pub struct JsonLocation {
     pub end_column: Option<usize>,
 }
 
+impl From<&Loc> for JsonLocation {
+    fn from(loc: &Loc) -> Self {
+        Self {
+            file: loc.file.clone(),
+            start_line: Some(loc.start_line),
+            end_line: Some(loc.end_line),
+            start_column: Some(loc.start_col),
+            end_column: Some(loc.end_col),
+        }
+    }
+}
+
+impl From<&FixSuggestion> for JsonFix {
+    fn from(suggestion: &FixSuggestion) -> Self {
+        Self {
+            description: suggestion.message.clone(),
+            edit: suggestion.replacement.as_ref().map(|r| JsonEdit {
+                location: JsonLocation::from(&r.loc),
+                replacement: r.text.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&AnalysisReport> for JsonSummary {
+    fn from(report: &AnalysisReport) -> Self {
+        let severity = &report.stats.bugs_by_severity;
+        Self {
+            total: report.bugs.len(),
+            critical: severity.critical,
+            high: severity.high,
+            medium: severity.medium,
+            low: severity.low,
+            info: severity.info,
+        }
+    }
+}
+
 impl From<&AnalysisReport> for JsonReport {
     fn from(report: &AnalysisReport) -> Self {
         Self {
@@ -106,19 +207,54 @@ impl From<&AnalysisReport> for JsonReport {
             duration_ms: report.duration.as_millis() as u64,
             source_language: report.source_language.clone(),
             files_analyzed: report.files_analyzed.clone(),
-            summary: JsonSummary {
-                total: report.bugs.len(),
-                critical: report.stats.bugs_by_severity.critical,
-                high: report.stats.bugs_by_severity.high,
-                medium: report.stats.bugs_by_severity.medium,
-                low: report.stats.bugs_by_severity.low,
-                info: report.stats.bugs_by_severity.info,
-            },
-            findings: report.bugs.iter().map(JsonFinding::from).collect(),
+            compiler_versions: report.compiler_versions.clone(),
+            timed_out: report.timed_out.iter().cloned().collect(),
+            compile_errors: report.compile_errors.clone(),
+            contract_metrics: report.contract_metrics.clone(),
+            summary: JsonSummary::from(report),
+            findings: report
+                .sorted_by_priority()
+                .into_iter()
+                .map(JsonFinding::from)
+                .collect(),
         }
     }
 }
 
+/// Serializes like [`JsonReport`], but converts findings lazily while
+/// writing. Field names and order must stay in sync with `JsonReport`.
+struct StreamedReport<'a>(&'a AnalysisReport);
+
+/// Lazily converted `findings` array of a [`StreamedReport`].
+struct StreamedFindings<'a>(Vec<&'a Bug>);
+
+impl Serialize for StreamedReport<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let report = self.0;
+        let summary = JsonSummary::from(report);
+
+        let mut state = serializer.serialize_struct("JsonReport", 11)?;
+        state.serialize_field("version", &report.version)?;
+        state.serialize_field("timestamp", &report.timestamp.to_rfc3339())?;
+        state.serialize_field("duration_ms", &(report.duration.as_millis() as u64))?;
+        state.serialize_field("source_language", &report.source_language)?;
+        state.serialize_field("files_analyzed", &report.files_analyzed)?;
+        state.serialize_field("compiler_versions", &report.compiler_versions)?;
+        state.serialize_field("timed_out", &report.timed_out)?;
+        state.serialize_field("compile_errors", &report.compile_errors)?;
+        state.serialize_field("contract_metrics", &report.contract_metrics)?;
+        state.serialize_field("summary", &summary)?;
+        state.serialize_field("findings", &StreamedFindings(report.sorted_by_priority()))?;
+        state.end()
+    }
+}
+
+impl Serialize for StreamedFindings<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|bug| JsonFinding::from(*bug)))
+    }
+}
+
 impl From<&Bug> for JsonFinding {
     fn from(bug: &Bug) -> Self {
         Self {
@@ -127,16 +263,15 @@ impl From<&Bug> for JsonFinding {
             description: bug.description.clone().unwrap_or_default(),
             severity: bug.risk_level.as_str().to_string(),
             category: bug.category.as_str().to_string(),
-            location: JsonLocation {
-                file: bug.loc.file.clone(),
-                start_line: Some(bug.loc.start_line),
-                end_line: Some(bug.loc.end_line),
-                start_column: Some(bug.loc.start_col),
-                end_column: Some(bug.loc.end_col),
-            },
+            location: JsonLocation::from(&bug.loc),
             swc_id: bug.swc_ids.first().map(|id| format!("SWC-{}", id)),
             cwe_id: bug.cwe_ids.first().map(|id| format!("CWE-{}", id)),
-            confidence: "high".to_string(), // Default confidence
+            confidence: ConfidenceTable::builtin()
+                .confidence_of(bug)
+                .as_str()
+                .to_lowercase(),
+            anchor: bug.anchor(),
+            fix: bug.suggestion.as_ref().map(JsonFix::from),
         }
     }
 }
@@ -154,4 +289,77 @@ mod tests {
         assert!(output.contains("\"version\""));
         assert!(output.contains("\"findings\""));
     }
+
+    #[test]
+    fn test_json_compiler_versions() {
+        let mut report =
+            AnalysisReport::new(vec![], vec!["Bank.sol".to_string()], Duration::from_secs(1));
+        report
+            .compiler_versions
+            .insert("Bank.sol".to_string(), "0.8.20".to_string());
+        let output = JsonFormatter::new(false).format(&report);
+        assert!(output.contains("\"compiler_versions\":{\"Bank.sol\":\"0.8.20\"}"));
+    }
+
+    #[test]
+    fn test_json_timed_out() {
+        let mut report =
+            AnalysisReport::new(vec![], vec!["Bank.sol".to_string()], Duration::from_secs(1));
+        report.timed_out.insert("Bank.sol".to_string());
+        let output = JsonFormatter::new(false).format(&report);
+        assert!(output.contains("\"timed_out\":[\"Bank.sol\"]"));
+    }
+
+    #[test]
+    fn test_json_fix_suggestion() {
+        use bugs::bug::{BugCategory, BugKind, RiskLevel};
+
+        let loc = Loc::new(1, 1, 1, 24).with_file("Bank.sol".to_string());
+        let bug = Bug::new(
+            "Floating Pragma",
+            None,
+            loc.clone(),
+            BugKind::Refactoring,
+            BugCategory::CodeQuality,
+            RiskLevel::Low,
+            vec![],
+            vec![103],
+            None,
+        )
+        .with_suggestion(FixSuggestion::replace(
+            "Pin the compiler version to 0.8.19.",
+            loc,
+            "pragma solidity 0.8.19;",
+        ));
+
+        let finding = serde_json::to_value(JsonFinding::from(&bug)).unwrap();
+        assert_eq!(finding["fix"]["description"], "Pin the compiler version to 0.8.19.");
+        assert_eq!(finding["fix"]["edit"]["replacement"], "pragma solidity 0.8.19;");
+        assert_eq!(finding["fix"]["edit"]["location"]["end_column"], 24);
+    }
+
+    #[test]
+    fn test_json_finding_confidence() {
+        use bugs::bug::{BugCategory, BugKind, Confidence, RiskLevel};
+
+        let mut bug = Bug::new(
+            "Stale State Read",
+            None,
+            Loc::new(1, 1, 1, 1),
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            RiskLevel::Low,
+            vec![],
+            vec![],
+            None,
+        );
+        let finding = serde_json::to_value(JsonFinding::from(&bug)).unwrap();
+        assert_eq!(finding["confidence"], "low");
+
+        // The confidence recorded by the pipeline wins, e.g. for custom
+        // detectors.
+        bug.confidence = Some(Confidence::High);
+        let finding = serde_json::to_value(JsonFinding::from(&bug)).unwrap();
+        assert_eq!(finding["confidence"], "high");
+    }
 }
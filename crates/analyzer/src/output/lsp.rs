@@ -0,0 +1,254 @@
+//! LSP diagnostics output formatter.
+//!
+//! Findings are rendered as Language Server Protocol `Diagnostic`s, grouped
+//! by source file, so that editor integrations can publish them as they
+//! are. LSP positions are zero-based and count characters in UTF-16 code
+//! units, whereas a [`Loc`] holds one-based columns counting Unicode
+//! characters, with the end column on the last character of the span. A
+//! [`LineIndex`] of the source text converts between the two; files that
+//! cannot be read are assumed to be ASCII.
+
+use crate::detectors::base::registry::{DetectorRegistry, register_all_detectors};
+use crate::output::formatter::{AnalysisReport, OutputFormatter};
+use crate::output::sarif::rule_id;
+use bugs::bug::{Bug, RiskLevel};
+use common::loc::Loc;
+use common::source::{FileSystem, SourceProvider};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// LSP diagnostics output formatter.
+#[derive(Debug, Default)]
+pub struct LspFormatter {
+    /// Whether to pretty print the output.
+    pub pretty: bool,
+}
+
+impl LspFormatter {
+    pub fn new(pretty: bool) -> Self {
+        Self { pretty }
+    }
+}
+
+impl OutputFormatter for LspFormatter {
+    fn format(&self, report: &AnalysisReport) -> String {
+        let diagnostics = lsp_diagnostics(report, &FileSystem);
+        let json = if self.pretty {
+            serde_json::to_string_pretty(&diagnostics)
+        } else {
+            serde_json::to_string(&diagnostics)
+        };
+        json.unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+}
+
+/// Diagnostics of the findings of `report`, keyed by source file, most
+/// important first. Positions are computed from the sources read through
+/// `sources`.
+pub fn lsp_diagnostics(
+    report: &AnalysisReport,
+    sources: &dyn SourceProvider,
+) -> BTreeMap<String, Vec<LspDiagnostic>> {
+    let mut registry = DetectorRegistry::new();
+    register_all_detectors(&mut registry);
+
+    let mut indexes: HashMap<String, Option<LineIndex>> = HashMap::new();
+    let mut diagnostics: BTreeMap<String, Vec<LspDiagnostic>> = BTreeMap::new();
+    for bug in report.sorted_by_priority() {
+        let file = bug
+            .loc
+            .file
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let index = indexes
+            .entry(file.clone())
+            .or_insert_with(|| sources.read(&file).ok().map(|text| LineIndex::new(&text)));
+        let diagnostic = LspDiagnostic::new(bug, rule_id(&registry, bug), index.as_ref());
+        diagnostics.entry(file).or_default().push(diagnostic);
+    }
+    diagnostics
+}
+
+/// An LSP `Diagnostic`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LspDiagnostic {
+    pub range: LspRange,
+    /// 1 (error) to 4 (hint).
+    pub severity: u8,
+    /// Id of the detector that reported the finding.
+    pub code: String,
+    pub source: String,
+    pub message: String,
+}
+
+impl LspDiagnostic {
+    /// Diagnostic of `bug` reported by the detector `code`. Columns are
+    /// converted to UTF-16 through `index`, the index of the finding's
+    /// source file, when it is known.
+    pub fn new(bug: &Bug, code: String, index: Option<&LineIndex>) -> Self {
+        LspDiagnostic {
+            range: match index {
+                Some(index) => index.range(&bug.loc),
+                None => LspRange::ascii(&bug.loc),
+            },
+            severity: severity_to_lsp(bug.risk_level),
+            code,
+            source: "verazt".to_string(),
+            message: bug.description.clone().unwrap_or_else(|| bug.name.clone()),
+        }
+    }
+}
+
+/// An LSP `Range`; the end position is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+impl LspRange {
+    /// Range of `loc` in a source file without multi-byte characters.
+    fn ascii(loc: &Loc) -> Self {
+        LspRange {
+            start: LspPosition::new(loc.start_line, loc.start_col.saturating_sub(1)),
+            end: LspPosition::new(loc.end_line, loc.end_col),
+        }
+    }
+}
+
+/// An LSP `Position`: zero-based line and UTF-16 offset in that line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LspPosition {
+    pub line: u32,
+    pub character: u32,
+}
+
+impl LspPosition {
+    /// Position on the one-based `line` after `character` UTF-16 units.
+    fn new(line: usize, character: usize) -> Self {
+        LspPosition { line: line.saturating_sub(1) as u32, character: character as u32 }
+    }
+}
+
+/// Line starts of a source text, to translate byte offsets and [`Loc`]
+/// columns to LSP positions.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    text: String,
+    /// Byte offset of the start of each line.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { text: text.to_string(), line_starts }
+    }
+
+    /// Position of the byte `offset`. Offsets inside a multi-byte character
+    /// map to the start of that character; offsets past the end map to the
+    /// end of the text.
+    pub fn position(&self, offset: usize) -> LspPosition {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = utf16_len(&self.text[self.line_starts[line]..offset]);
+        LspPosition { line: line as u32, character: character as u32 }
+    }
+
+    /// Range covering `loc`.
+    pub fn range(&self, loc: &Loc) -> LspRange {
+        LspRange {
+            start: self.char_position(loc.start_line, loc.start_col.saturating_sub(1)),
+            end: self.char_position(loc.end_line, loc.end_col),
+        }
+    }
+
+    /// Position after the first `chars` characters of the one-based `line`.
+    fn char_position(&self, line: usize, chars: usize) -> LspPosition {
+        let Some(&start) = self.line_starts.get(line.saturating_sub(1)) else {
+            return LspPosition::new(line, chars);
+        };
+        let text = self.text[start..].lines().next().unwrap_or_default();
+        let prefix: String = text.chars().take(chars).collect();
+        LspPosition::new(line, utf16_len(&prefix))
+    }
+}
+
+/// Length of `s` in UTF-16 code units.
+fn utf16_len(s: &str) -> usize {
+    s.chars().map(char::len_utf16).sum()
+}
+
+/// LSP `DiagnosticSeverity` of a finding.
+fn severity_to_lsp(severity: RiskLevel) -> u8 {
+    match severity {
+        RiskLevel::Critical | RiskLevel::High => 1,
+        RiskLevel::Medium => 2,
+        RiskLevel::Low => 3,
+        RiskLevel::No => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bugs::bug::{BugCategory, BugKind};
+    use common::source::InMemorySources;
+    use std::time::Duration;
+
+    #[test]
+    fn test_line_index_position() {
+        // `é` takes two bytes and one UTF-16 unit, `𝔘` four bytes and two.
+        let index = LineIndex::new("ab\né𝔘c\n");
+        assert_eq!(index.position(1), LspPosition { line: 0, character: 1 });
+        assert_eq!(index.position(3), LspPosition { line: 1, character: 0 });
+        assert_eq!(index.position(9), LspPosition { line: 1, character: 3 });
+        assert_eq!(index.position(10), LspPosition { line: 1, character: 4 });
+        // Inside `𝔘`.
+        assert_eq!(index.position(7), LspPosition { line: 1, character: 1 });
+    }
+
+    #[test]
+    fn test_lsp_diagnostics() {
+        let source = "contract Vault {\n    string s = \"𝔘\"; uint x;\n}\n";
+        let sources = InMemorySources::new().with_file("Vault.sol", source);
+        // `uint x;` on line 2, columns 21 to 27.
+        let bug = Bug::new(
+            "Unused State Variable",
+            Some("'x' is never used."),
+            Loc::new(2, 21, 2, 27).with_file("Vault.sol".to_string()),
+            BugKind::Refactoring,
+            BugCategory::Other,
+            RiskLevel::Low,
+            vec![],
+            vec![],
+            None,
+        );
+        let report = AnalysisReport::new(vec![bug], vec![], Duration::from_secs(1));
+
+        let diagnostics = lsp_diagnostics(&report, &sources);
+        let diagnostic = &diagnostics["Vault.sol"][0];
+        assert_eq!(diagnostic.severity, 3);
+        assert_eq!(diagnostic.message, "'x' is never used.");
+        assert_eq!(diagnostic.range.start, LspPosition { line: 1, character: 21 });
+        assert_eq!(diagnostic.range.end, LspPosition { line: 1, character: 28 });
+
+        // Without the source, columns are taken as UTF-16 offsets.
+        let diagnostics = lsp_diagnostics(&report, &InMemorySources::new());
+        let range = diagnostics["Vault.sol"][0].range;
+        assert_eq!(range.start, LspPosition { line: 1, character: 20 });
+    }
+}
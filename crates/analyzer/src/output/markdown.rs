@@ -1,15 +1,63 @@
 //! Markdown output formatter.
 
-use crate::output::formatter::{AnalysisReport, OutputFormatter, format_location};
+use crate::output::formatter::{
+    AnalysisReport, OutputFormatter, OutputOptions, format_location, format_snippet,
+};
 use bugs::bug::RiskLevel;
+use std::collections::HashMap;
+
+/// Severities in report order, most severe first.
+const SEVERITIES: [RiskLevel; 5] = [
+    RiskLevel::Critical,
+    RiskLevel::High,
+    RiskLevel::Medium,
+    RiskLevel::Low,
+    RiskLevel::No,
+];
 
 /// Markdown output formatter.
 #[derive(Debug, Default)]
-pub struct MarkdownFormatter;
+pub struct MarkdownFormatter {
+    options: OutputOptions,
+}
 
 impl MarkdownFormatter {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Create a Markdown formatter with custom rendering options.
+    pub fn with_options(options: OutputOptions) -> Self {
+        Self { options }
+    }
+
+    /// Render one row per triggered detector and severity, most severe
+    /// first and then by number of findings.
+    fn format_detector_table(report: &AnalysisReport) -> String {
+        let mut counts: HashMap<(&str, RiskLevel), usize> = HashMap::new();
+        for bug in &report.bugs {
+            *counts
+                .entry((bug.name.as_str(), bug.risk_level))
+                .or_default() += 1;
+        }
+        let rank = |risk: &RiskLevel| SEVERITIES.iter().position(|s| s == risk);
+        let mut rows: Vec<_> = counts.into_iter().collect();
+        rows.sort_by(|((name_a, risk_a), count_a), ((name_b, risk_b), count_b)| {
+            rank(risk_a)
+                .cmp(&rank(risk_b))
+                .then(count_b.cmp(count_a))
+                .then(name_a.cmp(name_b))
+        });
+
+        let mut output = String::new();
+        output.push_str("### Findings by Detector\n\n");
+        output.push_str("| Detector | Severity | Count |\n");
+        output.push_str("|----------|----------|-------|\n");
+        for ((name, risk), count) in rows {
+            output.push_str(&format!("| {} | {} | {} |\n", name, risk, count));
+        }
+        output.push('\n');
+        output
     }
 }
 
@@ -41,6 +89,10 @@ impl OutputFormatter for MarkdownFormatter {
         output.push_str(&format!("| **Total** | **{}** |\n", report.total_bugs()));
         output.push('\n');
 
+        if !report.bugs.is_empty() {
+            output.push_str(&Self::format_detector_table(report));
+        }
+
         // Files
         if !report.files_analyzed.is_empty() {
             output.push_str("### Files Analyzed\n\n");
@@ -55,18 +107,10 @@ impl OutputFormatter for MarkdownFormatter {
             output.push_str("## Findings\n\n");
 
             // Group by severity
-            let severities = [
-                RiskLevel::Critical,
-                RiskLevel::High,
-                RiskLevel::Medium,
-                RiskLevel::Low,
-                RiskLevel::No,
-            ];
-
-            for severity in severities {
+            for severity in SEVERITIES {
                 let bugs: Vec<_> = report
-                    .bugs
-                    .iter()
+                    .sorted_by_priority()
+                    .into_iter()
                     .filter(|b| b.risk_level.as_str() == severity.as_str())
                     .collect();
 
@@ -103,7 +147,17 @@ impl OutputFormatter for MarkdownFormatter {
                         output.push_str(&format!("- **Category**: {}\n", bug.kind.as_str()));
                         output.push('\n');
 
-                        if let Some(desc) = &bug.description {
+                        if let Some(context) = self.options.context_lines
+                            && let Some(snippet) = format_snippet(bug, context)
+                        {
+                            output.push_str(&format!("```{}\n", report.source_language));
+                            output.push_str(&snippet);
+                            output.push_str("```\n\n");
+                        }
+
+                        if let Some(desc) = &bug.description
+                            && self.options.include_description
+                        {
                             output.push_str("**Description:**\n\n");
                             output.push_str(desc);
                             output.push_str("\n\n");
@@ -153,5 +207,39 @@ mod tests {
         let output = formatter.format(&report);
         assert!(output.contains("# Verazt Analyzer Analysis Report"));
         assert!(output.contains("No issues found"));
+        assert!(!output.contains("Findings by Detector"));
+    }
+
+    #[test]
+    fn test_markdown_detector_table() {
+        use bugs::bug::{Bug, BugCategory, BugKind};
+        use common::loc::Loc;
+
+        let bug = |name: &str, risk| {
+            Bug::new(
+                name,
+                None,
+                Loc::new(1, 1, 1, 1),
+                BugKind::Vulnerability,
+                BugCategory::Other,
+                risk,
+                vec![],
+                vec![],
+                None,
+            )
+        };
+        let bugs = vec![
+            bug("Floating Pragma", RiskLevel::Low),
+            bug("Reentrancy", RiskLevel::High),
+            bug("Floating Pragma", RiskLevel::Low),
+            bug("Floating Pragma", RiskLevel::Low),
+        ];
+        let report = AnalysisReport::new(bugs, vec![], Duration::from_secs(1));
+        let output = MarkdownFormatter::new().format(&report);
+
+        let reentrancy = output.find("| Reentrancy | High | 1 |").unwrap();
+        let pragma = output.find("| Floating Pragma | Low | 3 |").unwrap();
+        assert!(reentrancy < pragma);
+        assert!(pragma < output.find("\n## Findings\n").unwrap());
     }
 }
@@ -2,12 +2,24 @@
 //!
 //! This module provides various output formats for analysis results.
 
+pub mod baseline;
 pub mod formatter;
+pub mod gitlab;
+pub mod html;
 pub mod json;
+pub mod lsp;
 pub mod markdown;
+pub mod ndjson;
 pub mod sarif;
+pub mod summary;
 
+pub use baseline::*;
 pub use formatter::*;
+pub use gitlab::*;
+pub use html::*;
 pub use json::*;
+pub use lsp::*;
 pub use markdown::*;
+pub use ndjson::*;
 pub use sarif::*;
+pub use summary::*;
@@ -0,0 +1,139 @@
+//! JSON Lines (NDJSON) output formatter.
+
+use crate::output::formatter::{AnalysisReport, OutputFormatter};
+use crate::output::json::{JsonFinding, JsonSummary};
+use crate::passes::sir::ContractMetrics;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+/// JSON Lines formatter, for consumers processing findings as they arrive.
+///
+/// Each finding is written on its own line, with the schema of an element of
+/// the `findings` array of [`JsonFormatter`](super::JsonFormatter). A last
+/// line holds the other fields of that report, including the `summary`.
+#[derive(Debug, Default)]
+pub struct NdjsonFormatter;
+
+impl NdjsonFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write the report to `writer`, one line at a time.
+    pub fn write_to<W: Write>(
+        &self,
+        report: &AnalysisReport,
+        mut writer: W,
+    ) -> serde_json::Result<()> {
+        for bug in report.sorted_by_priority() {
+            serde_json::to_writer(&mut writer, &JsonFinding::from(bug))?;
+            writeln!(writer).map_err(serde_json::Error::io)?;
+        }
+        serde_json::to_writer(&mut writer, &NdjsonSummary::from(report))?;
+        writeln!(writer).map_err(serde_json::Error::io)?;
+        writer.flush().map_err(serde_json::Error::io)
+    }
+}
+
+impl OutputFormatter for NdjsonFormatter {
+    fn format(&self, report: &AnalysisReport) -> String {
+        let mut output = vec![];
+        match self.write_to(report, &mut output) {
+            Ok(()) => String::from_utf8_lossy(&output).into_owned(),
+            Err(e) => format!("{{\"error\": \"{}\"}}\n", e),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        "jsonl"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/x-ndjson"
+    }
+}
+
+/// Last line of the output: the fields of a `JsonReport` but `findings`.
+#[derive(Serialize)]
+struct NdjsonSummary<'a> {
+    version: &'a str,
+    timestamp: String,
+    duration_ms: u64,
+    source_language: &'a str,
+    files_analyzed: &'a [String],
+    compiler_versions: &'a BTreeMap<String, String>,
+    timed_out: &'a BTreeSet<String>,
+    compile_errors: &'a BTreeMap<String, String>,
+    contract_metrics: &'a BTreeMap<String, ContractMetrics>,
+    summary: JsonSummary,
+}
+
+impl<'a> From<&'a AnalysisReport> for NdjsonSummary<'a> {
+    fn from(report: &'a AnalysisReport) -> Self {
+        Self {
+            version: &report.version,
+            timestamp: report.timestamp.to_rfc3339(),
+            duration_ms: report.duration.as_millis() as u64,
+            source_language: &report.source_language,
+            files_analyzed: &report.files_analyzed,
+            compiler_versions: &report.compiler_versions,
+            timed_out: &report.timed_out,
+            compile_errors: &report.compile_errors,
+            contract_metrics: &report.contract_metrics,
+            summary: JsonSummary::from(report),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::JsonFormatter;
+    use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+    use common::loc::Loc;
+    use serde_json::Value;
+    use std::time::Duration;
+
+    fn bug(name: &str, risk: RiskLevel) -> Bug {
+        Bug::new(
+            name,
+            Some("description"),
+            Loc::new(3, 1, 3, 10).with_file("Bank.sol".to_string()),
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            risk,
+            vec![],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_ndjson_formatter() {
+        let report = AnalysisReport::new(
+            vec![
+                bug("Reentrancy", RiskLevel::High),
+                bug("Floating Pragma", RiskLevel::Low),
+            ],
+            vec!["Bank.sol".to_string()],
+            Duration::from_secs(1),
+        );
+        let output = NdjsonFormatter::new().format(&report);
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+
+        // Findings match the elements of the JSON report's `findings`.
+        let json: Value =
+            serde_json::from_str(&JsonFormatter::new(false).format(&report)).unwrap();
+        assert_eq!(lines[..2], json["findings"].as_array().unwrap()[..]);
+
+        let summary = &lines[2];
+        assert_eq!(summary["summary"]["total"], 2);
+        assert_eq!(summary["files_analyzed"], json["files_analyzed"]);
+        assert!(summary.get("findings").is_none());
+    }
+}
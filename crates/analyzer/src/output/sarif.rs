@@ -2,10 +2,22 @@
 //!
 //! SARIF (Static Analysis Results Interchange Format) is a standard format
 //! for the output of static analysis tools.
+//!
+//! Rules are the detectors that reported findings, described from their
+//! metadata. Each result carries a `primaryLocationLineHash` fingerprint of
+//! its rule and of the source line it points at, so that code-scanning
+//! services can match alerts across runs even when unrelated edits move
+//! the line. Suggested fixes that edit the source become `fixes` of their
+//! result.
 
+use crate::detectors::base::registry::{DetectorRegistry, register_all_detectors};
 use crate::output::formatter::{AnalysisReport, OutputFormatter};
-use bugs::bug::RiskLevel;
+use bugs::bug::{Bug, BugKind, FixSuggestion, RiskLevel};
+use common::loc::Loc;
+use common::source::{FileSystem, SourceProvider};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tiny_keccak::{Hasher, Keccak};
 
 /// SARIF output formatter.
 #[derive(Debug, Default)]
@@ -90,6 +102,16 @@ pub struct SarifRule {
     pub help_uri: Option<String>,
     #[serde(rename = "defaultConfiguration")]
     pub default_configuration: SarifRuleConfiguration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<SarifRuleProperties>,
+}
+
+/// Property bag of a rule.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifRuleProperties {
+    /// Category of the rule (`security`, `gas` or `style`) followed by the
+    /// detector's topic tags.
+    pub tags: Vec<String>,
 }
 
 /// Rule configuration.
@@ -112,6 +134,53 @@ pub struct SarifResult {
     pub level: String,
     pub message: SarifMessage,
     pub locations: Vec<SarifLocation>,
+    #[serde(
+        rename = "partialFingerprints",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub partial_fingerprints: Option<SarifPartialFingerprints>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<SarifFix>,
+}
+
+/// A proposed fix of a result.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifFix {
+    pub description: SarifMessage,
+    #[serde(rename = "artifactChanges")]
+    pub artifact_changes: Vec<SarifArtifactChange>,
+}
+
+/// Changes of one file made by a fix.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifArtifactChange {
+    #[serde(rename = "artifactLocation")]
+    pub artifact_location: SarifArtifactLocation,
+    pub replacements: Vec<SarifReplacement>,
+}
+
+/// Replacement of a region of a file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifReplacement {
+    #[serde(rename = "deletedRegion")]
+    pub deleted_region: SarifRegion,
+    #[serde(rename = "insertedContent")]
+    pub inserted_content: SarifArtifactContent,
+}
+
+/// Text content inserted by a replacement.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifArtifactContent {
+    pub text: String,
+}
+
+/// Fingerprints identifying a result across runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifPartialFingerprints {
+    /// Hash of the rule and of the source line of the result, followed by
+    /// `:n` for its `n`-th occurrence in the same file.
+    #[serde(rename = "primaryLocationLineHash")]
+    pub primary_location_line_hash: String,
 }
 
 /// A location.
@@ -152,6 +221,16 @@ pub struct SarifRegion {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SarifArtifact {
     pub location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<SarifArtifactProperties>,
+}
+
+/// Property bag of an artifact.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifArtifactProperties {
+    /// Version of the compiler that compiled the file.
+    #[serde(rename = "compilerVersion")]
+    pub compiler_version: String,
 }
 
 /// Invocation information.
@@ -165,69 +244,65 @@ pub struct SarifInvocation {
 
 impl From<&AnalysisReport> for SarifLog {
     fn from(report: &AnalysisReport) -> Self {
+        Self::with_sources(report, &FileSystem)
+    }
+}
+
+impl SarifLog {
+    /// Build the log of `report`, reading the source lines that the
+    /// fingerprints are computed from through `sources`.
+    pub fn with_sources(report: &AnalysisReport, sources: &dyn SourceProvider) -> Self {
+        let mut registry = DetectorRegistry::new();
+        register_all_detectors(&mut registry);
+
         // Collect unique rules from bugs
-        let mut rules_map = std::collections::HashMap::new();
+        let mut rules_map = std::collections::BTreeMap::new();
         for bug in &report.bugs {
-            let rule_id = bug.kind.as_str().to_lowercase().replace(' ', "-");
-            if !rules_map.contains_key(&rule_id) {
-                rules_map.insert(
-                    rule_id.clone(),
-                    SarifRule {
-                        id: rule_id.clone(),
-                        name: bug.name.clone(),
-                        short_description: SarifMessage { text: bug.name.clone() },
-                        full_description: bug
-                            .description
-                            .clone()
-                            .map(|d| SarifMessage { text: d }),
-                        help: bug.remediation.clone().map(|r| SarifMessage { text: r }),
-                        help_uri: bug
-                            .swc_ids
-                            .first()
-                            .map(|id| format!("https://swcregistry.io/docs/SWC-{}", id)),
-                        default_configuration: SarifRuleConfiguration {
-                            level: risk_level_to_sarif(&bug.risk_level),
-                        },
-                    },
-                );
-            }
+            let rule_id = rule_id(&registry, bug);
+            rules_map
+                .entry(rule_id.clone())
+                .or_insert_with(|| sarif_rule(&registry, bug, rule_id));
         }
 
         let rules: Vec<_> = rules_map.into_values().collect();
 
+        let mut fingerprints = LineHasher::new(sources);
         let results: Vec<_> = report
-            .bugs
-            .iter()
-            .map(|bug| SarifResult {
-                rule_id: format!("{:?}", bug.kind).to_lowercase().replace(' ', "-"),
-                level: risk_level_to_sarif(&bug.risk_level),
-                message: SarifMessage {
-                    text: bug.description.clone().unwrap_or_else(|| bug.name.clone()),
-                },
-                locations: vec![SarifLocation {
-                    physical_location: SarifPhysicalLocation {
-                        artifact_location: SarifArtifactLocation {
-                            uri: bug
-                                .loc
-                                .file
-                                .clone()
-                                .unwrap_or_else(|| "unknown".to_string()),
-                        },
-                        region: SarifRegion {
-                            start_line: bug.loc.start_line,
-                            start_column: Some(bug.loc.start_col),
-                            end_line: Some(bug.loc.end_line),
-                            end_column: Some(bug.loc.end_col),
-                        },
+            .sorted_by_priority()
+            .into_iter()
+            .map(|bug| {
+                let rule_id = rule_id(&registry, bug);
+                let line_hash = fingerprints.line_hash(&rule_id, bug);
+                SarifResult {
+                    rule_id,
+                    level: risk_level_to_sarif(&bug.risk_level),
+                    message: SarifMessage {
+                        text: bug.description.clone().unwrap_or_else(|| bug.name.clone()),
                     },
-                }],
+                    locations: vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: artifact_location(&bug.loc),
+                            region: region(&bug.loc),
+                        },
+                    }],
+                    partial_fingerprints: Some(SarifPartialFingerprints {
+                        primary_location_line_hash: line_hash,
+                    }),
+                    fixes: bug.suggestion.iter().filter_map(sarif_fix).collect(),
+                }
             })
             .collect();
 
         let artifacts: Vec<_> = report
             .files_analyzed
             .iter()
-            .map(|f| SarifArtifact { location: SarifArtifactLocation { uri: f.clone() } })
+            .map(|f| SarifArtifact {
+                location: SarifArtifactLocation { uri: f.clone() },
+                properties: report
+                    .compiler_versions
+                    .get(f)
+                    .map(|v| SarifArtifactProperties { compiler_version: v.clone() }),
+            })
             .collect();
 
         SarifLog {
@@ -253,6 +328,135 @@ impl From<&AnalysisReport> for SarifLog {
     }
 }
 
+fn artifact_location(loc: &Loc) -> SarifArtifactLocation {
+    SarifArtifactLocation { uri: loc.file.clone().unwrap_or_else(|| "unknown".to_string()) }
+}
+
+fn region(loc: &Loc) -> SarifRegion {
+    SarifRegion {
+        start_line: loc.start_line,
+        start_column: Some(loc.start_col),
+        end_line: Some(loc.end_line),
+        end_column: Some(loc.end_col),
+    }
+}
+
+/// SARIF fix of a suggestion. Suggestions without a source edit have none,
+/// since a SARIF fix must change at least one file.
+fn sarif_fix(suggestion: &FixSuggestion) -> Option<SarifFix> {
+    let replacement = suggestion.replacement.as_ref()?;
+    Some(SarifFix {
+        description: SarifMessage { text: suggestion.message.clone() },
+        artifact_changes: vec![SarifArtifactChange {
+            artifact_location: artifact_location(&replacement.loc),
+            replacements: vec![SarifReplacement {
+                deleted_region: region(&replacement.loc),
+                inserted_content: SarifArtifactContent { text: replacement.text.clone() },
+            }],
+        }],
+    })
+}
+
+/// Rule id of `bug`: the id of the detector that reported it, or its kind
+/// for detectors outside the registry.
+pub(crate) fn rule_id(registry: &DetectorRegistry, bug: &Bug) -> String {
+    match registry.get(&bug.name) {
+        Some(detector) => detector.detector_id().as_str().to_string(),
+        None => bug.kind.as_str().to_lowercase().replace(' ', "-"),
+    }
+}
+
+/// Rule describing the detector that reported `bug`. Findings of unknown
+/// detectors are described from the finding itself.
+fn sarif_rule(registry: &DetectorRegistry, bug: &Bug, id: String) -> SarifRule {
+    let detector = registry.get(&bug.name);
+    let full_description = match detector {
+        Some(d) => Some(d.description().to_string()),
+        None => bug.description.clone(),
+    };
+    let help = match detector {
+        Some(d) if !d.recommendation().is_empty() => Some(d.recommendation().to_string()),
+        _ => bug.remediation.clone(),
+    };
+    let help_uri = detector
+        .and_then(|d| d.references().first().map(|r| r.to_string()))
+        .or_else(|| {
+            bug.swc_ids
+                .first()
+                .map(|id| format!("https://swcregistry.io/docs/SWC-{}", id))
+        });
+    let mut tags = vec![category_tag(&bug.kind).to_string()];
+    if let Some(d) = detector {
+        tags.extend(d.tags().into_iter().map(str::to_string));
+    }
+
+    SarifRule {
+        id,
+        name: bug.name.clone(),
+        short_description: SarifMessage { text: bug.name.clone() },
+        full_description: full_description.map(|text| SarifMessage { text }),
+        help: help.map(|text| SarifMessage { text }),
+        help_uri,
+        default_configuration: SarifRuleConfiguration {
+            level: risk_level_to_sarif(&bug.risk_level),
+        },
+        properties: Some(SarifRuleProperties { tags }),
+    }
+}
+
+/// Code-scanning category of a kind of finding.
+fn category_tag(kind: &BugKind) -> &'static str {
+    match kind {
+        BugKind::Vulnerability => "security",
+        BugKind::Optimization => "gas",
+        BugKind::Refactoring => "style",
+    }
+}
+
+/// Computes `primaryLocationLineHash` fingerprints, reading each source
+/// file once.
+struct LineHasher<'s> {
+    sources: &'s dyn SourceProvider,
+    contents: HashMap<String, Option<String>>,
+    occurrences: HashMap<(String, String), usize>,
+}
+
+impl<'s> LineHasher<'s> {
+    fn new(sources: &'s dyn SourceProvider) -> Self {
+        Self { sources, contents: HashMap::new(), occurrences: HashMap::new() }
+    }
+
+    /// Fingerprint of `bug` under `rule_id`. The hash covers the rule and the
+    /// whitespace-normalized source line, so it survives edits elsewhere in
+    /// the file; the occurrence count tells apart identical lines. Findings
+    /// whose source cannot be read are hashed from their message instead.
+    fn line_hash(&mut self, rule_id: &str, bug: &Bug) -> String {
+        let file = bug.loc.file.clone().unwrap_or_default();
+        let sources = self.sources;
+        let line = self
+            .contents
+            .entry(file.clone())
+            .or_insert_with(|| sources.read(&file).ok())
+            .as_deref()
+            .and_then(|content| content.lines().nth(bug.loc.start_line.checked_sub(1)?))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "));
+        let context =
+            line.unwrap_or_else(|| bug.description.clone().unwrap_or_else(|| bug.name.clone()));
+
+        let mut keccak = Keccak::v256();
+        keccak.update(rule_id.as_bytes());
+        keccak.update(&[0]);
+        keccak.update(context.as_bytes());
+        let mut hash = [0u8; 32];
+        keccak.finalize(&mut hash);
+        let hash: String = hash[..8].iter().map(|b| format!("{b:02x}")).collect();
+
+        let occurrence = self.occurrences.entry((file, hash.clone())).or_insert(0);
+        *occurrence += 1;
+        format!("{hash}:{occurrence}")
+    }
+}
+
 fn risk_level_to_sarif(level: &RiskLevel) -> String {
     match level {
         RiskLevel::Critical | RiskLevel::High => "error".to_string(),
@@ -264,6 +468,8 @@ fn risk_level_to_sarif(level: &RiskLevel) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bugs::bug::BugCategory;
+    use common::source::InMemorySources;
     use std::time::Duration;
 
     #[test]
@@ -274,4 +480,101 @@ mod tests {
         assert!(output.contains("\"$schema\""));
         assert!(output.contains("\"version\": \"2.1.0\""));
     }
+
+    #[test]
+    fn test_sarif_artifact_compiler_version() {
+        let files = vec!["Bank.sol".to_string(), "Lib.vy".to_string()];
+        let mut report = AnalysisReport::new(vec![], files, Duration::from_secs(1));
+        report
+            .compiler_versions
+            .insert("Bank.sol".to_string(), "0.8.20".to_string());
+        let sarif = SarifLog::from(&report);
+        let artifacts = &sarif.runs[0].artifacts;
+        assert_eq!(
+            artifacts[0]
+                .properties
+                .as_ref()
+                .map(|p| p.compiler_version.as_str()),
+            Some("0.8.20")
+        );
+        assert!(artifacts[1].properties.is_none());
+    }
+
+    fn tx_origin_bug(line: usize) -> Bug {
+        Bug::new(
+            "Dangerous use of tx.origin",
+            Some("tx.origin compared with 'owner'"),
+            Loc::new(line, 9, line, 40).with_file("Wallet.sol".to_string()),
+            BugKind::Vulnerability,
+            BugCategory::AccessControl,
+            RiskLevel::High,
+            vec![477],
+            vec![115],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_sarif_rule_metadata_and_tags() {
+        let report = AnalysisReport::new(vec![tx_origin_bug(2)], vec![], Duration::from_secs(1));
+        let sarif = SarifLog::with_sources(&report, &InMemorySources::new());
+        let run = &sarif.runs[0];
+        let rule = &run.tool.driver.rules[0];
+        assert_eq!(rule.id, "tx-origin");
+        assert_eq!(run.results[0].rule_id, "tx-origin");
+        assert!(rule.full_description.is_some());
+        assert!(rule.help_uri.is_some());
+        let tags = &rule.properties.as_ref().unwrap().tags;
+        assert_eq!(tags[0], "security");
+    }
+
+    #[test]
+    fn test_sarif_fingerprint_follows_line_content() {
+        let before = "contract Wallet {\n  require(tx.origin == owner);\n}\n";
+        let after = "// moved\ncontract Wallet {\n    require(tx.origin ==  owner);\n}\n";
+        let hash = |source: &str, line: usize| {
+            let sources = InMemorySources::new().with_file("Wallet.sol", source);
+            let bugs = vec![tx_origin_bug(line), tx_origin_bug(line)];
+            let report = AnalysisReport::new(bugs, vec![], Duration::from_secs(1));
+            let sarif = SarifLog::with_sources(&report, &sources);
+            sarif.runs[0]
+                .results
+                .iter()
+                .map(|r| {
+                    r.partial_fingerprints
+                        .as_ref()
+                        .unwrap()
+                        .primary_location_line_hash
+                        .clone()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let old = hash(before, 2);
+        assert_eq!(old, hash(after, 3));
+        assert!(old[0].ends_with(":1") && old[1].ends_with(":2"));
+        assert_ne!(old, hash(before, 1));
+    }
+
+    #[test]
+    fn test_sarif_fixes() {
+        let loc = Loc::new(2, 9, 2, 40).with_file("Wallet.sol".to_string());
+        let fixed = tx_origin_bug(2).with_suggestion(FixSuggestion::replace(
+            "Compare msg.sender instead.",
+            loc,
+            "require(msg.sender == owner);",
+        ));
+        let advised = tx_origin_bug(3).with_suggestion(FixSuggestion::new("Use msg.sender."));
+        let report = AnalysisReport::new(vec![fixed, advised], vec![], Duration::from_secs(1));
+        let sarif = SarifLog::with_sources(&report, &InMemorySources::new());
+        let results = &sarif.runs[0].results;
+
+        let fix = &results[0].fixes[0];
+        assert_eq!(fix.description.text, "Compare msg.sender instead.");
+        let change = &fix.artifact_changes[0];
+        assert_eq!(change.artifact_location.uri, "Wallet.sol");
+        assert_eq!(change.replacements[0].deleted_region.start_column, Some(9));
+        assert_eq!(change.replacements[0].inserted_content.text, "require(msg.sender == owner);");
+        assert!(results[1].fixes.is_empty());
+    }
 }
@@ -0,0 +1,111 @@
+//! Summary-only output formatter.
+
+use crate::output::formatter::{AnalysisReport, OutputFormatter, format_contract_metrics};
+
+/// Plain-text formatter that renders only aggregate counts, by severity and
+/// by detector, for dashboards and CI gates. Individual findings are never
+/// listed, and the output is built from [`AnalysisReport::stats`] alone.
+#[derive(Debug, Default)]
+pub struct SummaryFormatter;
+
+impl SummaryFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl OutputFormatter for SummaryFormatter {
+    fn format(&self, report: &AnalysisReport) -> String {
+        let severity = &report.stats.bugs_by_severity;
+        let total =
+            severity.critical + severity.high + severity.medium + severity.low + severity.info;
+
+        let mut output = String::new();
+        output.push_str(&format!("Files analyzed: {}\n", report.files_analyzed.len()));
+        output.push_str(&format!("Total findings: {}\n", total));
+        output.push_str(&format!("  Critical: {}\n", severity.critical));
+        output.push_str(&format!("  High: {}\n", severity.high));
+        output.push_str(&format!("  Medium: {}\n", severity.medium));
+        output.push_str(&format!("  Low: {}\n", severity.low));
+        output.push_str(&format!("  Info: {}\n", severity.info));
+
+        if !report.stats.bugs_by_detector.is_empty() {
+            output.push_str("Findings by detector:\n");
+            for (detector, count) in &report.stats.bugs_by_detector {
+                output.push_str(&format!("  {}: {}\n", detector, count));
+            }
+        }
+
+        if !report.timed_out.is_empty() {
+            output.push_str(&format!("Timed out: {}\n", report.timed_out.len()));
+        }
+
+        output.push_str(&format_contract_metrics(report));
+
+        output
+    }
+
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "text/plain"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passes::sir::ContractMetrics;
+    use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+    use common::loc::Loc;
+    use std::time::Duration;
+
+    fn bug(name: &str, risk: RiskLevel, message: &str) -> Bug {
+        Bug::new(
+            name,
+            Some(message),
+            Loc::new(1, 1, 1, 10),
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            risk,
+            vec![],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_summary_counts_without_findings() {
+        let bugs = vec![
+            bug("Reentrancy", RiskLevel::High, "'Bank.withdraw' re-enters"),
+            bug("Reentrancy", RiskLevel::High, "'Bank.claim' re-enters"),
+            bug("Tx Origin", RiskLevel::Medium, "'Bank.auth' uses tx.origin"),
+        ];
+        let report =
+            AnalysisReport::new(bugs, vec!["Bank.sol".to_string()], Duration::from_secs(1));
+        let output = SummaryFormatter::new().format(&report);
+
+        assert!(output.contains("Total findings: 3\n"));
+        assert!(output.contains("  High: 2\n"));
+        assert!(output.contains("  Medium: 1\n"));
+        assert!(output.contains("  Reentrancy: 2\n"));
+        assert!(output.contains("  Tx Origin: 1\n"));
+        assert!(!output.contains("re-enters"));
+        assert!(!output.contains("tx.origin"));
+    }
+
+    #[test]
+    fn test_summary_contract_metrics() {
+        let mut report = AnalysisReport::new(vec![], vec![], Duration::from_secs(1));
+        report.contract_metrics.insert(
+            "Bank".to_string(),
+            ContractMetrics { functions: 3, cyclomatic_complexity: 7, ..Default::default() },
+        );
+        let output = SummaryFormatter::new().format(&report);
+
+        assert!(output.contains("Contract metrics:\n  Bank: 3 functions, 0 external calls"));
+        assert!(output.contains("cyclomatic complexity 7\n"));
+    }
+}
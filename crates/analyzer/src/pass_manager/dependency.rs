@@ -136,6 +136,46 @@ impl DependencyGraph {
         Ok(())
     }
 
+    /// Find a dependency cycle, if any.
+    ///
+    /// Returns the passes of the cycle in dependency order, the first pass
+    /// repeated at the end: `[a, b, a]` when `a` depends on `b` and `b` on
+    /// `a`.
+    pub fn find_cycle(&self) -> Option<Vec<TypeId>> {
+        let mut visited = HashSet::new();
+        let mut path = Vec::new();
+        self.passes
+            .iter()
+            .find_map(|&pass_id| self.find_cycle_from(pass_id, &mut visited, &mut path))
+    }
+
+    fn find_cycle_from(
+        &self,
+        pass_id: TypeId,
+        visited: &mut HashSet<TypeId>,
+        path: &mut Vec<TypeId>,
+    ) -> Option<Vec<TypeId>> {
+        if let Some(start) = path.iter().position(|&p| p == pass_id) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(pass_id);
+            return Some(cycle);
+        }
+        if !visited.insert(pass_id) {
+            return None;
+        }
+
+        path.push(pass_id);
+        if let Some(deps) = self.dependencies.get(&pass_id) {
+            for dep in deps {
+                if let Some(cycle) = self.find_cycle_from(*dep, visited, path) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+        None
+    }
+
     /// Compute execution levels for parallel execution.
     ///
     /// Returns a vector of levels, where each level contains passes
@@ -267,5 +307,10 @@ mod tests {
 
         let result = graph.topological_sort();
         assert!(result.is_err());
+
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(cycle.contains(&id_a) && cycle.contains(&id_b));
     }
 }
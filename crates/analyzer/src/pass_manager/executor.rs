@@ -27,6 +27,9 @@ pub struct ExecutorConfig {
 
     /// Enable detailed timing.
     pub timing: bool,
+
+    /// Passes not started by this instant are skipped.
+    pub deadline: Option<Instant>,
 }
 
 impl Default for ExecutorConfig {
@@ -36,6 +39,7 @@ impl Default for ExecutorConfig {
             max_workers: 0, // auto-detect
             fail_fast: true,
             timing: true,
+            deadline: None,
         }
     }
 }
@@ -55,6 +59,9 @@ pub struct ExecutionResult {
     /// Number of failed passes.
     pub failed: usize,
 
+    /// Number of passes skipped because the deadline had passed.
+    pub timed_out: usize,
+
     /// Errors encountered.
     pub errors: Vec<PassError>,
 }
@@ -103,6 +110,7 @@ impl PassExecutor {
         let mut errors = Vec::new();
         let mut successful = 0;
         let mut failed = 0;
+        let timed_out_before = context.stats.passes_timed_out;
 
         for (level_idx, level) in schedule.levels.iter().enumerate() {
             log::debug!("Executing level {} ({} passes)", level_idx, level.len());
@@ -137,6 +145,7 @@ impl PassExecutor {
             total_duration: start.elapsed(),
             successful,
             failed,
+            timed_out: context.stats.passes_timed_out - timed_out_before,
             errors,
         })
     }
@@ -186,6 +195,13 @@ impl PassExecutor {
         let start = Instant::now();
         let name = pass.name().to_string();
 
+        // Skip if the deadline has passed
+        if self.config.deadline.is_some_and(|d| start > d) {
+            log::debug!("Pass '{}' skipped: timeout exceeded", name);
+            context.stats.passes_timed_out += 1;
+            return Ok(None);
+        }
+
         log::info!("Running pass: {} ({:?})", name, pass_id);
 
         match pass.run(context) {
@@ -265,6 +281,7 @@ mod tests {
             total_duration: std::time::Duration::from_millis(100),
             successful: 5,
             failed: 0,
+            timed_out: 0,
             errors: vec![],
         };
         assert!(result.is_success());
@@ -8,9 +8,10 @@
 
 use crate::pass_manager::dependency::DependencyGraph;
 use crate::passes::base::meta::PassRepresentation;
-use crate::passes::base::{Pass, PassResult};
+use crate::passes::base::{Pass, PassError, PassResult};
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
 
 /// Execution level containing passes grouped by representation.
 #[derive(Debug, Clone, Default)]
@@ -69,6 +70,9 @@ pub struct PassScheduler {
     /// Pass representations.
     representations: HashMap<TypeId, PassRepresentation>,
 
+    /// Pass names, for diagnostics.
+    names: HashMap<TypeId, &'static str>,
+
     /// Registered pass IDs.
     registered_passes: HashSet<TypeId>,
 }
@@ -85,6 +89,7 @@ impl PassScheduler {
         Self {
             dependency_graph: DependencyGraph::new(),
             representations: HashMap::new(),
+            names: HashMap::new(),
             registered_passes: HashSet::new(),
         }
     }
@@ -95,6 +100,7 @@ impl PassScheduler {
 
         self.registered_passes.insert(pass_id);
         self.representations.insert(pass_id, pass.representation());
+        self.names.insert(pass_id, pass.name());
         self.dependency_graph.add_pass(pass_id);
 
         for dep in pass.dependencies() {
@@ -111,6 +117,11 @@ impl PassScheduler {
 
     /// Compute the execution schedule.
     pub fn compute_schedule(&self) -> PassResult<ExecutionSchedule> {
+        if let Some(cycle) = self.dependency_graph.find_cycle() {
+            let names: Vec<String> = cycle.iter().map(|id| self.pass_name(id)).collect();
+            return Err(PassError::CircularDependency(names.join(" -> ")));
+        }
+
         // Get base levels from dependency graph
         let base_levels = self.dependency_graph.compute_levels()?;
 
@@ -137,6 +148,68 @@ impl PassScheduler {
         Ok(ExecutionSchedule { levels })
     }
 
+    /// The passes in the order they run: level by level, dependencies
+    /// before dependents, and by name within a level. Fails with the passes
+    /// of a dependency cycle, if any.
+    pub fn execution_plan(&self) -> PassResult<Vec<TypeId>> {
+        Ok(self.sorted_levels()?.into_iter().flatten().collect())
+    }
+
+    /// Describe the execution plan: each pass with the execution level
+    /// assigned to it, its representation and its declared dependencies,
+    /// or the passes of a dependency cycle.
+    pub fn explain(&self) -> String {
+        let levels = match self.sorted_levels() {
+            Ok(levels) => levels,
+            Err(err) => return format!("{err}\n"),
+        };
+
+        let mut output = String::new();
+        for (level, passes) in levels.iter().enumerate() {
+            for id in passes {
+                let repr = self
+                    .representations
+                    .get(id)
+                    .map_or("unregistered", |repr| repr.as_str());
+                let _ = write!(output, "level {}: {} [{}]", level, self.pass_name(id), repr);
+                let mut deps: Vec<String> = self
+                    .dependency_graph
+                    .get_dependencies(id)
+                    .iter()
+                    .map(|dep| self.pass_name(dep))
+                    .collect();
+                if !deps.is_empty() {
+                    deps.sort();
+                    let _ = write!(output, " <- {}", deps.join(", "));
+                }
+                output.push('\n');
+            }
+        }
+        output
+    }
+
+    /// The passes of each level of the schedule, by name.
+    fn sorted_levels(&self) -> PassResult<Vec<Vec<TypeId>>> {
+        let schedule = self.compute_schedule()?;
+        Ok(schedule
+            .levels
+            .iter()
+            .map(|level| {
+                let mut passes = level.all_passes();
+                passes.sort_by_key(|id| self.pass_name(id));
+                passes
+            })
+            .collect())
+    }
+
+    /// Name of a pass, or its ID if it is only known as a dependency.
+    fn pass_name(&self, pass_id: &TypeId) -> String {
+        match self.names.get(pass_id) {
+            Some(name) => name.to_string(),
+            None => format!("{pass_id:?}"),
+        }
+    }
+
     /// Get passes that can be executed given current completion state.
     pub fn get_ready_passes(&self, completed: &HashSet<TypeId>) -> Vec<TypeId> {
         self.registered_passes
@@ -165,6 +238,7 @@ impl PassScheduler {
     pub fn clear(&mut self) {
         self.dependency_graph.clear();
         self.representations.clear();
+        self.names.clear();
         self.registered_passes.clear();
     }
 }
@@ -249,4 +323,82 @@ mod tests {
 
         assert!(!schedule.is_empty());
     }
+
+    #[test]
+    fn test_execution_plan_and_explain() {
+        let mut scheduler = PassScheduler::new();
+        scheduler.register_pass(&MockIrCallGraphPass);
+        scheduler.register_pass(&MockIrCfgPass);
+        scheduler.register_pass(&MockCfgPass);
+
+        let plan = scheduler.execution_plan().unwrap();
+        assert_eq!(
+            plan,
+            vec![
+                TypeId::of::<MockCfgPass>(),
+                TypeId::of::<MockIrCallGraphPass>(),
+                TypeId::of::<MockIrCfgPass>(),
+            ]
+        );
+        assert_eq!(
+            scheduler.explain(),
+            "level 0: MockCfgPass [IR]\n\
+             level 1: MockIrCallGraphPass [IR] <- MockCfgPass\n\
+             level 1: MockIrCfgPass [IR] <- MockCfgPass\n"
+        );
+    }
+
+    struct MockCyclicPass;
+    impl Pass for MockCyclicPass {
+        fn name(&self) -> &'static str {
+            "MockCyclicPass"
+        }
+        fn description(&self) -> &'static str {
+            "Mock pass depending on a pass depending on it"
+        }
+        fn level(&self) -> PassLevel {
+            PassLevel::Function
+        }
+        fn representation(&self) -> PassRepresentation {
+            PassRepresentation::Air
+        }
+        fn dependencies(&self) -> Vec<TypeId> {
+            vec![TypeId::of::<MockCyclicDepPass>()]
+        }
+    }
+
+    struct MockCyclicDepPass;
+    impl Pass for MockCyclicDepPass {
+        fn name(&self) -> &'static str {
+            "MockCyclicDepPass"
+        }
+        fn description(&self) -> &'static str {
+            "Mock pass depending on a pass depending on it"
+        }
+        fn level(&self) -> PassLevel {
+            PassLevel::Function
+        }
+        fn representation(&self) -> PassRepresentation {
+            PassRepresentation::Air
+        }
+        fn dependencies(&self) -> Vec<TypeId> {
+            vec![TypeId::of::<MockCyclicPass>()]
+        }
+    }
+
+    #[test]
+    fn test_dependency_cycle_names_passes() {
+        let mut scheduler = PassScheduler::new();
+        scheduler.register_pass(&MockCfgPass);
+        scheduler.register_pass(&MockCyclicPass);
+        scheduler.register_pass(&MockCyclicDepPass);
+
+        let err = scheduler.execution_plan().unwrap_err().to_string();
+        let cycle = err.strip_prefix("Circular dependency detected: ").unwrap();
+        let names: Vec<&str> = cycle.split(" -> ").collect();
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[0], names[2]);
+        assert!(names.contains(&"MockCyclicPass") && names.contains(&"MockCyclicDepPass"));
+        assert_eq!(scheduler.explain(), format!("{err}\n"));
+    }
 }
@@ -0,0 +1,256 @@
+//! Access Control Analysis Pass
+//!
+//! Finds, for each BIR function, the checks on `msg.sender` that guard the
+//! code after them: branches on a condition depending on `msg.sender`, such
+//! as those `require(msg.sender == owner)` and inlined `onlyOwner`-style
+//! modifiers lower to, and assertions of such conditions. Code that a
+//! transaction can only reach through one of these checks is
+//! access-controlled.
+//!
+//! The dependency on `msg.sender` is traced along operands and, since BIR
+//! reads variables by name, from a variable read to every op defining the
+//! variable, so that `address caller = msg.sender;` followed by
+//! `require(caller == owner);` is recognized as well.
+
+use crate::context::{AnalysisContext, ContextKey};
+use crate::passes::base::meta::{PassLevel, PassRepresentation};
+use crate::passes::base::{AnalysisPass, Pass, PassResult};
+use crate::passes::bir::def_use::collect_operands;
+use scirs::bir::cfg::{BlockId, Function, Terminator};
+use scirs::bir::ops::{Op, OpId, OpKind, OpRef};
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+// ═══════════════════════════════════════════════════════════════════
+// Artifact
+// ═══════════════════════════════════════════════════════════════════
+
+/// Artifact key for the access control analysis.
+///
+/// Maps function name → checks on `msg.sender` in the function.
+pub struct AccessControlArtifact;
+
+impl ContextKey for AccessControlArtifact {
+    type Value = HashMap<String, AccessGuards>;
+    const NAME: &'static str = "access_control";
+}
+
+/// Checks on `msg.sender` in a function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessGuards {
+    /// Blocks branching on a condition that depends on `msg.sender`.
+    pub branches: HashSet<BlockId>,
+
+    /// Assertions of a condition that depends on `msg.sender`.
+    pub asserts: HashSet<OpId>,
+}
+
+impl AccessGuards {
+    /// Find the checks on `msg.sender` in `func`.
+    pub fn of(func: &Function) -> Self {
+        let mut ops: HashMap<OpId, &Op> = HashMap::new();
+        let mut defs: HashMap<&str, Vec<OpId>> = HashMap::new();
+        for op in func.blocks.iter().flat_map(|block| &block.ops) {
+            ops.insert(op.id, op);
+            if let Some((name, _)) = &op.result {
+                defs.entry(name.base.as_str()).or_default().push(op.id);
+            }
+        }
+        let depends_on_sender = |start: OpId| {
+            let mut seen = HashSet::from([start]);
+            let mut stack = vec![start];
+            while let Some(id) = stack.pop() {
+                let Some(op) = ops.get(&id) else {
+                    continue;
+                };
+                if is_msg_sender(op) {
+                    return true;
+                }
+                let mut sources: Vec<OpId> = collect_operands(&op.kind)
+                    .into_iter()
+                    .map(|OpRef(id)| id)
+                    .collect();
+                if let OpKind::PseudoValue { label, .. } = &op.kind
+                    && let Some(label_defs) = defs.get(label.as_str())
+                {
+                    sources.extend(label_defs);
+                }
+                stack.extend(sources.into_iter().filter(|id| seen.insert(*id)));
+            }
+            false
+        };
+
+        let mut guards = AccessGuards::default();
+        for block in &func.blocks {
+            if let Terminator::Branch { cond: OpRef(cond), .. } = block.term
+                && depends_on_sender(cond)
+            {
+                guards.branches.insert(block.id);
+            }
+            for op in &block.ops {
+                if let OpKind::Assert { cond: OpRef(cond) } = op.kind
+                    && depends_on_sender(cond)
+                {
+                    guards.asserts.insert(op.id);
+                }
+            }
+        }
+        guards
+    }
+
+    /// Returns `true` if the function has no check on `msg.sender`.
+    pub fn is_empty(&self) -> bool {
+        self.branches.is_empty() && self.asserts.is_empty()
+    }
+}
+
+/// Returns `true` if `op` reads `msg.sender`.
+fn is_msg_sender(op: &Op) -> bool {
+    match &op.kind {
+        OpKind::TaintSrc(src) => src.op_name == "msg_sender",
+        OpKind::Opaque { description } => description.contains("evm.msg_sender()"),
+        _ => false,
+    }
+}
+
+/// Compute the checks on `msg.sender` of every function of `modules`.
+pub fn compute_access_guards(modules: &[scirs::bir::Module]) -> HashMap<String, AccessGuards> {
+    modules
+        .iter()
+        .flat_map(|module| &module.functions)
+        .map(|func| (func.id.0.clone(), AccessGuards::of(func)))
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Pass
+// ═══════════════════════════════════════════════════════════════════
+
+/// Access control analysis pass.
+pub struct AccessControlPass;
+
+impl Pass for AccessControlPass {
+    fn name(&self) -> &'static str {
+        "access-control"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find the checks on msg.sender guarding each BIR function"
+    }
+
+    fn level(&self) -> PassLevel {
+        PassLevel::Function
+    }
+
+    fn representation(&self) -> PassRepresentation {
+        PassRepresentation::Air
+    }
+
+    fn dependencies(&self) -> Vec<TypeId> {
+        vec![]
+    }
+}
+
+impl AnalysisPass for AccessControlPass {
+    fn run(&self, ctx: &mut AnalysisContext) -> PassResult<()> {
+        let guards = compute_access_guards(ctx.air_units());
+        ctx.store::<AccessControlArtifact>(guards);
+        ctx.mark_pass_completed(self.id());
+        Ok(())
+    }
+
+    fn is_completed(&self, ctx: &AnalysisContext) -> bool {
+        ctx.is_pass_completed(self.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::AnalysisConfig;
+    use scirs::sir::dialect::evm::{EvmExpr, EvmMsgSender, EvmType};
+    use scirs::sir::*;
+
+    fn address(name: &str) -> Expr {
+        let ty = Type::Dialect(DialectType::Evm(EvmType::Address));
+        Expr::Var(VarExpr::new(name.to_string(), ty, None))
+    }
+
+    /// `require(lhs == owner); total = 1;`
+    fn require_owner(lhs: Expr) -> Vec<Stmt> {
+        let cond = Expr::BinOp(BinOpExpr {
+            op: BinOp::Eq,
+            lhs: Box::new(lhs),
+            rhs: Box::new(address("owner")),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        });
+        vec![
+            Stmt::If(IfStmt {
+                cond: Expr::UnOp(UnOpExpr { op: UnOp::Not, operand: Box::new(cond), span: None }),
+                then_body: vec![Stmt::Revert(RevertStmt {
+                    error: None,
+                    args: vec![],
+                    span: None,
+                })],
+                else_body: None,
+                span: None,
+            }),
+            Stmt::Assign(AssignStmt {
+                lhs: Expr::Var(VarExpr::new("total".to_string(), Type::I256, None)),
+                rhs: Expr::Lit(Lit::Num(NumLit::new(Num::Int(IntNum::one()), None))),
+                span: None,
+            }),
+        ]
+    }
+
+    fn guards(functions: Vec<(&str, Vec<Stmt>)>) -> HashMap<String, AccessGuards> {
+        let members = functions
+            .into_iter()
+            .map(|(name, body)| {
+                MemberDecl::Function(FunctionDecl::new(
+                    name.to_string(),
+                    vec![],
+                    vec![],
+                    Some(body),
+                    None,
+                ))
+            })
+            .collect();
+        let contract = ContractDecl::new("Wallet".to_string(), members, None);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(contract)],
+        };
+        let mut ctx = AnalysisContext::new(vec![module], AnalysisConfig::default());
+        AccessControlPass.run(&mut ctx).unwrap();
+        ctx.get::<AccessControlArtifact>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_access_control_pass() {
+        let sender = Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgSender(EvmMsgSender {
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        let caller = Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl {
+                name: "caller".to_string(),
+                ty: Type::Dialect(DialectType::Evm(EvmType::Address)),
+            })],
+            init: Some(sender.clone()),
+            span: None,
+        });
+        let mut through_local = vec![caller];
+        through_local.extend(require_owner(address("caller")));
+
+        let guards = guards(vec![
+            ("direct", require_owner(sender)),
+            ("local", through_local),
+            ("other", require_owner(address("spender"))),
+        ]);
+        assert_eq!(guards["Wallet.direct"].branches.len(), 1);
+        assert_eq!(guards["Wallet.local"].branches.len(), 1);
+        assert!(guards["Wallet.other"].is_empty());
+    }
+}
@@ -96,7 +96,7 @@ impl AnalysisPass for DefUsePass {
 }
 
 /// Extract all OpRef operands from an OpKind.
-fn collect_operands(kind: &OpKind) -> Vec<OpRef> {
+pub(crate) fn collect_operands(kind: &OpKind) -> Vec<OpRef> {
     match kind {
         OpKind::BinOp { lhs, rhs, .. } => vec![*lhs, *rhs],
         OpKind::UnOp { operand, .. } => vec![*operand],
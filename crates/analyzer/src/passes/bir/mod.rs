@@ -1,5 +1,6 @@
 //! BIR-layer analysis passes.
 
+pub mod access_control;
 pub mod def_use;
 pub mod dominance;
 pub mod icfg;
@@ -7,9 +8,10 @@ pub mod interval;
 pub mod taint;
 pub mod taint_propagation;
 
+pub use access_control::{AccessControlArtifact, AccessControlPass, AccessGuards};
 pub use def_use::{DefUseArtifact, DefUsePass};
 pub use dominance::{DominanceArtifact, DominancePass};
 pub use icfg::{ICFGArtifact, ICFGPass};
 pub use interval::{Interval, IntervalArtifact, IntervalPass};
-pub use taint::{TaintArtifact, TaintPass};
+pub use taint::{TaintArtifact, TaintLabels, TaintPass};
 pub use taint_propagation::TaintPropagationPass;
@@ -1,16 +1,21 @@
 //! Extended Taint Analysis Pass
 //!
 //! Builds on the existing `TaintPropagationPass` but stores the result
-//! as a typed `TaintArtifact` (set of taint labels per `OpId`).
+//! as a typed `TaintArtifact` (set of taint labels per op of each function).
 //!
 //! Extended sources: TxOrigin, Timestamp, MsgValue, ExternalCallReturn.
 //! Extended sinks:  branch conditions, storage writes, arithmetic operands.
+//!
+//! An optional, depth-limited interprocedural mode carries taint through
+//! the calls of the BIR call graph, see [`TaintPass::interprocedural`].
 
 use crate::context::{AnalysisContext, ContextKey};
 use crate::passes::base::meta::{PassLevel, PassRepresentation};
 use crate::passes::base::{AnalysisPass, Pass, PassResult};
 use crate::passes::bir::icfg::ICFGPass;
-use scirs::bir::interfaces::TaintLabel;
+use scirs::bir::Module;
+use scirs::bir::cfg::{Function, FunctionId};
+use scirs::bir::interfaces::{CallTarget, TaintLabel};
 use scirs::bir::ops::{OpId, OpKind};
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
@@ -21,20 +26,57 @@ use std::collections::{HashMap, HashSet};
 
 /// Artifact key for extended taint analysis.
 ///
-/// Maps `OpId` → set of `TaintLabel` that reach this op.
+/// Maps `(FunctionId, OpId)` → set of `TaintLabel` that reach this op. Op
+/// IDs are only unique within a function, hence the function in the key.
 pub struct TaintArtifact;
 
 impl ContextKey for TaintArtifact {
-    type Value = HashMap<OpId, HashSet<TaintLabel>>;
+    type Value = TaintLabels;
     const NAME: &'static str = "taint";
 }
 
+/// Taint labels per op of each function.
+pub type TaintLabels = HashMap<(FunctionId, OpId), HashSet<TaintLabel>>;
+
 // ═══════════════════════════════════════════════════════════════════
 // Pass
 // ═══════════════════════════════════════════════════════════════════
 
+/// Most rounds of each fixed-point propagation.
+const MAX_ITERATIONS: usize = 100;
+
 /// Extended taint analysis pass.
-pub struct TaintPass;
+///
+/// By default taint stops at call boundaries. With
+/// [`interprocedural`](Self::interprocedural), calls resolved by the call
+/// graph of the module (same-contract and library calls) map the taint of
+/// their arguments to the callee's parameters and the taint of the callee's
+/// returned values back to the call, following chains of up to `max_depth`
+/// calls.
+///
+/// The interprocedural mode is context-insensitive: the parameters of a
+/// callee join the taint of all its call sites, so a value returned to one
+/// caller may carry the taint passed by another. Each level of calls costs
+/// one more propagation over every function of the module; the depth limit
+/// also bounds the work on recursive calls.
+#[derive(Debug, Default)]
+pub struct TaintPass {
+    /// Most calls followed from a taint source; 0 keeps the analysis
+    /// intraprocedural.
+    pub interprocedural_depth: usize,
+}
+
+impl TaintPass {
+    /// Intraprocedural taint analysis.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Taint analysis following chains of up to `max_depth` calls.
+    pub fn interprocedural(max_depth: usize) -> Self {
+        Self { interprocedural_depth: max_depth }
+    }
+}
 
 impl Pass for TaintPass {
     fn name(&self) -> &'static str {
@@ -60,40 +102,49 @@ impl Pass for TaintPass {
 
 impl AnalysisPass for TaintPass {
     fn run(&self, ctx: &mut AnalysisContext) -> PassResult<()> {
-        let mut taint_map: HashMap<OpId, HashSet<TaintLabel>> = HashMap::new();
+        let mut taint_map = TaintLabels::new();
 
         for module in ctx.air_units() {
-            // Phase 1: Seed taint sources from taint graph and ops
-            for seed in &module.taint_graph.seeds {
-                taint_map.entry(seed.op).or_default().insert(seed.label);
-            }
-
-            // Also seed from TaintSrc ops in functions
+            // Phase 1: Seed taint sources. The seeds of the taint graph only
+            // carry an op ID, so the source ops are found in each function.
             for func in &module.functions {
                 for block in &func.blocks {
                     for op in &block.ops {
-                        if let OpKind::TaintSrc(src) = &op.kind {
-                            taint_map.entry(op.id).or_default().insert(src.label);
-                        }
+                        let label = match &op.kind {
+                            OpKind::TaintSrc(src) => src.label,
+                            OpKind::Storage(storage) if !storage.is_write => {
+                                TaintLabel::StorageLoaded
+                            }
+                            _ => continue,
+                        };
+                        taint_map
+                            .entry((func.id.clone(), op.id))
+                            .or_default()
+                            .insert(label);
                     }
                 }
             }
 
-            // Phase 2: Propagate through taint graph edges (fixed-point)
+            // Phase 2: Propagate through taint graph edges (fixed-point),
+            // within each function defining both ops
             let mut changed = true;
             let mut iteration = 0;
-            const MAX_ITERATIONS: usize = 100;
 
             while changed && iteration < MAX_ITERATIONS {
                 changed = false;
                 iteration += 1;
 
-                for &(src, dst) in &module.taint_graph.propagation {
-                    if let Some(src_labels) = taint_map.get(&src).cloned() {
-                        let entry = taint_map.entry(dst).or_default();
-                        for label in src_labels {
-                            if entry.insert(label) {
-                                changed = true;
+                for func in &module.functions {
+                    for &(src, dst) in &module.taint_graph.propagation {
+                        if !has_op(func, dst) {
+                            continue;
+                        }
+                        if let Some(src_labels) = taint_map.get(&(func.id.clone(), src)).cloned() {
+                            let entry = taint_map.entry((func.id.clone(), dst)).or_default();
+                            for label in src_labels {
+                                if entry.insert(label) {
+                                    changed = true;
+                                }
                             }
                         }
                     }
@@ -101,61 +152,93 @@ impl AnalysisPass for TaintPass {
             }
 
             // Phase 3: Also propagate through SSA def-use within functions
-            changed = true;
-            iteration = 0;
-            while changed && iteration < MAX_ITERATIONS {
-                changed = false;
-                iteration += 1;
+            propagate_intraprocedural(&module.functions, &mut taint_map);
 
-                for func in &module.functions {
-                    for block in &func.blocks {
-                        for op in &block.ops {
-                            // For BinOp: propagate labels from both operands
-                            if let OpKind::BinOp { lhs, rhs, .. } = &op.kind {
-                                let mut labels = HashSet::new();
-                                if let Some(l) = taint_map.get(&lhs.0) {
-                                    labels.extend(l.iter());
-                                }
-                                if let Some(r) = taint_map.get(&rhs.0) {
-                                    labels.extend(r.iter());
-                                }
-                                if !labels.is_empty() {
-                                    let entry = taint_map.entry(op.id).or_default();
-                                    for label in labels {
-                                        if entry.insert(label) {
-                                            changed = true;
-                                        }
-                                    }
+            // Phase 4: Propagate across the calls of the call graph, one
+            // more level of calls per round
+            for _ in 0..self.interprocedural_depth {
+                if !propagate_calls(module, &mut taint_map) {
+                    break;
+                }
+                propagate_intraprocedural(&module.functions, &mut taint_map);
+            }
+        }
+
+        ctx.store::<TaintArtifact>(taint_map);
+        ctx.mark_pass_completed(self.id());
+        Ok(())
+    }
+
+    fn is_completed(&self, ctx: &AnalysisContext) -> bool {
+        ctx.is_pass_completed(self.id())
+    }
+}
+
+/// Returns `true` if `func` defines an op with ID `id`.
+fn has_op(func: &Function, id: OpId) -> bool {
+    func.blocks
+        .iter()
+        .flat_map(|block| &block.ops)
+        .any(|op| op.id == id)
+}
+
+/// Propagate taint through SSA def-use within each of `functions` until a
+/// fixed point.
+fn propagate_intraprocedural(functions: &[Function], taint_map: &mut TaintLabels) {
+    let mut changed = true;
+    let mut iteration = 0;
+    while changed && iteration < MAX_ITERATIONS {
+        changed = false;
+        iteration += 1;
+
+        for func in functions {
+            let key = |id: OpId| (func.id.clone(), id);
+            for block in &func.blocks {
+                for op in &block.ops {
+                    // For BinOp: propagate labels from both operands
+                    if let OpKind::BinOp { lhs, rhs, .. } = &op.kind {
+                        let mut labels = HashSet::new();
+                        if let Some(l) = taint_map.get(&key(lhs.0)) {
+                            labels.extend(l.iter());
+                        }
+                        if let Some(r) = taint_map.get(&key(rhs.0)) {
+                            labels.extend(r.iter());
+                        }
+                        if !labels.is_empty() {
+                            let entry = taint_map.entry(key(op.id)).or_default();
+                            for label in labels {
+                                if entry.insert(label) {
+                                    changed = true;
                                 }
                             }
+                        }
+                    }
 
-                            // For UnOp: propagate from operand
-                            if let OpKind::UnOp { operand, .. } = &op.kind {
-                                if let Some(labels) = taint_map.get(&operand.0).cloned() {
-                                    let entry = taint_map.entry(op.id).or_default();
-                                    for label in labels {
-                                        if entry.insert(label) {
-                                            changed = true;
-                                        }
-                                    }
-                                }
+                    // For UnOp: propagate from operand
+                    if let OpKind::UnOp { operand, .. } = &op.kind
+                        && let Some(labels) = taint_map.get(&key(operand.0)).cloned()
+                    {
+                        let entry = taint_map.entry(key(op.id)).or_default();
+                        for label in labels {
+                            if entry.insert(label) {
+                                changed = true;
                             }
+                        }
+                    }
 
-                            // For Phi: union from all incoming
-                            if let OpKind::Phi(args) = &op.kind {
-                                let mut labels = HashSet::new();
-                                for (_, incoming) in args {
-                                    if let Some(l) = taint_map.get(&incoming.0) {
-                                        labels.extend(l.iter());
-                                    }
-                                }
-                                if !labels.is_empty() {
-                                    let entry = taint_map.entry(op.id).or_default();
-                                    for label in labels {
-                                        if entry.insert(label) {
-                                            changed = true;
-                                        }
-                                    }
+                    // For Phi: union from all incoming
+                    if let OpKind::Phi(args) = &op.kind {
+                        let mut labels = HashSet::new();
+                        for (_, incoming) in args {
+                            if let Some(l) = taint_map.get(&key(incoming.0)) {
+                                labels.extend(l.iter());
+                            }
+                        }
+                        if !labels.is_empty() {
+                            let entry = taint_map.entry(key(op.id)).or_default();
+                            for label in labels {
+                                if entry.insert(label) {
+                                    changed = true;
                                 }
                             }
                         }
@@ -163,15 +246,93 @@ impl AnalysisPass for TaintPass {
                 }
             }
         }
+    }
+}
 
-        ctx.store::<TaintArtifact>(taint_map);
-        ctx.mark_pass_completed(self.id());
-        Ok(())
+/// The function of `module` that `caller` calls as `name`, if the call
+/// graph has the edge: a function with that ID, or one of the caller's
+/// contract (`Contract.name`).
+fn resolve_callee<'a>(
+    module: &'a Module,
+    caller: &FunctionId,
+    name: &str,
+) -> Option<&'a Function> {
+    let has_edge = module
+        .call_graph
+        .static_edges
+        .iter()
+        .any(|(from, to)| from == caller && to.0 == name);
+    if !has_edge {
+        return None;
     }
+    let qualified = caller
+        .0
+        .split_once('.')
+        .map(|(contract, _)| format!("{contract}.{name}"));
+    module
+        .functions
+        .iter()
+        .find(|func| func.id.0 == name)
+        .or_else(|| {
+            let qualified = qualified.as_deref()?;
+            module.functions.iter().find(|func| func.id.0 == qualified)
+        })
+}
 
-    fn is_completed(&self, ctx: &AnalysisContext) -> bool {
-        ctx.is_pass_completed(self.id())
+/// Map the taint of the arguments of each statically resolved call of
+/// `module` to the parameters of the callee, and the taint of the callee's
+/// returned values back to the call. Returns whether any label was added.
+fn propagate_calls(module: &Module, taint_map: &mut TaintLabels) -> bool {
+    let mut changed = false;
+    for func in &module.functions {
+        for block in &func.blocks {
+            for op in &block.ops {
+                let OpKind::Call(call) = &op.kind else {
+                    continue;
+                };
+                let CallTarget::Static(name) = &call.callee else {
+                    continue;
+                };
+                let Some(callee) = resolve_callee(module, &func.id, name) else {
+                    continue;
+                };
+
+                let mut returned = HashSet::new();
+                for callee_op in callee.blocks.iter().flat_map(|block| &block.ops) {
+                    match &callee_op.kind {
+                        OpKind::Param { index } => {
+                            let labels = call
+                                .args
+                                .get(*index)
+                                .and_then(|arg| taint_map.get(&(func.id.clone(), arg.0)))
+                                .cloned()
+                                .unwrap_or_default();
+                            let entry = taint_map
+                                .entry((callee.id.clone(), callee_op.id))
+                                .or_default();
+                            for label in labels {
+                                changed |= entry.insert(label);
+                            }
+                        }
+                        OpKind::Return(values) => {
+                            for value in values {
+                                if let Some(labels) = taint_map.get(&(callee.id.clone(), value.0))
+                                {
+                                    returned.extend(labels.iter().copied());
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                let entry = taint_map.entry((func.id.clone(), op.id)).or_default();
+                for label in returned {
+                    changed |= entry.insert(label);
+                }
+            }
+        }
     }
+    changed
 }
 
 #[cfg(test)]
@@ -179,7 +340,8 @@ mod tests {
     use super::*;
     use crate::context::AnalysisConfig;
     use scirs::bir::cfg::{BasicBlock, BlockId, Function, FunctionId, Terminator};
-    use scirs::bir::ops::{Op, OpId, OpKind, OpRef, SsaName, TaintSourceOp};
+    use scirs::bir::interfaces::CallRisk;
+    use scirs::bir::ops::{CallDialectOp, Op, OpId, OpKind, OpRef, SsaName, TaintSourceOp};
     use scirs::sir::Type;
 
     #[test]
@@ -211,11 +373,87 @@ mod tests {
         // Run ICFGPass first (dependency)
         crate::passes::bir::icfg::ICFGPass.run(&mut ctx).unwrap();
 
-        let pass = TaintPass;
+        let pass = TaintPass::new();
         pass.run(&mut ctx).unwrap();
 
         let taint = ctx.get::<TaintArtifact>().unwrap();
-        let labels = taint.get(&OpId(0)).unwrap();
+        let labels = taint.get(&(FunctionId("test".into()), OpId(0))).unwrap();
         assert!(labels.contains(&TaintLabel::UserControlled));
     }
+
+    /// `%0 = param 0; return %0` as function `id`.
+    fn identity(id: &str) -> Function {
+        let mut func = Function::new(FunctionId(id.into()), false);
+        let mut bb0 = BasicBlock::new(BlockId(0));
+        bb0.ops = vec![
+            Op::new(OpId(0), OpKind::Param { index: 0 })
+                .with_result(SsaName::new("arg", 0), Type::Si256),
+            Op::new(OpId(1), OpKind::Return(vec![OpRef(OpId(0))])),
+        ];
+        bb0.term = Terminator::TxnExit { reverted: false };
+        func.blocks = vec![bb0];
+        func
+    }
+
+    #[test]
+    fn test_taint_pass_interprocedural() {
+        // C.f: %0 = msg_sender; %1 = call g(%0)
+        let mut caller = Function::new(FunctionId("C.f".into()), true);
+        let mut bb0 = BasicBlock::new(BlockId(0));
+        bb0.ops = vec![
+            Op::new(
+                OpId(0),
+                OpKind::TaintSrc(TaintSourceOp {
+                    label: TaintLabel::UserControlled,
+                    dialect_name: "evm".into(),
+                    op_name: "msg_sender".into(),
+                }),
+            ),
+            Op::new(
+                OpId(1),
+                OpKind::Call(CallDialectOp {
+                    callee: CallTarget::Static("g".into()),
+                    call_risk: CallRisk { reentrancy: false, value_transfer: false },
+                    args: vec![OpRef(OpId(0))],
+                    dialect_name: "sir".into(),
+                    op_name: "call".into(),
+                }),
+            ),
+        ];
+        bb0.term = Terminator::TxnExit { reverted: false };
+        caller.blocks = vec![bb0];
+
+        let mut air_module = scirs::bir::Module::new("test".into());
+        // Op IDs restart from 0 in every function: `C.h` is not called.
+        air_module.functions = vec![caller, identity("C.g"), identity("C.h")];
+        air_module
+            .call_graph
+            .add_static_edge(FunctionId("C.f".into()), FunctionId("g".into()));
+
+        let taint_of = |pass: TaintPass, func: &str, op: usize| {
+            let mut ctx = AnalysisContext::new(vec![], AnalysisConfig::default());
+            ctx.set_air_units(vec![air_module.clone()]);
+            crate::passes::bir::icfg::ICFGPass.run(&mut ctx).unwrap();
+            pass.run(&mut ctx).unwrap();
+            let taint = ctx.get::<TaintArtifact>().unwrap();
+            taint
+                .get(&(FunctionId(func.into()), OpId(op)))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        // Taint stops at the call by default.
+        assert!(taint_of(TaintPass::new(), "C.g", 0).is_empty());
+        assert!(taint_of(TaintPass::new(), "C.f", 1).is_empty());
+
+        // The argument reaches the parameter, and the return value the call.
+        let tainted = |func, op| {
+            taint_of(TaintPass::interprocedural(1), func, op).contains(&TaintLabel::UserControlled)
+        };
+        assert!(tainted("C.g", 0));
+        assert!(tainted("C.f", 1));
+        // The ops with the same IDs in other functions stay clean.
+        assert!(!tainted("C.h", 0));
+        assert!(!tainted("C.h", 1));
+    }
 }
@@ -0,0 +1,310 @@
+//! Contract Metrics
+//!
+//! Computes size and complexity metrics of each contract for triage:
+//! numbers of functions, external calls, loops, storage variables and
+//! modifiers, lines of code, and cyclomatic complexity. The cyclomatic
+//! complexity of a contract is the sum over its functions of one plus
+//! their branch points: `if`, `while`, `for`, `?:`, `&&`, `||` and catch
+//! clauses.
+
+use crate::context::{AnalysisContext, ContextKey};
+use crate::passes::base::meta::{PassLevel, PassRepresentation};
+use crate::passes::base::{AnalysisPass, Pass, PassResult};
+use scirs::sir::dialect::EvmCallExt;
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl, EvmStmt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    BinOp, BinOpExpr, CallExpr, ContractDecl, Decl, DialectExpr, DialectMemberDecl, DialectStmt,
+    ForStmt, IfStmt, MemberDecl, Module, TernaryExpr, WhileStmt,
+};
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+// ═══════════════════════════════════════════════════════════════════
+// Artifact
+// ═══════════════════════════════════════════════════════════════════
+
+/// Artifact key for the contract metrics, keyed by contract name.
+pub struct MetricsArtifact;
+
+impl ContextKey for MetricsArtifact {
+    type Value = BTreeMap<String, ContractMetrics>;
+    const NAME: &'static str = "contract_metrics";
+}
+
+/// Size and complexity metrics of a contract.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractMetrics {
+    pub functions: usize,
+    pub external_calls: usize,
+    pub loops: usize,
+    pub storage_variables: usize,
+    pub modifiers: usize,
+    /// Lines spanned by the contract declaration.
+    pub lines_of_code: usize,
+    pub cyclomatic_complexity: usize,
+}
+
+impl ContractMetrics {
+    /// Compute the metrics of `contract`.
+    pub fn of(contract: &ContractDecl) -> Self {
+        let mut metrics = ContractMetrics {
+            lines_of_code: contract
+                .span
+                .as_ref()
+                .filter(|span| span.is_valid())
+                .map_or(0, |span| span.end_line + 1 - span.start_line),
+            ..Self::default()
+        };
+        for member in &contract.members {
+            match member {
+                MemberDecl::Storage(_) => metrics.storage_variables += 1,
+                MemberDecl::Function(func) => {
+                    let mut counter = MetricsCounter::default();
+                    if let Some(body) = &func.body {
+                        counter.visit_stmts(body);
+                    }
+                    metrics.functions += 1;
+                    metrics.external_calls += counter.external_calls;
+                    metrics.loops += counter.loops;
+                    metrics.cyclomatic_complexity += 1 + counter.branches;
+                }
+                MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(_))) => {
+                    metrics.modifiers += 1
+                }
+                _ => {}
+            }
+        }
+        metrics
+    }
+}
+
+/// Compute the metrics of every contract of `modules`.
+pub fn compute_metrics(modules: &[Module]) -> BTreeMap<String, ContractMetrics> {
+    modules
+        .iter()
+        .flat_map(|module| &module.decls)
+        .filter_map(|decl| match decl {
+            Decl::Contract(contract) => {
+                Some((contract.name.clone(), ContractMetrics::of(contract)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Pass
+// ═══════════════════════════════════════════════════════════════════
+
+/// Contract metrics pass.
+pub struct MetricsPass;
+
+impl Pass for MetricsPass {
+    fn name(&self) -> &'static str {
+        "metrics"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compute per-contract size and complexity metrics"
+    }
+
+    fn level(&self) -> PassLevel {
+        PassLevel::Contract
+    }
+
+    fn representation(&self) -> PassRepresentation {
+        PassRepresentation::Ir
+    }
+
+    fn dependencies(&self) -> Vec<TypeId> {
+        vec![]
+    }
+}
+
+impl AnalysisPass for MetricsPass {
+    fn run(&self, ctx: &mut AnalysisContext) -> PassResult<()> {
+        let metrics = ctx
+            .ir_units
+            .as_deref()
+            .map(compute_metrics)
+            .unwrap_or_default();
+        ctx.store::<MetricsArtifact>(metrics);
+        ctx.mark_pass_completed(self.id());
+        Ok(())
+    }
+
+    fn is_completed(&self, ctx: &AnalysisContext) -> bool {
+        ctx.is_pass_completed(self.id())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// Helpers
+// ═══════════════════════════════════════════════════════════════════
+
+/// Counts external calls, loops and branch points in a function body.
+#[derive(Default)]
+struct MetricsCounter {
+    external_calls: usize,
+    loops: usize,
+    branches: usize,
+}
+
+impl<'a> Visit<'a> for MetricsCounter {
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt) {
+        self.branches += 1;
+        visit::default::visit_if_stmt(self, stmt);
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &'a WhileStmt) {
+        self.loops += 1;
+        self.branches += 1;
+        visit::default::visit_while_stmt(self, stmt);
+    }
+
+    fn visit_for_stmt(&mut self, stmt: &'a ForStmt) {
+        self.loops += 1;
+        self.branches += 1;
+        visit::default::visit_for_stmt(self, stmt);
+    }
+
+    fn visit_ternary_expr(&mut self, expr: &'a TernaryExpr) {
+        self.branches += 1;
+        visit::default::visit_ternary_expr(self, expr);
+    }
+
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        if matches!(expr.op, BinOp::And | BinOp::Or) {
+            self.branches += 1;
+        }
+        visit::default::visit_binop_expr(self, expr);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if expr.is_evm_external_call() {
+            self.external_calls += 1;
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        if matches!(
+            expr,
+            DialectExpr::Evm(
+                EvmExpr::LowLevelCall(_)
+                    | EvmExpr::RawCall(_)
+                    | EvmExpr::Send(_)
+                    | EvmExpr::Transfer(_)
+                    | EvmExpr::Delegatecall(_)
+            )
+        ) {
+            self.external_calls += 1;
+        }
+    }
+
+    fn visit_dialect_stmt(&mut self, stmt: &'a DialectStmt) {
+        if let DialectStmt::Evm(EvmStmt::TryCatch(tc)) = stmt {
+            self.branches += tc.catch_clauses.len();
+            self.visit_expr(&tc.guarded_expr);
+            self.visit_stmts(&tc.body);
+            for clause in &tc.catch_clauses {
+                self.visit_stmts(&clause.body);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::loc::Loc;
+    use scirs::sir::dialect::evm::{EvmModifierDef, EvmTransfer};
+    use scirs::sir::*;
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), Type::Bool, None))
+    }
+
+    /// `while (a && b) { if (c) { payable(to).transfer(amount); } }`
+    fn body() -> Vec<Stmt> {
+        let transfer = Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(EvmTransfer {
+            target: Box::new(var("to")),
+            amount: Box::new(var("amount")),
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        let cond = Expr::BinOp(BinOpExpr {
+            op: BinOp::And,
+            lhs: Box::new(var("a")),
+            rhs: Box::new(var("b")),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        });
+        vec![Stmt::While(WhileStmt {
+            cond,
+            body: vec![Stmt::If(IfStmt {
+                cond: var("c"),
+                then_body: vec![Stmt::Expr(ExprStmt { expr: transfer, span: None })],
+                else_body: None,
+                span: None,
+            })],
+            invariant: None,
+            span: None,
+        })]
+    }
+
+    #[test]
+    fn test_metrics_pass() {
+        let members = vec![
+            MemberDecl::Storage(StorageDecl::new("owner".to_string(), Type::Bool, None, None)),
+            MemberDecl::Function(FunctionDecl::new(
+                "drain".to_string(),
+                vec![],
+                vec![],
+                Some(body()),
+                None,
+            )),
+            MemberDecl::Function(FunctionDecl::new(
+                "noop".to_string(),
+                vec![],
+                vec![],
+                None,
+                None,
+            )),
+            MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(
+                EvmModifierDef {
+                    name: "onlyOwner".to_string(),
+                    params: vec![],
+                    body: vec![],
+                    loc: Loc::new(0, 0, 0, 0),
+                },
+            ))),
+        ];
+        let contract =
+            ContractDecl::new("Vault".to_string(), members, Some(Loc::new(3, 1, 22, 1)));
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(contract)],
+        };
+        let mut ctx = AnalysisContext::new(vec![], Default::default());
+        ctx.ir_units = Some(vec![module]);
+
+        MetricsPass.run(&mut ctx).unwrap();
+        let metrics = &ctx.get::<MetricsArtifact>().unwrap()["Vault"];
+        assert_eq!(
+            *metrics,
+            ContractMetrics {
+                functions: 2,
+                external_calls: 1,
+                loops: 1,
+                storage_variables: 1,
+                modifiers: 1,
+                lines_of_code: 20,
+                // `drain`: 1 + while + && + if; `noop`: 1.
+                cyclomatic_complexity: 5,
+            }
+        );
+    }
+}
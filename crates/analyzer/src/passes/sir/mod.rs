@@ -5,6 +5,8 @@
 //! `frameworks::cfa`.  The `scirs::sir::cfg` data types remain
 //! available for any SIR-level tooling.
 
+pub mod metrics;
 pub mod write_set;
 
+pub use metrics::{ContractMetrics, MetricsArtifact, MetricsPass, compute_metrics};
 pub use write_set::{WriteSetArtifact, WriteSetPass};
@@ -6,22 +6,27 @@
 //!    dependency level
 //! 2. **Detection Phase**: Run all enabled detectors fully in parallel
 
+use crate::anchor::anchor_bugs;
 use crate::config::InputLanguage;
 use crate::context::AnalysisContext;
 use crate::detectors::BugDetectionPass;
 use crate::detectors::base::registry::{DetectorRegistry, register_all_detectors};
 use crate::pass_manager::manager::{PassManager, PassManagerConfig};
-use crate::passes::base::AnalysisPass;
 use crate::passes::base::meta::PassRepresentation;
+use crate::passes::base::{AnalysisPass, Pass, PassExecutionInfo};
+use crate::passes::sir::{ContractMetrics, MetricsArtifact, MetricsPass};
 use bugs::bug::Bug;
+use scanner::DetectorParams;
 use std::any::TypeId;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
 /// Configuration for the pipeline.
 #[derive(Debug, Clone)]
 pub struct PipelineConfig {
-    /// Enable parallel execution.
+    /// Enable parallel execution: detectors run in parallel, and so do the
+    /// files of [`PipelineEngine::run_files`].
     pub parallel: bool,
 
     /// Number of worker threads (0 = auto-detect).
@@ -32,11 +37,30 @@ pub struct PipelineConfig {
 
     /// List of detector IDs to disable.
     pub disabled: Vec<String>,
+
+    /// Detector tags to enable (empty = all). Combined with `enabled`, a
+    /// detector runs if it is listed there or carries one of these tags.
+    pub tags: Vec<String>,
+
+    /// Per-detector parameters, keyed by detector ID.
+    pub params: HashMap<String, DetectorParams>,
+
+    /// Time budget of one run. Once exceeded, the remaining analysis passes
+    /// and detectors are skipped and the result is marked as timed out.
+    pub timeout: Option<Duration>,
 }
 
 impl Default for PipelineConfig {
     fn default() -> Self {
-        Self { parallel: true, num_threads: 0, enabled: vec![], disabled: vec![] }
+        Self {
+            parallel: true,
+            num_threads: 0,
+            enabled: vec![],
+            disabled: vec![],
+            tags: vec![],
+            params: HashMap::new(),
+            timeout: None,
+        }
     }
 }
 
@@ -53,6 +77,8 @@ pub struct DetectorStats {
     pub success: bool,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Whether the detector was skipped because the timeout was exceeded.
+    pub skipped: bool,
 }
 
 /// Result of running the full pipeline.
@@ -62,12 +88,18 @@ pub struct PipelineResult {
     pub bugs: Vec<Bug>,
     /// Per-detector statistics.
     pub detector_stats: Vec<DetectorStats>,
+    /// Executions of the analysis passes.
+    pub pass_stats: Vec<PassExecutionInfo>,
     /// Analysis phase duration.
     pub analysis_duration: Duration,
     /// Detection phase duration.
     pub detection_duration: Duration,
     /// Total pipeline duration.
     pub total_duration: Duration,
+    /// Whether the run exceeded its timeout; findings are then partial.
+    pub timed_out: bool,
+    /// Size and complexity metrics of each contract, keyed by name.
+    pub contract_metrics: BTreeMap<String, ContractMetrics>,
 }
 
 impl PipelineResult {
@@ -80,8 +112,51 @@ impl PipelineResult {
     pub fn has_bugs(&self) -> bool {
         !self.bugs.is_empty()
     }
+
+    /// Wall-clock time of each analysis pass, keyed `pass:NAME`, and of each
+    /// detector, keyed `detector:NAME`, summed over the runs merged into
+    /// this result.
+    pub fn timing(&self) -> BTreeMap<String, Duration> {
+        let passes = self
+            .pass_stats
+            .iter()
+            .map(|pass| (format!("pass:{}", pass.name), pass.duration));
+        let detectors = self
+            .detector_stats
+            .iter()
+            .map(|stats| (format!("detector:{}", stats.name), stats.duration));
+        let mut timing = BTreeMap::new();
+        for (key, duration) in passes.chain(detectors) {
+            *timing.entry(key).or_default() += duration;
+        }
+        timing
+    }
 }
 
+/// Receives progress events of a pipeline run, e.g. to drive a progress bar
+/// or to publish findings as soon as they are found. All hooks do nothing by
+/// default.
+///
+/// When detectors run in parallel, events of different detectors interleave,
+/// but they are never delivered concurrently.
+pub trait DetectionObserver: Send {
+    /// A detector is about to run.
+    fn on_detector_start(&mut self, _detector: &dyn BugDetectionPass) {}
+
+    /// A detector has finished, successfully or not, or was skipped.
+    fn on_detector_finish(&mut self, _detector: &dyn BugDetectionPass, _stats: &DetectorStats) {}
+
+    /// A detector has reported `bug`. Findings are delivered before the
+    /// deduplication and anchoring of [`PipelineEngine::run`], so the final
+    /// result may contain fewer of them.
+    fn on_bug(&mut self, _bug: &Bug) {}
+}
+
+/// Observer ignoring every event.
+struct NoopObserver;
+
+impl DetectionObserver for NoopObserver {}
+
 /// The main pipeline engine that orchestrates analysis and detection.
 ///
 /// Execution flow:
@@ -94,6 +169,9 @@ pub struct PipelineEngine {
     registry: DetectorRegistry,
     /// Pipeline configuration.
     config: PipelineConfig,
+    /// Thread pool sized by `config.num_threads`; `None` uses rayon's
+    /// global pool.
+    pool: Option<rayon::ThreadPool>,
 }
 
 impl PipelineEngine {
@@ -101,12 +179,26 @@ impl PipelineEngine {
     pub fn new(config: PipelineConfig) -> Self {
         let mut registry = DetectorRegistry::new();
         register_all_detectors(&mut registry);
-        Self { registry, config }
+        registry.configure(&config.params);
+        Self::with_registry(registry, config)
     }
 
-    /// Create a pipeline engine with an empty registry (for testing).
+    /// Create a pipeline engine running the detectors of `registry`, e.g.
+    /// the built-in detectors of [`register_all_detectors`] together with
+    /// custom ones. Unlike [`new`](Self::new), the detector parameters of
+    /// `config` are not applied; call [`DetectorRegistry::configure`]
+    /// beforehand.
     pub fn with_registry(registry: DetectorRegistry, config: PipelineConfig) -> Self {
-        Self { registry, config }
+        let pool = (config.parallel && config.num_threads > 0)
+            .then(|| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(config.num_threads)
+                    .build()
+                    .map_err(|e| log::warn!("Failed to create thread pool: {}", e))
+                    .ok()
+            })
+            .flatten();
+        Self { registry, config, pool }
     }
 
     /// Get a reference to the detector registry.
@@ -121,21 +213,38 @@ impl PipelineEngine {
 
     /// Run the full pipeline: analysis phase then detection phase.
     pub fn run(&self, context: &mut AnalysisContext) -> PipelineResult {
+        self.run_with_observer(context, &mut NoopObserver)
+    }
+
+    /// Like [`run`](Self::run), reporting the progress of the detection
+    /// phase to `observer`.
+    pub fn run_with_observer(
+        &self,
+        context: &mut AnalysisContext,
+        observer: &mut dyn DetectionObserver,
+    ) -> PipelineResult {
         let start = Instant::now();
+        let deadline = self.config.timeout.map(|timeout| start + timeout);
 
         // Step 1: Resolve which detectors to run (language-aware)
         let enabled_detectors = self.resolve_detectors_for_language(context.input_language);
 
         // Step 2: Phase 1 - AST + IR analysis (existing)
         let analysis_start = Instant::now();
-        if let Err(e) = self.run_analysis_phase(&enabled_detectors, context) {
-            log::error!("Analysis phase failed: {}", e);
-        }
+        let (mut pass_stats, passes_timed_out) =
+            match self.run_analysis_phase(&enabled_detectors, context, deadline) {
+                Ok(result) => result,
+                Err(e) => {
+                    log::error!("Analysis phase failed: {}", e);
+                    (vec![], false)
+                }
+            };
 
         // Phase 2 — SIR structural analysis
         if context.has_ir() {
-            if let Err(e) = self.run_sir_phase(context) {
-                log::error!("SIR structural phase failed: {}", e);
+            match self.run_sir_phase(context) {
+                Ok(info) => pass_stats.push(info),
+                Err(e) => log::error!("SIR structural phase failed: {}", e),
             }
         }
 
@@ -149,18 +258,123 @@ impl PipelineEngine {
 
         // Step 3: Phase 4 - Detection (parallel)
         let detection_start = Instant::now();
-        let (bugs, detector_stats) = self.run_detection_phase(&enabled_detectors, context);
+        let observer = Mutex::new(observer);
+        let context = &*context;
+        let (bugs, detector_stats, detectors_timed_out) = self.install(|| {
+            self.run_detection_phase(&enabled_detectors, context, deadline, &observer)
+        });
         let detection_duration = detection_start.elapsed();
 
         // Deduplicate bugs across tiers
-        let bugs = Self::deduplicate_bugs(bugs);
+        let mut bugs = Self::deduplicate_bugs(bugs);
+        if context.has_ir() {
+            anchor_bugs(&mut bugs, context.ir_units());
+        }
+        self.sort_bugs(&mut bugs);
 
         PipelineResult {
             bugs,
             detector_stats,
+            pass_stats,
             analysis_duration,
             detection_duration,
             total_duration: start.elapsed(),
+            timed_out: passes_timed_out || detectors_timed_out,
+            contract_metrics: context
+                .get::<MetricsArtifact>()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Run the pipeline separately on the context of each file, so that the
+    /// configured timeout applies to every file on its own. With
+    /// [`parallel`](PipelineConfig::parallel) set, the files are analyzed in
+    /// parallel.
+    ///
+    /// Returns the merged result, whose findings are sorted by file, line
+    /// and detector, and the files whose run timed out. The result does not
+    /// depend on whether the files ran in parallel.
+    pub fn run_files(
+        &self,
+        files: Vec<(String, AnalysisContext)>,
+    ) -> (PipelineResult, Vec<String>) {
+        let start = Instant::now();
+        let (mut merged, timed_out) = self.merge_results(self.run_each(files));
+        merged.total_duration = start.elapsed();
+        (merged, timed_out)
+    }
+
+    /// Like [`run_files`](Self::run_files), but returns the result of each
+    /// file, in the order of `files`, instead of merging them.
+    pub fn run_each(
+        &self,
+        files: Vec<(String, AnalysisContext)>,
+    ) -> Vec<(String, PipelineResult)> {
+        let run_file = |(file, mut context): (String, AnalysisContext)| {
+            let result = self.run(&mut context);
+            (file, result)
+        };
+        if self.config.parallel && files.len() > 1 {
+            use rayon::prelude::*;
+            self.install(|| files.into_par_iter().map(run_file).collect())
+        } else {
+            files.into_iter().map(run_file).collect()
+        }
+    }
+
+    /// Merge the results of [`run_each`](Self::run_each) as
+    /// [`run_files`](Self::run_files) does. The total duration of the merged
+    /// result is the sum of those of the files.
+    pub fn merge_results(
+        &self,
+        results: Vec<(String, PipelineResult)>,
+    ) -> (PipelineResult, Vec<String>) {
+        let mut merged = PipelineResult::default();
+        let mut timed_out = vec![];
+        for (file, result) in results {
+            if result.timed_out {
+                log::warn!("Analysis of {} timed out; findings are partial", file);
+                timed_out.push(file);
+            }
+            merged.bugs.extend(result.bugs);
+            merged.detector_stats.extend(result.detector_stats);
+            merged.pass_stats.extend(result.pass_stats);
+            merged.analysis_duration += result.analysis_duration;
+            merged.detection_duration += result.detection_duration;
+            merged.total_duration += result.total_duration;
+            merged.contract_metrics.extend(result.contract_metrics);
+        }
+
+        merged.bugs = Self::deduplicate_bugs(merged.bugs);
+        self.sort_bugs(&mut merged.bugs);
+        merged.timed_out = !timed_out.is_empty();
+        (merged, timed_out)
+    }
+
+    /// Identity of the detectors that run on `language` and of their
+    /// parameters, for caching findings across runs.
+    pub fn detector_fingerprint(&self, language: InputLanguage) -> String {
+        let mut detectors: Vec<&str> = self
+            .resolve_detectors_for_language(language)
+            .iter()
+            .map(|d| d.detector_id().as_str())
+            .collect();
+        detectors.sort_unstable();
+        let params: BTreeMap<&String, BTreeMap<&String, &String>> = self
+            .config
+            .params
+            .iter()
+            .map(|(id, params)| (id, params.iter().collect()))
+            .collect();
+        format!("{} {:?} {:?}", env!("CARGO_PKG_VERSION"), detectors, params)
+    }
+
+    /// Run `op` in the thread pool of the engine.
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(op),
+            None => op(),
         }
     }
 
@@ -204,9 +418,11 @@ impl PipelineEngine {
             return false;
         }
 
-        // If enabled list is non-empty, detector must be in it
-        if !self.config.enabled.is_empty() {
-            return self.config.enabled.iter().any(|d| d == name || d == id);
+        // If an enabled list or tags are given, detector must match one
+        if !self.config.enabled.is_empty() || !self.config.tags.is_empty() {
+            let tags = detector.tags();
+            return self.config.enabled.iter().any(|d| d == name || d == id)
+                || self.config.tags.iter().any(|t| tags.contains(&t.as_str()));
         }
 
         true
@@ -220,12 +436,15 @@ impl PipelineEngine {
     ///
     /// Only passes actually needed by the enabled detectors are scheduled.
     /// Passes are executed in dependency-level order, with passes at the
-    /// same level running in parallel.
+    /// same level running in parallel. Passes not started by `deadline` are
+    /// skipped. Returns the executions of the passes and whether any was
+    /// skipped.
     fn run_analysis_phase(
         &self,
         enabled_detectors: &[&dyn BugDetectionPass],
         context: &mut AnalysisContext,
-    ) -> Result<(), String> {
+        deadline: Option<Instant>,
+    ) -> Result<(Vec<PassExecutionInfo>, bool), String> {
         // Collect required passes from detector dependencies
         let required: HashSet<TypeId> = enabled_detectors
             .iter()
@@ -234,7 +453,7 @@ impl PipelineEngine {
 
         if required.is_empty() {
             log::debug!("No analysis passes required by enabled detectors");
-            return Ok(());
+            return Ok((vec![], false));
         }
 
         log::info!("Analysis phase: {} passes required", required.len());
@@ -246,6 +465,7 @@ impl PipelineEngine {
             fail_fast: true,
             verbose: false,
             timing: true,
+            deadline,
         });
 
         // Create and register only the required analysis passes
@@ -266,7 +486,7 @@ impl PipelineEngine {
                     report.passes_executed,
                     report.total_duration
                 );
-                Ok(())
+                Ok((report.pass_info, report.passes_timed_out > 0))
             }
             Err(e) => Err(format!("Analysis phase failed: {}", e)),
         }
@@ -281,13 +501,22 @@ impl PipelineEngine {
     /// These passes operate on `scirs::sir::Module` and detect issues visible
     /// in the SIR tree structure (missing annotations, wrong overflow
     /// semantics, etc.).
-    fn run_sir_phase(&self, _context: &mut AnalysisContext) -> Result<(), String> {
+    fn run_sir_phase(&self, context: &mut AnalysisContext) -> Result<PassExecutionInfo, String> {
         log::info!("SIR structural phase");
         // SIR structural passes store their findings as context data.
         // They are registered as analysis passes and run via the normal
         // PassManager scheduling.  The create_analysis_pass factory already
-        // handles them; this method is a logical grouping marker for now.
-        Ok(())
+        // handles them; only the contract metrics, which every report
+        // includes, are computed here.
+        let start = Instant::now();
+        MetricsPass.run(context).map_err(|e| e.to_string())?;
+        Ok(PassExecutionInfo {
+            pass_id: MetricsPass.id(),
+            name: MetricsPass.name().to_string(),
+            duration: start.elapsed(),
+            success: true,
+            error: None,
+        })
     }
 
     // ========================================================================
@@ -314,19 +543,24 @@ impl PipelineEngine {
     /// Run all enabled detectors.
     ///
     /// Detectors read from the immutable AnalysisContext, so they can run
-    /// fully in parallel.
+    /// fully in parallel. Returns the findings, the statistics of the
+    /// detectors and whether any was skipped because `deadline` had passed.
     fn run_detection_phase(
         &self,
         enabled_detectors: &[&dyn BugDetectionPass],
         context: &AnalysisContext,
-    ) -> (Vec<Bug>, Vec<DetectorStats>) {
+        deadline: Option<Instant>,
+        observer: &Observer,
+    ) -> (Vec<Bug>, Vec<DetectorStats>, bool) {
         log::info!("Detection phase: {} detectors", enabled_detectors.len());
 
-        if self.config.parallel && enabled_detectors.len() > 1 {
-            self.run_detectors_parallel(enabled_detectors, context)
+        let (bugs, stats) = if self.config.parallel && enabled_detectors.len() > 1 {
+            self.run_detectors_parallel(enabled_detectors, context, deadline, observer)
         } else {
-            self.run_detectors_sequential(enabled_detectors, context)
-        }
+            self.run_detectors_sequential(enabled_detectors, context, deadline, observer)
+        };
+        let timed_out = stats.iter().any(|stat| stat.skipped);
+        (bugs, stats, timed_out)
     }
 
     /// Run detectors sequentially.
@@ -334,12 +568,14 @@ impl PipelineEngine {
         &self,
         detectors: &[&dyn BugDetectionPass],
         context: &AnalysisContext,
+        deadline: Option<Instant>,
+        observer: &Observer,
     ) -> (Vec<Bug>, Vec<DetectorStats>) {
         let mut all_bugs = Vec::new();
         let mut all_stats = Vec::new();
 
         for &detector in detectors {
-            let (bugs, stat) = run_single_detector(detector, context);
+            let (bugs, stat) = run_single_detector(detector, context, deadline, observer);
             all_bugs.extend(bugs);
             all_stats.push(stat);
         }
@@ -352,12 +588,14 @@ impl PipelineEngine {
         &self,
         detectors: &[&dyn BugDetectionPass],
         context: &AnalysisContext,
+        deadline: Option<Instant>,
+        observer: &Observer,
     ) -> (Vec<Bug>, Vec<DetectorStats>) {
         use rayon::prelude::*;
 
         let results: Vec<_> = detectors
             .par_iter()
-            .map(|&d| run_single_detector(d, context))
+            .map(|&d| run_single_detector(d, context, deadline, observer))
             .collect();
 
         let mut all_bugs = Vec::new();
@@ -395,18 +633,66 @@ impl PipelineEngine {
 
         bugs
     }
+
+    /// Sort `bugs` by file, line, column and the id of the detector that
+    /// reported them. Findings of detectors outside the registry sort by
+    /// name.
+    fn sort_bugs(&self, bugs: &mut [Bug]) {
+        bugs.sort_by_cached_key(|bug| {
+            let detector = match self.registry.get(&bug.name) {
+                Some(detector) => detector.detector_id().as_str().to_string(),
+                None => bug.name.clone(),
+            };
+            (bug.loc.file.clone(), bug.loc.start_line, bug.loc.start_col, detector)
+        });
+    }
 }
 
-/// Run a single detector and collect results.
+/// Observer of a run, shared by the detectors running in parallel.
+type Observer<'a> = Mutex<&'a mut dyn DetectionObserver>;
+
+/// Run a single detector, reporting it to `observer`.
 fn run_single_detector(
     detector: &dyn BugDetectionPass,
     context: &AnalysisContext,
+    deadline: Option<Instant>,
+    observer: &Observer,
+) -> (Vec<Bug>, DetectorStats) {
+    observer
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .on_detector_start(detector);
+    let (bugs, stat) = run_detector(detector, context, deadline);
+    let mut observer = observer.lock().unwrap_or_else(PoisonError::into_inner);
+    for bug in &bugs {
+        observer.on_bug(bug);
+    }
+    observer.on_detector_finish(detector, &stat);
+    (bugs, stat)
+}
+
+/// Run a single detector and collect results. The detector is skipped if
+/// `deadline` has already passed.
+fn run_detector(
+    detector: &dyn BugDetectionPass,
+    context: &AnalysisContext,
+    deadline: Option<Instant>,
 ) -> (Vec<Bug>, DetectorStats) {
     let start = Instant::now();
     let mut stat = DetectorStats { name: detector.name().to_string(), ..Default::default() };
 
+    if deadline.is_some_and(|d| start > d) {
+        log::debug!("Detector '{}' skipped: timeout exceeded", detector.name());
+        stat.error = Some("skipped: timeout exceeded".to_string());
+        stat.skipped = true;
+        return (vec![], stat);
+    }
+
     match detector.detect(context) {
-        Ok(bugs) => {
+        Ok(mut bugs) => {
+            for bug in &mut bugs {
+                bug.confidence.get_or_insert(detector.confidence());
+            }
             stat.bug_count = bugs.len();
             stat.success = true;
             stat.duration = start.elapsed();
@@ -434,6 +720,8 @@ fn run_single_detector(
 fn create_analysis_pass(pass_id: TypeId) -> Option<Box<dyn AnalysisPass>> {
     if pass_id == TypeId::of::<crate::passes::bir::TaintPropagationPass>() {
         Some(Box::new(crate::passes::bir::TaintPropagationPass))
+    } else if pass_id == TypeId::of::<crate::passes::bir::AccessControlPass>() {
+        Some(Box::new(crate::passes::bir::AccessControlPass))
     } else {
         log::warn!("No analysis pass implementation for {:?}", pass_id);
         None
@@ -482,6 +770,258 @@ mod tests {
         assert_eq!(detectors.len(), 1);
     }
 
+    #[test]
+    fn test_resolve_detectors_by_tag() {
+        let engine = PipelineEngine::new(PipelineConfig {
+            tags: vec!["proxy".to_string()],
+            ..PipelineConfig::default()
+        });
+        let detectors = engine.resolve_detectors();
+        assert!(!detectors.is_empty());
+        assert!(detectors.iter().all(|d| d.tags().contains(&"proxy")));
+        assert_eq!(detectors.len(), engine.registry().by_tag("proxy").len());
+    }
+
+    /// Module-level scan detector for timeout tests: the slow variant
+    /// sleeps on modules named `slow`, the other reports every module.
+    struct Probe {
+        slow: bool,
+    }
+
+    impl scanner::ScanDetector for Probe {
+        fn id(&self) -> &'static str {
+            if self.slow {
+                "slow-probe"
+            } else {
+                "marker-probe"
+            }
+        }
+        fn name(&self) -> &'static str {
+            if self.slow {
+                "Slow Probe"
+            } else {
+                "Marker Probe"
+            }
+        }
+        fn description(&self) -> &'static str {
+            ""
+        }
+        fn bug_kind(&self) -> bugs::bug::BugKind {
+            bugs::bug::BugKind::Vulnerability
+        }
+        fn bug_category(&self) -> bugs::bug::BugCategory {
+            bugs::bug::BugCategory::Other
+        }
+        fn risk_level(&self) -> bugs::bug::RiskLevel {
+            bugs::bug::RiskLevel::Low
+        }
+        fn confidence(&self) -> scanner::Confidence {
+            scanner::Confidence::High
+        }
+        fn target(&self) -> scanner::Target {
+            scanner::Target::Evm
+        }
+        fn level(&self) -> scanner::detector::DetectionLevel {
+            scanner::detector::DetectionLevel::Module
+        }
+        fn cwe_ids(&self) -> Vec<usize> {
+            vec![]
+        }
+        fn check_module(&self, module: &scirs::sir::Module) -> Vec<Bug> {
+            if self.slow {
+                if module.id == "slow" {
+                    std::thread::sleep(Duration::from_millis(200));
+                }
+                return vec![];
+            }
+            vec![Bug::new(
+                self.name(),
+                Some(&module.id),
+                common::loc::Loc::new(0, 0, 0, 0).with_file(module.id.clone()),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                vec![],
+                vec![],
+                None,
+            )]
+        }
+    }
+
+    #[test]
+    fn test_run_files_timeout() {
+        use crate::detectors::scan_adapter::ScanDetectorAdapter;
+
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(ScanDetectorAdapter::new(Box::new(Probe { slow: true }))));
+        registry.register(Box::new(ScanDetectorAdapter::new(Box::new(Probe { slow: false }))));
+        let engine = PipelineEngine::with_registry(
+            registry,
+            PipelineConfig {
+                parallel: false,
+                timeout: Some(Duration::from_millis(50)),
+                ..PipelineConfig::default()
+            },
+        );
+
+        let context = |id: &str| {
+            let module = scirs::sir::Module { id: id.to_string(), attrs: vec![], decls: vec![] };
+            AnalysisContext::new(vec![module], crate::AnalysisConfig::default())
+        };
+        let files = vec![
+            ("Slow.sol".to_string(), context("slow")),
+            ("Fast.sol".to_string(), context("fast")),
+        ];
+        let (result, timed_out) = engine.run_files(files);
+
+        // The marker never runs on the slow file; the fast file completes.
+        assert_eq!(timed_out, vec!["Slow.sol".to_string()]);
+        assert!(result.timed_out);
+        assert_eq!(result.bugs.len(), 1);
+        assert_eq!(result.bugs[0].description.as_deref(), Some("fast"));
+    }
+
+    #[test]
+    fn test_run_files_parallel_matches_sequential() {
+        use crate::detectors::scan_adapter::ScanDetectorAdapter;
+
+        let run = |parallel: bool| {
+            let mut registry = DetectorRegistry::new();
+            registry.register(Box::new(ScanDetectorAdapter::new(Box::new(Probe { slow: false }))));
+            let config = PipelineConfig { parallel, num_threads: 4, ..PipelineConfig::default() };
+            let engine = PipelineEngine::with_registry(registry, config);
+            let files = ["c", "a", "d", "b"]
+                .into_iter()
+                .map(|id| {
+                    let module =
+                        scirs::sir::Module { id: id.to_string(), attrs: vec![], decls: vec![] };
+                    let context =
+                        AnalysisContext::new(vec![module], crate::AnalysisConfig::default());
+                    (format!("{id}.sol"), context)
+                })
+                .collect();
+            engine.run_files(files).0.bugs
+        };
+
+        let parallel = run(true);
+        let files: Vec<_> = parallel.iter().map(|bug| bug.loc.file.as_deref()).collect();
+        assert_eq!(files, [Some("a"), Some("b"), Some("c"), Some("d")]);
+        assert_eq!(format!("{parallel:?}"), format!("{:?}", run(false)));
+    }
+
+    #[test]
+    fn test_run_files_matches_single_context() {
+        use crate::detectors::scan_adapter::ScanDetectorAdapter;
+
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(ScanDetectorAdapter::new(Box::new(Probe { slow: false }))));
+        let engine = PipelineEngine::with_registry(registry, PipelineConfig::default());
+        let module =
+            |id: &str| scirs::sir::Module { id: id.to_string(), attrs: vec![], decls: vec![] };
+        let context = |ids: &[&str]| {
+            let modules = ids.iter().map(|id| module(id)).collect();
+            AnalysisContext::new(modules, crate::AnalysisConfig::default())
+        };
+
+        // `Token.sol` imports `Base.sol`, so both of their contexts hold
+        // `Base`, and so does the single context twice, as in the CLI.
+        let files = vec![
+            ("Token.sol".to_string(), context(&["Base", "Token"])),
+            ("Other.sol".to_string(), context(&["Other"])),
+            ("Base.sol".to_string(), context(&["Base"])),
+        ];
+        let (per_file, _) = engine.run_files(files);
+        let single = engine.run(&mut context(&["Base", "Token", "Other", "Base"]));
+
+        let files: Vec<_> = per_file
+            .bugs
+            .iter()
+            .map(|bug| bug.loc.file.as_deref())
+            .collect();
+        assert_eq!(files, [Some("Base"), Some("Other"), Some("Token")]);
+        assert_eq!(format!("{:?}", per_file.bugs), format!("{:?}", single.bugs));
+    }
+
+    #[test]
+    fn test_pipeline_timing() {
+        use crate::detectors::scan_adapter::ScanDetectorAdapter;
+        use crate::output::{AnalysisReport, format_profile};
+
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(ScanDetectorAdapter::new(Box::new(Probe { slow: true }))));
+        let config = PipelineConfig { parallel: false, ..PipelineConfig::default() };
+        let engine = PipelineEngine::with_registry(registry, config);
+        let files = ["slow", "fast"]
+            .into_iter()
+            .map(|id| {
+                let module =
+                    scirs::sir::Module { id: id.to_string(), attrs: vec![], decls: vec![] };
+                let context = AnalysisContext::new(vec![module], crate::AnalysisConfig::default());
+                (format!("{id}.sol"), context)
+            })
+            .collect();
+        let timing = engine.run_files(files).0.timing();
+
+        let keys: Vec<&str> = timing.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["detector:Slow Probe", "pass:metrics"]);
+        assert!(timing["detector:Slow Probe"] >= Duration::from_millis(200));
+
+        let mut report = AnalysisReport::new(vec![], vec![], Duration::ZERO);
+        report.timing = timing;
+        let profile = format_profile(&report);
+        let lines: Vec<&str> = profile.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "Profile (slowest first):");
+        assert!(lines[2].ends_with("detector:Slow Probe"));
+        assert!(lines[3].ends_with("pass:metrics"));
+    }
+
+    #[test]
+    fn test_run_with_observer() {
+        use crate::detectors::scan_adapter::ScanDetectorAdapter;
+
+        #[derive(Default)]
+        struct Recorder {
+            events: Vec<String>,
+        }
+
+        impl DetectionObserver for Recorder {
+            fn on_detector_start(&mut self, detector: &dyn BugDetectionPass) {
+                self.events.push(format!("start {}", detector.name()));
+            }
+            fn on_detector_finish(
+                &mut self,
+                detector: &dyn BugDetectionPass,
+                stats: &DetectorStats,
+            ) {
+                self.events
+                    .push(format!("finish {} {}", detector.name(), stats.bug_count));
+            }
+            fn on_bug(&mut self, bug: &Bug) {
+                self.events.push(format!("bug {}", bug.name));
+            }
+        }
+
+        let mut registry = DetectorRegistry::new();
+        registry.register(Box::new(ScanDetectorAdapter::new(Box::new(Probe { slow: false }))));
+        let engine = PipelineEngine::with_registry(registry, PipelineConfig::default());
+        let module = scirs::sir::Module { id: "fast".to_string(), attrs: vec![], decls: vec![] };
+        let mut context = AnalysisContext::new(vec![module], crate::AnalysisConfig::default());
+
+        let mut recorder = Recorder::default();
+        let result = engine.run_with_observer(&mut context, &mut recorder);
+        assert_eq!(result.total_bugs(), 1);
+        assert_eq!(result.bugs[0].confidence, Some(bugs::bug::Confidence::High));
+        assert_eq!(
+            recorder.events,
+            [
+                "start Marker Probe",
+                "bug Marker Probe",
+                "finish Marker Probe 1"
+            ]
+        );
+    }
+
     #[test]
     fn test_create_analysis_pass() {
         assert!(
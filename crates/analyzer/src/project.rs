@@ -0,0 +1,428 @@
+//! Project-level analysis.
+//!
+//! Analyzes a directory of Solidity sources as one project: all `.sol` files
+//! are discovered recursively, compiled with shared project settings, and
+//! lowered together so that cross-contract analyses see every contract.
+//! Files that fail to compile are recorded in the report's
+//! `compile_errors` and do not stop the analysis of the others; a failure to
+//! lower the project records every file there.
+
+use crate::context::SmtFindingsArtifact;
+use crate::incremental::{BuildCache, CompiledFile, ImportResolver};
+use crate::{
+    AnalysisConfig, AnalysisContext, AnalysisReport, Config, InputLanguage, PipelineConfig,
+    PipelineEngine,
+};
+use common::source::{FileSystem, SourceProvider};
+use frontend::solidity::ast::SourceUnit;
+use frontend::solidity::parsing::{
+    compile_input_file_to_json_from, compile_input_file_to_json_with_smt_from,
+    parse_compiled_json_from,
+};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use walkdir::WalkDir;
+
+/// Analyze a Solidity project directory, see [`analyze_path`].
+///
+/// Fails only if `path` is not a directory; files that do not compile are
+/// listed in the report's `compile_errors`.
+pub fn analyze_directory(path: &Path, config: &Config) -> Result<AnalysisReport, String> {
+    if !path.is_dir() {
+        return Err(format!("'{}' is not a directory", path.display()));
+    }
+    Ok(analyze_path(path, config))
+}
+
+/// Analyze a Solidity file or a project directory.
+///
+/// When `path` is a directory, every `.sol` file below it that matches the
+/// project's include globs and is not excluded is compiled. Source units
+/// reached from several entry points (e.g. shared imports) are analyzed
+/// once. Files that fail to compile are skipped and recorded in the report's
+/// `compile_errors`, like every file if the project cannot be lowered.
+///
+/// With a `cache_dir`, Solc outputs are reused from earlier runs for every
+/// file whose import closure is unchanged. With `model_checker`, every file
+/// is compiled again and Solc's SMTChecker findings are stored in the
+/// analysis context as [`SmtFindingsArtifact`].
+///
+/// The project is analyzed as a single unit, so the per-file timeout bounds
+/// the whole run; when it is exceeded every analyzed file is reported as
+/// timed out.
+pub fn analyze_path(path: &Path, config: &Config) -> AnalysisReport {
+    let start = Instant::now();
+    let project = &config.project;
+
+    let root = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(Path::new("."))
+    };
+    let base_path = project
+        .base_path
+        .clone()
+        .or_else(|| root.to_str().map(str::to_string));
+
+    let files = if path.is_dir() {
+        discover_source_files(path, &project.include, &project.exclude)
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    analyze_files(&FileSystem, &files, base_path.as_deref(), config, start)
+}
+
+/// Analyze sources supplied by `sources` rather than read from disk, e.g.
+/// editor buffers or [`InMemorySources`](common::source::InMemorySources).
+///
+/// Each of `entry_files` is compiled like a file of [`analyze_path`], with
+/// the project's base path, include paths and remappings; imports are
+/// resolved among the provider's paths. Solc outputs of virtual sources are
+/// never cached.
+pub fn analyze_sources(
+    sources: &dyn SourceProvider,
+    entry_files: &[String],
+    config: &Config,
+) -> AnalysisReport {
+    let start = Instant::now();
+    let files: Vec<PathBuf> = entry_files.iter().map(PathBuf::from).collect();
+    analyze_files(sources, &files, config.project.base_path.as_deref(), config, start)
+}
+
+/// Compile `files` through `sources` and analyze them as one project.
+fn analyze_files(
+    sources: &dyn SourceProvider,
+    files: &[PathBuf],
+    base_path: Option<&str>,
+    config: &Config,
+    start: Instant,
+) -> AnalysisReport {
+    let project = &config.project;
+
+    let mut smt_findings = vec![];
+    let mut compile = |file: &Path| {
+        let file = file.to_string_lossy();
+        let (include_paths, remappings) = (&project.include_paths, &project.remappings);
+        let solc_version = project.solc_version.as_deref();
+        let compiled = if project.model_checker {
+            compile_input_file_to_json_with_smt_from(
+                sources,
+                &file,
+                base_path,
+                include_paths,
+                remappings,
+                solc_version,
+            )
+            .map(|(json, solc_ver, findings)| {
+                // Shared imports are checked with every file importing them.
+                for finding in findings {
+                    if !smt_findings.contains(&finding) {
+                        smt_findings.push(finding);
+                    }
+                }
+                (json, solc_ver)
+            })
+        } else {
+            compile_input_file_to_json_from(
+                sources,
+                &file,
+                base_path,
+                include_paths,
+                remappings,
+                solc_version,
+            )
+        };
+        compiled
+            .map(|(json, solc_ver)| CompiledFile { json, solc_version: solc_ver.to_string() })
+            .map_err(|err| err.to_string())
+    };
+    let outputs = match &project.cache_dir {
+        Some(cache_dir) if sources.virtual_paths().is_none() && !project.model_checker => {
+            let resolver =
+                ImportResolver::new(base_path, &project.include_paths, &project.remappings);
+            let settings = format!(
+                "{:?}",
+                (&base_path, &project.include_paths, &project.remappings, &project.solc_version)
+            );
+            BuildCache::open(Path::new(cache_dir), resolver, &settings).compile(files, compile)
+        }
+        _ => files
+            .iter()
+            .map(|file| (file.clone(), compile(file)))
+            .collect(),
+    };
+
+    let mut source_units: Vec<SourceUnit> = vec![];
+    let mut files_analyzed: Vec<String> = vec![];
+    let mut compiler_versions = BTreeMap::new();
+    let mut compile_errors = BTreeMap::new();
+    for (file, output) in outputs {
+        let file_str = file.to_string_lossy().to_string();
+        let parsed = output.and_then(|compiled| {
+            let solc_ver = node_semver::Version::parse(&compiled.solc_version)
+                .map_err(|err| err.to_string())?;
+            parse_compiled_json_from(
+                sources,
+                &compiled.json,
+                &file_str,
+                base_path,
+                &project.remappings,
+                &solc_ver,
+            )
+            .map(|units| (units, solc_ver))
+            .map_err(|err| err.to_string())
+        });
+        match parsed {
+            Ok((units, solc_ver)) => {
+                for unit in units {
+                    if !source_units.iter().any(|u| u.path == unit.path) {
+                        source_units.push(unit);
+                    }
+                }
+                compiler_versions.insert(file_str.clone(), solc_ver.to_string());
+                files_analyzed.push(file_str);
+            }
+            Err(err) => {
+                compile_errors.insert(file_str, err);
+            }
+        }
+    }
+
+    // The project is lowered as one unit: if that fails, none of its files
+    // is analyzed.
+    let ir_units = match frontend::solidity::lowering::lower_source_units(&source_units) {
+        Ok(modules) => modules,
+        Err(err) => {
+            for file in files_analyzed.drain(..) {
+                compiler_versions.remove(&file);
+                compile_errors.insert(file, format!("Error lowering project: {err}"));
+            }
+            vec![]
+        }
+    };
+
+    let analysis_config =
+        AnalysisConfig { input_language: InputLanguage::Solidity, ..AnalysisConfig::default() };
+    let mut context = AnalysisContext::new(ir_units, analysis_config);
+    if project.model_checker {
+        context.store::<SmtFindingsArtifact>(smt_findings);
+    }
+
+    let engine = PipelineEngine::new(PipelineConfig {
+        parallel: config.num_threads > 1,
+        num_threads: config.num_threads,
+        enabled: config.detectors.enabled.clone(),
+        disabled: config.detectors.disabled.clone(),
+        tags: config.detectors.tags.clone(),
+        params: config.detectors.params.clone(),
+        timeout: config.file_timeout,
+    });
+    let result = engine.run(&mut context);
+    let timing = result.timing();
+
+    let bugs = result
+        .bugs
+        .into_iter()
+        .filter(|bug| config.should_report_severity(&bug.risk_level))
+        .collect();
+
+    let mut report =
+        AnalysisReport::with_language(bugs, files_analyzed, start.elapsed(), "solidity");
+    report.compiler_versions = compiler_versions;
+    report.compile_errors = compile_errors;
+    report.contract_metrics = result.contract_metrics;
+    report.timing = timing;
+    if result.timed_out {
+        report.timed_out = report.files_analyzed.iter().cloned().collect();
+    }
+    report
+}
+
+/// Recursively collect the `.sol` files below `root`, sorted by path.
+///
+/// Patterns are matched against paths relative to `root`. An empty `include`
+/// list selects every Solidity file. `exclude` follows `.gitignore` rules,
+/// see [`ExcludeList`].
+pub fn discover_source_files(root: &Path, include: &[String], exclude: &[String]) -> Vec<PathBuf> {
+    let include: Vec<Regex> = include.iter().filter_map(|g| glob_to_regex(g)).collect();
+    let exclude = ExcludeList::new(exclude);
+
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sol"))
+        .filter(|path| {
+            let rel = path.strip_prefix(root).unwrap_or(path);
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            (include.is_empty() || include.iter().any(|re| re.is_match(&rel)))
+                && !exclude.is_excluded(&rel)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Exclude patterns in `.gitignore` syntax.
+///
+/// A pattern without a `/` (other than a trailing one) matches a file or
+/// directory name at any depth, e.g. `mocks` or `*.t.sol`; otherwise it is
+/// relative to the project root, with an optional leading `/`. A trailing
+/// `/` matches directories only. Excluding a directory excludes everything
+/// below it, and a `!pattern` re-includes what earlier patterns excluded;
+/// the last matching pattern wins.
+#[derive(Debug, Default)]
+pub struct ExcludeList {
+    /// Each pattern, whether it negates, and whether it matches only
+    /// directories.
+    patterns: Vec<(Regex, bool, bool)>,
+}
+
+impl ExcludeList {
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty() && !p.starts_with('#'))
+            .filter_map(|pattern| {
+                let (negated, pattern) = match pattern.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, pattern),
+                };
+                let (dir_only, pattern) = match pattern.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, pattern),
+                };
+                let glob = match pattern.strip_prefix('/') {
+                    Some(rest) => rest.to_string(),
+                    None if pattern.contains('/') => pattern.to_string(),
+                    None => format!("**/{}", pattern),
+                };
+                glob_to_regex(&glob).map(|re| (re, negated, dir_only))
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Whether the file at `rel`, a `/`-separated path relative to the
+    /// project root, is excluded.
+    pub fn is_excluded(&self, rel: &str) -> bool {
+        let mut excluded = false;
+        for (re, negated, dir_only) in &self.patterns {
+            let dir_matches = rel
+                .match_indices('/')
+                .any(|(end, _)| re.is_match(&rel[..end]));
+            if dir_matches || (!dir_only && re.is_match(rel)) {
+                excluded = !negated;
+            }
+        }
+        excluded
+    }
+}
+
+/// Translate a glob pattern into an anchored regular expression.
+///
+/// Supports `**` (any path, including separators), `*` (any run of
+/// characters within a path component) and `?` (one such character).
+fn glob_to_regex(glob: &str) -> Option<Regex> {
+    let mut re = String::from("^");
+    let chars: Vec<char> = glob.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                // `**/` matches zero or more leading directories.
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    re.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    re.push('$');
+    Regex::new(&re).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = glob_to_regex("test/**").unwrap();
+        assert!(re.is_match("test/Token.t.sol"));
+        assert!(re.is_match("test/unit/Vault.t.sol"));
+        assert!(!re.is_match("src/Token.sol"));
+
+        let re = glob_to_regex("**/*.t.sol").unwrap();
+        assert!(re.is_match("Token.t.sol"));
+        assert!(re.is_match("test/unit/Vault.t.sol"));
+        assert!(!re.is_match("src/Vault.sol"));
+    }
+
+    #[test]
+    fn test_discover_source_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/lib")).unwrap();
+        std::fs::create_dir_all(root.join("test")).unwrap();
+        std::fs::write(root.join("src/Token.sol"), "").unwrap();
+        std::fs::write(root.join("src/lib/Math.sol"), "").unwrap();
+        std::fs::write(root.join("src/README.md"), "").unwrap();
+        std::fs::write(root.join("test/Token.t.sol"), "").unwrap();
+
+        let all = discover_source_files(root, &[], &[]);
+        assert_eq!(all.len(), 3);
+
+        let files = discover_source_files(root, &[], &["test/**".to_string()]);
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.strip_prefix(root).unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["src/Token.sol", "src/lib/Math.sol"]);
+    }
+
+    #[test]
+    fn test_exclude_list_gitignore_rules() {
+        let patterns = [
+            "mocks/",
+            "*.t.sol",
+            "/script",
+            "!src/mocks/Keep.sol",
+            "# comment",
+        ];
+        let exclude = ExcludeList::new(&patterns.map(String::from));
+        assert!(exclude.is_excluded("src/mocks/Token.sol"));
+        assert!(exclude.is_excluded("test/unit/Vault.t.sol"));
+        assert!(exclude.is_excluded("script/Deploy.sol"));
+        assert!(!exclude.is_excluded("src/script/Deploy.sol"));
+        assert!(!exclude.is_excluded("src/mocks/Keep.sol"));
+        // `mocks/` matches directories only.
+        assert!(!exclude.is_excluded("src/mocks"));
+        assert!(!exclude.is_excluded("src/Vault.sol"));
+    }
+
+    #[test]
+    fn test_analyze_directory_records_compile_errors() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        assert!(analyze_directory(file.path(), &Config::default()).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Broken.sol"), "contract {").unwrap();
+        let report = analyze_directory(dir.path(), &Config::default()).unwrap();
+        assert!(report.files_analyzed.is_empty());
+        assert_eq!(report.compile_errors.len(), 1);
+    }
+}
@@ -0,0 +1,188 @@
+//! Heuristic ranking of findings.
+//!
+//! [`rank`] orders findings by a weighted score of their severity, the
+//! confidence of the detector that reported them and whether they are
+//! reachable from an entry contract, so that a "top N" view shows the most
+//! important findings first. The score is a plain weighted sum; equal scores
+//! keep the report order, so the ranking is deterministic. The pipeline
+//! records the confidence of the reporting detector on each finding; for
+//! findings from elsewhere, [`ConfidenceTable`] looks it up from the
+//! built-in detectors.
+
+use crate::detectors::ConfidenceLevel;
+use crate::detectors::base::registry::{DetectorRegistry, register_all_detectors};
+use bugs::bug::{Bug, RiskLevel};
+use std::sync::LazyLock;
+
+/// Weights of the components of a finding's score. Each component is
+/// normalized to `0.0..=1.0` before weighting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankingWeights {
+    /// Weight of the severity, from informational (0) to critical (1).
+    pub severity: f64,
+
+    /// Weight of the detector's confidence, from low (0) to high (1).
+    pub confidence: f64,
+
+    /// Weight of reachability: 1 for findings reachable from an entry
+    /// contract, 0 for those set apart as unreachable.
+    pub reachability: f64,
+}
+
+impl Default for RankingWeights {
+    fn default() -> Self {
+        Self { severity: 3.0, confidence: 1.0, reachability: 2.0 }
+    }
+}
+
+impl RankingWeights {
+    /// Score of a finding with the given severity, confidence and
+    /// reachability.
+    pub fn score(&self, risk: RiskLevel, confidence: ConfidenceLevel, reachable: bool) -> f64 {
+        let severity = match risk {
+            RiskLevel::No => 0.0,
+            RiskLevel::Low => 0.25,
+            RiskLevel::Medium => 0.5,
+            RiskLevel::High => 0.75,
+            RiskLevel::Critical => 1.0,
+        };
+        let confidence = match confidence {
+            ConfidenceLevel::Low => 0.0,
+            ConfidenceLevel::Medium => 0.5,
+            ConfidenceLevel::High => 1.0,
+        };
+        let reachable = if reachable { 1.0 } else { 0.0 };
+        self.severity * severity + self.confidence * confidence + self.reachability * reachable
+    }
+}
+
+/// Confidence of findings: the one recorded on the finding, else that of
+/// the built-in detector named by [`Bug::name`]. Other findings count as
+/// medium confidence.
+pub struct ConfidenceTable {
+    registry: DetectorRegistry,
+}
+
+impl Default for ConfidenceTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfidenceTable {
+    pub fn new() -> Self {
+        let mut registry = DetectorRegistry::new();
+        register_all_detectors(&mut registry);
+        Self { registry }
+    }
+
+    /// Table of the built-in detectors, built on first use.
+    pub fn builtin() -> &'static Self {
+        static TABLE: LazyLock<ConfidenceTable> = LazyLock::new(ConfidenceTable::new);
+        &TABLE
+    }
+
+    /// Confidence of the detector that reported `bug`.
+    pub fn confidence_of(&self, bug: &Bug) -> ConfidenceLevel {
+        bug.confidence.unwrap_or_else(|| {
+            self.registry
+                .get(&bug.name)
+                .map_or(ConfidenceLevel::Medium, |d| d.confidence())
+        })
+    }
+}
+
+/// Rank `reachable` and `unreachable` findings together, highest score
+/// first, with confidences from [`ConfidenceTable`]. Without a reachability
+/// analysis `unreachable` is empty and every finding counts as reachable.
+pub fn rank<'a>(
+    reachable: &'a [Bug],
+    unreachable: &'a [Bug],
+    weights: &RankingWeights,
+) -> Vec<&'a Bug> {
+    let table = ConfidenceTable::builtin();
+    let confidence = |bug: &Bug| table.confidence_of(bug);
+
+    let mut scored: Vec<(f64, &Bug)> = reachable
+        .iter()
+        .map(|bug| (bug, true))
+        .chain(unreachable.iter().map(|bug| (bug, false)))
+        .map(|(bug, reachable)| (weights.score(bug.risk_level, confidence(bug), reachable), bug))
+        .collect();
+    scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    scored.into_iter().map(|(_, bug)| bug).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bugs::bug::{BugCategory, BugKind};
+    use common::loc::Loc;
+
+    fn bug(name: &str, risk: RiskLevel) -> Bug {
+        Bug::new(
+            name,
+            None,
+            Loc::new(1, 1, 1, 1),
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            risk,
+            vec![],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_reachable_high_ranks_above_unreachable_low() {
+        let reachable = [bug("Reachable", RiskLevel::High)];
+        let unreachable = [bug("Unreachable", RiskLevel::Low)];
+        let ranked = rank(&reachable, &unreachable, &RankingWeights::default());
+        let names: Vec<&str> = ranked.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["Reachable", "Unreachable"]);
+    }
+
+    #[test]
+    fn test_weights_are_configurable() {
+        let reachable = [bug("Reachable", RiskLevel::Low)];
+        let unreachable = [bug("Unreachable", RiskLevel::High)];
+        let weights = RankingWeights { severity: 0.0, confidence: 1.0, reachability: 1.0 };
+        let ranked = rank(&reachable, &unreachable, &weights);
+        assert_eq!(ranked[0].name, "Reachable");
+    }
+
+    #[test]
+    fn test_report_priority_blends_severity_and_confidence() {
+        use crate::output::AnalysisReport;
+        use std::time::Duration;
+
+        // Low severity from a low-confidence detector, then high severity
+        // from a high-confidence one.
+        let bugs = vec![
+            bug("Stale State Read", RiskLevel::Low),
+            bug("Dangerous use of tx.origin", RiskLevel::High),
+        ];
+        let report = AnalysisReport::new(bugs, vec![], Duration::from_secs(1));
+        let names: Vec<&str> = report
+            .sorted_by_priority()
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        assert_eq!(names, ["Dangerous use of tx.origin", "Stale State Read"]);
+
+        let confident = report.with_min_confidence(ConfidenceLevel::Medium);
+        assert_eq!(confident.bugs.len(), 1);
+        assert_eq!(confident.bugs[0].name, "Dangerous use of tx.origin");
+        assert_eq!(confident.stats.bugs_by_severity.low, 0);
+    }
+
+    #[test]
+    fn test_recorded_confidence_is_used() {
+        // Findings of custom detectors carry their detector's confidence.
+        let mut custom = bug("Custom Probe", RiskLevel::Low);
+        let table = ConfidenceTable::builtin();
+        assert_eq!(table.confidence_of(&custom), ConfidenceLevel::Medium);
+        custom.confidence = Some(ConfidenceLevel::High);
+        assert_eq!(table.confidence_of(&custom), ConfidenceLevel::High);
+    }
+}
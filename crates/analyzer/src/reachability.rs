@@ -0,0 +1,366 @@
+//! Reachable attack surface.
+//!
+//! Given the contracts that are actually deployed, [`ReachableSurface`]
+//! collects their externally callable functions and everything those can
+//! reach: internal calls resolved along each entry contract's inheritance
+//! chain, `super` calls, modifiers, library calls and calls into other
+//! contracts of the project. Findings in code outside that surface (helpers
+//! nobody calls, contracts that are never deployed) are far less urgent;
+//! [`AnalysisReport::reachable_findings`] sets them apart.
+//!
+//! Functions are identified as `Contract::signature`, the contract being the
+//! one that declares the function, which matches [`Bug::anchor`].
+//!
+//! [`AnalysisReport::reachable_findings`]: crate::output::AnalysisReport::reachable_findings
+
+use crate::anchor::anchor_bugs;
+use bugs::bug::Bug;
+use scirs::sir::dialect::DialectMemberDecl;
+use scirs::sir::dialect::EvmFunctionExt;
+use scirs::sir::dialect::evm::EvmMemberDecl;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    CallExpr, ContractDecl, Decl, Expr, FunctionDecl, MemberDecl, Module, Stmt, Type,
+};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Functions reachable from a set of entry contracts.
+#[derive(Debug, Clone, Default)]
+pub struct ReachableSurface {
+    entry_points: BTreeSet<String>,
+    functions: BTreeSet<String>,
+    contracts: BTreeSet<String>,
+}
+
+impl ReachableSurface {
+    /// Compute the surface of `entry_contracts` over all contracts of
+    /// `modules`. Unknown entry names are ignored.
+    pub fn compute(modules: &[Module], entry_contracts: &[String]) -> Self {
+        let contracts: HashMap<&str, &ContractDecl> = modules
+            .iter()
+            .flat_map(|m| &m.decls)
+            .filter_map(|d| match d {
+                Decl::Contract(c) => Some((c.name.as_str(), c)),
+                _ => None,
+            })
+            .collect();
+        let mut walker = Walker { contracts, surface: Self::default(), seen: HashSet::new() };
+
+        let mut pending = vec![];
+        for entry in entry_contracts {
+            let Some(&entry) = walker.contracts.get(entry.as_str()) else {
+                continue;
+            };
+            let lineage = walker.lineage(entry);
+            for (i, contract) in lineage.iter().enumerate() {
+                for func in functions(contract) {
+                    let callable = func.is_public()
+                        || func.is_external()
+                        || matches!(func.name.as_str(), "fallback" | "receive");
+                    // Every constructor of the chain runs on deployment.
+                    let overridden = lineage[..i]
+                        .iter()
+                        .any(|c| functions(c).any(|f| f.abi_signature() == func.abi_signature()));
+                    if func.is_constructor() || (callable && !overridden) {
+                        walker.surface.entry_points.insert(key(contract, func));
+                        pending.push((entry, *contract, func));
+                    }
+                }
+            }
+        }
+        walker.walk(pending);
+        walker.surface
+    }
+
+    /// Externally callable functions of the entry contracts, and their
+    /// constructors.
+    pub fn entry_points(&self) -> &BTreeSet<String> {
+        &self.entry_points
+    }
+
+    /// Every function reachable from an entry point, entry points included.
+    pub fn functions(&self) -> &BTreeSet<String> {
+        &self.functions
+    }
+
+    /// Returns `true` unless `bug` lies in a function, or outside any
+    /// function in a contract, that cannot be reached. Findings without an
+    /// anchor cannot be placed and count as reachable.
+    pub fn is_reachable(&self, bug: &Bug) -> bool {
+        let Some(anchor) = &bug.source_anchor else {
+            return true;
+        };
+        match &anchor.function {
+            Some(sig) => self
+                .functions
+                .contains(&format!("{}::{}", anchor.contract, sig)),
+            None => self.contracts.contains(&anchor.contract),
+        }
+    }
+
+    /// Split `bugs` into reachable and unreachable findings, anchoring them
+    /// in `modules` first where needed.
+    pub fn partition(&self, bugs: &[Bug], modules: &[Module]) -> (Vec<Bug>, Vec<Bug>) {
+        let mut bugs = bugs.to_vec();
+        anchor_bugs(&mut bugs, modules);
+        bugs.into_iter().partition(|bug| self.is_reachable(bug))
+    }
+}
+
+fn key(contract: &ContractDecl, func: &FunctionDecl) -> String {
+    format!("{}::{}", contract.name, func.abi_signature())
+}
+
+fn functions(contract: &ContractDecl) -> impl Iterator<Item = &FunctionDecl> {
+    contract.members.iter().filter_map(|m| match m {
+        MemberDecl::Function(f) => Some(f),
+        _ => None,
+    })
+}
+
+/// A call site, before resolution against a contract.
+enum Call {
+    /// `f(..)` or `this.f(..)`: dispatched on the contract being executed.
+    Virtual(String),
+    /// `super.f(..)`: the next definition above the calling contract.
+    Super(String),
+    /// `C.f(..)` or `c.f(..)` with `c` of type `C`: library calls and calls
+    /// into other contracts.
+    Member(String, String),
+}
+
+/// Call sites of `body`. Member calls are kept when the base names a
+/// contract or library, or has a contract type.
+fn calls(body: &[Stmt], contracts: &HashMap<&str, &ContractDecl>) -> Vec<Call> {
+    struct Collector<'c, 'm> {
+        contracts: &'c HashMap<&'m str, &'m ContractDecl>,
+        calls: Vec<Call>,
+    }
+    impl<'a> Visit<'a> for Collector<'_, '_> {
+        fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+            let call = match &*expr.callee {
+                Expr::Var(v) => Some(Call::Virtual(v.name.clone())),
+                Expr::FieldAccess(fa) => match &*fa.base {
+                    Expr::Var(b) if b.name == "this" => Some(Call::Virtual(fa.field.clone())),
+                    Expr::Var(b) if b.name == "super" => Some(Call::Super(fa.field.clone())),
+                    Expr::Var(b) if self.contracts.contains_key(b.name.as_str()) => {
+                        Some(Call::Member(b.name.clone(), fa.field.clone()))
+                    }
+                    base => match base.typ() {
+                        Type::TypeRef(name) => Some(Call::Member(name, fa.field.clone())),
+                        _ => None,
+                    },
+                },
+                _ => None,
+            };
+            self.calls.extend(call);
+            visit::default::visit_call_expr(self, expr);
+        }
+    }
+
+    let mut collector = Collector { contracts, calls: vec![] };
+    collector.visit_stmts(body);
+    collector.calls
+}
+
+struct Walker<'m> {
+    contracts: HashMap<&'m str, &'m ContractDecl>,
+    surface: ReachableSurface,
+    /// Visited `(executing contract, function key)` pairs.
+    seen: HashSet<(String, String)>,
+}
+
+impl<'m> Walker<'m> {
+    /// `contract` followed by its ancestors, most derived first.
+    fn lineage(&self, contract: &'m ContractDecl) -> Vec<&'m ContractDecl> {
+        let mut lineage = vec![];
+        let mut pending = VecDeque::from([contract]);
+        let mut seen = HashSet::new();
+        while let Some(contract) = pending.pop_front() {
+            if !seen.insert(contract.name.as_str()) {
+                continue;
+            }
+            lineage.push(contract);
+            // Solidity lists bases from most base-like to most derived.
+            for parent in contract.parents.iter().rev() {
+                if let Some(&parent) = self.contracts.get(parent.as_str()) {
+                    pending.push_back(parent);
+                }
+            }
+        }
+        lineage
+    }
+
+    /// Definitions named `name` in the first contract of `lineage` that has
+    /// any.
+    fn resolve(
+        lineage: &[&'m ContractDecl],
+        name: &str,
+    ) -> Vec<(&'m ContractDecl, &'m FunctionDecl)> {
+        lineage
+            .iter()
+            .map(|c| {
+                functions(c)
+                    .filter(|f| f.name == name)
+                    .map(|f| (*c, f))
+                    .collect::<Vec<_>>()
+            })
+            .find(|defs| !defs.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Contracts deriving from `name`, itself included.
+    fn implementors(&self, name: &str) -> Vec<&'m ContractDecl> {
+        self.contracts
+            .values()
+            .filter(|c| self.lineage(c).iter().any(|a| a.name == name))
+            .copied()
+            .collect()
+    }
+
+    /// Mark everything reachable from `pending`, a list of functions with
+    /// the contract they execute in and the contract declaring them.
+    fn walk(&mut self, mut pending: Vec<(&'m ContractDecl, &'m ContractDecl, &'m FunctionDecl)>) {
+        while let Some((this, owner, func)) = pending.pop() {
+            let key = key(owner, func);
+            if !self.seen.insert((this.name.clone(), key.clone())) {
+                continue;
+            }
+            self.surface.functions.insert(key);
+            self.surface.contracts.insert(this.name.clone());
+            self.surface.contracts.insert(owner.name.clone());
+
+            let lineage = self.lineage(this);
+            let mut calls = calls(func.body.as_deref().unwrap_or_default(), &self.contracts);
+            for invoc in &func.modifier_invocs {
+                let modifier = lineage.iter().find_map(|c| {
+                    c.members.iter().find_map(|m| match m {
+                        MemberDecl::Dialect(DialectMemberDecl::Evm(
+                            EvmMemberDecl::ModifierDef(m),
+                        )) if m.name == invoc.name => Some(m),
+                        _ => None,
+                    })
+                });
+                if let Some(modifier) = modifier {
+                    calls.extend(self::calls(&modifier.body, &self.contracts));
+                }
+            }
+
+            for call in calls {
+                let targets: Vec<_> = match call {
+                    Call::Virtual(name) => Self::resolve(&lineage, &name)
+                        .into_iter()
+                        .map(|(c, f)| (this, c, f))
+                        .collect(),
+                    Call::Super(name) => {
+                        let above = self.lineage(owner);
+                        Self::resolve(&above[1..], &name)
+                            .into_iter()
+                            .map(|(c, f)| (this, c, f))
+                            .collect()
+                    }
+                    Call::Member(contract, name) => {
+                        let mut targets = vec![];
+                        for target in self.implementors(&contract) {
+                            let lineage = self.lineage(target);
+                            targets.extend(
+                                Self::resolve(&lineage, &name)
+                                    .into_iter()
+                                    .map(|(c, f)| (target, c, f)),
+                            );
+                        }
+                        targets
+                    }
+                };
+                pending.extend(targets);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::AnalysisReport;
+    use bugs::bug::{BugCategory, BugKind, RiskLevel};
+    use common::loc::Loc;
+    use scirs::sir::*;
+    use std::time::Duration;
+
+    /// `name() <visibility> { <callee>(); }` spanning `line..line + 2`.
+    fn function(name: &str, visibility: &str, callee: Option<&str>, line: usize) -> MemberDecl {
+        let body = callee
+            .map(|callee| {
+                let call = Expr::FunctionCall(CallExpr {
+                    callee: Box::new(Expr::Var(VarExpr::new(
+                        callee.to_string(),
+                        Type::None,
+                        None,
+                    ))),
+                    args: CallArgs::Positional(vec![]),
+                    ty: Type::None,
+                    span: None,
+                });
+                Stmt::Expr(ExprStmt { expr: call, span: None })
+            })
+            .into_iter()
+            .collect();
+        let span = Some(Loc::new(line, 1, line + 2, 1));
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), span);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String(visibility.to_string())));
+        MemberDecl::Function(func)
+    }
+
+    /// `Vault.withdraw()` calls `_send()`; nothing calls `_legacySend()`.
+    fn vault() -> Module {
+        let members = vec![
+            function("withdraw", "external", Some("_send"), 2),
+            function("_send", "internal", None, 5),
+            function("_legacySend", "internal", None, 8),
+        ];
+        let contract =
+            ContractDecl::new("Vault".to_string(), members, Some(Loc::new(1, 1, 11, 1)));
+        Module {
+            id: "Vault.sol".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(contract)],
+        }
+    }
+
+    fn bug_at(line: usize) -> Bug {
+        Bug::new(
+            "Test Bug",
+            None,
+            Loc::new(line, 5, line, 20),
+            BugKind::Vulnerability,
+            BugCategory::Other,
+            RiskLevel::High,
+            vec![],
+            vec![],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_entry_points_and_reachable_functions() {
+        let surface = ReachableSurface::compute(&[vault()], &["Vault".to_string()]);
+        let entry_points: Vec<&str> = surface.entry_points().iter().map(|s| s.as_str()).collect();
+        assert_eq!(entry_points, vec!["Vault::withdraw()"]);
+        assert!(surface.functions().contains("Vault::_send()"));
+        assert!(!surface.functions().contains("Vault::_legacySend()"));
+    }
+
+    #[test]
+    fn test_unreachable_finding_is_set_apart() {
+        let report =
+            AnalysisReport::new(vec![bug_at(6), bug_at(9)], vec![], Duration::from_secs(0));
+        let reachable = report.reachable_findings(&[vault()], &["Vault".to_string()]);
+
+        assert_eq!(reachable.bugs.len(), 1);
+        assert_eq!(reachable.bugs[0].loc.start_line, 6);
+        assert_eq!(reachable.unreachable.len(), 1);
+        assert_eq!(reachable.unreachable[0].anchor(), "Vault::_legacySend()");
+        assert_eq!(reachable.stats.bugs_by_severity.high, 1);
+    }
+}
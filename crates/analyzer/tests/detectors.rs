@@ -1,6 +1,8 @@
 //! Unit tests for detectors.
 
-use analyzer::{DetectorRegistry, register_all_detectors};
+use analyzer::{Category, DetectorRegistry, Severity, register_all_detectors};
+use bugs::bug::{Bug, BugKind};
+use common::loc::Loc;
 
 fn create_registry() -> DetectorRegistry {
     let mut registry = DetectorRegistry::new();
@@ -46,6 +48,31 @@ fn test_tx_origin_detector() {
     assert_eq!(detector.cwe_ids(), vec![345]);
 }
 
+/// Test selecting detectors by tag.
+#[test]
+fn test_detectors_by_tag() {
+    let registry = create_registry();
+
+    let mut ids: Vec<&str> = registry
+        .by_tag("proxy")
+        .iter()
+        .map(|d| d.detector_id().as_str())
+        .collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "delegatecall",
+            "delegatecall-mutable-target",
+            "missing-storage-gap",
+            "storage-layout-collision",
+            "transparent-proxy-clash"
+        ]
+    );
+
+    assert!(registry.by_tag("no-such-tag").is_empty());
+}
+
 /// Test reentrancy detector metadata.
 #[test]
 fn test_reentrancy_detector() {
@@ -142,3 +169,214 @@ fn test_dead_code_detector() {
     assert_eq!(detector.detector_id().as_str(), "dead-code");
     assert!(detector.cwe_ids().contains(&561));
 }
+
+/// A pass implemented directly against `BugDetectionPass`.
+struct InfoPass;
+
+impl analyzer::Pass for InfoPass {
+    fn name(&self) -> &'static str {
+        "Info Pass"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports an informational finding"
+    }
+
+    fn level(&self) -> analyzer::PassLevel {
+        analyzer::PassLevel::Function
+    }
+
+    fn representation(&self) -> analyzer::PassRepresentation {
+        analyzer::PassRepresentation::Ir
+    }
+
+    fn dependencies(&self) -> Vec<std::any::TypeId> {
+        vec![]
+    }
+}
+
+impl analyzer::BugDetectionPass for InfoPass {
+    fn detector_id(&self) -> analyzer::DetectorId {
+        analyzer::DetectorId::DeprecatedSelfdestruct
+    }
+
+    fn detect(&self, _context: &analyzer::AnalysisContext) -> analyzer::DetectorResult<Vec<Bug>> {
+        Ok(vec![analyzer::create_bug(self, None, Loc::new(1, 1, 1, 1), None)])
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> Category {
+        Category::CodeQuality
+    }
+
+    fn risk_level(&self) -> Severity {
+        Severity::No
+    }
+
+    fn confidence(&self) -> analyzer::ConfidenceLevel {
+        analyzer::ConfidenceLevel::Medium
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+}
+
+/// Test that scan detectors and analyzer passes share one severity vocabulary.
+#[test]
+fn test_scan_and_pass_findings_share_severity_vocabulary() {
+    use analyzer::BugDetectionPass;
+    use scanner::ScanDetector;
+    use scirs::sir::dialect::evm::{EvmSelfdestruct, EvmStmt};
+    use scirs::sir::*;
+
+    // Finding from a scan detector.
+    let selfdestruct = Stmt::Dialect(DialectStmt::Evm(EvmStmt::Selfdestruct(EvmSelfdestruct {
+        recipient: Expr::Var(VarExpr::new("owner".to_string(), Type::I256, None)),
+        loc: Loc::new(1, 1, 1, 1),
+    })));
+    let func =
+        FunctionDecl::new("close".to_string(), vec![], vec![], Some(vec![selfdestruct]), None);
+    let contract = ContractDecl {
+        name: "Wallet".to_string(),
+        parents: vec![],
+        attrs: vec![],
+        members: vec![],
+        span: None,
+    };
+    let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+    let scan_detector = scanner::detectors::DeprecatedSelfdestructDetector::new();
+    let scan_bugs = scan_detector.check_function(&func, &contract, &module);
+
+    // Finding from a native pass.
+    let context = analyzer::AnalysisContext::new(vec![], analyzer::AnalysisConfig::default());
+    let pass_bugs = InfoPass.detect(&context).unwrap();
+
+    assert_eq!(scan_detector.confidence(), InfoPass.confidence());
+    for bug in scan_bugs.iter().chain(&pass_bugs) {
+        let value = serde_json::to_value(bug).unwrap();
+        assert_eq!(value["risk_level"], "Informational");
+        assert_eq!(value["category"], "CodeQuality");
+        assert_eq!(analyzer::output::JsonFinding::from(bug).severity, "Informational");
+    }
+    assert_eq!(scan_bugs.len(), 1);
+    assert_eq!(pass_bugs.len(), 1);
+}
+
+/// A detector of a downstream crate, reporting every contract.
+struct ContractListPass;
+
+impl analyzer::Pass for ContractListPass {
+    fn name(&self) -> &'static str {
+        "Contract List"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports every contract"
+    }
+
+    fn level(&self) -> analyzer::PassLevel {
+        analyzer::PassLevel::Contract
+    }
+
+    fn representation(&self) -> analyzer::PassRepresentation {
+        analyzer::PassRepresentation::Ir
+    }
+
+    fn dependencies(&self) -> Vec<std::any::TypeId> {
+        vec![]
+    }
+}
+
+impl analyzer::BugDetectionPass for ContractListPass {
+    fn detector_id(&self) -> analyzer::DetectorId {
+        analyzer::DetectorId::Custom("org-contract-list")
+    }
+
+    fn detect(&self, context: &analyzer::AnalysisContext) -> analyzer::DetectorResult<Vec<Bug>> {
+        let contracts = context
+            .ir_units()
+            .iter()
+            .flat_map(|module| &module.decls)
+            .filter_map(|decl| match decl {
+                scirs::sir::Decl::Contract(contract) => Some(contract),
+                _ => None,
+            });
+        Ok(contracts
+            .map(|contract| {
+                let desc = format!("Contract '{}'", contract.name);
+                analyzer::create_bug(self, Some(&desc), Loc::new(1, 1, 1, 1), None)
+            })
+            .collect())
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> Category {
+        Category::CodeQuality
+    }
+
+    fn risk_level(&self) -> Severity {
+        Severity::No
+    }
+
+    fn confidence(&self) -> analyzer::ConfidenceLevel {
+        analyzer::ConfidenceLevel::High
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "None."
+    }
+}
+
+/// Test registering a custom detector and running it with the built-in ones.
+#[test]
+fn test_custom_detector_registration() {
+    use analyzer::{AnalysisConfig, AnalysisContext, PipelineConfig, PipelineEngine};
+    use scirs::sir::{ContractDecl, Decl, Module};
+
+    let mut registry = create_registry();
+    let builtin = registry.len();
+    registry.register(Box::new(ContractListPass));
+    assert_eq!(registry.len(), builtin + 1);
+    let detector = registry.get("org-contract-list").unwrap();
+    assert_eq!(detector.name(), "Contract List");
+
+    let module = Module {
+        id: "test".to_string(),
+        attrs: vec![],
+        decls: vec![Decl::Contract(ContractDecl::new(
+            "Vault".to_string(),
+            vec![],
+            None,
+        ))],
+    };
+    let mut context = AnalysisContext::new(vec![], AnalysisConfig::default());
+    context.set_ir_units(vec![module]);
+    let config = PipelineConfig {
+        enabled: vec!["org-contract-list".to_string()],
+        ..PipelineConfig::default()
+    };
+    let result = PipelineEngine::with_registry(registry, config).run(&mut context);
+
+    assert_eq!(result.bugs.len(), 1);
+    assert_eq!(result.bugs[0].name, "Contract List");
+    assert_eq!(result.bugs[0].description.as_deref(), Some("Contract 'Vault'"));
+}
@@ -0,0 +1,48 @@
+//! Tests for the detector explanation (dry-run) mode.
+
+use analyzer::{TraceKind, explain_detector};
+
+const REENTRANT_BANK: &str = r#"
+pragma solidity ^0.8.0;
+
+contract Bank {
+    mapping(address => uint256) public balances;
+
+    function withdraw() public {
+        uint256 amount = balances[msg.sender];
+        (bool ok, ) = msg.sender.call{value: amount}("");
+        require(ok);
+        balances[msg.sender] = 0;
+    }
+}
+"#;
+
+/// Explain `reentrancy` on a vulnerable snippet. Skipped at runtime when no
+/// Solidity compiler is available.
+#[test]
+fn test_explain_reentrancy() {
+    let explanation = match explain_detector("reentrancy", REENTRANT_BANK, "0.8.20") {
+        Ok(explanation) => explanation,
+        Err(err) => {
+            eprintln!("Skipping explain test (Solidity compiler not available): {err}");
+            return;
+        }
+    };
+
+    assert_eq!(explanation.detector_id, "reentrancy");
+    assert!(!explanation.trace.is_empty());
+    assert!(!explanation.bugs.is_empty());
+    assert!(
+        explanation
+            .trace
+            .iter()
+            .any(|step| step.kind == TraceKind::Match && step.message.contains("Bank.withdraw"))
+    );
+}
+
+/// Unknown detector IDs are rejected before compiling.
+#[test]
+fn test_explain_unknown_detector() {
+    let err = explain_detector("no-such-detector", REENTRANT_BANK, "0.8.20").unwrap_err();
+    assert!(err.contains("no-such-detector"));
+}
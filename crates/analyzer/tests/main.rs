@@ -6,8 +6,10 @@ use analyzer::{
 use std::time::Duration;
 
 mod detectors;
+mod explain;
 mod output;
 mod passes;
+mod project;
 
 /// Test creation of default registry is handled in detectors.rs via helper now.
 
@@ -20,6 +22,55 @@ fn test_config_default() {
     assert_eq!(config.num_threads, 1);
 }
 
+/// Test reading detector options from a TOML configuration.
+#[test]
+fn test_config_detector_options() {
+    let config = Config::from_toml(
+        r#"
+[detectors]
+refactoring = true
+disabled = ["dead-code"]
+
+[detectors.floating-pragma]
+allow_caret = true
+
+[detectors.tx-origin]
+enabled = false
+
+[detectors.params.deep-nesting]
+max_depth = 4
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.detectors.disabled, ["dead-code", "tx-origin"]);
+    assert!(!config.is_detector_enabled("tx-origin"));
+    assert_eq!(config.detectors.params["floating-pragma"]["allow_caret"], "true");
+    assert_eq!(config.detectors.params["deep-nesting"]["max_depth"], "4");
+    assert!(!config.detectors.params.contains_key("tx-origin"));
+
+    assert!(Config::from_toml("[detectors]\ndisabled = \"dead-code\"").is_err());
+}
+
+/// Test that disabling a detector in its table keeps the `disabled` list.
+#[test]
+fn test_config_disabled_list_and_detector_table() {
+    let config = Config::from_toml(
+        r#"
+[detectors]
+disabled = ["dead-code"]
+
+[detectors.arbitrary-send]
+enabled = false
+"#,
+    )
+    .unwrap();
+
+    assert!(!config.is_detector_enabled("dead-code"));
+    assert!(!config.is_detector_enabled("arbitrary-send"));
+    assert!(config.is_detector_enabled("tx-origin"));
+}
+
 /// Test creating an analysis report.
 #[test]
 fn test_analysis_report_creation() {
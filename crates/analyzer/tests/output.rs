@@ -62,3 +62,79 @@ fn test_report_stats() {
     assert_eq!(report.stats.bugs_by_severity.low, 0);
     assert_eq!(report.stats.bugs_by_severity.info, 0);
 }
+
+#[test]
+fn test_markdown_formatter_context_lines() {
+    use analyzer::output::OutputOptions;
+    use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+    use common::loc::Loc;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("Snippet.sol");
+    let source = (1..=9)
+        .map(|i| format!("line{i}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&file, source).unwrap();
+
+    let loc = Loc::new(5, 1, 5, 5).with_file(file.to_string_lossy().to_string());
+    let bug = Bug::new(
+        "Test",
+        Some("A finding on line 5."),
+        loc,
+        BugKind::Vulnerability,
+        BugCategory::Other,
+        RiskLevel::Medium,
+        vec![],
+        vec![],
+        None,
+    );
+    let report = AnalysisReport::new(vec![bug], vec![], Duration::from_millis(100));
+
+    let render = |context_lines| {
+        let options = OutputOptions { context_lines: Some(context_lines), ..Default::default() };
+        MarkdownFormatter::with_options(options).format(&report)
+    };
+
+    let narrow = render(1);
+    assert!(narrow.contains("line4") && narrow.contains("line6"));
+    assert!(!narrow.contains("line3"));
+
+    let wide = render(3);
+    assert!(wide.contains("line2") && wide.contains("line8"));
+
+    // Default options render no snippet, as before.
+    let default = MarkdownFormatter::new().format(&report);
+    assert!(!default.contains("line5"));
+}
+
+#[test]
+fn test_json_formatter_write_to_matches_format() {
+    use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+    use common::loc::Loc;
+
+    let bugs = (1..=3)
+        .map(|i| {
+            Bug::new(
+                "Reentrancy",
+                Some(&format!("Finding \"{i}\" with escapes\n")),
+                Loc::new(i, 1, i, 10).with_file("Bank.sol".to_string()),
+                BugKind::Vulnerability,
+                BugCategory::Reentrancy,
+                RiskLevel::High,
+                vec![841],
+                vec![107],
+                Some("Use a guard."),
+            )
+        })
+        .collect();
+    let report =
+        AnalysisReport::new(bugs, vec!["Bank.sol".to_string()], Duration::from_millis(42));
+
+    for pretty in [false, true] {
+        let formatter = JsonFormatter::new(pretty);
+        let mut streamed = Vec::new();
+        formatter.write_to(&report, &mut streamed).unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), formatter.format(&report));
+    }
+}
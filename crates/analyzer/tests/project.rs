@@ -0,0 +1,73 @@
+//! Tests for analyzing a directory as a project.
+
+use analyzer::{Config, analyze_path, analyze_sources};
+use common::source::InMemorySources;
+use std::path::Path;
+
+/// Analyze the example project and check that findings come from more than
+/// one file. Skipped at runtime when no Solidity compiler is available.
+#[test]
+fn test_analyze_project_directory() {
+    let project = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/solidity/project");
+    let report = analyze_path(&project, &Config::default());
+
+    if report.files_analyzed.is_empty() {
+        eprintln!("Skipping project test (Solidity compiler not available)");
+        return;
+    }
+
+    // Token.sol, Wallet.sol and the shared lib/Math.sol are all entry points.
+    assert_eq!(report.files_analyzed.len(), 3);
+
+    // Every compiled file records the solc version that compiled it.
+    for file in &report.files_analyzed {
+        let version = report.compiler_versions.get(file);
+        assert!(version.is_some_and(|v| v.starts_with("0.")), "no solc version for {file}");
+    }
+
+    let names: Vec<&str> = report.bugs.iter().map(|b| b.name.as_str()).collect();
+    assert!(names.contains(&"Unprotected Mint/Burn"), "missing Token.sol finding: {names:?}");
+    assert!(
+        names.contains(&"Dangerous use of tx.origin"),
+        "missing Wallet.sol finding: {names:?}"
+    );
+}
+
+/// Analyze sources that exist only in memory, including an import between
+/// them. Skipped at runtime when no Solidity compiler is available.
+#[test]
+fn test_analyze_in_memory_sources() {
+    let sources = InMemorySources::new()
+        .with_file(
+            "src/Wallet.sol",
+            "pragma solidity ^0.8.20;\n\
+             import \"./lib/Auth.sol\";\n\
+             contract Wallet is Auth {\n\
+             \x20   function withdraw(address payable to, uint256 amount) public {\n\
+             \x20       require(tx.origin == owner, \"Not owner\");\n\
+             \x20       to.transfer(amount);\n\
+             \x20   }\n\
+             }\n",
+        )
+        .with_file(
+            "src/lib/Auth.sol",
+            "pragma solidity ^0.8.20;\n\
+             contract Auth {\n\
+             \x20   address public owner;\n\
+             }\n",
+        );
+    assert!(!Path::new("src/Wallet.sol").exists());
+
+    let report = analyze_sources(&sources, &["src/Wallet.sol".to_string()], &Config::default());
+    if report.files_analyzed.is_empty() {
+        eprintln!("Skipping in-memory test (Solidity compiler not available)");
+        return;
+    }
+
+    let finding = report
+        .bugs
+        .iter()
+        .find(|b| b.name == "Dangerous use of tx.origin")
+        .expect("missing tx.origin finding");
+    assert_eq!(finding.loc.file.as_deref(), Some("src/Wallet.sol"));
+}
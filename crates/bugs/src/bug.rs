@@ -18,6 +18,49 @@ pub struct Bug {
     pub cwe_ids: Vec<usize>, // Related CWE: https://cwe.mitre.org/index.html
     pub swc_ids: Vec<usize>, // Related SWC: https://swcregistry.io/
     pub remediation: Option<String>,
+    /// Code-based position of the finding, filled in after detection. See
+    /// [`Bug::anchor`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_anchor: Option<SourceAnchor>,
+    /// Suggested fix, for detectors that can propose one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<FixSuggestion>,
+    /// Confidence of the detector that reported the finding, filled in
+    /// after detection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+}
+
+/// Position of a finding in terms of the code it points at rather than
+/// line and column offsets, so it does not change when code moves within a
+/// file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceAnchor {
+    /// Enclosing contract.
+    pub contract: String,
+    /// Signature of the enclosing function, e.g. `withdraw(uint256)`.
+    pub function: Option<String>,
+    /// Hash of the normalized statement the finding points at, or of the
+    /// declaration of the contract member it points at outside functions.
+    pub statement: Option<String>,
+}
+
+/// Suggested fix of a finding: a message for humans and, when the fix is a
+/// plain source edit, the edit itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixSuggestion {
+    /// What the fix does, e.g. "Pin the compiler version to 0.8.19.".
+    pub message: String,
+    /// Text replacing a source range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<TextReplacement>,
+}
+
+/// Replacement of the source text at `loc` by `text`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextReplacement {
+    pub loc: Loc,
+    pub text: String,
 }
 
 // FIXME: find a better name
@@ -28,8 +71,12 @@ pub enum BugKind {
     Vulnerability,
 }
 
+/// Severity of a finding. Serialized with the same names as
+/// [`RiskLevel::as_str`]; the legacy `"No"` spelling is still accepted when
+/// reading.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RiskLevel {
+    #[serde(rename = "Informational", alias = "No")]
     No,
     Low,
     Medium,
@@ -37,6 +84,17 @@ pub enum RiskLevel {
     Critical,
 }
 
+/// How likely a detector's findings are to be true positives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Confidence {
+    /// Possible issue, needs careful review.
+    Low,
+    /// Likely issue but may need manual review.
+    Medium,
+    /// Very likely to be a real issue.
+    High,
+}
+
 /// Classification of bugs by vulnerability category, aligned with the
 /// SmartBugs dataset categories.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -54,6 +112,12 @@ pub enum BugCategory {
     Other,
 }
 
+/// Shared severity taxonomy of scan detectors and analyzer passes.
+pub type Severity = RiskLevel;
+
+/// Shared category taxonomy of scan detectors and analyzer passes.
+pub type Category = BugCategory;
+
 //-------------------------------------------------------------------------
 // Implementation for BugCategory
 //-------------------------------------------------------------------------
@@ -152,6 +216,30 @@ impl Bug {
             swc_ids,
             cwe_ids,
             remediation: remediation.map(|s| s.to_string()),
+            source_anchor: None,
+            suggestion: None,
+            confidence: None,
+        }
+    }
+
+    /// Attach a suggested fix to this bug.
+    pub fn with_suggestion(mut self, suggestion: FixSuggestion) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+
+    /// Stable identifier of the finding's position, for fingerprints and
+    /// suppressions: `Contract::function(types)::statement-hash`, with the
+    /// parts that do not apply left out. Findings without a
+    /// [`SourceAnchor`] fall back to their raw location.
+    pub fn anchor(&self) -> String {
+        match &self.source_anchor {
+            Some(anchor) => std::iter::once(anchor.contract.as_str())
+                .chain(anchor.function.as_deref())
+                .chain(anchor.statement.as_deref())
+                .collect::<Vec<_>>()
+                .join("::"),
+            None => self.loc.to_string(),
         }
     }
 
@@ -236,6 +324,25 @@ impl Display for Bug {
     }
 }
 
+//-------------------------------------------------------------------------
+// Implementation for FixSuggestion
+//-------------------------------------------------------------------------
+
+impl FixSuggestion {
+    /// A suggestion described only in words.
+    pub fn new(message: &str) -> Self {
+        FixSuggestion { message: message.to_string(), replacement: None }
+    }
+
+    /// A suggestion replacing the source text at `loc` by `text`.
+    pub fn replace(message: &str, loc: Loc, text: &str) -> Self {
+        FixSuggestion {
+            message: message.to_string(),
+            replacement: Some(TextReplacement { loc, text: text.to_string() }),
+        }
+    }
+}
+
 //-------------------------------------------------------------------------
 // Implementation for BugKind
 //-------------------------------------------------------------------------
@@ -278,6 +385,26 @@ impl Display for RiskLevel {
     }
 }
 
+//-------------------------------------------------------------------------
+// Implementation for Confidence
+//-------------------------------------------------------------------------
+
+impl Confidence {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Confidence::Low => "Low",
+            Confidence::Medium => "Medium",
+            Confidence::High => "High",
+        }
+    }
+}
+
+impl Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 //-------------------------------------------------------------------------
 // Tests
 //-------------------------------------------------------------------------
@@ -372,6 +499,29 @@ mod tests {
         assert_eq!(parsed.risk_level, RiskLevel::High);
     }
 
+    #[test]
+    fn test_bug_anchor() {
+        let mut bug = Bug::new(
+            "Test Bug",
+            None,
+            Loc::new(3, 5, 3, 20).with_file("Bank.sol".to_string()),
+            BugKind::Vulnerability,
+            BugCategory::Reentrancy,
+            RiskLevel::High,
+            vec![],
+            vec![],
+            None,
+        );
+        assert_eq!(bug.anchor(), "Bank.sol:3:5");
+
+        bug.source_anchor = Some(SourceAnchor {
+            contract: "Bank".to_string(),
+            function: Some("withdraw(uint256)".to_string()),
+            statement: Some("0a1b2c3d".to_string()),
+        });
+        assert_eq!(bug.anchor(), "Bank::withdraw(uint256)::0a1b2c3d");
+    }
+
     #[test]
     fn test_risk_level_display() {
         assert_eq!(RiskLevel::No.as_str(), "Informational");
@@ -381,6 +531,22 @@ mod tests {
         assert_eq!(RiskLevel::Critical.as_str(), "Critical");
     }
 
+    #[test]
+    fn test_risk_level_serde_matches_display() {
+        for level in [
+            RiskLevel::No,
+            RiskLevel::Low,
+            RiskLevel::Medium,
+            RiskLevel::High,
+            RiskLevel::Critical,
+        ] {
+            let json = serde_json::to_string(&level).unwrap();
+            assert_eq!(json, format!("\"{}\"", level.as_str()));
+        }
+        let legacy: RiskLevel = serde_json::from_str("\"No\"").unwrap();
+        assert_eq!(legacy, RiskLevel::No);
+    }
+
     #[test]
     fn test_bug_kind_display() {
         assert_eq!(BugKind::Optimization.as_str(), "Optimization");
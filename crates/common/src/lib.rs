@@ -1,5 +1,6 @@
 pub mod error;
 pub mod loc;
 pub mod snippet;
+pub mod source;
 pub mod string;
 pub mod utils;
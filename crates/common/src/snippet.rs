@@ -1,3 +1,5 @@
+use crate::source::{FileSystem, SourceProvider};
+
 /// Extract a code snippet around the given range, with context lines and column
 /// markers.
 ///
@@ -15,7 +17,20 @@ pub fn extract_snippet(
     end_col: usize,
     context: usize,
 ) -> Option<String> {
-    let content = std::fs::read_to_string(file_path).ok()?;
+    extract_snippet_from(&FileSystem, file_path, start_line, end_line, start_col, end_col, context)
+}
+
+/// Like [`extract_snippet`], reading the file through `sources`.
+pub fn extract_snippet_from(
+    sources: &dyn SourceProvider,
+    file_path: &str,
+    start_line: usize,
+    end_line: usize,
+    start_col: usize,
+    end_col: usize,
+    context: usize,
+) -> Option<String> {
+    let content = sources.read(file_path).ok()?;
     let lines: Vec<&str> = content.lines().collect();
     if start_line == 0 || start_line > lines.len() {
         return None;
@@ -103,6 +118,14 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_extract_snippet_from_in_memory_sources() {
+        let sources = crate::source::InMemorySources::new()
+            .with_file("Foo.sol", "pragma solidity ^0.8.0;\ncontract Foo {\n}\n");
+        let snippet = extract_snippet_from(&sources, "Foo.sol", 2, 2, 0, 0, 0).unwrap();
+        assert_eq!(snippet, ">    2 | contract Foo {\n");
+    }
+
     #[test]
     fn test_extract_snippet_nonexistent_file() {
         assert!(extract_snippet("/tmp/nonexistent_verazt_test.sol", 1, 1, 0, 0, 1).is_none());
@@ -0,0 +1,101 @@
+//! Pluggable access to source files.
+//!
+//! Compilation, parsing and snippet extraction read sources through a
+//! [`SourceProvider`] rather than from disk directly, so editor buffers,
+//! tests and sandboxes can supply sources that were never saved.
+//! [`FileSystem`] is the default provider; [`InMemorySources`] keeps sources
+//! in a map keyed by path.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// A source of file contents, keyed by path.
+pub trait SourceProvider: Send + Sync {
+    /// Read the content of the file at `path`.
+    fn read(&self, path: &str) -> io::Result<String>;
+
+    /// Whether the file at `path` exists.
+    fn exists(&self, path: &str) -> bool {
+        self.read(path).is_ok()
+    }
+
+    /// Paths of all files when they are not on disk, so that they can be
+    /// written out for external tools such as Solc. `None` means the paths
+    /// are real files.
+    fn virtual_paths(&self) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// Reads sources from the local file system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystem;
+
+impl SourceProvider for FileSystem {
+    fn read(&self, path: &str) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists()
+    }
+}
+
+/// Sources held in memory. Paths should be relative, e.g.
+/// `contracts/Token.sol`, and imports between sources use the same paths.
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySources {
+    files: BTreeMap<String, String>,
+}
+
+impl InMemorySources {
+    /// Create an empty set of sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) the file at `path`.
+    pub fn insert(&mut self, path: impl Into<String>, content: impl Into<String>) {
+        self.files.insert(path.into(), content.into());
+    }
+
+    /// Builder-style [`insert`](Self::insert).
+    pub fn with_file(mut self, path: impl Into<String>, content: impl Into<String>) -> Self {
+        self.insert(path, content);
+        self
+    }
+}
+
+impl SourceProvider for InMemorySources {
+    fn read(&self, path: &str) -> io::Result<String> {
+        self.files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{path} not found")))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+    }
+
+    fn virtual_paths(&self) -> Option<Vec<String>> {
+        Some(self.files.keys().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_sources() {
+        let sources = InMemorySources::new().with_file("Token.sol", "contract Token {}");
+        assert_eq!(sources.read("Token.sol").unwrap(), "contract Token {}");
+        assert!(sources.exists("Token.sol"));
+        assert!(!sources.exists("Missing.sol"));
+        assert_eq!(sources.read("Missing.sol").unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(sources.virtual_paths(), Some(vec!["Token.sol".to_string()]));
+        assert_eq!(FileSystem.virtual_paths(), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Module finding the enclosing definitions and statements of AST nodes.
+//!
+//! [`NodeIndex`] walks a list of source units once and remembers, for every
+//! expression, statement and block, where in the tree it occurs. Queries then
+//! take time proportional to the nesting depth of the node. Nodes are
+//! identified by address, so the index must be built from the same source
+//! units the node was taken from, e.g. the output of the normalization
+//! passes when those have run.
+
+use crate::solidity::ast::utils::visit::{self, Visit};
+use crate::solidity::ast::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Enclosing nodes of an AST node.
+#[derive(Debug, Clone)]
+pub struct NodeContext<'a> {
+    pub source_unit: &'a SourceUnit,
+    pub contract: Option<&'a ContractDef>,
+    pub function: Option<&'a FuncDef>,
+    /// Enclosing statements, innermost first. A statement is not part of
+    /// its own context.
+    pub stmts: Vec<&'a Stmt>,
+}
+
+impl<'a> NodeContext<'a> {
+    /// The innermost statement containing the node.
+    pub fn enclosing_stmt(&self) -> Option<&'a Stmt> {
+        self.stmts.first().copied()
+    }
+}
+
+/// A scope of the tree: a source unit, contract or function, or a statement
+/// nested in one.
+#[derive(Debug, Clone, Copy)]
+struct Frame<'a> {
+    source_unit: &'a SourceUnit,
+    contract: Option<&'a ContractDef>,
+    function: Option<&'a FuncDef>,
+    stmt: Option<&'a Stmt>,
+    /// Enclosing frame of a statement frame.
+    parent: Option<usize>,
+}
+
+/// Index answering [`NodeIndex::node_context`] queries for the nodes of a
+/// list of source units.
+#[derive(Debug, Default)]
+pub struct NodeIndex<'a> {
+    frames: Vec<Frame<'a>>,
+    /// Frame directly enclosing each node, keyed by address and type.
+    nodes: HashMap<(usize, TypeId), usize>,
+}
+
+impl<'a> NodeIndex<'a> {
+    /// Index all expressions, statements and blocks of `source_units`.
+    pub fn new(source_units: &'a [SourceUnit]) -> Self {
+        let mut builder = IndexBuilder { index: NodeIndex::default(), current: None };
+        builder.visit_source_units(source_units);
+        builder.index
+    }
+
+    /// The context of `node`, an [`Expr`], [`Stmt`] or [`Block`] of the
+    /// indexed source units, or `None` for any other node.
+    pub fn node_context<T: Any>(&self, node: &T) -> Option<NodeContext<'a>> {
+        let frame = *self.nodes.get(&key(node))?;
+        let Frame { source_unit, contract, function, .. } = self.frames[frame];
+        let mut stmts = vec![];
+        let mut next = Some(frame);
+        while let Some(i) = next {
+            stmts.extend(self.frames[i].stmt);
+            next = self.frames[i].parent;
+        }
+        Some(NodeContext { source_unit, contract, function, stmts })
+    }
+}
+
+fn key<T: Any>(node: &T) -> (usize, TypeId) {
+    (node as *const T as usize, TypeId::of::<T>())
+}
+
+struct IndexBuilder<'a> {
+    index: NodeIndex<'a>,
+    current: Option<usize>,
+}
+
+impl<'a> IndexBuilder<'a> {
+    /// Run `f` inside a new frame derived from the current one by `update`.
+    fn enter(&mut self, update: impl FnOnce(&mut Frame<'a>), f: impl FnOnce(&mut Self)) {
+        let Some(current) = self.current else {
+            return;
+        };
+        let mut frame = self.index.frames[current];
+        update(&mut frame);
+        self.index.frames.push(frame);
+        self.current = Some(self.index.frames.len() - 1);
+        f(self);
+        self.current = Some(current);
+    }
+
+    fn record<T: Any>(&mut self, node: &T) {
+        if let Some(current) = self.current {
+            self.index.nodes.entry(key(node)).or_insert(current);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for IndexBuilder<'a> {
+    fn visit_source_unit(&mut self, source_unit: &'a SourceUnit) {
+        let frame =
+            Frame { source_unit, contract: None, function: None, stmt: None, parent: None };
+        self.index.frames.push(frame);
+        self.current = Some(self.index.frames.len() - 1);
+        visit::default::visit_source_unit(self, source_unit);
+        self.current = None;
+    }
+
+    fn visit_contract_def(&mut self, contract: &'a ContractDef) {
+        self.enter(
+            |frame| {
+                frame.contract = Some(contract);
+                frame.stmt = None;
+                frame.parent = None;
+            },
+            |this| visit::default::visit_contract_def(this, contract),
+        );
+    }
+
+    fn visit_func_def(&mut self, func: &'a FuncDef) {
+        self.enter(
+            |frame| {
+                frame.function = Some(func);
+                frame.stmt = None;
+                frame.parent = None;
+            },
+            |this| visit::default::visit_func_def(this, func),
+        );
+    }
+
+    fn visit_block(&mut self, block: &'a Block) {
+        self.record(block);
+        visit::default::visit_block(self, block);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        self.record(stmt);
+        let parent = self.current;
+        self.enter(
+            |frame| {
+                frame.stmt = Some(stmt);
+                frame.parent = parent;
+            },
+            |this| visit::default::visit_stmt(this, stmt),
+        );
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        self.record(expr);
+        visit::default::visit_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(name: &str) -> Expr {
+        Expr::Ident(Identifier::new(None, Name::from(name), Type::Bool, None))
+    }
+
+    /// `contract C { function f() public { if (a) { a && b; } } }`
+    fn source_unit() -> SourceUnit {
+        let and = BinaryExpr::new(None, BinOp::And, ident("a"), ident("b"), Type::Bool, None);
+        let inner = Stmt::Expr(ExprStmt::new(None, Expr::Binary(and), None));
+        let if_stmt = Stmt::If(IfStmt::new(
+            None,
+            ident("a"),
+            Stmt::Block(Block::new(None, vec![inner], false, None)),
+            None,
+            None,
+        ));
+        let func = FuncDef::new(
+            None,
+            None,
+            Name::from("f"),
+            FuncKind::ContractFunc,
+            Some(Block::new(None, vec![if_stmt], false, None)),
+            false,
+            FuncVis::Public,
+            FuncMut::NonPayable,
+            vec![],
+            vec![],
+            Overriding::None,
+            vec![],
+            None,
+            None,
+        );
+        let contract = ContractDef::new(
+            None,
+            None,
+            Name::from("C"),
+            ContractKind::Contract,
+            false,
+            vec![],
+            vec![ContractElem::Func(func)],
+            None,
+        );
+        SourceUnit::new(None, "C.sol".to_string(), vec![SourceUnitElem::Contract(contract)])
+    }
+
+    #[test]
+    fn test_node_context_of_nested_expr() {
+        let units = vec![source_unit()];
+        let index = NodeIndex::new(&units);
+
+        let SourceUnitElem::Contract(contract) = &units[0].elems[0] else {
+            unreachable!()
+        };
+        let ContractElem::Func(func) = &contract.body[0] else {
+            unreachable!()
+        };
+        let if_stmt = &func.body.as_ref().unwrap().body[0];
+        let Stmt::If(IfStmt { true_branch, .. }) = if_stmt else {
+            unreachable!()
+        };
+        let Stmt::Block(block) = true_branch.as_ref() else {
+            unreachable!()
+        };
+        let Stmt::Expr(ExprStmt { expr: Expr::Binary(and), .. }) = &block.body[0] else {
+            unreachable!()
+        };
+
+        let context = index.node_context(and.left.as_ref()).unwrap();
+        assert_eq!(context.source_unit.path, "C.sol");
+        assert_eq!(context.contract.unwrap().name.base, "C");
+        assert_eq!(context.function.unwrap().name.base, "f");
+        assert_eq!(context.stmts.len(), 3);
+        assert!(std::ptr::eq(context.enclosing_stmt().unwrap(), &block.body[0]));
+        assert!(std::ptr::eq(context.stmts[2], if_stmt));
+
+        assert!(index.node_context(&ident("a")).is_none());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod compare;
+pub mod context;
 pub mod export;
 pub mod fold;
 pub mod map;
@@ -299,8 +299,10 @@ pub fn eliminate_import(source_units: &[SourceUnit]) -> Vec<SourceUnit> {
                 let source_unit_path = Path::new(&sunit.path);
                 let imported_path = import.get_import_path();
 
-                // Compute the full path of the imported source unit
+                // Compute the full path of the imported source unit. Remapped
+                // imports already hold the path of the imported source unit.
                 let imported_full_path = match source_unit_path.parent() {
+                    _ if source_unit_map.contains_key(&imported_path) => imported_path.clone(),
                     Some(parent_path) => parent_path
                         .join(&imported_path)
                         .to_str()
@@ -0,0 +1,574 @@
+//! Pass to inline small `internal` and `private` helper functions, so that
+//! later analyses see their bodies in place of a call.
+//!
+//! A helper is a non-virtual contract function without modifiers or inline
+//! assembly that is not (mutually) recursive, has at most `max_stmts`
+//! statements, never assigns to its parameters, and ends with its only
+//! `return`, of a single value. Its parameters are substituted with the
+//! arguments of the call, so a call is only inlined when every argument is
+//! a literal or a side-effect-free path (`x`, `s.f`, `a[i]`), and a storage
+//! reference parameter keeps aliasing the storage passed to it.
+//!
+//! A helper whose body is a single `return e;` is inlined wherever it is
+//! called by name. A helper with statements before its `return` is inlined
+//! only at statement level (`f(a);`, `x = f(a);`, `T x = f(a);` and
+//! `return f(a);`), and only when its arguments are literals or locals of
+//! the caller, so that its statements cannot change them. Calls inside
+//! `unchecked` blocks are not inlined, and helper definitions are kept.
+
+use super::substitution;
+use crate::solidity::ast::utils::{Map, Visit, map, visit};
+use crate::solidity::ast::*;
+use std::collections::{HashMap, HashSet};
+
+/// Default statement-count threshold of [`inline_internal_helpers`].
+pub const DEFAULT_MAX_INLINE_STMTS: usize = 5;
+
+//-------------------------------------------------
+// Finding helpers
+//-------------------------------------------------
+
+/// A helper function that can be inlined.
+#[derive(Clone)]
+struct Helper {
+    params: Vec<Identifier>,
+    param_decls: Vec<VarDecl>,
+    /// Statements before the `return`.
+    prefix: Vec<Stmt>,
+    /// The returned expression.
+    result: Expr,
+    /// Names declared in the body.
+    locals: HashSet<Name>,
+    /// Names read by the body, other than parameters and locals.
+    free_names: HashSet<Name>,
+}
+
+/// Facts about a function body needed to decide whether it is a helper.
+#[derive(Default)]
+struct BodyInfo {
+    stmts: usize,
+    returns: usize,
+    has_asm: bool,
+    declared: HashSet<Name>,
+    read: HashSet<Name>,
+    /// Roots of assigned, incremented, decremented or deleted paths.
+    written: HashSet<Name>,
+    callees: HashSet<Name>,
+}
+
+/// Name of the variable at the root of a path such as `s.f[i]`.
+fn root_name(expr: &Expr) -> Option<&Name> {
+    match expr {
+        Expr::Ident(ident) => Some(&ident.name),
+        Expr::Member(e) => root_name(&e.base),
+        Expr::Index(e) => root_name(&e.base_expr),
+        _ => None,
+    }
+}
+
+impl BodyInfo {
+    fn of(block: &Block) -> Self {
+        let mut info = BodyInfo::default();
+        info.visit_block(block);
+        info
+    }
+
+    fn write(&mut self, target: &Expr) {
+        match target {
+            Expr::Tuple(tuple) => tuple.elems.iter().flatten().for_each(|e| self.write(e)),
+            _ => self.written.extend(root_name(target).cloned()),
+        }
+    }
+}
+
+impl<'a> Visit<'a> for BodyInfo {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Block(_) => {}
+            Stmt::Asm(_) => self.has_asm = true,
+            Stmt::Return(_) => self.returns += 1,
+            _ => {}
+        }
+        if !matches!(stmt, Stmt::Block(_)) {
+            self.stmts += 1;
+        }
+        visit::default::visit_stmt(self, stmt)
+    }
+
+    fn visit_var_decl(&mut self, vdecl: &'a VarDecl) {
+        self.declared.insert(vdecl.name.clone());
+        visit::default::visit_var_decl(self, vdecl)
+    }
+
+    fn visit_ident(&mut self, ident: &'a Identifier) {
+        self.read.insert(ident.name.clone());
+    }
+
+    fn visit_assign_expr(&mut self, expr: &'a AssignExpr) {
+        self.write(&expr.left);
+        visit::default::visit_assign_expr(self, expr)
+    }
+
+    fn visit_unary_expr(&mut self, expr: &'a UnaryExpr) {
+        if matches!(
+            expr.op,
+            UnaryOp::PreIncr
+                | UnaryOp::PostIncr
+                | UnaryOp::PreDecr
+                | UnaryOp::PostDecr
+                | UnaryOp::Delete
+        ) {
+            self.write(&expr.body);
+        }
+        visit::default::visit_unary_expr(self, expr)
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::Ident(callee) = &*expr.callee {
+            self.callees.insert(callee.name.clone());
+        }
+        visit::default::visit_call_expr(self, expr)
+    }
+}
+
+/// The helper of `func` if it can be inlined, with the helpers it calls.
+fn as_helper(func: &FuncDef, max_stmts: usize) -> Option<(Helper, HashSet<Name>)> {
+    let is_internal = matches!(func.visibility, FuncVis::Internal | FuncVis::Private);
+    if func.kind != FuncKind::ContractFunc
+        || !is_internal
+        || func.is_virtual
+        || !func.modifier_invocs.is_empty()
+        || func.returns.len() != 1
+    {
+        return None;
+    }
+    let body = func.body.as_ref()?;
+    let (Stmt::Return(ReturnStmt { expr: Some(result), .. }), prefix) = body.body.split_last()?
+    else {
+        return None;
+    };
+
+    let info = BodyInfo::of(body);
+    let params: HashSet<&Name> = func.params.iter().map(|p| &p.name).collect();
+    if info.returns != 1
+        || info.has_asm
+        || info.stmts > max_stmts
+        || info.written.iter().any(|name| params.contains(name))
+        || info.read.contains(&func.returns[0].name)
+    {
+        return None;
+    }
+
+    let free_names = info
+        .read
+        .iter()
+        .filter(|name| !params.contains(name) && !info.declared.contains(*name))
+        .cloned()
+        .collect();
+    let helper = Helper {
+        params: func
+            .params
+            .iter()
+            .map(|p| Identifier::new(p.id, p.name.clone(), p.typ.clone(), p.loc.clone()))
+            .collect(),
+        param_decls: func.params.clone(),
+        prefix: prefix.to_vec(),
+        result: result.clone(),
+        locals: info.declared,
+        free_names,
+    };
+    Some((helper, info.callees))
+}
+
+/// Helpers of `contract` that can be inlined, keyed by name.
+fn find_helpers(contract: &ContractDef, max_stmts: usize) -> HashMap<Name, Helper> {
+    let mut counts: HashMap<&Name, usize> = HashMap::new();
+    for elem in &contract.body {
+        if let ContractElem::Func(func) = elem {
+            *counts.entry(&func.name).or_default() += 1;
+        }
+    }
+
+    let mut helpers = HashMap::new();
+    let mut callees = HashMap::new();
+    for elem in &contract.body {
+        if let ContractElem::Func(func) = elem
+            && counts[&func.name] == 1
+            && let Some((helper, calls)) = as_helper(func, max_stmts)
+        {
+            helpers.insert(func.name.clone(), helper);
+            callees.insert(func.name.clone(), calls);
+        }
+    }
+
+    // Drop the helpers that can reach themselves through other helpers.
+    let recursive: Vec<Name> = helpers
+        .keys()
+        .filter(|name| {
+            let mut stack: Vec<&Name> = callees[*name].iter().collect();
+            let mut seen = HashSet::new();
+            while let Some(callee) = stack.pop() {
+                if callee == *name {
+                    return true;
+                }
+                if seen.insert(callee)
+                    && let Some(next) = callees.get(callee)
+                {
+                    stack.extend(next);
+                }
+            }
+            false
+        })
+        .cloned()
+        .collect();
+    for name in recursive {
+        helpers.remove(&name);
+    }
+    helpers
+}
+
+//-------------------------------------------------
+// Inlining calls
+//-------------------------------------------------
+
+/// Whether `expr` is a literal or a side-effect-free path such as `x`,
+/// `s.f` or `a[i]`.
+fn is_path(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) | Expr::Ident(_) => true,
+        Expr::Member(e) => is_path(&e.base),
+        Expr::Index(e) => is_path(&e.base_expr) && e.index.as_deref().is_some_and(is_path),
+        _ => false,
+    }
+}
+
+/// Names read by `expr`.
+fn read_names(expr: &Expr) -> HashSet<Name> {
+    let mut info = BodyInfo::default();
+    info.visit_expr(expr);
+    info.read
+}
+
+/// `typ(expr)`.
+fn convert(typ: &Type, expr: &Expr) -> Expr {
+    let callee = Expr::TypeName(TypeNameExpr { id: None, typ: typ.clone(), loc: None });
+    let call = CallExpr::new_call_unnamed_args(
+        None,
+        callee,
+        vec![],
+        vec![expr.clone()],
+        CallKind::TypeConversionCall,
+        typ.clone(),
+        expr.loc(),
+    );
+    Expr::Call(call)
+}
+
+struct HelperInliner {
+    max_stmts: usize,
+    helpers: HashMap<Name, Helper>,
+    /// Names declared in the function being rewritten.
+    scope: HashSet<Name>,
+    /// Whether the current block is `unchecked`.
+    unchecked: bool,
+}
+
+impl HelperInliner {
+    fn new(max_stmts: usize) -> Self {
+        HelperInliner {
+            max_stmts,
+            helpers: HashMap::new(),
+            scope: HashSet::new(),
+            unchecked: false,
+        }
+    }
+
+    /// The helper called by `call` and the arguments to substitute for its
+    /// parameters, if the call can be inlined. `captured` are further names
+    /// of the call site that the helper's locals must not shadow.
+    fn bind(&self, call: &CallExpr, captured: &[&Name]) -> Option<(Helper, Vec<Expr>)> {
+        let Expr::Ident(callee) = &*call.callee else {
+            return None;
+        };
+        let helper = self.helpers.get(&callee.name)?;
+        let CallArgs::Unnamed(args) = &call.args else {
+            return None;
+        };
+        if self.unchecked
+            || call.kind != CallKind::FuncCall
+            || !call.call_opts.is_empty()
+            || self.scope.contains(&callee.name)
+            || args.len() != helper.params.len()
+            || helper
+                .free_names
+                .iter()
+                .any(|name| self.scope.contains(name))
+            || helper.locals.iter().any(|name| self.scope.contains(name))
+            || captured.iter().any(|name| helper.locals.contains(*name))
+        {
+            return None;
+        }
+
+        let mut bound = vec![];
+        for (param, arg) in helper.param_decls.iter().zip(args) {
+            let is_caller_local = match arg {
+                Expr::Lit(_) => true,
+                Expr::Ident(ident) => self.scope.contains(&ident.name),
+                _ => false,
+            };
+            if !is_path(arg)
+                || (!helper.prefix.is_empty() && !is_caller_local)
+                || read_names(arg)
+                    .iter()
+                    .any(|name| helper.locals.contains(name))
+            {
+                return None;
+            }
+            let arg = match param.typ.data_loc() {
+                // Storage references alias the path passed to them.
+                DataLoc::Storage if !matches!(arg, Expr::Lit(_)) => arg.clone(),
+                DataLoc::Storage => return None,
+                DataLoc::None if arg.typ() == param.typ => arg.clone(),
+                DataLoc::None if !param.typ.is_func_type() => convert(&param.typ, arg),
+                DataLoc::None => return None,
+                // Memory and calldata arguments are passed by reference.
+                loc if arg.typ().data_loc() == loc => arg.clone(),
+                _ => return None,
+            };
+            bound.push(arg);
+        }
+        Some((helper.clone(), bound))
+    }
+
+    /// The statements and the result of `call` inlined, for a helper with
+    /// statements before its `return`. Statements `spliced` into the
+    /// caller's block, rather than wrapped in a block of their own, must
+    /// not declare locals.
+    fn inline_prefixed(
+        &mut self,
+        call: &Expr,
+        captured: &[&Name],
+        spliced: bool,
+    ) -> Option<(Vec<Stmt>, Expr)> {
+        let Expr::Call(call) = call else {
+            return None;
+        };
+        let (helper, args) = self.bind(call, captured)?;
+        if helper.prefix.is_empty() || (spliced && !helper.locals.is_empty()) {
+            return None;
+        }
+
+        self.scope.extend(helper.locals.iter().cloned());
+        let stmts = helper
+            .prefix
+            .iter()
+            .map(|stmt| {
+                let stmt =
+                    substitution::substitute_stmt(helper.params.clone(), args.clone(), stmt);
+                self.map_stmt(&stmt)
+            })
+            .collect();
+        let result = substitution::substitute_expr(helper.params, args, &helper.result);
+        let result = self.map_expr(&result);
+        for name in &helper.locals {
+            self.scope.remove(name);
+        }
+        Some((stmts, result))
+    }
+}
+
+/// Names declared by `func`: its parameters, returns and locals.
+fn declared_names(func: &FuncDef) -> HashSet<Name> {
+    let mut info = BodyInfo::default();
+    info.visit_func_def(func);
+    info.declared
+}
+
+impl Map<'_> for HelperInliner {
+    fn map_contract_def(&mut self, contract: &ContractDef) -> ContractDef {
+        self.helpers = find_helpers(contract, self.max_stmts);
+        let contract = map::default::map_contract_def(self, contract);
+        self.helpers.clear();
+        contract
+    }
+
+    fn map_func_def(&mut self, func: &FuncDef) -> FuncDef {
+        self.scope = declared_names(func);
+        let func = map::default::map_func_def(self, func);
+        self.scope.clear();
+        func
+    }
+
+    /// Override `map_block` to splice `T x = f(a);` into the block.
+    fn map_block(&mut self, block: &Block) -> Block {
+        let unchecked = self.unchecked;
+        self.unchecked |= block.unchecked;
+        let mut nstmts = vec![];
+        for stmt in &block.body {
+            if let Stmt::VarDecl(decl) = stmt
+                && let [Some(var)] = decl.var_decls.as_slice()
+                && let Some(value) = &decl.value
+                && let Some((stmts, result)) = self.inline_prefixed(value, &[&var.name], true)
+            {
+                nstmts.extend(stmts);
+                let var_decls = decl.var_decls.clone();
+                nstmts.push(Stmt::VarDecl(VarDeclStmt {
+                    var_decls,
+                    value: Some(result),
+                    ..decl.clone()
+                }));
+                continue;
+            }
+            nstmts.push(self.map_stmt(stmt));
+        }
+        self.unchecked = unchecked;
+        Block { body: nstmts, ..block.clone() }
+    }
+
+    /// Override `map_stmt` to inline helpers called at statement level.
+    fn map_stmt(&mut self, stmt: &Stmt) -> Stmt {
+        let inlined = match stmt {
+            Stmt::Expr(s) => match &s.expr {
+                Expr::Assign(assign) if assign.operator == AssignOp::Assign => {
+                    let Expr::Ident(lhs) = &*assign.left else {
+                        return map::default::map_stmt(self, stmt);
+                    };
+                    self.inline_prefixed(&assign.right, &[&lhs.name], false)
+                        .map(|(mut stmts, result)| {
+                            let right = Box::new(result);
+                            let expr = Expr::Assign(AssignExpr { right, ..assign.clone() });
+                            stmts.push(Stmt::Expr(ExprStmt { expr, ..s.clone() }));
+                            (stmts, s.loc.clone())
+                        })
+                }
+                expr => self
+                    .inline_prefixed(expr, &[], false)
+                    .map(|(mut stmts, result)| {
+                        stmts.push(Stmt::Expr(ExprStmt { expr: result, ..s.clone() }));
+                        (stmts, s.loc.clone())
+                    }),
+            },
+            Stmt::Return(s) => s.expr.as_ref().and_then(|expr| {
+                self.inline_prefixed(expr, &[], false)
+                    .map(|(mut stmts, result)| {
+                        stmts.push(Stmt::Return(ReturnStmt { expr: Some(result), ..s.clone() }));
+                        (stmts, s.loc.clone())
+                    })
+            }),
+            _ => None,
+        };
+        match inlined {
+            Some((stmts, loc)) => Stmt::Block(Block::new(None, stmts, false, loc)),
+            None => map::default::map_stmt(self, stmt),
+        }
+    }
+
+    /// Override `map_expr` to inline helpers that only return a value.
+    fn map_expr(&mut self, expr: &Expr) -> Expr {
+        if let Expr::Call(call) = expr
+            && let Some((helper, args)) = self.bind(call, &[])
+            && helper.prefix.is_empty()
+        {
+            let result = substitution::substitute_expr(helper.params, args, &helper.result);
+            return self.map_expr(&result);
+        }
+        map::default::map_expr(self, expr)
+    }
+}
+
+/// Inline calls to small `internal` and `private` helpers with at most
+/// `max_stmts` statements in the given source units.
+pub fn inline_internal_helpers(source_units: &[SourceUnit], max_stmts: usize) -> Vec<SourceUnit> {
+    let mut inliner = HelperInliner::new(max_stmts);
+    inliner.map_source_units(source_units)
+}
+
+//-------------------------------------------------
+// Unit tests
+//-------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_MAX_INLINE_STMTS, inline_internal_helpers};
+    use crate::solidity::{
+        ast::utils::syntactic_comparer::compare_source_units,
+        lowering::utils::configure_unit_test_env, parsing::parse_solidity_source_code,
+    };
+    use indoc::indoc;
+
+    #[test]
+    fn inline_helpers() {
+        let _ = configure_unit_test_env();
+
+        // Input contract
+        let input_contract = indoc! {r###"
+            contract Vault {
+                uint256 public total;
+                mapping(address => uint256) balances;
+
+                function _fee(uint256 amount) internal pure returns (uint256) {
+                    return amount / 100;
+                }
+
+                function _credit(address to, uint256 amount) private returns (uint256) {
+                    require(to != address(0));
+                    balances[to] += amount;
+                    return balances[to];
+                }
+
+                function _sum(uint256 n) internal pure returns (uint256) {
+                    return n == 0 ? 0 : n + _sum(n - 1);
+                }
+
+                function deposit(address to, uint256 amount) public {
+                    uint256 net = amount - _fee(amount);
+                    uint256 balance = _credit(to, net);
+                    total += balance + _sum(amount);
+                }
+            }"###};
+
+        // Expected output contract: `_sum` is recursive and stays a call.
+        let expected_contract = indoc! {r###"
+            contract Vault {
+                uint256 public total;
+                mapping(address => uint256) balances;
+
+                function _fee(uint256 amount) internal pure returns (uint256) {
+                    return amount / 100;
+                }
+
+                function _credit(address to, uint256 amount) private returns (uint256) {
+                    require(to != address(0));
+                    balances[to] += amount;
+                    return balances[to];
+                }
+
+                function _sum(uint256 n) internal pure returns (uint256) {
+                    return n == 0 ? 0 : n + _sum(n - 1);
+                }
+
+                function deposit(address to, uint256 amount) public {
+                    uint256 net = amount - amount / 100;
+                    require(to != address(0));
+                    balances[to] += net;
+                    uint256 balance = balances[to];
+                    total += balance + _sum(amount);
+                }
+            }"###};
+
+        let input_sunits = match parse_solidity_source_code(input_contract, "0.8.19") {
+            Ok(sunits) => sunits,
+            Err(err) => panic!("Failed to parse input source unit: {}", err),
+        };
+        let output_sunits = inline_internal_helpers(&input_sunits, DEFAULT_MAX_INLINE_STMTS);
+
+        let expected_sunits = match parse_solidity_source_code(expected_contract, "0.8.19") {
+            Ok(sunits) => sunits,
+            Err(err) => panic!("Failed to parse expected source unit: {}", err),
+        };
+
+        if let Err(err) = compare_source_units(&output_sunits, &expected_sunits) {
+            panic!("Failed to inline helpers: {}", err)
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::solidity::ast;
 use crate::solidity::ast::Loc;
+use crate::solidity::ast::utils::visit::{self, Visit};
 use common::{error::Result, fail};
 use log::trace;
 use scirs::sir::attrs::{Attr, AttrValue, sir_attrs};
@@ -29,8 +30,25 @@ fn print_output_source_units(source_units: &[ast::SourceUnit]) {
     }
 }
 
+/// Options of the optional normalization passes.
+#[derive(Debug, Clone, Default)]
+pub struct PassOptions {
+    /// Inline `internal`/`private` helpers with at most this many statements
+    /// (the `inline_internal_helpers` pass). Disabled when `None`.
+    pub inline_helpers: Option<usize>,
+}
+
 /// Run all normalization passes on source units.
 pub fn run_passes(source_units: &[ast::SourceUnit]) -> Vec<ast::SourceUnit> {
+    run_passes_with_options(source_units, &PassOptions::default())
+}
+
+/// Run all normalization passes on source units, including the optional
+/// passes enabled in `options`.
+pub fn run_passes_with_options(
+    source_units: &[ast::SourceUnit],
+    options: &PassOptions,
+) -> Vec<ast::SourceUnit> {
     let source_units = super::unroll_tuples::unroll_unary_tuple(source_units);
     print_output_source_units(&source_units);
 
@@ -53,6 +71,16 @@ pub fn run_passes(source_units: &[ast::SourceUnit]) -> Vec<ast::SourceUnit> {
     let source_units = super::strip_specifiers::strip_specifiers(&source_units);
     print_output_source_units(&source_units);
 
+    let source_units = match options.inline_helpers {
+        Some(max_stmts) => {
+            let source_units =
+                super::inline_internal_helpers::inline_internal_helpers(&source_units, max_stmts);
+            print_output_source_units(&source_units);
+            source_units
+        }
+        None => source_units,
+    };
+
     super::unroll_tuples::unroll_unary_tuple(&source_units)
 }
 
@@ -65,6 +93,28 @@ pub struct Lowerer {
     tmp_var_index: usize,
 }
 
+/// Collects deprecated syntax that lowering erases: `throw`, which becomes
+/// a `revert()`, and the `years` unit, which is folded into the literal.
+#[derive(Default)]
+struct DeprecatedSyntaxFinder {
+    found: Vec<(&'static str, Option<Loc>)>,
+}
+
+impl<'a> Visit<'a> for DeprecatedSyntaxFinder {
+    fn visit_throw_stmt(&mut self, stmt: &'a ast::ThrowStmt) {
+        self.found.push(("throw", stmt.loc.clone()));
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        if let ast::Expr::Lit(ast::Lit::Num(lit)) = expr
+            && lit.unit == Some(ast::NumUnit::Years)
+        {
+            self.found.push(("years", lit.loc.clone()));
+        }
+        visit::default::visit_expr(self, expr);
+    }
+}
+
 impl Lowerer {
     pub fn new() -> Self {
         Lowerer { tmp_var_index: 0 }
@@ -315,6 +365,11 @@ impl Lowerer {
         let mut decl =
             FunctionDecl::new(f.name.to_string(), params, returns, body, loc_to_span(&f.loc));
         decl.modifier_invocs = modifier_invocs;
+        decl.return_names = f
+            .returns
+            .iter()
+            .map(|r| (!r.name.base.is_empty()).then(|| r.name.to_string()))
+            .collect();
 
         // Propagate visibility attribute to SIR.
         let vis_str = match &f.visibility {
@@ -342,6 +397,53 @@ impl Lowerer {
                 .push(Attr::sir(sir_attrs::MUTABILITY, AttrValue::String(m.to_string())));
         }
 
+        // Propagate the override specifier, with the named base contracts.
+        let overrides = match &f.overriding {
+            ast::Overriding::None => None,
+            ast::Overriding::All => Some(String::new()),
+            ast::Overriding::Some(bases) => Some(
+                bases
+                    .iter()
+                    .map(|b| b.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        };
+        if let Some(bases) = overrides {
+            decl.attrs
+                .push(Attr::evm(evm_attrs::OVERRIDES, AttrValue::String(bases)));
+        }
+
+        if f.kind == ast::FuncKind::Constructor {
+            decl.attrs
+                .push(Attr::evm(evm_attrs::IS_CONSTRUCTOR, AttrValue::Bool(true)));
+        }
+
+        if f.is_virtual {
+            decl.attrs
+                .push(Attr::evm(evm_attrs::IS_VIRTUAL, AttrValue::Bool(true)));
+        }
+
+        if f.returns
+            .iter()
+            .any(|r| r.typ.data_loc().is_storage() || r.data_loc.is_some_and(|l| l.is_storage()))
+        {
+            decl.attrs
+                .push(Attr::evm(evm_attrs::RETURNS_STORAGE_REF, AttrValue::Bool(true)));
+        }
+
+        // Keep deprecated syntax visible to detectors after lowering.
+        if let Some(body) = &f.body {
+            let mut finder = DeprecatedSyntaxFinder::default();
+            finder.visit_block(body);
+            for (keyword, loc) in finder.found {
+                decl.attrs.push(
+                    Attr::evm(evm_attrs::DEPRECATED_SYNTAX, AttrValue::String(keyword.into()))
+                        .with_span(loc_to_span(&loc)),
+                );
+            }
+        }
+
         Ok(decl)
     }
 
@@ -1489,7 +1591,10 @@ impl Lowerer {
                     Some(EvmExpr::BlockNumber(EvmBlockNumber { loc: Default::default() }))
                 }
                 ("block", "difficulty") | ("block", "prevrandao") => {
-                    Some(EvmExpr::BlockDifficulty(EvmBlockDifficulty { loc: Default::default() }))
+                    Some(EvmExpr::BlockDifficulty(EvmBlockDifficulty {
+                        is_prevrandao: member == "prevrandao",
+                        loc: Default::default(),
+                    }))
                 }
                 ("block", "gaslimit") => {
                     Some(EvmExpr::BlockGaslimit(EvmBlockGaslimit { loc: Default::default() }))
@@ -11,6 +11,7 @@ pub mod eliminate_named_args;
 pub mod eliminate_using;
 pub mod flatten_expr;
 pub mod flatten_names;
+pub mod inline_internal_helpers;
 pub mod merge_pragmas;
 pub mod rename_callees;
 pub mod rename_contracts;
@@ -28,6 +29,7 @@ pub use eliminate_imports::eliminate_import;
 pub use eliminate_using::eliminate_using_directives;
 pub use flatten_expr::flatten_expr;
 pub use flatten_names::flatten_name;
+pub use inline_internal_helpers::inline_internal_helpers;
 pub use merge_pragmas::merge_pragmas;
 pub use rename_callees::rename_callees;
 pub use rename_contracts::rename_contracts;
@@ -39,13 +41,22 @@ use crate::solidity::ast::SourceUnit;
 use common::error::Result;
 use scirs::sir::Module;
 
-pub use lower::Lowerer;
+pub use lower::{Lowerer, PassOptions};
 
 /// Lower one or more Solidity source units into SIR modules.
 ///
 /// Internally runs all normalization passes before lowering.
 pub fn lower_source_units(source_units: &[SourceUnit]) -> Result<Vec<Module>> {
-    let normalized = lower::run_passes(source_units);
+    lower_source_units_with_options(source_units, &PassOptions::default())
+}
+
+/// Lower one or more Solidity source units into SIR modules, enabling the
+/// optional normalization passes selected by `options`.
+pub fn lower_source_units_with_options(
+    source_units: &[SourceUnit],
+    options: &PassOptions,
+) -> Result<Vec<Module>> {
+    let normalized = lower::run_passes_with_options(source_units, options);
     normalized
         .iter()
         .map(|su| lower::lower_source_unit(su))
@@ -47,6 +47,11 @@ pub fn substitute_stmt(params: Vec<Identifier>, args: Vec<Expr>, stmt: &Stmt) ->
     substitutor.map_stmt(stmt)
 }
 
+pub fn substitute_expr(params: Vec<Identifier>, args: Vec<Expr>, expr: &Expr) -> Expr {
+    let mut substitutor = IdentExprSubstitutor::new(params, args);
+    substitutor.map_expr(expr)
+}
+
 //-------------------------------------------------
 // Substitute name
 //-------------------------------------------------
@@ -6,6 +6,7 @@ use crate::solidity::parsing::yul_parser;
 use crate::solidity::{ast::*, parsing::type_parser::type_parser};
 use codespan_reporting::files::{Files, SimpleFiles};
 use color_eyre::eyre::Result;
+use common::source::{FileSystem, SourceProvider};
 use common::{error, fail};
 use itertools::izip;
 use lazy_static::lazy_static;
@@ -13,7 +14,7 @@ use num_bigint::BigInt;
 use regex::Regex;
 use rust_decimal::Decimal;
 use serde_json::Value;
-use std::{fs, ops::Deref, path::Path, str::FromStr};
+use std::{ops::Deref, path::Path, str::FromStr};
 
 //------------------------------------------------------------------
 // Static variables
@@ -29,7 +30,7 @@ lazy_static! {
 // Data structure representing JSON AST Parser
 //------------------------------------------------------------------
 
-pub struct AstParser {
+pub struct AstParser<'s> {
     pub solidity_json: Option<String>,
     pub input_file: Option<String>,
     pub base_path: Option<String>,
@@ -41,6 +42,10 @@ pub struct AstParser {
     default_sol_ver: Option<node_semver::Range>,
     /// Current Solidity version, overridden by pragma if found.
     current_sol_ver: Option<node_semver::Range>,
+    /// Where the content of each source unit is read from.
+    sources: &'s dyn SourceProvider,
+    /// Import remappings as `(prefix, target)` pairs.
+    remappings: Vec<(String, String)>,
 }
 
 pub struct JsonAst {
@@ -67,7 +72,7 @@ impl JsonAst {
 // Implementation for AST Parser
 //------------------------------------------------------------------
 
-impl AstParser {
+impl<'s> AstParser<'s> {
     pub fn new(solidity_json: &JsonAst, solc_ver: Option<&node_semver::Version>) -> Self {
         let default_sol_ver =
             solc_ver.and_then(|v| node_semver::Range::parse(&format!("{}", v)).ok());
@@ -80,9 +85,42 @@ impl AstParser {
             current_file_path: None,
             default_sol_ver,
             current_sol_ver: None,
+            sources: &FileSystem,
+            remappings: vec![],
         }
     }
 
+    /// Read source units through `sources` instead of the file system.
+    pub fn with_sources(mut self, sources: &'s dyn SourceProvider) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Resolve source unit and import paths through the import remappings
+    /// `remappings`, given in Solc's `[context:]prefix=target` form.
+    pub fn with_remappings(mut self, remappings: &[String]) -> Self {
+        self.remappings = remappings
+            .iter()
+            .filter_map(|r| r.split_once('='))
+            .map(|(prefix, target)| {
+                let prefix = prefix.split_once(':').map_or(prefix, |(_, prefix)| prefix);
+                (prefix.to_string(), target.to_string())
+            })
+            .collect();
+        self
+    }
+
+    /// Apply the remapping with the longest prefix of `path`, as Solc does.
+    /// Paths without a matching remapping are returned unchanged.
+    fn remap_path(&self, path: &str) -> String {
+        self.remappings
+            .iter()
+            .filter(|(prefix, _)| !prefix.is_empty() && path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, target)| format!("{target}{}", &path[prefix.len()..]))
+            .unwrap_or_else(|| path.to_string())
+    }
+
     pub fn parse_solidity_json(&mut self) -> Result<Vec<SourceUnit>> {
         let node: Value = match &self.solidity_json {
             Some(content) => serde_json::from_str(content)?,
@@ -113,6 +151,42 @@ impl AstParser {
         Ok(source_units)
     }
 
+    /// Parse the output of `solc --standard-json`, whose `sources` map
+    /// holds the `ast` of every source by path. Sources are parsed in the
+    /// order of their `id`, as Solc assigns them.
+    pub fn parse_standard_json(&mut self) -> Result<Vec<SourceUnit>> {
+        let node: Value = match &self.solidity_json {
+            Some(content) => serde_json::from_str(content)?,
+            None => fail!("Input JSON AST not found!"),
+        };
+        let errors = node.get("errors").and_then(|v| v.as_array());
+        for error in errors.into_iter().flatten() {
+            if error.get("severity").and_then(|v| v.as_str()) == Some("error") {
+                let msg = error
+                    .get("formattedMessage")
+                    .or_else(|| error.get("message"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                fail!("Compilation error in standard JSON output: {}", msg.trim());
+            }
+        }
+        let sources_node = node
+            .get("sources")
+            .ok_or_else(|| error!("Sources node not found in standard JSON: {node}"))?
+            .as_object()
+            .ok_or_else(|| error!("Sources node is not an object: {node}"))?;
+        let mut sources = sources_node.iter().collect::<Vec<_>>();
+        sources.sort_by_key(|(_, source_node)| source_node.get("id").and_then(|v| v.as_i64()));
+        let mut source_units = vec![];
+        for (source_name, source_node) in sources {
+            let ast_node = source_node
+                .get("ast")
+                .ok_or_else(|| error!("AST node not found for source: {}", source_name))?;
+            source_units.push(self.parse_ast(ast_node)?)
+        }
+        Ok(source_units)
+    }
+
     //-------------------------------------------------
     // Common utilities to handle AST nodes
     //-------------------------------------------------
@@ -218,7 +292,7 @@ impl AstParser {
         let id = self.parse_id(node).ok();
         self.current_sol_ver = self.default_sol_ver.clone();
         let file_path = self.parse_source_unit_path(node)?;
-        let file_source = fs::read_to_string(&file_path)?;
+        let file_source = self.sources.read(&file_path)?;
         self.current_file_id = self.file_dictionary.add(file_path.clone(), file_source);
         self.current_file_path = Some(file_path.clone());
         let elems = node
@@ -246,8 +320,8 @@ impl AstParser {
             .get("absolutePath")
             .ok_or_else(|| error!("Parsing source unit: absolute path not found: {node}"))?
             .as_str()
-            .ok_or_else(|| error!("Parsing source unit: absolute path invalid: {node}"))?
-            .to_string();
+            .ok_or_else(|| error!("Parsing source unit: absolute path invalid: {node}"))?;
+        let source_file_abs = self.remap_path(source_file_abs);
         let path = match &self.base_path {
             None => source_file_abs,
             Some(base) => {
@@ -353,8 +427,8 @@ impl AstParser {
             .get("absolutePath")
             .ok_or_else(|| error!("Import directive: absolute path not found: {node}"))?
             .as_str()
-            .ok_or_else(|| error!("Import directive: absolute path invalid: {node}"))?
-            .to_string();
+            .ok_or_else(|| error!("Import directive: absolute path invalid: {node}"))?;
+        let abs_path = self.remap_path(abs_path);
         let symbol_aliases = node
             .get("symbolAliases")
             .ok_or_else(|| error!("Import directive: symbol aliases not found: {node}"))?
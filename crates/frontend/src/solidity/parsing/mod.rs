@@ -1,4 +1,5 @@
 pub mod json_ast_parser;
+pub mod smt_checker;
 pub mod type_parser;
 pub mod version_parser;
 pub mod yul_parser;
@@ -6,13 +7,16 @@ pub mod yul_parser;
 pub use json_ast_parser::ast_parser;
 
 use self::json_ast_parser::{AstParser, JsonAst};
+use self::smt_checker::{MODEL_CHECKER_ARGS, SmtFinding, parse_smt_warnings};
+use self::version_parser::version_parser::parse_pragma_solidity_version;
 use crate::solidity::{
     ast::utils::version::{
         check_range_constraint, check_version_constraint, find_compatible_solc_versions,
-        find_pragma_solidity_versions, normalize_version_constraint,
+        normalize_version_constraint,
     },
     ast::{self, SourceUnit, utils::export},
 };
+use common::source::{FileSystem, SourceProvider};
 use common::{error::Result, fail};
 use node_semver::Version;
 use regex::Regex;
@@ -69,6 +73,24 @@ fn save_to_temporary_files(source_code_list: &[(&str, &str)]) -> Result<Vec<Stri
     Ok(output_files)
 }
 
+/// Write the sources at `paths` of a provider that is not backed by files
+/// into a fresh temporary directory, keeping their relative paths, so that
+/// Solc can read them.
+fn write_virtual_sources(
+    sources: &dyn SourceProvider,
+    paths: &[String],
+) -> Result<tempfile::TempDir> {
+    let dir = tempfile::tempdir()?;
+    for path in paths {
+        let file_path = dir.path().join(path.trim_start_matches('/'));
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file_path, sources.read(path)?)?;
+    }
+    Ok(dir)
+}
+
 fn get_installed_solc_vers() -> Result<String> {
     let cmd_args = " versions".to_string();
     let cmd_output = Command::new(SOLC_SELECT)
@@ -120,17 +142,231 @@ pub fn parse_input_file(
     include_paths: &[String],
     solc_ver: Option<&str>,
 ) -> Result<Vec<ast::SourceUnit>> {
+    parse_input_file_with_remappings(input_file, base_path, include_paths, &[], solc_ver)
+}
+
+/// Parse input file to source units in AST format, passing import
+/// remappings (`prefix=target`, e.g.
+/// `@openzeppelin/=node_modules/@openzeppelin/`) to Solc >=0.5.0.
+pub fn parse_input_file_with_remappings(
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<Vec<ast::SourceUnit>> {
+    compile_input_file(input_file, base_path, include_paths, remappings, solc_ver)
+        .map(|(source_units, _)| source_units)
+}
+
+/// Compile an input file with Solc and parse the resulting AST.
+///
+/// Several Solc versions may satisfy the file's pragma and `solc_ver`; they
+/// are tried in order of preference. Returns the source units together with
+/// the version that compiled them.
+pub fn compile_input_file(
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<(Vec<ast::SourceUnit>, Version)> {
+    compile_input_file_from(
+        &FileSystem,
+        input_file,
+        base_path,
+        include_paths,
+        remappings,
+        solc_ver,
+    )
+}
+
+/// Like [`compile_input_file`], reading the input file, its imports and the
+/// source of every parsed unit through `sources`.
+pub fn compile_input_file_from(
+    sources: &dyn SourceProvider,
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<(Vec<ast::SourceUnit>, Version)> {
+    let (json_data, solc_ver) = compile_input_file_to_json_from(
+        sources,
+        input_file,
+        base_path,
+        include_paths,
+        remappings,
+        solc_ver,
+    )?;
+    let source_units = parse_compiled_json_from(
+        sources, &json_data, input_file, base_path, remappings, &solc_ver,
+    )?;
+    Ok((source_units, solc_ver))
+}
+
+/// Like [`compile_input_file`], also returning the targets that Solc's
+/// SMTChecker could not prove, see
+/// [`compile_input_file_to_json_with_smt_from`].
+pub fn compile_input_file_with_smt(
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<(Vec<ast::SourceUnit>, Version, Vec<SmtFinding>)> {
+    let (json_data, solc_ver, smt_findings) = compile_input_file_to_json_with_smt_from(
+        &FileSystem,
+        input_file,
+        base_path,
+        include_paths,
+        remappings,
+        solc_ver,
+    )?;
+    let source_units =
+        parse_compiled_json(&json_data, input_file, base_path, remappings, &solc_ver)?;
+    Ok((source_units, solc_ver, smt_findings))
+}
+
+/// Parse the combined JSON output of Solc for `input_file`, as returned by
+/// [`compile_input_file_to_json`], into source units. Source unit and import
+/// paths are resolved through `remappings`, the ones given to Solc.
+pub fn parse_compiled_json(
+    json_data: &str,
+    input_file: &str,
+    base_path: Option<&str>,
+    remappings: &[String],
+    solc_ver: &Version,
+) -> Result<Vec<ast::SourceUnit>> {
+    parse_compiled_json_from(&FileSystem, json_data, input_file, base_path, remappings, solc_ver)
+}
+
+/// Like [`parse_compiled_json`], reading the source of every unit through
+/// `sources`.
+pub fn parse_compiled_json_from(
+    sources: &dyn SourceProvider,
+    json_data: &str,
+    input_file: &str,
+    base_path: Option<&str>,
+    remappings: &[String],
+    solc_ver: &Version,
+) -> Result<Vec<ast::SourceUnit>> {
+    let json_ast = JsonAst::new(json_data, Some(input_file), base_path);
+    let mut parser = AstParser::new(&json_ast, Some(solc_ver))
+        .with_sources(sources)
+        .with_remappings(remappings);
+    match parser.parse_solidity_json() {
+        Ok(source_units) => Ok(source_units),
+        Err(err) => fail!(err),
+    }
+}
+
+/// Parse the output of `solc --standard-json`, produced by an existing
+/// build, into source units without running Solc.
+///
+/// The output must contain the `ast` of every source. The source units keep
+/// the paths of the `sources` map, and their code is read from the file
+/// system relative to the working directory.
+pub fn compile_from_standard_json(json: &str) -> Result<Vec<ast::SourceUnit>> {
+    compile_from_standard_json_from(&FileSystem, json)
+}
+
+/// Like [`compile_from_standard_json`], reading the source of every unit
+/// through `sources`.
+pub fn compile_from_standard_json_from(
+    sources: &dyn SourceProvider,
+    json: &str,
+) -> Result<Vec<ast::SourceUnit>> {
+    let json_ast = JsonAst::new(json, None, None);
+    let mut parser = AstParser::new(&json_ast, None).with_sources(sources);
+    parser.parse_standard_json()
+}
+
+/// Compile an input file with Solc and return its combined JSON output
+/// (the AST of the file and of everything it imports), together with the
+/// Solc version that produced it.
+pub fn compile_input_file_to_json(
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<(String, Version)> {
+    compile_input_file_to_json_from(
+        &FileSystem,
+        input_file,
+        base_path,
+        include_paths,
+        remappings,
+        solc_ver,
+    )
+}
+
+/// Like [`compile_input_file_to_json`], reading sources through `sources`.
+///
+/// Solc only reads files, so the sources of a provider with
+/// [`virtual_paths`](SourceProvider::virtual_paths) are written to a
+/// temporary directory for the duration of the compilation, and Solc runs
+/// there so that the paths in its output are the virtual ones.
+pub fn compile_input_file_to_json_from(
+    sources: &dyn SourceProvider,
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<(String, Version)> {
+    compile_with_solc(sources, input_file, base_path, include_paths, remappings, solc_ver, false)
+        .map(|(json_data, solc_ver, _)| (json_data, solc_ver))
+}
+
+/// Like [`compile_input_file_to_json_from`], also running Solc's
+/// SMTChecker with the CHC engine on all targets and returning the targets
+/// it could not prove. This is much slower than a plain compilation.
+///
+/// Solc older than 0.8.4 cannot select the targets; no model checking is
+/// done with it.
+pub fn compile_input_file_to_json_with_smt_from(
+    sources: &dyn SourceProvider,
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+) -> Result<(String, Version, Vec<SmtFinding>)> {
+    compile_with_solc(sources, input_file, base_path, include_paths, remappings, solc_ver, true)
+        .map(|(json_data, solc_ver, stderr)| (json_data, solc_ver, parse_smt_warnings(&stderr)))
+}
+
+/// Compile `input_file` to combined JSON, returning Solc's standard error
+/// alongside, which holds its warnings.
+fn compile_with_solc(
+    sources: &dyn SourceProvider,
+    input_file: &str,
+    base_path: Option<&str>,
+    include_paths: &[String],
+    remappings: &[String],
+    solc_ver: Option<&str>,
+    model_checker: bool,
+) -> Result<(String, Version, String)> {
     let input_file_path = Path::new(input_file);
     let rel_input_file = common::utils::format_relative_path(input_file_path);
     println!("\nCompiling input file: {rel_input_file}");
-    if !input_file_path.exists() {
+    if !sources.exists(input_file) {
         fail!("Input file does not exist: {}", input_file);
     }
+    let workdir = match sources.virtual_paths() {
+        Some(paths) => Some(write_virtual_sources(sources, &paths)?),
+        None => None,
+    };
 
     // Checking Solc version indicated in smart contract source code
-    let pragma_solc_ver = match find_pragma_solidity_versions(input_file) {
-        Ok(vers) if !vers.is_empty() => Some(vers.join(", ")),
-        _ => None,
+    let pragma_solc_ver = match sources.read(input_file) {
+        Ok(content) => {
+            let vers = parse_pragma_solidity_version(&content);
+            (!vers.is_empty()).then(|| vers.join(", "))
+        }
+        Err(_) => None,
     };
     if let Some(ver) = &pragma_solc_ver {
         let ver = normalize_version_constraint(ver);
@@ -197,6 +433,14 @@ pub fn parse_input_file(
                 args += &format!(" --include-path {include_path}");
             }
         }
+        if check_version_constraint(solc_ver, ">=0.5.0") {
+            for remapping in remappings {
+                args += &format!(" {remapping}");
+            }
+        }
+        if model_checker && check_version_constraint(solc_ver, ">=0.8.4") {
+            args += &format!(" {MODEL_CHECKER_ARGS}");
+        }
 
         // Solc 0.8.10 and newer don't need the flag `compact-format`
         // compact-format was introduced in Solc 0.4.12
@@ -207,7 +451,11 @@ pub fn parse_input_file(
 
         // Compile source code to JSON AST
         debug!("Command: SOLC_VERSION={solc_ver} solc {args}");
-        let output = Command::new(SOLC)
+        let mut command = Command::new(SOLC);
+        if let Some(dir) = &workdir {
+            command.current_dir(dir.path());
+        }
+        let output = command
             .env("SOLC_VERSION", format!("{solc_ver}"))
             .args(args.split_whitespace())
             .output()?;
@@ -229,18 +477,19 @@ pub fn parse_input_file(
 
         match std::str::from_utf8(&output.stdout) {
             Ok(json_data) => {
-                let (output_dir, file_stem) = export::prepare_logging_directory(input_file)?;
+                // Virtual sources keep their logs in the temporary directory.
+                let log_file = match &workdir {
+                    Some(dir) => dir.path().join(input_file).to_string_lossy().to_string(),
+                    None => input_file.to_string(),
+                };
+                let (output_dir, file_stem) = export::prepare_logging_directory(&log_file)?;
                 let output_file_path = output_dir.join(format!("{file_stem}.sol.json"));
                 let rel_output = common::utils::format_relative_path(&output_file_path);
                 println!("\nExport JSON output to: {rel_output}");
                 let mut file = File::create(output_file_path)?;
                 file.write_all(json_data.as_bytes())?;
-                let json_ast = JsonAst::new(json_data, Some(input_file), base_path);
-                let mut parser = AstParser::new(&json_ast, Some(solc_ver));
-                match parser.parse_solidity_json() {
-                    Ok(source_units) => return Ok(source_units),
-                    Err(err) => fail!(err),
-                }
+                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                return Ok((json_data.to_string(), solc_ver.clone(), stderr));
             }
             Err(_) => fail!("Failed to parse JSON of: {}", input_file),
         }
@@ -294,3 +543,119 @@ pub fn parse_solidity_source_code_list(
     // Return result.
     Ok(output_sunits)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::source::InMemorySources;
+    use serde_json::json;
+
+    /// Standard JSON output AST of `contract <name> {}` in `path`.
+    fn source(id: i64, path: &str, name: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "ast": {
+                "nodeType": "SourceUnit",
+                "id": id * 10,
+                "absolutePath": path,
+                "src": "0:14:0",
+                "nodes": [{
+                    "nodeType": "ContractDefinition",
+                    "id": id * 10 + 1,
+                    "name": name,
+                    "contractKind": "contract",
+                    "baseContracts": [],
+                    "nodes": [],
+                    "src": "0:14:0",
+                }],
+            },
+        })
+    }
+
+    #[test]
+    fn test_compile_from_standard_json() {
+        let output = json!({
+            "sources": {
+                "contracts/Vault.sol": source(1, "contracts/Vault.sol", "Vault"),
+                "contracts/Token.sol": source(0, "contracts/Token.sol", "Token"),
+            },
+            "contracts": {},
+        });
+        let sources = InMemorySources::new()
+            .with_file("contracts/Token.sol", "contract Token {}")
+            .with_file("contracts/Vault.sol", "contract Vault {}");
+
+        let units = compile_from_standard_json_from(&sources, &output.to_string()).unwrap();
+        let paths: Vec<&str> = units.iter().map(|u| u.path.as_str()).collect();
+        assert_eq!(paths, ["contracts/Token.sol", "contracts/Vault.sol"]);
+        let ast::SourceUnitElem::Contract(contract) = &units[1].elems[0] else {
+            panic!("Expected a contract: {:?}", units[1].elems);
+        };
+        assert_eq!(contract.name.base, "Vault");
+    }
+
+    #[test]
+    fn test_parse_compiled_json_with_remapped_import() {
+        let mut token = source(0, "contracts/Token.sol", "Token")["ast"].clone();
+        token["nodes"].as_array_mut().unwrap().insert(
+            0,
+            json!({
+                "nodeType": "ImportDirective",
+                "id": 2,
+                "file": "@openzeppelin/contracts/ERC20.sol",
+                "absolutePath": "@openzeppelin/contracts/ERC20.sol",
+                "symbolAliases": [],
+                "unitAlias": "",
+                "src": "0:14:0",
+            }),
+        );
+        let erc20 = source(1, "@openzeppelin/contracts/ERC20.sol", "ERC20")["ast"].clone();
+        let output = json!({
+            "sourceList": ["contracts/Token.sol", "@openzeppelin/contracts/ERC20.sol"],
+            "sources": {
+                "contracts/Token.sol": { "AST": token },
+                "@openzeppelin/contracts/ERC20.sol": { "AST": erc20 },
+            },
+        });
+        let sources = InMemorySources::new()
+            .with_file("contracts/Token.sol", "contract Token {}")
+            .with_file("node_modules/@openzeppelin/contracts/ERC20.sol", "contract ERC20 {}");
+        let remappings = ["@openzeppelin/=node_modules/@openzeppelin/".to_string()];
+
+        let units = parse_compiled_json_from(
+            &sources,
+            &output.to_string(),
+            "contracts/Token.sol",
+            None,
+            &remappings,
+            &Version::parse("0.8.20").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(units[1].path, "node_modules/@openzeppelin/contracts/ERC20.sol");
+        let ast::SourceUnitElem::Import(import) = &units[0].elems[0] else {
+            panic!("Expected an import: {:?}", units[0].elems);
+        };
+        assert_eq!(import.get_import_path(), units[1].path);
+
+        // The import is resolved to the remapped source unit.
+        let units = crate::solidity::lowering::eliminate_import(&units);
+        let names: Vec<String> = units[0]
+            .elems
+            .iter()
+            .filter_map(|elem| elem.get_name())
+            .map(|n| n.base)
+            .collect();
+        assert!(names.contains(&"ERC20".to_string()), "{names:?}");
+    }
+
+    #[test]
+    fn test_compile_from_standard_json_reports_errors() {
+        let output = json!({
+            "errors": [{ "severity": "error", "formattedMessage": "ParserError: oops" }],
+            "sources": {},
+        });
+        let err = compile_from_standard_json_from(&InMemorySources::new(), &output.to_string())
+            .unwrap_err();
+        assert!(err.to_string().contains("ParserError: oops"));
+    }
+}
@@ -0,0 +1,166 @@
+//! Results of Solc's SMTChecker.
+//!
+//! Given [`MODEL_CHECKER_ARGS`], Solc tries to prove verification targets
+//! (overflows, assertions, divisions by zero, ...) while compiling, and
+//! prints those it cannot prove as warnings on its standard error:
+//!
+//! ```text
+//! Warning: CHC: Overflow (resulting value larger than 2**256 - 1) happens here.
+//! Counterexample:
+//! x = 1
+//!
+//!  --> contracts/Vault.sol:7:16:
+//!   |
+//! 7 |         return x + y;
+//!   |                ^^^^^
+//! ```
+//!
+//! [`parse_smt_warnings`] turns these warnings into [`SmtFinding`]s.
+//! Summary messages without a location, such as the number of targets
+//! proved safe, are dropped.
+
+use common::loc::Loc;
+
+/// Solc arguments enabling the CHC engine on all verification targets.
+pub const MODEL_CHECKER_ARGS: &str = "--model-checker-engine chc --model-checker-targets all";
+
+/// A verification target that Solc's SMTChecker could not prove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtFinding {
+    /// Engine that reported the target, `CHC` or `BMC`.
+    pub engine: String,
+    /// Solc's message, e.g. "Assertion violation happens here."
+    pub message: String,
+    /// Assignments of the counterexample, when Solc found one.
+    pub counterexample: Vec<String>,
+    /// Location of the target.
+    pub loc: Loc,
+}
+
+impl SmtFinding {
+    /// Returns `true` if the target lies on the lines of `loc`, in the same
+    /// file when both files are known. Paths are compared by suffix, since
+    /// Solc reports them relative to its base path.
+    pub fn overlaps(&self, loc: &Loc) -> bool {
+        let same_file = match (&self.loc.file, &loc.file) {
+            (Some(a), Some(b)) => a.ends_with(b.as_str()) || b.ends_with(a.as_str()),
+            _ => true,
+        };
+        same_file && self.loc.start_line <= loc.end_line && loc.start_line <= self.loc.end_line
+    }
+}
+
+/// Parse the SMTChecker warnings of Solc's standard error `stderr`.
+pub fn parse_smt_warnings(stderr: &str) -> Vec<SmtFinding> {
+    let mut findings = vec![];
+    let mut current: Option<SmtFinding> = None;
+    let mut in_counterexample = false;
+
+    for line in stderr.lines() {
+        if let Some(rest) = line.strip_prefix("Warning: ") {
+            findings.extend(current.take().filter(|f| f.loc.is_valid()));
+            in_counterexample = false;
+            current = rest.split_once(": ").and_then(|(engine, message)| {
+                matches!(engine, "CHC" | "BMC").then(|| SmtFinding {
+                    engine: engine.to_string(),
+                    message: message.trim().to_string(),
+                    counterexample: vec![],
+                    loc: Loc::default(),
+                })
+            });
+            continue;
+        }
+        if line.starts_with("Error: ") || line.starts_with("Info: ") {
+            findings.extend(current.take().filter(|f| f.loc.is_valid()));
+            continue;
+        }
+        let Some(finding) = &mut current else {
+            continue;
+        };
+
+        let trimmed = line.trim();
+        if let Some(location) = trimmed.strip_prefix("--> ") {
+            in_counterexample = false;
+            if let Some(loc) = parse_location(location) {
+                finding.loc = loc;
+            }
+        } else if trimmed == "Counterexample:" {
+            in_counterexample = true;
+        } else if in_counterexample && !trimmed.is_empty() {
+            finding.counterexample.push(trimmed.to_string());
+        } else if finding.loc.is_valid()
+            && finding.loc.end_col == finding.loc.start_col
+            && let Some((_, marker)) = line.split_once('|')
+            && let Some(width) = caret_width(marker)
+        {
+            // The first marker line underlines the target.
+            finding.loc.end_col = finding.loc.start_col + width - 1;
+        } else if trimmed.is_empty() {
+            in_counterexample = false;
+        }
+    }
+    findings.extend(current.filter(|f| f.loc.is_valid()));
+    findings
+}
+
+/// Parse a `path:line:column:` location.
+fn parse_location(location: &str) -> Option<Loc> {
+    let mut parts = location.trim_end_matches(':').rsplitn(3, ':');
+    let col: usize = parts.next()?.parse().ok()?;
+    let line: usize = parts.next()?.parse().ok()?;
+    let file = parts.next()?;
+    Some(Loc::new(line, col, line, col).with_file(file.to_string()))
+}
+
+/// Number of `^` in a marker line of a source snippet.
+fn caret_width(marker: &str) -> Option<usize> {
+    let carets = marker.trim();
+    (!carets.is_empty() && carets.chars().all(|c| c == '^')).then_some(carets.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_smt_warnings() {
+        let stderr = indoc! {"
+            Warning: CHC: Overflow (resulting value larger than 2**256 - 1) happens here.
+            Counterexample:
+            x = 1
+            y = 115792089237316195423570985008687907853269984665640564039457584007913129639935
+
+            Transaction trace:
+            Vault.constructor()
+             --> contracts/Vault.sol:7:16:
+              |
+            7 |         return x + y;
+              |                ^^^^^
+
+            Warning: Unused local variable.
+             --> contracts/Vault.sol:9:9:
+              |
+            9 |         uint z;
+              |         ^^^^^^
+
+            Info: CHC: 2 verification condition(s) proved safe!
+        "};
+
+        let findings = parse_smt_warnings(stderr);
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.engine, "CHC");
+        assert_eq!(
+            finding.message,
+            "Overflow (resulting value larger than 2**256 - 1) happens here."
+        );
+        assert_eq!(finding.counterexample.len(), 2);
+        assert_eq!(finding.loc, Loc::new(7, 16, 7, 20).with_file("contracts/Vault.sol".into()));
+
+        assert!(
+            finding.overlaps(&Loc::new(5, 5, 8, 5).with_file("/src/contracts/Vault.sol".into()))
+        );
+        assert!(!finding.overlaps(&Loc::new(7, 5, 7, 30).with_file("Token.sol".into())));
+    }
+}
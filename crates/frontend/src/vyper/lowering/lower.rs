@@ -220,6 +220,7 @@ impl Lowerer {
             type_params: vec![],
             params,
             returns,
+            return_names: vec![],
             modifier_invocs: vec![],
             attrs,
             spec: None,
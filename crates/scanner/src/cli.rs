@@ -166,6 +166,9 @@ fn print_detectors() {
     println!("=====================================\n");
 
     // Group by level
+    let project_dets: Vec<_> = registry.all().iter()
+        .filter(|d| d.level() == crate::DetectionLevel::Project)
+        .collect();
     let module_dets: Vec<_> = registry.all().iter()
         .filter(|d| d.level() == crate::DetectionLevel::Module)
         .collect();
@@ -176,7 +179,13 @@ fn print_detectors() {
         .filter(|d| d.level() == crate::DetectionLevel::Function)
         .collect();
 
-    println!("MODULE ({}):", module_dets.len());
+    println!("PROJECT ({}):", project_dets.len());
+    for d in &project_dets {
+        println!("  {:<25} {:<30} {:?}   {:?}",
+            d.id(), d.name(), d.bug_kind(), d.risk_level());
+    }
+
+    println!("\nMODULE ({}):", module_dets.len());
     for d in &module_dets {
         println!("  {:<25} {:<30} {:?}   {:?}",
             d.id(), d.name(), d.bug_kind(), d.risk_level());
@@ -1,23 +1,12 @@
 use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
 use scirs::sir::{ContractDecl, FunctionDecl, Module};
+use std::collections::HashMap;
 
-/// Confidence level for a scan finding.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Confidence {
-    Low,
-    Medium,
-    High,
-}
+/// Confidence level for a scan finding, shared with `analyzer`'s passes.
+pub use bugs::bug::Confidence;
 
-impl std::fmt::Display for Confidence {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Confidence::High => write!(f, "High"),
-            Confidence::Medium => write!(f, "Medium"),
-            Confidence::Low => write!(f, "Low"),
-        }
-    }
-}
+/// Per-detector parameters (e.g. thresholds), as `key = value` strings.
+pub type DetectorParams = HashMap<String, String>;
 
 /// Target platform that a detector applies to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -32,11 +21,13 @@ pub enum Target {
 
 /// The SIR hierarchy level at which a detector operates.
 ///
-/// Inspired by `analyzer::PassLevel`, but simplified to the three
+/// Inspired by `analyzer::PassLevel`, but simplified to the four
 /// levels that scanner detectors actually need. The `ScanEngine`
 /// uses this to dispatch detectors during its single-pass walk.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DetectionLevel {
+    /// Operates on all modules of a run at once (e.g., project-wide usage).
+    Project,
     /// Operates on whole modules (e.g., pragma checks).
     Module,
     /// Operates on individual contracts (e.g., access control, state vars).
@@ -103,8 +94,30 @@ pub trait ScanDetector: Send + Sync {
         vec![]
     }
 
+    /// Topic tags (e.g. `erc20`, `proxy`, `defi`, `gas`) for selecting
+    /// related detectors as a group.
+    fn tags(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    // ── Configuration ───────────────────────────────────
+
+    /// Apply per-detector parameters. Unknown keys and unparsable values
+    /// are ignored, keeping the detector's defaults.
+    fn configure(&mut self, _params: &DetectorParams) {}
+
+    /// Names of the parameters accepted by [`configure`](Self::configure).
+    fn param_names(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
     // ── Detection (only one is called, based on level()) ──
 
+    /// Check all modules together. Called when `level() == Project`.
+    fn check_project(&self, _modules: &[Module]) -> Vec<Bug> {
+        vec![]
+    }
+
     /// Check a module. Called when `level() == Module`.
     fn check_module(&self, _module: &Module) -> Vec<Bug> {
         vec![]
@@ -0,0 +1,356 @@
+//! Ambiguous Override Detector
+//!
+//! Detects functions a contract inherits from several unrelated bases
+//! without an `override(A, B)` specifier naming all of them:
+//!
+//! ```solidity
+//! contract B { function f() public virtual {} }
+//! contract C { function f() public virtual {} }
+//! contract D is B, C {
+//!     function f() public override(B) {}   // `C.f` is not named
+//! }
+//! ```
+//!
+//! Which base implementation `super.f()` reaches then depends on the C3
+//! linearization of `D` rather than on the specifier. A base is only a
+//! conflicting one if no other base defining the function derives from it.
+//! Functions are matched by ABI signature, and `private` functions, which
+//! cannot be overridden, are ignored.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::attrs::{AttrValue, sir_attrs};
+use scirs::sir::dialect::evm::EvmFunctionExt;
+use scirs::sir::lower::linearize;
+use scirs::sir::{ContractDecl, Decl, FunctionDecl, MemberDecl, Module};
+use std::collections::{HashMap, HashSet};
+
+/// Scan detector for functions inherited from several bases without an
+/// explicit override of all of them.
+#[derive(Debug, Default)]
+pub struct AmbiguousOverrideDetector;
+
+impl AmbiguousOverrideDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Functions of a contract that can be overridden, i.e. all but the
+/// constructor and `private` functions.
+fn functions(contract: &ContractDecl) -> impl Iterator<Item = &FunctionDecl> {
+    contract.members.iter().filter_map(|m| match m {
+        MemberDecl::Function(f) if !f.is_constructor() && !is_private(f) => Some(f),
+        _ => None,
+    })
+}
+
+fn is_private(func: &FunctionDecl) -> bool {
+    func.attrs.iter().any(|a| {
+        a.namespace == "sir"
+            && a.key == sir_attrs::VISIBILITY
+            && matches!(&a.value, AttrValue::String(s) if s == "private")
+    })
+}
+
+/// Ancestors of `contract` declared in the module, in C3 linearization
+/// order from the most base one. `None` if the hierarchy cannot be
+/// linearized.
+fn ancestors<'m>(
+    contract: &str,
+    contracts: &HashMap<String, &'m ContractDecl>,
+) -> Option<Vec<&'m ContractDecl>> {
+    let order = linearize(contract, contracts).ok()?;
+    Some(
+        order[1..]
+            .iter()
+            .rev()
+            .filter_map(|name| contracts.get(name).copied())
+            .collect(),
+    )
+}
+
+impl ScanDetector for AmbiguousOverrideDetector {
+    fn id(&self) -> &'static str {
+        "ambiguous-override"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ambiguous Override"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects functions inherited from multiple bases without an \
+         `override(...)` specifier naming all of them"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![710]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Override the function in the derived contract with an \
+         `override(A, B)` specifier naming every base that defines it, and \
+         call the intended base implementations explicitly."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/contracts.html#function-overriding",
+            "https://docs.soliditylang.org/en/latest/contracts.html#multiple-inheritance-and-linearization",
+        ]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+        if contract.parents.len() < 2 {
+            return bugs;
+        }
+
+        let contracts: HashMap<String, &ContractDecl> = module
+            .decls
+            .iter()
+            .filter_map(|d| match d {
+                Decl::Contract(c) => Some((c.name.clone(), c)),
+                _ => None,
+            })
+            .collect();
+        // The compiler rejects hierarchies without a linearization.
+        let Some(inherited) = ancestors(&contract.name, &contracts) else {
+            return bugs;
+        };
+        let ancestry: HashMap<&str, HashSet<&str>> = inherited
+            .iter()
+            .map(|a| {
+                let names = ancestors(&a.name, &contracts)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|b| b.name.as_str())
+                    .collect();
+                (a.name.as_str(), names)
+            })
+            .collect();
+
+        // Bases defining each function signature, most base first.
+        let mut definers: Vec<(String, &str, Vec<&str>)> = Vec::new();
+        for ancestor in &inherited {
+            for func in functions(ancestor) {
+                let signature = func.abi_signature();
+                match definers.iter_mut().find(|(s, _, _)| *s == signature) {
+                    Some((_, _, bases)) => bases.push(&ancestor.name),
+                    None => definers.push((signature, &func.name, vec![&ancestor.name])),
+                }
+            }
+        }
+
+        for (signature, name, bases) in definers {
+            // Bases overridden by another defining base do not conflict.
+            let conflicting: Vec<&str> = bases
+                .iter()
+                .copied()
+                .filter(|base| {
+                    !bases
+                        .iter()
+                        .any(|other| ancestry.get(other).is_some_and(|a| a.contains(base)))
+                })
+                .collect();
+            if conflicting.len() < 2 {
+                continue;
+            }
+
+            let own = functions(contract).find(|f| f.abi_signature() == signature);
+            let missing: Vec<&str> = match own.and_then(|f| f.overridden_bases()) {
+                Some(named) => conflicting
+                    .iter()
+                    .copied()
+                    .filter(|b| !named.contains(b))
+                    .collect(),
+                None => conflicting.clone(),
+            };
+            if missing.is_empty() {
+                continue;
+            }
+
+            let reason = match own {
+                Some(_) => {
+                    format!("its `override` specifier does not name {}", missing.join(", "))
+                }
+                None => "it does not override it".to_string(),
+            };
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "Function '{}' of '{}' is inherited from multiple bases ({}) but {}.",
+                    name,
+                    contract.name,
+                    conflicting.join(", "),
+                    reason
+                )),
+                own.and_then(|f| f.span.clone())
+                    .or_else(|| contract.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::attrs::{Attr, evm_attrs};
+    use scirs::sir::{Param, Type};
+
+    fn function(name: &str, overrides: Option<&str>) -> MemberDecl {
+        function_with(name, vec![], overrides)
+    }
+
+    fn function_with(name: &str, params: Vec<Param>, overrides: Option<&str>) -> MemberDecl {
+        let mut func = FunctionDecl::new(name.to_string(), params, vec![], Some(vec![]), None);
+        if let Some(bases) = overrides {
+            func.attrs
+                .push(Attr::evm(evm_attrs::OVERRIDES, AttrValue::String(bases.to_string())));
+        }
+        MemberDecl::Function(func)
+    }
+
+    fn contract(name: &str, parents: &[&str], members: Vec<MemberDecl>) -> ContractDecl {
+        ContractDecl {
+            name: name.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            attrs: vec![],
+            members,
+            span: None,
+        }
+    }
+
+    /// `B` and `C` both define `f`; `B is A` and `C is A`.
+    fn check(derived: ContractDecl) -> Vec<Bug> {
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![
+                Decl::Contract(contract("A", &[], vec![function("f", None)])),
+                Decl::Contract(contract("B", &["A"], vec![function("f", Some("A"))])),
+                Decl::Contract(contract("C", &["A"], vec![function("f", Some("A"))])),
+                Decl::Contract(derived.clone()),
+            ],
+        };
+        AmbiguousOverrideDetector::new().check_contract(&derived, &module)
+    }
+
+    #[test]
+    fn test_partial_override_is_flagged() {
+        let bugs = check(contract("D", &["B", "C"], vec![function("f", Some("B"))]));
+        assert_eq!(bugs.len(), 1);
+        let description = bugs[0].description.as_ref().unwrap();
+        assert!(description.contains("multiple bases (B, C)"));
+        assert!(description.ends_with("does not name C."));
+
+        // A bare `override` names none of the bases.
+        assert_eq!(check(contract("D", &["B", "C"], vec![function("f", Some(""))])).len(), 1);
+        // Not overriding at all leaves the choice to the linearization.
+        assert_eq!(check(contract("D", &["B", "C"], vec![])).len(), 1);
+    }
+
+    #[test]
+    fn test_full_override_is_not_flagged() {
+        assert!(check(contract("D", &["B", "C"], vec![function("f", Some("B,C"))])).is_empty());
+        // `A.f` is overridden by both `B` and `C`: only they conflict.
+        assert!(check(contract("D", &["A", "B"], vec![])).is_empty());
+    }
+
+    #[test]
+    fn test_distinct_signatures_are_not_flagged() {
+        // `B.g(uint256)` and `C.g(bool)` are overloads, not conflicts.
+        let g = |base: &str, ty: Type| {
+            contract(
+                base,
+                &[],
+                vec![function_with(
+                    "g",
+                    vec![Param::new("x".to_string(), ty)],
+                    None,
+                )],
+            )
+        };
+        let derived = contract("D", &["B", "C"], vec![]);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![
+                Decl::Contract(g("B", Type::I256)),
+                Decl::Contract(g("C", Type::Bool)),
+                Decl::Contract(derived.clone()),
+            ],
+        };
+        assert!(
+            AmbiguousOverrideDetector::new()
+                .check_contract(&derived, &module)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_private_functions_are_not_flagged() {
+        let private = |base: &str| {
+            let MemberDecl::Function(mut func) = function("h", None) else {
+                unreachable!()
+            };
+            func.attrs
+                .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String("private".to_string())));
+            contract(base, &[], vec![MemberDecl::Function(func)])
+        };
+        let derived = contract("D", &["B", "C"], vec![]);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![
+                Decl::Contract(private("B")),
+                Decl::Contract(private("C")),
+                Decl::Contract(derived.clone()),
+            ],
+        };
+        assert!(
+            AmbiguousOverrideDetector::new()
+                .check_contract(&derived, &module)
+                .is_empty()
+        );
+    }
+}
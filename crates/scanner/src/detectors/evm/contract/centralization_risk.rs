@@ -101,6 +101,10 @@ impl ScanDetector for CentralizationRiskDetector {
         ]
     }
 
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["defi"]
+    }
+
     fn check_contract(&self, contract: &ContractDecl, _module: &Module) -> Vec<Bug> {
         let mut bugs = Vec::new();
 
@@ -71,6 +71,10 @@ impl ScanDetector for ConstantStateVarDetector {
         ]
     }
 
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+
     fn check_contract(&self, contract: &ContractDecl, _module: &Module) -> Vec<Bug> {
         let mut bugs = Vec::new();
 
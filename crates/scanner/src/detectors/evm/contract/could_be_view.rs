@@ -0,0 +1,454 @@
+//! Could Be View Detector
+//!
+//! Detects functions that are not marked `view`/`pure` but never write
+//! state, emit events, send ETH or make external calls.
+//!
+//! State changes are found by walking the SIR bodies of the contract's
+//! functions and invoked modifiers, and propagated along internal calls.
+//! Empty `virtual` functions, such as `_beforeTokenTransfer() {}`, are hooks
+//! for derived contracts to fill in and count as changing state, and so do
+//! their callers.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::attrs::{AttrValue, evm_attrs, sir_attrs};
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl, EvmStmt};
+use scirs::sir::dialect::{DialectMemberDecl, DialectStmt, EvmCallExt, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, CallExpr, ContractDecl, DialectExpr, Expr, FunctionDecl,
+    LocalVarStmt, MemberDecl, Module, Stmt, Type, UnOp, UnOpExpr,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Scan detector for functions whose mutability could be `view` or `pure`.
+#[derive(Debug, Default)]
+pub struct CouldBeViewDetector;
+
+impl CouldBeViewDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Returns `true` if the function already declares a mutability
+/// (`view`, `pure`, `constant` or `payable`).
+fn has_declared_mutability(func: &FunctionDecl) -> bool {
+    func.attrs
+        .iter()
+        .any(|a| a.namespace == "sir" && a.key == sir_attrs::MUTABILITY)
+}
+
+/// Returns `true` for constructors, fallback and receive functions, which
+/// cannot be declared `view`.
+fn is_special_function(func: &FunctionDecl) -> bool {
+    let is_ctor = func.attrs.iter().any(|a| {
+        a.namespace == "evm"
+            && a.key == evm_attrs::IS_CONSTRUCTOR
+            && matches!(a.value, AttrValue::Bool(true))
+    });
+    is_ctor || matches!(func.name.as_str(), "" | "constructor" | "fallback" | "receive")
+}
+
+/// Visitor that looks for state-changing operations in a function body.
+///
+/// Any assignment whose root variable is not a parameter or local variable
+/// is treated as a state write, which also covers inherited state variables.
+/// Calls to other contract functions are collected so that mutation can be
+/// propagated through the internal call graph.
+struct MutationFinder {
+    locals: HashSet<String>,
+    mutates: bool,
+    internal_calls: Vec<String>,
+}
+
+impl MutationFinder {
+    fn new(func: &FunctionDecl) -> Self {
+        let locals = func.params.iter().map(|p| p.name.clone()).collect();
+        Self { locals, mutates: false, internal_calls: vec![] }
+    }
+
+    fn writes_storage(&self, lhs: &Expr) -> bool {
+        root_var(lhs).is_some_and(|name| !self.locals.contains(name))
+    }
+}
+
+/// Returns the name of the variable at the root of an lvalue expression.
+fn root_var(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(ia) => root_var(&ia.base),
+        Expr::FieldAccess(fa) => root_var(&fa.base),
+        _ => None,
+    }
+}
+
+impl<'a> Visit<'a> for MutationFinder {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        // Storage pointers (`S storage s = items[i];`) stay non-local so
+        // that writes through them are seen as state writes.
+        let is_storage_ref = stmt
+            .init
+            .as_ref()
+            .is_some_and(|init| self.writes_storage(init));
+        for decl in stmt.vars.iter().flatten() {
+            let is_reference = matches!(
+                decl.ty,
+                Type::TypeRef(_) | Type::Array(_) | Type::FixedArray(..) | Type::Map(..)
+            );
+            if !(is_storage_ref && is_reference) {
+                self.locals.insert(decl.name.clone());
+            }
+        }
+        visit::default::visit_local_var_stmt(self, stmt);
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        if self.writes_storage(&stmt.lhs) {
+            self.mutates = true;
+        }
+        visit::default::visit_assign_stmt(self, stmt);
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        if self.writes_storage(&stmt.lhs) {
+            self.mutates = true;
+        }
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_unop_expr(&mut self, expr: &'a UnOpExpr) {
+        if expr.op == UnOp::Delete && self.writes_storage(&expr.operand) {
+            self.mutates = true;
+        }
+        visit::default::visit_unop_expr(self, expr);
+    }
+
+    fn visit_call_expr(&mut self, call: &'a CallExpr) {
+        match &*call.callee {
+            // Member calls (`token.transfer(..)`, `arr.push(..)`, library
+            // calls on storage) may change state.
+            Expr::FieldAccess(_) => self.mutates = true,
+            Expr::Var(v) if v.name.starts_with("new__") => self.mutates = true,
+            Expr::Var(v) => self.internal_calls.push(v.name.clone()),
+            _ => {}
+        }
+        if call.is_evm_external_call() {
+            self.mutates = true;
+        }
+        visit::default::visit_call_expr(self, call);
+    }
+
+    fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+        if matches!(
+            d,
+            DialectExpr::Evm(
+                EvmExpr::LowLevelCall(_)
+                    | EvmExpr::RawCall(_)
+                    | EvmExpr::Send(_)
+                    | EvmExpr::Transfer(_)
+                    | EvmExpr::Delegatecall(_)
+                    | EvmExpr::InlineAsm(_)
+            )
+        ) {
+            self.mutates = true;
+        }
+    }
+
+    fn visit_dialect_stmt(&mut self, d: &'a DialectStmt) {
+        if matches!(
+            d,
+            DialectStmt::Evm(
+                EvmStmt::EmitEvent(_) | EvmStmt::Selfdestruct(_) | EvmStmt::TryCatch(_)
+            )
+        ) {
+            self.mutates = true;
+        }
+    }
+}
+
+/// Compute the set of functions in the contract that may change state,
+/// propagating through internal calls and invoked modifiers.
+fn mutating_functions(contract: &ContractDecl) -> HashSet<String> {
+    let modifiers: HashMap<&str, &[Stmt]> = contract
+        .members
+        .iter()
+        .filter_map(|m| match m {
+            MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(md))) => {
+                Some((md.name.as_str(), md.body.as_slice()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let functions: Vec<&FunctionDecl> = contract
+        .members
+        .iter()
+        .filter_map(|m| match m {
+            MemberDecl::Function(f) => Some(f),
+            _ => None,
+        })
+        .collect();
+    let function_names: HashSet<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+
+    let mut mutating = HashSet::new();
+    let mut calls: HashMap<String, Vec<String>> = HashMap::new();
+
+    for func in &functions {
+        let mut finder = MutationFinder::new(func);
+        if let Some(body) = &func.body {
+            finder.visit_stmts(body);
+        }
+        for invoc in &func.modifier_invocs {
+            match modifiers.get(invoc.name.as_str()) {
+                Some(body) => finder.visit_stmts(body),
+                // Modifiers defined outside this contract are unknown.
+                None => finder.mutates = true,
+            }
+        }
+        // Calls to functions not defined in this contract (e.g. inherited
+        // ones) cannot be resolved and are treated as state-changing.
+        if finder
+            .internal_calls
+            .iter()
+            .any(|c| !function_names.contains(c.as_str()))
+        {
+            finder.mutates = true;
+        }
+        // Overrides of an empty hook may change state.
+        if func.is_virtual() && func.body.as_ref().is_some_and(|body| body.is_empty()) {
+            finder.mutates = true;
+        }
+        if finder.mutates {
+            mutating.insert(func.name.clone());
+        }
+        calls.insert(func.name.clone(), finder.internal_calls);
+    }
+
+    // Propagate mutation from callees to callers until a fixpoint.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (caller, callees) in &calls {
+            if !mutating.contains(caller) && callees.iter().any(|c| mutating.contains(c)) {
+                mutating.insert(caller.clone());
+                changed = true;
+            }
+        }
+    }
+
+    mutating
+}
+
+impl ScanDetector for CouldBeViewDetector {
+    fn id(&self) -> &'static str {
+        "could-be-view"
+    }
+
+    fn name(&self) -> &'static str {
+        "Function Could Be View"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects functions that never modify state but are not declared view or pure"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Optimization
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Declare functions that do not modify state as `view` (or `pure` if \
+         they also do not read state) to document intent and let callers \
+         invoke them without a transaction."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.soliditylang.org/en/latest/contracts.html#view-functions"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, _module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+        let mutating = mutating_functions(contract);
+
+        for member in &contract.members {
+            if let MemberDecl::Function(func) = member {
+                if func.body.is_none()
+                    || has_declared_mutability(func)
+                    || is_special_function(func)
+                    || mutating.contains(&func.name)
+                {
+                    continue;
+                }
+
+                bugs.push(Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "Function '{}' in '{}' does not modify state and could \
+                         be declared `view` or `pure`.",
+                        func.name, contract.name,
+                    )),
+                    func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                ));
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::*;
+
+    fn total() -> Expr {
+        Expr::Var(VarExpr::new("total".to_string(), Type::I256, None))
+    }
+
+    fn make_contract() -> ContractDecl {
+        // contract C {
+        //     uint256 total;
+        //     function getTotal() public returns (uint256) { return total; }
+        //     function setTotal() public { total = 1; }
+        // }
+        let storage =
+            MemberDecl::Storage(StorageDecl::new("total".to_string(), Type::I256, None, None));
+
+        let get_total = FunctionDecl::new(
+            "getTotal".to_string(),
+            vec![],
+            vec![Type::I256],
+            Some(vec![Stmt::Return(ReturnStmt {
+                value: Some(total()),
+                span: None,
+            })]),
+            None,
+        );
+
+        let set_total = FunctionDecl::new(
+            "setTotal".to_string(),
+            vec![],
+            vec![],
+            Some(vec![Stmt::Assign(AssignStmt {
+                lhs: total(),
+                rhs: Expr::Lit(Lit::one(None)),
+                span: None,
+            })]),
+            None,
+        );
+
+        ContractDecl {
+            name: "C".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![
+                storage,
+                MemberDecl::Function(get_total),
+                MemberDecl::Function(set_total),
+            ],
+            span: None,
+        }
+    }
+
+    #[test]
+    fn test_could_be_view_detector() {
+        let detector = CouldBeViewDetector::new();
+        assert_eq!(detector.id(), "could-be-view");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_read_only_function_is_flagged() {
+        let contract = make_contract();
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        let bugs = CouldBeViewDetector::new().check_contract(&contract, &module);
+        assert_eq!(bugs.len(), 1);
+        assert!(bugs[0].description.as_deref().unwrap().contains("getTotal"));
+    }
+
+    #[test]
+    fn test_empty_virtual_hook_is_not_flagged() {
+        // function _beforeTokenTransfer() internal virtual {}
+        // function transfer() public { _beforeTokenTransfer(); }
+        let mut hook = FunctionDecl::new(
+            "_beforeTokenTransfer".to_string(),
+            vec![],
+            vec![],
+            Some(vec![]),
+            None,
+        );
+        hook.attrs
+            .push(Attr::evm(evm_attrs::IS_VIRTUAL, AttrValue::Bool(true)));
+
+        let call = CallExpr {
+            callee: Box::new(Expr::Var(VarExpr::new(
+                "_beforeTokenTransfer".to_string(),
+                Type::None,
+                None,
+            ))),
+            args: CallArgs::Positional(vec![]),
+            ty: Type::None,
+            span: None,
+        };
+        let transfer = FunctionDecl::new(
+            "transfer".to_string(),
+            vec![],
+            vec![],
+            Some(vec![Stmt::Expr(ExprStmt {
+                expr: Expr::FunctionCall(call),
+                span: None,
+            })]),
+            None,
+        );
+
+        let contract = ContractDecl {
+            name: "Token".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![MemberDecl::Function(hook), MemberDecl::Function(transfer)],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        assert!(
+            CouldBeViewDetector::new()
+                .check_contract(&contract, &module)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,438 @@
+//! Cross-Function Reentrancy Detector
+//!
+//! Detects state that a function writes after an external call while another
+//! public or external function of the contract reads or writes it:
+//!
+//! ```solidity
+//! function withdraw() external {
+//!     (bool ok, ) = msg.sender.call{value: balances[msg.sender]}("");
+//!     require(ok);
+//!     balances[msg.sender] = 0;  // written after the call
+//! }
+//!
+//! function transfer(address to, uint256 amount) external {
+//!     require(balances[msg.sender] >= amount);  // re-entered from the call
+//!     balances[to] += amount;
+//!     balances[msg.sender] -= amount;
+//! }
+//! ```
+//!
+//! The `reentrancy` detector reports the write in `withdraw` itself; this
+//! detector reports the other functions the call can re-enter to observe or
+//! change the state before it is updated. Reads and writes of internal
+//! functions count for their callers. Pairs of functions that both have a
+//! reentrancy guard are skipped, since the guard is shared by the contract.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::stmt_has_external_call;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmStmt;
+use scirs::sir::dialect::{EvmFunctionExt, EvmStorageExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, CallExpr, ContractDecl, DialectStmt, Expr, FunctionDecl,
+    MemberDecl, Module, Stmt, VarExpr,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Scan detector for reentrancy across functions sharing state.
+#[derive(Debug, Default)]
+pub struct CrossFunctionReentrancyDetector;
+
+impl CrossFunctionReentrancyDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Variables a function body reads or writes, the variables it writes, and
+/// the internal functions it calls.
+#[derive(Default)]
+struct StateAccess {
+    accessed: HashSet<String>,
+    written: HashSet<String>,
+    callees: HashSet<String>,
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+impl<'a> Visit<'a> for StateAccess {
+    fn visit_var_expr(&mut self, var: &'a VarExpr) {
+        self.accessed.insert(var.name.clone());
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        self.written
+            .extend(root_name(&stmt.lhs).map(str::to_string));
+        visit::default::visit_assign_stmt(self, stmt);
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        self.written
+            .extend(root_name(&stmt.lhs).map(str::to_string));
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::Var(v) = &*expr.callee {
+            self.callees.insert(v.name.clone());
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+}
+
+/// State accesses of every function of `contract`, including those of the
+/// internal functions it calls, directly or not.
+fn state_accesses(contract: &ContractDecl) -> HashMap<String, StateAccess> {
+    let mut accesses: HashMap<String, StateAccess> = HashMap::new();
+    for member in &contract.members {
+        if let MemberDecl::Function(f) = member {
+            let mut access = StateAccess::default();
+            access.visit_stmts(f.body.as_deref().unwrap_or_default());
+            accesses.insert(f.name.clone(), access);
+        }
+    }
+
+    let names: Vec<String> = accesses.keys().cloned().collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for name in &names {
+            let (mut accessed, mut written) = (HashSet::new(), HashSet::new());
+            for callee in &accesses[name].callees {
+                if let Some(callee) = accesses.get(callee) {
+                    accessed.extend(callee.accessed.iter().cloned());
+                    written.extend(callee.written.iter().cloned());
+                }
+            }
+            let access = accesses.get_mut(name).unwrap();
+            let before = access.accessed.len() + access.written.len();
+            access.accessed.extend(accessed);
+            access.written.extend(written);
+            changed |= access.accessed.len() + access.written.len() != before;
+        }
+    }
+    accesses
+}
+
+/// Walks a function body in order and records the state variables written
+/// once an external call may have happened on the current path.
+struct LateWrites<'s> {
+    state: &'s HashSet<String>,
+    accesses: &'s HashMap<String, StateAccess>,
+    /// Location of the first late write of each variable.
+    writes: BTreeMap<String, Option<Loc>>,
+}
+
+impl LateWrites<'_> {
+    fn walk(&mut self, stmts: &[Stmt], seen_ext_call: &mut bool) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::If(s) => {
+                    let mut then_seen = *seen_ext_call;
+                    self.walk(&s.then_body, &mut then_seen);
+                    let mut else_seen = *seen_ext_call;
+                    self.walk(s.else_body.as_deref().unwrap_or_default(), &mut else_seen);
+                    *seen_ext_call = then_seen || else_seen;
+                }
+                Stmt::While(s) => self.walk(&s.body, seen_ext_call),
+                Stmt::For(s) => self.walk(&s.body, seen_ext_call),
+                Stmt::Block(inner) => self.walk(inner, seen_ext_call),
+                Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+                    *seen_ext_call = true;
+                    self.walk(&tc.body, seen_ext_call);
+                    for clause in &tc.catch_clauses {
+                        self.walk(&clause.body, seen_ext_call);
+                    }
+                }
+                _ => {
+                    // The call in `x = target.call(..)` happens before the write.
+                    *seen_ext_call |= stmt_has_external_call(stmt);
+                    if *seen_ext_call {
+                        self.record(stmt);
+                    }
+                }
+            }
+        }
+    }
+
+    fn record(&mut self, stmt: &Stmt) {
+        let mut access = StateAccess::default();
+        access.visit_stmt(stmt);
+        let callee_writes = access
+            .callees
+            .iter()
+            .filter_map(|callee| self.accesses.get(callee))
+            .flat_map(|callee| callee.written.iter());
+        for var in access.written.iter().chain(callee_writes) {
+            if self.state.contains(var) {
+                self.writes
+                    .entry(var.clone())
+                    .or_insert_with(|| stmt.span().cloned());
+            }
+        }
+    }
+}
+
+impl ScanDetector for CrossFunctionReentrancyDetector {
+    fn id(&self) -> &'static str {
+        "cross-function-reentrancy"
+    }
+
+    fn name(&self) -> &'static str {
+        "Cross-Function Reentrancy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects state written after an external call that another public or \
+         external function reads or writes"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Reentrancy
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![841]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![107]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Update shared state before making external calls, or protect every \
+         function using that state with the same reentrancy guard (e.g., \
+         OpenZeppelin's ReentrancyGuard)."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-107",
+            "https://consensys.github.io/smart-contract-best-practices/attacks/reentrancy/",
+        ]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, _module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let state: HashSet<String> = contract
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                MemberDecl::Storage(s) if !s.is_constant_storage() => Some(s.name.clone()),
+                _ => None,
+            })
+            .collect();
+        if state.is_empty() {
+            return bugs;
+        }
+
+        let accesses = state_accesses(contract);
+        let entries: Vec<&FunctionDecl> = contract
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                MemberDecl::Function(f)
+                    if (f.is_public() || f.is_external())
+                        && !f.is_constructor()
+                        && f.body.is_some() =>
+                {
+                    Some(f)
+                }
+                _ => None,
+            })
+            .collect();
+
+        for func in &entries {
+            let mut late =
+                LateWrites { state: &state, accesses: &accesses, writes: BTreeMap::new() };
+            late.walk(func.body.as_deref().unwrap_or_default(), &mut false);
+            if late.writes.is_empty() {
+                continue;
+            }
+
+            for other in &entries {
+                if other.name == func.name
+                    || (func.has_reentrancy_guard() && other.has_reentrancy_guard())
+                {
+                    continue;
+                }
+                let Some(access) = accesses.get(&other.name) else {
+                    continue;
+                };
+                let shared: Vec<(&String, &Option<Loc>)> = late
+                    .writes
+                    .iter()
+                    .filter(|(var, _)| access.accessed.contains(*var))
+                    .collect();
+                let Some((_, loc)) = shared.first() else {
+                    continue;
+                };
+                let vars: Vec<&str> = shared.iter().map(|(var, _)| var.as_str()).collect();
+                bugs.push(Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "'{}.{}' writes '{}' after an external call, which can re-enter \
+                         '{}.{}' before the update.",
+                        contract.name,
+                        func.name,
+                        vars.join("', '"),
+                        contract.name,
+                        other.name
+                    )),
+                    (*loc)
+                        .clone()
+                        .or_else(|| func.span.clone())
+                        .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                ));
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::var;
+    use scirs::sir::*;
+
+    /// `recipient.call("")`
+    fn call_stmt() -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                    base: Box::new(var("recipient")),
+                    field: "call".to_string(),
+                    ty: Type::None,
+                    span: None,
+                })),
+                args: CallArgs::Positional(vec![]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    /// `lhs = rhs`
+    fn assign(lhs: &str, rhs: Expr) -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var(lhs), rhs, span: None })
+    }
+
+    /// `name()`
+    fn internal_call(name: &str) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(var(name)),
+                args: CallArgs::Positional(vec![]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    fn function(name: &str, visibility: &str, body: Vec<Stmt>) -> FunctionDecl {
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String(visibility.into())));
+        func
+    }
+
+    fn check(functions: Vec<FunctionDecl>) -> Vec<Bug> {
+        let storage = StorageDecl::new("balance".to_string(), Type::I256, None, None);
+        let mut members = vec![MemberDecl::Storage(storage)];
+        members.extend(functions.into_iter().map(MemberDecl::Function));
+        let contract = ContractDecl::new("Bank".to_string(), members, None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        CrossFunctionReentrancyDetector::new().check_contract(&contract, &module)
+    }
+
+    fn withdraw() -> FunctionDecl {
+        function("withdraw", "external", vec![call_stmt(), assign("balance", var("zero"))])
+    }
+
+    #[test]
+    fn test_cross_function_reentrancy_detector() {
+        let detector = CrossFunctionReentrancyDetector::new();
+        assert_eq!(detector.id(), "cross-function-reentrancy");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_reentered_function_is_reported() {
+        let transfer = function("transfer", "public", vec![assign("to", var("balance"))]);
+        let bugs = check(vec![withdraw(), transfer]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "'Bank.withdraw' writes 'balance' after an external call, which can re-enter \
+                 'Bank.transfer' before the update."
+            )
+        );
+    }
+
+    #[test]
+    fn test_accesses_through_internal_functions_count() {
+        let helper = function("_credit", "internal", vec![assign("balance", var("amount"))]);
+        let deposit = function("deposit", "external", vec![internal_call("_credit")]);
+        assert_eq!(check(vec![withdraw(), helper, deposit]).len(), 1);
+
+        // Writing before the call leaves nothing to re-enter for.
+        let safe =
+            function("withdraw", "external", vec![assign("balance", var("zero")), call_stmt()]);
+        let deposit = function("deposit", "external", vec![internal_call("_credit")]);
+        let helper = function("_credit", "internal", vec![assign("balance", var("amount"))]);
+        assert!(check(vec![safe, helper, deposit]).is_empty());
+    }
+
+    #[test]
+    fn test_shared_reentrancy_guard_is_not_reported() {
+        let guard = || Attr::sir(sir_attrs::REENTRANCY_GUARD, AttrValue::Bool(true));
+        let mut withdraw = withdraw();
+        withdraw.attrs.push(guard());
+        let mut transfer = function("transfer", "public", vec![assign("to", var("balance"))]);
+        assert_eq!(check(vec![withdraw.clone(), transfer.clone()]).len(), 1);
+        transfer.attrs.push(guard());
+        assert!(check(vec![withdraw, transfer]).is_empty());
+    }
+}
@@ -0,0 +1,415 @@
+//! Delegatecall Mutable Target Detector
+//!
+//! Detects `delegatecall` into an address held in storage that any caller
+//! can overwrite:
+//!
+//! ```solidity
+//! address public implementation;
+//!
+//! function setImplementation(address impl) external {
+//!     implementation = impl;
+//! }
+//!
+//! function forward(bytes calldata data) external {
+//!     implementation.delegatecall(data);
+//! }
+//! ```
+//!
+//! Whoever sets the target runs arbitrary code in the context of the
+//! contract, with full access to its storage and balance. A writer counts as
+//! protected if it, a modifier it uses, or an internal function it calls
+//! branches or asserts on `msg.sender`, or if it carries an `only*` or
+//! `initializer` modifier. Constant and immutable targets, and targets only
+//! admins can set, are left to the more general `delegatecall` detector.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::reads_sender;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl};
+use scirs::sir::dialect::{DialectMemberDecl, EvmFunctionExt, EvmStorageExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssertStmt, CallExpr, ContractDecl, Decl, DialectExpr, Expr, IfStmt, LocalVarStmt, MemberDecl,
+    Module, Stmt,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Scan detector for delegatecall targets that non-admin callers can change.
+#[derive(Debug, Default)]
+pub struct DelegatecallMutableTargetDetector;
+
+impl DelegatecallMutableTargetDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Storage variables a body delegatecalls into, with the location of each
+/// call. Targets read through a local copy (`address impl = _impl;`) or an
+/// `address(..)` cast are resolved to the storage variable.
+struct TargetFinder<'s> {
+    storage: &'s HashSet<String>,
+    aliases: HashMap<String, String>,
+    targets: Vec<(String, Loc)>,
+}
+
+impl TargetFinder<'_> {
+    fn storage_var(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Var(v) if self.storage.contains(&v.name) => Some(v.name.clone()),
+            Expr::Var(v) => self.aliases.get(&v.name).cloned(),
+            Expr::TypeCast(e) => self.storage_var(&e.expr),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Visit<'a> for TargetFinder<'_> {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        if let [Some(decl)] = stmt.vars.as_slice()
+            && let Some(var) = stmt.init.as_ref().and_then(|e| self.storage_var(e))
+        {
+            self.aliases.insert(decl.name.clone(), var);
+        }
+        visit::default::visit_local_var_stmt(self, stmt);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::FieldAccess(fa) = &*expr.callee
+            && fa.field == "delegatecall"
+            && let Some(var) = self.storage_var(&fa.base)
+        {
+            let loc = expr.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0));
+            self.targets.push((var, loc));
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        if let DialectExpr::Evm(EvmExpr::Delegatecall(call)) = expr
+            && let Some(var) = self.storage_var(&call.target)
+        {
+            self.targets.push((var, call.loc.clone()));
+        }
+    }
+}
+
+/// What a function or modifier body does directly.
+#[derive(Default)]
+struct Routine {
+    /// Externally callable, and neither a constructor nor `fallback`/`receive`.
+    entry_point: bool,
+    /// Branches or asserts on `msg.sender`, or carries an access-control
+    /// modifier.
+    guarded: bool,
+    /// Delegatecall targets the body assigns.
+    writes: HashSet<String>,
+    /// Names of the internal functions and modifiers it calls.
+    callees: HashSet<String>,
+}
+
+impl<'a> Visit<'a> for Routine {
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt) {
+        self.guarded |= reads_sender(&stmt.cond);
+        visit::default::visit_if_stmt(self, stmt);
+    }
+
+    fn visit_assert_stmt(&mut self, stmt: &'a AssertStmt) {
+        self.guarded |= reads_sender(&stmt.cond);
+        visit::default::visit_assert_stmt(self, stmt);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::Var(v) = &*expr.callee {
+            self.callees.insert(v.name.clone());
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+}
+
+impl Routine {
+    fn of(body: &[Stmt], targets: &[String]) -> Self {
+        let mut routine = Self::default();
+        routine.visit_stmts(body);
+        routine.writes = targets
+            .iter()
+            .filter(|t| ContractDecl::has_storage_write(body, std::slice::from_ref(t)))
+            .cloned()
+            .collect();
+        routine
+    }
+}
+
+/// Names of the routines in `routines` satisfying `seed`, directly or
+/// through a routine they call.
+fn close(routines: &HashMap<String, Routine>, seed: impl Fn(&Routine) -> bool) -> HashSet<String> {
+    let mut set: HashSet<String> = routines
+        .iter()
+        .filter(|(_, r)| seed(r))
+        .map(|(name, _)| name.clone())
+        .collect();
+    loop {
+        let reached: Vec<String> = routines
+            .iter()
+            .filter(|(name, r)| !set.contains(*name) && r.callees.iter().any(|c| set.contains(c)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if reached.is_empty() {
+            return set;
+        }
+        set.extend(reached);
+    }
+}
+
+/// `contract` followed by its ancestors declared in `module`.
+fn lineage<'a>(contract: &'a ContractDecl, module: &'a Module) -> Vec<&'a ContractDecl> {
+    let mut lineage = vec![];
+    let mut pending = vec![contract];
+    let mut seen = HashSet::new();
+    while let Some(contract) = pending.pop() {
+        if !seen.insert(contract.name.clone()) {
+            continue;
+        }
+        lineage.push(contract);
+        pending.extend(module.decls.iter().filter_map(|d| match d {
+            Decl::Contract(c) if contract.parents.contains(&c.name) => Some(c),
+            _ => None,
+        }));
+    }
+    lineage
+}
+
+impl ScanDetector for DelegatecallMutableTargetDetector {
+    fn id(&self) -> &'static str {
+        "delegatecall-mutable-target"
+    }
+
+    fn name(&self) -> &'static str {
+        "Delegatecall to Mutable Target"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects delegatecall into a storage address that a function without \
+         access control can change"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![829]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![112]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Restrict every function that sets a delegatecall target to trusted \
+         callers, or make the target `immutable`."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://swcregistry.io/docs/SWC-112"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["proxy"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let lineage = lineage(contract, module);
+        let storage: HashSet<String> = lineage
+            .iter()
+            .flat_map(|c| &c.members)
+            .filter_map(|m| match m {
+                MemberDecl::Storage(s) if !s.is_constant_storage() => Some(s.name.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut calls = vec![];
+        for member in &contract.members {
+            let MemberDecl::Function(func) = member else {
+                continue;
+            };
+            let mut finder =
+                TargetFinder { storage: &storage, aliases: HashMap::new(), targets: vec![] };
+            finder.visit_stmts(func.body.as_deref().unwrap_or_default());
+            calls.extend(
+                finder
+                    .targets
+                    .into_iter()
+                    .map(|(var, loc)| (func, var, loc)),
+            );
+        }
+        if calls.is_empty() {
+            return bugs;
+        }
+
+        let targets: Vec<String> = calls.iter().map(|(_, var, _)| var.clone()).collect();
+        let mut routines: HashMap<String, Routine> = HashMap::new();
+        for c in &lineage {
+            for member in &c.members {
+                match member {
+                    MemberDecl::Function(f) => {
+                        let mut routine =
+                            Routine::of(f.body.as_deref().unwrap_or_default(), &targets);
+                        routine.entry_point = (f.is_public() || f.is_external())
+                            && !f.is_constructor()
+                            && !matches!(f.name.as_str(), "fallback" | "receive");
+                        routine.guarded |= f.modifier_invocs.iter().any(|m| {
+                            m.name.starts_with("only")
+                                || matches!(m.name.as_str(), "initializer" | "reinitializer")
+                        });
+                        routine
+                            .callees
+                            .extend(f.modifier_invocs.iter().map(|m| m.name.clone()));
+                        routines.entry(f.name.clone()).or_insert(routine);
+                    }
+                    MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(m))) => {
+                        routines
+                            .entry(m.name.clone())
+                            .or_insert(Routine::of(&m.body, &targets));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let guarded = close(&routines, |r| r.guarded);
+        for (func, var, loc) in calls {
+            let writers = close(&routines, |r| r.writes.contains(&var));
+            let open: BTreeSet<&String> = writers
+                .iter()
+                .filter(|name| routines[*name].entry_point && !guarded.contains(*name))
+                .collect();
+            let Some(setter) = open.first() else {
+                continue;
+            };
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' delegatecalls into '{}', which any caller can change through '{}'.",
+                    contract.name, func.name, var, setter
+                )),
+                loc,
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::dialect::DialectType;
+    use scirs::sir::dialect::evm::{EvmDelegatecall, EvmType};
+    use scirs::sir::*;
+
+    fn address() -> Type {
+        Type::Dialect(DialectType::Evm(EvmType::Address))
+    }
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), address(), None))
+    }
+
+    fn external(name: &str, body: Vec<Stmt>, modifiers: &[&str]) -> MemberDecl {
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String("external".to_string())));
+        func.modifier_invocs = modifiers
+            .iter()
+            .map(|m| ModifierInvoc { name: m.to_string(), args: vec![], span: None })
+            .collect();
+        MemberDecl::Function(func)
+    }
+
+    /// `Proxy` with storage `implementation`, a `forward()` that delegates
+    /// to it and a `setImplementation(impl)` carrying `modifiers`.
+    fn proxy(modifiers: &[&str]) -> Vec<Bug> {
+        let storage = MemberDecl::Storage(StorageDecl::new(
+            "implementation".to_string(),
+            address(),
+            None,
+            None,
+        ));
+        let set =
+            Stmt::Assign(AssignStmt { lhs: var("implementation"), rhs: var("impl"), span: None });
+        let call = Expr::Dialect(DialectExpr::Evm(EvmExpr::Delegatecall(EvmDelegatecall {
+            target: Box::new(var("implementation")),
+            data: Box::new(var("data")),
+            loc: Loc::new(12, 9, 12, 40),
+        })));
+        let forward = Stmt::Expr(ExprStmt { expr: call, span: None });
+        let contract = ContractDecl::new(
+            "Proxy".to_string(),
+            vec![
+                storage,
+                external("setImplementation", vec![set], modifiers),
+                external("forward", vec![forward], &[]),
+            ],
+            None,
+        );
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        DelegatecallMutableTargetDetector::new().check_contract(&contract, &module)
+    }
+
+    #[test]
+    fn test_delegatecall_mutable_target_detector() {
+        let detector = DelegatecallMutableTargetDetector::new();
+        assert_eq!(detector.id(), "delegatecall-mutable-target");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_publicly_settable_target_is_flagged() {
+        let bugs = proxy(&[]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].loc.start_line, 12);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("setImplementation")
+        );
+    }
+
+    #[test]
+    fn test_admin_settable_target_is_not_flagged() {
+        assert!(proxy(&["onlyOwner"]).is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+//! Missing ERC165 Detector
+//!
+//! Detects contracts that inherit an introspectable interface (ERC721,
+//! ERC1155, ERC2981, ...) but do not advertise it through ERC165
+//! `supportsInterface`. Marketplaces and wallets query `supportsInterface`
+//! before interacting with a token, so a missing or incomplete override makes
+//! the contract invisible to them.
+//!
+//! Ancestors are resolved through the `parents` of the contracts in the same
+//! module. A parent that cannot be resolved there and is not a known
+//! interface may provide `supportsInterface` itself, so such contracts are
+//! skipped.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::{ContractDecl, Decl, FunctionDecl, MemberDecl, Module};
+use std::collections::HashSet;
+
+/// Interfaces whose support is expected to be reported through ERC165.
+const INTROSPECTABLE_INTERFACES: &[&str] = &[
+    "IERC165",
+    "IERC721",
+    "IERC721Metadata",
+    "IERC721Enumerable",
+    "IERC1155",
+    "IERC1155MetadataURI",
+    "IERC2981",
+];
+
+/// Well-known base implementations that already override
+/// `supportsInterface` for their interfaces.
+const ERC165_IMPLEMENTATIONS: &[&str] = &["ERC165", "ERC721", "ERC1155", "ERC2981"];
+
+/// Scan detector for missing or incomplete `supportsInterface` overrides.
+#[derive(Debug, Default)]
+pub struct Erc165MissingDetector;
+
+impl Erc165MissingDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Ancestry of a contract, as far as it can be resolved in its module.
+struct Ancestry<'a> {
+    /// Resolved contracts, starting with the contract itself.
+    contracts: Vec<&'a ContractDecl>,
+    /// Introspectable interfaces inherited anywhere in the hierarchy.
+    interfaces: Vec<String>,
+    /// Whether a parent is a well-known ERC165 implementation.
+    has_implementation: bool,
+    /// Whether some parent could not be resolved.
+    has_unknown: bool,
+}
+
+fn resolve_ancestry<'a>(contract: &'a ContractDecl, module: &'a Module) -> Ancestry<'a> {
+    let mut ancestry = Ancestry {
+        contracts: vec![],
+        interfaces: vec![],
+        has_implementation: false,
+        has_unknown: false,
+    };
+    let mut visited = HashSet::new();
+    let mut worklist = vec![contract];
+    while let Some(current) = worklist.pop() {
+        if !visited.insert(current.name.as_str()) {
+            continue;
+        }
+        ancestry.contracts.push(current);
+        for parent in &current.parents {
+            let name = parent.as_str();
+            if INTROSPECTABLE_INTERFACES.contains(&name) {
+                if !ancestry.interfaces.iter().any(|i| i == name) {
+                    ancestry.interfaces.push(parent.clone());
+                }
+                continue;
+            }
+            match find_contract(module, name) {
+                Some(base) => worklist.push(base),
+                None if ERC165_IMPLEMENTATIONS.contains(&name) => {
+                    ancestry.has_implementation = true
+                }
+                None => ancestry.has_unknown = true,
+            }
+        }
+    }
+    ancestry
+}
+
+fn find_contract<'a>(module: &'a Module, name: &str) -> Option<&'a ContractDecl> {
+    module.decls.iter().find_map(|d| match d {
+        Decl::Contract(c) if c.name == name => Some(c),
+        _ => None,
+    })
+}
+
+fn functions(contract: &ContractDecl) -> impl Iterator<Item = &FunctionDecl> {
+    contract.members.iter().filter_map(|m| match m {
+        MemberDecl::Function(f) => Some(f),
+        _ => None,
+    })
+}
+
+/// Returns the implemented `supportsInterface` of a contract, if any.
+fn supports_interface(contract: &ContractDecl) -> Option<&FunctionDecl> {
+    functions(contract).find(|f| f.name == "supportsInterface" && f.body.is_some())
+}
+
+/// Returns `true` if a `supportsInterface` body mentions one of the
+/// interfaces (e.g. `type(IERC721).interfaceId`) or defers to a base
+/// implementation via `super.supportsInterface`.
+fn advertises(func: &FunctionDecl, interfaces: &[String]) -> bool {
+    let body: String = func.body.iter().flatten().map(|s| s.to_string()).collect();
+    body.contains("supportsInterface") || interfaces.iter().any(|i| body.contains(i.as_str()))
+}
+
+/// Returns `true` if the contract only declares functions, i.e. it is an
+/// interface or a fully abstract contract.
+fn is_interface_like(contract: &ContractDecl) -> bool {
+    functions(contract).all(|f| f.body.is_none())
+}
+
+impl ScanDetector for Erc165MissingDetector {
+    fn id(&self) -> &'static str {
+        "erc165-missing"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing ERC165 Support"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects contracts implementing introspectable interfaces such as \
+         ERC721 or ERC1155 without a `supportsInterface` override that \
+         advertises them"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1068]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Override `supportsInterface(bytes4)` to return true for \
+         `type(I).interfaceId` of every implemented interface, and fall back \
+         to `super.supportsInterface(interfaceId)`."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://eips.ethereum.org/EIPS/eip-165",
+            "https://docs.openzeppelin.com/contracts/5.x/api/utils#ERC165",
+        ]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if INTROSPECTABLE_INTERFACES.contains(&contract.name.as_str())
+            || is_interface_like(contract)
+        {
+            return bugs;
+        }
+
+        let ancestry = resolve_ancestry(contract, module);
+        if ancestry.interfaces.is_empty() {
+            return bugs;
+        }
+
+        let claimed = ancestry.interfaces.join(", ");
+        let inherited = ancestry.contracts[1..]
+            .iter()
+            .any(|c| supports_interface(c).is_some());
+        let description = match supports_interface(contract) {
+            Some(func) if advertises(func, &ancestry.interfaces) => return bugs,
+            Some(_) => format!(
+                "Contract '{}' implements {} but its `supportsInterface` neither \
+                 advertises them nor defers to a base implementation.",
+                contract.name, claimed
+            ),
+            None if inherited || ancestry.has_implementation || ancestry.has_unknown => {
+                return bugs;
+            }
+            None => format!(
+                "Contract '{}' implements {} but does not implement \
+                 `supportsInterface`.",
+                contract.name, claimed
+            ),
+        };
+
+        bugs.push(Bug::new(
+            self.name(),
+            Some(&description),
+            contract
+                .span
+                .clone()
+                .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        ));
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::*;
+
+    fn function(name: &str, body: Option<Vec<Stmt>>) -> MemberDecl {
+        MemberDecl::Function(FunctionDecl::new(name.to_string(), vec![], vec![], body, None))
+    }
+
+    fn contract(name: &str, parents: &[&str], members: Vec<MemberDecl>) -> ContractDecl {
+        ContractDecl {
+            name: name.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            attrs: vec![],
+            members,
+            span: None,
+        }
+    }
+
+    fn check(target: ContractDecl) -> Vec<Bug> {
+        let ierc721 = contract("IERC721", &["IERC165"], vec![function("ownerOf", None)]);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(ierc721), Decl::Contract(target.clone())],
+        };
+        Erc165MissingDetector::new().check_contract(&target, &module)
+    }
+
+    #[test]
+    fn test_erc165_missing_detector() {
+        let detector = Erc165MissingDetector::new();
+        assert_eq!(detector.id(), "erc165-missing");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_erc721_without_supports_interface_is_flagged() {
+        let token = contract("Token", &["IERC721"], vec![function("ownerOf", Some(vec![]))]);
+        assert_eq!(check(token).len(), 1);
+    }
+
+    #[test]
+    fn test_erc721_with_supports_interface_is_not_flagged() {
+        let ret = Stmt::Return(ReturnStmt {
+            value: Some(Expr::Var(VarExpr::new(
+                "interfaceId__type__IERC721".to_string(),
+                Type::Bool,
+                None,
+            ))),
+            span: None,
+        });
+        let token = contract(
+            "Token",
+            &["IERC721"],
+            vec![
+                function("ownerOf", Some(vec![])),
+                function("supportsInterface", Some(vec![ret])),
+            ],
+        );
+        assert!(check(token).is_empty());
+    }
+
+    #[test]
+    fn test_contract_without_interfaces_is_not_flagged() {
+        let vault = contract("Vault", &[], vec![function("deposit", Some(vec![]))]);
+        assert!(check(vault).is_empty());
+    }
+}
@@ -301,6 +301,10 @@ impl ScanDetector for FrontRunningDetector {
         ]
     }
 
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20", "defi"]
+    }
+
     fn check_contract(&self, contract: &ContractDecl, _module: &Module) -> Vec<Bug> {
         let mut bugs = Vec::new();
         let storage_vars = contract.storage_names();
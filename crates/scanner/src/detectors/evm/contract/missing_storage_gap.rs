@@ -0,0 +1,260 @@
+//! Missing Storage Gap Detector
+//!
+//! Detects upgradeable base contracts that do not end their storage layout
+//! with a fixed-size `__gap` array. Without the gap, adding a state variable
+//! to the base in a later version shifts the storage of every derived
+//! contract and corrupts deployed proxies.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::EvmStorageExt;
+use scirs::sir::{ContractDecl, Decl, MemberDecl, Module, StorageDecl, Type};
+use std::collections::HashSet;
+
+/// Scan detector for upgradeable base contracts without a storage gap.
+#[derive(Debug, Default)]
+pub struct MissingStorageGapDetector;
+
+impl MissingStorageGapDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Modifiers that only appear in contracts deployed behind a proxy.
+const INITIALIZER_MODIFIERS: &[&str] = &["initializer", "onlyInitializing", "reinitializer"];
+
+/// Returns `true` if the contract name marks an upgrade-safe OpenZeppelin
+/// style contract.
+fn is_upgradeable_name(name: &str) -> bool {
+    name.contains("Upgradeable") || name.contains("Initializable")
+}
+
+/// Returns `true` if `contract` or one of its ancestors is upgradeable.
+fn has_upgradeable_ancestry(contract: &ContractDecl, module: &Module) -> bool {
+    let contracts: Vec<&ContractDecl> = module
+        .decls
+        .iter()
+        .filter_map(|d| match d {
+            Decl::Contract(c) => Some(c),
+            _ => None,
+        })
+        .collect();
+
+    let mut visited = HashSet::new();
+    let mut worklist = vec![contract];
+    while let Some(current) = worklist.pop() {
+        if !visited.insert(current.name.as_str()) {
+            continue;
+        }
+        if is_upgradeable_name(&current.name) || uses_initializer(current) {
+            return true;
+        }
+        for parent in &current.parents {
+            if is_upgradeable_name(parent) {
+                return true;
+            }
+            if let Some(base) = contracts.iter().find(|c| &c.name == parent) {
+                worklist.push(base);
+            }
+        }
+    }
+    false
+}
+
+/// Returns `true` if any function of the contract uses an initializer
+/// modifier.
+fn uses_initializer(contract: &ContractDecl) -> bool {
+    contract.members.iter().any(|m| match m {
+        MemberDecl::Function(f) => f
+            .modifier_invocs
+            .iter()
+            .any(|inv| INITIALIZER_MODIFIERS.contains(&inv.name.as_str())),
+        _ => false,
+    })
+}
+
+/// Returns `true` if another contract in the module inherits from `contract`.
+fn is_inherited(contract: &ContractDecl, module: &Module) -> bool {
+    module.decls.iter().any(|d| match d {
+        Decl::Contract(c) => c.parents.iter().any(|p| p == &contract.name),
+        _ => false,
+    })
+}
+
+/// Returns `true` if the storage variable is an OpenZeppelin-style gap.
+fn is_storage_gap(storage: &StorageDecl) -> bool {
+    storage.name.starts_with("__gap") && matches!(storage.ty, Type::FixedArray(..))
+}
+
+impl ScanDetector for MissingStorageGapDetector {
+    fn id(&self) -> &'static str {
+        "missing-storage-gap"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Storage Gap"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects upgradeable base contracts whose storage layout does not end \
+         with a fixed-size `__gap` array"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1321]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Reserve storage for future versions by declaring `uint256[N] private __gap;` \
+         as the last state variable of the base contract, and shrink N when new \
+         variables are added."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.openzeppelin.com/upgrades-plugins/writing-upgradeable#storage-gaps"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["proxy"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        // The gap convention only concerns upgradeable contracts used as bases.
+        if contract.name.contains("Initializable")
+            || !is_inherited(contract, module)
+            || !has_upgradeable_ancestry(contract, module)
+        {
+            return bugs;
+        }
+
+        let storage: Vec<&StorageDecl> = contract
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                MemberDecl::Storage(s) if !s.is_constant_storage() => Some(s),
+                _ => None,
+            })
+            .collect();
+
+        if let Some(last) = storage.last()
+            && !is_storage_gap(last)
+        {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "Upgradeable base contract '{}' declares {} state variable(s) \
+                     but no trailing `__gap` array.",
+                    contract.name,
+                    storage.len()
+                )),
+                contract
+                    .span
+                    .clone()
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(name: &str, parents: &[&str], storage: Vec<StorageDecl>) -> ContractDecl {
+        ContractDecl {
+            name: name.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            attrs: vec![],
+            members: storage.into_iter().map(MemberDecl::Storage).collect(),
+            span: None,
+        }
+    }
+
+    fn owner() -> StorageDecl {
+        StorageDecl::new("owner".to_string(), Type::I256, None, None)
+    }
+
+    fn gap() -> StorageDecl {
+        StorageDecl::new(
+            "__gap".to_string(),
+            Type::FixedArray(Box::new(Type::I256), 49),
+            None,
+            None,
+        )
+    }
+
+    fn check(base: ContractDecl) -> Vec<Bug> {
+        let derived = contract("Vault", &[&base.name], vec![]);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(base.clone()), Decl::Contract(derived)],
+        };
+        MissingStorageGapDetector::new().check_contract(&base, &module)
+    }
+
+    #[test]
+    fn test_missing_storage_gap_detector() {
+        let detector = MissingStorageGapDetector::new();
+        assert_eq!(detector.id(), "missing-storage-gap");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_upgradeable_base_without_gap_is_flagged() {
+        let base = contract("OwnableBase", &["Initializable"], vec![owner()]);
+        assert_eq!(check(base).len(), 1);
+    }
+
+    #[test]
+    fn test_upgradeable_base_with_gap_is_not_flagged() {
+        let base = contract("OwnableBase", &["Initializable"], vec![owner(), gap()]);
+        assert!(check(base).is_empty());
+    }
+
+    #[test]
+    fn test_non_upgradeable_base_is_not_flagged() {
+        let base = contract("OwnableBase", &[], vec![owner()]);
+        assert!(check(base).is_empty());
+    }
+}
@@ -1,17 +1,39 @@
 //! Contract-level EVM detectors
 
+pub mod ambiguous_override;
 pub mod centralization_risk;
 pub mod constant_state_var;
+pub mod could_be_view;
+pub mod cross_function_reentrancy;
 pub mod dead_code;
+pub mod delegatecall_mutable_target;
+pub mod erc165_missing;
 pub mod front_running;
 pub mod missing_access_control;
+pub mod missing_storage_gap;
+pub mod nonstandard_erc_override;
+pub mod redundant_safemath;
+pub mod tx_origin;
 pub mod uninitialized;
+pub mod unprotected_mint;
+pub mod unused_payable;
 pub mod visibility;
 
+pub use ambiguous_override::AmbiguousOverrideDetector;
 pub use centralization_risk::CentralizationRiskDetector;
 pub use constant_state_var::ConstantStateVarDetector;
+pub use could_be_view::CouldBeViewDetector;
+pub use cross_function_reentrancy::CrossFunctionReentrancyDetector;
 pub use dead_code::DeadCodeDetector;
+pub use delegatecall_mutable_target::DelegatecallMutableTargetDetector;
+pub use erc165_missing::Erc165MissingDetector;
 pub use front_running::FrontRunningDetector;
 pub use missing_access_control::MissingAccessControlDetector;
+pub use missing_storage_gap::MissingStorageGapDetector;
+pub use nonstandard_erc_override::NonstandardErcOverrideDetector;
+pub use redundant_safemath::RedundantSafemathDetector;
+pub use tx_origin::TxOriginDetector;
 pub use uninitialized::UninitializedDetector;
+pub use unprotected_mint::UnprotectedMintDetector;
+pub use unused_payable::UnusedPayableDetector;
 pub use visibility::VisibilityDetector;
@@ -0,0 +1,271 @@
+//! Non-Standard ERC Override Detector
+//!
+//! Detects functions of ERC20, ERC721 and ERC1155 contracts that implement a
+//! standard function with the wrong parameter or return types:
+//!
+//! ```solidity
+//! contract Token is IERC20 {
+//!     function transfer(address to, uint256 amount) external {  // no `bool`
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! Callers compiled against the standard interface decode the return data
+//! of such a function and revert (or, for parameters, hit a different
+//! selector), so the token cannot be used by wallets, exchanges or other
+//! contracts. The standard is recognized from the contract's ancestors. A
+//! function with the name of a standard function but a different number of
+//! parameters is an overload, not an override, and is not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::ancestor_names;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::{EvmFunctionExt, EvmTypeExt};
+use scirs::sir::{ContractDecl, FunctionDecl, MemberDecl, Module};
+
+/// A standard function: name, parameter types and return types.
+type StandardFunction = (&'static str, &'static [&'static str], &'static [&'static str]);
+
+const ERC20_FUNCTIONS: &[StandardFunction] = &[
+    ("totalSupply", &[], &["uint256"]),
+    ("balanceOf", &["address"], &["uint256"]),
+    ("transfer", &["address", "uint256"], &["bool"]),
+    ("transferFrom", &["address", "address", "uint256"], &["bool"]),
+    ("approve", &["address", "uint256"], &["bool"]),
+    ("allowance", &["address", "address"], &["uint256"]),
+    ("name", &[], &["string"]),
+    ("symbol", &[], &["string"]),
+    ("decimals", &[], &["uint8"]),
+];
+
+const ERC721_FUNCTIONS: &[StandardFunction] = &[
+    ("balanceOf", &["address"], &["uint256"]),
+    ("ownerOf", &["uint256"], &["address"]),
+    ("safeTransferFrom", &["address", "address", "uint256", "bytes"], &[]),
+    ("safeTransferFrom", &["address", "address", "uint256"], &[]),
+    ("transferFrom", &["address", "address", "uint256"], &[]),
+    ("approve", &["address", "uint256"], &[]),
+    ("setApprovalForAll", &["address", "bool"], &[]),
+    ("getApproved", &["uint256"], &["address"]),
+    ("isApprovedForAll", &["address", "address"], &["bool"]),
+];
+
+const ERC1155_FUNCTIONS: &[StandardFunction] = &[
+    ("balanceOf", &["address", "uint256"], &["uint256"]),
+    ("balanceOfBatch", &["address[]", "uint256[]"], &["uint256[]"]),
+    ("setApprovalForAll", &["address", "bool"], &[]),
+    ("isApprovedForAll", &["address", "address"], &["bool"]),
+    ("safeTransferFrom", &["address", "address", "uint256", "uint256", "bytes"], &[]),
+    ("safeBatchTransferFrom", &["address", "address", "uint256[]", "uint256[]", "bytes"], &[]),
+];
+
+/// Standards, with the base contracts and interfaces that identify them.
+const STANDARDS: &[(&str, &[&str], &[StandardFunction])] = &[
+    ("ERC20", &["IERC20", "IERC20Metadata", "ERC20"], ERC20_FUNCTIONS),
+    ("ERC721", &["IERC721", "IERC721Metadata", "ERC721"], ERC721_FUNCTIONS),
+    ("ERC1155", &["IERC1155", "ERC1155"], ERC1155_FUNCTIONS),
+];
+
+/// Scan detector for standard ERC functions implemented with non-standard
+/// types.
+#[derive(Debug, Default)]
+pub struct NonstandardErcOverrideDetector;
+
+impl NonstandardErcOverrideDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn render((name, params, returns): &StandardFunction) -> String {
+    match returns {
+        [] => format!("{name}({})", params.join(",")),
+        _ => format!("{name}({}) returns ({})", params.join(","), returns.join(",")),
+    }
+}
+
+fn render_actual(func: &FunctionDecl) -> String {
+    let params: Vec<String> = func.params.iter().map(|p| p.ty.abi_name()).collect();
+    let returns: Vec<String> = func.returns.iter().map(|t| t.abi_name()).collect();
+    match returns.as_slice() {
+        [] => format!("{}({})", func.name, params.join(",")),
+        _ => format!("{}({}) returns ({})", func.name, params.join(","), returns.join(",")),
+    }
+}
+
+impl ScanDetector for NonstandardErcOverrideDetector {
+    fn id(&self) -> &'static str {
+        "nonstandard-erc-override"
+    }
+
+    fn name(&self) -> &'static str {
+        "Non-Standard ERC Override"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects ERC20/ERC721/ERC1155 functions implemented with parameter or \
+         return types that differ from the standard"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![628]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Declare the function with exactly the parameter and return types of \
+         the standard."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://eips.ethereum.org/EIPS/eip-20",
+            "https://eips.ethereum.org/EIPS/eip-721",
+            "https://eips.ethereum.org/EIPS/eip-1155",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let ancestors = ancestor_names(contract, module);
+        let standards: Vec<(&str, &[StandardFunction])> = STANDARDS
+            .iter()
+            .filter(|(_, bases, _)| bases.iter().any(|b| ancestors.contains(*b)))
+            .map(|(name, _, functions)| (*name, *functions))
+            .collect();
+        if standards.is_empty() {
+            return bugs;
+        }
+
+        for member in &contract.members {
+            let MemberDecl::Function(func) = member else {
+                continue;
+            };
+            if !(func.is_public() || func.is_external()) {
+                continue;
+            }
+            let params: Vec<String> = func.params.iter().map(|p| p.ty.abi_name()).collect();
+            let returns: Vec<String> = func.returns.iter().map(|t| t.abi_name()).collect();
+            let candidates: Vec<(&str, &StandardFunction)> = standards
+                .iter()
+                .flat_map(|(standard, functions)| functions.iter().map(move |f| (*standard, f)))
+                .filter(|(_, (name, ps, _))| *name == func.name && ps.len() == params.len())
+                .collect();
+            let matches = |(_, (_, ps, rs)): &(&str, &StandardFunction)| {
+                ps.iter().eq(params.iter()) && rs.iter().eq(returns.iter())
+            };
+            let Some((standard, expected)) = candidates.first() else {
+                continue;
+            };
+            if candidates.iter().any(matches) {
+                continue;
+            }
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' is declared as '{}', but {} specifies '{}'.",
+                    contract.name,
+                    func.name,
+                    render_actual(func),
+                    standard,
+                    render(expected)
+                )),
+                func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::dialect::DialectType;
+    use scirs::sir::dialect::evm::EvmType;
+    use scirs::sir::*;
+
+    /// `Token is IERC20` with `transfer(address,uint256) external returns
+    /// (<returns>)`.
+    fn check(returns: Vec<Type>) -> Vec<Bug> {
+        let params = vec![
+            Param::new("to".to_string(), Type::Dialect(DialectType::Evm(EvmType::Address))),
+            Param::new("amount".to_string(), Type::I256),
+        ];
+        let mut transfer =
+            FunctionDecl::new("transfer".to_string(), params, returns, Some(vec![]), None);
+        transfer
+            .attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String("external".to_string())));
+        let mut token =
+            ContractDecl::new("Token".to_string(), vec![MemberDecl::Function(transfer)], None);
+        token.parents = vec!["IERC20".to_string()];
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        NonstandardErcOverrideDetector::new().check_contract(&token, &module)
+    }
+
+    #[test]
+    fn test_nonstandard_erc_override_detector() {
+        let detector = NonstandardErcOverrideDetector::new();
+        assert_eq!(detector.id(), "nonstandard-erc-override");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_transfer_without_bool_return_is_flagged() {
+        let bugs = check(vec![]);
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("transfer(address,uint256) returns (bool)")
+        );
+    }
+
+    #[test]
+    fn test_standard_transfer_is_not_flagged() {
+        assert!(check(vec![Type::Bool]).is_empty());
+    }
+}
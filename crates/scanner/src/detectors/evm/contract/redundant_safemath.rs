@@ -0,0 +1,251 @@
+//! Redundant SafeMath Detector
+//!
+//! Detects SafeMath calls in code compiled with Solidity 0.8 or later:
+//!
+//! ```solidity
+//! pragma solidity ^0.8.0;
+//!
+//! contract Vault {
+//!     using SafeMath for uint256;
+//!
+//!     function deposit(uint256 amount) external {
+//!         total = total.add(amount);  // `total + amount` reverts as well
+//!     }
+//! }
+//! ```
+//!
+//! Since 0.8 the compiler checks arithmetic for overflow itself, so the
+//! library only adds a function call per operation. Calls are recognized as
+//! `x.add(y)` (and `sub`, `mul`, `div`, `mod`) on an integer when the
+//! contract or its file is `using` a SafeMath library, and as
+//! `SafeMath.add(x, y)`. Files whose pragma admits a compiler older than 0.8
+//! are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmModuleExt;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{CallExpr, ContractDecl, Decl, Expr, MemberDecl, Module, Type};
+
+/// First compiler version with checked arithmetic.
+const CHECKED_ARITHMETIC_VERSION: (u64, u64, u64) = (0, 8, 0);
+
+/// SafeMath functions with a built-in checked operator.
+const SAFE_FUNCTIONS: &[&str] = &["add", "sub", "mul", "div", "mod"];
+
+/// Scan detector for SafeMath calls under checked arithmetic.
+#[derive(Debug, Default)]
+pub struct RedundantSafemathDetector;
+
+impl RedundantSafemathDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Whether `name` is a SafeMath library, e.g. `SafeMath`,
+/// `SafeMathUpgradeable` or `SignedSafeMath`, possibly renamed with an index
+/// suffix.
+fn is_safemath(name: &str) -> bool {
+    name.contains("SafeMath")
+}
+
+/// Whether `contract` or its file attaches a SafeMath library to a type.
+fn uses_safemath(contract: &ContractDecl, module: &Module) -> bool {
+    let global = module.decls.iter().filter_map(|decl| match decl {
+        Decl::Contract(c) if c.name == "__global__" => Some(c),
+        _ => None,
+    });
+    std::iter::once(contract)
+        .chain(global)
+        .flat_map(|c| &c.members)
+        .any(|member| matches!(member, MemberDecl::UsingFor(u) if is_safemath(&u.library)))
+        || contract.parents.iter().any(|p| is_safemath(p))
+}
+
+/// Collects SafeMath calls.
+struct SafemathFinder {
+    using_safemath: bool,
+    calls: Vec<(String, Option<Loc>)>,
+}
+
+impl<'a> Visit<'a> for SafemathFinder {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::FieldAccess(fa) = &*expr.callee
+            && SAFE_FUNCTIONS.contains(&fa.field.as_str())
+        {
+            let library_call = match &*fa.base {
+                Expr::Var(v) => is_safemath(&v.name),
+                base => matches!(base.typ(), Type::TypeRef(name) if is_safemath(&name)),
+            };
+            if library_call || (self.using_safemath && fa.base.typ().is_integer()) {
+                self.calls.push((fa.field.clone(), expr.span.clone()));
+            }
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+}
+
+impl ScanDetector for RedundantSafemathDetector {
+    fn id(&self) -> &'static str {
+        "redundant-safemath"
+    }
+
+    fn name(&self) -> &'static str {
+        "Redundant SafeMath"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects SafeMath calls in contracts compiled with Solidity 0.8+, \
+         where arithmetic is checked by default"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Optimization
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1164]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Use the built-in arithmetic operators, which revert on overflow \
+         since Solidity 0.8, and remove the SafeMath library."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.soliditylang.org/en/latest/080-breaking-changes.html"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if module
+            .solidity_min_version()
+            .is_none_or(|v| v < CHECKED_ARITHMETIC_VERSION)
+        {
+            return bugs;
+        }
+
+        let mut finder =
+            SafemathFinder { using_safemath: uses_safemath(contract, module), calls: vec![] };
+        for member in &contract.members {
+            if let MemberDecl::Function(func) = member {
+                finder.visit_function_decl(func);
+            }
+        }
+
+        for (function, loc) in finder.calls {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}' calls SafeMath '{}' although Solidity 0.8+ checks \
+                     arithmetic by default.",
+                    contract.name, function
+                )),
+                loc.or_else(|| contract.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::*;
+
+    /// A contract `using SafeMath for uint256` that computes `a.add(b)`,
+    /// under `pragma solidity <pragma>`.
+    fn check(pragma: &str) -> Vec<Bug> {
+        let add = Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(typed_var("a", Type::I256)),
+                field: "add".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![typed_var("b", Type::I256)]),
+            ty: Type::I256,
+            span: None,
+        });
+        let body = vec![Stmt::Return(ReturnStmt { value: Some(add), span: None })];
+        let params = vec![
+            Param::new("a".to_string(), Type::I256),
+            Param::new("b".to_string(), Type::I256),
+        ];
+        let func =
+            FunctionDecl::new("sum".to_string(), params, vec![Type::I256], Some(body), None);
+        let using = UsingForDecl {
+            library: "SafeMath".to_string(),
+            target_type: Some(Type::I256),
+            span: None,
+        };
+        let contract = ContractDecl::new(
+            "Vault".to_string(),
+            vec![MemberDecl::UsingFor(using), MemberDecl::Function(func)],
+            None,
+        );
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![Attr::sir(
+                sir_attrs::PRAGMA_SOLIDITY,
+                AttrValue::String(pragma.to_string()),
+            )],
+            decls: vec![],
+        };
+        RedundantSafemathDetector::new().check_contract(&contract, &module)
+    }
+
+    #[test]
+    fn test_redundant_safemath_detector() {
+        let detector = RedundantSafemathDetector::new();
+        assert_eq!(detector.id(), "redundant-safemath");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_safemath_call_under_0_8_is_flagged() {
+        assert_eq!(check("^0.8.0").len(), 1);
+    }
+
+    #[test]
+    fn test_safemath_call_before_0_8_is_not_flagged() {
+        assert!(check("^0.7.6").is_empty());
+        assert!(check(">=0.6.0 <0.9.0").is_empty());
+    }
+}
@@ -0,0 +1,481 @@
+//! tx.origin Detector
+//!
+//! Detects reads of `tx.origin`: in comparisons, which name the operand
+//! `tx.origin` is compared with, assignments, call and modifier arguments,
+//! and events. The severity of a finding depends on the use:
+//!
+//! - High when `tx.origin` appears in a condition, such as a `require`, of a
+//!   function that writes storage or makes an external call, or of a modifier
+//!   applied to such a function;
+//! - Low when it is only emitted in an event;
+//! - Medium otherwise.
+//!
+//! `msg.sender == tx.origin` guards are reported by the `eoa-only-check`
+//! detector instead, unless the `allow_eoa_check` option is `false`.
+
+use crate::detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
+use crate::detectors::evm::utils::{is_eoa_check, stmt_has_external_call, unwrap_casts};
+use crate::pattern::{Pattern, PatternBuilder as P};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl, EvmStmt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssertStmt, BinOp, BinOpExpr, ContractDecl, DialectExpr, DialectMemberDecl, DialectStmt, Expr,
+    FunctionDecl, IfStmt, MemberDecl, Module, Stmt, WhileStmt,
+};
+
+/// Scan detector for tx.origin usage.
+#[derive(Debug)]
+pub struct TxOriginDetector {
+    /// Whether `msg.sender == tx.origin` checks are left out.
+    allow_eoa_check: bool,
+}
+
+impl TxOriginDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `body`, the body of the function or modifier `owner` of
+    /// `contract`. `gates_effect` tells whether the conditions of the body
+    /// guard a storage write or an external call.
+    fn check_body(
+        &self,
+        body: &[Stmt],
+        args: &[&Expr],
+        contract: &ContractDecl,
+        owner: &str,
+        gates_effect: bool,
+    ) -> Vec<Bug> {
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: vec![],
+            contract_name: contract.name.clone(),
+            func_name: owner.to_string(),
+            comparison: origin_comparison(),
+            gates_effect,
+            in_condition: false,
+            in_event: false,
+        };
+        for arg in args {
+            visitor.visit_expr(arg);
+        }
+        visitor.visit_stmts(body);
+        visitor.bugs
+    }
+}
+
+impl Default for TxOriginDetector {
+    fn default() -> Self {
+        Self { allow_eoa_check: true }
+    }
+}
+
+/// `tx.origin == other` or `other != tx.origin`, capturing `origin` and
+/// `other`.
+fn origin_comparison() -> Pattern {
+    let ops = [BinOp::Eq, BinOp::Ne];
+    let origin = || P::capture("origin", P::evm(|e| matches!(e, EvmExpr::TxOrigin(_))));
+    let other = || P::capture("other", P::any());
+    P::or(vec![
+        P::binop(&ops, origin(), other()),
+        P::binop(&ops, other(), origin()),
+    ])
+}
+
+/// Returns `true` if `func` writes a storage variable of `contract` or
+/// makes an external call.
+fn has_effect(func: &FunctionDecl, contract: &ContractDecl) -> bool {
+    func.body.as_deref().is_some_and(|body| {
+        ContractDecl::has_storage_write(body, &contract.storage_names())
+            || body.iter().any(stmt_has_external_call)
+    })
+}
+
+/// Collects the reads of `tx.origin` in a function or modifier body.
+struct Visitor<'b> {
+    detector: &'b TxOriginDetector,
+    bugs: Vec<Bug>,
+    contract_name: String,
+    func_name: String,
+    comparison: Pattern,
+    /// Whether the conditions of the body guard a state change.
+    gates_effect: bool,
+    in_condition: bool,
+    in_event: bool,
+}
+
+impl Visitor<'_> {
+    fn report(&mut self, msg: &str, loc: Loc) {
+        let risk_level = if self.in_event {
+            RiskLevel::Low
+        } else if self.in_condition && self.gates_effect {
+            RiskLevel::High
+        } else {
+            RiskLevel::Medium
+        };
+        self.bugs.push(Bug::new(
+            self.detector.name(),
+            Some(&format!(
+                "{} in '{}.{}'. Consider using msg.sender instead.",
+                msg, self.contract_name, self.func_name
+            )),
+            loc,
+            self.detector.bug_kind(),
+            self.detector.bug_category(),
+            risk_level,
+            self.detector.cwe_ids(),
+            self.detector.swc_ids(),
+            Some(self.detector.recommendation()),
+        ));
+    }
+
+    fn is_skipped(&self, expr: &BinOpExpr) -> bool {
+        self.detector.allow_eoa_check && is_eoa_check(expr)
+    }
+
+    fn visit_condition(&mut self, cond: &Expr) {
+        let outer = std::mem::replace(&mut self.in_condition, true);
+        self.visit_expr(cond);
+        self.in_condition = outer;
+    }
+}
+
+impl<'a> Visit<'a> for Visitor<'_> {
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt) {
+        self.visit_condition(&stmt.cond);
+        self.visit_stmts(&stmt.then_body);
+        if let Some(else_body) = &stmt.else_body {
+            self.visit_stmts(else_body);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &'a WhileStmt) {
+        self.visit_condition(&stmt.cond);
+        self.visit_stmts(&stmt.body);
+    }
+
+    fn visit_assert_stmt(&mut self, stmt: &'a AssertStmt) {
+        self.visit_condition(&stmt.cond);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Expr::BinOp(b) = expr
+            && !self.is_skipped(b)
+            && let Some(m) = self.comparison.matches(expr)
+            && let Some(Expr::Dialect(DialectExpr::Evm(EvmExpr::TxOrigin(origin)))) =
+                m.capture_expr("origin").map(unwrap_casts)
+            && let Some(other) = m.capture_expr("other")
+        {
+            let operand = match m.capture_ident("other") {
+                Some(ident) => ident.to_string(),
+                None => other.to_string(),
+            };
+            let msg = format!("tx.origin compared with '{operand}'");
+            self.report(&msg, origin.loc.clone());
+            self.visit_expr(other);
+            return;
+        }
+        visit::default::visit_expr(self, expr);
+    }
+
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        if !self.is_skipped(expr) {
+            visit::default::visit_binop_expr(self, expr);
+        }
+    }
+
+    fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+        if let DialectExpr::Evm(EvmExpr::TxOrigin(e)) = d {
+            let msg = if self.in_event {
+                "tx.origin logged"
+            } else {
+                "tx.origin used"
+            };
+            self.report(msg, e.loc.clone());
+        }
+    }
+
+    fn visit_dialect_stmt(&mut self, stmt: &'a DialectStmt) {
+        match stmt {
+            DialectStmt::Evm(EvmStmt::EmitEvent(emit)) => {
+                self.in_event = true;
+                emit.args.iter().for_each(|arg| self.visit_expr(arg));
+                self.in_event = false;
+            }
+            DialectStmt::Evm(EvmStmt::TryCatch(tc)) => {
+                self.visit_expr(&tc.guarded_expr);
+                self.visit_stmts(&tc.body);
+                for clause in &tc.catch_clauses {
+                    self.visit_stmts(&clause.body);
+                }
+            }
+            DialectStmt::Evm(EvmStmt::Selfdestruct(s)) => self.visit_expr(&s.recipient),
+            _ => {}
+        }
+    }
+}
+
+impl ScanDetector for TxOriginDetector {
+    fn id(&self) -> &'static str {
+        "tx-origin"
+    }
+
+    fn name(&self) -> &'static str {
+        "Dangerous use of tx.origin"
+    }
+
+    fn description(&self) -> &'static str {
+        "Using tx.origin for authentication is vulnerable to phishing attacks."
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![345]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![115]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Replace `tx.origin` with `msg.sender` for authentication. `tx.origin` \
+         returns the original external account, making the contract vulnerable \
+         to phishing attacks where a malicious contract relays the call."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-115",
+            "https://consensys.github.io/smart-contract-best-practices/development-recommendations/solidity-specific/tx-origin/",
+        ]
+    }
+
+    fn configure(&mut self, params: &DetectorParams) {
+        if let Some(allow) = params.get("allow_eoa_check").and_then(|v| v.parse().ok()) {
+            self.allow_eoa_check = allow;
+        }
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        vec!["allow_eoa_check"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = vec![];
+        for member in &contract.members {
+            match member {
+                MemberDecl::Function(func) => {
+                    bugs.extend(self.check_function(func, contract, module));
+                }
+                MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(m))) => {
+                    let gates_effect = contract.members.iter().any(|member| {
+                        matches!(member, MemberDecl::Function(func)
+                            if func.modifier_invocs.iter().any(|inv| inv.name == m.name)
+                                && has_effect(func, contract))
+                    });
+                    bugs.extend(self.check_body(&m.body, &[], contract, &m.name, gates_effect));
+                }
+                _ => {}
+            }
+        }
+        bugs
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let args: Vec<&Expr> = func
+            .modifier_invocs
+            .iter()
+            .flat_map(|inv| &inv.args)
+            .collect();
+        let body = func.body.as_deref().unwrap_or_default();
+        self.check_body(body, &args, contract, &func.name, has_effect(func, contract))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::var;
+    use scirs::sir::dialect::evm::{EvmEmitEvent, EvmModifierDef, EvmMsgSender, EvmTxOrigin};
+    use scirs::sir::*;
+
+    fn evm(e: EvmExpr) -> Expr {
+        Expr::Dialect(DialectExpr::Evm(e))
+    }
+
+    fn origin(line: usize) -> Expr {
+        evm(EvmExpr::TxOrigin(EvmTxOrigin { loc: Loc::new(line, 9, line, 18) }))
+    }
+
+    fn eq(lhs: Expr, rhs: Expr) -> Expr {
+        Expr::BinOp(BinOpExpr {
+            op: BinOp::Eq,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        })
+    }
+
+    fn assert_stmt(cond: Expr) -> Stmt {
+        Stmt::Assert(AssertStmt { cond, message: None, span: None })
+    }
+
+    fn assign(lhs: &str, rhs: Expr) -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var(lhs), rhs, span: None })
+    }
+
+    fn emit(arg: Expr) -> Stmt {
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::EmitEvent(EvmEmitEvent {
+            event: "Called".to_string(),
+            args: vec![arg],
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    fn function(name: &str, modifiers: &[&str], body: Vec<Stmt>) -> MemberDecl {
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), None);
+        func.modifier_invocs = modifiers
+            .iter()
+            .map(|m| ModifierInvoc { name: m.to_string(), args: vec![], span: None })
+            .collect();
+        MemberDecl::Function(func)
+    }
+
+    fn check(detector: &TxOriginDetector, members: Vec<MemberDecl>) -> Vec<Bug> {
+        let mut members = members;
+        members.push(MemberDecl::Storage(StorageDecl::new(
+            "owner".to_string(),
+            Type::I256,
+            None,
+            None,
+        )));
+        let contract = ContractDecl::new("Vault".to_string(), members, None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        detector.check_contract(&contract, &module)
+    }
+
+    #[test]
+    fn test_tx_origin_detector() {
+        let detector = TxOriginDetector::new();
+        assert_eq!(detector.id(), "tx-origin");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_tx_origin_severity_depends_on_use() {
+        // `require(tx.origin == owner); owner = 1;` gates a storage write.
+        let guarded = function(
+            "setOwner",
+            &[],
+            vec![
+                assert_stmt(eq(origin(1), var("owner"))),
+                assign("owner", var("one")),
+            ],
+        );
+        // `caller = tx.origin; emit Called(tx.origin);`
+        let view = function("whoami", &[], vec![assign("caller", origin(2)), emit(origin(3))]);
+        // `require(tx.origin == owner)` in a function with no effect.
+        let check_only = function("check", &[], vec![assert_stmt(eq(origin(4), var("owner")))]);
+
+        let bugs = check(&TxOriginDetector::new(), vec![guarded, view, check_only]);
+        let levels: Vec<(usize, RiskLevel)> = bugs
+            .iter()
+            .map(|bug| (bug.loc.start_line, bug.risk_level))
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                (1, RiskLevel::High),
+                (2, RiskLevel::Medium),
+                (3, RiskLevel::Low),
+                (4, RiskLevel::Medium),
+            ]
+        );
+        assert_eq!(
+            bugs[2].description.as_deref(),
+            Some("tx.origin logged in 'Vault.whoami'. Consider using msg.sender instead.")
+        );
+    }
+
+    #[test]
+    fn test_tx_origin_in_modifier() {
+        let modifier = MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(
+            EvmModifierDef {
+                name: "onlyOrigin".to_string(),
+                params: vec![],
+                body: vec![assert_stmt(eq(origin(1), var("owner")))],
+                loc: Loc::new(0, 0, 0, 0),
+            },
+        )));
+        let withdraw = function("withdraw", &["onlyOrigin"], vec![assign("owner", var("one"))]);
+
+        let bugs = check(&TxOriginDetector::new(), vec![modifier, withdraw]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].risk_level, RiskLevel::High);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "tx.origin compared with 'owner' in 'Vault.onlyOrigin'. Consider using \
+                 msg.sender instead."
+            )
+        );
+    }
+
+    #[test]
+    fn test_eoa_only_check_is_left_to_its_detector() {
+        let sender = || evm(EvmExpr::MsgSender(EvmMsgSender { loc: Loc::new(0, 0, 0, 0) }));
+        let body = || {
+            vec![
+                assert_stmt(eq(sender(), origin(1))),
+                assert_stmt(eq(origin(2), var("owner"))),
+            ]
+        };
+
+        let bugs = check(&TxOriginDetector::new(), vec![function("mint", &[], body())]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "tx.origin compared with 'owner' in 'Vault.mint'. Consider using msg.sender instead."
+            )
+        );
+
+        let mut detector = TxOriginDetector::new();
+        let params = DetectorParams::from([("allow_eoa_check".to_string(), "false".to_string())]);
+        detector.configure(&params);
+        let bugs = check(&detector, vec![function("mint", &[], body())]);
+        assert_eq!(bugs.len(), 2);
+        assert_eq!(bugs[0].loc.start_line, 1);
+    }
+}
@@ -0,0 +1,340 @@
+//! Unprotected Mint/Burn Detector
+//!
+//! Detects public mint/burn functions that change token supply without an
+//! access-control modifier or a guard before the change. Only a `require`
+//! or `assert` that checks `msg.sender`, or that caps a supply variable with
+//! `<`, `<=`, `>` or `>=`, counts as a guard: `require(to != address(0))`
+//! does not.
+//!
+//! Supply changes are found with the SIR storage-write queries
+//! ([`ContractDecl::has_storage_write`]) on storage variables whose name
+//! contains `supply`, plus calls to internal `_mint`/`_burn` helpers.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::reads_sender;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::EvmFunctionExt;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    BinOp, BinOpExpr, CallExpr, ContractDecl, Expr, FunctionDecl, MemberDecl, Module, Stmt,
+    VarExpr,
+};
+
+/// Scan detector for unprotected mint/burn functions.
+#[derive(Debug, Default)]
+pub struct UnprotectedMintDetector;
+
+impl UnprotectedMintDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Returns `true` if the function name looks like a mint or burn entry point.
+fn is_supply_function_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.starts_with("mint") || lower.starts_with("burn")
+}
+
+/// Returns `true` if the function carries an `only*` modifier such as
+/// `onlyOwner`, `onlyMinter` or `onlyRole(...)`.
+fn has_only_modifier(func: &FunctionDecl) -> bool {
+    func.modifier_invocs.iter().any(|m| m.name.starts_with("only"))
+}
+
+/// Returns `true` if `cond` can restrict who mints or how much: it reads
+/// `msg.sender`, or orders a supply variable against another value.
+fn is_guard_condition(cond: &Expr, supply_vars: &[String]) -> bool {
+    struct CapFinder<'s> {
+        supply_vars: &'s [String],
+        found: bool,
+    }
+    impl<'a> Visit<'a> for CapFinder<'_> {
+        fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+            if matches!(expr.op, BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge) {
+                let mut vars = VarFinder { supply_vars: self.supply_vars, found: false };
+                vars.visit_binop_expr(expr);
+                self.found |= vars.found;
+            }
+            visit::default::visit_binop_expr(self, expr);
+        }
+    }
+    struct VarFinder<'s> {
+        supply_vars: &'s [String],
+        found: bool,
+    }
+    impl<'a> Visit<'a> for VarFinder<'_> {
+        fn visit_var_expr(&mut self, var: &'a VarExpr) {
+            self.found |= self.supply_vars.contains(&var.name);
+        }
+    }
+
+    if reads_sender(cond) {
+        return true;
+    }
+    let mut caps = CapFinder { supply_vars, found: false };
+    caps.visit_expr(cond);
+    caps.found
+}
+
+/// Returns `true` if a guard (`require`, which lowers to `if .. revert`, or
+/// `assert`) on the sender or on a supply cap appears before the first supply
+/// change in the statement list.
+fn has_guard_before_supply_change(stmts: &[Stmt], supply_vars: &[String]) -> bool {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Assert(s) if is_guard_condition(&s.cond, supply_vars) => return true,
+            Stmt::If(s)
+                if s.then_body.iter().any(|st| matches!(st, Stmt::Revert(_)))
+                    && is_guard_condition(&s.cond, supply_vars) =>
+            {
+                return true;
+            }
+            _ if stmt_changes_supply(stmt, supply_vars) => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Returns `true` if the statement writes a supply variable or calls an
+/// internal `_mint`/`_burn` helper.
+fn stmt_changes_supply(stmt: &Stmt, supply_vars: &[String]) -> bool {
+    if ContractDecl::has_storage_write(std::slice::from_ref(stmt), supply_vars) {
+        return true;
+    }
+    let mut finder = SupplyHelperCallFinder { found: false };
+    finder.visit_stmt(stmt);
+    finder.found
+}
+
+/// Visitor that looks for calls to internal `_mint`/`_burn` helpers.
+struct SupplyHelperCallFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for SupplyHelperCallFinder {
+    fn visit_call_expr(&mut self, call: &'a CallExpr) {
+        if let Expr::Var(v) = &*call.callee
+            && matches!(v.name.as_str(), "_mint" | "_burn")
+        {
+            self.found = true;
+        }
+        visit::default::visit_call_expr(self, call);
+    }
+}
+
+impl ScanDetector for UnprotectedMintDetector {
+    fn id(&self) -> &'static str {
+        "unprotected-mint"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unprotected Mint/Burn"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects public mint/burn functions without access control or supply cap"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![284]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![105]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Restrict mint/burn functions with an access control modifier \
+         (e.g., `onlyOwner` or `onlyMinter`) and enforce a supply cap with \
+         `require(totalSupply + amount <= cap)` where applicable."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://swcregistry.io/docs/SWC-105"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, _module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let supply_vars: Vec<String> = contract
+            .storage_names()
+            .into_iter()
+            .filter(|n| n.to_lowercase().contains("supply"))
+            .collect();
+
+        for member in &contract.members {
+            if let MemberDecl::Function(func) = member {
+                if !(func.is_public() || func.is_external()) {
+                    continue;
+                }
+                if !is_supply_function_name(&func.name) || has_only_modifier(func) {
+                    continue;
+                }
+
+                let Some(body) = &func.body else {
+                    continue;
+                };
+
+                let changes_supply = body.iter().any(|s| stmt_changes_supply(s, &supply_vars));
+                if !changes_supply || has_guard_before_supply_change(body, &supply_vars) {
+                    continue;
+                }
+
+                bugs.push(Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "Function '{}' in '{}' changes token supply without \
+                         access control or a supply cap.",
+                        func.name, contract.name,
+                    )),
+                    func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                ));
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, var};
+    use scirs::sir::*;
+
+    fn make_mint_contract(modifiers: Vec<ModifierInvoc>) -> (ContractDecl, Module) {
+        // contract Token {
+        //     uint256 totalSupply;
+        //     function mint(uint256 amount) public <modifiers> {
+        //         totalSupply += amount;
+        //     }
+        // }
+        let storage =
+            MemberDecl::Storage(StorageDecl::new("totalSupply".to_string(), Type::I256, None, None));
+
+        let body = vec![Stmt::AugAssign(AugAssignStmt {
+            op: BinOp::Add,
+            lhs: Expr::Var(VarExpr::new("totalSupply".to_string(), Type::I256, None)),
+            rhs: Expr::Var(VarExpr::new("amount".to_string(), Type::I256, None)),
+            span: None,
+        })];
+
+        let mut mint = FunctionDecl::new(
+            "mint".to_string(),
+            vec![Param::new("amount".to_string(), Type::I256)],
+            vec![],
+            Some(body),
+            None,
+        );
+        mint.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String("public".to_string())));
+        mint.modifier_invocs = modifiers;
+
+        let contract = ContractDecl {
+            name: "Token".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![storage, MemberDecl::Function(mint)],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        (contract, module)
+    }
+
+    /// `make_mint_contract` with `if (cond) revert;` before the supply change.
+    fn check_with_require(cond: Expr) -> Vec<Bug> {
+        let (mut contract, module) = make_mint_contract(vec![]);
+        let MemberDecl::Function(mint) = &mut contract.members[1] else {
+            unreachable!()
+        };
+        let revert = Stmt::Revert(RevertStmt { error: None, args: vec![], span: None });
+        let guard =
+            Stmt::If(IfStmt { cond, then_body: vec![revert], else_body: None, span: None });
+        mint.body.as_mut().unwrap().insert(0, guard);
+        UnprotectedMintDetector::new().check_contract(&contract, &module)
+    }
+
+    #[test]
+    fn test_unprotected_mint_detector() {
+        let detector = UnprotectedMintDetector::new();
+        assert_eq!(detector.id(), "unprotected-mint");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_unguarded_mint_is_flagged() {
+        let (contract, module) = make_mint_contract(vec![]);
+        let bugs = UnprotectedMintDetector::new().check_contract(&contract, &module);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].risk_level, RiskLevel::High);
+    }
+
+    #[test]
+    fn test_only_owner_mint_is_not_flagged() {
+        let guard = ModifierInvoc { name: "onlyOwner".to_string(), args: vec![], span: None };
+        let (contract, module) = make_mint_contract(vec![guard]);
+        let bugs = UnprotectedMintDetector::new().check_contract(&contract, &module);
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_unrelated_require_is_not_a_guard() {
+        // require(to != address(0)); totalSupply += amount;
+        let zero = Expr::Lit(Lit::Num(NumLit {
+            value: Num::Int(IntNum { value: 0.into(), typ: Type::I256 }),
+            span: None,
+        }));
+        let bugs = check_with_require(binop(BinOp::Eq, var("to"), zero));
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_sender_or_cap_require_is_a_guard() {
+        // require(msg.sender == minter);
+        let sender = Expr::Dialect(DialectExpr::Evm(dialect::evm::EvmExpr::MsgSender(
+            dialect::evm::EvmMsgSender { loc: Loc::new(0, 0, 0, 0) },
+        )));
+        assert!(check_with_require(binop(BinOp::Ne, sender, var("minter"))).is_empty());
+
+        // require(totalSupply + amount <= cap);
+        let total = binop(BinOp::Add, var("totalSupply"), var("amount"));
+        assert!(check_with_require(binop(BinOp::Gt, total, var("cap"))).is_empty());
+    }
+}
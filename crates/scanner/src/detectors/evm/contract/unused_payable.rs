@@ -0,0 +1,275 @@
+//! Unused Payable Detector
+//!
+//! Detects `payable` functions that never look at the Ether they receive:
+//!
+//! ```solidity
+//! function setFee(uint256 fee) external payable onlyOwner {
+//!     _fee = fee;
+//! }
+//! ```
+//!
+//! Ether sent along with such a call is accepted silently. Unless some other
+//! function moves the contract's balance it is locked forever, and even then
+//! it is credited to nobody. A function counts as handling value if it, a
+//! modifier it uses, or an internal function it calls reads `msg.value` or a
+//! balance, or forwards Ether (`transfer`, `send`, `call{value: ..}`).
+//! Constructors, `receive` and `fallback` are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl};
+use scirs::sir::dialect::{DialectMemberDecl, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    CallExpr, ContractDecl, Decl, DialectExpr, Expr, FieldAccessExpr, MemberDecl, Module, Stmt,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Scan detector for `payable` functions that ignore `msg.value`.
+#[derive(Debug, Default)]
+pub struct UnusedPayableDetector;
+
+impl UnusedPayableDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Whether a function or modifier body deals with Ether directly, and the
+/// internal functions it calls.
+#[derive(Default)]
+struct ValueUse {
+    handles_value: bool,
+    callees: HashSet<String>,
+}
+
+impl ValueUse {
+    fn of(body: &[Stmt]) -> Self {
+        let mut uses = Self::default();
+        uses.visit_stmts(body);
+        uses
+    }
+}
+
+impl<'a> Visit<'a> for ValueUse {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::Var(v) = &*expr.callee {
+            self.callees.insert(v.name.clone());
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_field_access_expr(&mut self, expr: &'a FieldAccessExpr) {
+        // `address(this).balance`, `to.transfer(..)`, `to.send(..)`
+        self.handles_value |= matches!(expr.field.as_str(), "balance" | "transfer" | "send");
+        visit::default::visit_field_access_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        let DialectExpr::Evm(evm) = expr else {
+            return;
+        };
+        self.handles_value |= match evm {
+            EvmExpr::MsgValue(_)
+            | EvmExpr::SelfBalance(_)
+            | EvmExpr::Send(_)
+            | EvmExpr::Transfer(_) => true,
+            EvmExpr::LowLevelCall(e) => e.value.is_some(),
+            EvmExpr::RawCall(e) => e.value.is_some(),
+            EvmExpr::InlineAsm(asm) => ["callvalue", "selfbalance", "balance("]
+                .iter()
+                .any(|op| asm.asm_text.contains(op)),
+            _ => false,
+        };
+    }
+}
+
+/// Names of the functions and modifiers of `contract` and its ancestors in
+/// `module` that handle value, directly or through the functions they call.
+fn value_handlers(contract: &ContractDecl, module: &Module) -> HashSet<String> {
+    let mut uses: HashMap<String, ValueUse> = HashMap::new();
+    let mut pending = vec![contract];
+    let mut seen = HashSet::new();
+    while let Some(contract) = pending.pop() {
+        if !seen.insert(contract.name.clone()) {
+            continue;
+        }
+        for member in &contract.members {
+            match member {
+                MemberDecl::Function(f) => {
+                    let mut body = ValueUse::of(f.body.as_deref().unwrap_or_default());
+                    body.callees
+                        .extend(f.modifier_invocs.iter().map(|m| m.name.clone()));
+                    uses.entry(f.name.clone()).or_insert(body);
+                }
+                MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(m))) => {
+                    uses.entry(m.name.clone()).or_insert(ValueUse::of(&m.body));
+                }
+                _ => {}
+            }
+        }
+        pending.extend(module.decls.iter().filter_map(|d| match d {
+            Decl::Contract(c) if contract.parents.contains(&c.name) => Some(c),
+            _ => None,
+        }));
+    }
+
+    let mut handlers: HashSet<String> = uses
+        .iter()
+        .filter(|(_, u)| u.handles_value)
+        .map(|(name, _)| name.clone())
+        .collect();
+    loop {
+        let reached: Vec<String> = uses
+            .iter()
+            .filter(|(name, u)| {
+                !handlers.contains(*name) && u.callees.iter().any(|c| handlers.contains(c))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if reached.is_empty() {
+            return handlers;
+        }
+        handlers.extend(reached);
+    }
+}
+
+impl ScanDetector for UnusedPayableDetector {
+    fn id(&self) -> &'static str {
+        "unused-payable"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unused Payable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects payable functions that never read msg.value or forward \
+         Ether, so Ether sent to them may be trapped"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Contract
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![710]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Remove `payable` from functions that are not meant to receive Ether, \
+         or account for `msg.value` in the function."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.soliditylang.org/en/latest/contracts.html#state-mutability"]
+    }
+
+    fn check_contract(&self, contract: &ContractDecl, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let handlers = value_handlers(contract, module);
+        for member in &contract.members {
+            let MemberDecl::Function(func) = member else {
+                continue;
+            };
+            if !func.is_payable()
+                || func.body.is_none()
+                || func.is_constructor()
+                || matches!(func.name.as_str(), "receive" | "fallback")
+                || handlers.contains(&func.name)
+            {
+                continue;
+            }
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' is payable but never uses the Ether it receives.",
+                    contract.name, func.name
+                )),
+                func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::dialect::evm::EvmMsgValue;
+    use scirs::sir::*;
+
+    /// `payable` function `name` whose body is `total = <rhs>;`.
+    fn payable(name: &str, rhs: Expr) -> MemberDecl {
+        let body = vec![Stmt::Assign(AssignStmt {
+            lhs: Expr::Var(VarExpr::new("total".to_string(), Type::I256, None)),
+            rhs,
+            span: None,
+        })];
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::MUTABILITY, AttrValue::String("payable".to_string())));
+        MemberDecl::Function(func)
+    }
+
+    fn check(members: Vec<MemberDecl>) -> Vec<Bug> {
+        let contract = ContractDecl::new("Vault".to_string(), members, None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        UnusedPayableDetector::new().check_contract(&contract, &module)
+    }
+
+    #[test]
+    fn test_unused_payable_detector() {
+        let detector = UnusedPayableDetector::new();
+        assert_eq!(detector.id(), "unused-payable");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_payable_ignoring_msg_value_is_flagged() {
+        let amount = Expr::Var(VarExpr::new("amount".to_string(), Type::I256, None));
+        let bugs = check(vec![payable("setTotal", amount)]);
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_payable_using_msg_value_is_not_flagged() {
+        let value = Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgValue(EvmMsgValue {
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        assert!(check(vec![payable("deposit", value)]).is_empty());
+    }
+}
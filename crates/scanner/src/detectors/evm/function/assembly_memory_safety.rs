@@ -0,0 +1,429 @@
+//! Assembly Memory Safety Detector
+//!
+//! Detects inline assembly that writes storage or memory outside of the
+//! locations managed by the compiler:
+//!
+//! ```solidity
+//! function set(bytes32 slot, uint256 value) external {
+//!     assembly {
+//!         sstore(slot, value)        // arbitrary storage slot
+//!         mstore(0x40, 0x80)         // free memory pointer reset
+//!         return(0x80, 0x20)         // raw memory pointer
+//!     }
+//! }
+//! ```
+//!
+//! SIR keeps inline assembly as text, so each block is parsed again with the
+//! Yul parser of the frontend. The following are reported:
+//! - `sstore` to a slot that is neither a literal, a `.slot` member nor a
+//!   constant, nor a Yul variable holding one;
+//! - `mstore(0x40, ..)` with a value not derived from `mload(0x40)`;
+//! - `mstore`, `return` and `revert` on a raw memory pointer: a literal past
+//!   the scratch space, or a computed address not derived from `mload(0x40)` or
+//!   from a Solidity variable.
+//!
+//! Findings are reported at the assembly block in the enclosing function.
+//! Blocks the Yul parser rejects are skipped.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use frontend::solidity::ast::yul::{YulBlock, YulExpr, YulLit, YulNumLit, YulStmt};
+use frontend::solidity::parsing::yul_parser::parse_inline_assembly_block;
+use num_traits::ToPrimitive;
+use scirs::sir::dialect::evm::{EvmExpr, EvmStorageExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{ContractDecl, DialectExpr, Expr, ExprStmt, FunctionDecl, MemberDecl, Module};
+use std::collections::HashSet;
+
+/// Address of the free memory pointer.
+const FREE_MEMORY_POINTER: u64 = 0x40;
+
+/// Scan detector for unsafe memory and storage accesses in inline assembly.
+#[derive(Debug, Default)]
+pub struct AssemblyMemorySafetyDetector;
+
+impl AssemblyMemorySafetyDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Value of a numeric Yul literal, if it fits in a `u64`.
+fn literal_value(expr: &YulExpr) -> Option<u64> {
+    match expr {
+        YulExpr::Lit(YulLit::Num(YulNumLit::Dec(n))) => n.to_u64(),
+        YulExpr::Lit(YulLit::Num(YulNumLit::Hex(s))) => {
+            u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if `expr` is `mload(0x40)`.
+fn is_free_memory_load(expr: &YulExpr) -> bool {
+    match expr {
+        YulExpr::Call(call) => {
+            call.callee.name.to_string() == "mload"
+                && call.args.len() == 1
+                && literal_value(&call.args[0]) == Some(FREE_MEMORY_POINTER)
+        }
+        _ => false,
+    }
+}
+
+/// Yul variables of one function scope, classified by where their value
+/// comes from.
+#[derive(Default)]
+struct Scope {
+    /// Variables declared in assembly.
+    locals: HashSet<String>,
+    /// Constants, and locals holding a literal, a `.slot` member or a
+    /// constant.
+    fixed_slots: HashSet<String>,
+    /// Locals derived from `mload(0x40)`.
+    free_pointers: HashSet<String>,
+    /// Locals derived from `mload(0x40)` or from a Solidity variable.
+    pointers: HashSet<String>,
+}
+
+impl Scope {
+    fn is_fixed_slot(&self, expr: &YulExpr) -> bool {
+        match expr {
+            YulExpr::Lit(_) => true,
+            YulExpr::Member(m) => m.member.to_string() == "slot",
+            YulExpr::Ident(id) => self.fixed_slots.contains(&id.name.to_string()),
+            YulExpr::Call(_) => false,
+        }
+    }
+
+    /// Returns `true` if `expr` is derived from the free memory pointer.
+    fn is_free_pointer(&self, expr: &YulExpr) -> bool {
+        match expr {
+            YulExpr::Ident(id) => self.free_pointers.contains(&id.name.to_string()),
+            YulExpr::Call(call) => {
+                is_free_memory_load(expr) || call.args.iter().any(|a| self.is_free_pointer(a))
+            }
+            YulExpr::Lit(_) | YulExpr::Member(_) => false,
+        }
+    }
+
+    /// Returns `true` if `expr` is derived from the free memory pointer or
+    /// from a Solidity variable, which may hold a memory reference.
+    fn is_pointer(&self, expr: &YulExpr) -> bool {
+        match expr {
+            YulExpr::Ident(id) => {
+                let name = id.name.to_string();
+                !self.locals.contains(&name) || self.pointers.contains(&name)
+            }
+            YulExpr::Member(_) => true,
+            YulExpr::Call(call) => {
+                is_free_memory_load(expr) || call.args.iter().any(|a| self.is_pointer(a))
+            }
+            YulExpr::Lit(_) => false,
+        }
+    }
+
+    /// Returns `true` if `expr` is a memory address the compiler does not
+    /// manage: a literal past the scratch space, or a computed address not
+    /// derived from a pointer.
+    fn is_raw_pointer(&self, expr: &YulExpr) -> bool {
+        match literal_value(expr) {
+            Some(addr) => addr >= FREE_MEMORY_POINTER,
+            None => matches!(expr, YulExpr::Lit(_)) || !self.is_pointer(expr),
+        }
+    }
+
+    fn assign(&mut self, var: String, value: Option<&YulExpr>) {
+        let fixed_slot = value.is_some_and(|v| self.is_fixed_slot(v));
+        let free_pointer = value.is_some_and(|v| self.is_free_pointer(v));
+        let pointer = value.is_some_and(|v| self.is_pointer(v));
+        let update = |set: &mut HashSet<String>, flag: bool| match flag {
+            true => set.insert(var.clone()),
+            false => set.remove(&var),
+        };
+        update(&mut self.fixed_slots, fixed_slot);
+        update(&mut self.free_pointers, free_pointer);
+        update(&mut self.pointers, pointer);
+    }
+}
+
+/// Walks the statements of a Yul block in order and collects the unsafe
+/// accesses as `(statement, issue)` pairs.
+fn scan_block(block: &YulBlock, scope: &mut Scope, issues: &mut Vec<(String, &'static str)>) {
+    for stmt in &block.body {
+        match stmt {
+            YulStmt::VarDecl(decl) => {
+                if let Some(value) = &decl.value {
+                    scan_expr(value, scope, issues);
+                }
+                let value = match decl.vars.len() {
+                    1 => decl.value.as_ref(),
+                    _ => None,
+                };
+                for var in &decl.vars {
+                    let name = var.name.to_string();
+                    scope.locals.insert(name.clone());
+                    scope.assign(name, value);
+                }
+            }
+            YulStmt::Assign(stmt) => {
+                scan_expr(&stmt.value, scope, issues);
+                let value = match stmt.vars.len() {
+                    1 => Some(&stmt.value),
+                    _ => None,
+                };
+                for var in &stmt.vars {
+                    scope.assign(var.name.to_string(), value);
+                }
+            }
+            YulStmt::Expr(expr) => scan_expr(expr, scope, issues),
+            YulStmt::Block(inner) => scan_block(inner, scope, issues),
+            YulStmt::If(stmt) => {
+                scan_expr(&stmt.cond, scope, issues);
+                scan_block(&stmt.body, scope, issues);
+            }
+            YulStmt::Switch(stmt) => {
+                scan_expr(&stmt.expr, scope, issues);
+                for case in &stmt.values {
+                    scan_block(&case.body, scope, issues);
+                }
+                if let Some(default) = &stmt.default {
+                    scan_block(&default.body, scope, issues);
+                }
+            }
+            YulStmt::For(stmt) => {
+                scan_block(&stmt.pre_loop, scope, issues);
+                scan_expr(&stmt.condition, scope, issues);
+                scan_block(&stmt.body, scope, issues);
+                scan_block(&stmt.post_loop, scope, issues);
+            }
+            YulStmt::FuncDef(def) => {
+                // Parameters of a Yul function may carry any pointer, so
+                // only the values computed inside it are judged.
+                let mut inner = Scope::default();
+                inner
+                    .locals
+                    .extend(def.returns.iter().map(|r| r.name.to_string()));
+                scan_block(&def.body, &mut inner, issues);
+            }
+            YulStmt::Break | YulStmt::Continue | YulStmt::Leave => {}
+        }
+    }
+}
+
+/// Checks the calls of a Yul expression, innermost first.
+fn scan_expr(expr: &YulExpr, scope: &Scope, issues: &mut Vec<(String, &'static str)>) {
+    let YulExpr::Call(call) = expr else {
+        return;
+    };
+    for arg in &call.args {
+        scan_expr(arg, scope, issues);
+    }
+    let issue = match (call.callee.name.to_string().as_str(), call.args.as_slice()) {
+        ("sstore", [slot, _]) if !scope.is_fixed_slot(slot) => {
+            Some("writes a computed storage slot")
+        }
+        ("mstore", [addr, value]) if literal_value(addr) == Some(FREE_MEMORY_POINTER) => {
+            match scope.is_free_pointer(value) {
+                true => None,
+                false => Some("sets the free memory pointer to a value not derived from it"),
+            }
+        }
+        ("mstore" | "mstore8", [addr, _]) if scope.is_raw_pointer(addr) => {
+            Some("writes memory through a raw pointer")
+        }
+        ("return" | "revert", [offset, _]) if scope.is_raw_pointer(offset) => {
+            Some("returns memory through a raw pointer")
+        }
+        _ => None,
+    };
+    if let Some(issue) = issue {
+        issues.push((call.to_string(), issue));
+    }
+}
+
+/// Parses the text of an inline assembly block and collects its unsafe
+/// accesses, given the constants of the contract. Returns nothing if the
+/// text is not valid Yul.
+fn scan_assembly(asm_text: &str, constants: &HashSet<String>) -> Vec<(String, &'static str)> {
+    let mut issues = vec![];
+    if let Ok(block) = parse_inline_assembly_block(&format!("{{\n{asm_text}\n}}")) {
+        let mut scope = Scope { fixed_slots: constants.clone(), ..Default::default() };
+        scan_block(&block, &mut scope, &mut issues);
+    }
+    issues
+}
+
+impl ScanDetector for AssemblyMemorySafetyDetector {
+    fn id(&self) -> &'static str {
+        "assembly-memory-safety"
+    }
+
+    fn name(&self) -> &'static str {
+        "Assembly Memory Safety"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects inline assembly writing computed storage slots, misusing the \
+         free memory pointer or accessing memory through raw pointers"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![787]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![124]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Derive storage slots from `.slot` members or constants, allocate memory \
+         through `mload(0x40)` and update the free memory pointer past the \
+         allocation, and mark blocks following these rules as `memory-safe`."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/assembly.html#memory-safety",
+            "https://swcregistry.io/docs/SWC-124",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        struct Visitor<'b> {
+            detector: &'b AssemblyMemorySafetyDetector,
+            bugs: &'b mut Vec<Bug>,
+            constants: HashSet<String>,
+            contract_name: String,
+            func_name: String,
+        }
+
+        impl<'a, 'b> Visit<'a> for Visitor<'b> {
+            fn visit_expr_stmt(&mut self, stmt: &'a ExprStmt) {
+                if let Expr::Dialect(DialectExpr::Evm(EvmExpr::InlineAsm(asm))) = &stmt.expr {
+                    for (code, issue) in scan_assembly(&asm.asm_text, &self.constants) {
+                        self.bugs.push(Bug::new(
+                            self.detector.name(),
+                            Some(&format!(
+                                "Inline assembly in '{}.{}' {}: `{}`.",
+                                self.contract_name, self.func_name, issue, code
+                            )),
+                            stmt.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                            self.detector.bug_kind(),
+                            self.detector.bug_category(),
+                            self.detector.risk_level(),
+                            self.detector.cwe_ids(),
+                            self.detector.swc_ids(),
+                            Some(self.detector.recommendation()),
+                        ));
+                    }
+                }
+                visit::default::visit_expr_stmt(self, stmt);
+            }
+        }
+
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: &mut bugs,
+            constants: contract
+                .members
+                .iter()
+                .filter_map(|m| match m {
+                    MemberDecl::Storage(s) if s.is_constant_storage() => Some(s.name.clone()),
+                    _ => None,
+                })
+                .collect(),
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+        };
+        visitor.visit_function_decl(func);
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(asm_text: &str) -> Vec<&'static str> {
+        let constants = HashSet::from(["IMPL_SLOT".to_string()]);
+        scan_assembly(asm_text, &constants)
+            .into_iter()
+            .map(|(_, issue)| issue)
+            .collect()
+    }
+
+    #[test]
+    fn test_assembly_memory_safety_detector() {
+        let detector = AssemblyMemorySafetyDetector::new();
+        assert_eq!(detector.id(), "assembly-memory-safety");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_computed_storage_slot_is_flagged() {
+        assert_eq!(issues("sstore(slot, value)"), vec!["writes a computed storage slot"]);
+        assert_eq!(
+            issues("let s := add(x.slot, 1)\nsstore(s, 1)"),
+            vec!["writes a computed storage slot"]
+        );
+        assert!(issues("sstore(x.slot, 1)\nlet s := 0x01\nsstore(s, 2)").is_empty());
+        assert!(issues("sstore(IMPL_SLOT, impl)").is_empty());
+    }
+
+    #[test]
+    fn test_free_memory_pointer_misuse_is_flagged() {
+        assert_eq!(
+            issues("mstore(0x40, 0x80)"),
+            vec!["sets the free memory pointer to a value not derived from it"]
+        );
+        let allocation = "let ptr := mload(0x40)\nmstore(ptr, v)\nmstore(0x40, add(ptr, 0x20))";
+        assert!(issues(allocation).is_empty());
+    }
+
+    #[test]
+    fn test_raw_memory_pointers_are_flagged() {
+        assert_eq!(
+            issues("mstore(0x80, v)\nreturn(calldataload(4), 0x20)"),
+            vec![
+                "writes memory through a raw pointer",
+                "returns memory through a raw pointer"
+            ]
+        );
+        // Scratch space and Solidity memory variables are fine.
+        assert!(issues("mstore(0, v)\nreturn(0, 0x20)\nrevert(add(data, 0x20), len)").is_empty());
+    }
+}
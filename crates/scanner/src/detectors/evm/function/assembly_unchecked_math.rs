@@ -0,0 +1,514 @@
+//! Assembly Unchecked Math Detector
+//!
+//! Detects `add`, `sub` and `mul` in inline assembly whose result reaches a
+//! value sink without an overflow check. Yul arithmetic wraps silently even
+//! under Solidity >= 0.8, so a wrapped amount can be stored or sent.
+//!
+//! Inline assembly is kept as opaque text in SIR, so the Yul statements are
+//! scanned line by line. Results of arithmetic are tainted and propagated
+//! through Yul and Solidity assignments until one of the following sinks:
+//! - `sstore` values and the `value` of `call`/`callcode` in assembly;
+//! - writes to storage variables;
+//! - `transfer`/`send` amounts, `call{value: ..}` and token transfers.
+//!
+//! A condition (`if`/`switch` in Yul, `if`/`require`/`assert` in Solidity)
+//! reading a tainted variable counts as an overflow check. Arithmetic on
+//! memory pointers and calldata offsets rarely carries amounts and is
+//! reported as informational only.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmStmt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, CallExpr, ContractDecl, DialectExpr, DialectStmt, Expr,
+    FunctionDecl, Module, Stmt,
+};
+use std::collections::HashMap;
+
+/// Unchecked Yul arithmetic opcodes.
+const ARITHMETIC_OPS: &[&str] = &["add", "sub", "mul"];
+
+/// Token-moving functions whose arguments are amounts.
+const TOKEN_TRANSFERS: &[&str] = &[
+    "transfer",
+    "transferFrom",
+    "safeTransfer",
+    "safeTransferFrom",
+];
+
+/// Scan detector for unchecked arithmetic in inline assembly.
+#[derive(Debug, Default)]
+pub struct AssemblyUncheckedMathDetector;
+
+impl AssemblyUncheckedMathDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Where a tainted variable got its value from.
+#[derive(Debug, Clone)]
+struct Origin {
+    /// The arithmetic opcode, e.g. `add`.
+    op: &'static str,
+    /// Whether the operands look like memory or calldata offsets.
+    is_offset: bool,
+    /// Location of the assembly block.
+    loc: Option<Loc>,
+}
+
+type Taint = HashMap<String, Origin>;
+
+/// Identifiers of a Yul or Solidity expression text, keeping member
+/// suffixes such as `x.offset`.
+fn idents(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$' || c == '.'))
+        .filter(|t| t.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$'))
+}
+
+/// The first tainted variable read by `text`.
+fn tainted_in<'t>(text: &str, taint: &'t Taint) -> Option<(&'t String, &'t Origin)> {
+    idents(text).find_map(|id| taint.get_key_value(id))
+}
+
+/// The arithmetic opcode of a Yul expression whose outermost call is one,
+/// with its arguments.
+fn arithmetic(text: &str) -> Option<(&'static str, Vec<&str>)> {
+    let text = text.trim();
+    let op = ARITHMETIC_OPS
+        .iter()
+        .find(|op| text.starts_with(&format!("{op}(")))?;
+    Some((op, call_args(text, op)?))
+}
+
+/// Top-level arguments of the first call to `name` in a Yul expression.
+fn call_args<'t>(text: &'t str, name: &str) -> Option<Vec<&'t str>> {
+    let pattern = format!("{name}(");
+    let start = text.match_indices(&pattern).find_map(|(i, _)| {
+        let preceding = text[..i].chars().next_back();
+        preceding
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+            .then_some(i)
+    })? + pattern.len();
+
+    let mut args = vec![];
+    let (mut depth, mut arg_start) = (0usize, start);
+    for (i, c) in text[start..].char_indices().map(|(i, c)| (start + i, c)) {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                args.push(text[arg_start..i].trim());
+                return Some(args.into_iter().filter(|a| !a.is_empty()).collect());
+            }
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(text[arg_start..i].trim());
+                arg_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns `true` if an arithmetic operand looks like a memory or calldata
+/// offset rather than an amount.
+fn is_offset_operand(operand: &str) -> bool {
+    let lower = operand.to_lowercase();
+    matches!(lower.as_str(), "0x20" | "0x40" | "32" | "64" | "0x1f" | "31")
+        || lower.starts_with("mload(0x40")
+        || lower.starts_with("calldatasize(")
+        || lower.starts_with("returndatasize(")
+        || idents(&lower).any(|id| {
+            id.ends_with(".offset")
+                || id.ends_with(".slot")
+                || id.contains("ptr")
+                || id.contains("offset")
+                || id.contains("pos")
+        })
+}
+
+/// Scans the Yul text of one assembly block, updating the taint and
+/// returning the tainted variables reaching an assembly sink.
+fn scan_assembly(asm: &str, loc: Option<&Loc>, taint: &mut Taint) -> Vec<(String, Origin)> {
+    let mut hits = vec![];
+    for line in asm.lines().map(str::trim) {
+        if let Some((lhs, rhs)) = line.split_once(":=") {
+            let lhs = lhs.trim().trim_start_matches("let ");
+            let origin = match arithmetic(rhs) {
+                Some((op, operands)) => Some(Origin {
+                    op,
+                    is_offset: operands.iter().any(|o| is_offset_operand(o)),
+                    loc: loc.cloned(),
+                }),
+                None => tainted_in(rhs, taint).map(|(_, o)| o.clone()),
+            };
+            for var in lhs.split(',').map(str::trim) {
+                match &origin {
+                    Some(origin) => taint.insert(var.to_string(), origin.clone()),
+                    None => taint.remove(var),
+                };
+            }
+        } else if line.starts_with("if ") || line.starts_with("switch ") {
+            let cond = line.split('{').next().unwrap_or(line);
+            taint.retain(|var, _| !idents(cond).any(|id| id == var));
+        } else {
+            let value = call_args(line, "sstore")
+                .and_then(|args| args.get(1).copied())
+                .or_else(|| call_args(line, "call").and_then(|args| args.get(2).copied()))
+                .or_else(|| call_args(line, "callcode").and_then(|args| args.get(2).copied()));
+            let Some(value) = value else {
+                continue;
+            };
+            if let Some((var, origin)) = tainted_in(value, taint) {
+                hits.push((var.clone(), origin.clone()));
+            } else if let Some((op, operands)) = arithmetic(value) {
+                let is_offset = operands.iter().any(|o| is_offset_operand(o));
+                let origin = Origin { op, is_offset, loc: loc.cloned() };
+                hits.push((format!("{op}(..)"), origin));
+            }
+        }
+    }
+    hits
+}
+
+/// Collects the amount and stored-value expressions of a simple statement.
+struct SinkFinder<'a> {
+    storage: &'a [String],
+    sinks: Vec<&'a Expr>,
+}
+
+impl<'a> Visit<'a> for SinkFinder<'a> {
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        if writes_storage(&stmt.lhs, self.storage) {
+            self.sinks.push(&stmt.rhs);
+        }
+        visit::default::visit_assign_stmt(self, stmt);
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        if writes_storage(&stmt.lhs, self.storage) {
+            self.sinks.push(&stmt.rhs);
+        }
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::FieldAccess(fa) = expr.callee.as_ref()
+            && TOKEN_TRANSFERS.contains(&fa.field.as_str())
+        {
+            self.sinks.extend(expr.args.exprs());
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        match expr {
+            DialectExpr::Evm(EvmExpr::Transfer(e)) => self.sinks.push(&e.amount),
+            DialectExpr::Evm(EvmExpr::Send(e)) => self.sinks.push(&e.value),
+            DialectExpr::Evm(EvmExpr::LowLevelCall(e)) => self.sinks.extend(e.value.as_deref()),
+            _ => {}
+        }
+    }
+}
+
+/// Returns `true` if an assignment target is (part of) a storage variable.
+fn writes_storage(lhs: &Expr, storage: &[String]) -> bool {
+    match lhs {
+        Expr::Var(v) => storage.contains(&v.name),
+        Expr::IndexAccess(e) => writes_storage(&e.base, storage),
+        Expr::FieldAccess(e) => writes_storage(&e.base, storage),
+        Expr::Tuple(t) => t.elems.iter().flatten().any(|e| writes_storage(e, storage)),
+        _ => false,
+    }
+}
+
+/// Walks a function body in order, tracking tainted variables.
+struct Scanner<'a> {
+    storage: &'a [String],
+    taint: Taint,
+    hits: Vec<(String, Origin)>,
+}
+
+impl<'a> Scanner<'a> {
+    fn scan_stmts(&mut self, stmts: &'a [Stmt]) {
+        for stmt in stmts {
+            self.scan_stmt(stmt);
+        }
+    }
+
+    fn scan_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Expr(s) => match &s.expr {
+                Expr::Dialect(DialectExpr::Evm(EvmExpr::InlineAsm(asm))) => {
+                    let hits = scan_assembly(&asm.asm_text, s.span.as_ref(), &mut self.taint);
+                    self.hits.extend(hits);
+                }
+                _ => self.check_sinks(stmt),
+            },
+            Stmt::LocalVar(s) => {
+                self.check_sinks(stmt);
+                let origin = s.init.as_ref().and_then(|init| self.origin_of(init));
+                for var in s.vars.iter().flatten() {
+                    self.update(&var.name, origin.clone());
+                }
+            }
+            Stmt::Assign(s) => {
+                self.check_sinks(stmt);
+                let origin = self.origin_of(&s.rhs);
+                if let Expr::Var(v) = &s.lhs {
+                    self.update(&v.name, origin);
+                }
+            }
+            Stmt::If(s) => {
+                self.check_cond(&s.cond);
+                self.scan_stmts(&s.then_body);
+                self.scan_stmts(s.else_body.as_deref().unwrap_or_default());
+            }
+            Stmt::Assert(s) => self.check_cond(&s.cond),
+            Stmt::While(s) => {
+                self.check_cond(&s.cond);
+                self.scan_stmts(&s.body);
+            }
+            Stmt::For(s) => {
+                if let Some(init) = &s.init {
+                    self.scan_stmt(init);
+                }
+                if let Some(cond) = &s.cond {
+                    self.check_cond(cond);
+                }
+                self.scan_stmts(&s.body);
+                if let Some(update) = &s.update {
+                    self.scan_stmt(update);
+                }
+            }
+            Stmt::Block(inner) => self.scan_stmts(inner),
+            Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+                self.scan_stmts(&tc.body);
+                for clause in &tc.catch_clauses {
+                    self.scan_stmts(&clause.body);
+                }
+            }
+            _ => self.check_sinks(stmt),
+        }
+    }
+
+    fn origin_of(&self, expr: &Expr) -> Option<Origin> {
+        tainted_in(&expr.to_string(), &self.taint).map(|(_, o)| o.clone())
+    }
+
+    fn update(&mut self, var: &str, origin: Option<Origin>) {
+        match origin {
+            Some(origin) => self.taint.insert(var.to_string(), origin),
+            None => self.taint.remove(var),
+        };
+    }
+
+    /// Sinks evaluated by the condition are checked first; the variables it
+    /// reads are then considered checked.
+    fn check_cond(&mut self, cond: &'a Expr) {
+        self.check_sinks_in(|finder| finder.visit_expr(cond));
+        let text = cond.to_string();
+        self.taint
+            .retain(|var, _| !idents(&text).any(|id| id == var));
+    }
+
+    fn check_sinks(&mut self, stmt: &'a Stmt) {
+        self.check_sinks_in(|finder| finder.visit_stmt(stmt));
+    }
+
+    fn check_sinks_in(&mut self, visit: impl FnOnce(&mut SinkFinder<'a>)) {
+        let mut finder = SinkFinder { storage: self.storage, sinks: vec![] };
+        visit(&mut finder);
+        for sink in finder.sinks {
+            if let Some((var, origin)) = tainted_in(&sink.to_string(), &self.taint) {
+                self.hits.push((var.clone(), origin.clone()));
+            }
+        }
+    }
+}
+
+impl ScanDetector for AssemblyUncheckedMathDetector {
+    fn id(&self) -> &'static str {
+        "assembly-unchecked-math"
+    }
+
+    fn name(&self) -> &'static str {
+        "Assembly Unchecked Math"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects inline assembly arithmetic whose unchecked result is stored \
+         or transferred without an overflow check"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Arithmetic
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![190, 191]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![101]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check assembly arithmetic explicitly (e.g. `if lt(sum, a) { revert(0, 0) }` \
+         after `add`), or compute amounts in checked Solidity arithmetic."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/yul.html#evm-dialect",
+            "https://swcregistry.io/docs/SWC-101",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let Some(body) = &func.body else {
+            return bugs;
+        };
+        let storage = contract.storage_names();
+        let mut scanner = Scanner { storage: &storage, taint: Taint::new(), hits: vec![] };
+        scanner.scan_stmts(body);
+
+        let mut reported = vec![];
+        for (var, origin) in scanner.hits {
+            if reported.contains(&var) {
+                continue;
+            }
+            let (risk, kind) = match origin.is_offset {
+                true => (RiskLevel::No, "offset arithmetic"),
+                false => (self.risk_level(), "arithmetic"),
+            };
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}': unchecked assembly {} (`{}`) flows into '{}', which is \
+                     stored or transferred without an overflow check.",
+                    contract.name, func.name, kind, origin.op, var
+                )),
+                origin
+                    .loc
+                    .or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                risk,
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+            reported.push(var);
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_body, var};
+    use scirs::sir::dialect::evm::{EvmInlineAsm, EvmTransfer};
+    use scirs::sir::*;
+
+    fn asm(text: &str) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::Dialect(DialectExpr::Evm(EvmExpr::InlineAsm(EvmInlineAsm {
+                asm_text: text.to_string(),
+                loc: Loc::new(0, 0, 0, 0),
+            }))),
+            span: None,
+        })
+    }
+
+    fn transfer(amount: &str) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(EvmTransfer {
+                target: Box::new(var("to")),
+                amount: Box::new(var(amount)),
+                loc: Loc::new(0, 0, 0, 0),
+            }))),
+            span: None,
+        })
+    }
+
+    #[test]
+    fn test_assembly_unchecked_math_detector() {
+        let detector = AssemblyUncheckedMathDetector::new();
+        assert_eq!(detector.id(), "assembly-unchecked-math");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_assembly_add_feeding_transfer_is_flagged() {
+        // assembly { amount := add(base, bonus) }
+        // payable(to).transfer(amount);
+        let bugs = check_body(
+            &AssemblyUncheckedMathDetector::new(),
+            vec![asm("amount := add(base, bonus)"), transfer("amount")],
+        );
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].risk_level, RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_checked_assembly_add_is_not_flagged() {
+        let checked = "amount := add(base, bonus)\nif lt(amount, base) {\n  revert(0, 0)\n}";
+        assert!(
+            check_body(
+                &AssemblyUncheckedMathDetector::new(),
+                vec![asm(checked), transfer("amount")]
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_offset_arithmetic_is_informational() {
+        let bugs = check_body(
+            &AssemblyUncheckedMathDetector::new(),
+            vec![asm("amount := mload(add(data, 0x20))"), transfer("amount")],
+        );
+        assert!(bugs.is_empty());
+
+        let bugs = check_body(
+            &AssemblyUncheckedMathDetector::new(),
+            vec![asm("let ptr := mload(0x40)\nsstore(0, add(ptr, 0x20))")],
+        );
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].risk_level, RiskLevel::No);
+    }
+}
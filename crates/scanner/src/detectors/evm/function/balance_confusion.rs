@@ -0,0 +1,337 @@
+//! Balance Confusion Detector
+//!
+//! Detects arithmetic and comparisons that mix the contract's native ether
+//! balance with its balance of an ERC20 token:
+//!
+//! ```solidity
+//! uint256 surplus = address(this).balance - token.balanceOf(address(this));
+//! ```
+//!
+//! The two are denominated in different assets, so the result is
+//! meaningless; accounting built on it usually lets the protocol pay out
+//! more than it holds. Each value is tagged with the kind of balance it was
+//! read from and the tag is followed through local and storage assignments,
+//! as in the decimals mismatch detector. Converting at a price multiplies or
+//! divides, so either operation drops the tag.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::DialectExpr;
+use scirs::sir::dialect::evm::{EvmCallExt, EvmExpr};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, BinOp, BinOpExpr, ContractDecl, Expr, FunctionDecl, LocalVarStmt,
+    Module, Type,
+};
+use std::collections::HashMap;
+
+/// Scan detector for arithmetic mixing ether and token balances.
+#[derive(Debug, Default)]
+pub struct BalanceConfusionDetector;
+
+impl BalanceConfusionDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Kind of a balance of the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Balance {
+    /// `address(this).balance`
+    Native,
+    /// `token.balanceOf(address(this))`
+    Token,
+}
+
+/// Whether `expr` is `this` or `address(this)`.
+fn is_this(expr: &Expr) -> bool {
+    match expr {
+        Expr::Var(v) => v.name == "this",
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::This(_))) => true,
+        Expr::TypeCast(cast) => is_this(&cast.expr),
+        _ => false,
+    }
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Tracks which kind of balance each variable holds, in program order, and
+/// records expressions combining the two kinds.
+#[derive(Default)]
+struct BalanceWalker {
+    kinds: HashMap<String, Balance>,
+    mixes: Vec<Option<Loc>>,
+}
+
+impl BalanceWalker {
+    /// Kind of balance `expr` evaluates to, if any.
+    fn kind(&self, expr: &Expr) -> Option<Balance> {
+        match expr {
+            Expr::Dialect(DialectExpr::Evm(EvmExpr::SelfBalance(_))) => Some(Balance::Native),
+            Expr::FieldAccess(fa) if fa.field == "balance" && is_this(&fa.base) => {
+                Some(Balance::Native)
+            }
+            Expr::FunctionCall(call)
+                if call
+                    .method_call_on_type("balanceOf", |ty| matches!(ty, Type::TypeRef(_)))
+                    .is_some()
+                    && matches!(call.args.exprs().as_slice(), [arg] if is_this(arg)) =>
+            {
+                Some(Balance::Token)
+            }
+            Expr::Var(v) => self.kinds.get(&v.name).copied(),
+            Expr::IndexAccess(_) | Expr::FieldAccess(_) => {
+                root_name(expr).and_then(|name| self.kinds.get(name).copied())
+            }
+            Expr::BinOp(b) if matches!(b.op, BinOp::Add | BinOp::Sub | BinOp::Mod) => {
+                self.kind(&b.lhs).or_else(|| self.kind(&b.rhs))
+            }
+            _ => None,
+        }
+    }
+
+    fn check_mix(&mut self, lhs: &Expr, rhs: &Expr, span: Option<&Loc>) {
+        if let (Some(a), Some(b)) = (self.kind(lhs), self.kind(rhs))
+            && a != b
+        {
+            self.mixes.push(span.cloned());
+        }
+    }
+
+    fn assign(&mut self, name: &str, rhs: &Expr) {
+        match self.kind(rhs) {
+            Some(kind) => self.kinds.insert(name.to_string(), kind),
+            None => self.kinds.remove(name),
+        };
+    }
+}
+
+impl<'a> Visit<'a> for BalanceWalker {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        if let Some(init) = &stmt.init
+            && let [Some(var)] = stmt.vars.as_slice()
+        {
+            self.assign(&var.name, init);
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        visit::default::visit_assign_stmt(self, stmt);
+        if let Some(name) = root_name(&stmt.lhs) {
+            self.assign(name, &stmt.rhs);
+        }
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        if matches!(stmt.op, BinOp::Add | BinOp::Sub) {
+            self.check_mix(&stmt.lhs, &stmt.rhs, stmt.span.as_ref());
+        }
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        if matches!(
+            expr.op,
+            BinOp::Add
+                | BinOp::Sub
+                | BinOp::Eq
+                | BinOp::Ne
+                | BinOp::Lt
+                | BinOp::Le
+                | BinOp::Gt
+                | BinOp::Ge
+        ) {
+            self.check_mix(&expr.lhs, &expr.rhs, expr.span.as_ref());
+        }
+        visit::default::visit_binop_expr(self, expr);
+    }
+}
+
+impl ScanDetector for BalanceConfusionDetector {
+    fn id(&self) -> &'static str {
+        "balance-confusion"
+    }
+
+    fn name(&self) -> &'static str {
+        "Balance Confusion"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects arithmetic or comparisons mixing the contract's ether \
+         balance with its ERC20 token balance"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Arithmetic
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![682]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Keep ether and token amounts in separate accounting, or convert one \
+         into the other through a price before combining them."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/682.html"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20", "defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut walker = BalanceWalker::default();
+        walker.visit_function_decl(func);
+
+        for loc in walker.mixes {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' combines the contract's ether balance with its \
+                     token balance.",
+                    contract.name, func.name
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::dialect::DialectType;
+    use scirs::sir::dialect::evm::EvmType;
+    use scirs::sir::*;
+
+    /// `address(this)`
+    fn this() -> Expr {
+        let address = Type::Dialect(DialectType::Evm(EvmType::Address));
+        Expr::TypeCast(TypeCastExpr {
+            ty: address,
+            expr: Box::new(typed_var("this", Type::TypeRef("Vault".to_string()))),
+            span: None,
+        })
+    }
+
+    /// `address(this).balance`
+    fn native_balance() -> Expr {
+        Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(this()),
+            field: "balance".to_string(),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    /// `token.balanceOf(address(this))`
+    fn token_balance() -> Expr {
+        Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(typed_var("token", Type::TypeRef("IERC20".to_string()))),
+                field: "balanceOf".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![this()]),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    fn sub(lhs: Expr, rhs: Expr) -> Expr {
+        Expr::BinOp(BinOpExpr {
+            op: BinOp::Sub,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        })
+    }
+
+    /// `surplus = <rhs>;`
+    fn check(rhs: Expr) -> Vec<Bug> {
+        let body = vec![Stmt::Assign(AssignStmt {
+            lhs: typed_var("surplus", Type::I256),
+            rhs,
+            span: None,
+        })];
+        let func = FunctionDecl::new("skim".to_string(), vec![], vec![], Some(body), None);
+        let contract = ContractDecl::new("Vault".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        BalanceConfusionDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_balance_confusion_detector() {
+        let detector = BalanceConfusionDetector::new();
+        assert_eq!(detector.id(), "balance-confusion");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_subtracting_token_from_ether_balance_is_flagged() {
+        assert_eq!(check(sub(native_balance(), token_balance())).len(), 1);
+    }
+
+    #[test]
+    fn test_one_kind_of_balance_is_not_flagged() {
+        assert!(check(sub(native_balance(), native_balance())).is_empty());
+        assert!(check(sub(token_balance(), token_balance())).is_empty());
+    }
+}
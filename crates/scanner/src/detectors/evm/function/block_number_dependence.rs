@@ -0,0 +1,318 @@
+//! Block Number Dependence Detector
+//!
+//! Detects `block.number` used as a clock in conditions that guard state
+//! changes or value transfers:
+//!
+//! ```solidity
+//! require(block.number >= unlockBlock);
+//! payable(msg.sender).transfer(amount);
+//! ```
+//!
+//! Block times vary between chains and over time (and L2 sequencers set
+//! their own block numbers), so a block count is a poor measure of
+//! elapsed time. A comparison involving `block.number` is reported when it
+//! is the condition of an `if` or loop whose body writes state or makes an
+//! external call, or of a `require`/`assert` followed by such a statement.
+//! Merely recording `block.number`, e.g. in an event, is not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::{always_exits, stmt_has_external_call};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmStmt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, BinOp, ContractDecl, DialectExpr, DialectStmt, Expr, FunctionDecl,
+    Module, Stmt,
+};
+use std::collections::HashSet;
+
+/// Scan detector for `block.number` used as a time source.
+#[derive(Debug, Default)]
+pub struct BlockNumberDependenceDetector;
+
+impl BlockNumberDependenceDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Location of a `block.number` compared in `cond`, looking through `&&`,
+/// `||` and `!`.
+fn compared_block_number(cond: &Expr) -> Option<Loc> {
+    match cond {
+        Expr::BinOp(b) if matches!(b.op, BinOp::And | BinOp::Or) => {
+            compared_block_number(&b.lhs).or_else(|| compared_block_number(&b.rhs))
+        }
+        Expr::BinOp(b)
+            if matches!(
+                b.op,
+                BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge | BinOp::Eq | BinOp::Ne
+            ) =>
+        {
+            let mut finder = BlockNumberFinder::default();
+            finder.visit_expr(cond);
+            finder.loc
+        }
+        Expr::UnOp(u) => compared_block_number(&u.operand),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct BlockNumberFinder {
+    loc: Option<Loc>,
+}
+
+impl<'a> Visit<'a> for BlockNumberFinder {
+    fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+        if let DialectExpr::Evm(EvmExpr::BlockNumber(e)) = d
+            && self.loc.is_none()
+        {
+            self.loc = Some(e.loc.clone());
+        }
+    }
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Finds state writes and external calls.
+struct EffectFinder<'s> {
+    storage: &'s HashSet<String>,
+    found: bool,
+}
+
+impl<'a> Visit<'a> for EffectFinder<'_> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if !self.found && stmt_has_external_call(stmt) {
+            self.found = true;
+        }
+        if !self.found {
+            visit::default::visit_stmt(self, stmt);
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        self.found |= root_name(&stmt.lhs).is_some_and(|n| self.storage.contains(n));
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        self.found |= root_name(&stmt.lhs).is_some_and(|n| self.storage.contains(n));
+    }
+}
+
+/// Walks a function body and records the guards comparing `block.number`.
+struct GuardWalker<'s> {
+    storage: &'s HashSet<String>,
+    guards: Vec<Loc>,
+}
+
+impl GuardWalker<'_> {
+    fn has_effect(&self, stmts: &[Stmt]) -> bool {
+        let mut finder = EffectFinder { storage: self.storage, found: false };
+        finder.visit_stmts(stmts);
+        finder.found
+    }
+
+    fn guard(&mut self, cond: &Expr, guarded: bool) {
+        if let Some(loc) = compared_block_number(cond)
+            && guarded
+        {
+            self.guards.push(loc);
+        }
+    }
+
+    fn walk(&mut self, stmts: &[Stmt]) {
+        for (i, stmt) in stmts.iter().enumerate() {
+            let rest = &stmts[i + 1..];
+            match stmt {
+                Stmt::If(s) => {
+                    let else_body = s.else_body.as_deref().unwrap_or_default();
+                    let guarded = self.has_effect(&s.then_body)
+                        || self.has_effect(else_body)
+                        || (always_exits(&s.then_body) && self.has_effect(rest));
+                    self.guard(&s.cond, guarded);
+                    self.walk(&s.then_body);
+                    self.walk(else_body);
+                }
+                Stmt::While(s) => {
+                    self.guard(&s.cond, self.has_effect(&s.body));
+                    self.walk(&s.body);
+                }
+                Stmt::For(s) => {
+                    if let Some(cond) = &s.cond {
+                        self.guard(cond, self.has_effect(&s.body));
+                    }
+                    self.walk(&s.body);
+                }
+                Stmt::Assert(a) => self.guard(&a.cond, self.has_effect(rest)),
+                Stmt::Block(inner) => self.walk(inner),
+                Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+                    self.walk(&tc.body);
+                    for clause in &tc.catch_clauses {
+                        self.walk(&clause.body);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl ScanDetector for BlockNumberDependenceDetector {
+    fn id(&self) -> &'static str {
+        "block-number-dependence"
+    }
+
+    fn name(&self) -> &'static str {
+        "Block Number Dependence"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects block.number used as a clock in conditions guarding state \
+         changes or value transfers"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::TimeManipulation
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![829]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![116]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Measure time with `block.timestamp` and tolerate small drifts, \
+         rather than assuming a fixed block time."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://swcregistry.io/docs/SWC-116"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let storage: HashSet<String> = contract.storage_names().into_iter().collect();
+        let mut walker = GuardWalker { storage: &storage, guards: vec![] };
+        walker.walk(func.body.as_deref().unwrap_or_default());
+
+        walker
+            .guards
+            .into_iter()
+            .map(|loc| {
+                Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "'{}.{}' uses block.number as a clock in a condition guarding \
+                         state changes or value transfers.",
+                        contract.name, func.name
+                    )),
+                    loc,
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_function, storage, var};
+    use scirs::sir::dialect::evm::{EvmBlockNumber, EvmEmitEvent};
+    use scirs::sir::*;
+
+    fn block_number() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::BlockNumber(EvmBlockNumber {
+            loc: Loc::new(3, 17, 3, 29),
+        })))
+    }
+
+    /// `require(block.number >= unlockBlock)`
+    fn require_unlocked() -> Stmt {
+        Stmt::Assert(AssertStmt {
+            cond: binop(BinOp::Ge, block_number(), var("unlockBlock")),
+            message: None,
+            span: None,
+        })
+    }
+
+    /// `<name> = 0`
+    fn assign(name: &str) -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var(name), rhs: var("zero"), span: None })
+    }
+
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let func = FunctionDecl::new("withdraw".to_string(), vec![], vec![], Some(body), None);
+        check_function(&BlockNumberDependenceDetector::new(), &func, vec![storage("locked")])
+    }
+
+    #[test]
+    fn test_block_number_dependence_detector() {
+        let detector = BlockNumberDependenceDetector::new();
+        assert_eq!(detector.id(), "block-number-dependence");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_guard_of_state_change_is_flagged() {
+        let bugs = check(vec![require_unlocked(), assign("locked")]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].loc.start_line, 3);
+
+        // Only local variables change after the check.
+        assert!(check(vec![require_unlocked(), assign("tmp")]).is_empty());
+    }
+
+    #[test]
+    fn test_recorded_block_number_is_not_flagged() {
+        // `emit Locked(block.number); locked = 0;`
+        let emit = Stmt::Dialect(DialectStmt::Evm(EvmStmt::EmitEvent(EvmEmitEvent {
+            event: "Locked".to_string(),
+            args: vec![block_number()],
+            loc: Loc::new(5, 9, 5, 35),
+        })));
+        assert!(check(vec![emit, assign("locked")]).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+//! Complex Signature Detector
+//!
+//! Detects functions that take more parameters than a configurable limit.
+//! Long parameter lists are hard to call correctly (arguments of the same
+//! type are easily swapped) and usually point to a function doing too much.
+//!
+//! The limit is read from the `max_params` detector parameter.
+
+use crate::detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::{ContractDecl, FunctionDecl, Module};
+
+/// Default maximum number of parameters.
+pub const DEFAULT_MAX_PARAMS: usize = 7;
+
+/// Scan detector for functions with too many parameters.
+#[derive(Debug)]
+pub struct ComplexSignatureDetector {
+    max_params: usize,
+}
+
+impl ComplexSignatureDetector {
+    pub fn new() -> Self {
+        Self { max_params: DEFAULT_MAX_PARAMS }
+    }
+
+    /// Create a detector flagging functions with more than `max_params`
+    /// parameters.
+    pub fn with_max_params(max_params: usize) -> Self {
+        Self { max_params }
+    }
+}
+
+impl Default for ComplexSignatureDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScanDetector for ComplexSignatureDetector {
+    fn id(&self) -> &'static str {
+        "complex-signature"
+    }
+
+    fn name(&self) -> &'static str {
+        "Complex Signature"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects functions with more parameters than the configured limit"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1064]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Group related parameters into a struct, or split the function into \
+         smaller functions with narrower responsibilities."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/1064.html"]
+    }
+
+    fn configure(&mut self, params: &DetectorParams) {
+        if let Some(max) = params.get("max_params").and_then(|v| v.parse().ok()) {
+            self.max_params = max;
+        }
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        vec!["max_params"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if func.params.len() > self.max_params {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "Function '{}.{}' takes {} parameters (limit: {}).",
+                    contract.name,
+                    func.name,
+                    func.params.len(),
+                    self.max_params
+                )),
+                func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::*;
+
+    fn check(detector: &ComplexSignatureDetector, num_params: usize) -> Vec<Bug> {
+        let params = (0..num_params)
+            .map(|i| Param::new(format!("p{i}"), Type::I256))
+            .collect();
+        let func = FunctionDecl::new("swap".to_string(), params, vec![], Some(vec![]), None);
+        let contract = ContractDecl {
+            name: "Router".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        detector.check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_complex_signature_detector() {
+        let detector = ComplexSignatureDetector::new();
+        assert_eq!(detector.id(), "complex-signature");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_parameter_limit() {
+        let detector = ComplexSignatureDetector::new();
+        assert_eq!(check(&detector, DEFAULT_MAX_PARAMS + 1).len(), 1);
+        assert!(check(&detector, DEFAULT_MAX_PARAMS).is_empty());
+    }
+
+    #[test]
+    fn test_limit_from_params() {
+        let mut detector = ComplexSignatureDetector::new();
+        let params = DetectorParams::from([("max_params".to_string(), "2".to_string())]);
+        detector.configure(&params);
+        assert_eq!(check(&detector, 3).len(), 1);
+    }
+}
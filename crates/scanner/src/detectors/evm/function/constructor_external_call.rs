@@ -0,0 +1,282 @@
+//! Constructor External Call Detector
+//!
+//! Detects constructors that call into an address supplied as a constructor
+//! argument, e.g.
+//!
+//! ```solidity
+//! constructor(IOracle oracle) {
+//!     price = oracle.latestPrice();
+//! }
+//! ```
+//!
+//! The deployer (or a front-runner replaying the deployment) picks that
+//! address, the code behind it may not exist yet, and the callee runs while
+//! the new contract is only partially initialized. Arguments are tracked
+//! through local and storage assignments within the constructor; calls to
+//! constants or to addresses not derived from an argument are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, CallExpr, ContractDecl, DialectExpr, Expr, FunctionDecl, LocalVarStmt, Module,
+    Type, VarExpr,
+};
+use std::collections::HashSet;
+
+/// Scan detector for constructor calls to argument-supplied addresses.
+#[derive(Debug, Default)]
+pub struct ConstructorExternalCallDetector;
+
+impl ConstructorExternalCallDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Walks a constructor body in order, propagating argument taint through
+/// assignments and collecting the calls made on tainted targets.
+struct TaintWalker {
+    tainted: HashSet<String>,
+    calls: Vec<(String, Option<Loc>)>,
+}
+
+impl TaintWalker {
+    fn is_tainted(&self, expr: &Expr) -> bool {
+        struct Reads<'t> {
+            tainted: &'t HashSet<String>,
+            found: bool,
+        }
+        impl<'a> Visit<'a> for Reads<'_> {
+            fn visit_var_expr(&mut self, var: &'a VarExpr) {
+                self.found |= self.tainted.contains(&var.name);
+            }
+        }
+        let mut reads = Reads { tainted: &self.tainted, found: false };
+        reads.visit_expr(expr);
+        reads.found
+    }
+
+    fn report(&mut self, target: &Expr, loc: Option<&Loc>) {
+        if self.is_tainted(target) {
+            self.calls.push((target.to_string(), loc.cloned()));
+        }
+    }
+}
+
+impl<'a> Visit<'a> for TaintWalker {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        if let Some(init) = &stmt.init
+            && self.is_tainted(init)
+        {
+            let names = stmt.vars.iter().flatten().map(|v| v.name.clone());
+            self.tainted.extend(names);
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        visit::default::visit_assign_stmt(self, stmt);
+        if self.is_tainted(&stmt.rhs)
+            && let Some(name) = root_name(&stmt.lhs)
+        {
+            self.tainted.insert(name.to_string());
+        }
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        // `target.f(..)` on a contract-typed receiver, or `target.call(..)`.
+        if let Expr::FieldAccess(fa) = &*expr.callee
+            && (matches!(fa.base.typ(), Type::TypeRef(_))
+                || matches!(fa.field.as_str(), "call" | "delegatecall" | "staticcall"))
+        {
+            self.report(&fa.base, expr.span.as_ref());
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        let (target, loc) = match expr {
+            DialectExpr::Evm(EvmExpr::LowLevelCall(e)) => (&e.target, &e.loc),
+            DialectExpr::Evm(EvmExpr::Delegatecall(e)) => (&e.target, &e.loc),
+            DialectExpr::Evm(EvmExpr::RawCall(e)) => (&e.target, &e.loc),
+            _ => return,
+        };
+        self.report(target, Some(loc));
+    }
+}
+
+impl ScanDetector for ConstructorExternalCallDetector {
+    fn id(&self) -> &'static str {
+        "constructor-external-call"
+    }
+
+    fn name(&self) -> &'static str {
+        "Constructor External Call"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects external calls in constructors to addresses passed as \
+         constructor arguments"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![829]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Avoid calling into argument-supplied addresses during deployment. \
+         Store the address and perform the call in a separate, access \
+         controlled initialization step, or validate the target (non-zero, \
+         has code, expected interface) before calling it."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/829.html"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if !func.is_constructor() {
+            return bugs;
+        }
+
+        let mut walker = TaintWalker {
+            tainted: func.params.iter().map(|p| p.name.clone()).collect(),
+            calls: vec![],
+        };
+        walker.visit_function_decl(func);
+
+        for (target, loc) in walker.calls {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "The constructor of '{}' calls '{}', an address supplied by the \
+                     deployer.",
+                    contract.name, target
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::*;
+
+    fn oracle(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), Type::TypeRef("IOracle".to_string()), None))
+    }
+
+    /// `price = <target>.latestPrice()`
+    fn query(target: Expr) -> Stmt {
+        let call = Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(target),
+                field: "latestPrice".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![]),
+            ty: Type::I256,
+            span: None,
+        });
+        Stmt::Assign(AssignStmt {
+            lhs: Expr::Var(VarExpr::new("price".to_string(), Type::I256, None)),
+            rhs: call,
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>, is_constructor: bool) -> Vec<Bug> {
+        let param = Param::new("_oracle".to_string(), Type::TypeRef("IOracle".to_string()));
+        let mut func =
+            FunctionDecl::new("Feed".to_string(), vec![param], vec![], Some(body), None);
+        if is_constructor {
+            func.attrs
+                .push(Attr::evm(evm_attrs::IS_CONSTRUCTOR, AttrValue::Bool(true)));
+        }
+        let contract = ContractDecl::new("Feed".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        ConstructorExternalCallDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_constructor_external_call_detector() {
+        let detector = ConstructorExternalCallDetector::new();
+        assert_eq!(detector.id(), "constructor-external-call");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_call_to_argument_is_flagged() {
+        // oracle = _oracle; price = oracle.latestPrice();
+        let store =
+            Stmt::Assign(AssignStmt { lhs: oracle("oracle"), rhs: oracle("_oracle"), span: None });
+        assert_eq!(check(vec![query(oracle("_oracle"))], true).len(), 1);
+        assert_eq!(check(vec![store, query(oracle("oracle"))], true).len(), 1);
+    }
+
+    #[test]
+    fn test_untainted_or_non_constructor_call_is_not_flagged() {
+        assert!(check(vec![query(oracle("CHAINLINK_FEED"))], true).is_empty());
+        assert!(check(vec![query(oracle("_oracle"))], false).is_empty());
+    }
+}
@@ -0,0 +1,433 @@
+//! Dangling Storage Reference Detector
+//!
+//! Detects storage pointers obtained from an internal function that are
+//! used after the array element they point to was removed:
+//!
+//! ```solidity
+//! function _last() internal view returns (Order storage) {
+//!     return orders[orders.length - 1];
+//! }
+//!
+//! function cancelLast() external {
+//!     Order storage order = _last();
+//!     orders.pop();
+//!     order.owner.transfer(order.deposit);  // reads a deleted slot
+//! }
+//! ```
+//!
+//! `pop` (or `delete`, or reassigning the array) clears the element, but the
+//! pointer still refers to its slot: reads see zeroes and writes resurrect
+//! data past the end of the array. The array behind a pointer is the one
+//! indexed by the callee's `return`; the caller is walked in program order,
+//! so a pointer is dangling after any path through an earlier branch may
+//! have removed its element.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::EvmFunctionExt;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, CallExpr, ContractDecl, Decl, Expr, FunctionDecl, LocalVarStmt, MemberDecl,
+    Module, ReturnStmt, UnOp, UnOpExpr, VarExpr,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Scan detector for storage pointers used after their element was removed.
+#[derive(Debug, Default)]
+pub struct DanglingStorageRefDetector;
+
+impl DanglingStorageRefDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Path of an array expression such as `items` or `book.orders`.
+fn array_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Var(v) => Some(v.name.clone()),
+        Expr::FieldAccess(fa) => Some(format!("{}.{}", array_path(&fa.base)?, fa.field)),
+        _ => None,
+    }
+}
+
+/// Arrays whose elements `func` returns a pointer to, e.g. `orders` for
+/// `return orders[i];`, either directly or through a named return value.
+fn returned_arrays(func: &FunctionDecl) -> HashSet<String> {
+    struct Finder<'f> {
+        return_names: &'f [Option<String>],
+        arrays: HashSet<String>,
+    }
+    impl Finder<'_> {
+        fn add(&mut self, expr: &Expr) {
+            if let Expr::IndexAccess(ia) = expr
+                && let Some(path) = array_path(&ia.base)
+            {
+                self.arrays.insert(path);
+            }
+        }
+    }
+    impl<'a> Visit<'a> for Finder<'_> {
+        fn visit_return_stmt(&mut self, stmt: &'a ReturnStmt) {
+            if let Some(value) = &stmt.value {
+                self.add(value);
+            }
+            visit::default::visit_return_stmt(self, stmt);
+        }
+
+        fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+            if let Expr::Var(v) = &stmt.lhs
+                && self.return_names.iter().flatten().any(|n| *n == v.name)
+            {
+                self.add(&stmt.rhs);
+            }
+            visit::default::visit_assign_stmt(self, stmt);
+        }
+    }
+
+    let mut finder = Finder { return_names: &func.return_names, arrays: HashSet::new() };
+    if let Some(body) = &func.body {
+        finder.visit_stmts(body);
+    }
+    finder.arrays
+}
+
+/// Functions of `contract` and its ancestors in `module` that return a
+/// pointer into a storage array, with the arrays they point into.
+fn storage_getters(contract: &ContractDecl, module: &Module) -> HashMap<String, HashSet<String>> {
+    let mut getters: HashMap<String, HashSet<String>> = HashMap::new();
+    let find = |name: &str| {
+        if name == contract.name {
+            return Some(contract);
+        }
+        module.decls.iter().find_map(|d| match d {
+            Decl::Contract(c) if c.name == name => Some(c),
+            _ => None,
+        })
+    };
+    let mut seen = HashSet::new();
+    let mut worklist = vec![contract.name.as_str()];
+    while let Some(name) = worklist.pop() {
+        if !seen.insert(name) {
+            continue;
+        }
+        let Some(c) = find(name) else {
+            continue;
+        };
+        worklist.extend(c.parents.iter().map(String::as_str));
+        for member in &c.members {
+            if let MemberDecl::Function(f) = member
+                && f.returns_storage_ref()
+            {
+                let arrays = returned_arrays(f);
+                if !arrays.is_empty() {
+                    getters.entry(f.name.clone()).or_default().extend(arrays);
+                }
+            }
+        }
+    }
+    getters
+}
+
+/// A local holding a pointer returned by a storage getter.
+struct Pointer {
+    arrays: HashSet<String>,
+    /// Span of the statement that removed the element, once it has.
+    removed_at: Option<Option<Loc>>,
+}
+
+/// Walks a function in program order, tracking pointers and reporting the
+/// first use of each after its element was removed.
+struct Walker<'g> {
+    getters: &'g HashMap<String, HashSet<String>>,
+    pointers: HashMap<String, Pointer>,
+    /// Pointer, use span and removal span of each dangling use.
+    hits: Vec<(String, Option<Loc>, Option<Loc>)>,
+}
+
+impl Walker<'_> {
+    fn bind(&mut self, name: &str, init: &Expr) {
+        let arrays = match init {
+            Expr::FunctionCall(call) => match &*call.callee {
+                Expr::Var(callee) => self.getters.get(&callee.name).cloned(),
+                _ => None,
+            },
+            Expr::Var(v) => self.pointers.get(&v.name).map(|p| p.arrays.clone()),
+            _ => None,
+        };
+        match arrays {
+            Some(arrays) => {
+                self.pointers
+                    .insert(name.to_string(), Pointer { arrays, removed_at: None });
+            }
+            None => {
+                self.pointers.remove(name);
+            }
+        }
+    }
+
+    fn remove_elements(&mut self, array: Option<String>, span: Option<&Loc>) {
+        let Some(array) = array else {
+            return;
+        };
+        for pointer in self.pointers.values_mut() {
+            if pointer.removed_at.is_none() && pointer.arrays.contains(&array) {
+                pointer.removed_at = Some(span.cloned());
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for Walker<'_> {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        if let [Some(var)] = stmt.vars.as_slice() {
+            match &stmt.init {
+                Some(init) => self.bind(&var.name, init),
+                None => {
+                    self.pointers.remove(&var.name);
+                }
+            }
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        // Re-pointing a local is not a use of its old target.
+        if let Expr::Var(v) = &stmt.lhs
+            && self.pointers.contains_key(&v.name)
+        {
+            self.visit_expr(&stmt.rhs);
+            self.bind(&v.name, &stmt.rhs);
+            return;
+        }
+        visit::default::visit_assign_stmt(self, stmt);
+        self.remove_elements(array_path(&stmt.lhs), stmt.span.as_ref());
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        visit::default::visit_call_expr(self, expr);
+        if let Expr::FieldAccess(fa) = &*expr.callee
+            && fa.field == "pop"
+        {
+            self.remove_elements(array_path(&fa.base), expr.span.as_ref());
+        }
+    }
+
+    fn visit_unop_expr(&mut self, expr: &'a UnOpExpr) {
+        visit::default::visit_unop_expr(self, expr);
+        if expr.op == UnOp::Delete {
+            self.remove_elements(array_path(&expr.operand), expr.span.as_ref());
+        }
+    }
+
+    fn visit_var_expr(&mut self, expr: &'a VarExpr) {
+        // Each dangling pointer is reported once, then forgotten.
+        if self
+            .pointers
+            .get(&expr.name)
+            .is_some_and(|p| p.removed_at.is_some())
+            && let Some(Pointer { removed_at: Some(removed_at), .. }) =
+                self.pointers.remove(&expr.name)
+        {
+            self.hits
+                .push((expr.name.clone(), expr.span.clone(), removed_at));
+        }
+        visit::default::visit_var_expr(self, expr);
+    }
+}
+
+impl ScanDetector for DanglingStorageRefDetector {
+    fn id(&self) -> &'static str {
+        "dangling-storage-ref"
+    }
+
+    fn name(&self) -> &'static str {
+        "Dangling Storage Reference"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects storage pointers returned by internal functions that are used \
+         after the array element they point to was removed"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![825]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Read what is needed through the storage pointer before removing the \
+         element, or copy the element to memory first."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/types.html#dangling-references-to-storage-array-elements",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let getters = storage_getters(contract, module);
+        if getters.is_empty() {
+            return bugs;
+        }
+        let mut walker = Walker { getters: &getters, pointers: HashMap::new(), hits: vec![] };
+        walker.visit_function_decl(func);
+
+        for (pointer, loc, removed_at) in walker.hits {
+            let removed = removed_at
+                .map(|l| format!(" (removed at line {})", l.start_line))
+                .unwrap_or_default();
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' uses the storage pointer '{}' after the element it \
+                     points to was removed{}.",
+                    contract.name, func.name, pointer, removed
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_function;
+    use scirs::sir::*;
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), Type::None, None))
+    }
+
+    fn call(callee: Expr) -> Expr {
+        Expr::FunctionCall(CallExpr {
+            callee: Box::new(callee),
+            args: CallArgs::Positional(vec![]),
+            ty: Type::None,
+            span: None,
+        })
+    }
+
+    fn field(base: Expr, name: &str) -> Expr {
+        Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(base),
+            field: name.to_string(),
+            ty: Type::None,
+            span: None,
+        })
+    }
+
+    /// `order.deposit = refund;`
+    fn use_order() -> Stmt {
+        Stmt::Assign(AssignStmt {
+            lhs: field(var("order"), "deposit"),
+            rhs: var("refund"),
+            span: None,
+        })
+    }
+
+    /// A contract with `_last() internal returns (Order storage) { return
+    /// orders[0]; }` and `cancel() { Order storage order = _last(); <body> }`.
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let ret = Stmt::Return(ReturnStmt {
+            value: Some(Expr::IndexAccess(IndexAccessExpr {
+                base: Box::new(var("orders")),
+                index: Some(Box::new(var("i"))),
+                ty: Type::TypeRef("Order".to_string()),
+                span: None,
+            })),
+            span: None,
+        });
+        let ty = Type::TypeRef("Order".to_string());
+        let mut last = FunctionDecl::new(
+            "_last".to_string(),
+            vec![],
+            vec![ty.clone()],
+            Some(vec![ret]),
+            None,
+        );
+        last.attrs
+            .push(Attr::evm(evm_attrs::RETURNS_STORAGE_REF, AttrValue::Bool(true)));
+
+        let mut stmts = vec![Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl { name: "order".to_string(), ty })],
+            init: Some(call(var("_last"))),
+            span: None,
+        })];
+        stmts.extend(body);
+        let cancel = FunctionDecl::new("cancel".to_string(), vec![], vec![], Some(stmts), None);
+        let members = vec![
+            MemberDecl::Function(last),
+            MemberDecl::Function(cancel.clone()),
+        ];
+        check_function(&DanglingStorageRefDetector::new(), &cancel, members)
+    }
+
+    fn pop() -> Stmt {
+        Stmt::Expr(ExprStmt { expr: call(field(var("orders"), "pop")), span: None })
+    }
+
+    #[test]
+    fn test_dangling_storage_ref_detector() {
+        let detector = DanglingStorageRefDetector::new();
+        assert_eq!(detector.id(), "dangling-storage-ref");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_use_after_pop_is_flagged() {
+        let bugs = check(vec![pop(), use_order()]);
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_use_before_pop_is_not_flagged() {
+        assert!(check(vec![use_order(), pop()]).is_empty());
+    }
+}
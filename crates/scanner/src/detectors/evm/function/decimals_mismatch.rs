@@ -0,0 +1,286 @@
+//! Decimals Mismatch Detector
+//!
+//! Detects arithmetic and comparisons that mix amounts read from two
+//! different tokens without normalizing their decimals:
+//!
+//! ```solidity
+//! uint256 total = tokenA.balanceOf(address(this)) + tokenB.balanceOf(address(this));
+//! ```
+//!
+//! USDC has 6 decimals and DAI has 18, so adding or comparing raw amounts of
+//! the two is off by a factor of 10^12. Each value is tagged with the token it
+//! was read from (`balanceOf`, `allowance`, `totalSupply`), and the tag is
+//! followed through local and storage assignments. Multiplying or dividing a
+//! value drops its tag, since that is how amounts are normally rescaled.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmCallExt;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, BinOp, BinOpExpr, ContractDecl, Expr, FunctionDecl, LocalVarStmt,
+    Module, Type,
+};
+use std::collections::HashMap;
+
+/// ERC20 getters returning an amount denominated in the token's decimals.
+const AMOUNT_GETTERS: &[&str] = &["balanceOf", "allowance", "totalSupply"];
+
+/// Scan detector for arithmetic mixing amounts of different tokens.
+#[derive(Debug, Default)]
+pub struct DecimalsMismatchDetector;
+
+impl DecimalsMismatchDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Tracks which token each variable's amount was read from, in program
+/// order, and records expressions combining amounts of two tokens.
+#[derive(Default)]
+struct OriginWalker {
+    origins: HashMap<String, String>,
+    /// The two tokens and the span of each mixing expression.
+    mixes: Vec<(String, String, Option<Loc>)>,
+}
+
+impl OriginWalker {
+    /// Token whose decimals `expr` is denominated in, if known.
+    fn origin(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::FunctionCall(call) => AMOUNT_GETTERS.iter().find_map(|getter| {
+                call.method_call_on_type(getter, |ty| matches!(ty, Type::TypeRef(_)))
+                    .map(|token| token.to_string())
+            }),
+            Expr::Var(v) => self.origins.get(&v.name).cloned(),
+            Expr::IndexAccess(_) | Expr::FieldAccess(_) => {
+                root_name(expr).and_then(|name| self.origins.get(name).cloned())
+            }
+            Expr::BinOp(b) if matches!(b.op, BinOp::Add | BinOp::Sub | BinOp::Mod) => {
+                self.origin(&b.lhs).or_else(|| self.origin(&b.rhs))
+            }
+            _ => None,
+        }
+    }
+
+    fn check_mix(&mut self, lhs: &Expr, rhs: &Expr, span: Option<&Loc>) {
+        if let (Some(a), Some(b)) = (self.origin(lhs), self.origin(rhs))
+            && a != b
+        {
+            self.mixes.push((a, b, span.cloned()));
+        }
+    }
+
+    fn assign(&mut self, name: &str, rhs: &Expr) {
+        match self.origin(rhs) {
+            Some(token) => self.origins.insert(name.to_string(), token),
+            None => self.origins.remove(name),
+        };
+    }
+}
+
+impl<'a> Visit<'a> for OriginWalker {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        if let Some(init) = &stmt.init
+            && let [Some(var)] = stmt.vars.as_slice()
+        {
+            self.assign(&var.name, init);
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        visit::default::visit_assign_stmt(self, stmt);
+        if let Some(name) = root_name(&stmt.lhs) {
+            self.assign(name, &stmt.rhs);
+        }
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        if matches!(stmt.op, BinOp::Add | BinOp::Sub) {
+            self.check_mix(&stmt.lhs, &stmt.rhs, stmt.span.as_ref());
+        }
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        if matches!(
+            expr.op,
+            BinOp::Add
+                | BinOp::Sub
+                | BinOp::Eq
+                | BinOp::Ne
+                | BinOp::Lt
+                | BinOp::Le
+                | BinOp::Gt
+                | BinOp::Ge
+        ) {
+            self.check_mix(&expr.lhs, &expr.rhs, expr.span.as_ref());
+        }
+        visit::default::visit_binop_expr(self, expr);
+    }
+}
+
+impl ScanDetector for DecimalsMismatchDetector {
+    fn id(&self) -> &'static str {
+        "decimals-mismatch"
+    }
+
+    fn name(&self) -> &'static str {
+        "Decimals Mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects arithmetic or comparisons mixing amounts of two different \
+         tokens without normalizing their decimals"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Arithmetic
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![682]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Scale amounts of different tokens to a common precision before \
+         combining them, e.g. `amount * 10**(18 - token.decimals())`, or \
+         convert them through a price oracle."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://eips.ethereum.org/EIPS/eip-20#decimals"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20", "defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut walker = OriginWalker::default();
+        walker.visit_function_decl(func);
+
+        for (a, b, loc) in walker.mixes {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' combines amounts of '{}' and '{}' without \
+                     normalizing their decimals.",
+                    contract.name, func.name, a, b
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, typed_var};
+    use scirs::sir::*;
+
+    /// `<token>.balanceOf(this)`
+    fn balance_of(token: &str) -> Expr {
+        Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(typed_var(token, Type::TypeRef("IERC20".to_string()))),
+                field: "balanceOf".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![typed_var("this", Type::None)]),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    /// `total = <rhs>;`
+    fn check(rhs: Expr) -> Vec<Bug> {
+        let body = vec![Stmt::Assign(AssignStmt {
+            lhs: typed_var("total", Type::I256),
+            rhs,
+            span: None,
+        })];
+        let func = FunctionDecl::new("tvl".to_string(), vec![], vec![], Some(body), None);
+        let contract = ContractDecl::new("Vault".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        DecimalsMismatchDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_decimals_mismatch_detector() {
+        let detector = DecimalsMismatchDetector::new();
+        assert_eq!(detector.id(), "decimals-mismatch");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_mixing_two_token_balances_is_flagged() {
+        let sum = binop(BinOp::Add, balance_of("usdc"), balance_of("dai"));
+        assert_eq!(check(sum).len(), 1);
+    }
+
+    #[test]
+    fn test_same_token_or_scaled_amounts_are_not_flagged() {
+        let same = binop(BinOp::Add, balance_of("usdc"), balance_of("usdc"));
+        assert!(check(same).is_empty());
+
+        // usdc.balanceOf(this) * 1e12 + dai.balanceOf(this)
+        let scaled = binop(BinOp::Mul, balance_of("usdc"), typed_var("SCALE", Type::I256));
+        assert!(check(binop(BinOp::Add, scaled, balance_of("dai"))).is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+//! Deep Nesting Detector
+//!
+//! Detects functions whose control flow is nested deeper than a configurable
+//! limit. Every `if`, loop and `try` adds one level; `else if` chains stay at
+//! the level of their first branch, and the `if (!c) revert` produced by
+//! `require` does not count.
+//!
+//! The limit is read from the `max_depth` detector parameter.
+
+use crate::detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmStmt;
+use scirs::sir::{ContractDecl, DialectStmt, FunctionDecl, Module, Stmt};
+
+/// Default maximum nesting depth.
+pub const DEFAULT_MAX_DEPTH: usize = 4;
+
+/// Scan detector for deeply nested function bodies.
+#[derive(Debug)]
+pub struct DeepNestingDetector {
+    max_depth: usize,
+}
+
+impl DeepNestingDetector {
+    pub fn new() -> Self {
+        Self { max_depth: DEFAULT_MAX_DEPTH }
+    }
+
+    /// Create a detector flagging bodies nested deeper than `max_depth`.
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self { max_depth }
+    }
+}
+
+impl Default for DeepNestingDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maximum nesting depth of a statement list.
+fn nesting_depth(stmts: &[Stmt]) -> usize {
+    stmts.iter().map(stmt_depth).max().unwrap_or(0)
+}
+
+fn stmt_depth(stmt: &Stmt) -> usize {
+    match stmt {
+        Stmt::If(s) => {
+            if matches!(s.then_body.as_slice(), [Stmt::Revert(_)]) && s.else_body.is_none() {
+                return 0;
+            }
+            let else_depth = match s.else_body.as_deref() {
+                // `else if` continues the chain at the same level.
+                Some([elif @ Stmt::If(_)]) => stmt_depth(elif).saturating_sub(1),
+                Some(body) => nesting_depth(body),
+                None => 0,
+            };
+            1 + nesting_depth(&s.then_body).max(else_depth)
+        }
+        Stmt::While(s) => 1 + nesting_depth(&s.body),
+        Stmt::For(s) => 1 + nesting_depth(&s.body),
+        Stmt::Block(inner) => nesting_depth(inner),
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+            let catch_depth = tc.catch_clauses.iter().map(|c| nesting_depth(&c.body));
+            1 + catch_depth.fold(nesting_depth(&tc.body), usize::max)
+        }
+        _ => 0,
+    }
+}
+
+impl ScanDetector for DeepNestingDetector {
+    fn id(&self) -> &'static str {
+        "deep-nesting"
+    }
+
+    fn name(&self) -> &'static str {
+        "Deep Nesting"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects functions whose control flow is nested deeper than the \
+         configured limit"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1124]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Flatten the control flow with early returns or `require` guards, or \
+         move nested blocks into helper functions."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/1124.html"]
+    }
+
+    fn configure(&mut self, params: &DetectorParams) {
+        if let Some(max) = params.get("max_depth").and_then(|v| v.parse().ok()) {
+            self.max_depth = max;
+        }
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        vec!["max_depth"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let depth = func.body.as_deref().map(nesting_depth).unwrap_or(0);
+        if depth > self.max_depth {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "Function '{}.{}' nests control flow {} levels deep (limit: {}).",
+                    contract.name, func.name, depth, self.max_depth
+                )),
+                func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_body, typed_var};
+    use scirs::sir::*;
+
+    /// `depth` nested `if (c) { ... }` statements.
+    fn nested_ifs(depth: usize) -> Vec<Stmt> {
+        (0..depth).fold(vec![], |body, _| {
+            vec![Stmt::If(IfStmt {
+                cond: typed_var("c", Type::Bool),
+                then_body: body,
+                else_body: None,
+                span: None,
+            })]
+        })
+    }
+
+    #[test]
+    fn test_deep_nesting_detector() {
+        let detector = DeepNestingDetector::new();
+        assert_eq!(detector.id(), "deep-nesting");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_nesting_over_limit_is_flagged() {
+        assert_eq!(
+            check_body(&DeepNestingDetector::new(), nested_ifs(DEFAULT_MAX_DEPTH + 1)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_nesting_under_limit_is_not_flagged() {
+        assert!(check_body(&DeepNestingDetector::new(), nested_ifs(DEFAULT_MAX_DEPTH)).is_empty());
+    }
+
+    #[test]
+    fn test_else_if_chain_is_not_nesting() {
+        // if (c) {} else if (c) {} else if (c) {} ...
+        let chain = (0..DEFAULT_MAX_DEPTH + 2).fold(None, |rest: Option<Vec<Stmt>>, _| {
+            Some(vec![Stmt::If(IfStmt {
+                cond: typed_var("c", Type::Bool),
+                then_body: vec![],
+                else_body: rest,
+                span: None,
+            })])
+        });
+        assert!(check_body(&DeepNestingDetector::new(), chain.unwrap()).is_empty());
+    }
+}
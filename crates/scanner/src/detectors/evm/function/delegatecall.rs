@@ -1,13 +1,31 @@
 //! Delegatecall Detector
 //!
-//! Detects dangerous usage of delegatecall.
+//! Detects dangerous usage of delegatecall, graded by where the target
+//! address comes from:
+//!
+//! ```solidity
+//! function run(address lib, bytes calldata data) external {
+//!     lib.delegatecall(data);             // High: caller-chosen target
+//!     implementation.delegatecall(data);  // Medium: mutable storage
+//!     IMPLEMENTATION.delegatecall(data);  // Low: constant or immutable
+//! }
+//! ```
+//!
+//! Parameter taint is tracked through local and storage assignments within
+//! the function. Targets that are literals, `this`, or constant or immutable
+//! state variables (directly or through a local copy) are fixed at
+//! deployment, which covers proxies with a fixed implementation slot.
 
 use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
 use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
 use common::loc::Loc;
-use scirs::sir::dialect::evm::EvmExpr;
+use scirs::sir::dialect::evm::{EvmExpr, EvmStorageExt};
 use scirs::sir::utils::visit::{self, Visit};
-use scirs::sir::{ContractDecl, DialectExpr, FieldAccessExpr, FunctionDecl, Module};
+use scirs::sir::{
+    AssignStmt, ContractDecl, DialectExpr, Expr, FieldAccessExpr, FunctionDecl, LocalVarStmt,
+    MemberDecl, Module, VarExpr,
+};
+use std::collections::HashSet;
 
 /// Scan detector for delegatecall usage.
 #[derive(Debug, Default)]
@@ -19,6 +37,118 @@ impl DelegatecallDetector {
     }
 }
 
+/// Where a delegatecall target comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetOrigin {
+    /// Derived from a function parameter.
+    Parameter,
+    /// A literal, `this`, or a constant or immutable state variable.
+    Fixed,
+    /// Anything else, e.g. mutable storage or a call result.
+    Unknown,
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Walks a function body in order, propagating parameter taint and fixed
+/// addresses through assignments, and collecting the delegatecalls with the
+/// origin of their target.
+struct TargetWalker {
+    tainted: HashSet<String>,
+    fixed: HashSet<String>,
+    calls: Vec<(String, TargetOrigin, Option<Loc>)>,
+}
+
+impl TargetWalker {
+    fn is_tainted(&self, expr: &Expr) -> bool {
+        struct Reads<'t> {
+            tainted: &'t HashSet<String>,
+            found: bool,
+        }
+        impl<'a> Visit<'a> for Reads<'_> {
+            fn visit_var_expr(&mut self, var: &'a VarExpr) {
+                self.found |= self.tainted.contains(&var.name);
+            }
+        }
+        let mut reads = Reads { tainted: &self.tainted, found: false };
+        reads.visit_expr(expr);
+        reads.found
+    }
+
+    fn is_fixed(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Lit(_) => true,
+            Expr::Var(v) => v.name == "this" || self.fixed.contains(&v.name),
+            Expr::TypeCast(e) => self.is_fixed(&e.expr),
+            _ => false,
+        }
+    }
+
+    fn origin(&self, target: &Expr) -> TargetOrigin {
+        if self.is_tainted(target) {
+            TargetOrigin::Parameter
+        } else if self.is_fixed(target) {
+            TargetOrigin::Fixed
+        } else {
+            TargetOrigin::Unknown
+        }
+    }
+
+    fn report(&mut self, target: &Expr, loc: Option<Loc>) {
+        self.calls
+            .push((target.to_string(), self.origin(target), loc));
+    }
+}
+
+impl<'a> Visit<'a> for TargetWalker {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        let Some(init) = &stmt.init else {
+            return;
+        };
+        let names = stmt.vars.iter().flatten().map(|v| v.name.clone());
+        if self.is_tainted(init) {
+            self.tainted.extend(names);
+        } else if let [Some(var)] = stmt.vars.as_slice()
+            && self.is_fixed(init)
+        {
+            self.fixed.insert(var.name.clone());
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        visit::default::visit_assign_stmt(self, stmt);
+        let Some(name) = root_name(&stmt.lhs) else {
+            return;
+        };
+        self.fixed.remove(name);
+        if self.is_tainted(&stmt.rhs) {
+            self.tainted.insert(name.to_string());
+        }
+    }
+
+    fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+        if let DialectExpr::Evm(EvmExpr::Delegatecall(e)) = d {
+            self.report(&e.target, Some(e.loc.clone()));
+        }
+    }
+
+    fn visit_field_access_expr(&mut self, fa: &'a FieldAccessExpr) {
+        if fa.field == "delegatecall" {
+            self.report(&fa.base, fa.span.clone());
+        }
+        visit::default::visit_field_access_expr(self, fa);
+    }
+}
+
 impl ScanDetector for DelegatecallDetector {
     fn id(&self) -> &'static str {
         "delegatecall"
@@ -75,6 +205,10 @@ impl ScanDetector for DelegatecallDetector {
         vec!["https://swcregistry.io/docs/SWC-112"]
     }
 
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["proxy"]
+    }
+
     fn check_function(
         &self,
         func: &FunctionDecl,
@@ -83,66 +217,62 @@ impl ScanDetector for DelegatecallDetector {
     ) -> Vec<Bug> {
         let mut bugs = Vec::new();
 
-        struct Visitor<'b> {
-            detector: &'b DelegatecallDetector,
-            bugs: &'b mut Vec<Bug>,
-            contract_name: String,
-            func_name: String,
-        }
-
-        impl<'a, 'b> Visit<'a> for Visitor<'b> {
-            fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
-                if let DialectExpr::Evm(EvmExpr::Delegatecall(e)) = d {
-                    self.bugs.push(Bug::new(
-                        self.detector.name(),
-                        Some(&format!(
-                            "Usage of delegatecall in '{}.{}'. \
-                             Delegatecall to an untrusted address can lead \
-                             to storage corruption and contract compromise.",
-                            self.contract_name, self.func_name
-                        )),
-                        e.loc.clone(),
-                        self.detector.bug_kind(),
-                        self.detector.bug_category(),
-                        self.detector.risk_level(),
-                        self.detector.cwe_ids(),
-                        self.detector.swc_ids(),
-                        Some(self.detector.recommendation()),
-                    ));
-                }
-            }
+        let mut walker = TargetWalker {
+            tainted: func.params.iter().map(|p| p.name.clone()).collect(),
+            fixed: contract
+                .members
+                .iter()
+                .filter_map(|m| match m {
+                    MemberDecl::Storage(s) if s.is_constant_storage() => Some(s.name.clone()),
+                    _ => None,
+                })
+                .collect(),
+            calls: vec![],
+        };
+        walker.visit_function_decl(func);
 
-            fn visit_field_access_expr(&mut self, fa: &'a FieldAccessExpr) {
-                if fa.field == "delegatecall" {
-                    self.bugs.push(Bug::new(
-                        self.detector.name(),
-                        Some(&format!(
-                            "Usage of delegatecall in '{}.{}'. \
-                             Delegatecall to an untrusted address can lead \
-                             to storage corruption and contract compromise.",
-                            self.contract_name, self.func_name
-                        )),
-                        fa.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
-                        self.detector.bug_kind(),
-                        self.detector.bug_category(),
-                        self.detector.risk_level(),
-                        self.detector.cwe_ids(),
-                        self.detector.swc_ids(),
-                        Some(self.detector.recommendation()),
-                    ));
-                }
-                visit::default::visit_field_access_expr(self, fa);
-            }
+        for (target, origin, loc) in walker.calls {
+            let (risk_level, message) = match origin {
+                TargetOrigin::Parameter => (
+                    RiskLevel::High,
+                    format!(
+                        "Delegatecall in '{}.{}' targets '{}', which is derived from a \
+                         function parameter. Any caller can run arbitrary code in the \
+                         context of the contract.",
+                        contract.name, func.name, target
+                    ),
+                ),
+                TargetOrigin::Unknown => (
+                    RiskLevel::Medium,
+                    format!(
+                        "Usage of delegatecall in '{}.{}'. Delegatecall to an untrusted \
+                         address can lead to storage corruption and contract compromise.",
+                        contract.name, func.name
+                    ),
+                ),
+                TargetOrigin::Fixed => (
+                    RiskLevel::Low,
+                    format!(
+                        "Delegatecall in '{}.{}' targets '{}', which is fixed at deployment. \
+                         Ensure the storage layouts of both contracts match.",
+                        contract.name, func.name, target
+                    ),
+                ),
+            };
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&message),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                risk_level,
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
         }
 
-        let mut visitor = Visitor {
-            detector: self,
-            bugs: &mut bugs,
-            contract_name: contract.name.clone(),
-            func_name: func.name.clone(),
-        };
-        visitor.visit_function_decl(func);
-
         bugs
     }
 }
@@ -150,6 +280,41 @@ impl ScanDetector for DelegatecallDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{check_function, var};
+    use scirs::sir::*;
+
+    /// `<target>.delegatecall(data)`
+    fn delegatecall(target: Expr) -> Stmt {
+        let call = Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(target),
+                field: "delegatecall".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![var("data")]),
+            ty: Type::None,
+            span: None,
+        });
+        Stmt::Expr(ExprStmt { expr: call, span: None })
+    }
+
+    /// Risk levels of the findings on `body`, in a contract with the
+    /// immutable `IMPLEMENTATION` and the mutable `implementation`.
+    fn check(body: Vec<Stmt>) -> Vec<RiskLevel> {
+        let mut fixed = StorageDecl::new("IMPLEMENTATION".to_string(), Type::I256, None, None);
+        fixed
+            .attrs
+            .push(Attr::evm(evm_attrs::IS_IMMUTABLE, AttrValue::Bool(true)));
+        let mutable = StorageDecl::new("implementation".to_string(), Type::I256, None, None);
+        let members = vec![MemberDecl::Storage(fixed), MemberDecl::Storage(mutable)];
+        let params = vec![Param::new("lib".to_string(), Type::I256)];
+        let func = FunctionDecl::new("run".to_string(), params, vec![], Some(body), None);
+        check_function(&DelegatecallDetector::new(), &func, members)
+            .iter()
+            .map(|bug| bug.risk_level)
+            .collect()
+    }
 
     #[test]
     fn test_delegatecall_detector() {
@@ -157,4 +322,32 @@ mod tests {
         assert_eq!(detector.id(), "delegatecall");
         assert_eq!(detector.risk_level(), RiskLevel::High);
     }
+
+    #[test]
+    fn test_parameter_target_is_high_severity() {
+        // target = lib; target.delegatecall(data);
+        let store = Stmt::Assign(AssignStmt { lhs: var("target"), rhs: var("lib"), span: None });
+        assert_eq!(check(vec![delegatecall(var("lib"))]), [RiskLevel::High]);
+        assert_eq!(check(vec![store, delegatecall(var("target"))]), [RiskLevel::High]);
+    }
+
+    #[test]
+    fn test_fixed_target_is_low_severity() {
+        // address impl = IMPLEMENTATION; impl.delegatecall(data);
+        let copy = Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl {
+                name: "impl".to_string(),
+                ty: Type::I256,
+            })],
+            init: Some(var("IMPLEMENTATION")),
+            span: None,
+        });
+        assert_eq!(check(vec![delegatecall(var("IMPLEMENTATION"))]), [RiskLevel::Low]);
+        assert_eq!(check(vec![copy, delegatecall(var("impl"))]), [RiskLevel::Low]);
+    }
+
+    #[test]
+    fn test_mutable_storage_target_is_medium_severity() {
+        assert_eq!(check(vec![delegatecall(var("implementation"))]), [RiskLevel::Medium]);
+    }
 }
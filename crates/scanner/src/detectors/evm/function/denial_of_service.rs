@@ -163,6 +163,10 @@ impl ScanDetector for DenialOfServiceDetector {
         ]
     }
 
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+
     fn check_function(
         &self,
         func: &FunctionDecl,
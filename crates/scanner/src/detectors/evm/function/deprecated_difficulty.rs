@@ -0,0 +1,199 @@
+//! Deprecated Difficulty Detector
+//!
+//! Detects reads of `block.difficulty` in modules targeting Solidity 0.8.18
+//! or later. Since the merge the field holds the beacon chain's
+//! `prevrandao` value, and 0.8.18 deprecated the old name in favour of
+//! `block.prevrandao`. Code still reading "difficulty" tends to assume
+//! properties (mining cost, unpredictability) it no longer has.
+//!
+//! Using either value as a randomness source is reported separately by the
+//! `bad-randomness` detector.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use scirs::sir::dialect::evm::{EvmExpr, EvmModuleExt};
+use scirs::sir::utils::visit::Visit;
+use scirs::sir::{ContractDecl, DialectExpr, FunctionDecl, Module};
+
+/// First Solidity version deprecating `block.difficulty`.
+const PREVRANDAO_VERSION: (u64, u64, u64) = (0, 8, 18);
+
+/// Scan detector for `block.difficulty` on post-merge compilers.
+#[derive(Debug, Default)]
+pub struct DeprecatedDifficultyDetector;
+
+impl DeprecatedDifficultyDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ScanDetector for DeprecatedDifficultyDetector {
+    fn id(&self) -> &'static str {
+        "deprecated-difficulty"
+    }
+
+    fn name(&self) -> &'static str {
+        "Deprecated block.difficulty"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `block.difficulty`, which returns `prevrandao` after the merge \
+         and is deprecated since Solidity 0.8.18"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![477]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Use `block.prevrandao`. Note that it is still not a safe randomness \
+         source: validators can bias it (see the `bad-randomness` detector); \
+         use a VRF or commit-reveal scheme instead."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://eips.ethereum.org/EIPS/eip-4399",
+            "https://docs.soliditylang.org/en/latest/units-and-global-variables.html#block-and-transaction-properties",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if module
+            .solidity_min_version()
+            .is_none_or(|v| v < PREVRANDAO_VERSION)
+        {
+            return bugs;
+        }
+
+        struct Visitor<'b> {
+            detector: &'b DeprecatedDifficultyDetector,
+            bugs: &'b mut Vec<Bug>,
+            contract_name: String,
+            func_name: String,
+        }
+
+        impl<'a, 'b> Visit<'a> for Visitor<'b> {
+            fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+                if let DialectExpr::Evm(EvmExpr::BlockDifficulty(e)) = d
+                    && !e.is_prevrandao
+                {
+                    self.bugs.push(Bug::new(
+                        self.detector.name(),
+                        Some(&format!(
+                            "'{}.{}' reads block.difficulty, which now returns \
+                             prevrandao; it is not a mining difficulty and not an \
+                             unbiased randomness source.",
+                            self.contract_name, self.func_name
+                        )),
+                        e.loc.clone(),
+                        self.detector.bug_kind(),
+                        self.detector.bug_category(),
+                        self.detector.risk_level(),
+                        self.detector.cwe_ids(),
+                        self.detector.swc_ids(),
+                        Some(self.detector.recommendation()),
+                    ));
+                }
+            }
+        }
+
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: &mut bugs,
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+        };
+        visitor.visit_function_decl(func);
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::loc::Loc;
+    use scirs::sir::attrs::sir_attrs;
+    use scirs::sir::dialect::evm::EvmBlockDifficulty;
+    use scirs::sir::*;
+
+    fn check(pragma: &str, is_prevrandao: bool) -> Vec<Bug> {
+        // seed = block.difficulty;
+        let read = Expr::Dialect(DialectExpr::Evm(EvmExpr::BlockDifficulty(EvmBlockDifficulty {
+            is_prevrandao,
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        let body = vec![Stmt::Assign(AssignStmt {
+            lhs: Expr::Var(VarExpr::new("seed".to_string(), Type::I256, None)),
+            rhs: read,
+            span: None,
+        })];
+        let func = FunctionDecl::new("draw".to_string(), vec![], vec![], Some(body), None);
+        let contract = ContractDecl::new("Lottery".to_string(), vec![], None);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![Attr::sir(
+                sir_attrs::PRAGMA_SOLIDITY,
+                AttrValue::String(pragma.into()),
+            )],
+            decls: vec![],
+        };
+        DeprecatedDifficultyDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_deprecated_difficulty_detector() {
+        let detector = DeprecatedDifficultyDetector::new();
+        assert_eq!(detector.id(), "deprecated-difficulty");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_difficulty_on_recent_pragma_is_flagged() {
+        assert_eq!(check("^0.8.20", false).len(), 1);
+        assert_eq!(check(">=0.8.18 <0.9.0", false).len(), 1);
+    }
+
+    #[test]
+    fn test_prevrandao_or_old_pragma_is_not_flagged() {
+        assert!(check("^0.8.20", true).is_empty());
+        assert!(check("^0.8.0", false).is_empty());
+    }
+}
@@ -1,20 +1,49 @@
 //! Deprecated Features Detector
 //!
-//! Detects usage of deprecated Solidity features.
+//! Detects usage of deprecated Solidity keywords and built-ins, each with
+//! its replacement:
+//!
+//! ```solidity
+//! pragma solidity ^0.4.24;
+//!
+//! contract Wallet {
+//!     function close() public {
+//!         if (now < unlock) throw;       // block.timestamp, revert()
+//!         suicide(owner);                // selfdestruct
+//!     }
+//! }
+//! ```
+//!
+//! `throw` and the `years` unit are erased by lowering and are read from the
+//! `#evm.deprecated_syntax` attributes of the function. `var` cannot be
+//! reported, since the parser replaces it with the inferred type. A name is
+//! not reported when the pragma only admits compilers that removed the
+//! keyword, as it must then refer to a user declaration.
 
 use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
 use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
 use common::loc::Loc;
+use scirs::sir::attrs::{AttrValue, evm_attrs};
+use scirs::sir::dialect::evm::EvmModuleExt;
 use scirs::sir::utils::visit::{self, Visit};
 use scirs::sir::{ContractDecl, FieldAccessExpr, FunctionDecl, Module, VarExpr};
 
-const DEPRECATED_IDENTS: &[(&str, &str)] = &[
-    ("suicide", "selfdestruct"),
-    ("sha3", "keccak256"),
-    ("throw", "revert()"),
+/// A deprecated keyword, its replacement and the compiler version that
+/// removed it.
+type Deprecation = (&'static str, &'static str, (u64, u64, u64));
+
+const DEPRECATED_IDENTS: &[Deprecation] = &[
+    ("now", "block.timestamp", (0, 7, 0)),
+    ("suicide", "selfdestruct", (0, 5, 0)),
+    ("sha3", "keccak256", (0, 5, 0)),
 ];
 
-const DEPRECATED_FIELDS: &[(&str, &str)] = &[("callcode", "delegatecall")];
+const DEPRECATED_FIELDS: &[Deprecation] = &[("callcode", "delegatecall", (0, 5, 0))];
+
+const DEPRECATED_SYNTAX: &[Deprecation] = &[
+    ("throw", "revert()", (0, 5, 0)),
+    ("years", "365 days", (0, 5, 0)),
+];
 
 /// Scan detector for deprecated features.
 #[derive(Debug, Default)]
@@ -26,9 +55,37 @@ impl DeprecatedFeaturesDetector {
     }
 }
 
+/// Collects uses of deprecated identifiers and members.
+struct DeprecationFinder {
+    min_version: Option<(u64, u64, u64)>,
+    found: Vec<(&'static str, &'static str, Option<Loc>)>,
+}
+
+impl DeprecationFinder {
+    fn check(&mut self, table: &[Deprecation], name: &str, loc: &Option<Loc>) {
+        if let Some((deprecated, replacement, removed)) =
+            table.iter().find(|(deprecated, ..)| *deprecated == name)
+            && self.min_version.is_none_or(|v| v < *removed)
+        {
+            self.found.push((deprecated, replacement, loc.clone()));
+        }
+    }
+}
+
+impl<'a> Visit<'a> for DeprecationFinder {
+    fn visit_var_expr(&mut self, v: &'a VarExpr) {
+        self.check(DEPRECATED_IDENTS, &v.name, &v.span);
+    }
+
+    fn visit_field_access_expr(&mut self, fa: &'a FieldAccessExpr) {
+        self.check(DEPRECATED_FIELDS, &fa.field, &fa.span);
+        visit::default::visit_field_access_expr(self, fa);
+    }
+}
+
 impl ScanDetector for DeprecatedFeaturesDetector {
     fn id(&self) -> &'static str {
-        "deprecated-features"
+        "deprecated"
     }
 
     fn name(&self) -> &'static str {
@@ -36,7 +93,7 @@ impl ScanDetector for DeprecatedFeaturesDetector {
     }
 
     fn description(&self) -> &'static str {
-        "Detects usage of deprecated Solidity constructs on SIR."
+        "Detects usage of deprecated Solidity keywords and built-ins."
     }
 
     fn bug_kind(&self) -> BugKind {
@@ -48,7 +105,7 @@ impl ScanDetector for DeprecatedFeaturesDetector {
     }
 
     fn risk_level(&self) -> RiskLevel {
-        RiskLevel::Low
+        RiskLevel::No
     }
 
     fn confidence(&self) -> Confidence {
@@ -73,82 +130,56 @@ impl ScanDetector for DeprecatedFeaturesDetector {
 
     fn recommendation(&self) -> &'static str {
         "Replace deprecated constructs with their modern equivalents: \
-         `suicide()` → `selfdestruct()`, `throw` → `revert()`, \
-         `sha3()` → `keccak256()`, `msg.gas` → `gasleft()`, \
-         `constant` (on functions) → `view` or `pure`."
+         `now` → `block.timestamp`, `suicide()` → `selfdestruct()`, \
+         `throw` → `revert()`, `sha3()` → `keccak256()`, \
+         `callcode` → `delegatecall`, `years` → `365 days`, \
+         `var` → an explicit type."
     }
 
     fn references(&self) -> Vec<&'static str> {
-        vec!["https://swcregistry.io/docs/SWC-111"]
+        vec![
+            "https://swcregistry.io/docs/SWC-111",
+            "https://docs.soliditylang.org/en/latest/050-breaking-changes.html",
+        ]
     }
 
     fn check_function(
         &self,
         func: &FunctionDecl,
         contract: &ContractDecl,
-        _module: &Module,
+        module: &Module,
     ) -> Vec<Bug> {
         let mut bugs = Vec::new();
 
-        struct Visitor<'b> {
-            detector: &'b DeprecatedFeaturesDetector,
-            bugs: &'b mut Vec<Bug>,
-            contract_name: String,
-            func_name: String,
-        }
-
-        impl<'a, 'b> Visit<'a> for Visitor<'b> {
-            fn visit_var_expr(&mut self, v: &'a VarExpr) {
-                for (deprecated, replacement) in DEPRECATED_IDENTS {
-                    if v.name == *deprecated {
-                        self.bugs.push(Bug::new(
-                            self.detector.name(),
-                            Some(&format!(
-                                "Deprecated '{}' used in '{}.{}'. Use '{}' instead.",
-                                deprecated, self.contract_name, self.func_name, replacement
-                            )),
-                            v.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
-                            self.detector.bug_kind(),
-                            self.detector.bug_category(),
-                            self.detector.risk_level(),
-                            self.detector.cwe_ids(),
-                            self.detector.swc_ids(),
-                            Some(self.detector.recommendation()),
-                        ));
-                    }
-                }
-            }
-
-            fn visit_field_access_expr(&mut self, fa: &'a FieldAccessExpr) {
-                for (deprecated, replacement) in DEPRECATED_FIELDS {
-                    if fa.field == *deprecated {
-                        self.bugs.push(Bug::new(
-                            self.detector.name(),
-                            Some(&format!(
-                                "Deprecated '{}' used in '{}.{}'. Use '{}' instead.",
-                                deprecated, self.contract_name, self.func_name, replacement
-                            )),
-                            fa.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
-                            self.detector.bug_kind(),
-                            self.detector.bug_category(),
-                            self.detector.risk_level(),
-                            self.detector.cwe_ids(),
-                            self.detector.swc_ids(),
-                            Some(self.detector.recommendation()),
-                        ));
-                    }
-                }
-                visit::default::visit_field_access_expr(self, fa);
+        let mut finder =
+            DeprecationFinder { min_version: module.solidity_min_version(), found: vec![] };
+        for attr in &func.attrs {
+            if attr.namespace == "evm"
+                && attr.key == evm_attrs::DEPRECATED_SYNTAX
+                && let AttrValue::String(keyword) = &attr.value
+            {
+                finder.check(DEPRECATED_SYNTAX, keyword, &attr.span);
             }
         }
+        finder.visit_function_decl(func);
 
-        let mut visitor = Visitor {
-            detector: self,
-            bugs: &mut bugs,
-            contract_name: contract.name.clone(),
-            func_name: func.name.clone(),
-        };
-        visitor.visit_function_decl(func);
+        for (deprecated, replacement, loc) in finder.found {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "Deprecated '{}' used in '{}.{}'. Use '{}' instead.",
+                    deprecated, contract.name, func.name, replacement
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
 
         bugs
     }
@@ -157,11 +188,98 @@ impl ScanDetector for DeprecatedFeaturesDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::var;
+    use scirs::sir::*;
+
+    fn call(callee: Expr) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(callee),
+                args: CallArgs::Positional(vec![]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    fn module(pragma: &str) -> Module {
+        Module {
+            id: "test".to_string(),
+            attrs: vec![Attr::sir(
+                sir_attrs::PRAGMA_SOLIDITY,
+                AttrValue::String(pragma.into()),
+            )],
+            decls: vec![],
+        }
+    }
+
+    /// A function using every deprecated keyword that survives lowering, and
+    /// carrying the attributes lowering leaves for `throw` and `years`.
+    fn function() -> FunctionDecl {
+        let callcode = Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(var("target")),
+            field: "callcode".to_string(),
+            ty: Type::None,
+            span: None,
+        });
+        let body = vec![
+            Stmt::Expr(ExprStmt { expr: var("now"), span: None }),
+            call(var("sha3")),
+            call(var("suicide")),
+            call(callcode),
+        ];
+        let mut func = FunctionDecl::new("close".to_string(), vec![], vec![], Some(body), None);
+        for keyword in ["throw", "years"] {
+            func.attrs.push(Attr::evm(
+                evm_attrs::DEPRECATED_SYNTAX,
+                AttrValue::String(keyword.to_string()),
+            ));
+        }
+        func
+    }
+
+    fn check(pragma: &str) -> Vec<Bug> {
+        let contract = ContractDecl::new("Wallet".to_string(), vec![], None);
+        DeprecatedFeaturesDetector::new().check_function(&function(), &contract, &module(pragma))
+    }
 
     #[test]
     fn test_deprecated_features_detector() {
         let detector = DeprecatedFeaturesDetector::new();
-        assert_eq!(detector.id(), "deprecated-features");
-        assert_eq!(detector.risk_level(), RiskLevel::Low);
+        assert_eq!(detector.id(), "deprecated");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_deprecated_keywords_are_flagged_with_replacements() {
+        let bugs = check("^0.4.24");
+        let mut messages: Vec<String> =
+            bugs.iter().filter_map(|b| b.description.clone()).collect();
+        messages.sort();
+        let expected = [
+            ("callcode", "delegatecall"),
+            ("now", "block.timestamp"),
+            ("sha3", "keccak256"),
+            ("suicide", "selfdestruct"),
+            ("throw", "revert()"),
+            ("years", "365 days"),
+        ];
+        assert_eq!(messages.len(), expected.len());
+        for (message, (deprecated, replacement)) in messages.iter().zip(expected) {
+            assert_eq!(
+                message,
+                &format!(
+                    "Deprecated '{deprecated}' used in 'Wallet.close'. Use '{replacement}' \
+                     instead."
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_removed_keywords_are_not_flagged_as_user_names() {
+        let bugs = check("^0.7.0");
+        assert!(bugs.is_empty());
     }
 }
@@ -0,0 +1,190 @@
+//! Deprecated Selfdestruct Detector
+//!
+//! Detects any use of `selfdestruct`. Since EIP-6780 (Cancun), the opcode
+//! only transfers the balance; code and storage are removed only when it runs
+//! in the same transaction that created the contract. Contracts that rely on
+//! destruction (e.g. to disable themselves or to redeploy with CREATE2) no
+//! longer behave as intended.
+//!
+//! Access control on `selfdestruct` is a separate concern and is not checked
+//! here.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use scirs::sir::dialect::evm::EvmStmt;
+use scirs::sir::utils::visit::Visit;
+use scirs::sir::{ContractDecl, DialectStmt, FunctionDecl, Module};
+
+/// Scan detector for `selfdestruct` after EIP-6780.
+#[derive(Debug, Default)]
+pub struct DeprecatedSelfdestructDetector;
+
+impl DeprecatedSelfdestructDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ScanDetector for DeprecatedSelfdestructDetector {
+    fn id(&self) -> &'static str {
+        "deprecated-selfdestruct"
+    }
+
+    fn name(&self) -> &'static str {
+        "Deprecated Selfdestruct"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `selfdestruct`, which no longer deletes contract code since \
+         EIP-6780 except in the transaction that created the contract"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![477]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Do not rely on `selfdestruct` to remove code or storage. Disable the \
+         contract with an explicit paused/closed state and withdraw funds with \
+         a regular transfer."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://eips.ethereum.org/EIPS/eip-6780",
+            "https://docs.soliditylang.org/en/latest/introduction-to-smart-contracts.html#deactivate-and-self-destruct",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        struct Visitor<'b> {
+            detector: &'b DeprecatedSelfdestructDetector,
+            bugs: &'b mut Vec<Bug>,
+            contract_name: String,
+            func_name: String,
+        }
+
+        impl<'a, 'b> Visit<'a> for Visitor<'b> {
+            fn visit_dialect_stmt(&mut self, stmt: &'a DialectStmt) {
+                match stmt {
+                    DialectStmt::Evm(EvmStmt::Selfdestruct(s)) => {
+                        self.bugs.push(Bug::new(
+                            self.detector.name(),
+                            Some(&format!(
+                                "'{}.{}' calls selfdestruct, which since EIP-6780 \
+                                 (https://eips.ethereum.org/EIPS/eip-6780) only sends \
+                                 the balance and no longer deletes the contract.",
+                                self.contract_name, self.func_name
+                            )),
+                            s.loc.clone(),
+                            self.detector.bug_kind(),
+                            self.detector.bug_category(),
+                            self.detector.risk_level(),
+                            self.detector.cwe_ids(),
+                            self.detector.swc_ids(),
+                            Some(self.detector.recommendation()),
+                        ));
+                    }
+                    DialectStmt::Evm(EvmStmt::TryCatch(tc)) => {
+                        self.visit_stmts(&tc.body);
+                        for clause in &tc.catch_clauses {
+                            self.visit_stmts(&clause.body);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: &mut bugs,
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+        };
+        visitor.visit_function_decl(func);
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_body, typed_var, var};
+    use common::loc::Loc;
+    use scirs::sir::dialect::evm::EvmSelfdestruct;
+    use scirs::sir::*;
+
+    fn selfdestruct_stmt() -> Stmt {
+        // selfdestruct(payable(owner))
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::Selfdestruct(EvmSelfdestruct {
+            recipient: var("owner"),
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    #[test]
+    fn test_deprecated_selfdestruct_detector() {
+        let detector = DeprecatedSelfdestructDetector::new();
+        assert_eq!(detector.id(), "deprecated-selfdestruct");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+        assert_eq!(detector.confidence(), Confidence::Medium);
+    }
+
+    #[test]
+    fn test_any_selfdestruct_is_flagged() {
+        let bugs = check_body(&DeprecatedSelfdestructDetector::new(), vec![selfdestruct_stmt()]);
+        assert_eq!(bugs.len(), 1);
+        assert!(bugs[0].description.as_deref().unwrap().contains("EIP-6780"));
+
+        // Behind an access check it is still deprecated.
+        let guarded = Stmt::If(IfStmt {
+            cond: typed_var("isOwner", Type::Bool),
+            then_body: vec![selfdestruct_stmt()],
+            else_body: None,
+            span: None,
+        });
+        assert_eq!(check_body(&DeprecatedSelfdestructDetector::new(), vec![guarded]).len(), 1);
+    }
+
+    #[test]
+    fn test_function_without_selfdestruct_is_not_flagged() {
+        assert!(check_body(&DeprecatedSelfdestructDetector::new(), vec![]).is_empty());
+    }
+}
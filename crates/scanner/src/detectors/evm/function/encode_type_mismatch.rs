@@ -0,0 +1,493 @@
+//! Encode Type Mismatch Detector
+//!
+//! Detects `abi.encodeWithSelector` and `abi.encodeWithSignature` calls whose
+//! arguments do not match the parameters of the function being called:
+//!
+//! ```solidity
+//! function transfer(address to, uint256 amount) external;
+//! ...
+//! abi.encodeWithSelector(IERC20.transfer.selector, amount, to);
+//! ```
+//!
+//! Unlike `abi.encodeCall`, neither form is type checked by the compiler, so
+//! the calldata is silently encoded for the wrong signature. The target is
+//! resolved from `C.f.selector` (when `C` is declared in the module, `f` is
+//! not overloaded, or `this.f.selector`) and from a literal signature string,
+//! either passed directly or hashed into the selector. Arguments that encode
+//! identically are accepted: narrower integers of the same signedness, number
+//! literals for integer parameters, and `string` for `bytes`.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmTypeExt};
+use scirs::sir::lits::Lit;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{ContractDecl, Decl, DialectExpr, Expr, FunctionDecl, MemberDecl, Module, Type};
+
+/// Scan detector for mis-typed `abi.encodeWithSelector` arguments.
+#[derive(Debug, Default)]
+pub struct EncodeTypeMismatchDetector;
+
+impl EncodeTypeMismatchDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Function an encoded call is meant for.
+struct EncodeTarget {
+    /// Human readable name, e.g. `IERC20.transfer` or
+    /// `transfer(address,uint256)`.
+    name: String,
+    /// ABI names of its parameters.
+    params: Vec<String>,
+}
+
+/// Parameter types of a signature such as `f(uint256,(address,bool))`.
+fn parse_signature(sig: &str) -> Option<Vec<String>> {
+    let open = sig.find('(')?;
+    let inner = sig[open + 1..].strip_suffix(')')?;
+    if open == 0 || inner.contains(' ') {
+        return None;
+    }
+    let mut params = vec![];
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                params.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !inner.is_empty() {
+        params.push(&inner[start..]);
+    }
+    let normalize = |p: &str| match p {
+        "uint" => "uint256".to_string(),
+        "int" => "int256".to_string(),
+        _ => p.to_string(),
+    };
+    Some(params.into_iter().map(normalize).collect())
+}
+
+/// The first string literal within `expr`, e.g. the signature in
+/// `bytes4(keccak256("transfer(address,uint256)"))`.
+fn string_literal(expr: &Expr) -> Option<String> {
+    struct Finder(Option<String>);
+    impl<'a> Visit<'a> for Finder {
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            match expr {
+                Expr::Lit(Lit::String(lit)) => {
+                    self.0.get_or_insert_with(|| lit.value.clone());
+                }
+                _ => visit::default::visit_expr(self, expr),
+            }
+        }
+
+        fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+            if let DialectExpr::Evm(EvmExpr::Keccak256(e)) = expr {
+                self.visit_expr(&e.expr);
+            }
+        }
+    }
+    let mut finder = Finder(None);
+    finder.visit_expr(expr);
+    finder.0
+}
+
+/// Functions named `name` declared in `contract` or its ancestors within
+/// `module`.
+fn functions_named<'m>(
+    contract: &'m ContractDecl,
+    name: &str,
+    module: &'m Module,
+    found: &mut Vec<&'m FunctionDecl>,
+) {
+    for member in &contract.members {
+        if let MemberDecl::Function(f) = member
+            && f.name == name
+        {
+            found.push(f);
+        }
+    }
+    for parent in &contract.parents {
+        if let Some(parent) = find_contract(module, parent) {
+            functions_named(parent, name, module, found);
+        }
+    }
+}
+
+fn find_contract<'m>(module: &'m Module, name: &str) -> Option<&'m ContractDecl> {
+    module.decls.iter().find_map(|decl| match decl {
+        Decl::Contract(c) if c.name == name => Some(c),
+        _ => None,
+    })
+}
+
+/// Resolves the target of a `C.f.selector` expression.
+fn selector_target(
+    selector: &Expr,
+    contract: &ContractDecl,
+    module: &Module,
+) -> Option<EncodeTarget> {
+    let Expr::FieldAccess(sel) = selector else {
+        return None;
+    };
+    if sel.field != "selector" {
+        return None;
+    }
+    let (owner, func_name) = match &*sel.base {
+        Expr::FieldAccess(fa) => {
+            let owner = match &*fa.base {
+                Expr::Dialect(DialectExpr::Evm(EvmExpr::This(_))) => Some(contract),
+                Expr::Var(v) if v.name == "this" => Some(contract),
+                Expr::Var(v) => find_contract(module, &v.name),
+                base => match base.typ() {
+                    Type::TypeRef(name) => find_contract(module, &name),
+                    _ => None,
+                },
+            };
+            (owner?, &fa.field)
+        }
+        Expr::Var(v) => (contract, &v.name),
+        _ => return None,
+    };
+
+    let mut candidates = vec![];
+    functions_named(owner, func_name, module, &mut candidates);
+    let [func] = candidates.as_slice() else {
+        return None;
+    };
+    Some(EncodeTarget {
+        name: format!("{}.{}", owner.name, func.name),
+        params: func.params.iter().map(|p| p.ty.abi_name()).collect(),
+    })
+}
+
+/// Bit width of an ABI integer type and whether it is signed.
+fn int_width(abi: &str) -> Option<(bool, u32)> {
+    let (signed, bits) = match abi.strip_prefix("uint") {
+        Some(bits) => (false, bits),
+        None => (true, abi.strip_prefix("int")?),
+    };
+    Some((signed, bits.parse().ok()?))
+}
+
+/// Returns `true` if `arg` encodes the same way as a value of ABI type
+/// `expected`, or if its type is not known precisely enough to tell.
+fn is_compatible(arg: &Expr, expected: &str) -> bool {
+    if let Expr::Lit(Lit::Num(_)) = arg {
+        return int_width(expected).is_some();
+    }
+    let ty = arg.typ();
+    if matches!(
+        ty,
+        Type::None
+            | Type::I1
+            | Type::Index
+            | Type::Map(..)
+            | Type::Option(_)
+            | Type::Function { .. }
+    ) {
+        return true;
+    }
+    let actual = ty.abi_name();
+    if actual == expected {
+        return true;
+    }
+    // Structs and enums are also rendered as `address`.
+    if matches!(ty, Type::TypeRef(_)) {
+        return true;
+    }
+    match (int_width(&actual), int_width(expected)) {
+        (Some((s1, w1)), Some((s2, w2))) => s1 == s2 && w1 <= w2,
+        _ => matches!((actual.as_str(), expected), ("string", "bytes") | ("bytes", "string")),
+    }
+}
+
+/// Collects encode calls whose arguments do not fit their target.
+struct EncodeFinder<'m> {
+    contract: &'m ContractDecl,
+    module: &'m Module,
+    /// Description of each mismatch and its span.
+    mismatches: Vec<(String, Loc)>,
+}
+
+impl EncodeFinder<'_> {
+    fn check(&mut self, target: Option<EncodeTarget>, args: &[Expr], loc: &Loc) {
+        let Some(target) = target else {
+            return;
+        };
+        let problem = if args.len() != target.params.len() {
+            Some(format!(
+                "passes {} argument(s) to '{}', which takes {}",
+                args.len(),
+                target.name,
+                target.params.len()
+            ))
+        } else {
+            args.iter()
+                .zip(&target.params)
+                .position(|(arg, param)| !is_compatible(arg, param))
+                .map(|i| {
+                    format!(
+                        "passes '{}' of type '{}' as argument {} of '{}', declared '{}'",
+                        args[i],
+                        args[i].typ().abi_name(),
+                        i + 1,
+                        target.name,
+                        target.params[i]
+                    )
+                })
+        };
+        if let Some(problem) = problem {
+            self.mismatches.push((problem, loc.clone()));
+        }
+    }
+}
+
+impl<'a> Visit<'a> for EncodeFinder<'_> {
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        let DialectExpr::Evm(evm) = expr else {
+            return;
+        };
+        match evm {
+            EvmExpr::AbiEncodeWithSelector(e) => {
+                let target =
+                    selector_target(&e.selector, self.contract, self.module).or_else(|| {
+                        let sig = string_literal(&e.selector)?;
+                        Some(EncodeTarget { params: parse_signature(&sig)?, name: sig })
+                    });
+                self.check(target, &e.args, &e.loc);
+                e.args.iter().for_each(|arg| self.visit_expr(arg));
+            }
+            EvmExpr::AbiEncodeWithSignature(e) => {
+                let target = match &*e.signature {
+                    Expr::Lit(Lit::String(sig)) => parse_signature(&sig.value)
+                        .map(|params| EncodeTarget { name: sig.value.clone(), params }),
+                    _ => None,
+                };
+                self.check(target, &e.args, &e.loc);
+                e.args.iter().for_each(|arg| self.visit_expr(arg));
+            }
+            // Encoded calldata is usually passed straight to a call.
+            EvmExpr::LowLevelCall(e) => self.visit_expr(&e.data),
+            EvmExpr::RawCall(e) => self.visit_expr(&e.data),
+            EvmExpr::Delegatecall(e) => self.visit_expr(&e.data),
+            _ => {}
+        }
+    }
+}
+
+impl ScanDetector for EncodeTypeMismatchDetector {
+    fn id(&self) -> &'static str {
+        "encode-type-mismatch"
+    }
+
+    fn name(&self) -> &'static str {
+        "Encode Type Mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects abi.encodeWithSelector/encodeWithSignature calls whose \
+         arguments do not match the parameters of the target function"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![686]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Use `abi.encodeCall(C.f, (args))`, which the compiler type checks \
+         against the target function, or cast the arguments to the declared \
+         parameter types."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/units-and-global-variables.html#abi-encoding-and-decoding-functions",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut finder = EncodeFinder { contract, module, mismatches: vec![] };
+        finder.visit_function_decl(func);
+
+        for (problem, loc) in finder.mismatches {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!("'{}.{}' {}.", contract.name, func.name, problem)),
+                loc,
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::dialect::evm::{EvmAbiEncodeWithSelector, EvmAbiEncodeWithSignature, EvmType};
+    use scirs::sir::*;
+
+    fn address() -> Type {
+        Type::Dialect(DialectType::Evm(EvmType::Address))
+    }
+
+    /// `IERC20.transfer.selector`
+    fn transfer_selector() -> Expr {
+        let func = Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(typed_var("IERC20", Type::None)),
+            field: "transfer".to_string(),
+            ty: Type::None,
+            span: None,
+        });
+        Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(func),
+            field: "selector".to_string(),
+            ty: Type::FixedBytes(4),
+            span: None,
+        })
+    }
+
+    /// `data = <encode>;` inside `Vault.pay`, next to an `IERC20` interface
+    /// declaring `transfer(address,uint256)`.
+    fn check(encode: EvmExpr) -> Vec<Bug> {
+        let body = vec![Stmt::Assign(AssignStmt {
+            lhs: typed_var("data", Type::Bytes),
+            rhs: Expr::Dialect(DialectExpr::Evm(encode)),
+            span: None,
+        })];
+        let func = FunctionDecl::new("pay".to_string(), vec![], vec![], Some(body), None);
+        let transfer = FunctionDecl::new(
+            "transfer".to_string(),
+            vec![
+                Param::new("to".to_string(), address()),
+                Param::new("amount".to_string(), Type::I256),
+            ],
+            vec![Type::Bool],
+            None,
+            None,
+        );
+        let token =
+            ContractDecl::new("IERC20".to_string(), vec![MemberDecl::Function(transfer)], None);
+        let contract = ContractDecl::new("Vault".to_string(), vec![], None);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![],
+            decls: vec![Decl::Contract(token), Decl::Contract(contract.clone())],
+        };
+        EncodeTypeMismatchDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_encode_type_mismatch_detector() {
+        let detector = EncodeTypeMismatchDetector::new();
+        assert_eq!(detector.id(), "encode-type-mismatch");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_parse_signature() {
+        assert_eq!(
+            parse_signature("f(uint,(address,bool),bytes32[])"),
+            Some(vec![
+                "uint256".into(),
+                "(address,bool)".into(),
+                "bytes32[]".into()
+            ])
+        );
+        assert_eq!(parse_signature("g()"), Some(vec![]));
+        assert_eq!(parse_signature("not a signature"), None);
+    }
+
+    #[test]
+    fn test_swapped_selector_arguments_are_flagged() {
+        // abi.encodeWithSelector(IERC20.transfer.selector, amount, to)
+        let encode = EvmExpr::AbiEncodeWithSelector(EvmAbiEncodeWithSelector {
+            selector: Box::new(transfer_selector()),
+            args: vec![typed_var("amount", Type::I256), typed_var("to", address())],
+            loc: Loc::new(0, 0, 0, 0),
+        });
+        assert_eq!(check(encode).len(), 1);
+    }
+
+    #[test]
+    fn test_matching_arguments_are_not_flagged() {
+        let args = vec![typed_var("to", address()), typed_var("amount", Type::I128)];
+        let encode = EvmExpr::AbiEncodeWithSelector(EvmAbiEncodeWithSelector {
+            selector: Box::new(transfer_selector()),
+            args: args.clone(),
+            loc: Loc::new(0, 0, 0, 0),
+        });
+        assert!(check(encode).is_empty());
+
+        let signature = StringLit::new("transfer(address,uint256)".to_string(), None);
+        let encode = EvmExpr::AbiEncodeWithSignature(EvmAbiEncodeWithSignature {
+            signature: Box::new(Expr::Lit(Lit::String(signature))),
+            args,
+            loc: Loc::new(0, 0, 0, 0),
+        });
+        assert!(check(encode).is_empty());
+    }
+
+    #[test]
+    fn test_signature_argument_mismatch_is_flagged() {
+        // abi.encodeWithSignature("transfer(address,uint256)", to, flag)
+        let signature = StringLit::new("transfer(address,uint256)".to_string(), None);
+        let encode = EvmExpr::AbiEncodeWithSignature(EvmAbiEncodeWithSignature {
+            signature: Box::new(Expr::Lit(Lit::String(signature))),
+            args: vec![typed_var("to", address()), typed_var("flag", Type::Bool)],
+            loc: Loc::new(0, 0, 0, 0),
+        });
+        assert_eq!(check(encode).len(), 1);
+    }
+}
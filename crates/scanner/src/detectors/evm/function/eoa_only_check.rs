@@ -0,0 +1,195 @@
+//! EOA-only Check Detector
+//!
+//! Detects `msg.sender == tx.origin` (or `!=`) comparisons used to restrict a
+//! function to externally owned accounts. The guard locks out smart contract
+//! wallets and ERC-4337 accounts, and does not reliably stop contracts either
+//! (code executing in a constructor passes it).
+//!
+//! Other uses of `tx.origin` are reported by the `tx-origin` detector.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::is_eoa_check;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{BinOpExpr, ContractDecl, FunctionDecl, Module};
+
+/// Scan detector for EOA-only guards.
+#[derive(Debug, Default)]
+pub struct EoaOnlyCheckDetector;
+
+impl EoaOnlyCheckDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ScanDetector for EoaOnlyCheckDetector {
+    fn id(&self) -> &'static str {
+        "eoa-only-check"
+    }
+
+    fn name(&self) -> &'static str {
+        "EOA-only Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `msg.sender == tx.origin` guards restricting callers to \
+         externally owned accounts"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![284]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Do not restrict callers to EOAs. Protect against the threat the guard \
+         targets directly, e.g. with a reentrancy guard or commit-reveal \
+         scheme, so that smart contract wallets remain supported."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://eips.ethereum.org/EIPS/eip-4337",
+            "https://consensys.github.io/smart-contract-best-practices/development-recommendations/solidity-specific/extcodesize-checks/",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        struct Visitor<'b> {
+            detector: &'b EoaOnlyCheckDetector,
+            bugs: &'b mut Vec<Bug>,
+            contract_name: String,
+            func_name: String,
+            func_span: Option<Loc>,
+        }
+
+        impl<'a, 'b> Visit<'a> for Visitor<'b> {
+            fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+                if !is_eoa_check(expr) {
+                    return visit::default::visit_binop_expr(self, expr);
+                }
+                self.bugs.push(Bug::new(
+                    self.detector.name(),
+                    Some(&format!(
+                        "'{}.{}' compares msg.sender with tx.origin, which rejects \
+                         smart contract wallets and ERC-4337 accounts.",
+                        self.contract_name, self.func_name
+                    )),
+                    expr.span
+                        .clone()
+                        .or_else(|| self.func_span.clone())
+                        .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.detector.bug_kind(),
+                    self.detector.bug_category(),
+                    self.detector.risk_level(),
+                    self.detector.cwe_ids(),
+                    self.detector.swc_ids(),
+                    Some(self.detector.recommendation()),
+                ));
+            }
+        }
+
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: &mut bugs,
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+            func_span: func.span.clone(),
+        };
+        visitor.visit_function_decl(func);
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, require, var};
+    use scirs::sir::dialect::evm::{EvmExpr, EvmMsgSender, EvmTxOrigin};
+    use scirs::sir::*;
+
+    fn tx_origin() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::TxOrigin(EvmTxOrigin {
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    fn msg_sender() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgSender(EvmMsgSender {
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    /// `require(lhs == rhs)`
+    fn require_eq(lhs: Expr, rhs: Expr) -> Stmt {
+        require(binop(BinOp::Eq, lhs, rhs))
+    }
+
+    #[test]
+    fn test_eoa_only_check_detector() {
+        let detector = EoaOnlyCheckDetector::new();
+        assert_eq!(detector.id(), "eoa-only-check");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_sender_origin_equality_is_flagged() {
+        assert_eq!(
+            check_body(&EoaOnlyCheckDetector::new(), vec![require_eq(msg_sender(), tx_origin())])
+                .len(),
+            1
+        );
+        assert_eq!(
+            check_body(&EoaOnlyCheckDetector::new(), vec![require_eq(tx_origin(), msg_sender())])
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_other_origin_comparison_is_not_flagged() {
+        // require(tx.origin == owner) is tx.origin authentication.
+        let owner = var("owner");
+        assert!(
+            check_body(&EoaOnlyCheckDetector::new(), vec![require_eq(tx_origin(), owner)])
+                .is_empty()
+        );
+    }
+}
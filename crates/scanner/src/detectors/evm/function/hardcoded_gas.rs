@@ -0,0 +1,347 @@
+//! Hardcoded Gas Detector
+//!
+//! Detects external calls that forward a fixed gas amount, which breaks when
+//! opcode gas costs are repriced by hard forks (e.g. EIP-1884). Findings are
+//! reported under one of two subtypes:
+//!
+//! - `gas-option`: a literal `gas` call option, e.g. `addr.call{gas:
+//!   2300}(...)`;
+//! - `gas-stipend`: `addr.transfer(x)` or `addr.send(x)` on an address, which
+//!   forward a fixed 2300 gas stipend.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::pattern::{Pattern, PatternBuilder as P};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmTypeExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{ContractDecl, DialectExpr, Expr, FunctionDecl, Module, Stmt};
+
+/// Scan detector for hardcoded gas amounts in external calls.
+#[derive(Debug, Default)]
+pub struct HardcodedGasDetector;
+
+impl HardcodedGasDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Subtype of a hardcoded gas finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardcodedGasKind {
+    /// Literal `gas` call option.
+    GasOption,
+    /// Fixed 2300 gas stipend of `transfer` and `send`.
+    GasStipend,
+}
+
+impl HardcodedGasKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HardcodedGasKind::GasOption => "gas-option",
+            HardcodedGasKind::GasStipend => "gas-stipend",
+        }
+    }
+}
+
+/// Returns the literal gas amount if the `gas` call option is a numeric
+/// literal. Forwarded values such as `gasleft()` are not literals.
+fn literal_gas(gas: Option<&Expr>) -> Option<&Expr> {
+    gas.filter(|g| matches!(g, Expr::Lit(_)))
+}
+
+/// Literal `gas` option of a low-level call, with the call location.
+fn call_literal_gas(expr: &Expr) -> Option<(&Expr, &Loc)> {
+    match expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::LowLevelCall(e))) => {
+            literal_gas(e.gas.as_deref()).map(|g| (g, &e.loc))
+        }
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::RawCall(e))) => {
+            literal_gas(e.gas.as_deref()).map(|g| (g, &e.loc))
+        }
+        _ => None,
+    }
+}
+
+/// Method name, receiver and location of a `transfer` or `send` call.
+fn stipend_call_parts(expr: &Expr) -> Option<(&'static str, &Expr, &Loc)> {
+    match expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(e))) => {
+            Some(("transfer", &e.target, &e.loc))
+        }
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Send(e))) => Some(("send", &e.target, &e.loc)),
+        _ => None,
+    }
+}
+
+/// `addr.call{gas: N}(...)` with a literal `N`.
+fn gas_option_call() -> Pattern {
+    P::where_(P::evm(|e| matches!(e, EvmExpr::LowLevelCall(_) | EvmExpr::RawCall(_))), |e| {
+        call_literal_gas(e).is_some()
+    })
+}
+
+/// `addr.transfer(x)` or `addr.send(x)` where `addr` is an address. Calls
+/// of a contract's own `transfer(x)` or `send(x)` functions are skipped.
+fn stipend_call() -> Pattern {
+    P::where_(P::evm(|e| matches!(e, EvmExpr::Transfer(_) | EvmExpr::Send(_))), |e| {
+        stipend_call_parts(e).is_some_and(|(_, target, _)| target.typ().is_address())
+    })
+}
+
+/// Collects the hardcoded gas calls of a function body.
+struct CallFinder<'d> {
+    detector: &'d HardcodedGasDetector,
+    gas_option: Pattern,
+    stipend: Pattern,
+    contract_name: String,
+    func_name: String,
+    /// Span of the innermost enclosing statement, used for dialect
+    /// expressions lowered without a location.
+    stmt_loc: Option<Loc>,
+    bugs: Vec<Bug>,
+}
+
+impl CallFinder<'_> {
+    fn report(&mut self, kind: HardcodedGasKind, loc: &Loc, message: String) {
+        let loc = match &self.stmt_loc {
+            Some(stmt_loc) if *loc == Loc::default() => stmt_loc.clone(),
+            _ => loc.clone(),
+        };
+        self.bugs.push(Bug::new(
+            self.detector.name(),
+            Some(&format!(
+                "[{}] {} in '{}.{}'.",
+                kind.as_str(),
+                message,
+                self.contract_name,
+                self.func_name
+            )),
+            loc,
+            self.detector.bug_kind(),
+            self.detector.bug_category(),
+            self.detector.risk_level(),
+            self.detector.cwe_ids(),
+            self.detector.swc_ids(),
+            Some(self.detector.recommendation()),
+        ));
+    }
+}
+
+impl<'a> Visit<'a> for CallFinder<'_> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        let outer = self.stmt_loc.clone();
+        if let Some(span) = stmt.span() {
+            self.stmt_loc = Some(span.clone());
+        }
+        visit::default::visit_stmt(self, stmt);
+        self.stmt_loc = outer;
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        // Patterns look through casts; the parts are only read from the call
+        // itself, so a cast call is reported once.
+        if self.gas_option.matches(expr).is_some()
+            && let Some((gas, loc)) = call_literal_gas(expr)
+        {
+            let message = format!("External call forwards a hardcoded gas amount of {gas}");
+            self.report(HardcodedGasKind::GasOption, loc, message);
+        } else if self.stipend.matches(expr).is_some()
+            && let Some((method, _, loc)) = stipend_call_parts(expr)
+        {
+            let message = format!("`{method}` forwards a fixed 2300 gas stipend");
+            self.report(HardcodedGasKind::GasStipend, loc, message);
+        }
+        visit::default::visit_expr(self, expr);
+    }
+}
+
+impl ScanDetector for HardcodedGasDetector {
+    fn id(&self) -> &'static str {
+        "hardcoded-gas"
+    }
+
+    fn name(&self) -> &'static str {
+        "Hardcoded Gas Amount"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects external calls that specify a fixed gas amount, including the \
+         2300 gas stipend of `transfer` and `send`"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::DenialOfService
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![655]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![134]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Avoid hardcoding gas amounts in external calls. Send Ether with \
+         `(bool ok, ) = addr.call{value: amount}(\"\")` and require `ok`, \
+         forwarding all available gas, and protect against reentrancy with \
+         the Checks-Effects-Interactions pattern instead of a gas limit."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-134",
+            "https://eips.ethereum.org/EIPS/eip-1884",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut finder = CallFinder {
+            detector: self,
+            gas_option: gas_option_call(),
+            stipend: stipend_call(),
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+            stmt_loc: func.span.clone(),
+            bugs: vec![],
+        };
+        finder.visit_function_decl(func);
+        finder.bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::dialect::evm::{EvmGasleft, EvmLowLevelCall, EvmSend, EvmTransfer, EvmType};
+    use scirs::sir::*;
+
+    fn recipient(ty: Type) -> Box<Expr> {
+        Box::new(Expr::Var(VarExpr::new("recipient".to_string(), ty, None)))
+    }
+
+    fn check_call(gas: Option<Expr>) -> Vec<Bug> {
+        // recipient.call{gas: <gas>}("")
+        let call = Expr::Dialect(DialectExpr::Evm(EvmExpr::LowLevelCall(EvmLowLevelCall {
+            target: recipient(Type::None),
+            data: Box::new(Expr::Lit(Lit::String(StringLit::new(String::new(), None)))),
+            value: None,
+            gas: gas.map(Box::new),
+            loc: Loc::new(1, 1, 1, 1),
+        })));
+        check_stmt(call)
+    }
+
+    fn check_stmt(expr: Expr) -> Vec<Bug> {
+        let body = vec![Stmt::Expr(ExprStmt {
+            expr,
+            span: Some(Loc::new(7, 9, 7, 30)),
+        })];
+        let func = FunctionDecl::new("pay".to_string(), vec![], vec![], Some(body), None);
+        let contract = ContractDecl {
+            name: "Payer".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        HardcodedGasDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_hardcoded_gas_detector() {
+        let detector = HardcodedGasDetector::new();
+        assert_eq!(detector.id(), "hardcoded-gas");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_literal_gas_is_flagged() {
+        let bugs = check_call(Some(Expr::Lit(Lit::one(None))));
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_ref()
+                .unwrap()
+                .starts_with("[gas-option]")
+        );
+        assert_eq!(bugs[0].loc.start_line, 1);
+    }
+
+    #[test]
+    fn test_transfer_and_send_on_address_are_flagged() {
+        let address = Type::Dialect(DialectType::Evm(EvmType::AddressPayable));
+        let amount = || Box::new(Expr::Lit(Lit::one(None)));
+        // Lowered `transfer`/`send` carry no location: the statement's is used.
+        let transfer = Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(EvmTransfer {
+            target: recipient(address.clone()),
+            amount: amount(),
+            loc: Loc::default(),
+        })));
+        let send = Expr::Dialect(DialectExpr::Evm(EvmExpr::Send(EvmSend {
+            target: recipient(address),
+            value: amount(),
+            loc: Loc::default(),
+        })));
+        for (expr, method) in [(transfer, "`transfer`"), (send, "`send`")] {
+            let bugs = check_stmt(expr);
+            assert_eq!(bugs.len(), 1);
+            let description = bugs[0].description.as_ref().unwrap();
+            assert!(description.starts_with(&format!("[gas-stipend] {method}")));
+            assert_eq!(bugs[0].loc.start_line, 7);
+        }
+    }
+
+    #[test]
+    fn test_transfer_on_non_address_is_not_flagged() {
+        // `token.transfer(x)` on a contract defining `transfer(uint256)`.
+        let transfer = Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(EvmTransfer {
+            target: recipient(Type::TypeRef("Token".to_string())),
+            amount: Box::new(Expr::Lit(Lit::one(None))),
+            loc: Loc::default(),
+        })));
+        assert!(check_stmt(transfer).is_empty());
+    }
+
+    #[test]
+    fn test_forwarded_or_missing_gas_is_not_flagged() {
+        let gasleft = Expr::Dialect(DialectExpr::Evm(EvmExpr::Gasleft(EvmGasleft {
+            loc: Loc::new(1, 1, 1, 1),
+        })));
+        assert!(check_call(Some(gasleft)).is_empty());
+        assert!(check_call(None).is_empty());
+    }
+}
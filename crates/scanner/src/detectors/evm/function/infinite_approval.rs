@@ -0,0 +1,233 @@
+//! Infinite Approval Detector
+//!
+//! Detects token approvals of the maximum amount:
+//!
+//! ```solidity
+//! token.approve(router, type(uint256).max);
+//! ```
+//!
+//! An unlimited allowance lets the spender move every token the contract
+//! will ever hold, so a compromised or upgraded spender can drain it long
+//! after the operation that needed the approval. The maximum is recognized
+//! as `type(uint256).max`, `2**256 - 1`, `~uint256(0)` or an equivalent
+//! literal. Revocations (`approve(spender, 0)`) and bounded amounts are not
+//! reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use num_traits::Zero;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    BinOp, CallArgs, CallExpr, ContractDecl, Expr, FunctionDecl, Lit, Module, Num, UnOp,
+};
+
+/// Scan detector for approvals of the maximum amount.
+#[derive(Debug, Default)]
+pub struct InfiniteApprovalDetector;
+
+impl InfiniteApprovalDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn int_literal(expr: &Expr) -> Option<&Num> {
+    match expr {
+        Expr::Lit(Lit::Num(n)) => Some(&n.value),
+        Expr::TypeCast(cast) => int_literal(&cast.expr),
+        _ => None,
+    }
+}
+
+/// Whether `expr` is the maximum `uint256`, or a literal of at least 2^255.
+fn is_max_amount(expr: &Expr) -> bool {
+    match expr {
+        // `type(uint256).max`, lowered to a call of `max__type__uint256`
+        Expr::FunctionCall(call) => {
+            matches!(&*call.callee, Expr::Var(v) if v.name.starts_with("max__type__uint"))
+        }
+        // `2**256 - 1`
+        Expr::BinOp(b) if b.op == BinOp::Sub => {
+            matches!(&*b.lhs, Expr::BinOp(p) if p.op == BinOp::Pow)
+        }
+        // `~uint256(0)`
+        Expr::UnOp(u) if u.op == UnOp::BitNot => {
+            matches!(int_literal(&u.operand), Some(Num::Int(i)) if i.value.is_zero())
+        }
+        Expr::TypeCast(cast) => is_max_amount(&cast.expr),
+        _ => match int_literal(expr) {
+            Some(Num::Int(i)) => i.value.bits() >= 256,
+            Some(Num::Hex(h)) => {
+                let digits = h.value.trim_start_matches("0x").replace('_', "");
+                digits.len() == 64 && digits.chars().all(|c| c == 'f' || c == 'F')
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Collects approvals of the maximum amount.
+#[derive(Default)]
+struct ApprovalFinder {
+    found: Vec<Option<Loc>>,
+}
+
+impl<'a> Visit<'a> for ApprovalFinder {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let (Expr::FieldAccess(fa), CallArgs::Positional(args)) = (&*expr.callee, &expr.args)
+            && matches!(fa.field.as_str(), "approve" | "safeApprove" | "forceApprove")
+            && let [_, amount] = args.as_slice()
+            && is_max_amount(amount)
+        {
+            self.found.push(expr.span.clone());
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+}
+
+impl ScanDetector for InfiniteApprovalDetector {
+    fn id(&self) -> &'static str {
+        "infinite-approval"
+    }
+
+    fn name(&self) -> &'static str {
+        "Infinite Approval"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects token approvals of type(uint256).max"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![250]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Approve only the amount the spender needs for the operation, and \
+         reset the allowance to zero afterwards."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/250.html"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20", "defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut finder = ApprovalFinder::default();
+        finder.visit_function_decl(func);
+
+        for loc in finder.found {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' grants an unlimited token allowance.",
+                    contract.name, func.name
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{num, typed_var};
+    use scirs::sir::*;
+
+    /// `type(uint256).max`
+    fn max_uint256() -> Expr {
+        Expr::FunctionCall(CallExpr {
+            callee: Box::new(typed_var("max__type__uint256", Type::None)),
+            args: CallArgs::Positional(vec![]),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    /// `token.approve(router, <amount>);`
+    fn check(amount: Expr) -> Vec<Bug> {
+        let approve = Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(typed_var("token", Type::TypeRef("IERC20".to_string()))),
+                field: "approve".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![typed_var("router", Type::None), amount]),
+            ty: Type::Bool,
+            span: None,
+        });
+        let body = vec![Stmt::Expr(ExprStmt { expr: approve, span: None })];
+        let func = FunctionDecl::new("setUp".to_string(), vec![], vec![], Some(body), None);
+        let contract = ContractDecl::new("Zap".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        InfiniteApprovalDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_infinite_approval_detector() {
+        let detector = InfiniteApprovalDetector::new();
+        assert_eq!(detector.id(), "infinite-approval");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_max_approval_is_flagged() {
+        assert_eq!(check(max_uint256()).len(), 1);
+    }
+
+    #[test]
+    fn test_bounded_approval_and_revocation_are_not_flagged() {
+        assert!(check(num(1_000)).is_empty());
+        assert!(check(num(0)).is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+//! Loop Clear Collection Detector
+//!
+//! Detects loops that "clear" a storage mapping or array element by element:
+//!
+//! ```solidity
+//! for (uint i = 0; i < users.length; i++) {
+//!     delete balances[users[i]];
+//! }
+//! ```
+//!
+//! Each `delete` (or `pop()`) is a storage write, so the gas cost grows with
+//! the collection. Once the collection is large enough the function no
+//! longer fits in a block and the data can never be cleared. Loops whose
+//! condition compares against a small literal bound are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use num_traits::ToPrimitive;
+use scirs::sir::lits::{Lit, Num};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    BinOp, CallExpr, ContractDecl, Expr, ForStmt, FunctionDecl, Module, UnOp, UnOpExpr, WhileStmt,
+};
+
+/// Largest literal loop bound considered small enough to be safe.
+const SMALL_LOOP_BOUND: u64 = 256;
+
+/// Scan detector for collections cleared in unbounded loops.
+#[derive(Debug, Default)]
+pub struct LoopClearCollectionDetector;
+
+impl LoopClearCollectionDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Returns `true` if `cond` compares the loop variable with a small literal,
+/// e.g. `i < 10`.
+fn has_small_literal_bound(cond: Option<&Expr>) -> bool {
+    let Some(Expr::BinOp(b)) = cond else {
+        return false;
+    };
+    if !matches!(b.op, BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge | BinOp::Ne) {
+        return false;
+    }
+    [&b.lhs, &b.rhs].into_iter().any(|side| match &**side {
+        Expr::Lit(Lit::Num(n)) => match &n.value {
+            Num::Int(i) => i.value.to_u64().is_some_and(|v| v <= SMALL_LOOP_BOUND),
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
+/// Storage variable at the root of an element access such as `a[i]` or
+/// `s.items[i]`.
+fn storage_root<'e>(expr: &'e Expr, storage: &[String]) -> Option<&'e str> {
+    match expr {
+        Expr::Var(v) if storage.contains(&v.name) => Some(&v.name),
+        Expr::IndexAccess(e) => storage_root(&e.base, storage),
+        Expr::FieldAccess(e) => storage_root(&e.base, storage),
+        _ => None,
+    }
+}
+
+/// Finds unbounded loops that delete or pop storage collection elements.
+struct Finder<'s> {
+    storage: &'s [String],
+    /// Spans of the enclosing unbounded loops, innermost last.
+    loops: Vec<Option<Loc>>,
+    /// Collection and span of each reported loop.
+    hits: Vec<(String, Option<Loc>)>,
+}
+
+impl Finder<'_> {
+    fn enter_loop(&mut self, cond: Option<&Expr>, span: Option<&Loc>) -> bool {
+        let unbounded = !has_small_literal_bound(cond);
+        if unbounded {
+            self.loops.push(span.cloned());
+        }
+        unbounded
+    }
+
+    fn record(&mut self, collection: &str) {
+        let Some(span) = self.loops.last() else {
+            return;
+        };
+        if !self.hits.iter().any(|(_, s)| s == span) {
+            self.hits.push((collection.to_string(), span.clone()));
+        }
+    }
+}
+
+impl<'a> Visit<'a> for Finder<'_> {
+    fn visit_for_stmt(&mut self, stmt: &'a ForStmt) {
+        let pushed = self.enter_loop(stmt.cond.as_ref(), stmt.span.as_ref());
+        visit::default::visit_for_stmt(self, stmt);
+        if pushed {
+            self.loops.pop();
+        }
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &'a WhileStmt) {
+        let pushed = self.enter_loop(Some(&stmt.cond), stmt.span.as_ref());
+        visit::default::visit_while_stmt(self, stmt);
+        if pushed {
+            self.loops.pop();
+        }
+    }
+
+    fn visit_unop_expr(&mut self, expr: &'a UnOpExpr) {
+        if expr.op == UnOp::Delete
+            && matches!(&*expr.operand, Expr::IndexAccess(_))
+            && let Some(root) = storage_root(&expr.operand, self.storage)
+        {
+            self.record(root);
+        }
+        visit::default::visit_unop_expr(self, expr);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::FieldAccess(fa) = &*expr.callee
+            && fa.field == "pop"
+            && let Some(root) = storage_root(&fa.base, self.storage)
+        {
+            self.record(root);
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+}
+
+impl ScanDetector for LoopClearCollectionDetector {
+    fn id(&self) -> &'static str {
+        "loop-clear-collection"
+    }
+
+    fn name(&self) -> &'static str {
+        "Loop Clear Collection"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects storage mappings or arrays cleared element by element in an \
+         unbounded loop, which can exceed the block gas limit"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::DenialOfService
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![400]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![128]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Do not clear collections in a loop. Version the data instead (e.g. key \
+         mappings by an epoch counter that is incremented to reset them), or \
+         clear in caller-sized batches."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-128",
+            "https://consensys.github.io/smart-contract-best-practices/attacks/denial-of-service/",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["gas"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let storage = contract.storage_names();
+        let mut finder = Finder { storage: &storage, loops: vec![], hits: vec![] };
+        finder.visit_function_decl(func);
+
+        for (collection, loc) in finder.hits {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' clears '{}' element by element in a loop whose \
+                     iteration count grows with the data.",
+                    contract.name, func.name, collection
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_function, num, storage, var};
+    use scirs::sir::*;
+
+    /// `for (; i < <bound>; ) { delete items[i]; }`
+    fn clear_loop(bound: Expr) -> Stmt {
+        let element = Expr::IndexAccess(IndexAccessExpr {
+            base: Box::new(var("items")),
+            index: Some(Box::new(var("i"))),
+            ty: Type::I256,
+            span: None,
+        });
+        let delete =
+            Expr::UnOp(UnOpExpr { op: UnOp::Delete, operand: Box::new(element), span: None });
+        Stmt::For(ForStmt {
+            init: None,
+            cond: Some(binop(BinOp::Lt, var("i"), bound)),
+            update: None,
+            body: vec![Stmt::Expr(ExprStmt { expr: delete, span: None })],
+            invariant: None,
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let func = FunctionDecl::new("reset".to_string(), vec![], vec![], Some(body), None);
+        check_function(&LoopClearCollectionDetector::new(), &func, vec![storage("items")])
+    }
+
+    #[test]
+    fn test_loop_clear_collection_detector() {
+        let detector = LoopClearCollectionDetector::new();
+        assert_eq!(detector.id(), "loop-clear-collection");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_delete_in_length_bounded_loop_is_flagged() {
+        // for (...; i < items.length; ...) delete items[i];
+        let length = Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(var("items")),
+            field: "length".to_string(),
+            ty: Type::I256,
+            span: None,
+        });
+        assert_eq!(check(vec![clear_loop(length)]).len(), 1);
+    }
+
+    #[test]
+    fn test_delete_in_small_fixed_loop_is_not_flagged() {
+        assert!(check(vec![clear_loop(num(10))]).is_empty());
+    }
+}
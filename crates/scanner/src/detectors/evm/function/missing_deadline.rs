@@ -0,0 +1,276 @@
+//! Missing Deadline Detector
+//!
+//! Detects swap, liquidity and permit calls whose deadline argument does not
+//! actually bound when the call may execute:
+//!
+//! ```solidity
+//! router.swapExactTokensForTokens(amountIn, minOut, path, to, block.timestamp);
+//! ```
+//!
+//! `block.timestamp` is the time of the block the transaction lands in, so
+//! the deadline check always passes; the same holds for `block.timestamp +
+//! n` and for far-future constants such as `type(uint256).max`. A pending
+//! transaction can then be held back and executed when the price has moved,
+//! or sandwiched. The deadline is found by position for the common Uniswap
+//! router functions and `permit`, and by name for struct arguments such as
+//! `ExactInputSingleParams({..., deadline: ...})`.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use num_traits::ToPrimitive;
+use scirs::sir::dialect::DialectExpr;
+use scirs::sir::dialect::evm::EvmExpr;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{BinOp, CallArgs, CallExpr, ContractDecl, Expr, FunctionDecl, Lit, Module, Num};
+
+/// Functions taking a deadline, with the position of the deadline argument.
+/// `None` means the last argument.
+const DEADLINE_FUNCTIONS: &[(&str, Option<usize>)] = &[
+    ("swapExactTokensForTokens", None),
+    ("swapTokensForExactTokens", None),
+    ("swapExactETHForTokens", None),
+    ("swapTokensForExactETH", None),
+    ("swapExactTokensForETH", None),
+    ("swapETHForExactTokens", None),
+    ("swapExactTokensForTokensSupportingFeeOnTransferTokens", None),
+    ("swapExactETHForTokensSupportingFeeOnTransferTokens", None),
+    ("swapExactTokensForETHSupportingFeeOnTransferTokens", None),
+    ("addLiquidity", None),
+    ("addLiquidityETH", None),
+    ("removeLiquidity", None),
+    ("removeLiquidityETH", None),
+    ("permit", Some(3)),
+];
+
+/// Deadlines above this (in 2106) are considered far in the future.
+const FAR_FUTURE: u64 = u32::MAX as u64;
+
+/// Scan detector for deadlines that never expire.
+#[derive(Debug, Default)]
+pub struct MissingDeadlineDetector;
+
+impl MissingDeadlineDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn is_timestamp(expr: &Expr) -> bool {
+    match expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Timestamp(_))) => true,
+        Expr::BinOp(b) if b.op == BinOp::Add => is_timestamp(&b.lhs) || is_timestamp(&b.rhs),
+        Expr::TypeCast(cast) => is_timestamp(&cast.expr),
+        _ => false,
+    }
+}
+
+fn is_far_future(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Num(n)) => {
+            matches!(&n.value, Num::Int(i) if i.value.to_u64().is_none_or(|v| v > FAR_FUTURE))
+        }
+        // `type(uint256).max`, lowered to a call of `max__type__uint256`
+        Expr::FunctionCall(call) => {
+            matches!(&*call.callee, Expr::Var(v) if v.name.starts_with("max__type__"))
+        }
+        Expr::TypeCast(cast) => is_far_future(&cast.expr),
+        _ => false,
+    }
+}
+
+/// Why `deadline` never expires, if it does not.
+fn never_expires(deadline: &Expr) -> Option<&'static str> {
+    if is_timestamp(deadline) {
+        Some("'block.timestamp'")
+    } else if is_far_future(deadline) {
+        Some("a far-future constant")
+    } else {
+        None
+    }
+}
+
+/// Deadline argument of `call`, if it is a recognized deadline-taking call.
+fn deadline_arg(call: &CallExpr) -> Option<&Expr> {
+    match &call.args {
+        CallArgs::Named(args) => args.iter().find(|a| a.name == "deadline").map(|a| &a.value),
+        CallArgs::Positional(args) => {
+            let Expr::FieldAccess(fa) = &*call.callee else {
+                return None;
+            };
+            let (_, position) = DEADLINE_FUNCTIONS
+                .iter()
+                .find(|(name, _)| *name == fa.field)?;
+            match position {
+                Some(i) => args.get(*i),
+                None => args.last(),
+            }
+        }
+    }
+}
+
+/// Collects calls whose deadline never expires.
+#[derive(Default)]
+struct DeadlineFinder {
+    found: Vec<(&'static str, Option<Loc>)>,
+}
+
+impl<'a> Visit<'a> for DeadlineFinder {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Some(reason) = deadline_arg(expr).and_then(never_expires) {
+            self.found.push((reason, expr.span.clone()));
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+}
+
+impl ScanDetector for MissingDeadlineDetector {
+    fn id(&self) -> &'static str {
+        "missing-deadline"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Deadline"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects swap, liquidity or permit calls whose deadline is \
+         block.timestamp or a far-future constant"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::FrontRunning
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![20]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Let the caller pass the deadline as a parameter and forward it, \
+         rather than using block.timestamp or a constant."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.uniswap.org/contracts/v2/reference/smart-contracts/router-02"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut finder = DeadlineFinder::default();
+        finder.visit_function_decl(func);
+
+        for (reason, loc) in finder.found {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' passes {} as a deadline, so the call never expires.",
+                    contract.name, func.name, reason
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::dialect::evm::EvmTimestamp;
+    use scirs::sir::*;
+
+    /// `router.swapExactTokensForTokens(amountIn, minOut, path, to,
+    /// <deadline>);`
+    fn check(deadline: Expr) -> Vec<Bug> {
+        let swap = Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(typed_var(
+                    "router",
+                    Type::TypeRef("IUniswapV2Router02".to_string()),
+                )),
+                field: "swapExactTokensForTokens".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![
+                typed_var("amountIn", Type::I256),
+                typed_var("minOut", Type::I256),
+                typed_var("path", Type::None),
+                typed_var("to", Type::None),
+                deadline,
+            ]),
+            ty: Type::None,
+            span: None,
+        });
+        let body = vec![Stmt::Expr(ExprStmt { expr: swap, span: None })];
+        let params = vec![Param::new("deadline".to_string(), Type::I256)];
+        let func = FunctionDecl::new("swap".to_string(), params, vec![], Some(body), None);
+        let contract = ContractDecl::new("Zap".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        MissingDeadlineDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_missing_deadline_detector() {
+        let detector = MissingDeadlineDetector::new();
+        assert_eq!(detector.id(), "missing-deadline");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_block_timestamp_deadline_is_flagged() {
+        let timestamp = Expr::Dialect(DialectExpr::Evm(EvmExpr::Timestamp(EvmTimestamp {
+            loc: Loc::new(1, 1, 1, 1),
+        })));
+        assert_eq!(check(timestamp).len(), 1);
+    }
+
+    #[test]
+    fn test_caller_deadline_is_not_flagged() {
+        assert!(check(typed_var("deadline", Type::I256)).is_empty());
+    }
+}
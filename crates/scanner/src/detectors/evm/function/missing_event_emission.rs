@@ -0,0 +1,400 @@
+//! Missing Event Emission Detector
+//!
+//! Detects public and external functions that change critical state
+//! without emitting an event, leaving off-chain monitoring blind to the
+//! change:
+//!
+//! ```solidity
+//! function setFee(uint256 newFee) external onlyOwner {
+//!     fee = newFee;  // no `emit FeeChanged(newFee);`
+//! }
+//! ```
+//!
+//! A storage variable is critical when its name matches `critical_vars`, a
+//! case-insensitive regex matching owner, pause, fee and rate variables by
+//! default. Writes and `emit`s in the internal functions a function calls
+//! count as its own, so an `onlyOwner` wrapper around an emitting
+//! `_transferOwnership` is not reported. Constructors are ignored.
+
+use crate::detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
+use crate::detectors::evm::utils::{ancestor_names, storage_names};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use regex::Regex;
+use scirs::sir::dialect::evm::EvmStmt;
+use scirs::sir::dialect::{DialectStmt, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, CallExpr, ContractDecl, Decl, Expr, FunctionDecl, MemberDecl,
+    Module, UnOp, UnOpExpr,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Critical variable names matched when `critical_vars` is not configured.
+const DEFAULT_CRITICAL_VARS: &str = "(?i)(owner|paused|fee|rate)";
+
+/// Scan detector for critical state changes without an event.
+#[derive(Debug)]
+pub struct MissingEventEmissionDetector {
+    /// Names of the critical storage variables.
+    critical_vars: Regex,
+}
+
+impl MissingEventEmissionDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for MissingEventEmissionDetector {
+    fn default() -> Self {
+        Self { critical_vars: Regex::new(DEFAULT_CRITICAL_VARS).unwrap() }
+    }
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Collects the critical storage variables written, the `emit`s and the
+/// internal calls of a function body.
+struct EffectFinder<'s> {
+    critical: &'s HashSet<String>,
+    written: BTreeSet<String>,
+    emits: bool,
+    calls: Vec<String>,
+}
+
+impl EffectFinder<'_> {
+    fn write(&mut self, lhs: &Expr) {
+        if let Some(name) = root_name(lhs)
+            && self.critical.contains(name)
+        {
+            self.written.insert(name.to_string());
+        }
+    }
+}
+
+impl<'a> Visit<'a> for EffectFinder<'_> {
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        self.write(&stmt.lhs);
+        visit::default::visit_assign_stmt(self, stmt);
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        self.write(&stmt.lhs);
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_unop_expr(&mut self, expr: &'a UnOpExpr) {
+        if expr.op == UnOp::Delete {
+            self.write(&expr.operand);
+        }
+        visit::default::visit_unop_expr(self, expr);
+    }
+
+    fn visit_call_expr(&mut self, call: &'a CallExpr) {
+        if let Expr::Var(v) = &*call.callee {
+            self.calls.push(v.name.clone());
+        }
+        visit::default::visit_call_expr(self, call);
+    }
+
+    fn visit_dialect_stmt(&mut self, stmt: &'a DialectStmt) {
+        if matches!(stmt, DialectStmt::Evm(EvmStmt::EmitEvent(_))) {
+            self.emits = true;
+        }
+    }
+}
+
+/// Functions of `contract` and of its ancestors declared in `module`, by
+/// name, the contract's own taking precedence.
+fn functions_by_name<'m>(
+    contract: &'m ContractDecl,
+    module: &'m Module,
+) -> HashMap<&'m str, &'m FunctionDecl> {
+    let ancestors = ancestor_names(contract, module);
+    let mut functions = HashMap::new();
+    let contracts = module.decls.iter().filter_map(|decl| match decl {
+        Decl::Contract(c) if ancestors.contains(&c.name) => Some(c),
+        _ => None,
+    });
+    for c in contracts.chain([contract]) {
+        for member in &c.members {
+            if let MemberDecl::Function(f) = member {
+                functions.insert(f.name.as_str(), f);
+            }
+        }
+    }
+    functions
+}
+
+impl ScanDetector for MissingEventEmissionDetector {
+    fn id(&self) -> &'static str {
+        "missing-event-emission"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Event Emission"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects functions that change critical state such as the owner, \
+         pause flag or fees without emitting an event"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![778]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Emit an event describing the change, e.g. \
+         `emit OwnershipTransferred(oldOwner, newOwner)`, whenever critical \
+         state is updated."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/778.html"]
+    }
+
+    fn configure(&mut self, params: &DetectorParams) {
+        if let Some(regex) = params.get("critical_vars").and_then(|v| Regex::new(v).ok()) {
+            self.critical_vars = regex;
+        }
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        vec!["critical_vars"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        if !(func.is_public() || func.is_external()) || func.is_constructor() {
+            return vec![];
+        }
+
+        let critical: HashSet<String> = storage_names(contract, module)
+            .into_iter()
+            .filter(|name| self.critical_vars.is_match(name))
+            .collect();
+        if critical.is_empty() {
+            return vec![];
+        }
+
+        // Collect the effects of the function and its internal callees.
+        let functions = functions_by_name(contract, module);
+        let mut finder = EffectFinder {
+            critical: &critical,
+            written: BTreeSet::new(),
+            emits: false,
+            calls: vec![],
+        };
+        let mut visited = HashSet::from([func.name.as_str()]);
+        let mut worklist = vec![func];
+        while let Some(f) = worklist.pop() {
+            if let Some(body) = &f.body {
+                finder.visit_stmts(body);
+            }
+            for callee in std::mem::take(&mut finder.calls) {
+                if let Some((name, callee)) = functions.get_key_value(callee.as_str())
+                    && visited.insert(name)
+                {
+                    worklist.push(callee);
+                }
+            }
+        }
+        if finder.emits || finder.written.is_empty() {
+            return vec![];
+        }
+
+        let vars: Vec<String> = finder.written.iter().map(|v| format!("'{v}'")).collect();
+        vec![Bug::new(
+            self.name(),
+            Some(&format!(
+                "'{}.{}' changes {} without emitting an event.",
+                contract.name,
+                func.name,
+                vars.join(", ")
+            )),
+            func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::var;
+    use scirs::sir::attrs::{evm_attrs, sir_attrs};
+    use scirs::sir::dialect::evm::EvmEmitEvent;
+    use scirs::sir::*;
+
+    fn assign(lhs: &str, rhs: &str) -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var(lhs), rhs: var(rhs), span: None })
+    }
+
+    fn emit(name: &str) -> Stmt {
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::EmitEvent(EvmEmitEvent {
+            event: name.to_string(),
+            args: vec![],
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    fn call(name: &str) -> Stmt {
+        let call = Expr::FunctionCall(CallExpr {
+            callee: Box::new(var(name)),
+            args: CallArgs::Positional(vec![var("value")]),
+            ty: Type::None,
+            span: None,
+        });
+        Stmt::Expr(ExprStmt { expr: call, span: None })
+    }
+
+    fn function(name: &str, visibility: &str, body: Vec<Stmt>) -> FunctionDecl {
+        let mut func = FunctionDecl::new(name.to_string(), vec![], vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String(visibility.into())));
+        func
+    }
+
+    fn check(detector: &MissingEventEmissionDetector, funcs: Vec<FunctionDecl>) -> Vec<Bug> {
+        let mut members: Vec<MemberDecl> = ["owner", "protocolFee", "balance"]
+            .iter()
+            .map(|name| {
+                MemberDecl::Storage(StorageDecl::new(name.to_string(), Type::I256, None, None))
+            })
+            .collect();
+        members.extend(funcs.into_iter().map(MemberDecl::Function));
+        let contract = ContractDecl::new("Vault".to_string(), members, None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        contract
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                MemberDecl::Function(f) => Some(f),
+                _ => None,
+            })
+            .flat_map(|f| detector.check_function(f, &contract, &module))
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_event_emission_detector() {
+        let detector = MissingEventEmissionDetector::new();
+        assert_eq!(detector.id(), "missing-event-emission");
+        assert_eq!(detector.risk_level(), RiskLevel::Low);
+    }
+
+    #[test]
+    fn test_critical_write_without_emit_is_flagged() {
+        let detector = MissingEventEmissionDetector::new();
+        let bugs = check(
+            &detector,
+            vec![
+                function("setFee", "external", vec![assign("protocolFee", "value")]),
+                function("deposit", "external", vec![assign("balance", "value")]),
+                function("setOwner", "public", vec![assign("owner", "value"), emit("OwnerSet")]),
+            ],
+        );
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some("'Vault.setFee' changes 'protocolFee' without emitting an event.")
+        );
+
+        let mut constructor = function("constructor", "public", vec![assign("owner", "value")]);
+        constructor
+            .attrs
+            .push(Attr::evm(evm_attrs::IS_CONSTRUCTOR, AttrValue::Bool(true)));
+        assert!(check(&detector, vec![constructor]).is_empty());
+    }
+
+    #[test]
+    fn test_effects_of_internal_callees_count() {
+        let detector = MissingEventEmissionDetector::new();
+        // `transferOwnership` delegates to `_transferOwnership`, which emits.
+        let bugs = check(
+            &detector,
+            vec![
+                function("transferOwnership", "external", vec![call("_transferOwnership")]),
+                function(
+                    "_transferOwnership",
+                    "internal",
+                    vec![assign("owner", "value"), emit("OwnershipTransferred")],
+                ),
+                function("setFee", "external", vec![call("_setFee")]),
+                function("_setFee", "internal", vec![assign("protocolFee", "value")]),
+            ],
+        );
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_ref()
+                .unwrap()
+                .starts_with("'Vault.setFee'")
+        );
+    }
+
+    #[test]
+    fn test_critical_vars_option() {
+        let mut detector = MissingEventEmissionDetector::new();
+        let params =
+            DetectorParams::from([("critical_vars".to_string(), "^balance$".to_string())]);
+        detector.configure(&params);
+        let bugs = check(
+            &detector,
+            vec![
+                function("setFee", "external", vec![assign("protocolFee", "value")]),
+                function("deposit", "external", vec![assign("balance", "value")]),
+            ],
+        );
+        assert_eq!(bugs.len(), 1);
+        assert!(bugs[0].description.as_ref().unwrap().contains("'balance'"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Missing Reentrancy Guard Detector
+//!
+//! A lint for public and external functions that both make an external call
+//! and write storage without a reentrancy guard:
+//!
+//! ```solidity
+//! function withdraw(uint256 amount) external {  // no `nonReentrant`
+//!     (bool ok, ) = msg.sender.call{value: amount}("");
+//!     balances[msg.sender] -= amount;
+//! }
+//! ```
+//!
+//! Unlike `reentrancy` and `cei-violation`, this does not look at the
+//! order of the call and the write: any unguarded function doing both is
+//! reported. A function is guarded if it applies one of the
+//! `guard_modifiers` (`nonReentrant` and `noReentrancy` by default, matched
+//! case-insensitively) or carries a reentrancy-guard attribute such as
+//! Vyper's `@nonreentrant`. `view` and `pure` functions are skipped.
+
+use crate::detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
+use crate::detectors::evm::utils::{stmt_has_external_call, storage_names};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::attrs::sir_attrs;
+use scirs::sir::dialect::EvmFunctionExt;
+use scirs::sir::utils::visit::Visit;
+use scirs::sir::{AssignStmt, AttrValue, AugAssignStmt, ContractDecl, Expr, FunctionDecl, Module};
+use std::collections::HashSet;
+
+/// Guard modifiers recognized when `guard_modifiers` is not configured.
+const DEFAULT_GUARD_MODIFIERS: &[&str] = &["nonReentrant", "noReentrancy"];
+
+/// Scan detector for unguarded functions making external calls and writing
+/// storage.
+#[derive(Debug)]
+pub struct MissingReentrancyGuardDetector {
+    /// Names of the modifiers that guard against reentrancy.
+    guard_modifiers: Vec<String>,
+}
+
+impl MissingReentrancyGuardDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if `func` applies a guard modifier or carries a
+    /// reentrancy-guard attribute.
+    fn is_guarded(&self, func: &FunctionDecl) -> bool {
+        func.has_reentrancy_guard()
+            || func.modifier_invocs.iter().any(|m| {
+                self.guard_modifiers
+                    .iter()
+                    .any(|g| g.eq_ignore_ascii_case(&m.name))
+            })
+    }
+}
+
+impl Default for MissingReentrancyGuardDetector {
+    fn default() -> Self {
+        Self {
+            guard_modifiers: DEFAULT_GUARD_MODIFIERS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Returns `true` for functions declared `view` or `pure`.
+fn is_read_only(func: &FunctionDecl) -> bool {
+    func.attrs.iter().any(|a| match &a.value {
+        AttrValue::String(s) if a.namespace == "sir" && a.key == sir_attrs::MUTABILITY => {
+            matches!(s.as_str(), "view" | "pure" | "constant")
+        }
+        _ => false,
+    })
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Finds assignments to storage variables.
+struct StorageWriteFinder<'s> {
+    storage: &'s HashSet<String>,
+    found: bool,
+}
+
+impl<'a> Visit<'a> for StorageWriteFinder<'_> {
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        self.found |= root_name(&stmt.lhs).is_some_and(|n| self.storage.contains(n));
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        self.found |= root_name(&stmt.lhs).is_some_and(|n| self.storage.contains(n));
+    }
+}
+
+impl ScanDetector for MissingReentrancyGuardDetector {
+    fn id(&self) -> &'static str {
+        "missing-reentrancy-guard"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Reentrancy Guard"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects public and external functions that make an external call and \
+         write storage without a reentrancy guard modifier"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Reentrancy
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![841]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![107]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Apply a reentrancy guard such as OpenZeppelin's `nonReentrant` to \
+         functions that make external calls and update state."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-107",
+            "https://docs.openzeppelin.com/contracts/5.x/api/utils#ReentrancyGuard",
+        ]
+    }
+
+    fn configure(&mut self, params: &DetectorParams) {
+        if let Some(names) = params.get("guard_modifiers") {
+            self.guard_modifiers = names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        vec!["guard_modifiers"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let Some(body) = func.body.as_deref() else {
+            return vec![];
+        };
+        if !(func.is_public() || func.is_external())
+            || func.is_constructor()
+            || is_read_only(func)
+            || self.is_guarded(func)
+            || !body.iter().any(stmt_has_external_call)
+        {
+            return vec![];
+        }
+
+        let storage = storage_names(contract, module);
+        let mut writes = StorageWriteFinder { storage: &storage, found: false };
+        writes.visit_stmts(body);
+        if !writes.found {
+            return vec![];
+        }
+
+        vec![Bug::new(
+            self.name(),
+            Some(&format!(
+                "'{}.{}' makes an external call and writes storage without a \
+                 reentrancy guard.",
+                contract.name, func.name
+            )),
+            func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::dialect::evm::{EvmExpr, EvmTransfer, EvmType};
+    use scirs::sir::*;
+
+    /// `payable(to).transfer(amount); balance = 0;`
+    fn withdraw(modifier: Option<&str>, mutability: Option<&str>) -> FunctionDecl {
+        let transfer = Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(EvmTransfer {
+            target: Box::new(typed_var("to", Type::Dialect(DialectType::Evm(EvmType::Address)))),
+            amount: Box::new(typed_var("amount", Type::I256)),
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        let body = vec![
+            Stmt::Expr(ExprStmt { expr: transfer, span: None }),
+            Stmt::Assign(AssignStmt {
+                lhs: typed_var("balance", Type::I256),
+                rhs: typed_var("zero", Type::I256),
+                span: None,
+            }),
+        ];
+        let mut func = FunctionDecl::new("withdraw".to_string(), vec![], vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String("external".into())));
+        if let Some(mutability) = mutability {
+            func.attrs
+                .push(Attr::sir(sir_attrs::MUTABILITY, AttrValue::String(mutability.into())));
+        }
+        if let Some(name) = modifier {
+            func.modifier_invocs.push(ModifierInvoc {
+                name: name.to_string(),
+                args: vec![],
+                span: None,
+            });
+        }
+        func
+    }
+
+    fn check(detector: &MissingReentrancyGuardDetector, func: FunctionDecl) -> Vec<Bug> {
+        let balance = StorageDecl::new("balance".to_string(), Type::I256, None, None);
+        let contract =
+            ContractDecl::new("Vault".to_string(), vec![MemberDecl::Storage(balance)], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        detector.check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_missing_reentrancy_guard_detector() {
+        let detector = MissingReentrancyGuardDetector::new();
+        assert_eq!(detector.id(), "missing-reentrancy-guard");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_unguarded_function_is_flagged() {
+        let detector = MissingReentrancyGuardDetector::new();
+        let bugs = check(&detector, withdraw(None, None));
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "'Vault.withdraw' makes an external call and writes storage without a \
+                 reentrancy guard."
+            )
+        );
+
+        assert!(check(&detector, withdraw(Some("nonReentrant"), None)).is_empty());
+        assert!(check(&detector, withdraw(None, Some("view"))).is_empty());
+    }
+
+    #[test]
+    fn test_guard_modifiers_option() {
+        let mut detector = MissingReentrancyGuardDetector::new();
+        let params =
+            DetectorParams::from([("guard_modifiers".to_string(), "lock, mutex".to_string())]);
+        detector.configure(&params);
+        assert!(check(&detector, withdraw(Some("lock"), None)).is_empty());
+        assert_eq!(check(&detector, withdraw(Some("nonReentrant"), None)).len(), 1);
+    }
+}
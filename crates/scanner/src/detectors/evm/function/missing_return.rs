@@ -0,0 +1,290 @@
+//! Missing Return Detector
+//!
+//! Detects functions declaring return values where some control-flow path
+//! reaches the end of the body without a `return` and without assigning
+//! every named return variable. Solidity accepts this and silently returns
+//! zero values, which is rarely what the author meant.
+//!
+//! Paths are followed through the structured body: both branches of an `if`
+//! must assign a variable for it to count, loop bodies may not run at all,
+//! and `return`/`revert` end a path.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmStmt;
+use scirs::sir::{ContractDecl, DialectStmt, Expr, FunctionDecl, Lit, Module, Stmt, TupleExpr};
+use std::collections::HashSet;
+
+/// Scan detector for paths that fall through without returning.
+#[derive(Debug, Default)]
+pub struct MissingReturnDetector;
+
+impl MissingReturnDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Variables definitely assigned on the paths falling out of `stmts`, or
+/// `None` if no path falls out (every path returns, reverts or jumps).
+fn fall_through(stmts: &[Stmt], assigned: HashSet<String>) -> Option<HashSet<String>> {
+    stmts
+        .iter()
+        .try_fold(assigned, |assigned, stmt| fall_through_stmt(stmt, assigned))
+}
+
+fn fall_through_stmt(stmt: &Stmt, mut assigned: HashSet<String>) -> Option<HashSet<String>> {
+    match stmt {
+        Stmt::Return(_) | Stmt::Revert(_) | Stmt::Break | Stmt::Continue => None,
+        Stmt::Assign(s) => {
+            assigned_vars(&s.lhs, &mut assigned);
+            Some(assigned)
+        }
+        Stmt::If(s) => {
+            let then_out = fall_through(&s.then_body, assigned.clone());
+            let else_out = match &s.else_body {
+                Some(body) => fall_through(body, assigned),
+                None => Some(assigned),
+            };
+            join(then_out, else_out)
+        }
+        Stmt::While(s) if is_true(&s.cond) && !has_break(&s.body) => None,
+        Stmt::For(s) if s.cond.as_ref().is_none_or(is_true) && !has_break(&s.body) => None,
+        // The body may not run, so it assigns nothing definitely.
+        Stmt::While(_) | Stmt::For(_) => Some(assigned),
+        Stmt::Block(inner) => fall_through(inner, assigned),
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => tc
+            .catch_clauses
+            .iter()
+            .fold(fall_through(&tc.body, assigned.clone()), |out, c| {
+                join(out, fall_through(&c.body, assigned.clone()))
+            }),
+        _ => Some(assigned),
+    }
+}
+
+/// Merge the states of two paths meeting after a branch.
+fn join(a: Option<HashSet<String>>, b: Option<HashSet<String>>) -> Option<HashSet<String>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn assigned_vars(lhs: &Expr, assigned: &mut HashSet<String>) {
+    match lhs {
+        Expr::Var(v) => {
+            assigned.insert(v.name.clone());
+        }
+        Expr::Tuple(TupleExpr { elems, .. }) => {
+            for elem in elems.iter().flatten() {
+                assigned_vars(elem, assigned);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_true(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(Lit::Bool(b)) if b.value)
+}
+
+/// Returns `true` if `stmts` contain a `break` of the enclosing loop.
+fn has_break(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Stmt::Break => true,
+        Stmt::If(s) => has_break(&s.then_body) || s.else_body.as_deref().is_some_and(has_break),
+        Stmt::Block(inner) => has_break(inner),
+        _ => false,
+    })
+}
+
+impl ScanDetector for MissingReturnDetector {
+    fn id(&self) -> &'static str {
+        "missing-return"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Return"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects functions with a path that reaches the end of the body \
+         without returning or assigning all named return values"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![457]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Assign every return value on all paths, end each path with an explicit \
+         `return`, or `revert` on paths that have no meaningful result."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/contracts.html#return-variables",
+            "https://cwe.mitre.org/data/definitions/457.html",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let Some(body) = &func.body else {
+            return bugs;
+        };
+        if func.returns.is_empty() {
+            return bugs;
+        }
+        let Some(assigned) = fall_through(body, HashSet::new()) else {
+            return bugs;
+        };
+
+        // Unnamed return values can only be set by `return`.
+        let names: Vec<Option<&str>> = match func.return_names.as_slice() {
+            [] => vec![None; func.returns.len()],
+            names => names.iter().map(|n| n.as_deref()).collect(),
+        };
+        let missing: Vec<&str> = names
+            .iter()
+            .filter_map(|n| match n {
+                Some(name) if assigned.contains(*name) => None,
+                Some(name) => Some(*name),
+                None => Some("<unnamed>"),
+            })
+            .collect();
+        if missing.is_empty() {
+            return bugs;
+        }
+
+        bugs.push(Bug::new(
+            self.name(),
+            Some(&format!(
+                "Function '{}.{}' can reach the end of its body without returning; \
+                 return value(s) {} may be left at their default.",
+                contract.name,
+                func.name,
+                missing.join(", ")
+            )),
+            func.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        ));
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::var;
+    use scirs::sir::*;
+
+    fn assign(name: &str) -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var(name), rhs: var("x"), span: None })
+    }
+
+    fn if_stmt(then_body: Vec<Stmt>, else_body: Option<Vec<Stmt>>) -> Stmt {
+        Stmt::If(IfStmt {
+            cond: Expr::Var(VarExpr::new("c".to_string(), Type::Bool, None)),
+            then_body,
+            else_body,
+            span: None,
+        })
+    }
+
+    fn check(return_names: Vec<Option<String>>, body: Vec<Stmt>) -> Vec<Bug> {
+        let mut func = FunctionDecl::new(
+            "price".to_string(),
+            vec![],
+            vec![Type::I256; return_names.len()],
+            Some(body),
+            None,
+        );
+        func.return_names = return_names;
+        let contract = ContractDecl {
+            name: "Oracle".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        MissingReturnDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_missing_return_detector() {
+        let detector = MissingReturnDetector::new();
+        assert_eq!(detector.id(), "missing-return");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_conditional_assignment_is_flagged() {
+        // function price() returns (uint r) { if (c) { r = x; } }
+        let bugs = check(vec![Some("r".to_string())], vec![if_stmt(vec![assign("r")], None)]);
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_assignment_on_all_paths_is_not_flagged() {
+        let body = vec![if_stmt(vec![assign("r")], Some(vec![assign("r")]))];
+        assert!(check(vec![Some("r".to_string())], body).is_empty());
+    }
+
+    #[test]
+    fn test_return_or_revert_on_all_paths_is_not_flagged() {
+        let ret = Stmt::Return(ReturnStmt { value: Some(var("x")), span: None });
+        let revert = Stmt::Revert(RevertStmt { error: None, args: vec![], span: None });
+        let body = vec![if_stmt(vec![ret], Some(vec![revert]))];
+        assert!(check(vec![None], body).is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_return_without_return_is_flagged() {
+        assert_eq!(check(vec![None], vec![assign("y")]).len(), 1);
+    }
+}
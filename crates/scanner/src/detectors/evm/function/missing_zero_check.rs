@@ -0,0 +1,326 @@
+//! Missing Zero Address Check Detector
+//!
+//! Detects public and external functions that store an address parameter in
+//! a state variable without ruling out the zero address first:
+//!
+//! ```solidity
+//! function setOwner(address newOwner) external onlyOwner {
+//!     owner = newOwner;  // `newOwner` may be address(0)
+//! }
+//! ```
+//!
+//! A zero owner, treasury or oracle usually locks the contract or burns the
+//! funds routed to it. A parameter counts as checked where a `require`,
+//! `assert` or `if (...) revert` ruling out zero dominates the assignment:
+//! checks inside a branch only cover that branch, and a reassignment of the
+//! parameter drops the check. State variables of direct parents declared in
+//! the same module are included.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::{
+    always_exits, nonzero_when_false, nonzero_when_true, unwrap_casts,
+};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmFunctionExt, EvmStmt, EvmTypeExt};
+use scirs::sir::{AssignStmt, ContractDecl, Decl, DialectStmt, Expr, FunctionDecl, Module, Stmt};
+use std::collections::HashSet;
+
+/// Scan detector for address setters without a zero-address check.
+#[derive(Debug, Default)]
+pub struct MissingZeroCheckDetector;
+
+impl MissingZeroCheckDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Base variable of an assignment target.
+fn root_name(lhs: &Expr) -> Option<&str> {
+    match lhs {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => None,
+    }
+}
+
+/// Walks a function body along its structure, so that the checks known at a
+/// statement are exactly those dominating it, and records the unchecked
+/// parameters stored in state variables.
+struct SetterWalker<'s> {
+    storage: &'s HashSet<String>,
+    params: HashSet<String>,
+    /// Parameter, state variable and location of each unchecked store.
+    stores: Vec<(String, String, Option<Loc>)>,
+}
+
+impl SetterWalker<'_> {
+    fn walk(&mut self, stmts: &[Stmt], mut checked: HashSet<String>) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::If(s) => {
+                    let mut names = Vec::new();
+                    nonzero_when_true(&s.cond, &mut names);
+                    let then_checked = checked.iter().cloned().chain(names).collect();
+                    self.walk(&s.then_body, then_checked);
+
+                    let mut names = Vec::new();
+                    nonzero_when_false(&s.cond, &mut names);
+                    let else_checked = checked.iter().cloned().chain(names).collect();
+                    if let Some(else_body) = &s.else_body {
+                        self.walk(else_body, else_checked);
+                    } else if always_exits(&s.then_body) {
+                        checked = else_checked;
+                    }
+                }
+                Stmt::While(s) => {
+                    let mut names = Vec::new();
+                    nonzero_when_true(&s.cond, &mut names);
+                    self.walk(&s.body, checked.iter().cloned().chain(names).collect());
+                }
+                Stmt::For(s) => self.walk(&s.body, checked.clone()),
+                Stmt::Block(inner) => self.walk(inner, checked.clone()),
+                Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+                    self.walk(&tc.body, checked.clone());
+                    for clause in &tc.catch_clauses {
+                        self.walk(&clause.body, checked.clone());
+                    }
+                }
+                Stmt::Assert(a) => {
+                    let mut names = Vec::new();
+                    nonzero_when_true(&a.cond, &mut names);
+                    checked.extend(names);
+                }
+                Stmt::Assign(a) => {
+                    self.check_store(a, &checked);
+                    if let Expr::Var(v) = &a.lhs {
+                        checked.remove(&v.name);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_store(&mut self, stmt: &AssignStmt, checked: &HashSet<String>) {
+        let Some(var) = root_name(&stmt.lhs) else {
+            return;
+        };
+        if let Expr::Var(param) = unwrap_casts(&stmt.rhs)
+            && self.params.contains(&param.name)
+            && !checked.contains(&param.name)
+            && self.storage.contains(var)
+            && stmt.lhs.typ().is_address()
+        {
+            let loc = stmt.span.clone();
+            self.stores.push((param.name.clone(), var.to_string(), loc));
+        }
+    }
+}
+
+impl ScanDetector for MissingZeroCheckDetector {
+    fn id(&self) -> &'static str {
+        "missing-zero-check"
+    }
+
+    fn name(&self) -> &'static str {
+        "Missing Zero Address Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects address parameters stored in state variables without a \
+         check against the zero address"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![20]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check address parameters with `require(param != address(0))` before \
+         storing them in state variables."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/20.html"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if !(func.is_public() || func.is_external()) {
+            return bugs;
+        }
+        let params: HashSet<String> = func
+            .params
+            .iter()
+            .filter(|p| p.ty.is_address())
+            .map(|p| p.name.clone())
+            .collect();
+        if params.is_empty() {
+            return bugs;
+        }
+
+        let mut storage: HashSet<String> = contract.storage_names().into_iter().collect();
+        for decl in &module.decls {
+            if let Decl::Contract(parent) = decl
+                && contract.parents.contains(&parent.name)
+            {
+                storage.extend(parent.storage_names());
+            }
+        }
+
+        let mut walker = SetterWalker { storage: &storage, params, stores: vec![] };
+        walker.walk(func.body.as_deref().unwrap_or_default(), HashSet::new());
+
+        for (param, var, loc) in walker.stores {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' stores '{}' in '{}' without checking that it is not the zero \
+                     address.",
+                    contract.name, func.name, param, var
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::dialect::DialectType;
+    use scirs::sir::dialect::evm::EvmType;
+    use scirs::sir::*;
+
+    fn address() -> Type {
+        Type::Dialect(DialectType::Evm(EvmType::Address))
+    }
+
+    fn var(name: &str) -> Expr {
+        Expr::Var(VarExpr::new(name.to_string(), address(), None))
+    }
+
+    fn zero() -> Expr {
+        Expr::Lit(Lit::Num(NumLit {
+            value: Num::Int(IntNum { value: 0.into(), typ: Type::I256 }),
+            span: None,
+        }))
+    }
+
+    /// `require(newOwner != address(0))`
+    fn require_nonzero() -> Stmt {
+        Stmt::Assert(AssertStmt {
+            cond: Expr::BinOp(BinOpExpr {
+                op: BinOp::Ne,
+                lhs: Box::new(var("newOwner")),
+                rhs: Box::new(zero()),
+                overflow: OverflowSemantics::Checked,
+                span: None,
+            }),
+            message: None,
+            span: None,
+        })
+    }
+
+    /// `owner = newOwner`
+    fn store() -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var("owner"), rhs: var("newOwner"), span: None })
+    }
+
+    fn check(body: Vec<Stmt>, visibility: &str) -> Vec<Bug> {
+        let params = vec![Param::new("newOwner".to_string(), address())];
+        let mut func = FunctionDecl::new("setOwner".to_string(), params, vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String(visibility.into())));
+        let owner = StorageDecl::new("owner".to_string(), address(), None, None);
+        let contract =
+            ContractDecl::new("Vault".to_string(), vec![MemberDecl::Storage(owner)], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        MissingZeroCheckDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_missing_zero_check_detector() {
+        let detector = MissingZeroCheckDetector::new();
+        assert_eq!(detector.id(), "missing-zero-check");
+        assert_eq!(detector.risk_level(), RiskLevel::Low);
+    }
+
+    #[test]
+    fn test_unchecked_setter_is_flagged() {
+        let bugs = check(vec![store()], "external");
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "'Vault.setOwner' stores 'newOwner' in 'owner' without checking that it is \
+                 not the zero address."
+            )
+        );
+    }
+
+    #[test]
+    fn test_dominating_check_is_required() {
+        assert!(check(vec![require_nonzero(), store()], "public").is_empty());
+
+        // A check inside a branch does not cover the store after it.
+        let branch = Stmt::If(IfStmt {
+            cond: var("flag"),
+            then_body: vec![require_nonzero()],
+            else_body: None,
+            span: None,
+        });
+        assert_eq!(check(vec![branch, store()], "public").len(), 1);
+    }
+
+    #[test]
+    fn test_internal_setter_is_not_flagged() {
+        assert!(check(vec![store()], "internal").is_empty());
+    }
+}
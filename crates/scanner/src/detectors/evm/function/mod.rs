@@ -1,29 +1,99 @@
 //! Function-level EVM detectors
 
 pub mod arithmetic_overflow;
+pub mod assembly_memory_safety;
+pub mod assembly_unchecked_math;
 pub mod bad_randomness;
+pub mod balance_confusion;
+pub mod block_number_dependence;
 pub mod cei_violation;
+pub mod complex_signature;
+pub mod constructor_external_call;
+pub mod dangling_storage_ref;
+pub mod decimals_mismatch;
+pub mod deep_nesting;
 pub mod delegatecall;
 pub mod denial_of_service;
+pub mod deprecated_difficulty;
 pub mod deprecated_features;
+pub mod deprecated_selfdestruct;
+pub mod encode_type_mismatch;
+pub mod eoa_only_check;
+pub mod hardcoded_gas;
+pub mod infinite_approval;
+pub mod loop_clear_collection;
 pub mod low_level_call;
+pub mod missing_deadline;
+pub mod missing_event_emission;
+pub mod missing_reentrancy_guard;
+pub mod missing_return;
+pub mod missing_zero_check;
+pub mod modify_while_iterate;
+pub mod mul_overflow_before_div;
+pub mod possible_underflow_revert;
+pub mod precision_loss_division;
+pub mod redundant_require;
 pub mod reentrancy;
+pub mod reentrancy_callback;
+pub mod send_to_zero;
 pub mod shadowing;
 pub mod short_address;
+pub mod signature_malleability;
+pub mod stale_state_read;
 pub mod timestamp_dependence;
-pub mod tx_origin;
+pub mod unchecked_abi_decode;
 pub mod unchecked_call;
+pub mod unchecked_ecrecover;
+pub mod unchecked_erc20_return;
+pub mod unchecked_send;
+pub mod weak_randomness;
+pub mod withdraw_ordering;
 
 pub use arithmetic_overflow::ArithmeticOverflowDetector;
+pub use assembly_memory_safety::AssemblyMemorySafetyDetector;
+pub use assembly_unchecked_math::AssemblyUncheckedMathDetector;
 pub use bad_randomness::BadRandomnessDetector;
+pub use balance_confusion::BalanceConfusionDetector;
+pub use block_number_dependence::BlockNumberDependenceDetector;
 pub use cei_violation::CeiViolationDetector;
+pub use complex_signature::ComplexSignatureDetector;
+pub use constructor_external_call::ConstructorExternalCallDetector;
+pub use dangling_storage_ref::DanglingStorageRefDetector;
+pub use decimals_mismatch::DecimalsMismatchDetector;
+pub use deep_nesting::DeepNestingDetector;
 pub use delegatecall::DelegatecallDetector;
 pub use denial_of_service::DenialOfServiceDetector;
+pub use deprecated_difficulty::DeprecatedDifficultyDetector;
 pub use deprecated_features::DeprecatedFeaturesDetector;
+pub use deprecated_selfdestruct::DeprecatedSelfdestructDetector;
+pub use encode_type_mismatch::EncodeTypeMismatchDetector;
+pub use eoa_only_check::EoaOnlyCheckDetector;
+pub use hardcoded_gas::HardcodedGasDetector;
+pub use infinite_approval::InfiniteApprovalDetector;
+pub use loop_clear_collection::LoopClearCollectionDetector;
 pub use low_level_call::LowLevelCallDetector;
+pub use missing_deadline::MissingDeadlineDetector;
+pub use missing_event_emission::MissingEventEmissionDetector;
+pub use missing_reentrancy_guard::MissingReentrancyGuardDetector;
+pub use missing_return::MissingReturnDetector;
+pub use missing_zero_check::MissingZeroCheckDetector;
+pub use modify_while_iterate::ModifyWhileIterateDetector;
+pub use mul_overflow_before_div::MulOverflowBeforeDivDetector;
+pub use possible_underflow_revert::PossibleUnderflowRevertDetector;
+pub use precision_loss_division::PrecisionLossDivisionDetector;
+pub use redundant_require::RedundantRequireDetector;
 pub use reentrancy::ReentrancyDetector;
+pub use reentrancy_callback::ReentrancyCallbackDetector;
+pub use send_to_zero::SendToZeroDetector;
 pub use shadowing::ShadowingDetector;
 pub use short_address::ShortAddressDetector;
+pub use signature_malleability::SignatureMalleabilityDetector;
+pub use stale_state_read::StaleStateReadDetector;
 pub use timestamp_dependence::TimestampDependenceDetector;
-pub use tx_origin::TxOriginDetector;
+pub use unchecked_abi_decode::UncheckedAbiDecodeDetector;
 pub use unchecked_call::UncheckedCallDetector;
+pub use unchecked_ecrecover::UncheckedEcrecoverDetector;
+pub use unchecked_erc20_return::UncheckedErc20ReturnDetector;
+pub use unchecked_send::UncheckedSendDetector;
+pub use weak_randomness::WeakRandomnessDetector;
+pub use withdraw_ordering::WithdrawOrderingDetector;
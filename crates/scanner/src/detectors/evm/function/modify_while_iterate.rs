@@ -0,0 +1,365 @@
+//! Modify While Iterate Detector
+//!
+//! Detects loops that change the length of the array they iterate over:
+//!
+//! ```solidity
+//! for (uint i = 0; i < orders.length; i++) {
+//!     if (orders[i].expired) {
+//!         orders[i] = orders[orders.length - 1];
+//!         orders.pop();
+//!     }
+//! }
+//! ```
+//!
+//! Every `pop()` makes the loop skip the element moved into slot `i`, and a
+//! `push()` can keep the loop running forever. When the bound was cached in
+//! a local (`uint n = orders.length;`), shrinking the array makes later
+//! iterations index past its end. A loop iterates over an array when its
+//! condition compares the array's length, directly or through such a local,
+//! against a counter. Changes to the length are `push`, `pop`, `delete` of
+//! the whole array and assignments to it. Mutations immediately followed by
+//! `break` or `return` are not reported, nor are draining loops such as
+//! `while (a.length > 0) a.pop();`.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::lits::Lit;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, BinOp, CallExpr, ContractDecl, Expr, ForStmt, FunctionDecl, LocalVarStmt, Module,
+    Stmt, UnOp, UnOpExpr, WhileStmt,
+};
+use std::collections::HashMap;
+
+/// Scan detector for loops that modify the array being iterated.
+#[derive(Debug, Default)]
+pub struct ModifyWhileIterateDetector;
+
+impl ModifyWhileIterateDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Path of an array expression such as `items` or `book.orders`.
+fn array_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Var(v) => Some(v.name.clone()),
+        Expr::FieldAccess(fa) => Some(format!("{}.{}", array_path(&fa.base)?, fa.field)),
+        _ => None,
+    }
+}
+
+/// Array whose length `expr` denotes: `items.length`, or a local holding it.
+fn length_of(expr: &Expr, lengths: &HashMap<String, String>) -> Option<String> {
+    match expr {
+        Expr::FieldAccess(fa) if fa.field == "length" => array_path(&fa.base),
+        Expr::Var(v) => lengths.get(&v.name).cloned(),
+        _ => None,
+    }
+}
+
+/// Arrays iterated by a loop with condition `cond`: those whose length is
+/// compared against something other than a literal, e.g. `i < items.length`.
+fn iterated_arrays(cond: &Expr, lengths: &HashMap<String, String>) -> Vec<String> {
+    let Expr::BinOp(b) = cond else {
+        return vec![];
+    };
+    match b.op {
+        BinOp::And | BinOp::Or => {
+            let mut arrays = iterated_arrays(&b.lhs, lengths);
+            arrays.extend(iterated_arrays(&b.rhs, lengths));
+            arrays
+        }
+        BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge | BinOp::Ne => {
+            let (lhs, rhs) = (&*b.lhs, &*b.rhs);
+            [(lhs, rhs), (rhs, lhs)]
+                .into_iter()
+                .filter(|(_, counter)| !matches!(counter, Expr::Lit(Lit::Num(_))))
+                .filter_map(|(bound, _)| length_of(bound, lengths))
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Returns `true` if `stmt` changes the length of `array`.
+fn changes_length(stmt: &Stmt, array: &str) -> bool {
+    struct Finder<'s> {
+        array: &'s str,
+        found: bool,
+    }
+    impl<'a> Visit<'a> for Finder<'_> {
+        fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+            if let Expr::FieldAccess(fa) = &*expr.callee
+                && matches!(fa.field.as_str(), "push" | "pop")
+            {
+                self.found |= array_path(&fa.base).as_deref() == Some(self.array);
+            }
+            visit::default::visit_call_expr(self, expr);
+        }
+
+        fn visit_unop_expr(&mut self, expr: &'a UnOpExpr) {
+            if expr.op == UnOp::Delete {
+                self.found |= array_path(&expr.operand).as_deref() == Some(self.array);
+            }
+            visit::default::visit_unop_expr(self, expr);
+        }
+
+        fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+            let target = match &stmt.lhs {
+                Expr::FieldAccess(fa) if fa.field == "length" => array_path(&fa.base),
+                lhs => array_path(lhs),
+            };
+            self.found |= target.as_deref() == Some(self.array);
+            visit::default::visit_assign_stmt(self, stmt);
+        }
+    }
+
+    let mut finder = Finder { array, found: false };
+    finder.visit_stmt(stmt);
+    finder.found
+}
+
+/// Returns `true` if some statement of a loop body changes the length of
+/// `array` and the iteration then carries on.
+fn changes_length_and_continues(stmts: &[Stmt], array: &str) -> bool {
+    stmts.iter().enumerate().any(|(i, stmt)| {
+        let changes = match stmt {
+            Stmt::If(s) => {
+                changes_length_and_continues(&s.then_body, array)
+                    || s.else_body
+                        .as_ref()
+                        .is_some_and(|b| changes_length_and_continues(b, array))
+            }
+            Stmt::Block(b) => changes_length_and_continues(b, array),
+            // A `break` in a nested loop leaves only the nested loop.
+            _ => changes_length(stmt, array),
+        };
+        changes
+            && !stmts[i..]
+                .iter()
+                .any(|s| matches!(s, Stmt::Break | Stmt::Return(_)))
+    })
+}
+
+/// Finds loops modifying the array they iterate over.
+#[derive(Default)]
+struct Finder {
+    /// Locals holding the length of an array, e.g. `uint n = items.length;`.
+    lengths: HashMap<String, String>,
+    /// Array and span of each reported loop.
+    hits: Vec<(String, Option<Loc>)>,
+}
+
+impl Finder {
+    fn check_loop(&mut self, cond: Option<&Expr>, body: &[Stmt], span: Option<&Loc>) {
+        let Some(cond) = cond else {
+            return;
+        };
+        for array in iterated_arrays(cond, &self.lengths) {
+            if changes_length_and_continues(body, &array)
+                && !self
+                    .hits
+                    .iter()
+                    .any(|(a, s)| *a == array && s.as_ref() == span)
+            {
+                self.hits.push((array, span.cloned()));
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for Finder {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        if let [Some(decl)] = stmt.vars.as_slice()
+            && let Some(array) = stmt.init.as_ref().and_then(|e| length_of(e, &self.lengths))
+        {
+            self.lengths.insert(decl.name.clone(), array);
+        }
+        visit::default::visit_local_var_stmt(self, stmt);
+    }
+
+    fn visit_for_stmt(&mut self, stmt: &'a ForStmt) {
+        if let Some(init) = &stmt.init {
+            self.visit_stmt(init);
+        }
+        self.check_loop(stmt.cond.as_ref(), &stmt.body, stmt.span.as_ref());
+        visit::default::visit_for_stmt(self, stmt);
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &'a WhileStmt) {
+        self.check_loop(Some(&stmt.cond), &stmt.body, stmt.span.as_ref());
+        visit::default::visit_while_stmt(self, stmt);
+    }
+}
+
+impl ScanDetector for ModifyWhileIterateDetector {
+    fn id(&self) -> &'static str {
+        "modify-while-iterate"
+    }
+
+    fn name(&self) -> &'static str {
+        "Modify While Iterate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects loops that push to, pop from or delete the array they \
+         iterate over, which skips elements or indexes out of bounds"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![129]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Do not change the length of an array while iterating over it. Iterate \
+         backwards when removing elements, only advance the index when nothing \
+         was removed, or collect the changes and apply them after the loop."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/129.html"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut finder = Finder::default();
+        finder.visit_function_decl(func);
+
+        for (array, loc) in finder.hits {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' changes the length of '{}' inside a loop that \
+                     iterates over it.",
+                    contract.name, func.name, array
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, var};
+    use scirs::sir::*;
+
+    fn field(base: Expr, name: &str) -> Expr {
+        Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(base),
+            field: name.to_string(),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    /// `items.pop();`
+    fn pop() -> Stmt {
+        let call = Expr::FunctionCall(CallExpr {
+            callee: Box::new(field(var("items"), "pop")),
+            args: CallArgs::Positional(vec![]),
+            ty: Type::None,
+            span: None,
+        });
+        Stmt::Expr(ExprStmt { expr: call, span: None })
+    }
+
+    /// `total = items[i];`
+    fn read() -> Stmt {
+        Stmt::Assign(AssignStmt {
+            lhs: var("total"),
+            rhs: Expr::IndexAccess(IndexAccessExpr {
+                base: Box::new(var("items")),
+                index: Some(Box::new(var("i"))),
+                ty: Type::I256,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    /// `for (; i < items.length; ) { <body> }`
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let cond = binop(BinOp::Lt, var("i"), field(var("items"), "length"));
+        let for_loop = Stmt::For(ForStmt {
+            init: None,
+            cond: Some(cond),
+            update: None,
+            body,
+            invariant: None,
+            span: None,
+        });
+        check_body(&ModifyWhileIterateDetector::new(), vec![for_loop])
+    }
+
+    #[test]
+    fn test_modify_while_iterate_detector() {
+        let detector = ModifyWhileIterateDetector::new();
+        assert_eq!(detector.id(), "modify-while-iterate");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_pop_from_iterated_array_is_flagged() {
+        let bugs = check(vec![read(), pop()]);
+        assert_eq!(bugs.len(), 1);
+        assert!(bugs[0].description.as_deref().unwrap().contains("'items'"));
+    }
+
+    #[test]
+    fn test_reading_iterated_array_is_not_flagged() {
+        assert!(check(vec![read()]).is_empty());
+    }
+
+    #[test]
+    fn test_pop_followed_by_break_is_not_flagged() {
+        assert!(check(vec![pop(), Stmt::Break]).is_empty());
+    }
+}
@@ -0,0 +1,261 @@
+//! Multiplication Overflow Before Division Detector
+//!
+//! Detects `a * b / c` where the intermediate product `a * b` can exceed the
+//! integer type's maximum before the division brings it back into range. On
+//! Solidity 0.8+ the multiplication reverts; before 0.8 it silently wraps and
+//! the quotient is wrong.
+//!
+//! Operand ranges are estimated from their bit widths: literals use their
+//! value, casts and declared types bound variables, and arithmetic combines
+//! the bounds of its operands. Products whose estimated width fits the type
+//! are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::exprs::{BinOp, Expr, OverflowSemantics};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{BinOpExpr, ContractDecl, FunctionDecl, Lit, Module, Num, Type};
+
+/// Scan detector for overflow-prone multiplications feeding a division.
+#[derive(Debug, Default)]
+pub struct MulOverflowBeforeDivDetector;
+
+impl MulOverflowBeforeDivDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Bit width of an integer type. Non-integer types are treated as 256 bits.
+fn type_bits(ty: &Type) -> u64 {
+    match ty {
+        Type::I1 | Type::Bool => 1,
+        Type::I8 | Type::Si8 => 8,
+        Type::I16 | Type::Si16 => 16,
+        Type::I32 | Type::Si32 => 32,
+        Type::I64 | Type::Si64 => 64,
+        Type::I128 | Type::Si128 => 128,
+        Type::FixedBytes(n) => u64::from(*n) * 8,
+        _ => 256,
+    }
+}
+
+/// Upper bound on the number of bits needed to hold the value of `expr`.
+fn value_bits(expr: &Expr) -> u64 {
+    match expr {
+        Expr::Lit(Lit::Num(lit)) => match &lit.value {
+            Num::Int(n) => n.value.bits().max(1),
+            _ => 256,
+        },
+        Expr::Lit(Lit::Bool(_)) => 1,
+        Expr::TypeCast(cast) => type_bits(&cast.ty).min(value_bits(&cast.expr)),
+        Expr::BinOp(b) => match b.op {
+            BinOp::Mul => value_bits(&b.lhs) + value_bits(&b.rhs),
+            BinOp::Add => value_bits(&b.lhs).max(value_bits(&b.rhs)) + 1,
+            BinOp::Div | BinOp::Shr | BinOp::Sub => value_bits(&b.lhs),
+            BinOp::Mod | BinOp::BitAnd => value_bits(&b.lhs).min(value_bits(&b.rhs)),
+            _ => type_bits(&expr.typ()),
+        },
+        _ => type_bits(&expr.typ()),
+    }
+}
+
+/// Returns the multiplication of `a * b / c` if the product may overflow.
+fn overflowing_product(expr: &BinOpExpr) -> Option<&BinOpExpr> {
+    if expr.op != BinOp::Div {
+        return None;
+    }
+    let Expr::BinOp(mul) = &*expr.lhs else {
+        return None;
+    };
+    if mul.op != BinOp::Mul {
+        return None;
+    }
+    let limit = type_bits(&mul.lhs.typ()).max(type_bits(&mul.rhs.typ()));
+    (value_bits(&mul.lhs) + value_bits(&mul.rhs) > limit).then_some(mul)
+}
+
+impl ScanDetector for MulOverflowBeforeDivDetector {
+    fn id(&self) -> &'static str {
+        "mul-overflow-before-div"
+    }
+
+    fn name(&self) -> &'static str {
+        "Multiplication Overflow Before Division"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `a * b / c` where the product can overflow before the division \
+         narrows it back into range"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Arithmetic
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![190]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![101]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Compute `a * b / c` with a full-precision helper such as OpenZeppelin's \
+         `Math.mulDiv` or Uniswap's `FullMath.mulDiv`, which keeps the 512-bit \
+         intermediate product."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-101",
+            "https://docs.openzeppelin.com/contracts/5.x/api/utils#Math-mulDiv-uint256-uint256-uint256-",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        struct Visitor<'b> {
+            detector: &'b MulOverflowBeforeDivDetector,
+            bugs: &'b mut Vec<Bug>,
+            contract_name: String,
+            func_name: String,
+        }
+
+        impl<'a, 'b> Visit<'a> for Visitor<'b> {
+            fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+                if let Some(mul) = overflowing_product(expr) {
+                    let effect = match mul.overflow {
+                        OverflowSemantics::Wrapping => "wraps silently",
+                        _ => "reverts",
+                    };
+                    self.bugs.push(Bug::new(
+                        self.detector.name(),
+                        Some(&format!(
+                            "The product '{}' in '{}.{}' may overflow before the \
+                             division and {}.",
+                            Expr::BinOp(mul.clone()),
+                            self.contract_name,
+                            self.func_name,
+                            effect
+                        )),
+                        expr.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                        self.detector.bug_kind(),
+                        self.detector.bug_category(),
+                        self.detector.risk_level(),
+                        self.detector.cwe_ids(),
+                        self.detector.swc_ids(),
+                        Some(self.detector.recommendation()),
+                    ));
+                }
+                visit::default::visit_binop_expr(self, expr);
+            }
+        }
+
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: &mut bugs,
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+        };
+        visitor.visit_function_decl(func);
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, typed_var};
+    use num_bigint::BigInt;
+    use scirs::sir::*;
+
+    fn wad() -> Expr {
+        Expr::Lit(Lit::Num(NumLit::new(
+            Num::Int(IntNum::new(BigInt::from(10u64).pow(18), Type::I256)),
+            None,
+        )))
+    }
+
+    fn check(ret: Expr) -> Vec<Bug> {
+        let func = FunctionDecl::new(
+            "fee".to_string(),
+            vec![],
+            vec![Type::I256],
+            Some(vec![Stmt::Return(ReturnStmt { value: Some(ret), span: None })]),
+            None,
+        );
+        let contract = ContractDecl {
+            name: "Pool".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        MulOverflowBeforeDivDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_mul_overflow_before_div_detector() {
+        let detector = MulOverflowBeforeDivDetector::new();
+        assert_eq!(detector.id(), "mul-overflow-before-div");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_wide_operand_is_flagged() {
+        // x * 1e18 / y with uint256 x
+        let expr = binop(
+            BinOp::Div,
+            binop(BinOp::Mul, typed_var("x", Type::I256), wad()),
+            typed_var("y", Type::I256),
+        );
+        assert_eq!(check(expr).len(), 1);
+    }
+
+    #[test]
+    fn test_small_operands_are_not_flagged() {
+        // uint256(x) * 1e18 / y with uint128 x
+        let cast = Expr::TypeCast(TypeCastExpr {
+            ty: Type::I256,
+            expr: Box::new(typed_var("x", Type::I128)),
+            span: None,
+        });
+        let expr = binop(BinOp::Div, binop(BinOp::Mul, cast, wad()), typed_var("y", Type::I256));
+        assert!(check(expr).is_empty());
+    }
+}
@@ -0,0 +1,414 @@
+//! Possible Underflow Revert Detector
+//!
+//! Detects unsigned subtractions `a - b` (and `a -= b`) where nothing shows
+//! that `b <= a`. Since Solidity 0.8 such a subtraction reverts instead of
+//! wrapping, so an accounting path that can see `b > a` (fees larger than a
+//! balance, timestamps out of order, ...) blocks the whole transaction.
+//!
+//! A subtraction is considered bounded when a dominating condition orders its
+//! operands: `require(a >= b)`, `if (a < b) revert()`, the branch of an
+//! `if (a > b)`, or a ternary `a >= b ? a - b : 0`. Conditions are forgotten
+//! once either operand is reassigned. Without a value analysis every other
+//! subtraction is reported, hence the low confidence.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::always_exits;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmModuleExt, EvmStmt};
+use scirs::sir::lits::{IntNum, Lit, Num};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, BinOp, BinOpExpr, ContractDecl, DialectStmt, Expr, FunctionDecl,
+    Module, Stmt, TernaryExpr, UnOp,
+};
+
+/// First Solidity version with checked arithmetic.
+const CHECKED_ARITHMETIC_VERSION: (u64, u64, u64) = (0, 8, 0);
+
+/// Scan detector for subtractions that may revert on underflow.
+#[derive(Debug, Default)]
+pub struct PossibleUnderflowRevertDetector;
+
+impl PossibleUnderflowRevertDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A known ordering `big >= small` between two expressions, kept as text.
+type Fact = (String, String);
+
+/// Orderings implied by `cond` holding (or, if `negated`, not holding).
+fn facts_of(cond: &Expr, negated: bool) -> Vec<Fact> {
+    match cond {
+        Expr::UnOp(u) if u.op == UnOp::Not => facts_of(&u.operand, !negated),
+        Expr::BinOp(b) => {
+            let op = match (b.op, negated) {
+                (BinOp::And, false) | (BinOp::Or, true) => {
+                    let mut facts = facts_of(&b.lhs, negated);
+                    facts.extend(facts_of(&b.rhs, negated));
+                    return facts;
+                }
+                (BinOp::Lt, true) => BinOp::Ge,
+                (BinOp::Le, true) => BinOp::Gt,
+                (BinOp::Gt, true) => BinOp::Le,
+                (BinOp::Ge, true) => BinOp::Lt,
+                (op, _) => op,
+            };
+            let (lhs, rhs) = (b.lhs.to_string(), b.rhs.to_string());
+            match op {
+                BinOp::Ge | BinOp::Gt => vec![(lhs, rhs)],
+                BinOp::Le | BinOp::Lt => vec![(rhs, lhs)],
+                _ => vec![],
+            }
+        }
+        _ => vec![],
+    }
+}
+
+fn int_lit(expr: &Expr) -> Option<&IntNum> {
+    match expr {
+        Expr::Lit(Lit::Num(n)) => match &n.value {
+            Num::Int(i) => Some(i),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Names of the variables a statement list may assign.
+fn assigned_names(stmts: &[Stmt]) -> Vec<String> {
+    struct Finder {
+        names: Vec<String>,
+    }
+    impl<'a> Visit<'a> for Finder {
+        fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+            self.names.push(root_name(&stmt.lhs));
+        }
+        fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+            self.names.push(root_name(&stmt.lhs));
+        }
+    }
+    let mut finder = Finder { names: vec![] };
+    finder.visit_stmts(stmts);
+    finder.names
+}
+
+/// Base variable of an assignment target, e.g. `balances` for
+/// `balances[user].amount`.
+fn root_name(lhs: &Expr) -> String {
+    match lhs {
+        Expr::IndexAccess(e) => root_name(&e.base),
+        Expr::FieldAccess(e) => root_name(&e.base),
+        _ => lhs.to_string(),
+    }
+}
+
+/// Drops the facts mentioning any of `names`.
+fn forget(facts: &mut Vec<Fact>, names: &[String]) {
+    let mentions = |text: &str, name: &str| {
+        text.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .any(|id| id == name)
+    };
+    facts.retain(|(big, small)| !names.iter().any(|n| mentions(big, n) || mentions(small, n)));
+}
+
+/// Collects the unbounded subtractions of an expression or simple statement.
+struct SubtractionFinder<'f> {
+    facts: &'f [Fact],
+    /// Orderings established by enclosing ternaries.
+    local_facts: Vec<Fact>,
+    hits: Vec<(String, Option<Loc>)>,
+}
+
+impl SubtractionFinder<'_> {
+    fn check(&mut self, lhs: &Expr, rhs: &Expr, span: Option<&Loc>) {
+        let ty = lhs.typ();
+        if !ty.is_integer() || ty.is_signed() {
+            return;
+        }
+        if let Some(r) = int_lit(rhs)
+            && (num_traits::Zero::is_zero(&r.value)
+                || int_lit(lhs).is_some_and(|l| l.value >= r.value))
+        {
+            return;
+        }
+        // `a - a % b` never underflows.
+        if let Expr::BinOp(m) = rhs
+            && m.op == BinOp::Mod
+            && *m.lhs == *lhs
+        {
+            return;
+        }
+        let fact = (lhs.to_string(), rhs.to_string());
+        if self.facts.contains(&fact) || self.local_facts.contains(&fact) {
+            return;
+        }
+        self.hits
+            .push((format!("{} - {}", fact.0, fact.1), span.cloned()));
+    }
+}
+
+impl<'a> Visit<'a> for SubtractionFinder<'_> {
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        if expr.op == BinOp::Sub {
+            self.check(&expr.lhs, &expr.rhs, expr.span.as_ref());
+        }
+        visit::default::visit_binop_expr(self, expr);
+    }
+
+    fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+        if stmt.op == BinOp::Sub {
+            self.check(&stmt.lhs, &stmt.rhs, stmt.span.as_ref());
+        }
+        visit::default::visit_aug_assign_stmt(self, stmt);
+    }
+
+    fn visit_ternary_expr(&mut self, expr: &'a TernaryExpr) {
+        self.visit_expr(&expr.cond);
+        let saved = self.local_facts.len();
+        self.local_facts.extend(facts_of(&expr.cond, false));
+        self.visit_expr(&expr.then_expr);
+        self.local_facts.truncate(saved);
+        self.local_facts.extend(facts_of(&expr.cond, true));
+        self.visit_expr(&expr.else_expr);
+        self.local_facts.truncate(saved);
+    }
+}
+
+/// Walks a function body in order, tracking the orderings known to hold.
+#[derive(Default)]
+struct Walker {
+    hits: Vec<(String, Option<Loc>)>,
+}
+
+impl Walker {
+    fn find(&mut self, facts: &[Fact], visit: impl FnOnce(&mut SubtractionFinder<'_>)) {
+        let mut finder = SubtractionFinder { facts, local_facts: vec![], hits: vec![] };
+        visit(&mut finder);
+        self.hits.extend(finder.hits);
+    }
+
+    fn walk(&mut self, stmts: &[Stmt], mut facts: Vec<Fact>) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::If(s) => {
+                    self.find(&facts, |f| f.visit_expr(&s.cond));
+                    let mut then_facts = facts.clone();
+                    then_facts.extend(facts_of(&s.cond, false));
+                    self.walk(&s.then_body, then_facts);
+                    let mut else_facts = facts.clone();
+                    else_facts.extend(facts_of(&s.cond, true));
+                    if let Some(else_body) = &s.else_body {
+                        self.walk(else_body, else_facts);
+                    } else if always_exits(&s.then_body) {
+                        facts = else_facts;
+                    }
+                    forget(&mut facts, &assigned_names(std::slice::from_ref(stmt)));
+                }
+                Stmt::While(_) | Stmt::For(_) => {
+                    let body = match stmt {
+                        Stmt::While(s) => &s.body,
+                        Stmt::For(s) => &s.body,
+                        _ => unreachable!(),
+                    };
+                    forget(&mut facts, &assigned_names(std::slice::from_ref(stmt)));
+                    let mut body_facts = facts.clone();
+                    if let Stmt::While(s) = stmt {
+                        self.find(&facts, |f| f.visit_expr(&s.cond));
+                        body_facts.extend(facts_of(&s.cond, false));
+                    }
+                    self.walk(body, body_facts);
+                }
+                Stmt::Block(inner) => {
+                    self.walk(inner, facts.clone());
+                    forget(&mut facts, &assigned_names(inner));
+                }
+                Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+                    self.walk(&tc.body, facts.clone());
+                    for clause in &tc.catch_clauses {
+                        self.walk(&clause.body, facts.clone());
+                    }
+                    forget(&mut facts, &assigned_names(std::slice::from_ref(stmt)));
+                }
+                _ => {
+                    self.find(&facts, |f| f.visit_stmt(stmt));
+                    if let Stmt::Assert(a) = stmt {
+                        facts.extend(facts_of(&a.cond, false));
+                    }
+                    forget(&mut facts, &assigned_names(std::slice::from_ref(stmt)));
+                }
+            }
+        }
+    }
+}
+
+impl ScanDetector for PossibleUnderflowRevertDetector {
+    fn id(&self) -> &'static str {
+        "possible-underflow-revert"
+    }
+
+    fn name(&self) -> &'static str {
+        "Possible Underflow Revert"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects unsigned subtractions without a check that the subtrahend is \
+         at most the minuend, which revert under checked arithmetic"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::DenialOfService
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![191]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check `require(a >= b)` with a meaningful error before subtracting, or \
+         saturate explicitly (`a > b ? a - b : 0`) where a negative result is \
+         legitimately possible."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/control-structures.html#checked-or-unchecked-arithmetic",
+            "https://cwe.mitre.org/data/definitions/191.html",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        // Before 0.8 subtraction wraps; see `arithmetic-overflow`.
+        if module
+            .solidity_min_version()
+            .is_some_and(|v| v < CHECKED_ARITHMETIC_VERSION)
+        {
+            return bugs;
+        }
+        let Some(body) = &func.body else {
+            return bugs;
+        };
+
+        let mut walker = Walker::default();
+        walker.walk(body, vec![]);
+
+        for (subtraction, loc) in walker.hits {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' computes `{}` without checking that the right operand \
+                     does not exceed the left one; the call reverts if it does.",
+                    contract.name, func.name, subtraction
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, require, var};
+    use scirs::sir::*;
+
+    /// `fee = balance - amount`
+    fn subtract() -> Stmt {
+        Stmt::Assign(AssignStmt {
+            lhs: var("rest"),
+            rhs: binop(BinOp::Sub, var("balance"), var("amount")),
+            span: None,
+        })
+    }
+
+    #[test]
+    fn test_possible_underflow_revert_detector() {
+        let detector = PossibleUnderflowRevertDetector::new();
+        assert_eq!(detector.id(), "possible-underflow-revert");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_unguarded_subtraction_is_flagged() {
+        assert_eq!(check_body(&PossibleUnderflowRevertDetector::new(), vec![subtract()]).len(), 1);
+    }
+
+    #[test]
+    fn test_guarded_subtraction_is_not_flagged() {
+        // require(balance >= amount); rest = balance - amount;
+        let guard = require(binop(BinOp::Ge, var("balance"), var("amount")));
+        assert!(
+            check_body(&PossibleUnderflowRevertDetector::new(), vec![guard, subtract()])
+                .is_empty()
+        );
+
+        // if (balance < amount) revert(); rest = balance - amount;
+        let early_exit = Stmt::If(IfStmt {
+            cond: binop(BinOp::Lt, var("balance"), var("amount")),
+            then_body: vec![Stmt::Revert(RevertStmt {
+                error: None,
+                args: vec![],
+                span: None,
+            })],
+            else_body: None,
+            span: None,
+        });
+        assert!(
+            check_body(&PossibleUnderflowRevertDetector::new(), vec![early_exit, subtract()])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_guard_is_forgotten_after_reassignment() {
+        let guard = require(binop(BinOp::Ge, var("balance"), var("amount")));
+        let reassign = Stmt::Assign(AssignStmt { lhs: var("amount"), rhs: var("x"), span: None });
+        assert_eq!(
+            check_body(&PossibleUnderflowRevertDetector::new(), vec![guard, reassign, subtract()])
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Precision Loss Division Detector
+//!
+//! Detects unscaled integer ratio divisions (e.g. `amount / totalSupply`)
+//! that silently round to zero when the numerator is smaller than the
+//! denominator.
+//!
+//! Without value ranges, the detector only reports divisions whose
+//! denominator is an aggregate that typically exceeds the numerator: a
+//! variable, field or call named after a total, supply, reserve or balance,
+//! divided into a value that is not such an aggregate itself.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::unwrap_casts;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::exprs::{BinOp, Expr};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{BinOpExpr, ContractDecl, FunctionDecl, Module};
+
+/// Scan detector for precision loss in unscaled divisions.
+#[derive(Debug, Default)]
+pub struct PrecisionLossDivisionDetector;
+
+impl PrecisionLossDivisionDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Returns `true` if the numerator carries a scaling factor, i.e. it is a
+/// multiplication or a left shift such as `a * 1e18` or `a << 64`.
+fn is_scaled(expr: &Expr) -> bool {
+    matches!(expr, Expr::BinOp(b) if matches!(b.op, BinOp::Mul | BinOp::Shl))
+}
+
+/// Name parts of quantities that aggregate many values, such as
+/// `totalSupply`, `reserve0` or `balanceOf(this)`.
+const AGGREGATE_NAMES: &[&str] = &["total", "supply", "reserve", "balance"];
+
+/// Returns `true` if `expr` is a variable, field or call named after an
+/// aggregate quantity.
+fn is_aggregate(expr: &Expr) -> bool {
+    let name = match unwrap_casts(expr) {
+        Expr::Var(v) => &v.name,
+        Expr::FieldAccess(f) => &f.field,
+        Expr::FunctionCall(c) => return is_aggregate(&c.callee),
+        _ => return false,
+    };
+    let name = name.to_lowercase();
+    AGGREGATE_NAMES.iter().any(|part| name.contains(part))
+}
+
+/// Returns `true` if the division is a ratio of a non-constant value to an
+/// aggregate, without a scaling factor on the numerator.
+fn is_unscaled_ratio(expr: &BinOpExpr) -> bool {
+    expr.op == BinOp::Div
+        && !matches!(&*expr.lhs, Expr::Lit(_))
+        && !is_scaled(&expr.lhs)
+        && !is_aggregate(&expr.lhs)
+        && is_aggregate(&expr.rhs)
+}
+
+impl ScanDetector for PrecisionLossDivisionDetector {
+    fn id(&self) -> &'static str {
+        "precision-loss-division"
+    }
+
+    fn name(&self) -> &'static str {
+        "Precision Loss in Division"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects unscaled integer divisions that may silently round to zero"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Arithmetic
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![682, 1339]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Scale the numerator before dividing (e.g., `a * 1e18 / b`) and \
+         divide out the scaling factor at the end, or use a fixed-point \
+         math library such as PRBMath or OpenZeppelin's `Math.mulDiv`."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.soliditylang.org/en/latest/types.html#division"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        struct Visitor<'b> {
+            detector: &'b PrecisionLossDivisionDetector,
+            bugs: &'b mut Vec<Bug>,
+            contract_name: String,
+            func_name: String,
+        }
+
+        impl<'a, 'b> Visit<'a> for Visitor<'b> {
+            fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+                if is_unscaled_ratio(expr) {
+                    self.bugs.push(Bug::new(
+                        self.detector.name(),
+                        Some(&format!(
+                            "Unscaled division '{}' in '{}.{}' may round down \
+                             to zero when the numerator is smaller than the \
+                             denominator.",
+                            Expr::BinOp(expr.clone()),
+                            self.contract_name,
+                            self.func_name
+                        )),
+                        expr.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                        self.detector.bug_kind(),
+                        self.detector.bug_category(),
+                        self.detector.risk_level(),
+                        self.detector.cwe_ids(),
+                        self.detector.swc_ids(),
+                        Some(self.detector.recommendation()),
+                    ));
+                }
+                visit::default::visit_binop_expr(self, expr);
+            }
+        }
+
+        let mut visitor = Visitor {
+            detector: self,
+            bugs: &mut bugs,
+            contract_name: contract.name.clone(),
+            func_name: func.name.clone(),
+        };
+        visitor.visit_function_decl(func);
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, var};
+    use num_bigint::BigInt;
+    use scirs::sir::*;
+
+    fn check(ret: Expr) -> Vec<Bug> {
+        let func = FunctionDecl::new(
+            "share".to_string(),
+            vec![],
+            vec![Type::I256],
+            Some(vec![Stmt::Return(ReturnStmt { value: Some(ret), span: None })]),
+            None,
+        );
+        let contract = ContractDecl {
+            name: "Vault".to_string(),
+            parents: vec![],
+            attrs: vec![],
+            members: vec![],
+            span: None,
+        };
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        PrecisionLossDivisionDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_precision_loss_division_detector() {
+        let detector = PrecisionLossDivisionDetector::new();
+        assert_eq!(detector.id(), "precision-loss-division");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_scaled_vs_unscaled_division() {
+        // amount / totalSupply
+        let bugs = check(binop(BinOp::Div, var("amount"), var("totalSupply")));
+        assert_eq!(bugs.len(), 1);
+
+        // amount * 1e18 / totalSupply
+        let wad = Expr::Lit(Lit::Num(NumLit::new(
+            Num::Int(IntNum::new(BigInt::from(10u64).pow(18), Type::I256)),
+            None,
+        )));
+        let scaled = binop(BinOp::Mul, var("amount"), wad);
+        let bugs = check(binop(BinOp::Div, scaled, var("totalSupply")));
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_division_by_non_aggregate_is_not_flagged() {
+        // a / b: nothing suggests that `b` exceeds `a`.
+        assert!(check(binop(BinOp::Div, var("a"), var("b"))).is_empty());
+        // totalAssets / totalSupply: a ratio of aggregates.
+        assert!(check(binop(BinOp::Div, var("totalAssets"), var("totalSupply"))).is_empty());
+    }
+}
@@ -0,0 +1,405 @@
+//! Redundant Require Detector
+//!
+//! Detects `require` conditions that repeat an earlier guard of the same
+//! function, or that are implied by it (e.g. `require(x > 0)` after
+//! `require(x > 10)`). The second check only costs gas and often points to a
+//! copy-paste mistake where a different condition was intended.
+//!
+//! A guard stops counting as established once a variable it reads is
+//! assigned, or once a call could have changed state.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use num_traits::ToPrimitive;
+use scirs::sir::exprs::{BinOp, Expr, UnOp};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, AugAssignStmt, CallExpr, ContractDecl, DialectExpr, DialectStmt, FunctionDecl,
+    Lit, LocalVarStmt, Module, Num, Stmt, VarExpr,
+};
+use std::collections::HashSet;
+
+/// Scan detector for duplicate or subsumed `require` conditions.
+#[derive(Debug, Default)]
+pub struct RedundantRequireDetector;
+
+/// A condition established by an earlier `require`.
+#[derive(Clone)]
+struct Guard {
+    /// Printed form of the condition, used for syntactic comparison.
+    text: String,
+    /// Conjuncts of the condition, each also usable on its own.
+    conjuncts: Vec<Expr>,
+    /// Variables read by the condition.
+    vars: HashSet<String>,
+}
+
+impl Guard {
+    fn new(cond: &Expr) -> Self {
+        let mut conjuncts = vec![];
+        split_conjuncts(cond, &mut conjuncts);
+        Self { text: cond.to_string(), conjuncts, vars: read_vars(cond) }
+    }
+}
+
+impl RedundantRequireDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn check_stmts(
+        &self,
+        stmts: &[Stmt],
+        guards: &mut Vec<Guard>,
+        bugs: &mut Vec<Bug>,
+        contract_name: &str,
+        func_name: &str,
+    ) {
+        for stmt in stmts {
+            if let Some(cond) = require_condition(stmt) {
+                let text = cond.to_string();
+                let earlier = guards.iter().find_map(|g| {
+                    if g.text == text {
+                        Some(format!("duplicates an earlier require('{}')", g.text))
+                    } else if g.conjuncts.iter().any(|c| implies(c, cond)) {
+                        Some(format!("is implied by an earlier require('{}')", g.text))
+                    } else {
+                        None
+                    }
+                });
+                match earlier {
+                    Some(reason) => bugs.push(Bug::new(
+                        self.name(),
+                        Some(&format!(
+                            "Condition '{}' in '{}.{}' {}.",
+                            text, contract_name, func_name, reason
+                        )),
+                        stmt.span().cloned().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                        self.bug_kind(),
+                        self.bug_category(),
+                        self.risk_level(),
+                        self.cwe_ids(),
+                        self.swc_ids(),
+                        Some(self.recommendation()),
+                    )),
+                    None => guards.push(Guard::new(cond)),
+                }
+                continue;
+            }
+
+            match stmt {
+                Stmt::If(s) => {
+                    let mut then_guards = guards.clone();
+                    self.check_stmts(
+                        &s.then_body,
+                        &mut then_guards,
+                        bugs,
+                        contract_name,
+                        func_name,
+                    );
+                    if let Some(else_body) = &s.else_body {
+                        let mut else_guards = guards.clone();
+                        self.check_stmts(
+                            else_body,
+                            &mut else_guards,
+                            bugs,
+                            contract_name,
+                            func_name,
+                        );
+                    }
+                }
+                Stmt::While(s) => {
+                    // Later iterations see the effects of the whole body.
+                    let mut body_guards = guards.clone();
+                    invalidate_guards(stmt, &mut body_guards);
+                    self.check_stmts(&s.body, &mut body_guards, bugs, contract_name, func_name);
+                }
+                Stmt::For(s) => {
+                    let mut body_guards = guards.clone();
+                    invalidate_guards(stmt, &mut body_guards);
+                    self.check_stmts(&s.body, &mut body_guards, bugs, contract_name, func_name);
+                }
+                Stmt::Block(inner) => {
+                    let mut block_guards = guards.clone();
+                    self.check_stmts(inner, &mut block_guards, bugs, contract_name, func_name);
+                }
+                _ => {}
+            }
+
+            invalidate_guards(stmt, guards);
+        }
+    }
+}
+
+/// Returns the condition of a lowered `require(cond)`, i.e.
+/// `if (!cond) { revert(..) }`.
+fn require_condition(stmt: &Stmt) -> Option<&Expr> {
+    let Stmt::If(s) = stmt else {
+        return None;
+    };
+    match (&s.cond, s.then_body.as_slice(), &s.else_body) {
+        (Expr::UnOp(u), [Stmt::Revert(_)], None) if u.op == UnOp::Not => Some(&u.operand),
+        _ => None,
+    }
+}
+
+fn split_conjuncts(expr: &Expr, out: &mut Vec<Expr>) {
+    match expr {
+        Expr::BinOp(b) if b.op == BinOp::And => {
+            split_conjuncts(&b.lhs, out);
+            split_conjuncts(&b.rhs, out);
+        }
+        _ => out.push(expr.clone()),
+    }
+}
+
+fn read_vars(expr: &Expr) -> HashSet<String> {
+    struct VarCollector {
+        vars: HashSet<String>,
+    }
+    impl<'a> Visit<'a> for VarCollector {
+        fn visit_var_expr(&mut self, var: &'a VarExpr) {
+            self.vars.insert(var.name.clone());
+        }
+    }
+    let mut collector = VarCollector { vars: HashSet::new() };
+    collector.visit_expr(expr);
+    collector.vars
+}
+
+/// Drop the guards that `stmt` may falsify: those reading an assigned
+/// variable, or all of them if the statement makes a call.
+fn invalidate_guards(stmt: &Stmt, guards: &mut Vec<Guard>) {
+    struct EffectFinder {
+        assigned: HashSet<String>,
+        has_call: bool,
+    }
+    impl<'a> Visit<'a> for EffectFinder {
+        fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+            self.assigned.extend(read_vars(&stmt.lhs));
+            visit::default::visit_assign_stmt(self, stmt);
+        }
+        fn visit_aug_assign_stmt(&mut self, stmt: &'a AugAssignStmt) {
+            self.assigned.extend(read_vars(&stmt.lhs));
+            visit::default::visit_aug_assign_stmt(self, stmt);
+        }
+        fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+            self.assigned
+                .extend(stmt.vars.iter().flatten().map(|v| v.name.clone()));
+            visit::default::visit_local_var_stmt(self, stmt);
+        }
+        fn visit_call_expr(&mut self, _call: &'a CallExpr) {
+            self.has_call = true;
+        }
+        fn visit_dialect_expr(&mut self, _expr: &'a DialectExpr) {
+            self.has_call = true;
+        }
+        fn visit_dialect_stmt(&mut self, _stmt: &'a DialectStmt) {
+            self.has_call = true;
+        }
+    }
+
+    let mut effects = EffectFinder { assigned: HashSet::new(), has_call: false };
+    effects.visit_stmt(stmt);
+    if effects.has_call {
+        guards.clear();
+    } else if !effects.assigned.is_empty() {
+        guards.retain(|g| g.vars.is_disjoint(&effects.assigned));
+    }
+}
+
+/// Integer value of a numeric literal, if it fits.
+fn int_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(Lit::Num(lit)) => match &lit.value {
+            Num::Int(n) => n.value.to_i128(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Inclusive bounds `(lower, upper)` that `x <op> c` puts on `x`.
+fn bounds(op: &BinOp, c: i128) -> Option<(Option<i128>, Option<i128>)> {
+    match op {
+        BinOp::Gt => Some((c.checked_add(1), None)),
+        BinOp::Ge => Some((Some(c), None)),
+        BinOp::Lt => Some((None, c.checked_sub(1))),
+        BinOp::Le => Some((None, Some(c))),
+        BinOp::Eq => Some((Some(c), Some(c))),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `earlier` being true makes `later` true, for
+/// comparisons of the same expression against integer literals.
+fn implies(earlier: &Expr, later: &Expr) -> bool {
+    let (Expr::BinOp(e), Expr::BinOp(l)) = (earlier, later) else {
+        return false;
+    };
+    if e.lhs.to_string() != l.lhs.to_string() {
+        return false;
+    }
+    let (Some(ec), Some(lc)) = (int_value(&e.rhs), int_value(&l.rhs)) else {
+        return false;
+    };
+    let Some((e_lo, e_hi)) = bounds(&e.op, ec) else {
+        return false;
+    };
+
+    // `x != c` holds whenever the earlier range excludes `c`.
+    if l.op == BinOp::Ne {
+        return e_lo.is_some_and(|lo| lo > lc) || e_hi.is_some_and(|hi| hi < lc);
+    }
+
+    let Some((l_lo, l_hi)) = bounds(&l.op, lc) else {
+        return false;
+    };
+    let lower_ok = match l_lo {
+        None => true,
+        Some(l_lo) => e_lo.is_some_and(|e_lo| e_lo >= l_lo),
+    };
+    let upper_ok = match l_hi {
+        None => true,
+        Some(l_hi) => e_hi.is_some_and(|e_hi| e_hi <= l_hi),
+    };
+    lower_ok && upper_ok
+}
+
+impl ScanDetector for RedundantRequireDetector {
+    fn id(&self) -> &'static str {
+        "redundant-require"
+    }
+
+    fn name(&self) -> &'static str {
+        "Redundant Require"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects require conditions that repeat or are implied by an earlier \
+         require in the same function"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Optimization
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1164]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Remove the redundant check, or fix it if a different condition was \
+         intended."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/1164.html"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+        if let Some(body) = &func.body {
+            let mut guards = Vec::new();
+            self.check_stmts(body, &mut guards, &mut bugs, &contract.name, &func.name);
+        }
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, num, require, var};
+
+    fn cmp(op: BinOp, name: &str, n: u64) -> Expr {
+        binop(op, var(name), num(n))
+    }
+
+    #[test]
+    fn test_redundant_require_detector() {
+        let detector = RedundantRequireDetector::new();
+        assert_eq!(detector.id(), "redundant-require");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_repeated_require_is_flagged() {
+        let bugs = check_body(
+            &RedundantRequireDetector::new(),
+            vec![
+                require(cmp(BinOp::Gt, "x", 0)),
+                require(cmp(BinOp::Gt, "y", 0)),
+                require(cmp(BinOp::Gt, "x", 0)),
+            ],
+        );
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_distinct_requires_are_not_flagged() {
+        let bugs = check_body(
+            &RedundantRequireDetector::new(),
+            vec![
+                require(cmp(BinOp::Gt, "x", 0)),
+                require(cmp(BinOp::Gt, "y", 0)),
+            ],
+        );
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_subsumed_require_is_flagged() {
+        let bugs = check_body(
+            &RedundantRequireDetector::new(),
+            vec![
+                require(cmp(BinOp::Gt, "x", 10)),
+                require(cmp(BinOp::Ne, "x", 0)),
+            ],
+        );
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_require_after_assignment_is_not_flagged() {
+        let bugs = check_body(
+            &RedundantRequireDetector::new(),
+            vec![
+                require(cmp(BinOp::Gt, "x", 0)),
+                Stmt::Assign(AssignStmt { lhs: var("x"), rhs: var("y"), span: None }),
+                require(cmp(BinOp::Gt, "x", 0)),
+            ],
+        );
+        assert!(bugs.is_empty());
+    }
+}
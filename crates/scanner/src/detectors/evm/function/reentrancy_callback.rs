@@ -0,0 +1,303 @@
+//! Callback Reentrancy Detector
+//!
+//! Detects reentrancy through token receiver hooks: ERC721/ERC1155
+//! `safeTransferFrom`, ERC777 `send` and similar transfers call back into the
+//! recipient (`onERC721Received`, `onERC1155Received`, `tokensReceived`), so a
+//! state modification after such a transfer is reachable on reentry even
+//! though no low-level call appears in the code. Plain ERC20 transfers have
+//! no recipient callback and are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::ContractDecl;
+use scirs::sir::dialect::{EvmCallExt, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{CallExpr, FunctionDecl, Module, Stmt};
+
+/// Scan detector for reentrancy through token transfer callbacks.
+#[derive(Debug, Default)]
+pub struct ReentrancyCallbackDetector;
+
+impl ReentrancyCallbackDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn check_stmts(
+        &self,
+        stmts: &[Stmt],
+        storage_vars: &[String],
+        seen_callback: &mut bool,
+        bugs: &mut Vec<Bug>,
+        contract_name: &str,
+        func_name: &str,
+    ) {
+        for stmt in stmts {
+            if !*seen_callback && self.stmt_has_callback_call(stmt) {
+                *seen_callback = true;
+            }
+
+            if *seen_callback && self.stmt_has_storage_write(stmt, storage_vars) {
+                bugs.push(Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "Potential callback reentrancy in '{}.{}': state modification \
+                         after a token transfer that calls back into the recipient.",
+                        contract_name, func_name,
+                    )),
+                    stmt.span().cloned().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                ));
+                return;
+            }
+
+            match stmt {
+                Stmt::If(s) => {
+                    let mut branch_seen = *seen_callback;
+                    self.check_stmts(
+                        &s.then_body,
+                        storage_vars,
+                        &mut branch_seen,
+                        bugs,
+                        contract_name,
+                        func_name,
+                    );
+                    if let Some(else_body) = &s.else_body {
+                        let mut else_seen = *seen_callback;
+                        self.check_stmts(
+                            else_body,
+                            storage_vars,
+                            &mut else_seen,
+                            bugs,
+                            contract_name,
+                            func_name,
+                        );
+                        branch_seen = branch_seen || else_seen;
+                    }
+                    *seen_callback = branch_seen;
+                }
+                Stmt::While(s) => {
+                    self.check_stmts(
+                        &s.body,
+                        storage_vars,
+                        seen_callback,
+                        bugs,
+                        contract_name,
+                        func_name,
+                    );
+                }
+                Stmt::For(s) => {
+                    self.check_stmts(
+                        &s.body,
+                        storage_vars,
+                        seen_callback,
+                        bugs,
+                        contract_name,
+                        func_name,
+                    );
+                }
+                Stmt::Block(inner) => {
+                    self.check_stmts(
+                        inner,
+                        storage_vars,
+                        seen_callback,
+                        bugs,
+                        contract_name,
+                        func_name,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn stmt_has_callback_call(&self, stmt: &Stmt) -> bool {
+        struct CallbackFinder {
+            found: bool,
+        }
+        impl<'a> Visit<'a> for CallbackFinder {
+            fn visit_call_expr(&mut self, call: &'a CallExpr) {
+                if call.is_evm_token_callback_call() {
+                    self.found = true;
+                }
+                if !self.found {
+                    visit::default::visit_call_expr(self, call);
+                }
+            }
+        }
+        let mut finder = CallbackFinder { found: false };
+        finder.visit_stmt(stmt);
+        finder.found
+    }
+
+    fn stmt_has_storage_write(&self, stmt: &Stmt, storage_vars: &[String]) -> bool {
+        match stmt {
+            Stmt::Assign(a) => ContractDecl::expr_references_storage(&a.lhs, storage_vars),
+            Stmt::AugAssign(a) => ContractDecl::expr_references_storage(&a.lhs, storage_vars),
+            _ => false,
+        }
+    }
+}
+
+impl ScanDetector for ReentrancyCallbackDetector {
+    fn id(&self) -> &'static str {
+        "reentrancy-callback"
+    }
+
+    fn name(&self) -> &'static str {
+        "Callback Reentrancy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects state modifications after ERC721/ERC1155/ERC777 transfers that \
+         invoke a receiver hook, which allows the recipient to reenter."
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Reentrancy
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![841]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![107]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Treat `safeTransferFrom`, `_safeMint` and ERC777 `send` as external calls: \
+         update state before the transfer or protect the function with a reentrancy \
+         guard (e.g., OpenZeppelin's ReentrancyGuard)."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-107",
+            "https://eips.ethereum.org/EIPS/eip-721",
+            "https://eips.ethereum.org/EIPS/eip-777",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if func.has_reentrancy_guard() {
+            return bugs;
+        }
+
+        let storage_vars = contract.storage_names();
+        if storage_vars.is_empty() {
+            return bugs;
+        }
+
+        if let Some(body) = &func.body {
+            let mut seen_callback = false;
+            self.check_stmts(
+                body,
+                &storage_vars,
+                &mut seen_callback,
+                &mut bugs,
+                &contract.name,
+                &func.name,
+            );
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_function, storage, var};
+    use scirs::sir::*;
+
+    fn transfer_stmt(token: &str, method: &str) -> Stmt {
+        // token.method(from, to, id)
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                    base: Box::new(var(token)),
+                    field: method.to_string(),
+                    ty: Type::None,
+                    span: None,
+                })),
+                args: CallArgs::Positional(vec![var("from"), var("to"), var("id")]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    fn write_stmt() -> Stmt {
+        // deposits = 1;
+        Stmt::Assign(AssignStmt {
+            lhs: var("deposits"),
+            rhs: Expr::Lit(Lit::one(None)),
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let func = FunctionDecl::new("withdraw".to_string(), vec![], vec![], Some(body), None);
+        check_function(&ReentrancyCallbackDetector::new(), &func, vec![storage("deposits")])
+    }
+
+    #[test]
+    fn test_reentrancy_callback_detector() {
+        let detector = ReentrancyCallbackDetector::new();
+        assert_eq!(detector.id(), "reentrancy-callback");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_safe_transfer_from_then_write_is_flagged() {
+        let bugs = check(vec![transfer_stmt("nft", "safeTransferFrom"), write_stmt()]);
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_erc20_transfer_from_is_not_flagged() {
+        let bugs = check(vec![transfer_stmt("token", "transferFrom"), write_stmt()]);
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_write_before_transfer_is_not_flagged() {
+        let bugs = check(vec![write_stmt(), transfer_stmt("nft", "safeTransferFrom")]);
+        assert!(bugs.is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+//! Send To Zero Address Detector
+//!
+//! Detects ether and token transfers whose recipient is `address(0)`, or a
+//! caller-supplied address that is not checked against zero before the
+//! transfer:
+//!
+//! ```solidity
+//! function withdraw(address payable to, uint256 amount) external {
+//!     (bool ok, ) = to.call{value: amount}("");  // `to` may be zero
+//!     require(ok);
+//! }
+//! ```
+//!
+//! Funds sent to the zero address are burnt. A recipient counts as checked
+//! once a preceding `require`, `assert` or `if (...) revert` rules out zero,
+//! and stops counting as checked when it is reassigned.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::{
+    is_zero_address, nonzero_when_false, nonzero_when_true, unwrap_casts,
+};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::DialectExpr;
+use scirs::sir::dialect::evm::{EvmCallExt, EvmExpr, EvmTypeExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssertStmt, AssignStmt, CallArgs, CallExpr, ContractDecl, Expr, FunctionDecl, IfStmt,
+    LocalVarStmt, Module, Stmt, Type,
+};
+use std::collections::HashSet;
+
+/// Scan detector for transfers to a possibly zero address.
+#[derive(Debug, Default)]
+pub struct SendToZeroDetector;
+
+impl SendToZeroDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Walks a function body in program order, tracking which parameters have
+/// been checked against zero, and records transfers to unchecked or zero
+/// recipients.
+struct ZeroWalker {
+    params: HashSet<String>,
+    checked: HashSet<String>,
+    /// Recipient description and location of each reported transfer.
+    sends: Vec<(String, Option<Loc>)>,
+}
+
+impl ZeroWalker {
+    fn new(func: &FunctionDecl) -> Self {
+        Self {
+            params: func.params.iter().map(|p| p.name.clone()).collect(),
+            checked: HashSet::new(),
+            sends: Vec::new(),
+        }
+    }
+
+    fn check_recipient(&mut self, recipient: &Expr, loc: Option<&Loc>) {
+        if is_zero_address(recipient) {
+            self.sends.push(("address(0)".to_string(), loc.cloned()));
+        } else if let Expr::Var(v) = unwrap_casts(recipient)
+            && self.params.contains(&v.name)
+            && !self.checked.contains(&v.name)
+        {
+            self.sends.push((format!("'{}'", v.name), loc.cloned()));
+        }
+    }
+
+    fn reassign(&mut self, name: &str) {
+        self.checked.remove(name);
+    }
+}
+
+impl<'a> Visit<'a> for ZeroWalker {
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt) {
+        visit::default::visit_if_stmt(self, stmt);
+        if stmt.else_body.is_none() && matches!(stmt.then_body.last(), Some(Stmt::Revert(_))) {
+            let mut names = Vec::new();
+            nonzero_when_false(&stmt.cond, &mut names);
+            self.checked.extend(names);
+        }
+    }
+
+    fn visit_assert_stmt(&mut self, stmt: &'a AssertStmt) {
+        visit::default::visit_assert_stmt(self, stmt);
+        let mut names = Vec::new();
+        nonzero_when_true(&stmt.cond, &mut names);
+        self.checked.extend(names);
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        visit::default::visit_assign_stmt(self, stmt);
+        if let Expr::Var(v) = &stmt.lhs {
+            self.reassign(&v.name);
+        }
+    }
+
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        for var in stmt.vars.iter().flatten() {
+            self.reassign(&var.name);
+        }
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        visit::default::visit_call_expr(self, expr);
+        let CallArgs::Positional(args) = &expr.args else {
+            return;
+        };
+        let is_token = |ty: &Type| matches!(ty, Type::TypeRef(_));
+        let recipient = match args.as_slice() {
+            [_] => expr
+                .method_call_on_type("transfer", Type::is_address)
+                .or_else(|| expr.method_call_on_type("send", Type::is_address)),
+            [to, _] => expr.method_call_on_type("transfer", is_token).map(|_| to),
+            [_, to, _] => expr
+                .method_call_on_type("transferFrom", is_token)
+                .map(|_| to),
+            _ => None,
+        };
+        if let Some(recipient) = recipient {
+            self.check_recipient(recipient, expr.span.as_ref());
+        }
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        let DialectExpr::Evm(evm) = expr else {
+            return;
+        };
+        match evm {
+            EvmExpr::LowLevelCall(call) if call.value.is_some() => {
+                self.check_recipient(&call.target, Some(&call.loc))
+            }
+            EvmExpr::RawCall(call) if call.value.is_some() => {
+                self.check_recipient(&call.target, Some(&call.loc))
+            }
+            EvmExpr::Transfer(t) => self.check_recipient(&t.target, Some(&t.loc)),
+            EvmExpr::Send(s) => self.check_recipient(&s.target, Some(&s.loc)),
+            _ => {}
+        }
+    }
+}
+
+impl ScanDetector for SendToZeroDetector {
+    fn id(&self) -> &'static str {
+        "send-to-zero"
+    }
+
+    fn name(&self) -> &'static str {
+        "Send To Zero Address"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects ether or token transfers to address(0) or to a parameter \
+         that is not checked against zero"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![20]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check the recipient with `require(to != address(0))` before \
+         transferring funds to it."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/20.html"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut walker = ZeroWalker::new(func);
+        walker.visit_function_decl(func);
+
+        for (recipient, loc) in walker.sends {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' transfers funds to {} without checking that it \
+                     is not the zero address.",
+                    contract.name, func.name, recipient
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_function, num, require, typed_var, var};
+    use scirs::sir::dialect::DialectType;
+    use scirs::sir::dialect::evm::{EvmLowLevelCall, EvmType};
+    use scirs::sir::*;
+
+    fn address() -> Type {
+        Type::Dialect(DialectType::Evm(EvmType::AddressPayable))
+    }
+
+    /// `to.call{value: amount}("")`
+    fn call_value() -> Stmt {
+        let call = EvmLowLevelCall {
+            target: Box::new(typed_var("to", address())),
+            data: Box::new(Expr::Lit(Lit::String(StringLit { value: String::new(), span: None }))),
+            value: Some(Box::new(var("amount"))),
+            gas: None,
+            loc: Loc::new(1, 1, 1, 1),
+        };
+        Stmt::Expr(ExprStmt {
+            expr: Expr::Dialect(DialectExpr::Evm(EvmExpr::LowLevelCall(call))),
+            span: None,
+        })
+    }
+
+    /// `require(to != address(0));`
+    fn require_nonzero() -> Stmt {
+        let zero =
+            Expr::TypeCast(TypeCastExpr { ty: address(), expr: Box::new(num(0)), span: None });
+        require(binop(BinOp::Ne, typed_var("to", address()), zero))
+    }
+
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let params = vec![
+            Param::new("to".to_string(), address()),
+            Param::new("amount".to_string(), Type::I256),
+        ];
+        let func = FunctionDecl::new("withdraw".to_string(), params, vec![], Some(body), None);
+        check_function(&SendToZeroDetector::new(), &func, vec![])
+    }
+
+    #[test]
+    fn test_send_to_zero_detector() {
+        let detector = SendToZeroDetector::new();
+        assert_eq!(detector.id(), "send-to-zero");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_call_to_unchecked_recipient_is_flagged() {
+        let bugs = check(vec![call_value()]);
+        assert_eq!(bugs.len(), 1);
+        assert!(bugs[0].description.as_deref().unwrap().contains("'to'"));
+    }
+
+    #[test]
+    fn test_call_to_checked_recipient_is_not_flagged() {
+        assert!(check(vec![require_nonzero(), call_value()]).is_empty());
+    }
+}
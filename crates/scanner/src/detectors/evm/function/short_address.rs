@@ -139,6 +139,10 @@ impl ScanDetector for ShortAddressDetector {
         ]
     }
 
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["erc20"]
+    }
+
     fn check_function(
         &self,
         func: &FunctionDecl,
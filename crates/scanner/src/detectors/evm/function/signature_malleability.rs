@@ -0,0 +1,270 @@
+//! Signature Malleability Detector
+//!
+//! Detects raw `ecrecover` calls whose `s` value is not range-checked. For
+//! every valid signature `(v, r, s)` the signature `(v', r, n - s)` recovers
+//! the same signer, so contracts that mark signatures as used (or derive IDs
+//! from them) can be replayed with the flipped form unless `s` is restricted
+//! to the lower half of the curve order.
+//!
+//! A comparison `s <= 0x7FFF...20A0` (or any ordering comparison on the
+//! variable passed as `s`) counts as the check. Signatures recovered through
+//! OpenZeppelin's `ECDSA.recover` never reach `ecrecover` here and are not
+//! reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use scirs::sir::dialect::evm::{EvmEcrecover, EvmExpr};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    BinOp, BinOpExpr, ContractDecl, DialectExpr, Expr, FunctionDecl, Module, VarExpr,
+};
+use std::collections::HashSet;
+
+/// Upper bound of the lower half of the secp256k1 order, as used by the
+/// low-`s` check (lower case, without `0x`).
+const HALF_ORDER: &str = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0";
+
+/// Scan detector for `ecrecover` without low-`s` enforcement.
+#[derive(Debug, Default)]
+pub struct SignatureMalleabilityDetector;
+
+impl SignatureMalleabilityDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn read_vars(expr: &Expr) -> HashSet<String> {
+    struct VarCollector {
+        vars: HashSet<String>,
+    }
+    impl<'a> Visit<'a> for VarCollector {
+        fn visit_var_expr(&mut self, var: &'a VarExpr) {
+            self.vars.insert(var.name.clone());
+        }
+    }
+    let mut collector = VarCollector { vars: HashSet::new() };
+    collector.visit_expr(expr);
+    collector.vars
+}
+
+/// The `ecrecover` calls and comparisons of a function body.
+#[derive(Default)]
+struct Collector<'a> {
+    recovers: Vec<&'a EvmEcrecover>,
+    /// Variables compared with `<`, `<=`, `>` or `>=`.
+    range_checked: HashSet<String>,
+    /// Variables compared in any way.
+    compared: HashSet<String>,
+    /// Whether the half-order constant appears, e.g. in inline assembly.
+    mentions_half_order: bool,
+}
+
+impl<'a> Visit<'a> for Collector<'a> {
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        let is_ordering = matches!(expr.op, BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge);
+        if is_ordering || matches!(expr.op, BinOp::Eq | BinOp::Ne) {
+            let vars = read_vars(&expr.lhs).into_iter().chain(read_vars(&expr.rhs));
+            for var in vars {
+                if is_ordering {
+                    self.range_checked.insert(var.clone());
+                }
+                self.compared.insert(var);
+            }
+        }
+        visit::default::visit_binop_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        match expr {
+            DialectExpr::Evm(EvmExpr::Ecrecover(e)) => {
+                self.recovers.push(e);
+                self.visit_expr(&e.hash);
+                self.visit_expr(&e.v);
+                self.visit_expr(&e.r);
+                self.visit_expr(&e.s);
+            }
+            DialectExpr::Evm(EvmExpr::InlineAsm(asm)) => {
+                self.mentions_half_order |= asm.asm_text.to_lowercase().contains(HALF_ORDER);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ScanDetector for SignatureMalleabilityDetector {
+    fn id(&self) -> &'static str {
+        "signature-malleability"
+    }
+
+    fn name(&self) -> &'static str {
+        "Signature Malleability"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects raw `ecrecover` calls without a low-`s` range check, which \
+         accept a second, equivalent form of every signature"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![347]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![117]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Use OpenZeppelin's `ECDSA.recover`, or reject signatures with \
+         `s > 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0` \
+         and `v` other than 27 or 28 before calling `ecrecover`."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-117",
+            "https://docs.openzeppelin.com/contracts/5.x/api/utils#ECDSA",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut collector = Collector::default();
+        collector.visit_function_decl(func);
+        if collector.mentions_half_order {
+            return bugs;
+        }
+
+        for recover in &collector.recovers {
+            let s_vars = read_vars(&recover.s);
+            if s_vars.is_empty() || s_vars.iter().any(|v| collector.range_checked.contains(v)) {
+                continue;
+            }
+            let v_checked = read_vars(&recover.v)
+                .iter()
+                .any(|v| collector.compared.contains(v));
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' calls ecrecover without checking that `s` is in the \
+                     lower half of the curve order{}; a flipped signature recovers \
+                     the same signer.",
+                    contract.name,
+                    func.name,
+                    if v_checked { "" } else { " or validating `v`" }
+                )),
+                recover.loc.clone(),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, var};
+    use common::loc::Loc;
+    use scirs::sir::*;
+
+    /// `signer = <rhs>; require(signer == owner);`
+    fn recover_body(rhs: Expr) -> Vec<Stmt> {
+        let eq = binop(BinOp::Eq, var("signer"), var("owner"));
+        vec![
+            Stmt::Assign(AssignStmt { lhs: var("signer"), rhs, span: None }),
+            Stmt::Assert(AssertStmt { cond: eq, message: None, span: None }),
+        ]
+    }
+
+    fn ecrecover() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Ecrecover(EvmEcrecover {
+            hash: Box::new(var("digest")),
+            v: Box::new(var("v")),
+            r: Box::new(var("r")),
+            s: Box::new(var("s")),
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    #[test]
+    fn test_signature_malleability_detector() {
+        let detector = SignatureMalleabilityDetector::new();
+        assert_eq!(detector.id(), "signature-malleability");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_raw_ecrecover_without_s_check_is_flagged() {
+        assert_eq!(
+            check_body(&SignatureMalleabilityDetector::new(), recover_body(ecrecover())).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_ecrecover_with_s_check_is_not_flagged() {
+        // require(uint256(s) <= 0x7FFF...20A0)
+        let le = binop(BinOp::Le, var("s"), var("HALF_ORDER"));
+        let mut body = vec![Stmt::Assert(AssertStmt {
+            cond: le,
+            message: None,
+            span: None,
+        })];
+        body.extend(recover_body(ecrecover()));
+        assert!(check_body(&SignatureMalleabilityDetector::new(), body).is_empty());
+    }
+
+    #[test]
+    fn test_ecdsa_recover_is_not_flagged() {
+        // signer = ECDSA.recover(digest, signature)
+        let call = Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(var("ECDSA")),
+                field: "recover".to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![var("digest"), var("signature")]),
+            ty: Type::I256,
+            span: None,
+        });
+        assert!(check_body(&SignatureMalleabilityDetector::new(), recover_body(call)).is_empty());
+    }
+}
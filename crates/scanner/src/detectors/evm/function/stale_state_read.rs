@@ -0,0 +1,289 @@
+//! Stale State Read Detector
+//!
+//! Detects reads of mutable state variables after an external call in the
+//! same function, where reentry may have changed the value being read.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::stmt_has_external_call;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::{EvmFunctionExt, EvmStorageExt};
+use scirs::sir::utils::visit::Visit;
+use scirs::sir::{ContractDecl, FunctionDecl, MemberDecl, Module, Stmt, VarExpr};
+
+/// Scan detector for state reads after external calls.
+#[derive(Debug, Default)]
+pub struct StaleStateReadDetector;
+
+impl StaleStateReadDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Walk statements in order, tracking whether an external call has been
+    /// seen on the current path. Returns the first stale read found.
+    fn find_stale_read(
+        &self,
+        stmts: &[Stmt],
+        state_vars: &[String],
+        seen_ext_call: &mut bool,
+    ) -> Option<(String, Option<Loc>)> {
+        for stmt in stmts {
+            match stmt {
+                Stmt::If(s) => {
+                    if *seen_ext_call
+                        && let Some(var) = first_state_read(stmt_cond_reads(stmt), state_vars)
+                    {
+                        return Some((var, stmt.span().cloned()));
+                    }
+                    let mut then_seen = *seen_ext_call;
+                    if let Some(found) =
+                        self.find_stale_read(&s.then_body, state_vars, &mut then_seen)
+                    {
+                        return Some(found);
+                    }
+                    let mut else_seen = *seen_ext_call;
+                    if let Some(else_body) = &s.else_body
+                        && let Some(found) =
+                            self.find_stale_read(else_body, state_vars, &mut else_seen)
+                    {
+                        return Some(found);
+                    }
+                    *seen_ext_call = then_seen || else_seen;
+                }
+                Stmt::While(s) => {
+                    if let Some(found) = self.find_stale_read(&s.body, state_vars, seen_ext_call) {
+                        return Some(found);
+                    }
+                }
+                Stmt::For(s) => {
+                    if let Some(found) = self.find_stale_read(&s.body, state_vars, seen_ext_call) {
+                        return Some(found);
+                    }
+                }
+                Stmt::Block(inner) => {
+                    if let Some(found) = self.find_stale_read(inner, state_vars, seen_ext_call) {
+                        return Some(found);
+                    }
+                }
+                _ => {
+                    if *seen_ext_call
+                        && let Some(var) = first_state_read(stmt_reads(stmt), state_vars)
+                    {
+                        return Some((var, stmt.span().cloned()));
+                    }
+                    if stmt_has_external_call(stmt) {
+                        *seen_ext_call = true;
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Collects the names of all variables read by a visited node.
+#[derive(Default)]
+struct ReadCollector {
+    names: Vec<String>,
+}
+
+impl<'a> Visit<'a> for ReadCollector {
+    fn visit_var_expr(&mut self, var: &'a VarExpr) {
+        self.names.push(var.name.clone());
+    }
+}
+
+/// Names read by a simple statement. The root variable on the left-hand side
+/// of a plain assignment is a write, not a read.
+fn stmt_reads(stmt: &Stmt) -> Vec<String> {
+    let mut collector = ReadCollector::default();
+    match stmt {
+        Stmt::Assign(a) => collector.visit_expr(&a.rhs),
+        _ => collector.visit_stmt(stmt),
+    }
+    collector.names
+}
+
+/// Names read by the condition of an `if` statement.
+fn stmt_cond_reads(stmt: &Stmt) -> Vec<String> {
+    let mut collector = ReadCollector::default();
+    if let Stmt::If(s) = stmt {
+        collector.visit_expr(&s.cond);
+    }
+    collector.names
+}
+
+fn first_state_read(reads: Vec<String>, state_vars: &[String]) -> Option<String> {
+    reads.into_iter().find(|n| state_vars.contains(n))
+}
+
+impl ScanDetector for StaleStateReadDetector {
+    fn id(&self) -> &'static str {
+        "stale-state-read"
+    }
+
+    fn name(&self) -> &'static str {
+        "Stale State Read"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects state variable reads after an external call that may observe \
+         values changed by reentry."
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Reentrancy
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![841]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![107]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Read state into local variables before making external calls, or \
+         protect the function and the functions that write the state with a \
+         reentrancy guard."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://swcregistry.io/docs/SWC-107"]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["defi"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        if func.has_reentrancy_guard() {
+            return bugs;
+        }
+
+        // Constants and immutables cannot be changed by reentry.
+        let state_vars: Vec<String> = contract
+            .members
+            .iter()
+            .filter_map(|m| match m {
+                MemberDecl::Storage(s) if !s.is_constant_storage() => Some(s.name.clone()),
+                _ => None,
+            })
+            .collect();
+        if state_vars.is_empty() {
+            return bugs;
+        }
+
+        if let Some(body) = &func.body {
+            let mut seen_ext_call = false;
+            if let Some((var, span)) = self.find_stale_read(body, &state_vars, &mut seen_ext_call)
+            {
+                bugs.push(Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "State variable '{}' is read after an external call in \
+                         '{}.{}' and may be stale due to reentry.",
+                        var, contract.name, func.name,
+                    )),
+                    span.or_else(|| func.span.clone())
+                        .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                ));
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_function, storage, var};
+    use scirs::sir::*;
+
+    fn call_stmt() -> Stmt {
+        // msg.sender.call("")
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                    base: Box::new(var("recipient")),
+                    field: "call".to_string(),
+                    ty: Type::None,
+                    span: None,
+                })),
+                args: CallArgs::Positional(vec![]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    fn read_stmt() -> Stmt {
+        // uint256 r = reserve;
+        Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl { name: "r".to_string(), ty: Type::I256 })],
+            init: Some(var("reserve")),
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>) -> Vec<Bug> {
+        let func = FunctionDecl::new("withdraw".to_string(), vec![], vec![], Some(body), None);
+        check_function(&StaleStateReadDetector::new(), &func, vec![storage("reserve")])
+    }
+
+    #[test]
+    fn test_stale_state_read_detector() {
+        let detector = StaleStateReadDetector::new();
+        assert_eq!(detector.id(), "stale-state-read");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_read_after_call_is_flagged() {
+        let bugs = check(vec![call_stmt(), read_stmt()]);
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_read_before_call_is_not_flagged() {
+        let bugs = check(vec![read_stmt(), call_stmt()]);
+        assert!(bugs.is_empty());
+    }
+}
@@ -0,0 +1,474 @@
+//! Unchecked abi.decode Detector
+//!
+//! Detects `abi.decode` of attacker-supplied bytes whose length is not
+//! checked first:
+//!
+//! ```solidity
+//! function execute(bytes calldata data) external {
+//!     (address to, uint256 amount) = abi.decode(data, (address, uint256));
+//! }
+//! ```
+//!
+//! Decoding bytes shorter than the decoded types reverts with no reason,
+//! and decoding bytes of an unexpected layout silently yields wrong values.
+//! The decoded bytes are traced back along local definitions to a `bytes`
+//! parameter of a public or external function, or to the data returned by
+//! a low-level call. A decode counts as checked where a `require`, `assert`
+//! or `if` on the `.length` of the bytes dominates it; a reassignment of
+//! the bytes drops the check.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::unwrap_casts;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmAbiDecode, EvmExpr, EvmFunctionExt, EvmStmt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{ContractDecl, DialectExpr, DialectStmt, Expr, FunctionDecl, Module, Stmt, Type};
+use std::collections::HashSet;
+
+/// Scan detector for `abi.decode` of bytes of unchecked length.
+#[derive(Debug, Default)]
+pub struct UncheckedAbiDecodeDetector;
+
+impl UncheckedAbiDecodeDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Variable whose bytes `expr` reads, through casts and slices.
+fn bytes_root(expr: &Expr) -> Option<&str> {
+    match unwrap_casts(expr) {
+        Expr::Var(v) => Some(&v.name),
+        Expr::IndexAccess(e) if e.index.is_none() => bytes_root(&e.base),
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Slice(s))) => bytes_root(&s.expr),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `expr` returns the data of a low-level call along with
+/// its success flag.
+fn is_low_level_call(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Dialect(DialectExpr::Evm(
+            EvmExpr::LowLevelCall(_) | EvmExpr::RawCall(_) | EvmExpr::Delegatecall(_)
+        ))
+    )
+}
+
+/// Names of the variables whose length `expr` reads.
+fn length_reads(expr: &Expr, names: &mut Vec<String>) {
+    struct Finder<'n>(&'n mut Vec<String>);
+
+    impl<'a> Visit<'a> for Finder<'_> {
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            if let Expr::FieldAccess(fa) = expr
+                && fa.field == "length"
+                && let Some(name) = bytes_root(&fa.base)
+            {
+                self.0.push(name.to_string());
+            }
+            visit::default::visit_expr(self, expr);
+        }
+
+        fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+            if let DialectExpr::Evm(EvmExpr::Len(len)) = expr
+                && let Some(name) = bytes_root(&len.expr)
+            {
+                self.0.push(name.to_string());
+            }
+        }
+    }
+
+    Finder(names).visit_expr(expr);
+}
+
+/// Collects the `abi.decode` calls of an expression.
+#[derive(Default)]
+struct DecodeFinder<'a> {
+    decodes: Vec<&'a EvmAbiDecode>,
+}
+
+impl<'a> Visit<'a> for DecodeFinder<'a> {
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        if let DialectExpr::Evm(EvmExpr::AbiDecode(decode)) = expr {
+            self.decodes.push(decode);
+            self.visit_expr(&decode.data);
+        }
+    }
+}
+
+/// Walks a function body along its structure, so that the length checks
+/// known at a statement are exactly those dominating it, and records the
+/// decodes of attacker-supplied bytes of unchecked length.
+struct DecodeWalker<'f> {
+    /// Variables holding attacker-supplied bytes.
+    tainted: HashSet<String>,
+    /// Decoded variable and location of each unchecked decode.
+    decodes: Vec<(String, Option<Loc>)>,
+    func_span: Option<&'f Loc>,
+}
+
+impl DecodeWalker<'_> {
+    fn walk(&mut self, stmts: &[Stmt], mut checked: HashSet<String>) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::If(s) => {
+                    self.check_exprs([&s.cond], &checked, stmt);
+                    let mut names = Vec::new();
+                    length_reads(&s.cond, &mut names);
+                    let branch_checked: HashSet<String> =
+                        checked.iter().cloned().chain(names).collect();
+                    self.walk(&s.then_body, branch_checked.clone());
+                    if let Some(else_body) = &s.else_body {
+                        self.walk(else_body, branch_checked);
+                    } else if matches!(s.then_body.last(), Some(Stmt::Revert(_) | Stmt::Return(_)))
+                    {
+                        checked = branch_checked;
+                    }
+                }
+                Stmt::While(s) => {
+                    self.check_exprs([&s.cond], &checked, stmt);
+                    let mut names = Vec::new();
+                    length_reads(&s.cond, &mut names);
+                    self.walk(&s.body, checked.iter().cloned().chain(names).collect());
+                }
+                Stmt::For(s) => {
+                    if let Some(init) = &s.init {
+                        self.walk(std::slice::from_ref(init), checked.clone());
+                    }
+                    self.check_exprs(s.cond.iter(), &checked, stmt);
+                    self.walk(&s.body, checked.clone());
+                }
+                Stmt::Block(inner) => self.walk(inner, checked.clone()),
+                Stmt::Dialect(DialectStmt::Evm(EvmStmt::TryCatch(tc))) => {
+                    self.check_exprs([&tc.guarded_expr], &checked, stmt);
+                    self.walk(&tc.body, checked.clone());
+                    for clause in &tc.catch_clauses {
+                        self.walk(&clause.body, checked.clone());
+                    }
+                }
+                Stmt::Assert(a) => {
+                    self.check_exprs([&a.cond], &checked, stmt);
+                    let mut names = Vec::new();
+                    length_reads(&a.cond, &mut names);
+                    checked.extend(names);
+                }
+                Stmt::LocalVar(s) => {
+                    self.check_exprs(s.init.iter(), &checked, stmt);
+                    let names = s.vars.iter().map(|v| v.as_ref().map(|v| (&v.name, &v.ty)));
+                    self.define(names.collect(), s.init.as_ref(), &mut checked);
+                }
+                Stmt::Assign(s) => {
+                    self.check_exprs([&s.rhs], &checked, stmt);
+                    let targets = match &s.lhs {
+                        Expr::Tuple(t) => t
+                            .elems
+                            .iter()
+                            .map(|e| match e {
+                                Some(Expr::Var(v)) => Some((&v.name, &v.ty)),
+                                _ => None,
+                            })
+                            .collect(),
+                        Expr::Var(v) => vec![Some((&v.name, &v.ty))],
+                        _ => vec![],
+                    };
+                    self.define(targets, Some(&s.rhs), &mut checked);
+                }
+                _ => {
+                    let mut finder = DecodeFinder::default();
+                    finder.visit_stmt(stmt);
+                    self.record(finder, &checked, stmt);
+                }
+            }
+        }
+    }
+
+    /// Record the unchecked decodes of `exprs`, expressions of `stmt`.
+    fn check_exprs<'e>(
+        &mut self,
+        exprs: impl IntoIterator<Item = &'e Expr>,
+        checked: &HashSet<String>,
+        stmt: &Stmt,
+    ) {
+        let mut finder = DecodeFinder::default();
+        for expr in exprs {
+            finder.visit_expr(expr);
+        }
+        self.record(finder, checked, stmt);
+    }
+
+    fn record(&mut self, finder: DecodeFinder<'_>, checked: &HashSet<String>, stmt: &Stmt) {
+        for decode in finder.decodes {
+            if let Some(name) = bytes_root(&decode.data)
+                && self.tainted.contains(name)
+                && !checked.contains(name)
+            {
+                let loc = Some(decode.loc.clone())
+                    .filter(|loc| loc.is_valid())
+                    .or_else(|| stmt.span().cloned())
+                    .or_else(|| self.func_span.cloned());
+                self.decodes.push((name.to_string(), loc));
+            }
+        }
+    }
+
+    /// Update the tainted and checked variables for the definition of
+    /// `targets` by `value`.
+    fn define(
+        &mut self,
+        targets: Vec<Option<(&String, &Type)>>,
+        value: Option<&Expr>,
+        checked: &mut HashSet<String>,
+    ) {
+        let call_data = value.is_some_and(is_low_level_call);
+        for (i, target) in targets.into_iter().enumerate() {
+            let Some((name, ty)) = target else {
+                continue;
+            };
+            checked.remove(name);
+            let tainted = if call_data {
+                i == 1
+            } else {
+                *ty == Type::Bytes
+                    && value
+                        .and_then(bytes_root)
+                        .is_some_and(|source| self.tainted.contains(source))
+            };
+            if tainted {
+                self.tainted.insert(name.clone());
+            } else {
+                self.tainted.remove(name);
+            }
+        }
+    }
+}
+
+impl ScanDetector for UncheckedAbiDecodeDetector {
+    fn id(&self) -> &'static str {
+        "unchecked-abi-decode"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked abi.decode"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects abi.decode of attacker-supplied bytes without a preceding \
+         length check"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![20]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check the length of attacker-supplied bytes, e.g. with \
+         `require(data.length >= 64)`, before decoding them with `abi.decode`."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/units-and-global-variables.html#abi-encoding-and-decoding-functions",
+            "https://cwe.mitre.org/data/definitions/20.html",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let tainted: HashSet<String> = if func.is_public() || func.is_external() {
+            func.params
+                .iter()
+                .filter(|p| p.ty == Type::Bytes)
+                .map(|p| p.name.clone())
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let mut walker = DecodeWalker { tainted, decodes: vec![], func_span: func.span.as_ref() };
+        walker.walk(func.body.as_deref().unwrap_or_default(), HashSet::new());
+
+        for (name, loc) in walker.decodes {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' decodes '{}' with abi.decode without checking its length.",
+                    contract.name, func.name, name
+                )),
+                loc.unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{num, typed_var};
+    use scirs::sir::dialect::evm::{EvmLowLevelCall, EvmType};
+    use scirs::sir::*;
+
+    fn bytes(name: &str) -> Expr {
+        typed_var(name, Type::Bytes)
+    }
+
+    /// `uint256 amount = abi.decode(data, (uint256));` on line 5.
+    fn decode(data: &str) -> Stmt {
+        Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl {
+                name: "amount".to_string(),
+                ty: Type::I256,
+            })],
+            init: Some(Expr::Dialect(DialectExpr::Evm(EvmExpr::AbiDecode(EvmAbiDecode {
+                data: Box::new(bytes(data)),
+                types: vec![Type::I256],
+                loc: Loc::new(5, 9, 5, 50),
+            })))),
+            span: None,
+        })
+    }
+
+    /// `require(data.length >= 32)`
+    fn require_length(data: &str) -> Stmt {
+        let length = Expr::FieldAccess(FieldAccessExpr {
+            base: Box::new(bytes(data)),
+            field: "length".to_string(),
+            ty: Type::I256,
+            span: None,
+        });
+        Stmt::Assert(AssertStmt {
+            cond: Expr::BinOp(BinOpExpr {
+                op: BinOp::Ge,
+                lhs: Box::new(length),
+                rhs: Box::new(num(32)),
+                overflow: OverflowSemantics::Checked,
+                span: None,
+            }),
+            message: None,
+            span: None,
+        })
+    }
+
+    /// `(bool ok, bytes memory ret) = target.call(payload);`
+    fn call() -> Stmt {
+        let address = Type::Dialect(DialectType::Evm(EvmType::Address));
+        Stmt::LocalVar(LocalVarStmt {
+            vars: vec![
+                Some(LocalVarDecl { name: "ok".to_string(), ty: Type::Bool }),
+                Some(LocalVarDecl { name: "ret".to_string(), ty: Type::Bytes }),
+            ],
+            init: Some(Expr::Dialect(DialectExpr::Evm(EvmExpr::LowLevelCall(EvmLowLevelCall {
+                target: Box::new(typed_var("target", address)),
+                data: Box::new(bytes("payload")),
+                value: None,
+                gas: None,
+                loc: Loc::new(0, 0, 0, 0),
+            })))),
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>, visibility: &str) -> Vec<Bug> {
+        let params = vec![
+            Param::new("data".to_string(), Type::Bytes),
+            Param::new("payload".to_string(), Type::Bytes),
+        ];
+        let mut func = FunctionDecl::new("execute".to_string(), params, vec![], Some(body), None);
+        func.attrs
+            .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String(visibility.into())));
+        let contract = ContractDecl::new("Router".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        UncheckedAbiDecodeDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_unchecked_abi_decode_detector() {
+        let detector = UncheckedAbiDecodeDetector::new();
+        assert_eq!(detector.id(), "unchecked-abi-decode");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_unchecked_decode_of_parameter() {
+        let bugs = check(vec![decode("data")], "external");
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(bugs[0].loc, Loc::new(5, 9, 5, 50));
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some("'Router.execute' decodes 'data' with abi.decode without checking its length.")
+        );
+
+        assert!(check(vec![require_length("data"), decode("data")], "external").is_empty());
+        // A check of other bytes does not count.
+        assert_eq!(check(vec![require_length("payload"), decode("data")], "public").len(), 1);
+        // Parameters of internal functions are not attacker-supplied.
+        assert!(check(vec![decode("data")], "internal").is_empty());
+    }
+
+    #[test]
+    fn test_unchecked_decode_of_call_data() {
+        let copy = Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl {
+                name: "result".to_string(),
+                ty: Type::Bytes,
+            })],
+            init: Some(bytes("ret")),
+            span: None,
+        });
+        let bugs = check(vec![call(), copy.clone(), decode("result")], "internal");
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("decodes 'result'")
+        );
+
+        let checked = vec![call(), copy, require_length("result"), decode("result")];
+        assert!(check(checked, "internal").is_empty());
+    }
+}
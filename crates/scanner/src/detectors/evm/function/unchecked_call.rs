@@ -1,7 +1,9 @@
 //! Unchecked Call Return Detector
 //!
-//! Detects low-level calls whose return values are not checked.
+//! Detects low-level calls whose return values are not checked. ERC20
+//! `transfer` calls are left to the unchecked ERC20 return detector.
 
+use super::unchecked_erc20_return::erc20_bool_call;
 use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
 use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
 use common::loc::Loc;
@@ -77,37 +79,39 @@ impl ScanDetector for UncheckedCallDetector {
         &self,
         func: &FunctionDecl,
         contract: &ContractDecl,
-        _module: &Module,
+        module: &Module,
     ) -> Vec<Bug> {
         let mut bugs = Vec::new();
 
         struct Visitor<'b> {
             detector: &'b UncheckedCallDetector,
             bugs: &'b mut Vec<Bug>,
+            module: &'b Module,
             contract_name: String,
             func_name: String,
         }
 
         impl<'a, 'b> Visit<'a> for Visitor<'b> {
             fn visit_expr_stmt(&mut self, stmt: &'a ExprStmt) {
-                if let scirs::sir::Expr::FunctionCall(call) = &stmt.expr {
-                    if call.is_evm_external_call() {
-                        self.bugs.push(Bug::new(
-                            self.detector.name(),
-                            Some(&format!(
-                                "Unchecked call return value in '{}.{}'. \
-                                 The return value of a low-level call is not checked.",
-                                self.contract_name, self.func_name,
-                            )),
-                            stmt.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
-                            self.detector.bug_kind(),
-                            self.detector.bug_category(),
-                            self.detector.risk_level(),
-                            self.detector.cwe_ids(),
-                            self.detector.swc_ids(),
-                            Some(self.detector.recommendation()),
-                        ));
-                    }
+                if let scirs::sir::Expr::FunctionCall(call) = &stmt.expr
+                    && call.is_evm_external_call()
+                    && erc20_bool_call(call, self.module).is_none()
+                {
+                    self.bugs.push(Bug::new(
+                        self.detector.name(),
+                        Some(&format!(
+                            "Unchecked call return value in '{}.{}'. \
+                             The return value of a low-level call is not checked.",
+                            self.contract_name, self.func_name,
+                        )),
+                        stmt.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                        self.detector.bug_kind(),
+                        self.detector.bug_category(),
+                        self.detector.risk_level(),
+                        self.detector.cwe_ids(),
+                        self.detector.swc_ids(),
+                        Some(self.detector.recommendation()),
+                    ));
                 }
                 visit::default::visit_expr_stmt(self, stmt);
             }
@@ -116,6 +120,7 @@ impl ScanDetector for UncheckedCallDetector {
         let mut visitor = Visitor {
             detector: self,
             bugs: &mut bugs,
+            module,
             contract_name: contract.name.clone(),
             func_name: func.name.clone(),
         };
@@ -0,0 +1,340 @@
+//! Unchecked Ecrecover Detector
+//!
+//! Detects `ecrecover` results compared with an expected signer without
+//! first ruling out `address(0)`:
+//!
+//! ```solidity
+//! function permit(bytes32 digest, uint8 v, bytes32 r, bytes32 s) external {
+//!     address signer = ecrecover(digest, v, r, s);
+//!     require(signer == owner);  // passes for any invalid signature
+//! }                              // while `owner` is unset
+//! ```
+//!
+//! `ecrecover` returns zero for an invalid signature instead of reverting, so
+//! the comparison authorizes anyone whenever the expected signer is zero. A
+//! recovered address counts as checked once a preceding `require`, `assert`
+//! or `if (...) revert`, or the left operand of the same `&&`, rules out
+//! zero; comparing with an address already checked against zero is also
+//! safe. Missing low-`s` range checks on the same call are reported by the
+//! `signature-malleability` detector.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::{
+    is_zero_address, nonzero_when_false, nonzero_when_true, unwrap_casts,
+};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::DialectExpr;
+use scirs::sir::dialect::evm::EvmExpr;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssertStmt, AssignStmt, BinOp, BinOpExpr, ContractDecl, Expr, FunctionDecl, IfStmt,
+    LocalVarStmt, Module, Stmt,
+};
+use std::collections::HashSet;
+
+/// Scan detector for `ecrecover` results used without a zero check.
+#[derive(Debug, Default)]
+pub struct UncheckedEcrecoverDetector;
+
+impl UncheckedEcrecoverDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Whether `expr` is a raw `ecrecover(...)` call.
+fn is_ecrecover(expr: &Expr) -> bool {
+    matches!(unwrap_casts(expr), Expr::Dialect(DialectExpr::Evm(EvmExpr::Ecrecover(_))))
+}
+
+/// Walks a function body in program order, tracking the locals holding an
+/// `ecrecover` result and the variables checked against zero, and records
+/// comparisons of an unchecked recovered address.
+#[derive(Default)]
+struct RecoverWalker {
+    recovered: HashSet<String>,
+    checked: HashSet<String>,
+    /// Compared operand and location of each reported comparison.
+    comparisons: Vec<(String, Option<Loc>)>,
+}
+
+impl RecoverWalker {
+    /// Whether `expr` is an `ecrecover` result not yet checked against zero.
+    fn is_unchecked_recovery(&self, expr: &Expr) -> bool {
+        match unwrap_casts(expr) {
+            Expr::Var(v) => self.recovered.contains(&v.name) && !self.checked.contains(&v.name),
+            expr => is_ecrecover(expr),
+        }
+    }
+
+    fn is_checked(&self, expr: &Expr) -> bool {
+        matches!(unwrap_casts(expr), Expr::Var(v) if self.checked.contains(&v.name))
+    }
+
+    fn assign(&mut self, name: &str, value: Option<&Expr>) {
+        self.checked.remove(name);
+        if value.is_some_and(is_ecrecover) {
+            self.recovered.insert(name.to_string());
+        } else {
+            self.recovered.remove(name);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for RecoverWalker {
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt) {
+        visit::default::visit_if_stmt(self, stmt);
+        if stmt.else_body.is_none() && matches!(stmt.then_body.last(), Some(Stmt::Revert(_))) {
+            let mut names = Vec::new();
+            nonzero_when_false(&stmt.cond, &mut names);
+            self.checked.extend(names);
+        }
+    }
+
+    fn visit_assert_stmt(&mut self, stmt: &'a AssertStmt) {
+        visit::default::visit_assert_stmt(self, stmt);
+        let mut names = Vec::new();
+        nonzero_when_true(&stmt.cond, &mut names);
+        self.checked.extend(names);
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        visit::default::visit_assign_stmt(self, stmt);
+        if let Expr::Var(v) = &stmt.lhs {
+            self.assign(&v.name, Some(&stmt.rhs));
+        }
+    }
+
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        visit::default::visit_local_var_stmt(self, stmt);
+        let init = match stmt.vars.as_slice() {
+            [_] => stmt.init.as_ref(),
+            _ => None,
+        };
+        for var in stmt.vars.iter().flatten() {
+            self.assign(&var.name, init);
+        }
+    }
+
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        match expr.op {
+            BinOp::And => {
+                // `recovered != address(0) && recovered == signer`
+                self.visit_expr(&expr.lhs);
+                let mut names = Vec::new();
+                nonzero_when_true(&expr.lhs, &mut names);
+                names.retain(|name| !self.checked.contains(name));
+                self.checked.extend(names.iter().cloned());
+                self.visit_expr(&expr.rhs);
+                for name in &names {
+                    self.checked.remove(name);
+                }
+            }
+            BinOp::Eq | BinOp::Ne => {
+                visit::default::visit_binop_expr(self, expr);
+                for (recovered, other) in [(&expr.lhs, &expr.rhs), (&expr.rhs, &expr.lhs)] {
+                    if self.is_unchecked_recovery(recovered)
+                        && !is_zero_address(other)
+                        && !self.is_checked(other)
+                        && !is_ecrecover(other)
+                    {
+                        self.comparisons
+                            .push((other.to_string(), expr.span.clone()));
+                        break;
+                    }
+                }
+            }
+            _ => visit::default::visit_binop_expr(self, expr),
+        }
+    }
+}
+
+impl ScanDetector for UncheckedEcrecoverDetector {
+    fn id(&self) -> &'static str {
+        "unchecked-ecrecover"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked Ecrecover"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `ecrecover` results compared with an expected signer without \
+         checking that the recovered address is not zero"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![347]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![122]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check `require(signer != address(0))` on the result of `ecrecover`, \
+         or use OpenZeppelin's `ECDSA.recover`, which reverts on invalid \
+         signatures."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-122",
+            "https://docs.openzeppelin.com/contracts/5.x/api/utils#ECDSA",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let mut walker = RecoverWalker::default();
+        walker.visit_function_decl(func);
+
+        for (other, loc) in walker.comparisons {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' compares the result of ecrecover with '{}' without \
+                     checking that it is not address(0); an invalid signature \
+                     matches whenever '{}' is zero.",
+                    contract.name, func.name, other, other
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, num, var};
+    use scirs::sir::dialect::evm::EvmEcrecover;
+
+    fn require(cond: Expr) -> Stmt {
+        Stmt::Assert(AssertStmt { cond, message: None, span: None })
+    }
+
+    fn ecrecover() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Ecrecover(EvmEcrecover {
+            hash: Box::new(var("digest")),
+            v: Box::new(var("v")),
+            r: Box::new(var("r")),
+            s: Box::new(var("s")),
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    /// `signer = ecrecover(digest, v, r, s);`
+    fn recover() -> Stmt {
+        Stmt::Assign(AssignStmt { lhs: var("signer"), rhs: ecrecover(), span: None })
+    }
+
+    #[test]
+    fn test_unchecked_ecrecover_detector() {
+        let detector = UncheckedEcrecoverDetector::new();
+        assert_eq!(detector.id(), "unchecked-ecrecover");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_unchecked_comparison_is_flagged() {
+        // signer = ecrecover(...); require(signer == owner);
+        let bugs = check_body(
+            &UncheckedEcrecoverDetector::new(),
+            vec![
+                recover(),
+                require(binop(BinOp::Eq, var("signer"), var("owner"))),
+            ],
+        );
+        assert_eq!(bugs.len(), 1);
+        assert!(bugs[0].description.as_ref().unwrap().contains("'owner'"));
+
+        // require(ecrecover(...) == owner);
+        let bugs = check_body(
+            &UncheckedEcrecoverDetector::new(),
+            vec![require(binop(BinOp::Eq, ecrecover(), var("owner")))],
+        );
+        assert_eq!(bugs.len(), 1);
+    }
+
+    #[test]
+    fn test_zero_checked_recovery_is_not_flagged() {
+        // require(signer != address(0)); require(signer == owner);
+        let body = vec![
+            recover(),
+            require(binop(BinOp::Ne, var("signer"), num(0))),
+            require(binop(BinOp::Eq, var("signer"), var("owner"))),
+        ];
+        assert!(check_body(&UncheckedEcrecoverDetector::new(), body).is_empty());
+
+        // require(signer != address(0) && signer == owner);
+        let cond = binop(
+            BinOp::And,
+            binop(BinOp::Ne, var("signer"), num(0)),
+            binop(BinOp::Eq, var("signer"), var("owner")),
+        );
+        assert!(
+            check_body(&UncheckedEcrecoverDetector::new(), vec![recover(), require(cond)])
+                .is_empty()
+        );
+
+        // The check must precede the comparison.
+        let body = vec![
+            recover(),
+            require(binop(BinOp::Eq, var("signer"), var("owner"))),
+            require(binop(BinOp::Ne, var("signer"), num(0))),
+        ];
+        assert_eq!(check_body(&UncheckedEcrecoverDetector::new(), body).len(), 1);
+    }
+
+    #[test]
+    fn test_comparison_with_checked_signer_is_not_flagged() {
+        // require(owner != address(0)); require(signer == owner);
+        let body = vec![
+            recover(),
+            require(binop(BinOp::Ne, var("owner"), num(0))),
+            require(binop(BinOp::Eq, var("signer"), var("owner"))),
+        ];
+        assert!(check_body(&UncheckedEcrecoverDetector::new(), body).is_empty());
+    }
+}
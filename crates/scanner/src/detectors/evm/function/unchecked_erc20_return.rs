@@ -0,0 +1,263 @@
+//! Unchecked ERC20 Return Detector
+//!
+//! Detects ERC20 `transfer`, `transferFrom` and `approve` calls whose
+//! boolean result is discarded:
+//!
+//! ```solidity
+//! IERC20 token;
+//! token.transfer(to, amount);  // `false` is silently ignored
+//! ```
+//!
+//! Some tokens signal failure by returning `false` instead of reverting, so
+//! a discarded result lets the caller carry on as if the tokens had moved.
+//! The receiver must be typed as an ERC20-like contract: `IERC20`,
+//! `IERC20Metadata` or `ERC20`, a contract of the module inheriting one of
+//! them, or one declaring `transfer`, `transferFrom` and `approve` returning
+//! `bool`. Calls through `SafeERC20` (`safeTransfer`, ...) are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::ancestor_names;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    CallExpr, ContractDecl, Decl, Expr, ExprStmt, FunctionDecl, MemberDecl, Module, Type,
+};
+
+/// Interfaces and base contracts that identify an ERC20 token.
+const ERC20_BASES: &[&str] = &["IERC20", "IERC20Metadata", "ERC20"];
+
+/// ERC20 functions returning a success flag.
+const ERC20_BOOL_FUNCTIONS: &[&str] = &["transfer", "transferFrom", "approve"];
+
+/// Scan detector for ERC20 calls whose boolean result is ignored.
+#[derive(Debug, Default)]
+pub struct UncheckedErc20ReturnDetector;
+
+impl UncheckedErc20ReturnDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Whether the contract type `name` is ERC20-like, resolving it in `module`.
+fn is_erc20_type(name: &str, module: &Module) -> bool {
+    if ERC20_BASES.contains(&name) {
+        return true;
+    }
+    module.decls.iter().any(|decl| match decl {
+        Decl::Contract(c) if c.name == name => {
+            ancestor_names(c, module)
+                .iter()
+                .any(|a| ERC20_BASES.contains(&a.as_str()))
+                || ERC20_BOOL_FUNCTIONS
+                    .iter()
+                    .all(|f| declares_bool_function(c, f))
+        }
+        _ => false,
+    })
+}
+
+fn declares_bool_function(contract: &ContractDecl, name: &str) -> bool {
+    contract.members.iter().any(
+        |m| matches!(m, MemberDecl::Function(f) if f.name == name && f.returns == [Type::Bool]),
+    )
+}
+
+/// The method called if `call` is `transfer`, `transferFrom` or `approve`
+/// on an ERC20-typed receiver.
+pub(crate) fn erc20_bool_call<'a>(call: &'a CallExpr, module: &Module) -> Option<&'a str> {
+    match &*call.callee {
+        Expr::FieldAccess(fa)
+            if ERC20_BOOL_FUNCTIONS.contains(&fa.field.as_str())
+                && matches!(fa.base.typ(), Type::TypeRef(name) if is_erc20_type(&name, module)) =>
+        {
+            Some(&fa.field)
+        }
+        _ => None,
+    }
+}
+
+/// Collects ERC20 calls used as statements.
+struct DiscardedCalls<'m> {
+    module: &'m Module,
+    found: Vec<(String, Option<Loc>)>,
+}
+
+impl<'a> Visit<'a> for DiscardedCalls<'_> {
+    fn visit_expr_stmt(&mut self, stmt: &'a ExprStmt) {
+        if let Expr::FunctionCall(call) = &stmt.expr
+            && let Some(method) = erc20_bool_call(call, self.module)
+        {
+            self.found.push((method.to_string(), stmt.span.clone()));
+        }
+        visit::default::visit_expr_stmt(self, stmt);
+    }
+}
+
+impl ScanDetector for UncheckedErc20ReturnDetector {
+    fn id(&self) -> &'static str {
+        "unchecked-erc20-return"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked ERC20 Return"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects ERC20 transfer, transferFrom and approve calls whose boolean \
+         result is ignored"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::UncheckedLowLevelCalls
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![252]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![104]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Use OpenZeppelin's `SafeERC20` (`safeTransfer`, `safeTransferFrom`, \
+         `forceApprove`), which reverts both on `false` and on tokens that \
+         return nothing."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://docs.openzeppelin.com/contracts/5.x/api/token/erc20#SafeERC20"]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        module: &Module,
+    ) -> Vec<Bug> {
+        let mut finder = DiscardedCalls { module, found: vec![] };
+        finder.visit_function_decl(func);
+
+        finder
+            .found
+            .into_iter()
+            .map(|(method, loc)| {
+                Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "'{}.{}' ignores the boolean returned by ERC20 '{}'.",
+                        contract.name, func.name, method
+                    )),
+                    loc.or_else(|| func.span.clone())
+                        .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::*;
+
+    /// `<token>.<method>(to, amount)` typed as `token_type`.
+    fn call(token_type: &str, method: &str) -> Expr {
+        Expr::FunctionCall(CallExpr {
+            callee: Box::new(Expr::FieldAccess(FieldAccessExpr {
+                base: Box::new(typed_var("token", Type::TypeRef(token_type.to_string()))),
+                field: method.to_string(),
+                ty: Type::None,
+                span: None,
+            })),
+            args: CallArgs::Positional(vec![
+                typed_var("to", Type::None),
+                typed_var("amount", Type::I256),
+            ]),
+            ty: Type::Bool,
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>, decls: Vec<Decl>) -> Vec<Bug> {
+        let func = FunctionDecl::new("pay".to_string(), vec![], vec![], Some(body), None);
+        let contract = ContractDecl::new("Vault".to_string(), vec![], None);
+        let module = Module { id: "test".to_string(), attrs: vec![], decls };
+        UncheckedErc20ReturnDetector::new().check_function(&func, &contract, &module)
+    }
+
+    fn stmt(expr: Expr) -> Stmt {
+        Stmt::Expr(ExprStmt { expr, span: None })
+    }
+
+    #[test]
+    fn test_unchecked_erc20_return_detector() {
+        let detector = UncheckedErc20ReturnDetector::new();
+        assert_eq!(detector.id(), "unchecked-erc20-return");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_discarded_transfer_is_flagged() {
+        let bugs = check(vec![stmt(call("IERC20", "transfer"))], vec![]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some("'Vault.pay' ignores the boolean returned by ERC20 'transfer'.")
+        );
+
+        // Checked results and SafeERC20 calls are fine.
+        let checked = Stmt::Assert(AssertStmt {
+            cond: call("IERC20", "transfer"),
+            message: None,
+            span: None,
+        });
+        assert!(check(vec![checked, stmt(call("IERC20", "safeTransfer"))], vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_receiver_type_is_resolved_in_module() {
+        // `contract Token is ERC20 {}`
+        let mut token = ContractDecl::new("Token".to_string(), vec![], None);
+        token.parents = vec!["ERC20".to_string()];
+        // `interface IPair { function approve(address, uint) returns (bool); }`
+        let approve =
+            FunctionDecl::new("approve".to_string(), vec![], vec![Type::Bool], None, None);
+        let pair =
+            ContractDecl::new("IPair".to_string(), vec![MemberDecl::Function(approve)], None);
+        let decls = vec![Decl::Contract(token), Decl::Contract(pair)];
+
+        assert_eq!(check(vec![stmt(call("Token", "transferFrom"))], decls.clone()).len(), 1);
+        // `IPair` lacks `transfer` and `transferFrom`, so it is not ERC20.
+        assert!(check(vec![stmt(call("IPair", "approve"))], decls).is_empty());
+    }
+}
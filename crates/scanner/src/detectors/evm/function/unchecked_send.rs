@@ -0,0 +1,260 @@
+//! Unchecked Send Detector
+//!
+//! Detects `addr.send(x)` calls whose boolean result is never used. Unlike
+//! `transfer`, `send` does not revert on failure (e.g. when the 2300 gas
+//! stipend runs out), so ignoring its result silently loses the payment.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::DialectExpr;
+use scirs::sir::dialect::evm::EvmExpr;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, ContractDecl, Expr, ExprStmt, FunctionDecl, LocalVarStmt, Module, VarExpr,
+};
+use std::collections::HashSet;
+
+/// Scan detector for ignored `send` results.
+#[derive(Debug, Default)]
+pub struct UncheckedSendDetector;
+
+impl UncheckedSendDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check whether an expression is an `addr.send(x)` call.
+    fn is_send(expr: &Expr) -> bool {
+        match expr {
+            Expr::Dialect(DialectExpr::Evm(EvmExpr::Send(_))) => true,
+            Expr::FunctionCall(call) => matches!(
+                &*call.callee,
+                Expr::FieldAccess(fa) if fa.field == "send" && call.args.exprs().len() == 1
+            ),
+            _ => false,
+        }
+    }
+
+    fn make_bug(&self, contract_name: &str, func_name: &str, loc: Option<&Loc>) -> Bug {
+        Bug::new(
+            self.name(),
+            Some(&format!(
+                "Unchecked send in '{}.{}': the boolean result of `send` is ignored.",
+                contract_name, func_name,
+            )),
+            loc.cloned().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        )
+    }
+}
+
+impl ScanDetector for UncheckedSendDetector {
+    fn id(&self) -> &'static str {
+        "unchecked-send"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unchecked Send"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects `send` calls whose boolean result is dropped instead of being \
+         required, branched on, or otherwise used."
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::UncheckedLowLevelCalls
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![252]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![104]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Check the result of `send`, e.g. `require(addr.send(x))`, or use \
+         `call{value: x}(\"\")` with an explicit success check."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-104",
+            "https://docs.soliditylang.org/en/latest/security-considerations.html#sending-and-receiving-ether",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        // Definitions of `send` results: dropped statements are reported
+        // directly, results bound to a local are kept for the use check.
+        struct DefFinder<'a> {
+            dropped: Vec<Option<&'a Loc>>,
+            bound: Vec<(String, Option<&'a Loc>)>,
+        }
+
+        impl<'a> Visit<'a> for DefFinder<'a> {
+            fn visit_expr_stmt(&mut self, stmt: &'a ExprStmt) {
+                if UncheckedSendDetector::is_send(&stmt.expr) {
+                    self.dropped.push(stmt.span.as_ref());
+                }
+                visit::default::visit_expr_stmt(self, stmt);
+            }
+
+            fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+                if let Some(init) = &stmt.init
+                    && UncheckedSendDetector::is_send(init)
+                    && let [Some(var)] = stmt.vars.as_slice()
+                {
+                    self.bound.push((var.name.clone(), stmt.span.as_ref()));
+                }
+                visit::default::visit_local_var_stmt(self, stmt);
+            }
+
+            fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+                if let Expr::Var(var) = &stmt.lhs
+                    && UncheckedSendDetector::is_send(&stmt.rhs)
+                {
+                    self.bound.push((var.name.clone(), stmt.span.as_ref()));
+                }
+                visit::default::visit_assign_stmt(self, stmt);
+            }
+        }
+
+        // Variables read anywhere in the function. Plain assignment targets
+        // are definitions, not uses.
+        struct UseFinder {
+            used: HashSet<String>,
+        }
+
+        impl<'a> Visit<'a> for UseFinder {
+            fn visit_var_expr(&mut self, var: &'a VarExpr) {
+                self.used.insert(var.name.clone());
+            }
+
+            fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+                if matches!(stmt.lhs, Expr::Var(_)) {
+                    self.visit_expr(&stmt.rhs);
+                } else {
+                    visit::default::visit_assign_stmt(self, stmt);
+                }
+            }
+        }
+
+        let mut defs = DefFinder { dropped: vec![], bound: vec![] };
+        defs.visit_function_decl(func);
+        for loc in defs.dropped {
+            bugs.push(self.make_bug(&contract.name, &func.name, loc));
+        }
+
+        if !defs.bound.is_empty() {
+            let mut uses = UseFinder { used: HashSet::new() };
+            uses.visit_function_decl(func);
+            for (name, loc) in defs.bound {
+                if !uses.used.contains(&name) {
+                    bugs.push(self.make_bug(&contract.name, &func.name, loc));
+                }
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_body, require, typed_var, var};
+    use scirs::sir::dialect::evm::EvmSend;
+    use scirs::sir::*;
+
+    fn send_expr() -> Expr {
+        // addr.send(x)
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Send(EvmSend {
+            target: Box::new(var("addr")),
+            value: Box::new(var("x")),
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    #[test]
+    fn test_unchecked_send_detector() {
+        let detector = UncheckedSendDetector::new();
+        assert_eq!(detector.id(), "unchecked-send");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_bare_send_is_flagged() {
+        let bugs = check_body(
+            &UncheckedSendDetector::new(),
+            vec![Stmt::Expr(ExprStmt { expr: send_expr(), span: None })],
+        );
+        assert_eq!(bugs.len(), 1);
+    }
+
+    fn bind_ok_stmt() -> Stmt {
+        // bool ok = addr.send(x);
+        Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl {
+                name: "ok".to_string(),
+                ty: Type::Bool,
+            })],
+            init: Some(send_expr()),
+            span: None,
+        })
+    }
+
+    #[test]
+    fn test_required_send_is_not_flagged() {
+        let bugs = check_body(&UncheckedSendDetector::new(), vec![require(send_expr())]);
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_checked_send_result_is_not_flagged() {
+        let ok = typed_var("ok", Type::Bool);
+        let bugs = check_body(&UncheckedSendDetector::new(), vec![bind_ok_stmt(), require(ok)]);
+        assert!(bugs.is_empty());
+    }
+
+    #[test]
+    fn test_unused_send_result_is_flagged() {
+        let bugs = check_body(&UncheckedSendDetector::new(), vec![bind_ok_stmt()]);
+        assert_eq!(bugs.len(), 1);
+    }
+}
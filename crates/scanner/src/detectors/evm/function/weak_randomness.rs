@@ -0,0 +1,408 @@
+//! Weak Randomness Detector
+//!
+//! Detects hashes of predictable on-chain values used to pick a result:
+//!
+//! ```solidity
+//! uint256 r = uint256(keccak256(abi.encodePacked(block.timestamp, msg.sender)));
+//! winner = players[r % players.length];
+//! ```
+//!
+//! Miners, validators and the caller know or choose every input, so they
+//! can predict or steer the outcome. A `keccak256` or `sha256` hash of
+//! `block.timestamp`, `block.number`, `block.difficulty`/`block.prevrandao`,
+//! `blockhash` or `msg.sender` is reported where it, or a local variable
+//! assigned from it, is reduced with `%` or compared. Hashes used as keys
+//! or identifiers are left alone.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::pattern::{Pattern, PatternBuilder as P};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::EvmExpr;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssignStmt, BinOp, BinOpExpr, ContractDecl, DialectExpr, Expr, FunctionDecl, LocalVarStmt,
+    Module,
+};
+use std::collections::HashMap;
+
+/// Scan detector for hashes of on-chain values used as random numbers.
+#[derive(Debug, Default)]
+pub struct WeakRandomnessDetector;
+
+impl WeakRandomnessDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Name of a predictable value read by `evm`.
+fn weak_source_name(evm: &EvmExpr) -> Option<&'static str> {
+    match evm {
+        EvmExpr::Timestamp(_) => Some("block.timestamp"),
+        EvmExpr::BlockNumber(_) => Some("block.number"),
+        EvmExpr::BlockDifficulty(_) => Some("block.prevrandao"),
+        EvmExpr::Blockhash(_) => Some("blockhash"),
+        EvmExpr::MsgSender(_) => Some("msg.sender"),
+        _ => None,
+    }
+}
+
+/// Collects the predictable values in an expression, looking into
+/// `abi.encode`/`abi.encodePacked` arguments.
+#[derive(Default)]
+struct SourceFinder {
+    sources: Vec<&'static str>,
+}
+
+impl SourceFinder {
+    fn add(&mut self, name: &'static str) {
+        if !self.sources.contains(&name) {
+            self.sources.push(name);
+        }
+    }
+
+    fn visit_exprs(&mut self, exprs: &[Expr]) {
+        for expr in exprs {
+            self.visit_expr(expr);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for SourceFinder {
+    fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+        let DialectExpr::Evm(evm) = d else {
+            return;
+        };
+        if let Some(name) = weak_source_name(evm) {
+            self.add(name);
+        }
+        match evm {
+            EvmExpr::AbiEncode(e) => self.visit_exprs(&e.args),
+            EvmExpr::AbiEncodePacked(e) => self.visit_exprs(&e.args),
+            _ => {}
+        }
+    }
+}
+
+/// Arguments of a hash call, whether lowered to the EVM dialect or kept as
+/// a call.
+fn hash_inputs(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Keccak256(h))) => vec![&h.expr],
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::Sha256(h))) => vec![&h.expr],
+        Expr::FunctionCall(call) => call.args.exprs(),
+        Expr::TypeCast(cast) => hash_inputs(&cast.expr),
+        _ => vec![],
+    }
+}
+
+fn weak_sources(hash: &Expr) -> Vec<&'static str> {
+    let mut finder = SourceFinder::default();
+    for input in hash_inputs(hash) {
+        finder.visit_expr(input);
+    }
+    finder.sources
+}
+
+/// `keccak256(..)` or `sha256(..)` of at least one predictable value.
+fn weak_hash() -> Pattern {
+    let is_hash = || P::evm(|e| matches!(e, EvmExpr::Keccak256(_) | EvmExpr::Sha256(_)));
+    P::capture(
+        "hash",
+        P::where_(P::or(vec![is_hash(), P::call(is_hash())]), |e| !weak_sources(e).is_empty()),
+    )
+}
+
+/// Collects the predictable values hashed into an expression, directly or
+/// through the variables in `vars`.
+struct RandomValueFinder<'p> {
+    pattern: &'p Pattern,
+    vars: &'p HashMap<String, Vec<&'static str>>,
+    sources: Vec<&'static str>,
+}
+
+impl<'a> Visit<'a> for RandomValueFinder<'_> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        let found = match self.pattern.matches(expr) {
+            Some(m) => m.capture_expr("hash").map(weak_sources),
+            None => match expr {
+                Expr::Var(v) => self.vars.get(&v.name).cloned(),
+                _ => None,
+            },
+        };
+        match found {
+            Some(sources) => {
+                for source in sources {
+                    if !self.sources.contains(&source) {
+                        self.sources.push(source);
+                    }
+                }
+            }
+            None => visit::default::visit_expr(self, expr),
+        }
+    }
+}
+
+fn random_sources(
+    expr: &Expr,
+    pattern: &Pattern,
+    vars: &HashMap<String, Vec<&'static str>>,
+) -> Vec<&'static str> {
+    let mut finder = RandomValueFinder { pattern, vars, sources: vec![] };
+    finder.visit_expr(expr);
+    finder.sources
+}
+
+/// Records the local variables holding a weak hash, with its sources.
+struct RandomVars<'p> {
+    pattern: &'p Pattern,
+    vars: HashMap<String, Vec<&'static str>>,
+    changed: bool,
+}
+
+impl RandomVars<'_> {
+    fn assign(&mut self, name: &str, value: &Expr) {
+        let sources = random_sources(value, self.pattern, &self.vars);
+        if !sources.is_empty() && !self.vars.contains_key(name) {
+            self.vars.insert(name.to_string(), sources);
+            self.changed = true;
+        }
+    }
+}
+
+impl<'a> Visit<'a> for RandomVars<'_> {
+    fn visit_local_var_stmt(&mut self, stmt: &'a LocalVarStmt) {
+        if let ([Some(var)], Some(init)) = (stmt.vars.as_slice(), &stmt.init) {
+            self.assign(&var.name, init);
+        }
+    }
+
+    fn visit_assign_stmt(&mut self, stmt: &'a AssignStmt) {
+        if let Expr::Var(v) = &stmt.lhs {
+            self.assign(&v.name, &stmt.rhs);
+        }
+    }
+}
+
+/// Collects the modulo operations and comparisons on weak random values.
+struct Selections<'p> {
+    pattern: &'p Pattern,
+    vars: &'p HashMap<String, Vec<&'static str>>,
+    found: Vec<(Vec<&'static str>, Option<Loc>)>,
+}
+
+impl<'a> Visit<'a> for Selections<'_> {
+    fn visit_binop_expr(&mut self, expr: &'a BinOpExpr) {
+        if matches!(
+            expr.op,
+            BinOp::Mod | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge | BinOp::Eq | BinOp::Ne
+        ) {
+            let mut sources = random_sources(&expr.lhs, self.pattern, self.vars);
+            for source in random_sources(&expr.rhs, self.pattern, self.vars) {
+                if !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+            if !sources.is_empty() {
+                self.found.push((sources, expr.span.clone()));
+                return;
+            }
+        }
+        visit::default::visit_binop_expr(self, expr);
+    }
+}
+
+impl ScanDetector for WeakRandomnessDetector {
+    fn id(&self) -> &'static str {
+        "weak-randomness"
+    }
+
+    fn name(&self) -> &'static str {
+        "Weak Randomness"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects hashes of block values or msg.sender reduced with % or \
+         compared to pick a result"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::BadRandomness
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![330]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![120]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Draw random numbers from a verifiable source such as Chainlink VRF, \
+         or use a commit-reveal scheme."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://swcregistry.io/docs/SWC-120",
+            "https://docs.chain.link/vrf",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let pattern = weak_hash();
+
+        let mut random_vars =
+            RandomVars { pattern: &pattern, vars: HashMap::new(), changed: true };
+        while random_vars.changed {
+            random_vars.changed = false;
+            random_vars.visit_function_decl(func);
+        }
+
+        let mut selections =
+            Selections { pattern: &pattern, vars: &random_vars.vars, found: vec![] };
+        selections.visit_function_decl(func);
+
+        selections
+            .found
+            .into_iter()
+            .map(|(sources, loc)| {
+                Bug::new(
+                    self.name(),
+                    Some(&format!(
+                        "'{}.{}' derives a random value from {}, which can be predicted \
+                         or influenced.",
+                        contract.name,
+                        func.name,
+                        sources.join(", ")
+                    )),
+                    loc.or_else(|| func.span.clone())
+                        .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{binop, check_body, var};
+    use scirs::sir::dialect::evm::{EvmAbiEncodePacked, EvmKeccak256, EvmMsgSender, EvmTimestamp};
+    use scirs::sir::*;
+
+    /// `uint256(keccak256(abi.encodePacked(block.timestamp, msg.sender)))`
+    fn weak_hash_expr() -> Expr {
+        let loc = Loc::new(0, 0, 0, 0);
+        let packed = EvmExpr::AbiEncodePacked(EvmAbiEncodePacked {
+            args: vec![
+                Expr::Dialect(DialectExpr::Evm(EvmExpr::Timestamp(EvmTimestamp {
+                    loc: loc.clone(),
+                }))),
+                Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgSender(EvmMsgSender {
+                    loc: loc.clone(),
+                }))),
+            ],
+            loc: loc.clone(),
+        });
+        let hash = EvmExpr::Keccak256(EvmKeccak256 {
+            expr: Box::new(Expr::Dialect(DialectExpr::Evm(packed))),
+            loc,
+        });
+        Expr::TypeCast(TypeCastExpr {
+            ty: Type::I256,
+            expr: Box::new(Expr::Dialect(DialectExpr::Evm(hash))),
+            span: None,
+        })
+    }
+
+    fn local(name: &str, init: Expr) -> Stmt {
+        Stmt::LocalVar(LocalVarStmt {
+            vars: vec![Some(LocalVarDecl {
+                name: name.to_string(),
+                ty: Type::I256,
+            })],
+            init: Some(init),
+            span: None,
+        })
+    }
+
+    #[test]
+    fn test_weak_randomness_detector() {
+        let detector = WeakRandomnessDetector::new();
+        assert_eq!(detector.id(), "weak-randomness");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_hash_reduced_through_local_is_flagged() {
+        // `uint256 r = uint256(keccak256(...)); winner = r % n;`
+        let pick = Stmt::Assign(AssignStmt {
+            lhs: var("winner"),
+            rhs: binop(BinOp::Mod, var("r"), var("n")),
+            span: None,
+        });
+        let bugs =
+            check_body(&WeakRandomnessDetector::new(), vec![local("r", weak_hash_expr()), pick]);
+        assert_eq!(bugs.len(), 1);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "'C.f' derives a random value from block.timestamp, msg.sender, \
+                 which can be predicted or influenced."
+            )
+        );
+    }
+
+    #[test]
+    fn test_hash_used_as_key_is_not_flagged() {
+        // `bytes32 id = keccak256(...); seen[id] = true;`
+        let mark = Stmt::Assign(AssignStmt {
+            lhs: Expr::IndexAccess(IndexAccessExpr {
+                base: Box::new(var("seen")),
+                index: Some(Box::new(var("id"))),
+                ty: Type::Bool,
+                span: None,
+            }),
+            rhs: var("yes"),
+            span: None,
+        });
+        assert!(
+            check_body(&WeakRandomnessDetector::new(), vec![local("id", weak_hash_expr()), mark])
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Withdraw Ordering Detector
+//!
+//! Detects withdraw functions that send ETH before updating the balance
+//! they paid out from, e.g.
+//!
+//! ```solidity
+//! require(balances[msg.sender] >= amount);
+//! payable(msg.sender).transfer(amount);
+//! balances[msg.sender] -= amount;
+//! ```
+//!
+//! A storage variable read before the send and written after it is taken to
+//! be the paid-out balance. Unlike `cei-violation`, the ordering is reported
+//! even when the function has a reentrancy guard: the guard only blocks
+//! re-entry into the same contract, and the balance is still stale while
+//! the recipient runs.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmLowLevelCall};
+use scirs::sir::utils::visit::Visit;
+use scirs::sir::{ContractDecl, DialectExpr, Expr, FunctionDecl, Module, Stmt, VarExpr};
+use std::collections::HashSet;
+
+/// Scan detector for balance updates after the ETH send of a withdraw.
+#[derive(Debug, Default)]
+pub struct WithdrawOrderingDetector;
+
+impl WithdrawOrderingDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Path state while walking a function body in order.
+#[derive(Clone, Default)]
+struct State {
+    /// Storage variables read so far.
+    read: HashSet<String>,
+    /// Whether ETH may have been sent.
+    sent: bool,
+}
+
+impl State {
+    fn join(self, other: State) -> State {
+        State {
+            read: self.read.union(&other.read).cloned().collect(),
+            sent: self.sent || other.sent,
+        }
+    }
+}
+
+/// Storage variable written by an assignment target, if any.
+fn storage_root<'e>(lhs: &'e Expr, storage: &[String]) -> Option<&'e str> {
+    match lhs {
+        Expr::Var(v) if storage.contains(&v.name) => Some(&v.name),
+        Expr::IndexAccess(e) => storage_root(&e.base, storage),
+        Expr::FieldAccess(e) => storage_root(&e.base, storage),
+        _ => None,
+    }
+}
+
+/// Collects the storage variables read by a statement or condition, and
+/// whether it sends ETH.
+struct EffectFinder<'s> {
+    storage: &'s [String],
+    read: HashSet<String>,
+    sends: bool,
+}
+
+impl<'s> EffectFinder<'s> {
+    fn new(storage: &'s [String]) -> Self {
+        Self { storage, read: HashSet::new(), sends: false }
+    }
+
+    fn apply(self, state: &mut State) {
+        state.read.extend(self.read);
+        state.sent |= self.sends;
+    }
+}
+
+impl<'a> Visit<'a> for EffectFinder<'_> {
+    fn visit_var_expr(&mut self, var: &'a VarExpr) {
+        if self.storage.contains(&var.name) {
+            self.read.insert(var.name.clone());
+        }
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        let amount = match expr {
+            DialectExpr::Evm(EvmExpr::Transfer(e)) => &e.amount,
+            DialectExpr::Evm(EvmExpr::Send(e)) => &e.value,
+            DialectExpr::Evm(EvmExpr::LowLevelCall(EvmLowLevelCall {
+                value: Some(value),
+                ..
+            })) => value,
+            _ => return,
+        };
+        self.sends = true;
+        self.visit_expr(amount);
+    }
+}
+
+/// Walks a function body in order, collecting balances written after a send.
+struct Walker<'s> {
+    storage: &'s [String],
+    late_writes: Vec<(String, Option<Loc>)>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, stmts: &[Stmt], mut state: State) -> State {
+        for stmt in stmts {
+            state = self.walk_stmt(stmt, state);
+        }
+        state
+    }
+
+    fn walk_stmt(&mut self, stmt: &Stmt, mut state: State) -> State {
+        match stmt {
+            Stmt::If(s) => {
+                let mut finder = EffectFinder::new(self.storage);
+                finder.visit_expr(&s.cond);
+                finder.apply(&mut state);
+                let then_state = self.walk(&s.then_body, state.clone());
+                let else_state = match &s.else_body {
+                    Some(body) => self.walk(body, state),
+                    None => state,
+                };
+                then_state.join(else_state)
+            }
+            // A second iteration sees the sends of the first one.
+            Stmt::While(s) => {
+                let first = self.walk(&s.body, state.clone());
+                state.join(self.walk(&s.body, first))
+            }
+            Stmt::For(s) => {
+                let first = self.walk(&s.body, state.clone());
+                state.join(self.walk(&s.body, first))
+            }
+            Stmt::Block(inner) => self.walk(inner, state),
+            _ => {
+                let written = match stmt {
+                    Stmt::Assign(a) => storage_root(&a.lhs, self.storage),
+                    Stmt::AugAssign(a) => storage_root(&a.lhs, self.storage),
+                    _ => None,
+                };
+                if let Some(var) = written
+                    && state.sent
+                    && state.read.contains(var)
+                    && !self.late_writes.iter().any(|(v, _)| v == var)
+                {
+                    self.late_writes
+                        .push((var.to_string(), stmt.span().cloned()));
+                }
+                let mut finder = EffectFinder::new(self.storage);
+                finder.visit_stmt(stmt);
+                finder.apply(&mut state);
+                state
+            }
+        }
+    }
+}
+
+impl ScanDetector for WithdrawOrderingDetector {
+    fn id(&self) -> &'static str {
+        "withdraw-ordering"
+    }
+
+    fn name(&self) -> &'static str {
+        "Withdraw Ordering"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects withdraw functions that decrement the balance after sending \
+         ETH instead of before, even when a reentrancy guard is present"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Reentrancy
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Function
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![841]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![107]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Decrement (or zero) the balance before sending ETH, following the \
+         Checks-Effects-Interactions pattern; a reentrancy guard does not make \
+         the reversed order safe for other contracts reading the balance."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/security-considerations.html#use-the-checks-effects-interactions-pattern",
+            "https://swcregistry.io/docs/SWC-107",
+        ]
+    }
+
+    fn check_function(
+        &self,
+        func: &FunctionDecl,
+        contract: &ContractDecl,
+        _module: &Module,
+    ) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let Some(body) = &func.body else {
+            return bugs;
+        };
+        let storage = contract.storage_names();
+        let mut walker = Walker { storage: &storage, late_writes: vec![] };
+        walker.walk(body, State::default());
+
+        for (var, loc) in walker.late_writes {
+            bugs.push(Bug::new(
+                self.name(),
+                Some(&format!(
+                    "'{}.{}' updates balance '{}' after sending ETH; update it \
+                     before the send.",
+                    contract.name, func.name, var
+                )),
+                loc.or_else(|| func.span.clone())
+                    .unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+                self.bug_kind(),
+                self.bug_category(),
+                self.risk_level(),
+                self.cwe_ids(),
+                self.swc_ids(),
+                Some(self.recommendation()),
+            ));
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::var;
+    use scirs::sir::dialect::evm::{EvmMsgSender, EvmTransfer};
+    use scirs::sir::*;
+
+    fn sender() -> Expr {
+        Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgSender(EvmMsgSender {
+            loc: Loc::new(0, 0, 0, 0),
+        })))
+    }
+
+    /// `balances[msg.sender]`
+    fn balance() -> Expr {
+        Expr::IndexAccess(IndexAccessExpr {
+            base: Box::new(var("balances")),
+            index: Some(Box::new(sender())),
+            ty: Type::I256,
+            span: None,
+        })
+    }
+
+    /// `require(balances[msg.sender] >= amount)`
+    fn check_balance() -> Stmt {
+        let ge = Expr::BinOp(BinOpExpr {
+            op: BinOp::Ge,
+            lhs: Box::new(balance()),
+            rhs: Box::new(var("amount")),
+            overflow: OverflowSemantics::Checked,
+            span: None,
+        });
+        Stmt::If(IfStmt {
+            cond: Expr::UnOp(UnOpExpr { op: UnOp::Not, operand: Box::new(ge), span: None }),
+            then_body: vec![Stmt::Revert(RevertStmt {
+                error: None,
+                args: vec![],
+                span: None,
+            })],
+            else_body: None,
+            span: None,
+        })
+    }
+
+    /// `balances[msg.sender] -= amount`
+    fn decrement() -> Stmt {
+        Stmt::AugAssign(AugAssignStmt {
+            op: BinOp::Sub,
+            lhs: balance(),
+            rhs: var("amount"),
+            span: None,
+        })
+    }
+
+    /// `payable(msg.sender).transfer(amount)`
+    fn send() -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::Dialect(DialectExpr::Evm(EvmExpr::Transfer(EvmTransfer {
+                target: Box::new(sender()),
+                amount: Box::new(var("amount")),
+                loc: Loc::new(0, 0, 0, 0),
+            }))),
+            span: None,
+        })
+    }
+
+    fn check(body: Vec<Stmt>, guarded: bool) -> Vec<Bug> {
+        let mut func = FunctionDecl::new("withdraw".to_string(), vec![], vec![], Some(body), None);
+        if guarded {
+            func.attrs
+                .push(Attr::sir(sir_attrs::REENTRANCY_GUARD, AttrValue::Bool(true)));
+        }
+        let storage = StorageDecl::new("balances".to_string(), Type::I256, None, None);
+        let contract = ContractDecl::new(
+            "Bank".to_string(),
+            vec![
+                MemberDecl::Storage(storage),
+                MemberDecl::Function(func.clone()),
+            ],
+            None,
+        );
+        let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+        WithdrawOrderingDetector::new().check_function(&func, &contract, &module)
+    }
+
+    #[test]
+    fn test_withdraw_ordering_detector() {
+        let detector = WithdrawOrderingDetector::new();
+        assert_eq!(detector.id(), "withdraw-ordering");
+        assert_eq!(detector.risk_level(), RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_decrement_after_send_is_flagged() {
+        let body = vec![check_balance(), send(), decrement()];
+        assert_eq!(check(body.clone(), false).len(), 1);
+        // The reentrancy guard does not fix the ordering.
+        assert_eq!(check(body, true).len(), 1);
+    }
+
+    #[test]
+    fn test_decrement_before_send_is_not_flagged() {
+        let body = vec![check_balance(), decrement(), send()];
+        assert!(check(body, false).is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 pub mod contract;
 pub mod function;
 pub mod module;
+pub(crate) mod utils;
 
 // Re-export all detector types
 pub use contract::*;
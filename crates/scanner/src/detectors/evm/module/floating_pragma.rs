@@ -1,10 +1,14 @@
 //! Floating Pragma Detector
 //!
 //! Detects unlocked compiler versions by inspecting the `#sir.pragma_solidity`
-//! attribute on SIR modules.
+//! attribute on SIR modules. With the `allow_caret` option, caret ranges
+//! such as `^0.8.20` are accepted and only wider ranges are reported.
+//!
+//! Each finding suggests pinning the pragma, to the `pinned_version` option
+//! when set and otherwise to the lowest version the range admits.
 
-use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
-use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use crate::detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, FixSuggestion, RiskLevel};
 use common::loc::Loc;
 use scirs::sir::AttrValue;
 use scirs::sir::Module;
@@ -12,17 +16,43 @@ use scirs::sir::attrs::sir_attrs;
 
 /// Scan detector for floating pragma.
 #[derive(Debug, Default)]
-pub struct FloatingPragmaDetector;
+pub struct FloatingPragmaDetector {
+    /// Whether caret ranges (`^0.8.20`) are accepted.
+    allow_caret: bool,
+    /// Version suggested in place of a floating pragma.
+    pinned_version: Option<String>,
+}
 
 impl FloatingPragmaDetector {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 
     /// Returns true if the pragma version string is "floating" (non-pinned).
     fn is_floating(version: &str) -> bool {
         version.contains('^') || version.contains('>') || version.contains('<')
     }
+
+    /// Returns true if the pragma version string should be reported.
+    fn is_reported(&self, version: &str) -> bool {
+        if self.allow_caret && !version.contains('>') && !version.contains('<') {
+            return false;
+        }
+        Self::is_floating(version)
+    }
+
+    /// Version to pin a floating pragma `version` to.
+    fn pinned(&self, version: &str) -> String {
+        if let Some(pinned) = &self.pinned_version {
+            return pinned.clone();
+        }
+        version
+            .split_whitespace()
+            .map(|bound| bound.trim_start_matches(['^', '~', '>', '=']))
+            .find(|bound| !bound.starts_with('<'))
+            .unwrap_or(version)
+            .to_string()
+    }
 }
 
 impl ScanDetector for FloatingPragmaDetector {
@@ -80,27 +110,47 @@ impl ScanDetector for FloatingPragmaDetector {
         vec!["https://swcregistry.io/docs/SWC-103"]
     }
 
+    fn configure(&mut self, params: &DetectorParams) {
+        if let Some(allow) = params.get("allow_caret").and_then(|v| v.parse().ok()) {
+            self.allow_caret = allow;
+        }
+        if let Some(version) = params.get("pinned_version") {
+            self.pinned_version = Some(version.clone());
+        }
+    }
+
+    fn param_names(&self) -> Vec<&'static str> {
+        vec!["allow_caret", "pinned_version"]
+    }
+
     fn check_module(&self, module: &Module) -> Vec<Bug> {
         let mut bugs = Vec::new();
 
         for attr in &module.attrs {
-            if attr.namespace == "sir" && attr.key == sir_attrs::PRAGMA_SOLIDITY {
-                if let AttrValue::String(version) = &attr.value {
-                    if Self::is_floating(version) {
-                        let loc = attr.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0));
-                        bugs.push(Bug::new(
-                            self.name(),
-                            Some(&format!("Floating pragma version '{}'.", version)),
-                            loc,
-                            self.bug_kind(),
-                            self.bug_category(),
-                            self.risk_level(),
-                            self.cwe_ids(),
-                            self.swc_ids(),
-                            Some(self.recommendation()),
-                        ));
-                    }
-                }
+            if attr.namespace == "sir"
+                && attr.key == sir_attrs::PRAGMA_SOLIDITY
+                && let AttrValue::String(version) = &attr.value
+                && self.is_reported(version)
+            {
+                let loc = attr.span.clone().unwrap_or_else(|| Loc::new(0, 0, 0, 0));
+                let pinned = self.pinned(version);
+                let suggestion = FixSuggestion::replace(
+                    &format!("Pin the compiler version to {}.", pinned),
+                    loc.clone(),
+                    &format!("pragma solidity {};", pinned),
+                );
+                let bug = Bug::new(
+                    self.name(),
+                    Some(&format!("Floating pragma version '{}'.", version)),
+                    loc,
+                    self.bug_kind(),
+                    self.bug_category(),
+                    self.risk_level(),
+                    self.cwe_ids(),
+                    self.swc_ids(),
+                    Some(self.recommendation()),
+                );
+                bugs.push(bug.with_suggestion(suggestion));
             }
         }
 
@@ -111,6 +161,7 @@ impl ScanDetector for FloatingPragmaDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use scirs::sir::Attr;
 
     #[test]
     fn test_floating_pragma_detector() {
@@ -127,4 +178,40 @@ mod tests {
         assert!(FloatingPragmaDetector::is_floating(">=0.6.0 <0.9.0"));
         assert!(!FloatingPragmaDetector::is_floating("0.8.17"));
     }
+
+    #[test]
+    fn test_allow_caret_option() {
+        let mut detector = FloatingPragmaDetector::new();
+        assert!(detector.is_reported("^0.8.0"));
+
+        let params = DetectorParams::from([("allow_caret".to_string(), "true".to_string())]);
+        detector.configure(&params);
+        assert!(!detector.is_reported("^0.8.0"));
+        assert!(detector.is_reported(">=0.8.0"));
+        assert!(!detector.is_reported("0.8.17"));
+    }
+
+    #[test]
+    fn test_pin_suggestion() {
+        let mut detector = FloatingPragmaDetector::new();
+        assert_eq!(detector.pinned("^0.8.0"), "0.8.0");
+        assert_eq!(detector.pinned(">=0.6.0 <0.9.0"), "0.6.0");
+
+        let params = DetectorParams::from([("pinned_version".to_string(), "0.8.19".to_string())]);
+        detector.configure(&params);
+        let module = Module {
+            id: "test".to_string(),
+            attrs: vec![
+                Attr::sir(sir_attrs::PRAGMA_SOLIDITY, AttrValue::String("^0.8.0".to_string()))
+                    .with_span(Some(Loc::new(1, 1, 1, 24))),
+            ],
+            decls: vec![],
+        };
+        let bugs = detector.check_module(&module);
+        let suggestion = bugs[0].suggestion.as_ref().unwrap();
+        assert_eq!(suggestion.message, "Pin the compiler version to 0.8.19.");
+        let replacement = suggestion.replacement.as_ref().unwrap();
+        assert_eq!(replacement.loc, Loc::new(1, 1, 1, 24));
+        assert_eq!(replacement.text, "pragma solidity 0.8.19;");
+    }
 }
@@ -1,5 +1,11 @@
 //! Module-level EVM detectors
 
 pub mod floating_pragma;
+pub mod storage_layout_collision;
+pub mod transparent_proxy_clash;
+pub mod unused_event;
 
 pub use floating_pragma::FloatingPragmaDetector;
+pub use storage_layout_collision::StorageLayoutCollisionDetector;
+pub use transparent_proxy_clash::TransparentProxyClashDetector;
+pub use unused_event::UnusedEventDetector;
@@ -0,0 +1,572 @@
+//! Storage Layout Collision Detector
+//!
+//! A proxy delegates to its implementation, so both read and write the
+//! same storage. When they declare state variables in a different order,
+//! the implementation overwrites the proxy's variables (or the other way
+//! round) after an upgrade:
+//!
+//! ```solidity
+//! contract TokenProxy { address implementation; address admin; ... }
+//! contract Token { uint256 totalSupply; address owner; ... }
+//! ```
+//!
+//! The storage layout of both contracts is computed with Solidity's rules:
+//! inherited variables first, in C3 linearization order from the most base
+//! contract; value types packed into 32-byte slots in declaration order;
+//! structs, arrays and mappings starting a new slot. Constants and
+//! immutables take no storage.
+//!
+//! A contract is a proxy if its name ends with `Proxy` or it has a
+//! `fallback` and performs a `delegatecall`. Its implementations are the
+//! contracts named after it (`Token`, `TokenImpl`, `TokenImplementation`,
+//! `TokenLogic` or `TokenV2` for `TokenProxy`) and the contracts sharing a
+//! base contract with it, as in the inherited-storage pattern. Each slot
+//! that holds different variables in the proxy and in an implementation is
+//! reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl, EvmStructDef, EvmType};
+use scirs::sir::dialect::{DialectMemberDecl, DialectType, EvmStorageExt, EvmTypeExt};
+use scirs::sir::lower::linearize;
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    CallExpr, ContractDecl, Decl, DialectExpr, Expr, MemberDecl, Module, StorageDecl, Type,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Scan detector for proxies and implementations with colliding storage.
+#[derive(Debug, Default)]
+pub struct StorageLayoutCollisionDetector;
+
+impl StorageLayoutCollisionDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Name suffixes of an implementation contract, after the proxy's name
+/// without `Proxy`.
+const IMPLEMENTATION_SUFFIXES: &[&str] = &["", "Impl", "Implementation", "Logic"];
+
+/// A state variable placed in storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StorageVar<'m> {
+    decl: &'m StorageDecl,
+    slot: u64,
+    /// Byte offset within the slot.
+    offset: u64,
+    /// Number of slots covered, 1 for packed value types.
+    slots: u64,
+}
+
+impl StorageVar<'_> {
+    fn covers(&self, slot: u64) -> bool {
+        self.slot <= slot && slot < self.slot + self.slots
+    }
+
+    fn describe(&self) -> String {
+        let mut desc = format!("'{} {}'", type_name(&self.decl.ty), self.decl.name);
+        if self.offset > 0 {
+            desc.push_str(&format!(" (offset {})", self.offset));
+        }
+        desc
+    }
+}
+
+/// Solidity spelling of a type.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Bool => "bool".to_string(),
+        Type::String => "string".to_string(),
+        Type::Bytes => "bytes".to_string(),
+        Type::FixedBytes(n) => format!("bytes{n}"),
+        Type::TypeRef(name) => name.clone(),
+        Type::Array(elem) => format!("{}[]", type_name(elem)),
+        Type::FixedArray(elem, len) => format!("{}[{len}]", type_name(elem)),
+        Type::Map(key, value) => format!("mapping({} => {})", type_name(key), type_name(value)),
+        _ => ty.abi_name(),
+    }
+}
+
+/// Storage sizes of the types of a module.
+struct TypeSizes<'m> {
+    structs: HashMap<&'m str, &'m EvmStructDef>,
+    enums: HashSet<&'m str>,
+}
+
+impl<'m> TypeSizes<'m> {
+    fn of(module: &'m Module) -> Self {
+        let mut sizes = TypeSizes { structs: HashMap::new(), enums: HashSet::new() };
+        for decl in &module.decls {
+            let Decl::Contract(contract) = decl else {
+                continue;
+            };
+            for member in &contract.members {
+                match member {
+                    MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::StructDef(s))) => {
+                        sizes.structs.insert(&s.name, s);
+                    }
+                    MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::EnumDef(e))) => {
+                        sizes.enums.insert(&e.name);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        sizes
+    }
+
+    /// Size in bytes of a value type packed with its neighbours, or `None`
+    /// for types that take whole slots.
+    fn packed_size(&self, ty: &Type) -> Option<u64> {
+        match ty {
+            Type::I1 | Type::I8 | Type::Si8 | Type::Bool => Some(1),
+            Type::I16 | Type::Si16 => Some(2),
+            Type::I32 | Type::Si32 => Some(4),
+            Type::I64 | Type::Si64 => Some(8),
+            Type::I128 | Type::Si128 => Some(16),
+            Type::I256 | Type::Si256 => Some(32),
+            Type::FixedBytes(n) => Some(u64::from(*n)),
+            Type::Dialect(DialectType::Evm(EvmType::Address | EvmType::AddressPayable)) => {
+                Some(20)
+            }
+            Type::TypeRef(name) if self.enums.contains(name.as_str()) => Some(1),
+            // Contracts and interfaces are stored as addresses.
+            Type::TypeRef(name) if !self.structs.contains_key(name.as_str()) => Some(20),
+            _ => None,
+        }
+    }
+
+    /// Number of slots taken by a type that is not packed.
+    fn slot_count(&self, ty: &Type) -> u64 {
+        match ty {
+            Type::FixedArray(elem, len) => match self.packed_size(elem) {
+                Some(size) => len.div_ceil(32 / size.max(1)),
+                None => len * self.slot_count(elem),
+            },
+            Type::TypeRef(name) => match self.structs.get(name.as_str()) {
+                Some(def) => {
+                    let (slot, offset) = self.place(def.fields.iter().map(|(_, ty)| ty), |_| {});
+                    (slot + u64::from(offset > 0)).max(1)
+                }
+                None => 1,
+            },
+            _ => 1,
+        }
+    }
+
+    /// Place `types` one after the other from slot 0, calling `placed`
+    /// with the slot, offset and slot count of each. Returns the next free
+    /// slot and offset.
+    fn place<'t>(
+        &self,
+        types: impl Iterator<Item = &'t Type>,
+        mut placed: impl FnMut((u64, u64, u64)),
+    ) -> (u64, u64) {
+        let (mut slot, mut offset) = (0, 0);
+        for ty in types {
+            match self.packed_size(ty) {
+                Some(size) => {
+                    if offset + size > 32 {
+                        slot += 1;
+                        offset = 0;
+                    }
+                    placed((slot, offset, 1));
+                    offset += size;
+                }
+                None => {
+                    if offset > 0 {
+                        slot += 1;
+                        offset = 0;
+                    }
+                    let count = self.slot_count(ty);
+                    placed((slot, 0, count));
+                    slot += count;
+                }
+            }
+        }
+        (slot, offset)
+    }
+
+    /// Storage layout of `contract`, inherited variables first.
+    fn layout(
+        &self,
+        contract: &'m ContractDecl,
+        contracts: &HashMap<String, &'m ContractDecl>,
+    ) -> Vec<StorageVar<'m>> {
+        let decls: Vec<&StorageDecl> = storage_order(contract, contracts)
+            .into_iter()
+            .flat_map(|c| &c.members)
+            .filter_map(|m| match m {
+                MemberDecl::Storage(s) if !s.is_constant_storage() => Some(s),
+                _ => None,
+            })
+            .collect();
+
+        let mut layout = Vec::new();
+        let mut decls_iter = decls.iter();
+        self.place(decls.iter().map(|s| &s.ty), |(slot, offset, slots)| {
+            if let Some(decl) = decls_iter.next() {
+                layout.push(StorageVar { decl, slot, offset, slots });
+            }
+        });
+        layout
+    }
+}
+
+/// The contracts whose state variables `contract` stores, most base first.
+fn storage_order<'m>(
+    contract: &'m ContractDecl,
+    contracts: &HashMap<String, &'m ContractDecl>,
+) -> Vec<&'m ContractDecl> {
+    match linearize(&contract.name, contracts) {
+        Ok(order) => order
+            .iter()
+            .rev()
+            .filter_map(|name| contracts.get(name.as_str()).copied())
+            .collect(),
+        Err(_) => vec![contract],
+    }
+}
+
+/// Finds `delegatecall`s.
+#[derive(Default)]
+struct DelegatecallFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for DelegatecallFinder {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        if let Expr::FieldAccess(fa) = &*expr.callee {
+            self.found |= fa.field == "delegatecall";
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        match expr {
+            DialectExpr::Evm(EvmExpr::Delegatecall(_)) => self.found = true,
+            DialectExpr::Evm(EvmExpr::InlineAsm(asm)) => {
+                self.found |= asm.asm_text.contains("delegatecall");
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_proxy(contract: &ContractDecl) -> bool {
+    if contract.name.ends_with("Proxy") {
+        return true;
+    }
+    let mut has_fallback = false;
+    let mut finder = DelegatecallFinder::default();
+    for member in &contract.members {
+        if let MemberDecl::Function(f) = member {
+            has_fallback |= f.name == "fallback";
+            finder.visit_stmts(f.body.as_deref().unwrap_or_default());
+        }
+    }
+    has_fallback && finder.found
+}
+
+/// Returns `true` if `name` is an implementation name for `proxy`.
+fn is_implementation_name(name: &str, proxy: &str) -> bool {
+    let stem = proxy.replace("Proxy", "");
+    let Some(suffix) = name.strip_prefix(&stem) else {
+        return false;
+    };
+    !stem.is_empty()
+        && (IMPLEMENTATION_SUFFIXES.contains(&suffix)
+            || suffix
+                .strip_prefix('V')
+                .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit())))
+}
+
+impl StorageLayoutCollisionDetector {
+    fn bug(&self, message: String, loc: Option<&Loc>) -> Bug {
+        Bug::new(
+            self.name(),
+            Some(&message),
+            loc.cloned().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        )
+    }
+}
+
+impl ScanDetector for StorageLayoutCollisionDetector {
+    fn id(&self) -> &'static str {
+        "storage-layout-collision"
+    }
+
+    fn name(&self) -> &'static str {
+        "Storage Layout Collision"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects proxies and implementations whose state variables occupy \
+         the same storage slots"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::Other
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Module
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1321]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Keep proxy state in EIP-1967 slots (or an unstructured storage slot) \
+         instead of state variables, and only ever append state variables to \
+         an implementation, in the same order across upgrades."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html",
+            "https://eips.ethereum.org/EIPS/eip-1967",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["proxy"]
+    }
+
+    fn check_module(&self, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let contracts: HashMap<String, &ContractDecl> = module
+            .decls
+            .iter()
+            .filter_map(|d| match d {
+                Decl::Contract(c) => Some((c.name.clone(), c)),
+                _ => None,
+            })
+            .collect();
+        let sizes = TypeSizes::of(module);
+        let ancestors = |c: &ContractDecl| -> HashSet<String> {
+            let mut names: HashSet<String> = storage_order(c, &contracts)
+                .iter()
+                .map(|a| a.name.clone())
+                .collect();
+            names.remove(&c.name);
+            names
+        };
+
+        for proxy in module.decls.iter().filter_map(|d| match d {
+            Decl::Contract(c) if is_proxy(c) => Some(c),
+            _ => None,
+        }) {
+            let proxy_layout = sizes.layout(proxy, &contracts);
+            if proxy_layout.is_empty() {
+                continue;
+            }
+            let proxy_bases = ancestors(proxy);
+
+            for implementation in module.decls.iter().filter_map(|d| match d {
+                Decl::Contract(c) if c.name != proxy.name && !is_proxy(c) => Some(c),
+                _ => None,
+            }) {
+                let bases = ancestors(implementation);
+                let related = is_implementation_name(&implementation.name, &proxy.name)
+                    || (!proxy_bases.is_disjoint(&bases)
+                        && !proxy_bases.contains(&implementation.name)
+                        && !bases.contains(&proxy.name));
+                if !related {
+                    continue;
+                }
+
+                let layout = sizes.layout(implementation, &contracts);
+                let slot_count = proxy_layout
+                    .iter()
+                    .map(|v| v.slot + v.slots)
+                    .max()
+                    .unwrap_or(0);
+                let mut last_reported: Option<(Vec<String>, Vec<String>)> = None;
+                for slot in 0..slot_count {
+                    let in_proxy: Vec<&StorageVar> =
+                        proxy_layout.iter().filter(|v| v.covers(slot)).collect();
+                    let in_implementation: Vec<&StorageVar> =
+                        layout.iter().filter(|v| v.covers(slot)).collect();
+                    let same = in_proxy.len() == in_implementation.len()
+                        && in_proxy.iter().zip(&in_implementation).all(|(p, i)| {
+                            p.decl.name == i.decl.name
+                                && p.decl.ty == i.decl.ty
+                                && (p.slot, p.offset) == (i.slot, i.offset)
+                        });
+                    if same || in_proxy.is_empty() || in_implementation.is_empty() {
+                        continue;
+                    }
+
+                    let described = (
+                        in_proxy.iter().map(|v| v.describe()).collect::<Vec<_>>(),
+                        in_implementation
+                            .iter()
+                            .map(|v| v.describe())
+                            .collect::<Vec<_>>(),
+                    );
+                    // Variables spanning several slots are reported once.
+                    if last_reported.as_ref() == Some(&described) {
+                        continue;
+                    }
+                    bugs.push(self.bug(
+                        format!(
+                            "Storage slot {} holds {} in '{}' but {} in '{}'.",
+                            slot,
+                            described.0.join(", "),
+                            proxy.name,
+                            described.1.join(", "),
+                            implementation.name
+                        ),
+                        in_proxy[0].decl.span.as_ref().or(proxy.span.as_ref()),
+                    ));
+                    last_reported = Some(described);
+                }
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address() -> Type {
+        Type::Dialect(DialectType::Evm(EvmType::Address))
+    }
+
+    fn contract(name: &str, parents: &[&str], storage: Vec<(&str, Type)>) -> ContractDecl {
+        let members = storage
+            .into_iter()
+            .map(|(n, ty)| MemberDecl::Storage(StorageDecl::new(n.to_string(), ty, None, None)))
+            .collect();
+        let mut contract = ContractDecl::new(name.to_string(), members, None);
+        contract.parents = parents.iter().map(|p| p.to_string()).collect();
+        contract
+    }
+
+    fn module(contracts: Vec<ContractDecl>) -> Module {
+        let decls = contracts.into_iter().map(Decl::Contract).collect();
+        Module { id: "test".to_string(), attrs: vec![], decls }
+    }
+
+    fn check(contracts: Vec<ContractDecl>) -> Vec<Bug> {
+        StorageLayoutCollisionDetector::new().check_module(&module(contracts))
+    }
+
+    #[test]
+    fn test_storage_layout_collision_detector() {
+        let detector = StorageLayoutCollisionDetector::new();
+        assert_eq!(detector.id(), "storage-layout-collision");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_layout_packing() {
+        let c = contract(
+            "Packed",
+            &[],
+            vec![
+                ("a", Type::Bool),
+                ("b", address()),
+                ("c", Type::I128),
+                ("d", Type::I256),
+                ("e", Type::FixedArray(Box::new(Type::I8), 40)),
+                ("f", Type::I8),
+            ],
+        );
+        let m = module(vec![c.clone()]);
+        let contracts = HashMap::from([("Packed".to_string(), &c)]);
+        let layout: Vec<(&str, u64, u64)> = TypeSizes::of(&m)
+            .layout(&c, &contracts)
+            .iter()
+            .map(|v| (v.decl.name.as_str(), v.slot, v.offset))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("a", 0, 0),
+                ("b", 0, 1),
+                ("c", 1, 0),
+                ("d", 2, 0),
+                ("e", 3, 0),
+                ("f", 5, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reordered_proxy_storage_is_flagged() {
+        let proxy =
+            contract("TokenProxy", &[], vec![("implementation", address()), ("admin", address())]);
+        let token =
+            contract("Token", &[], vec![("totalSupply", Type::I256), ("owner", address())]);
+
+        let bugs = check(vec![proxy, token]);
+        assert_eq!(bugs.len(), 2);
+        assert_eq!(
+            bugs[0].description.as_deref(),
+            Some(
+                "Storage slot 0 holds 'address implementation' in 'TokenProxy' but \
+                 'uint256 totalSupply' in 'Token'."
+            )
+        );
+    }
+
+    #[test]
+    fn test_inherited_storage_follows_linearization() {
+        let storage = contract("ProxyStorage", &[], vec![("implementation", address())]);
+        let base = contract("Base", &[], vec![("count", Type::I256)]);
+        let proxy = contract("Proxy", &["ProxyStorage"], vec![]);
+
+        // `ProxyStorage` comes first: the layouts line up.
+        let logic = contract("Logic", &["ProxyStorage", "Base"], vec![]);
+        let contracts = vec![storage.clone(), base.clone(), proxy.clone(), logic];
+        assert!(check(contracts).is_empty());
+
+        // `Base` comes first and takes slot 0.
+        let logic = contract("Logic", &["Base", "ProxyStorage"], vec![]);
+        let bugs = check(vec![storage, base, proxy, logic]);
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("'uint256 count'")
+        );
+    }
+}
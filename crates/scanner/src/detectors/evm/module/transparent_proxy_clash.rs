@@ -0,0 +1,464 @@
+//! Transparent Proxy Clash Detector
+//!
+//! A transparent proxy exposes its admin functions (`upgradeTo`,
+//! `changeAdmin`, ...) next to a `fallback` that delegates every other call
+//! to the implementation. Each admin function must route non-admin callers
+//! to the fallback, which is what OpenZeppelin's `ifAdmin` modifier does:
+//!
+//! ```solidity
+//! modifier ifAdmin() {
+//!     if (msg.sender == _getAdmin()) { _; } else { _fallback(); }
+//! }
+//! ```
+//!
+//! An admin function guarded by a plain `require(msg.sender == admin)`
+//! instead makes its selector unusable for everyone else, and any
+//! implementation function with the same 4-byte selector can never be
+//! reached through the proxy. Both cases are reported; admin functions
+//! whose body or modifiers fall through to a delegating function are not.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use crate::detectors::evm::utils::reads_sender;
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use common::loc::Loc;
+use scirs::sir::dialect::evm::{EvmExpr, EvmMemberDecl};
+use scirs::sir::dialect::{DialectMemberDecl, EvmFunctionExt};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    AssertStmt, CallExpr, ContractDecl, Decl, DialectExpr, Expr, FunctionDecl, IfStmt, MemberDecl,
+    Module, Stmt,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Scan detector for transparent proxy admin functions that clash with, or
+/// shadow, implementation functions.
+#[derive(Debug, Default)]
+pub struct TransparentProxyClashDetector;
+
+impl TransparentProxyClashDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// What a function or modifier body does directly.
+#[derive(Default)]
+struct BodyFacts {
+    /// Performs a `delegatecall` (Solidity or inline assembly).
+    delegates: bool,
+    /// Branches or asserts on `msg.sender`.
+    checks_sender: bool,
+    /// Names of the internal functions it calls.
+    callees: HashSet<String>,
+}
+
+impl BodyFacts {
+    fn of(body: &[Stmt]) -> Self {
+        let mut facts = Self::default();
+        facts.visit_stmts(body);
+        facts
+    }
+}
+
+impl<'a> Visit<'a> for BodyFacts {
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt) {
+        self.checks_sender |= reads_sender(&stmt.cond);
+        visit::default::visit_if_stmt(self, stmt);
+    }
+
+    fn visit_assert_stmt(&mut self, stmt: &'a AssertStmt) {
+        self.checks_sender |= reads_sender(&stmt.cond);
+        visit::default::visit_assert_stmt(self, stmt);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr) {
+        match &*expr.callee {
+            Expr::Var(v) => {
+                self.callees.insert(v.name.clone());
+            }
+            Expr::FieldAccess(fa) if fa.field == "delegatecall" => self.delegates = true,
+            _ => {}
+        }
+        visit::default::visit_call_expr(self, expr);
+    }
+
+    fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+        match expr {
+            DialectExpr::Evm(EvmExpr::Delegatecall(_)) => self.delegates = true,
+            DialectExpr::Evm(EvmExpr::InlineAsm(asm)) => {
+                self.delegates |= asm.asm_text.contains("delegatecall");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Facts of every function and modifier of a contract, closed over calls
+/// and modifier invocations: a routine delegates (or checks the sender) if
+/// anything it calls or is guarded by does.
+struct ContractFacts {
+    delegating: HashSet<String>,
+    sender_checking: HashSet<String>,
+}
+
+impl ContractFacts {
+    fn of(contract: &ContractDecl) -> Self {
+        let mut facts: HashMap<String, BodyFacts> = HashMap::new();
+        for member in &contract.members {
+            match member {
+                MemberDecl::Function(f) => {
+                    let mut body = BodyFacts::of(f.body.as_deref().unwrap_or_default());
+                    body.callees
+                        .extend(f.modifier_invocs.iter().map(|m| m.name.clone()));
+                    facts.insert(f.name.clone(), body);
+                }
+                MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(m))) => {
+                    facts.insert(m.name.clone(), BodyFacts::of(&m.body));
+                }
+                _ => {}
+            }
+        }
+
+        let close = |seed: &dyn Fn(&BodyFacts) -> bool| {
+            let mut set: HashSet<String> = facts
+                .iter()
+                .filter(|(_, f)| seed(f))
+                .map(|(name, _)| name.clone())
+                .collect();
+            loop {
+                let reached: Vec<String> = facts
+                    .iter()
+                    .filter(|(name, f)| {
+                        !set.contains(*name) && f.callees.iter().any(|c| set.contains(c))
+                    })
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                if reached.is_empty() {
+                    return set;
+                }
+                set.extend(reached);
+            }
+        };
+
+        Self { delegating: close(&|f| f.delegates), sender_checking: close(&|f| f.checks_sender) }
+    }
+}
+
+/// Externally callable functions of a contract, excluding the special
+/// constructor, fallback and receive functions.
+fn entry_points(contract: &ContractDecl) -> impl Iterator<Item = &FunctionDecl> {
+    contract.members.iter().filter_map(|m| match m {
+        MemberDecl::Function(f)
+            if (f.is_public() || f.is_external())
+                && !f.is_constructor()
+                && !matches!(f.name.as_str(), "fallback" | "receive") =>
+        {
+            Some(f)
+        }
+        _ => None,
+    })
+}
+
+fn hex(selector: [u8; 4]) -> String {
+    selector.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+impl TransparentProxyClashDetector {
+    fn bug(&self, message: String, loc: Option<&Loc>) -> Bug {
+        Bug::new(
+            self.name(),
+            Some(&message),
+            loc.cloned().unwrap_or_else(|| Loc::new(0, 0, 0, 0)),
+            self.bug_kind(),
+            self.bug_category(),
+            self.risk_level(),
+            self.cwe_ids(),
+            self.swc_ids(),
+            Some(self.recommendation()),
+        )
+    }
+}
+
+impl ScanDetector for TransparentProxyClashDetector {
+    fn id(&self) -> &'static str {
+        "transparent-proxy-clash"
+    }
+
+    fn name(&self) -> &'static str {
+        "Transparent Proxy Clash"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects transparent proxy admin functions that do not route \
+         non-admin callers to the implementation, or whose selector clashes \
+         with an implementation function"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Vulnerability
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::AccessControl
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Module
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![436]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Guard proxy admin functions with an `ifAdmin`-style modifier that \
+         forwards non-admin callers to the fallback, or move them to a \
+         separate ProxyAdmin contract. Rename implementation functions whose \
+         selector clashes with a proxy function."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec![
+            "https://blog.openzeppelin.com/the-transparent-proxy-pattern",
+            "https://docs.openzeppelin.com/upgrades-plugins/proxies#transparent-proxies-and-function-clashes",
+        ]
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        vec!["proxy"]
+    }
+
+    fn check_module(&self, module: &Module) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let contracts: Vec<(&ContractDecl, ContractFacts)> = module
+            .decls
+            .iter()
+            .filter_map(|d| match d {
+                Decl::Contract(c) => Some((c, ContractFacts::of(c))),
+                _ => None,
+            })
+            .collect();
+
+        let is_proxy =
+            |(_, facts): &&(&ContractDecl, ContractFacts)| facts.delegating.contains("fallback");
+
+        for (proxy, facts) in contracts.iter().filter(is_proxy) {
+            // Implementations: other non-proxy contracts with function bodies.
+            let implementations: Vec<&ContractDecl> = contracts
+                .iter()
+                .filter(|entry| !is_proxy(entry) && !proxy.parents.contains(&entry.0.name))
+                .map(|(c, _)| *c)
+                .collect();
+
+            for func in entry_points(proxy) {
+                if facts.delegating.contains(&func.name) {
+                    continue;
+                }
+
+                let selector = func.selector();
+                let clash = implementations.iter().find_map(|imp| {
+                    entry_points(imp)
+                        .find(|f| f.body.is_some() && f.selector() == selector)
+                        .map(|f| (imp.name.as_str(), f))
+                });
+
+                if let Some((imp_name, imp_func)) = clash {
+                    bugs.push(self.bug(
+                        format!(
+                            "'{}.{}' has selector 0x{} like '{}.{}', but does not \
+                             forward non-admin callers; the implementation function \
+                             is unreachable through the proxy.",
+                            proxy.name,
+                            func.abi_signature(),
+                            hex(selector),
+                            imp_name,
+                            imp_func.abi_signature()
+                        ),
+                        func.span.as_ref(),
+                    ));
+                } else if facts.sender_checking.contains(&func.name) {
+                    bugs.push(self.bug(
+                        format!(
+                            "Admin function '{}.{}' rejects non-admin callers instead \
+                             of forwarding them to the implementation (no `ifAdmin` \
+                             routing).",
+                            proxy.name, func.name
+                        ),
+                        func.span.as_ref(),
+                    ));
+                }
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::typed_var;
+    use scirs::sir::dialect::evm::{EvmDelegatecall, EvmModifierDef, EvmMsgSender, EvmType};
+    use scirs::sir::*;
+
+    fn address() -> Type {
+        Type::Dialect(DialectType::Evm(EvmType::Address))
+    }
+
+    fn call(name: &str) -> Stmt {
+        Stmt::Expr(ExprStmt {
+            expr: Expr::FunctionCall(CallExpr {
+                callee: Box::new(typed_var(name, Type::None)),
+                args: CallArgs::Positional(vec![]),
+                ty: Type::None,
+                span: None,
+            }),
+            span: None,
+        })
+    }
+
+    fn function(name: &str, params: Vec<Param>, body: Vec<Stmt>, public: bool) -> FunctionDecl {
+        let mut func = FunctionDecl::new(name.to_string(), params, vec![], Some(body), None);
+        if public {
+            func.attrs
+                .push(Attr::sir(sir_attrs::VISIBILITY, AttrValue::String("external".into())));
+        }
+        func
+    }
+
+    /// `if (msg.sender != admin) revert();`
+    fn only_admin() -> Stmt {
+        let sender = Expr::Dialect(DialectExpr::Evm(EvmExpr::MsgSender(EvmMsgSender {
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        Stmt::If(IfStmt {
+            cond: Expr::BinOp(BinOpExpr {
+                op: BinOp::Ne,
+                lhs: Box::new(sender),
+                rhs: Box::new(typed_var("admin", address())),
+                overflow: OverflowSemantics::Checked,
+                span: None,
+            }),
+            then_body: vec![Stmt::Revert(RevertStmt {
+                error: None,
+                args: vec![],
+                span: None,
+            })],
+            else_body: None,
+            span: None,
+        })
+    }
+
+    /// A proxy with a delegating fallback and `upgradeTo(address)`, guarded
+    /// either by `ifAdmin` or by a plain sender check.
+    fn proxy(routed: bool) -> ContractDecl {
+        let delegate = Expr::Dialect(DialectExpr::Evm(EvmExpr::Delegatecall(EvmDelegatecall {
+            target: Box::new(typed_var("implementation", address())),
+            data: Box::new(typed_var("data", Type::Bytes)),
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        let internal_fallback = function(
+            "_fallback",
+            vec![],
+            vec![Stmt::Expr(ExprStmt { expr: delegate, span: None })],
+            false,
+        );
+        let fallback = function("fallback", vec![], vec![call("_fallback")], true);
+        let if_admin = EvmModifierDef {
+            name: "ifAdmin".to_string(),
+            params: vec![],
+            body: vec![only_admin(), call("_fallback")],
+            loc: Loc::new(0, 0, 0, 0),
+        };
+
+        let param = Param::new("newImplementation".to_string(), address());
+        let mut upgrade = function("upgradeTo", vec![param], vec![], true);
+        if routed {
+            upgrade.modifier_invocs.push(ModifierInvoc {
+                name: "ifAdmin".to_string(),
+                args: vec![],
+                span: None,
+            });
+        } else {
+            upgrade.body = Some(vec![only_admin()]);
+        }
+
+        let members = vec![
+            MemberDecl::Function(internal_fallback),
+            MemberDecl::Function(fallback),
+            MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(if_admin))),
+            MemberDecl::Function(upgrade),
+        ];
+        ContractDecl::new("Proxy".to_string(), members, None)
+    }
+
+    fn check(contracts: Vec<ContractDecl>) -> Vec<Bug> {
+        let decls = contracts.into_iter().map(Decl::Contract).collect();
+        let module = Module { id: "test".to_string(), attrs: vec![], decls };
+        TransparentProxyClashDetector::new().check_module(&module)
+    }
+
+    #[test]
+    fn test_transparent_proxy_clash_detector() {
+        let detector = TransparentProxyClashDetector::new();
+        assert_eq!(detector.id(), "transparent-proxy-clash");
+        assert_eq!(detector.risk_level(), RiskLevel::High);
+    }
+
+    #[test]
+    fn test_selector() {
+        let param = Param::new("to".to_string(), address());
+        let amount = Param::new("amount".to_string(), Type::I256);
+        let transfer = function("transfer", vec![param, amount], vec![], true);
+        assert_eq!(transfer.abi_signature(), "transfer(address,uint256)");
+        assert_eq!(hex(transfer.selector()), "a9059cbb");
+    }
+
+    #[test]
+    fn test_clashing_admin_function_is_flagged() {
+        // The implementation also exposes `upgradeTo(address)`.
+        let param = Param::new("newImplementation".to_string(), address());
+        let upgrade = function("upgradeTo", vec![param], vec![], true);
+        let implementation =
+            ContractDecl::new("Logic".to_string(), vec![MemberDecl::Function(upgrade)], None);
+
+        let bugs = check(vec![proxy(false), implementation]);
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("Logic.upgradeTo")
+        );
+
+        // Without a clash the missing routing is still reported.
+        assert_eq!(check(vec![proxy(false)]).len(), 1);
+    }
+
+    #[test]
+    fn test_routed_admin_function_is_not_flagged() {
+        let param = Param::new("newImplementation".to_string(), address());
+        let upgrade = function("upgradeTo", vec![param], vec![], true);
+        let implementation =
+            ContractDecl::new("Logic".to_string(), vec![MemberDecl::Function(upgrade)], None);
+        assert!(check(vec![proxy(true), implementation]).is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+//! Unused Event Detector
+//!
+//! Detects events that are declared but never emitted anywhere in the
+//! project:
+//!
+//! ```solidity
+//! event FeeChanged(uint256 oldFee, uint256 newFee);
+//!
+//! function setFee(uint256 fee) external onlyOwner {
+//!     _fee = fee; // no `emit FeeChanged(..)`
+//! }
+//! ```
+//!
+//! Off-chain indexers and monitoring that subscribe to such an event never
+//! hear from it, which usually means an `emit` was forgotten. An event
+//! counts as used if any function or modifier of any module emits an event
+//! of that name, so events declared in an interface or a base contract and
+//! emitted by a derived contract are not reported.
+
+use crate::detector::{Confidence, DetectionLevel, ScanDetector, Target};
+use bugs::bug::{Bug, BugCategory, BugKind, RiskLevel};
+use scirs::sir::dialect::evm::{EvmMemberDecl, EvmStmt};
+use scirs::sir::dialect::{DialectMemberDecl, DialectStmt};
+use scirs::sir::utils::visit::Visit;
+use scirs::sir::{Decl, MemberDecl, Module};
+use std::collections::HashSet;
+
+/// Scan detector for events that are never emitted.
+#[derive(Debug, Default)]
+pub struct UnusedEventDetector;
+
+impl UnusedEventDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Names of all events emitted in `modules`.
+fn emitted_events(modules: &[Module]) -> HashSet<String> {
+    #[derive(Default)]
+    struct Collector(HashSet<String>);
+    impl<'a> Visit<'a> for Collector {
+        fn visit_dialect_stmt(&mut self, stmt: &'a DialectStmt) {
+            match stmt {
+                DialectStmt::Evm(EvmStmt::EmitEvent(emit)) => {
+                    self.0.insert(emit.event.clone());
+                }
+                DialectStmt::Evm(EvmStmt::TryCatch(tc)) => {
+                    self.visit_stmts(&tc.body);
+                    for clause in &tc.catch_clauses {
+                        self.visit_stmts(&clause.body);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        fn visit_dialect_member_decl(&mut self, decl: &'a DialectMemberDecl) {
+            if let DialectMemberDecl::Evm(EvmMemberDecl::ModifierDef(m)) = decl {
+                self.visit_stmts(&m.body);
+            }
+        }
+    }
+
+    let mut collector = Collector::default();
+    collector.visit_modules(modules);
+    collector.0
+}
+
+impl ScanDetector for UnusedEventDetector {
+    fn id(&self) -> &'static str {
+        "unused-event"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unused Event"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects events that are declared but never emitted anywhere in the \
+         project"
+    }
+
+    fn bug_kind(&self) -> BugKind {
+        BugKind::Refactoring
+    }
+
+    fn bug_category(&self) -> BugCategory {
+        BugCategory::CodeQuality
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::No
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn target(&self) -> Target {
+        Target::Evm
+    }
+
+    fn level(&self) -> DetectionLevel {
+        DetectionLevel::Project
+    }
+
+    fn cwe_ids(&self) -> Vec<usize> {
+        vec![1164]
+    }
+
+    fn swc_ids(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn recommendation(&self) -> &'static str {
+        "Emit the event where the state it describes changes, or remove the \
+         declaration."
+    }
+
+    fn references(&self) -> Vec<&'static str> {
+        vec!["https://cwe.mitre.org/data/definitions/1164.html"]
+    }
+
+    fn check_project(&self, modules: &[Module]) -> Vec<Bug> {
+        let mut bugs = Vec::new();
+
+        let emitted = emitted_events(modules);
+        // Imported declarations are copied into every importing module.
+        let mut reported = HashSet::new();
+        for module in modules {
+            for decl in &module.decls {
+                let Decl::Contract(contract) = decl else {
+                    continue;
+                };
+                for member in &contract.members {
+                    let MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::EventDef(
+                        event,
+                    ))) = member
+                    else {
+                        continue;
+                    };
+                    if emitted.contains(&event.name)
+                        || !reported.insert((contract.name.clone(), event.name.clone()))
+                    {
+                        continue;
+                    }
+                    bugs.push(Bug::new(
+                        self.name(),
+                        Some(&format!(
+                            "Event '{}.{}' is declared but never emitted.",
+                            contract.name, event.name
+                        )),
+                        event.loc.clone(),
+                        self.bug_kind(),
+                        self.bug_category(),
+                        self.risk_level(),
+                        self.cwe_ids(),
+                        self.swc_ids(),
+                        Some(self.recommendation()),
+                    ));
+                }
+            }
+        }
+
+        bugs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::loc::Loc;
+    use scirs::sir::dialect::evm::{EvmEmitEvent, EvmEventDef};
+    use scirs::sir::*;
+
+    fn event(name: &str) -> MemberDecl {
+        MemberDecl::Dialect(DialectMemberDecl::Evm(EvmMemberDecl::EventDef(EvmEventDef {
+            name: name.to_string(),
+            params: vec![],
+            indexed: vec![],
+            anonymous: false,
+            loc: Loc::new(2, 5, 2, 30),
+        })))
+    }
+
+    fn emit(name: &str) -> Stmt {
+        Stmt::Dialect(DialectStmt::Evm(EvmStmt::EmitEvent(EvmEmitEvent {
+            event: name.to_string(),
+            args: vec![],
+            loc: Loc::new(5, 9, 5, 30),
+        })))
+    }
+
+    #[test]
+    fn test_unused_event_detector() {
+        let detector = UnusedEventDetector::new();
+        assert_eq!(detector.id(), "unused-event");
+        assert_eq!(detector.risk_level(), RiskLevel::No);
+    }
+
+    #[test]
+    fn test_only_unemitted_event_is_flagged() {
+        // `Base` declares both events; only `Deposited` is emitted, by the
+        // derived `Vault` in another module.
+        let base = ContractDecl::new(
+            "Base".to_string(),
+            vec![event("Deposited"), event("FeeChanged")],
+            None,
+        );
+        let deposit = FunctionDecl::new(
+            "deposit".to_string(),
+            vec![],
+            vec![],
+            Some(vec![emit("Deposited")]),
+            None,
+        );
+        let mut vault =
+            ContractDecl::new("Vault".to_string(), vec![MemberDecl::Function(deposit)], None);
+        vault.parents = vec!["Base".to_string()];
+        let modules = [
+            Module {
+                id: "Base.sol".to_string(),
+                attrs: vec![],
+                decls: vec![Decl::Contract(base)],
+            },
+            Module {
+                id: "Vault.sol".to_string(),
+                attrs: vec![],
+                decls: vec![Decl::Contract(vault)],
+            },
+        ];
+
+        let bugs = UnusedEventDetector::new().check_project(&modules);
+        assert_eq!(bugs.len(), 1);
+        assert!(
+            bugs[0]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("Base.FeeChanged")
+        );
+    }
+}
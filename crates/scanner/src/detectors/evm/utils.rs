@@ -0,0 +1,218 @@
+//! Helpers shared by the EVM detectors.
+
+use num_traits::Zero;
+use scirs::sir::dialect::DialectExpr;
+use scirs::sir::dialect::evm::{EvmCallExt, EvmExpr};
+use scirs::sir::utils::visit::{self, Visit};
+use scirs::sir::{
+    BinOp, BinOpExpr, CallExpr, ContractDecl, Decl, Expr, Lit, Module, Num, Stmt, UnOp,
+};
+use std::collections::HashSet;
+
+// ─── Expressions ─────────────────────────────────────────────────────────────
+
+/// Strip conversions such as `payable(to)` or `address(to)`.
+pub(crate) fn unwrap_casts(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::TypeCast(cast) => unwrap_casts(&cast.expr),
+        _ => expr,
+    }
+}
+
+/// Whether `expr` is `address(0)` (or a plain `0`).
+pub(crate) fn is_zero_address(expr: &Expr) -> bool {
+    match unwrap_casts(expr) {
+        Expr::Lit(Lit::Num(n)) => matches!(&n.value, Num::Int(i) if i.value.is_zero()),
+        _ => false,
+    }
+}
+
+/// Name of the variable compared with zero by `lhs <op> rhs`, if either
+/// side is zero and the other a variable.
+fn compared_with_zero<'a>(lhs: &'a Expr, rhs: &'a Expr) -> Option<&'a str> {
+    let (var, other) = if is_zero_address(rhs) {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    match unwrap_casts(var) {
+        Expr::Var(v) if is_zero_address(other) => Some(&v.name),
+        _ => None,
+    }
+}
+
+/// Variables that `cond` being true proves non-zero.
+pub(crate) fn nonzero_when_true(cond: &Expr, out: &mut Vec<String>) {
+    match cond {
+        Expr::BinOp(b) if b.op == BinOp::And => {
+            nonzero_when_true(&b.lhs, out);
+            nonzero_when_true(&b.rhs, out);
+        }
+        Expr::BinOp(b) if b.op == BinOp::Ne => {
+            out.extend(compared_with_zero(&b.lhs, &b.rhs).map(str::to_string));
+        }
+        Expr::UnOp(u) if u.op == UnOp::Not => nonzero_when_false(&u.operand, out),
+        _ => {}
+    }
+}
+
+/// Variables that `cond` being false proves non-zero.
+pub(crate) fn nonzero_when_false(cond: &Expr, out: &mut Vec<String>) {
+    match cond {
+        Expr::BinOp(b) if b.op == BinOp::Or => {
+            nonzero_when_false(&b.lhs, out);
+            nonzero_when_false(&b.rhs, out);
+        }
+        Expr::BinOp(b) if b.op == BinOp::Eq => {
+            out.extend(compared_with_zero(&b.lhs, &b.rhs).map(str::to_string));
+        }
+        Expr::UnOp(u) if u.op == UnOp::Not => nonzero_when_true(&u.operand, out),
+        _ => {}
+    }
+}
+
+/// Returns `true` if `expr` compares `msg.sender` with `tx.origin`, in
+/// either order.
+pub(crate) fn is_eoa_check(expr: &BinOpExpr) -> bool {
+    fn evm(expr: &Expr) -> Option<&EvmExpr> {
+        match expr {
+            Expr::Dialect(DialectExpr::Evm(e)) => Some(e),
+            Expr::TypeCast(c) => evm(&c.expr),
+            _ => None,
+        }
+    }
+
+    matches!(expr.op, BinOp::Eq | BinOp::Ne)
+        && matches!(
+            (evm(&expr.lhs), evm(&expr.rhs)),
+            (Some(EvmExpr::MsgSender(_)), Some(EvmExpr::TxOrigin(_)))
+                | (Some(EvmExpr::TxOrigin(_)), Some(EvmExpr::MsgSender(_)))
+        )
+}
+
+/// Returns `true` if `expr` reads `msg.sender`.
+pub(crate) fn reads_sender(expr: &Expr) -> bool {
+    struct Finder(bool);
+    impl<'a> Visit<'a> for Finder {
+        fn visit_dialect_expr(&mut self, expr: &'a DialectExpr) {
+            self.0 |= matches!(expr, DialectExpr::Evm(EvmExpr::MsgSender(_)));
+        }
+    }
+    let mut finder = Finder(false);
+    finder.visit_expr(expr);
+    finder.0
+}
+
+// ─── Statements ──────────────────────────────────────────────────────────────
+
+/// Returns `true` if `stmt` makes an external call: a call to another
+/// contract, a low-level call, or an Ether transfer.
+pub(crate) fn stmt_has_external_call(stmt: &Stmt) -> bool {
+    struct CallFinder {
+        found: bool,
+    }
+    impl<'a> Visit<'a> for CallFinder {
+        fn visit_call_expr(&mut self, call: &'a CallExpr) {
+            if call.is_evm_external_call() {
+                self.found = true;
+            }
+            if !self.found {
+                visit::default::visit_call_expr(self, call);
+            }
+        }
+
+        fn visit_dialect_expr(&mut self, d: &'a DialectExpr) {
+            if matches!(
+                d,
+                DialectExpr::Evm(
+                    EvmExpr::LowLevelCall(_)
+                        | EvmExpr::RawCall(_)
+                        | EvmExpr::Send(_)
+                        | EvmExpr::Transfer(_)
+                        | EvmExpr::Delegatecall(_)
+                )
+            ) {
+                self.found = true;
+            }
+        }
+    }
+    let mut finder = CallFinder { found: false };
+    finder.visit_stmt(stmt);
+    finder.found
+}
+
+/// Returns `true` if control never falls through `stmts`: the last
+/// statement is a `revert` or `return`, or a block or an `if` with an `else`
+/// whose branches all end so. Loops are not looked into.
+pub(crate) fn always_exits(stmts: &[Stmt]) -> bool {
+    match stmts.last() {
+        Some(Stmt::Revert(_) | Stmt::Return(_)) => true,
+        Some(Stmt::Block(block)) => always_exits(block),
+        Some(Stmt::If(s)) => {
+            always_exits(&s.then_body) && s.else_body.as_deref().is_some_and(always_exits)
+        }
+        _ => false,
+    }
+}
+
+// ─── Contracts ───────────────────────────────────────────────────────────────
+
+/// Names of all ancestors of `contract`, following the parents that can be
+/// resolved in `module`.
+pub(crate) fn ancestor_names(contract: &ContractDecl, module: &Module) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut worklist: Vec<&String> = contract.parents.iter().collect();
+    while let Some(name) = worklist.pop() {
+        if !names.insert(name.clone()) {
+            continue;
+        }
+        for decl in &module.decls {
+            if let Decl::Contract(c) = decl
+                && &c.name == name
+            {
+                worklist.extend(&c.parents);
+            }
+        }
+    }
+    names
+}
+
+/// Storage variables of `contract` and of its ancestors declared in `module`.
+pub(crate) fn storage_names(contract: &ContractDecl, module: &Module) -> HashSet<String> {
+    let ancestors = ancestor_names(contract, module);
+    let mut names: HashSet<String> = contract.storage_names().into_iter().collect();
+    for decl in &module.decls {
+        if let Decl::Contract(c) = decl
+            && ancestors.contains(&c.name)
+        {
+            names.extend(c.storage_names());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scirs::sir::{IfStmt, RevertStmt};
+
+    #[test]
+    fn test_always_exits() {
+        let revert = || Stmt::Revert(RevertStmt { error: None, args: vec![], span: None });
+        let branch = |else_body| {
+            Stmt::If(IfStmt {
+                cond: Expr::Lit(Lit::one(None)),
+                then_body: vec![revert()],
+                else_body,
+                span: None,
+            })
+        };
+
+        assert!(always_exits(&[revert()]));
+        assert!(always_exits(&[branch(Some(vec![revert()]))]));
+        assert!(always_exits(&[Stmt::Block(vec![revert()])]));
+        assert!(!always_exits(&[branch(None)]));
+        assert!(!always_exits(&[branch(Some(vec![]))]));
+        assert!(!always_exits(&[]));
+    }
+}
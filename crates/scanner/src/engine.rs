@@ -33,6 +33,7 @@ pub struct ScanReport {
 pub struct ScanEngine {
     _config: ScanConfig,
     /// Detectors grouped by detection level for O(1) dispatch.
+    project_detectors: Vec<Box<dyn ScanDetector>>,
     module_detectors: Vec<Box<dyn ScanDetector>>,
     contract_detectors: Vec<Box<dyn ScanDetector>>,
     function_detectors: Vec<Box<dyn ScanDetector>>,
@@ -40,12 +41,14 @@ pub struct ScanEngine {
 
 impl ScanEngine {
     pub fn new(config: ScanConfig, detectors: Vec<Box<dyn ScanDetector>>) -> Self {
+        let mut project_detectors = Vec::new();
         let mut module_detectors = Vec::new();
         let mut contract_detectors = Vec::new();
         let mut function_detectors = Vec::new();
 
         for d in detectors {
             match d.level() {
+                DetectionLevel::Project => project_detectors.push(d),
                 DetectionLevel::Module => module_detectors.push(d),
                 DetectionLevel::Contract => contract_detectors.push(d),
                 DetectionLevel::Function => function_detectors.push(d),
@@ -54,6 +57,7 @@ impl ScanEngine {
 
         Self {
             _config: config,
+            project_detectors,
             module_detectors,
             contract_detectors,
             function_detectors,
@@ -65,10 +69,16 @@ impl ScanEngine {
     pub fn run(&self, modules: &[Module]) -> ScanReport {
         let start = Instant::now();
         let mut bugs = Vec::new();
-        let detectors_run = self.module_detectors.len()
+        let detectors_run = self.project_detectors.len()
+            + self.module_detectors.len()
             + self.contract_detectors.len()
             + self.function_detectors.len();
 
+        // ── Project-level detectors ─────────────────
+        for d in &self.project_detectors {
+            bugs.extend(d.check_project(modules));
+        }
+
         for module in modules {
             // ── Module-level detectors ──────────────────
             for d in &self.module_detectors {
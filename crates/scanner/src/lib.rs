@@ -27,8 +27,14 @@ pub mod cli;
 pub mod detector;
 pub mod detectors;
 pub mod engine;
+pub mod pattern;
 pub mod registry;
 
-pub use detector::{Confidence, DetectionLevel, ScanDetector, Target};
+#[cfg(test)]
+mod test_utils;
+
+pub use bugs::bug::{Category, Severity};
+pub use detector::{Confidence, DetectionLevel, DetectorParams, ScanDetector, Target};
 pub use engine::{ScanConfig, ScanEngine, ScanReport};
+pub use pattern::{Match, Pattern, PatternBuilder};
 pub use registry::{ScanRegistry, register_all_detectors};
@@ -0,0 +1,248 @@
+//! Expression patterns with named captures.
+//!
+//! A [`Pattern`] describes the shape of a SIR expression. Sub-patterns can be
+//! tagged with [`PatternBuilder::capture`], so that a detector can pick the
+//! matched sub-expressions out of the resulting [`Match`] by name instead of
+//! destructuring the expression again:
+//!
+//! ```ignore
+//! use scanner::pattern::PatternBuilder as P;
+//!
+//! // `tx.origin == x` or `x == tx.origin`, capturing `x` as "other".
+//! let origin = || P::evm(|e| matches!(e, EvmExpr::TxOrigin(_)));
+//! let other = || P::capture("other", P::any());
+//! let pattern = P::or(vec![
+//!     P::binop(&[BinOp::Eq], origin(), other()),
+//!     P::binop(&[BinOp::Eq], other(), origin()),
+//! ]);
+//! if let Some(m) = pattern.matches(expr) {
+//!     let other = m.capture_expr("other");
+//! }
+//! ```
+//!
+//! Leaf patterns ([`Pattern::Var`] and [`Pattern::Evm`]) look through type
+//! casts, so `address(tx.origin)` matches like `tx.origin`.
+
+use crate::detectors::evm::utils::unwrap_casts;
+use scirs::sir::dialect::evm::EvmExpr;
+use scirs::sir::{BinOp, DialectExpr, Expr};
+use std::collections::HashMap;
+
+/// Pattern over SIR expressions.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// Matches any expression.
+    Any,
+    /// Matches a variable, with the given name if any.
+    Var(Option<String>),
+    /// Matches a field access whose base matches, with the given field name
+    /// if any.
+    Field(Box<Pattern>, Option<String>),
+    /// Matches a binary operation with one of the operators (any operator if
+    /// empty) whose operands match.
+    BinOp(Vec<BinOp>, Box<Pattern>, Box<Pattern>),
+    /// Matches a call whose callee matches.
+    Call(Box<Pattern>),
+    /// Matches an EVM dialect expression satisfying the predicate.
+    Evm(fn(&EvmExpr) -> bool),
+    /// Matches if all patterns match, keeping the captures of all of them.
+    And(Vec<Pattern>),
+    /// Matches if one of the patterns matches, keeping the captures of the
+    /// first one that does.
+    Or(Vec<Pattern>),
+    /// Matches if the pattern does not match.
+    Not(Box<Pattern>),
+    /// Matches like the inner pattern and captures the matched expression
+    /// under the given name.
+    Capture(String, Box<Pattern>),
+    /// Matches like the inner pattern if the expression also satisfies the
+    /// predicate, e.g. one inspecting the arguments of a call.
+    Where(Box<Pattern>, fn(&Expr) -> bool),
+}
+
+/// Constructors of [`Pattern`]s.
+pub struct PatternBuilder;
+
+impl PatternBuilder {
+    pub fn any() -> Pattern {
+        Pattern::Any
+    }
+
+    pub fn var(name: Option<&str>) -> Pattern {
+        Pattern::Var(name.map(str::to_string))
+    }
+
+    pub fn field(base: Pattern, field: Option<&str>) -> Pattern {
+        Pattern::Field(Box::new(base), field.map(str::to_string))
+    }
+
+    pub fn binop(ops: &[BinOp], lhs: Pattern, rhs: Pattern) -> Pattern {
+        Pattern::BinOp(ops.to_vec(), Box::new(lhs), Box::new(rhs))
+    }
+
+    pub fn call(callee: Pattern) -> Pattern {
+        Pattern::Call(Box::new(callee))
+    }
+
+    pub fn evm(predicate: fn(&EvmExpr) -> bool) -> Pattern {
+        Pattern::Evm(predicate)
+    }
+
+    pub fn and(patterns: Vec<Pattern>) -> Pattern {
+        Pattern::And(patterns)
+    }
+
+    pub fn or(patterns: Vec<Pattern>) -> Pattern {
+        Pattern::Or(patterns)
+    }
+
+    pub fn not(pattern: Pattern) -> Pattern {
+        Pattern::Not(Box::new(pattern))
+    }
+
+    /// Tags the subtree matched by `pattern` with `name`.
+    pub fn capture(name: &str, pattern: Pattern) -> Pattern {
+        Pattern::Capture(name.to_string(), Box::new(pattern))
+    }
+
+    /// Restricts `pattern` to expressions satisfying `predicate`.
+    pub fn where_(pattern: Pattern, predicate: fn(&Expr) -> bool) -> Pattern {
+        Pattern::Where(Box::new(pattern), predicate)
+    }
+}
+
+/// A successful match, holding the captured sub-expressions.
+#[derive(Debug, Clone, Default)]
+pub struct Match<'a> {
+    captures: HashMap<String, &'a Expr>,
+}
+
+impl<'a> Match<'a> {
+    /// Expression captured under `name`.
+    pub fn capture_expr(&self, name: &str) -> Option<&'a Expr> {
+        self.captures.get(name).copied()
+    }
+
+    /// Identifier captured under `name`: the name of a captured variable or
+    /// the field of a captured field access, looking through type casts.
+    pub fn capture_ident(&self, name: &str) -> Option<&'a str> {
+        match unwrap_casts(self.capture_expr(name)?) {
+            Expr::Var(v) => Some(&v.name),
+            Expr::FieldAccess(fa) => Some(&fa.field),
+            _ => None,
+        }
+    }
+
+    /// Names of all captures.
+    pub fn capture_names(&self) -> impl Iterator<Item = &str> {
+        self.captures.keys().map(String::as_str)
+    }
+}
+
+impl Pattern {
+    /// Matches `expr` against the pattern.
+    pub fn matches<'a>(&self, expr: &'a Expr) -> Option<Match<'a>> {
+        let mut m = Match::default();
+        self.match_into(expr, &mut m).then_some(m)
+    }
+
+    fn match_into<'a>(&self, expr: &'a Expr, m: &mut Match<'a>) -> bool {
+        match self {
+            Pattern::Any => true,
+            Pattern::Var(name) => match unwrap_casts(expr) {
+                Expr::Var(v) => name.as_ref().is_none_or(|n| *n == v.name),
+                _ => false,
+            },
+            Pattern::Field(base, field) => match expr {
+                Expr::FieldAccess(fa) => {
+                    field.as_ref().is_none_or(|f| *f == fa.field) && base.match_into(&fa.base, m)
+                }
+                _ => false,
+            },
+            Pattern::BinOp(ops, lhs, rhs) => match expr {
+                Expr::BinOp(e) => {
+                    (ops.is_empty() || ops.contains(&e.op))
+                        && lhs.match_into(&e.lhs, m)
+                        && rhs.match_into(&e.rhs, m)
+                }
+                _ => false,
+            },
+            Pattern::Call(callee) => match expr {
+                Expr::FunctionCall(call) => callee.match_into(&call.callee, m),
+                _ => false,
+            },
+            Pattern::Evm(predicate) => match unwrap_casts(expr) {
+                Expr::Dialect(DialectExpr::Evm(e)) => predicate(e),
+                _ => false,
+            },
+            Pattern::And(patterns) => patterns.iter().all(|p| p.match_into(expr, m)),
+            Pattern::Or(patterns) => patterns.iter().any(|p| {
+                let mut alt = m.clone();
+                let matched = p.match_into(expr, &mut alt);
+                if matched {
+                    *m = alt;
+                }
+                matched
+            }),
+            Pattern::Not(pattern) => pattern.matches(expr).is_none(),
+            Pattern::Capture(name, pattern) => {
+                let matched = pattern.match_into(expr, m);
+                if matched {
+                    m.captures.insert(name.clone(), expr);
+                }
+                matched
+            }
+            Pattern::Where(pattern, predicate) => predicate(expr) && pattern.match_into(expr, m),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PatternBuilder as P;
+    use super::*;
+    use crate::test_utils::{binop, var};
+    use common::loc::Loc;
+    use scirs::sir::dialect::evm::EvmTxOrigin;
+
+    #[test]
+    fn test_capture_inside_and_pattern() {
+        // `(a + b) == owner`
+        let expr = binop(BinOp::Eq, binop(BinOp::Add, var("a"), var("b")), var("owner"));
+        let pattern = P::binop(
+            &[BinOp::Eq],
+            P::and(vec![
+                P::binop(&[BinOp::Add], P::capture("left", P::var(None)), P::any()),
+                P::capture("sum", P::any()),
+            ]),
+            P::capture("owner", P::var(Some("owner"))),
+        );
+
+        let m = pattern.matches(&expr).unwrap();
+        assert_eq!(m.capture_ident("left"), Some("a"));
+        assert_eq!(m.capture_ident("owner"), Some("owner"));
+        assert!(matches!(m.capture_expr("sum"), Some(Expr::BinOp(_))));
+        assert_eq!(m.capture_ident("sum"), None);
+        assert_eq!(m.capture_expr("missing"), None);
+    }
+
+    #[test]
+    fn test_failed_alternative_drops_its_captures() {
+        let origin = Expr::Dialect(DialectExpr::Evm(EvmExpr::TxOrigin(EvmTxOrigin {
+            loc: Loc::new(0, 0, 0, 0),
+        })));
+        let expr = binop(BinOp::Eq, var("owner"), origin);
+        let is_origin = || P::evm(|e| matches!(e, EvmExpr::TxOrigin(_)));
+        let other = || P::capture("other", P::any());
+        let pattern = P::or(vec![
+            P::binop(&[BinOp::Eq], other(), P::not(P::any())),
+            P::binop(&[BinOp::Eq], P::capture("origin", is_origin()), other()),
+            P::binop(&[BinOp::Eq], other(), P::capture("origin", is_origin())),
+        ]);
+
+        let m = pattern.matches(&expr).unwrap();
+        assert_eq!(m.capture_ident("other"), Some("owner"));
+        assert!(m.capture_expr("origin").is_some());
+        assert!(P::not(pattern).matches(&expr).is_none());
+    }
+}
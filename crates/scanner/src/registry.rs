@@ -29,6 +29,15 @@ impl ScanRegistry {
             .collect()
     }
 
+    /// Detectors carrying `tag`.
+    pub fn by_tag(&self, tag: &str) -> Vec<&dyn ScanDetector> {
+        self.detectors
+            .iter()
+            .filter(|d| d.tags().contains(&tag))
+            .map(|d| d.as_ref())
+            .collect()
+    }
+
     /// Consume the registry and return owned detectors (for adapter use).
     pub fn into_detectors(self) -> Vec<Box<dyn ScanDetector>> {
         self.detectors
@@ -55,26 +64,75 @@ pub fn register_all_detectors(registry: &mut ScanRegistry) {
 
     // ── Security: EVM ───────────────────────────────────────────
     registry.register(Box::new(ArithmeticOverflowDetector::new()));
+    registry.register(Box::new(AssemblyMemorySafetyDetector::new()));
+    registry.register(Box::new(AssemblyUncheckedMathDetector::new()));
     registry.register(Box::new(BadRandomnessDetector::new()));
+    registry.register(Box::new(BalanceConfusionDetector::new()));
+    registry.register(Box::new(BlockNumberDependenceDetector::new()));
     registry.register(Box::new(CeiViolationDetector::new()));
     registry.register(Box::new(CentralizationRiskDetector::new()));
+    registry.register(Box::new(ConstructorExternalCallDetector::new()));
+    registry.register(Box::new(CrossFunctionReentrancyDetector::new()));
+    registry.register(Box::new(DanglingStorageRefDetector::new()));
+    registry.register(Box::new(DecimalsMismatchDetector::new()));
     registry.register(Box::new(DelegatecallDetector::new()));
+    registry.register(Box::new(DelegatecallMutableTargetDetector::new()));
     registry.register(Box::new(DenialOfServiceDetector::new()));
+    registry.register(Box::new(EncodeTypeMismatchDetector::new()));
+    registry.register(Box::new(Erc165MissingDetector::new()));
     registry.register(Box::new(FrontRunningDetector::new()));
+    registry.register(Box::new(HardcodedGasDetector::new()));
+    registry.register(Box::new(InfiniteApprovalDetector::new()));
+    registry.register(Box::new(LoopClearCollectionDetector::new()));
     registry.register(Box::new(LowLevelCallDetector::new()));
     registry.register(Box::new(MissingAccessControlDetector::new()));
+    registry.register(Box::new(MissingDeadlineDetector::new()));
+    registry.register(Box::new(MissingEventEmissionDetector::new()));
+    registry.register(Box::new(MissingReentrancyGuardDetector::new()));
+    registry.register(Box::new(MissingReturnDetector::new()));
+    registry.register(Box::new(MissingZeroCheckDetector::new()));
+    registry.register(Box::new(ModifyWhileIterateDetector::new()));
+    registry.register(Box::new(MulOverflowBeforeDivDetector::new()));
+    registry.register(Box::new(NonstandardErcOverrideDetector::new()));
+    registry.register(Box::new(PossibleUnderflowRevertDetector::new()));
+    registry.register(Box::new(PrecisionLossDivisionDetector::new()));
     registry.register(Box::new(ReentrancyDetector::new()));
+    registry.register(Box::new(ReentrancyCallbackDetector::new()));
+    registry.register(Box::new(SendToZeroDetector::new()));
     registry.register(Box::new(ShortAddressDetector::new()));
+    registry.register(Box::new(SignatureMalleabilityDetector::new()));
+    registry.register(Box::new(StaleStateReadDetector::new()));
+    registry.register(Box::new(StorageLayoutCollisionDetector::new()));
     registry.register(Box::new(TimestampDependenceDetector::new()));
+    registry.register(Box::new(TransparentProxyClashDetector::new()));
     registry.register(Box::new(TxOriginDetector::new()));
+    registry.register(Box::new(UncheckedAbiDecodeDetector::new()));
     registry.register(Box::new(UncheckedCallDetector::new()));
+    registry.register(Box::new(UncheckedEcrecoverDetector::new()));
+    registry.register(Box::new(UncheckedErc20ReturnDetector::new()));
+    registry.register(Box::new(UncheckedSendDetector::new()));
     registry.register(Box::new(UninitializedDetector::new()));
+    registry.register(Box::new(UnprotectedMintDetector::new()));
+    registry.register(Box::new(WeakRandomnessDetector::new()));
+    registry.register(Box::new(WithdrawOrderingDetector::new()));
 
     // ── Quality: EVM ────────────────────────────────────────────
+    registry.register(Box::new(AmbiguousOverrideDetector::new()));
+    registry.register(Box::new(ComplexSignatureDetector::new()));
     registry.register(Box::new(ConstantStateVarDetector::new()));
+    registry.register(Box::new(CouldBeViewDetector::new()));
     registry.register(Box::new(DeadCodeDetector::new()));
+    registry.register(Box::new(DeepNestingDetector::new()));
+    registry.register(Box::new(DeprecatedDifficultyDetector::new()));
     registry.register(Box::new(DeprecatedFeaturesDetector::new()));
+    registry.register(Box::new(DeprecatedSelfdestructDetector::new()));
+    registry.register(Box::new(EoaOnlyCheckDetector::new()));
     registry.register(Box::new(FloatingPragmaDetector::new()));
+    registry.register(Box::new(MissingStorageGapDetector::new()));
+    registry.register(Box::new(RedundantRequireDetector::new()));
+    registry.register(Box::new(RedundantSafemathDetector::new()));
     registry.register(Box::new(ShadowingDetector::new()));
+    registry.register(Box::new(UnusedEventDetector::new()));
+    registry.register(Box::new(UnusedPayableDetector::new()));
     registry.register(Box::new(VisibilityDetector::new()));
 }
@@ -0,0 +1,75 @@
+//! SIR builders shared by the detector unit tests.
+
+use crate::detector::ScanDetector;
+use bugs::bug::Bug;
+use scirs::sir::{
+    BinOp, BinOpExpr, ContractDecl, Expr, FunctionDecl, IfStmt, IntNum, Lit, MemberDecl, Module,
+    Num, NumLit, OverflowSemantics, RevertStmt, Stmt, StorageDecl, Type, UnOp, UnOpExpr, VarExpr,
+};
+
+/// A `uint256` variable.
+pub(crate) fn var(name: &str) -> Expr {
+    typed_var(name, Type::I256)
+}
+
+/// A variable of type `ty`.
+pub(crate) fn typed_var(name: &str, ty: Type) -> Expr {
+    Expr::Var(VarExpr::new(name.to_string(), ty, None))
+}
+
+/// An integer literal.
+pub(crate) fn num(value: u64) -> Expr {
+    Expr::Lit(Lit::Num(NumLit {
+        value: Num::Int(IntNum { value: value.into(), typ: Type::I256 }),
+        span: None,
+    }))
+}
+
+/// A checked binary operation.
+pub(crate) fn binop(op: BinOp, lhs: Expr, rhs: Expr) -> Expr {
+    Expr::BinOp(BinOpExpr {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+        overflow: OverflowSemantics::Checked,
+        span: None,
+    })
+}
+
+/// `require(cond)`, lowered to `if (!cond) revert`.
+pub(crate) fn require(cond: Expr) -> Stmt {
+    Stmt::If(IfStmt {
+        cond: Expr::UnOp(UnOpExpr { op: UnOp::Not, operand: Box::new(cond), span: None }),
+        then_body: vec![Stmt::Revert(RevertStmt {
+            error: None,
+            args: vec![],
+            span: None,
+        })],
+        else_body: None,
+        span: None,
+    })
+}
+
+/// A `uint256` storage variable.
+pub(crate) fn storage(name: &str) -> MemberDecl {
+    MemberDecl::Storage(StorageDecl::new(name.to_string(), Type::I256, None, None))
+}
+
+/// Runs a function-level `detector` on `func`, declared in a contract `C`
+/// alongside `members`.
+pub(crate) fn check_function(
+    detector: &dyn ScanDetector,
+    func: &FunctionDecl,
+    members: Vec<MemberDecl>,
+) -> Vec<Bug> {
+    let contract = ContractDecl::new("C".to_string(), members, None);
+    let module = Module { id: "test".to_string(), attrs: vec![], decls: vec![] };
+    detector.check_function(func, &contract, &module)
+}
+
+/// Runs a function-level `detector` on a parameterless function `f` with
+/// `body`.
+pub(crate) fn check_body(detector: &dyn ScanDetector, body: Vec<Stmt>) -> Vec<Bug> {
+    let func = FunctionDecl::new("f".to_string(), vec![], vec![], Some(body), None);
+    check_function(detector, &func, vec![])
+}
@@ -27,6 +27,25 @@ impl CallGraph {
     pub fn add_dynamic_edge(&mut self, call_site: OpId, callee: FunctionId) {
         self.dynamic_edges.push((call_site, callee));
     }
+
+    /// Render the call graph in Graphviz DOT format. Dynamic edges start at
+    /// their call-site op and are dashed.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('"', "\\\""));
+        let mut dot = String::from("digraph CallGraph {\n");
+        for (caller, callee) in &self.static_edges {
+            dot.push_str(&format!("    {} -> {};\n", quote(&caller.0), quote(&callee.0)));
+        }
+        for (call_site, callee) in &self.dynamic_edges {
+            dot.push_str(&format!(
+                "    {} -> {} [style=dashed];\n",
+                quote(&call_site.to_string()),
+                quote(&callee.0)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
 }
 
 impl Display for CallGraph {
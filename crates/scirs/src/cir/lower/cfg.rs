@@ -5,7 +5,8 @@
 
 use crate::bir::cfg::{BasicBlock, BlockId, Terminator};
 use crate::bir::ops::{Op, OpId, OpKind, OpRef, SsaName};
-use crate::sir::{Expr, Param, Stmt, Type};
+use crate::sir::dialect::evm::EvmStmt;
+use crate::sir::{DialectStmt, Expr, Param, Stmt, Type};
 
 /// State for the CFG builder.
 struct CfgBuilder {
@@ -303,11 +304,16 @@ fn flatten_stmt(builder: &mut CfgBuilder, stmt: &Stmt, current: BlockId) -> Bloc
 
         Stmt::Block(stmts) => flatten_stmts(builder, stmts, current),
 
-        Stmt::Dialect(_dialect_stmt) => {
+        Stmt::Dialect(dialect_stmt) => {
             // Dialect statements are retained as opaque ops.
             // They will be lowered in Step 4.
             let op_id = builder.new_op_id();
-            let op = Op::new(op_id, OpKind::Opaque { description: format!("{stmt}") });
+            let mut op = Op::new(op_id, OpKind::Opaque { description: format!("{stmt}") });
+            if let DialectStmt::Evm(EvmStmt::Selfdestruct(s)) = dialect_stmt
+                && s.loc.is_valid()
+            {
+                op = op.with_span(s.loc.clone());
+            }
             builder.append_op(current, op);
             current
         }
@@ -75,6 +75,11 @@ fn try_lower_opaque(description: &str, dialect: &str) -> Option<OpKind> {
 
 /// EVM dialect lowering table.
 fn try_lower_evm(description: &str) -> Option<OpKind> {
+    // `selfdestruct` stays opaque, even when its recipient is `msg.sender`.
+    if description.starts_with("selfdestruct(") {
+        return None;
+    }
+
     if description.contains("evm.msg_sender()") {
         return Some(OpKind::TaintSrc(TaintSourceOp {
             label: TaintLabel::UserControlled,
@@ -110,6 +110,10 @@ pub mod evm_attrs {
     pub const IS_CONSTRUCTOR: &str = "is_constructor";
     pub const IS_CONSTANT: &str = "is_constant";
     pub const IS_IMMUTABLE: &str = "is_immutable";
+    pub const RETURNS_STORAGE_REF: &str = "returns_storage_ref"; // Solidity `returns (T storage)`
+    pub const DEPRECATED_SYNTAX: &str = "deprecated_syntax"; // Solidity `throw`, `years`
+    pub const OVERRIDES: &str = "overrides"; // `override(A, B)` as "A,B", "" for `override`
+    pub const IS_VIRTUAL: &str = "is_virtual";
 }
 
 /// Move dialect `#move.*` attribute keys.
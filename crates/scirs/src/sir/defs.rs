@@ -72,6 +72,9 @@ pub struct FunctionDecl {
     pub type_params: Vec<TypeParam>,
     pub params: Vec<Param>,
     pub returns: Vec<Type>,
+    /// Names of the return values, parallel to `returns` (`None` for
+    /// unnamed ones). Empty when the source language has no named returns.
+    pub return_names: Vec<Option<String>>,
     pub attrs: Vec<Attr>,
     pub spec: Option<FuncSpec>,
     pub body: Option<Vec<Stmt>>,
@@ -238,6 +241,7 @@ impl FunctionDecl {
             type_params: vec![],
             params,
             returns,
+            return_names: vec![],
             attrs: vec![],
             spec: None,
             body,
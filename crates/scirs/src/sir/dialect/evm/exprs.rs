@@ -271,6 +271,9 @@ pub struct EvmMsgSig {
 /// `block.difficulty` / `block.prevrandao` — previous block's RANDAO.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EvmBlockDifficulty {
+    /// `true` if written as `block.prevrandao` rather than the pre-merge
+    /// `block.difficulty`.
+    pub is_prevrandao: bool,
     pub loc: Loc,
 }
 
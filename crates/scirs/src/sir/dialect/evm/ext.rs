@@ -6,9 +6,12 @@
 
 use crate::sir::attrs::{AttrValue, evm_attrs, sir_attrs};
 use crate::sir::defs::{FunctionDecl, StorageDecl};
-use crate::sir::dialect::DialectExpr;
-use crate::sir::dialect::evm::EvmExpr;
+use crate::sir::dialect::evm::{EvmExpr, EvmType};
+use crate::sir::dialect::{DialectExpr, DialectType};
 use crate::sir::exprs::{CallExpr, Expr};
+use crate::sir::module::Module;
+use crate::sir::types::Type;
+use tiny_keccak::{Hasher, Keccak};
 
 // ═══════════════════════════════════════════════════════════════════
 // EvmFunctionExt — queries on FunctionDecl
@@ -20,9 +23,43 @@ pub trait EvmFunctionExt {
     /// (`#sir.visibility = "public"`).
     fn is_public(&self) -> bool;
 
+    /// Returns `true` if the function has external visibility
+    /// (`#sir.visibility = "external"`).
+    fn is_external(&self) -> bool;
+
     /// Returns `true` if the function has a reentrancy guard
     /// (`#sir.reentrancy_guard` or `#evm.nonreentrant`).
     fn has_reentrancy_guard(&self) -> bool;
+
+    /// Returns `true` if the function is a constructor
+    /// (`#evm.is_constructor = true`).
+    fn is_constructor(&self) -> bool;
+
+    /// Returns `true` if the function accepts Ether: Solidity's
+    /// `#sir.mutability = "payable"` or Vyper's `#evm.payable = true`.
+    fn is_payable(&self) -> bool;
+
+    /// Returns `true` if the function returns a `storage` reference
+    /// (`#evm.returns_storage_ref = true`).
+    fn returns_storage_ref(&self) -> bool;
+
+    /// Base contracts named by the function's `override` specifier
+    /// (`#evm.overrides`): `Some(vec![])` for a bare `override`, `None`
+    /// without one.
+    fn overridden_bases(&self) -> Option<Vec<&str>>;
+
+    /// Returns `true` if derived contracts may override the function
+    /// (`#evm.is_virtual = true`).
+    fn is_virtual(&self) -> bool;
+
+    /// Canonical ABI signature, e.g. `transfer(address,uint256)`. Named
+    /// types are rendered as `address`, which is exact for contract and
+    /// interface parameters only.
+    fn abi_signature(&self) -> String;
+
+    /// The 4-byte selector: the leading bytes of the Keccak-256 hash of
+    /// [`abi_signature`](Self::abi_signature).
+    fn selector(&self) -> [u8; 4];
 }
 
 impl EvmFunctionExt for FunctionDecl {
@@ -34,12 +71,125 @@ impl EvmFunctionExt for FunctionDecl {
         })
     }
 
+    fn is_external(&self) -> bool {
+        self.attrs.iter().any(|a| {
+            a.namespace == "sir"
+                && a.key == sir_attrs::VISIBILITY
+                && matches!(&a.value, AttrValue::String(s) if s == "external")
+        })
+    }
+
     fn has_reentrancy_guard(&self) -> bool {
         self.attrs.iter().any(|a| {
             (a.namespace == "sir" && a.key == sir_attrs::REENTRANCY_GUARD)
                 || (a.namespace == "evm" && a.key == evm_attrs::NONREENTRANT)
         })
     }
+
+    fn is_constructor(&self) -> bool {
+        self.attrs.iter().any(|a| {
+            a.namespace == "evm"
+                && a.key == evm_attrs::IS_CONSTRUCTOR
+                && matches!(a.value, AttrValue::Bool(true))
+        })
+    }
+
+    fn is_payable(&self) -> bool {
+        self.attrs.iter().any(|a| match a.namespace.as_str() {
+            "sir" => {
+                a.key == sir_attrs::MUTABILITY
+                    && matches!(&a.value, AttrValue::String(s) if s == "payable")
+            }
+            "evm" => a.key == evm_attrs::PAYABLE && matches!(a.value, AttrValue::Bool(true)),
+            _ => false,
+        })
+    }
+
+    fn returns_storage_ref(&self) -> bool {
+        self.attrs.iter().any(|a| {
+            a.namespace == "evm"
+                && a.key == evm_attrs::RETURNS_STORAGE_REF
+                && matches!(a.value, AttrValue::Bool(true))
+        })
+    }
+
+    fn overridden_bases(&self) -> Option<Vec<&str>> {
+        self.attrs.iter().find_map(|a| match &a.value {
+            AttrValue::String(bases) if a.namespace == "evm" && a.key == evm_attrs::OVERRIDES => {
+                Some(bases.split(',').filter(|b| !b.is_empty()).collect())
+            }
+            _ => None,
+        })
+    }
+
+    fn is_virtual(&self) -> bool {
+        self.attrs.iter().any(|a| {
+            a.namespace == "evm"
+                && a.key == evm_attrs::IS_VIRTUAL
+                && matches!(a.value, AttrValue::Bool(true))
+        })
+    }
+
+    fn abi_signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(|p| p.ty.abi_name()).collect();
+        format!("{}({})", self.name, params.join(","))
+    }
+
+    fn selector(&self) -> [u8; 4] {
+        let mut hash = [0u8; 32];
+        let mut keccak = Keccak::v256();
+        keccak.update(self.abi_signature().as_bytes());
+        keccak.finalize(&mut hash);
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════
+// EvmTypeExt — queries on Type
+// ═══════════════════════════════════════════════════════════════════
+
+/// Extension trait for EVM-dialect queries on [`Type`].
+pub trait EvmTypeExt {
+    /// ABI name of the type as used in function signatures, e.g. `uint256`
+    /// or `address[]`. Named types are rendered as `address`.
+    fn abi_name(&self) -> String;
+
+    /// Returns `true` for `address` and `address payable`.
+    fn is_address(&self) -> bool;
+}
+
+impl EvmTypeExt for Type {
+    fn abi_name(&self) -> String {
+        match self {
+            Type::I8 => "uint8".to_string(),
+            Type::I16 => "uint16".to_string(),
+            Type::I32 => "uint32".to_string(),
+            Type::I64 => "uint64".to_string(),
+            Type::I128 => "uint128".to_string(),
+            Type::I256 => "uint256".to_string(),
+            Type::Si8 => "int8".to_string(),
+            Type::Si16 => "int16".to_string(),
+            Type::Si32 => "int32".to_string(),
+            Type::Si64 => "int64".to_string(),
+            Type::Si128 => "int128".to_string(),
+            Type::Si256 => "int256".to_string(),
+            Type::Array(elem) => format!("{}[]", elem.abi_name()),
+            Type::FixedArray(elem, len) => format!("{}[{len}]", elem.abi_name()),
+            Type::Tuple(elems) => {
+                let elems: Vec<String> = elems.iter().map(|e| e.abi_name()).collect();
+                format!("({})", elems.join(","))
+            }
+            Type::TypeRef(_)
+            | Type::Dialect(DialectType::Evm(EvmType::Address | EvmType::AddressPayable)) => {
+                "address".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    fn is_address(&self) -> bool {
+        matches!(self, Type::Dialect(DialectType::Evm(EvmType::Address | EvmType::AddressPayable)))
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════
@@ -52,6 +202,26 @@ pub trait EvmCallExt {
     /// `.call`, `.delegatecall`, `.staticcall`, `.transfer`, `.send`,
     /// or an EVM dialect call variant.
     fn is_evm_external_call(&self) -> bool;
+
+    /// Returns `true` if the call is a token transfer that invokes a hook on
+    /// the recipient: ERC721/ERC1155 `safeTransferFrom`, ERC1155
+    /// `safeBatchTransferFrom`, ERC721 `_safeMint`/`_safeTransfer`, and
+    /// ERC777 `send`/`operatorSend`. Calls through an ERC20 receiver (e.g.
+    /// `SafeERC20`) are excluded since ERC20 has no recipient callback.
+    fn is_evm_token_callback_call(&self) -> bool;
+
+    /// Returns the receiver if the call is `receiver.method(..)` and the
+    /// receiver's type satisfies `type_filter`, e.g. `.transfer()` on an
+    /// address:
+    ///
+    /// ```ignore
+    /// call.method_call_on_type("transfer", Type::is_address)
+    /// ```
+    fn method_call_on_type(
+        &self,
+        method: &str,
+        type_filter: impl Fn(&Type) -> bool,
+    ) -> Option<&Expr>;
 }
 
 impl EvmCallExt for CallExpr {
@@ -75,6 +245,44 @@ impl EvmCallExt for CallExpr {
         }
         false
     }
+
+    fn is_evm_token_callback_call(&self) -> bool {
+        match &*self.callee {
+            Expr::FieldAccess(fa) => {
+                if let Expr::Var(v) = &*fa.base
+                    && v.name.contains("ERC20")
+                {
+                    return false;
+                }
+                if let Type::TypeRef(name) = fa.base.typ()
+                    && name.contains("ERC20")
+                {
+                    return false;
+                }
+                match fa.field.as_str() {
+                    "safeTransferFrom" | "safeBatchTransferFrom" | "operatorSend" => true,
+                    // ERC777 `send(to, amount, data)`, not `address.send(amount)`.
+                    "send" => self.args.exprs().len() == 3,
+                    _ => false,
+                }
+            }
+            Expr::Var(v) => matches!(v.name.as_str(), "_safeMint" | "_safeTransfer"),
+            _ => false,
+        }
+    }
+
+    fn method_call_on_type(
+        &self,
+        method: &str,
+        type_filter: impl Fn(&Type) -> bool,
+    ) -> Option<&Expr> {
+        match &*self.callee {
+            Expr::FieldAccess(fa) if fa.field == method && type_filter(&fa.base.typ()) => {
+                Some(&fa.base)
+            }
+            _ => None,
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════
@@ -97,3 +305,35 @@ impl EvmStorageExt for StorageDecl {
         })
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════
+// EvmModuleExt — queries on Module
+// ═══════════════════════════════════════════════════════════════════
+
+/// Extension trait for EVM-dialect queries on [`Module`].
+pub trait EvmModuleExt {
+    /// Lowest compiler version allowed by the `#sir.pragma_solidity`
+    /// attribute, e.g. `(0, 8, 18)` for `>=0.8.18 <0.9.0`. Upper bounds are
+    /// ignored.
+    fn solidity_min_version(&self) -> Option<(u64, u64, u64)>;
+}
+
+impl EvmModuleExt for Module {
+    fn solidity_min_version(&self) -> Option<(u64, u64, u64)> {
+        self.attrs
+            .iter()
+            .filter(|a| a.namespace == "sir" && a.key == sir_attrs::PRAGMA_SOLIDITY)
+            .filter_map(|a| match &a.value {
+                AttrValue::String(pragma) => Some(pragma),
+                _ => None,
+            })
+            .flat_map(|pragma| pragma.split_whitespace())
+            .filter(|part| !part.starts_with('<'))
+            .filter_map(|part| {
+                let version = part.trim_start_matches(['^', '~', '>', '=', 'v']);
+                let mut nums = version.split('.').map(|n| n.parse::<u64>().ok());
+                Some((nums.next()??, nums.next()??, nums.next().flatten().unwrap_or(0)))
+            })
+            .min()
+    }
+}
@@ -23,6 +23,8 @@ mod elim_using;
 mod flatten_expr;
 mod resolve_inheritance;
 
+pub use resolve_inheritance::linearize;
+
 use crate::cir::defs::*;
 use crate::cir::exprs::*;
 use crate::cir::module::*;
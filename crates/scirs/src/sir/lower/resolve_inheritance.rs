@@ -12,7 +12,7 @@
 
 use crate::sir;
 use crate::sir::lower::CirLowerError;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Resolve inheritance and flatten contracts.
 pub fn run(module: &sir::Module) -> Result<sir::Module, CirLowerError> {
@@ -49,7 +49,7 @@ fn flatten_contract(
         return Ok(contract.clone());
     }
 
-    // Compute C3 linearisation (ancestors, most-base first).
+    // Compute C3 linearisation, walked below from the most-base ancestor.
     let linear = linearize(&contract.name, map)?;
 
     // Merge members: walk from the most-base ancestor toward the derived
@@ -57,7 +57,7 @@ fn flatten_contract(
     // functions (same name).  Storage and dialect members are accumulated.
     let mut merged: Vec<sir::MemberDecl> = Vec::new();
 
-    for ancestor_name in &linear {
+    for ancestor_name in linear.iter().rev() {
         if ancestor_name == &contract.name {
             continue; // own members added last
         }
@@ -118,9 +118,22 @@ fn add_member(members: &mut Vec<sir::MemberDecl>, member: &sir::MemberDecl) {
 
 // ─── C3 Linearisation ────────────────────────────────────────────────────────
 
-fn linearize(
+/// Solidity's C3 linearization of the contract `name`, most derived first.
+///
+/// Direct bases are listed most base first, so `D is B, C` linearizes as
+/// `[D, C, B, ...]`. Contracts missing from `map` (e.g. imported
+/// interfaces) have no bases. Fails on cyclic or inconsistent hierarchies.
+pub fn linearize(
     name: &str,
     map: &HashMap<String, &sir::ContractDecl>,
+) -> Result<Vec<String>, CirLowerError> {
+    linearize_visiting(name, map, &mut HashSet::new())
+}
+
+fn linearize_visiting(
+    name: &str,
+    map: &HashMap<String, &sir::ContractDecl>,
+    visiting: &mut HashSet<String>,
 ) -> Result<Vec<String>, CirLowerError> {
     let Some(contract) = map.get(name) else {
         return Ok(vec![name.to_string()]);
@@ -130,13 +143,20 @@ fn linearize(
         return Ok(vec![name.to_string()]);
     }
 
-    // Build the lists required by C3: L(contract) = contract + merge(L(p1), ...,
-    // L(pn), [p1,...,pn])
+    if !visiting.insert(name.to_string()) {
+        return Err(CirLowerError::General(format!(
+            "Cannot linearize inheritance: {name} inherits from itself"
+        )));
+    }
+
+    // Build the lists required by C3, with the bases right to left:
+    // L(contract) = contract + merge(L(pn), ..., L(p1), [pn, ..., p1])
     let mut lists: Vec<Vec<String>> = Vec::new();
-    for parent in &contract.parents {
-        lists.push(linearize(parent, map)?);
+    for parent in contract.parents.iter().rev() {
+        lists.push(linearize_visiting(parent, map, visiting)?);
     }
-    lists.push(contract.parents.clone());
+    lists.push(contract.parents.iter().rev().cloned().collect());
+    visiting.remove(name);
 
     let mut result = vec![name.to_string()];
     result.extend(c3_merge(lists)?);
@@ -193,6 +193,7 @@ pub mod default {
             type_params: func.type_params.clone(),
             params: func.params.iter().map(|p| mapper.map_param(p)).collect(),
             returns: func.returns.iter().map(|t| mapper.map_type(t)).collect(),
+            return_names: func.return_names.clone(),
             attrs: func.attrs.clone(),
             spec: func.spec.clone(),
             body: func.body.as_ref().map(|b| mapper.map_stmts(b)),
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "./lib/Math.sol";
+
+/// @title Token — intentional unprotected mint.
+contract Token {
+    uint256 public totalSupply;
+    mapping(address => uint256) public balanceOf;
+
+    function mint(address to, uint256 amount) public {
+        totalSupply += amount;
+        balanceOf[to] += amount;
+    }
+
+    function cappedAmount(uint256 amount, uint256 cap) public pure returns (uint256) {
+        return Math.min(amount, cap);
+    }
+}
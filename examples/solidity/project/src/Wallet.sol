@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "./lib/Math.sol";
+
+/// @title Wallet — intentional tx.origin authorization.
+contract Wallet {
+    address public owner;
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    function withdraw(address payable to, uint256 amount, uint256 limit) public {
+        require(tx.origin == owner, "Not owner");
+        to.transfer(Math.min(amount, limit));
+    }
+}